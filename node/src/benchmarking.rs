@@ -0,0 +1,167 @@
+//! The blocks benchmarked by `benchmark overhead`: empty blocks, and blocks full of remarks
+//! signed by Alice, who must therefore be endowed by the chain specification.
+//!
+//! The blocks are built on top of the genesis block, with the inherents and the aura digest of a
+//! block authored now, as the consensus hook of the runtimes requires.
+
+use std::sync::Arc;
+
+use codec::{Compact, Decode, Encode};
+use cumulus_client_parachain_inherent::{MockValidationDataInherentDataProvider, MockXcmConfig};
+use cumulus_primitives_core::{
+	relay_chain::{well_known_keys, HeadData},
+	ParaId,
+};
+use frame_benchmarking_cli::ExtrinsicBuilder;
+use pop_runtime_common::{Hash, Nonce, RELAY_CHAIN_SLOT_DURATION_MILLIS, SLOT_DURATION};
+use sp_blockchain::HeaderBackend;
+use sp_consensus_aura::{Slot, AURA_ENGINE_ID};
+use sp_core::{sr25519, Pair};
+use sp_inherents::{InherentData, InherentDataProvider};
+use sp_runtime::{generic::SignedPayload, DigestItem, MultiAddress, OpaqueExtrinsic};
+
+use crate::service::ParachainClient;
+
+/// Builds the signed extrinsics of the blocks benchmarked, for the api of a runtime.
+pub(crate) trait BenchmarkExtrinsics {
+	/// A remark of Alice with `nonce`, immortal on the chain of the genesis block `genesis`.
+	fn remark(genesis: Hash, nonce: Nonce) -> OpaqueExtrinsic;
+}
+
+/// Implements [`BenchmarkExtrinsics`] for the api of each of the runtimes, given the data their
+/// signed extensions sign in addition to an extrinsic.
+macro_rules! impl_benchmark_extrinsics {
+	($($runtime:ident: |$version:ident, $genesis:ident| $additional:expr;)*) => {$(
+		impl BenchmarkExtrinsics for $runtime::RuntimeApi {
+			fn remark(genesis: Hash, nonce: Nonce) -> OpaqueExtrinsic {
+				use $runtime::{RuntimeCall, SignedExtra, UncheckedExtrinsic, VERSION};
+				let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+				// Immortal, with the nonce, no tip and without a metadata hash.
+				let extra = SignedExtra::decode(&mut &(0u8, Compact(nonce), [0u8; 3]).encode()[..])
+					.expect("The signed extensions are decodable from an era, a nonce and zeros");
+				let ($version, $genesis) = (VERSION, genesis);
+				let payload = SignedPayload::from_raw(call.clone(), extra.clone(), $additional);
+				let signer = alice();
+				let signature = payload.using_encoded(|payload| signer.sign(payload));
+				UncheckedExtrinsic::new_signed(
+					call,
+					MultiAddress::Id(signer.public().into()),
+					signature.into(),
+					extra,
+				)
+				.into()
+			}
+		}
+	)*};
+}
+
+impl_benchmark_extrinsics! {
+	pop_runtime_devnet: |version, genesis| (
+		(),
+		version.spec_version,
+		version.transaction_version,
+		genesis,
+		genesis,
+		(),
+		(),
+		(),
+		(),
+		None,
+	);
+	pop_runtime_testnet: |version, genesis| (
+		(),
+		version.spec_version,
+		version.transaction_version,
+		genesis,
+		genesis,
+		(),
+		((), (), ()),
+		None,
+		(),
+	);
+	pop_runtime_mainnet: |version, genesis| (
+		(),
+		version.spec_version,
+		version.transaction_version,
+		genesis,
+		genesis,
+		(),
+		(),
+		(),
+		(),
+		None,
+	);
+}
+
+/// The signer of the extrinsics benchmarked.
+fn alice() -> sr25519::Pair {
+	sr25519::Pair::from_string("//Alice", None).expect("The seed of Alice is valid")
+}
+
+/// Builds the remarks filling the blocks benchmarked by `benchmark overhead`.
+pub(crate) struct RemarkBuilder<RuntimeApi> {
+	client: Arc<ParachainClient<RuntimeApi>>,
+}
+
+impl<RuntimeApi> RemarkBuilder<RuntimeApi> {
+	/// Create new `RemarkBuilder` with the given reference to the client.
+	pub fn new(client: Arc<ParachainClient<RuntimeApi>>) -> Self {
+		Self { client }
+	}
+}
+
+impl<RuntimeApi> ExtrinsicBuilder for RemarkBuilder<RuntimeApi>
+where
+	RuntimeApi: BenchmarkExtrinsics + Send + Sync,
+{
+	fn pallet(&self) -> &str {
+		"system"
+	}
+
+	fn extrinsic(&self) -> &str {
+		"remark"
+	}
+
+	fn build(&self, nonce: u32) -> std::result::Result<OpaqueExtrinsic, &'static str> {
+		Ok(RuntimeApi::remark(self.client.info().genesis_hash, nonce))
+	}
+}
+
+/// The inherent data and the digest of the blocks benchmarked on top of the genesis block of the
+/// parachain `para_id`: a timestamp of now, along with the validation data of a relay chain block
+/// of the same slot.
+pub(crate) fn inherent_benchmark_data<RuntimeApi>(
+	client: &ParachainClient<RuntimeApi>,
+	para_id: ParaId,
+) -> sc_cli::Result<(InherentData, Vec<DigestItem>)>
+where
+	RuntimeApi: Send + Sync,
+{
+	let genesis = client.info().genesis_hash;
+	let genesis_header = client.header(genesis)?.ok_or("The genesis block is not stored")?;
+	let slot = sp_timestamp::Timestamp::current().as_millis() / SLOT_DURATION;
+	let timestamp = slot * SLOT_DURATION;
+	let relay_chain_slot = Slot::from(timestamp / RELAY_CHAIN_SLOT_DURATION_MILLIS as u64);
+	let relay_chain = MockValidationDataInherentDataProvider {
+		current_para_block: 1,
+		para_id,
+		current_para_block_head: Some(HeadData(genesis_header.encode())),
+		relay_offset: 1000,
+		relay_blocks_per_para_block: 1,
+		para_blocks_per_relay_epoch: 10,
+		relay_randomness_config: (),
+		xcm_config: MockXcmConfig::new(client, genesis, Default::default()),
+		raw_downward_messages: vec![],
+		raw_horizontal_messages: vec![],
+		additional_key_values: Some(vec![(
+			well_known_keys::CURRENT_SLOT.to_vec(),
+			relay_chain_slot.encode(),
+		)]),
+	};
+	let timestamp = sp_timestamp::InherentDataProvider::new(timestamp.into());
+	let inherent_data =
+		futures::executor::block_on((timestamp, relay_chain).create_inherent_data())
+			.map_err(|e| format!("Failed to create the inherent data: {e}"))?;
+	let digest = vec![DigestItem::PreRuntime(AURA_ENGINE_ID, Slot::from(slot).encode())];
+	Ok((inherent_data, digest))
+}
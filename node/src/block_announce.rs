@@ -0,0 +1,93 @@
+//! Bounds the number of block announcements validated at once, as set by
+//! `--block-announce-validation-concurrency`.
+
+use std::{error::Error, future::Future, num::NonZeroU32, pin::Pin, sync::Arc};
+
+use sp_consensus::block_validation::{BlockAnnounceValidator, Validation};
+use sp_runtime::traits::Block as BlockT;
+use tokio::sync::Semaphore;
+
+/// The number of block announcements `sc-network-sync` validates at once at most, beyond which
+/// bounding the concurrency has no effect.
+pub const MAX_CONCURRENCY: u32 = 256;
+
+/// The result of the validation of a block announcement.
+type ValidationFuture =
+	Pin<Box<dyn Future<Output = Result<Validation, Box<dyn Error + Send>>> + Send>>;
+
+/// Validates block announcements with an inner validator, at most a given number at once. The
+/// other announcements wait for a validation to complete before being validated.
+pub struct BoundedValidator<B: BlockT> {
+	inner: Box<dyn BlockAnnounceValidator<B> + Send>,
+	permits: Arc<Semaphore>,
+}
+
+impl<B: BlockT> BoundedValidator<B> {
+	/// Validate block announcements with `inner`, at most `concurrency` at once.
+	pub fn new(inner: Box<dyn BlockAnnounceValidator<B> + Send>, concurrency: NonZeroU32) -> Self {
+		Self { inner, permits: Arc::new(Semaphore::new(concurrency.get() as usize)) }
+	}
+}
+
+impl<B: BlockT> BlockAnnounceValidator<B> for BoundedValidator<B> {
+	fn validate(&mut self, header: &B::Header, data: &[u8]) -> ValidationFuture {
+		// The inner validation only makes progress once polled, i.e. once a permit is acquired.
+		let validation = self.inner.validate(header, data);
+		let permits = self.permits.clone();
+		Box::pin(async move {
+			let _permit =
+				permits.acquire_owned().await.expect("the semaphore is never closed; qed");
+			validation.await
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use futures::{channel::oneshot, FutureExt};
+	use pop_runtime_common::Block;
+	use sp_runtime::traits::Header as _;
+
+	use super::*;
+
+	/// Validates each announcement once its completion is sent.
+	struct Pending(Vec<oneshot::Receiver<()>>);
+
+	impl BlockAnnounceValidator<Block> for Pending {
+		fn validate(&mut self, _: &<Block as BlockT>::Header, _: &[u8]) -> ValidationFuture {
+			let completed = self.0.remove(0);
+			Box::pin(async move {
+				let _ = completed.await;
+				Ok(Validation::Success { is_new_best: false })
+			})
+		}
+	}
+
+	#[tokio::test]
+	async fn validations_beyond_the_concurrency_wait() {
+		let (complete, completions): (Vec<_>, Vec<_>) = (0..2).map(|_| oneshot::channel()).unzip();
+		let mut validator =
+			BoundedValidator::new(Box::new(Pending(completions)), NonZeroU32::new(1).unwrap());
+		let header = <Block as BlockT>::Header::new(
+			1,
+			Default::default(),
+			Default::default(),
+			Default::default(),
+			Default::default(),
+		);
+		let mut first = validator.validate(&header, &[]);
+		let mut second = validator.validate(&header, &[]);
+		assert!((&mut first).now_or_never().is_none());
+
+		// The second validation is not polled until the first completes, even once its own
+		// completion is sent.
+		let mut complete = complete.into_iter();
+		let (complete_first, complete_second) =
+			(complete.next().unwrap(), complete.next().unwrap());
+		complete_second.send(()).unwrap();
+		assert!((&mut second).now_or_never().is_none());
+		complete_first.send(()).unwrap();
+		assert!(first.await.is_ok());
+		assert!(second.await.is_ok());
+	}
+}
@@ -0,0 +1,385 @@
+//! Watches the production of parachain blocks, as seen from the relay chain, so that operators can
+//! tell a parachain which stopped being backed from a node which fell behind or lost its peers.
+//!
+//! The head of the parachain included by each best relay chain block is tracked, and a warning is
+//! logged once the head has not advanced for more than `--max-block-lag` relay chain blocks, along
+//! with a notice once it advances again. With prometheus enabled, the following metrics are
+//! reported besides `pop_relay_parent_lag`:
+//! - `pop_unincluded_blocks`: the number of blocks the best parachain block is ahead of the
+//!   included one.
+//! - `pop_relay_blocks_since_inclusion` and `pop_last_inclusion_age_seconds`: the relay chain
+//!   blocks and time since the included head advanced last.
+//! - `pop_block_production_stalled`: whether the head has not advanced for more than
+//!   `--max-block-lag` relay chain blocks.
+//! - `pop_collations`: the blocks authored by the node, by `outcome`: `authored`, then either
+//!   `included`, or `dropped` if another block was included in their stead.
+//!
+//! The blocks authored and their outcome are also logged under the `pop::collation` target, and
+//! the reads of the included head under the `pop::relay-chain` target.
+
+use std::{
+	collections::{BTreeMap, HashSet},
+	sync::Arc,
+	time::Instant,
+};
+
+use codec::Decode;
+use cumulus_primitives_core::{relay_chain::OccupiedCoreAssumption, ParaId};
+use cumulus_relay_chain_interface::RelayChainInterface;
+use futures::{stream, StreamExt};
+use pop_runtime_common::{Block, BlockNumber, Hash};
+use prometheus_endpoint::{register, CounterVec, Gauge, Opts, PrometheusError, Registry, U64};
+use sc_client_api::BlockchainEvents;
+use sc_service::TaskManager;
+use sp_blockchain::HeaderBackend;
+use sp_consensus::BlockOrigin;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+
+use crate::structured_logs::{relay_chain_call, COLLATION};
+
+const LOG_TARGET: &str = "block-production";
+
+/// The default number of relay chain blocks after which the parachain is considered stalled if
+/// none of its blocks was included: a minute of relay chain blocks.
+pub const DEFAULT_MAX_BLOCK_LAG: u32 = 10;
+
+/// The head of the parachain included by the relay chain, as of the relay chain block it was
+/// first seen included by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Inclusion {
+	number: BlockNumber,
+	relay_number: u32,
+	at: Instant,
+}
+
+/// Tracks the inclusion of the parachain head, telling when the parachain stalls or recovers.
+struct Watchdog {
+	/// The number of relay chain blocks without inclusion after which the parachain is stalled.
+	max_block_lag: u32,
+	/// The last advance of the included head, if any was seen.
+	inclusion: Option<Inclusion>,
+	stalled: bool,
+}
+
+impl Watchdog {
+	fn new(max_block_lag: u32) -> Self {
+		Self { max_block_lag, inclusion: None, stalled: false }
+	}
+
+	/// Note that the relay chain block `relay_number` includes the parachain head `included`, at
+	/// `now`. Returns whether the included head advanced.
+	fn note_relay_block(&mut self, relay_number: u32, included: BlockNumber, now: Instant) -> bool {
+		match self.inclusion {
+			Some(inclusion) if included <= inclusion.number => false,
+			_ => {
+				self.inclusion = Some(Inclusion { number: included, relay_number, at: now });
+				true
+			},
+		}
+	}
+
+	/// The number of relay chain blocks since the included head advanced last, as of the relay
+	/// chain block `relay_number`.
+	fn relay_blocks_since_inclusion(&self, relay_number: u32) -> u32 {
+		self.inclusion
+			.map_or(0, |inclusion| relay_number.saturating_sub(inclusion.relay_number))
+	}
+
+	/// Check whether the parachain is stalled as of the relay chain block `relay_number`,
+	/// returning the new state if it changed.
+	fn check(&mut self, relay_number: u32) -> Option<bool> {
+		let stalled = self.relay_blocks_since_inclusion(relay_number) > self.max_block_lag;
+		(stalled != self.stalled).then(|| {
+			self.stalled = stalled;
+			stalled
+		})
+	}
+}
+
+/// The blocks authored by the node which were neither included nor dropped yet.
+#[derive(Default)]
+struct Collations {
+	pending: BTreeMap<BlockNumber, Vec<Hash>>,
+}
+
+impl Collations {
+	/// Note the block `hash` authored by the node at `number`.
+	fn author(&mut self, number: BlockNumber, hash: Hash) {
+		self.pending.entry(number).or_default().push(hash);
+	}
+
+	/// The lowest number of the pending blocks, if any.
+	fn lowest(&self) -> Option<BlockNumber> {
+		self.pending.keys().next().copied()
+	}
+
+	/// Settle the pending blocks up to the included head `included`, given the hashes of the
+	/// included chain, returning the numbers of blocks included and dropped.
+	fn settle(&mut self, included: BlockNumber, chain: &HashSet<Hash>) -> (u64, u64) {
+		let pending = self.pending.split_off(&(included + 1));
+		let settled = std::mem::replace(&mut self.pending, pending);
+		settled.into_values().flatten().fold((0, 0), |(included, dropped), hash| {
+			if chain.contains(&hash) {
+				(included + 1, dropped)
+			} else {
+				(included, dropped + 1)
+			}
+		})
+	}
+}
+
+/// The metrics of the production of parachain blocks.
+struct Metrics {
+	unincluded_blocks: Gauge<U64>,
+	relay_blocks_since_inclusion: Gauge<U64>,
+	last_inclusion_age: Gauge<U64>,
+	stalled: Gauge<U64>,
+	collations: CounterVec<U64>,
+}
+
+impl Metrics {
+	/// Register the metrics with `registry`.
+	fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			unincluded_blocks: register(
+				Gauge::new(
+					"pop_unincluded_blocks",
+					"The number of blocks the best parachain block is ahead of the block included \
+					 by the best relay chain block",
+				)?,
+				registry,
+			)?,
+			relay_blocks_since_inclusion: register(
+				Gauge::new(
+					"pop_relay_blocks_since_inclusion",
+					"The number of relay chain blocks since the included parachain block advanced \
+					 last",
+				)?,
+				registry,
+			)?,
+			last_inclusion_age: register(
+				Gauge::new(
+					"pop_last_inclusion_age_seconds",
+					"The number of seconds since the included parachain block advanced last",
+				)?,
+				registry,
+			)?,
+			stalled: register(
+				Gauge::new(
+					"pop_block_production_stalled",
+					"Whether no parachain block was included for more than `--max-block-lag` \
+					 relay chain blocks",
+				)?,
+				registry,
+			)?,
+			collations: register(
+				CounterVec::new(
+					Opts::new("pop_collations", "The blocks authored by the node, by outcome"),
+					&["outcome"],
+				)?,
+				registry,
+			)?,
+		})
+	}
+}
+
+/// An update of the state watched.
+enum Update {
+	/// The best relay chain block, by number and hash.
+	BestRelay(u32, Hash),
+	/// A block authored by the node, by number and hash.
+	Authored(BlockNumber, Hash),
+}
+
+/// Spawn the task watching the production of the blocks of the parachain `para_id`, reporting
+/// metrics to `registry` if any.
+pub(crate) fn spawn<C>(
+	task_manager: &TaskManager,
+	client: Arc<C>,
+	relay_chain_interface: Arc<dyn RelayChainInterface>,
+	para_id: ParaId,
+	max_block_lag: u32,
+	registry: Option<&Registry>,
+) -> Result<(), PrometheusError>
+where
+	C: BlockchainEvents<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+{
+	let metrics = registry.map(Metrics::register).transpose()?;
+	task_manager.spawn_handle().spawn(
+		"block-production",
+		None,
+		run(client, relay_chain_interface, para_id, Watchdog::new(max_block_lag), metrics),
+	);
+	Ok(())
+}
+
+/// The hashes of the chain of `head`, down to the block `lowest`.
+fn chain_of<C: HeaderBackend<Block>>(client: &C, head: Hash, lowest: BlockNumber) -> HashSet<Hash> {
+	let mut chain = HashSet::new();
+	let mut hash = head;
+	while let Ok(Some(header)) = client.header(hash) {
+		chain.insert(hash);
+		if *header.number() <= lowest {
+			break;
+		}
+		hash = *header.parent_hash();
+	}
+	chain
+}
+
+/// Follow the best relay chain blocks and the blocks authored by the node, updating `watchdog`
+/// and `metrics`.
+async fn run<C>(
+	client: Arc<C>,
+	relay_chain_interface: Arc<dyn RelayChainInterface>,
+	para_id: ParaId,
+	mut watchdog: Watchdog,
+	metrics: Option<Metrics>,
+) where
+	C: BlockchainEvents<Block> + HeaderBackend<Block>,
+{
+	let best_relay = match relay_chain_interface.new_best_notification_stream().await {
+		Ok(headers) => headers.map(|header| Update::BestRelay(header.number, header.hash())),
+		Err(e) => {
+			log::warn!(target: LOG_TARGET, "Unable to follow the best relay chain block: {e}");
+			return;
+		},
+	};
+	let authored = client
+		.import_notification_stream()
+		.filter(|notification| futures::future::ready(notification.origin == BlockOrigin::Own))
+		.map(|notification| Update::Authored(*notification.header.number(), notification.hash));
+
+	let mut collations = Collations::default();
+	let mut updates = stream::select(best_relay, authored);
+	while let Some(update) = updates.next().await {
+		let (relay_number, relay_hash) = match update {
+			Update::BestRelay(number, hash) => (number, hash),
+			Update::Authored(number, hash) => {
+				tracing::info!(target: COLLATION, number, hash = %hash, "Authored block");
+				collations.author(number, hash);
+				if let Some(metrics) = &metrics {
+					metrics.collations.with_label_values(&["authored"]).inc();
+				}
+				continue;
+			},
+		};
+		// The head included, rather than pending availability, by the relay chain block.
+		let data = relay_chain_interface.persisted_validation_data(
+			relay_hash,
+			para_id,
+			OccupiedCoreAssumption::TimedOut,
+		);
+		let included = match relay_chain_call("persisted_validation_data", relay_hash, data).await {
+			Ok(Some(data)) => <Block as BlockT>::Header::decode(&mut &data.parent_head.0[..]),
+			Ok(None) => {
+				log::debug!(target: LOG_TARGET, "No parachain head as of relay block {relay_hash}");
+				continue;
+			},
+			Err(e) => {
+				log::debug!(target: LOG_TARGET, "Unable to read the head as of {relay_hash}: {e}");
+				continue;
+			},
+		};
+		let Ok(included) = included else {
+			log::debug!(target: LOG_TARGET, "Unable to decode the head as of {relay_hash}");
+			continue;
+		};
+
+		let now = Instant::now();
+		let number = *included.number();
+		if watchdog.note_relay_block(relay_number, number, now) {
+			if let Some(lowest) = collations.lowest().filter(|lowest| *lowest <= number) {
+				let chain = chain_of(&*client, included.hash(), lowest);
+				let (included, dropped) = collations.settle(number, &chain);
+				tracing::info!(
+					target: COLLATION,
+					relay_block = relay_number,
+					head = number,
+					included,
+					dropped,
+					"Settled authored blocks",
+				);
+				if let Some(metrics) = &metrics {
+					metrics.collations.with_label_values(&["included"]).inc_by(included);
+					metrics.collations.with_label_values(&["dropped"]).inc_by(dropped);
+				}
+			}
+		}
+		let since = watchdog.relay_blocks_since_inclusion(relay_number);
+		match watchdog.check(relay_number) {
+			Some(true) => log::warn!(
+				target: LOG_TARGET,
+				"⚠️  No parachain block was included for {since} relay chain blocks, the last \
+				 being #{number}. The parachain may not be backed by the relay chain validators.",
+			),
+			Some(false) =>
+				log::info!(target: LOG_TARGET, "Parachain block #{number} was included again"),
+			None => {},
+		}
+
+		if let Some(metrics) = &metrics {
+			let best = client.info().best_number;
+			metrics.unincluded_blocks.set(best.saturating_sub(number).into());
+			metrics.relay_blocks_since_inclusion.set(since.into());
+			if let Some(inclusion) = watchdog.inclusion {
+				metrics.last_inclusion_age.set(now.duration_since(inclusion.at).as_secs());
+			}
+			metrics.stalled.set(watchdog.stalled.into());
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn watchdog_warns_once_inclusion_lags() {
+		let now = Instant::now();
+		let mut watchdog = Watchdog::new(2);
+		assert!(watchdog.note_relay_block(100, 10, now));
+		assert_eq!(watchdog.check(100), None);
+		// The head included by the next relay chain blocks does not advance.
+		assert!(!watchdog.note_relay_block(101, 10, now));
+		assert!(!watchdog.note_relay_block(102, 10, now));
+		assert_eq!(watchdog.check(102), None);
+		assert!(!watchdog.note_relay_block(103, 10, now));
+		assert_eq!(watchdog.relay_blocks_since_inclusion(103), 3);
+		assert_eq!(watchdog.check(103), Some(true));
+		// Stalls are only reported once.
+		assert_eq!(watchdog.check(104), None);
+		// Recovered once the head advances.
+		assert!(watchdog.note_relay_block(105, 11, now));
+		assert_eq!(watchdog.check(105), Some(false));
+		assert_eq!(watchdog.relay_blocks_since_inclusion(105), 0);
+	}
+
+	#[test]
+	fn authored_blocks_are_included_or_dropped() {
+		let hash = |n: u8| Hash::repeat_byte(n);
+		let mut collations = Collations::default();
+		collations.author(1, hash(1));
+		collations.author(2, hash(2));
+		collations.author(2, hash(3));
+		collations.author(3, hash(4));
+		assert_eq!(collations.lowest(), Some(1));
+
+		// Block 2 is included, on top of block 1, while the fork of block 2 is dropped.
+		let chain = [hash(1), hash(2)].into_iter().collect();
+		assert_eq!(collations.settle(2, &chain), (2, 1));
+		// Block 3 remains pending.
+		assert_eq!(collations.lowest(), Some(3));
+		assert_eq!(collations.settle(3, &HashSet::new()), (0, 1));
+		assert_eq!(collations.lowest(), None);
+	}
+
+	#[test]
+	fn metrics_are_registered() {
+		let registry = Registry::new();
+		let metrics = Metrics::register(&registry).unwrap();
+		metrics.collations.with_label_values(&["authored"]).inc();
+		assert!(Metrics::register(&registry).is_err());
+		assert_eq!(metrics.collations.with_label_values(&["authored"]).get(), 1);
+	}
+}
@@ -0,0 +1,71 @@
+//! Bootnodes provided through a file, in addition to those of the chain spec and `--bootnodes`.
+
+use std::path::Path;
+
+use sc_network::config::MultiaddrWithPeerId;
+
+/// Read the newline-delimited bootnodes in the file at `path`. Empty lines and lines starting
+/// with `#` are ignored, whereas invalid lines are skipped with a warning.
+pub fn read(path: &Path) -> Result<Vec<MultiaddrWithPeerId>, String> {
+	let contents = std::fs::read_to_string(path)
+		.map_err(|e| format!("Unable to read bootnodes file {}: {e}", path.display()))?;
+	Ok(parse(&contents))
+}
+
+fn parse(contents: &str) -> Vec<MultiaddrWithPeerId> {
+	contents
+		.lines()
+		.enumerate()
+		.map(|(number, line)| (number + 1, line.trim()))
+		.filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+		.filter_map(|(number, line)| match line.parse() {
+			Ok(bootnode) => Some(bootnode),
+			Err(e) => {
+				log::warn!("Skipping invalid bootnode on line {number} of bootnodes file: {e}");
+				None
+			},
+		})
+		.collect()
+}
+
+/// Append `bootnodes` to `existing`, skipping any already present.
+pub fn merge(existing: &mut Vec<MultiaddrWithPeerId>, bootnodes: Vec<MultiaddrWithPeerId>) {
+	for bootnode in bootnodes {
+		if !existing.contains(&bootnode) {
+			existing.push(bootnode);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const ALICE: &str =
+		"/ip4/127.0.0.1/tcp/30333/p2p/12D3KooWEyoppNCUx8Yx66oV9fJnriXwCcXwDDUA2kj6vnc6iDEp";
+	const BOB: &str =
+		"/dns/bob.pop.network/tcp/30334/p2p/12D3KooWHdiAxVd8uMQR1hGWXccidmfCwLqcMpGwR6QcTP6QRMuD";
+
+	#[test]
+	fn valid_entries_are_merged_into_bootnodes() {
+		let path = std::env::temp_dir().join("pop-node-bootnodes-test");
+		std::fs::write(
+			&path,
+			format!(
+				"# managed by infra\n{ALICE}\n\nnot a multiaddr\n/ip4/127.0.0.1/tcp/1\n  {BOB}  \n"
+			),
+		)
+		.unwrap();
+		let bootnodes = read(&path).unwrap();
+		std::fs::remove_file(&path).unwrap();
+
+		let mut effective = vec![ALICE.parse().unwrap()];
+		merge(&mut effective, bootnodes);
+		assert_eq!(effective, vec![ALICE.parse().unwrap(), BOB.parse().unwrap()]);
+	}
+
+	#[test]
+	fn missing_file_is_an_error() {
+		assert!(read(Path::new("/non/existent/bootnodes")).is_err());
+	}
+}
@@ -1,11 +1,20 @@
+use std::path::{Path, PathBuf};
+
+use codec::{Decode, Encode};
 use cumulus_primitives_core::ParaId;
-use pop_runtime_common::{AccountId, AuraId, Signature};
+use pop_runtime_common::{AccountId, AuraId, Balance, BlockNumber, Nonce};
 use pop_runtime_mainnet::SudoAddress;
-use sc_chain_spec::{ChainSpecExtension, ChainSpecGroup};
+use pop_runtime_testnet::genesis_config_presets as presets;
+use sc_chain_spec::{ChainSpec, ChainSpecExtension, ChainSpecGroup};
 use sc_service::ChainType;
+use sc_telemetry::TelemetryEndpoints;
 use serde::{Deserialize, Serialize};
-use sp_core::{crypto::Ss58Codec, sr25519, Pair, Public};
-use sp_runtime::traits::{IdentifyAccount, Verify};
+use sp_core::{blake2_128, crypto::Ss58Codec, sr25519, twox_128};
+use sp_runtime::{BuildStorage, Percent};
+
+pub mod accounts;
+
+pub use self::accounts::{get_account_id_from_seed, get_collator_keys_from_seed, get_from_seed};
 
 /// Specialized `ChainSpec` for the development parachain runtime.
 pub type DevnetChainSpec = sc_service::GenericChainSpec<Extensions>;
@@ -19,18 +28,25 @@ pub type MainnetChainSpec = sc_service::GenericChainSpec<Extensions>;
 /// The default XCM version to set in genesis config.
 const SAFE_XCM_VERSION: u32 = xcm::prelude::XCM_VERSION;
 
+/// The telemetry server live chains report to, unless overridden with `--telemetry-url`.
+const POLKADOT_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
+
 pub(crate) enum Relay {
 	Paseo,
 	PaseoLocal,
+	/// A local relay chain other than `paseo-local`, e.g. `rococo-local`, or another parachain id,
+	/// e.g. to launch several parachains from the same node in zombienet tests.
+	Local {
+		/// The id of the relay chain.
+		relay_chain: String,
+		/// The id of the parachain on the relay chain.
+		para_id: u32,
+	},
 	Polkadot,
 }
 
-/// Helper function to generate a crypto pair from seed
-pub fn get_from_seed<TPublic: Public>(seed: &str) -> <TPublic::Pair as Pair>::Public {
-	TPublic::Pair::from_string(&format!("//{}", seed), None)
-		.expect("static values are valid; qed")
-		.public()
-}
+/// The id of the parachain on Paseo.
+pub const PASEO_PARA_ID: u32 = presets::PARA_ID;
 
 /// The extensions for the [`ChainSpec`].
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ChainSpecGroup, ChainSpecExtension)]
@@ -41,6 +57,12 @@ pub struct Extensions {
 	/// The id of the Parachain.
 	#[serde(alias = "paraId", alias = "ParaId")]
 	pub para_id: u32,
+	/// The environment the chain is deployed to, e.g. `staging`, as stamped by deployment tooling.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub environment: Option<String>,
+	/// The git commit the runtime of the chain was built from, as stamped by deployment tooling.
+	#[serde(default, alias = "runtimeCommit", skip_serializing_if = "Option::is_none")]
+	pub runtime_commit: Option<String>,
 }
 
 impl Extensions {
@@ -50,85 +72,303 @@ impl Extensions {
 	}
 }
 
-type AccountPublic = <Signature as Verify>::Signer;
-
-/// Generate collator keys from seed.
+/// The keys a collator authors blocks with, from which the session keys of every runtime are
+/// built by [`session_keys`].
 ///
-/// This function's return type must always match the session keys of the chain in tuple format.
-pub fn get_collator_keys_from_seed(seed: &str) -> AuraId {
-	get_from_seed::<AuraId>(seed)
+/// Moving to another authoring scheme, or adding a session key such as a grandpa key, only requires
+/// changing this alias, e.g. to a tuple of keys, along with [`impl_session_keys`] and the
+/// `SessionKeys` of the runtimes.
+pub type CollatorKeys = AuraId;
+
+/// The session keys of a runtime, built from the keys of a collator.
+pub trait FromCollatorKeys {
+	/// Build the session keys from the keys of a collator.
+	fn from_collator_keys(keys: CollatorKeys) -> Self;
 }
 
-/// Helper function to generate an account ID from seed
-pub fn get_account_id_from_seed<TPublic: Public>(seed: &str) -> AccountId
-where
-	AccountPublic: From<<TPublic::Pair as Pair>::Public>,
-{
-	AccountPublic::from(get_from_seed::<TPublic>(seed)).into_account()
+/// Implement [`FromCollatorKeys`] for the `SessionKeys` of each given runtime. The only place the
+/// session keys are constructed from the keys of a collator.
+macro_rules! impl_session_keys {
+	($($runtime:ident),*) => {
+		$(
+			impl FromCollatorKeys for $runtime::SessionKeys {
+				fn from_collator_keys(aura: CollatorKeys) -> Self {
+					Self { aura }
+				}
+			}
+		)*
+	};
 }
+impl_session_keys!(pop_runtime_devnet, pop_runtime_testnet, pop_runtime_mainnet);
 
-/// Generate the session keys from individual elements.
-///
-/// The input must be a tuple of individual keys (a single arg for now since we have just one key).
-pub fn pop_devnet_session_keys(keys: AuraId) -> pop_runtime_devnet::SessionKeys {
-	pop_runtime_devnet::SessionKeys { aura: keys }
+/// Build the session keys of a runtime from the keys of a collator.
+pub fn session_keys<Keys: FromCollatorKeys>(keys: CollatorKeys) -> Keys {
+	Keys::from_collator_keys(keys)
 }
-/// Generate the session keys from individual elements.
-///
-/// The input must be a tuple of individual keys (a single arg for now since we have just one key).
-pub fn pop_testnet_session_keys(keys: AuraId) -> pop_runtime_testnet::SessionKeys {
-	pop_runtime_testnet::SessionKeys { aura: keys }
+
+/// Build the session keys of the devnet runtime from the keys of a collator.
+pub fn pop_devnet_session_keys(keys: CollatorKeys) -> pop_runtime_devnet::SessionKeys {
+	session_keys(keys)
 }
-/// Generate the session keys from individual elements.
-///
-/// The input must be a tuple of individual keys (a single arg for now since we have just one key).
-pub fn pop_mainnet_session_keys(keys: AuraId) -> pop_runtime_mainnet::SessionKeys {
-	pop_runtime_mainnet::SessionKeys { aura: keys }
+/// Build the session keys of the testnet runtime from the keys of a collator.
+pub fn pop_testnet_session_keys(keys: CollatorKeys) -> pop_runtime_testnet::SessionKeys {
+	session_keys(keys)
+}
+/// Build the session keys of the mainnet runtime from the keys of a collator.
+pub fn pop_mainnet_session_keys(keys: CollatorKeys) -> pop_runtime_mainnet::SessionKeys {
+	session_keys(keys)
 }
 
-fn configure_for_relay(
-	relay: Relay,
-	properties: &mut sc_chain_spec::Properties,
-) -> (Extensions, u32) {
-	let para_id;
-
-	match relay {
-		Relay::Paseo | Relay::PaseoLocal => {
-			para_id = 4001;
-			properties.insert("tokenSymbol".into(), "PAS".into());
-			properties.insert("tokenDecimals".into(), 10.into());
-
-			let relay_chain = if let Relay::Paseo = relay {
-				properties.insert("ss58Format".into(), 0.into());
-				"paseo".into()
-			} else {
-				properties.insert("ss58Format".into(), 42.into());
-				"paseo-local".into()
-			};
-			(Extensions { relay_chain, para_id }, para_id)
-		},
-		Relay::Polkadot => {
-			para_id = 3395;
-			properties.insert("ss58Format".into(), 0.into());
-			properties.insert("tokenSymbol".into(), "DOT".into());
-			properties.insert("tokenDecimals".into(), 10.into());
-			(Extensions { relay_chain: "polkadot".into(), para_id }, para_id)
-		},
+/// The parameters of a chain specification depending on the relay chain the parachain runs on:
+/// its native token, as displayed by wallets, and its id on the relay chain.
+pub(crate) struct ChainSpecParams {
+	/// The symbol of the native token.
+	pub symbol: &'static str,
+	/// The decimal places of the native token.
+	pub decimals: u8,
+	/// The SS58 prefix addresses are displayed with.
+	pub ss58: u16,
+	/// The id of the parachain on the relay chain.
+	pub para_id: u32,
+	/// The id of the relay chain.
+	pub relay_chain: String,
+}
+
+impl From<Relay> for ChainSpecParams {
+	fn from(relay: Relay) -> Self {
+		// The native token is the token of the relay chain.
+		let (symbol, decimals) = match relay {
+			Relay::Polkadot => ("DOT", 10),
+			_ => ("PAS", 10),
+		};
+		let (ss58, para_id, relay_chain) = match relay {
+			Relay::Paseo => (0, PASEO_PARA_ID, "paseo".into()),
+			Relay::PaseoLocal => (42, PASEO_PARA_ID, "paseo-local".into()),
+			Relay::Local { relay_chain, para_id } => (42, para_id, relay_chain),
+			Relay::Polkadot => (0, 3395, "polkadot".into()),
+		};
+		Self { symbol, decimals, ss58, para_id, relay_chain }
 	}
 }
 
-pub fn development_config(relay: Relay) -> DevnetChainSpec {
-	// Give your base currency a unit name and decimal places
-	let mut properties = sc_chain_spec::Properties::new();
-	let (extensions, para_id) = configure_for_relay(relay, &mut properties);
+impl ChainSpecParams {
+	/// The properties of the chain specification, i.e. `tokenSymbol`, `tokenDecimals` and
+	/// `ss58Format`.
+	pub fn properties(&self) -> sc_chain_spec::Properties {
+		let mut properties = sc_chain_spec::Properties::new();
+		properties.insert("tokenSymbol".into(), self.symbol.into());
+		properties.insert("tokenDecimals".into(), self.decimals.into());
+		properties.insert("ss58Format".into(), self.ss58.into());
+		properties
+	}
 
-	DevnetChainSpec::builder(
-		pop_runtime_devnet::WASM_BINARY.expect("WASM binary was not built, please build it!"),
-		extensions,
+	/// The extensions of the chain specification, without deployment metadata.
+	pub fn extensions(&self) -> Extensions {
+		Extensions {
+			relay_chain: self.relay_chain.clone(),
+			para_id: self.para_id,
+			environment: None,
+			runtime_commit: None,
+		}
+	}
+}
+
+/// A builder of the chain specification `id` of the runtime `code`, named `name`, with the
+/// properties and extensions of `params`. The protocol id defaults to the id of the chain.
+fn builder(
+	code: &[u8],
+	params: &ChainSpecParams,
+	name: &str,
+	id: &str,
+	chain_type: ChainType,
+) -> sc_chain_spec::ChainSpecBuilder<Extensions> {
+	sc_service::GenericChainSpec::builder(code, params.extensions())
+		.with_name(name)
+		.with_id(id)
+		.with_chain_type(chain_type)
+		.with_protocol_id(id)
+		.with_properties(params.properties())
+}
+
+/// The build of a runtime embedded as `:code:` in the genesis of a chain specification.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Code {
+	/// The compact build, compressed with zstd, which keeps chain specifications small.
+	#[default]
+	Compressed,
+	/// The build as output by the compiler, e.g. to debug the runtime with its symbol names.
+	/// Typically several times the size of the compressed build, as is the genesis embedding it.
+	Uncompressed,
+}
+
+impl Code {
+	/// The build of the runtime whose compressed and uncompressed builds are `compressed` and
+	/// `uncompressed`, i.e. its `WASM_BINARY` and `WASM_BINARY_BLOATY`.
+	fn of(
+		self,
+		compressed: Option<&'static [u8]>,
+		uncompressed: Option<&'static [u8]>,
+	) -> &'static [u8] {
+		match self {
+			Code::Compressed => compressed,
+			Code::Uncompressed => uncompressed,
+		}
+		.expect("WASM binary was not built, please build it!")
+	}
+}
+
+/// The protocol id used by development chains when none is specified.
+pub const DEFAULT_DEVNET_PROTOCOL_ID: &str = "pop-devnet";
+
+/// Reads the contract codes at `paths`, skipping codes identical to one read before.
+pub fn read_contract_codes(paths: &[PathBuf]) -> Result<Vec<Vec<u8>>, String> {
+	let mut codes = Vec::with_capacity(paths.len());
+	for path in paths {
+		let code = std::fs::read(path)
+			.map_err(|e| format!("Failed to read contract code {}: {e}", path.display()))?;
+		if !codes.contains(&code) {
+			codes.push(code);
+		}
+	}
+	Ok(codes)
+}
+
+/// Reads the accounts to endow at genesis from the CSV file at `path`, e.g. a token distribution
+/// exported from a spreadsheet. The file has a header followed by `address,amount` rows, with the
+/// address in SS58 format and the amount in the smallest unit of the native token. Errors point
+/// at the line of the first invalid row.
+pub fn endowments_from_csv(path: &Path) -> Result<Vec<(AccountId, Balance)>, String> {
+	let csv = std::fs::read_to_string(path)
+		.map_err(|e| format!("Failed to read endowments {}: {e}", path.display()))?;
+	let mut endowments: Vec<(AccountId, Balance)> = Vec::new();
+	for (index, row) in csv.lines().enumerate().skip(1) {
+		let line = index + 1;
+		let row = row.trim();
+		if row.is_empty() {
+			continue;
+		}
+		let invalid = |reason: String| format!("{}:{line}: {reason}", path.display());
+		let (address, amount) = row
+			.split_once(',')
+			.ok_or_else(|| invalid(format!("expected `address,amount`, found \"{row}\"")))?;
+		let account = AccountId::from_ss58check(address.trim())
+			.map_err(|e| invalid(format!("invalid address \"{address}\": {e:?}")))?;
+		let amount = amount.trim();
+		if amount.starts_with('-') {
+			return Err(invalid(format!("negative amount {amount}")));
+		}
+		let amount: Balance = amount
+			.parse()
+			.map_err(|e| invalid(format!("invalid amount \"{amount}\": {e}")))?;
+		if amount == 0 {
+			return Err(invalid("zero amount".into()));
+		}
+		// Genesis is refused for accounts endowed twice.
+		if endowments.iter().any(|(endowed, _)| endowed == &account) {
+			return Err(invalid(format!("{address} is endowed twice")));
+		}
+		endowments.push((account, amount));
+	}
+	Ok(endowments)
+}
+
+/// The number of development accounts endowed at genesis of the development chain specification
+/// unless overridden with `--endow-accounts`: Alice to Ferdie, without their stash accounts.
+pub const DEFAULT_DEV_ACCOUNTS: u8 = 6;
+
+/// The balance accounts are endowed with at genesis of the development chain specification
+/// unless overridden with `--endowment`.
+pub const DEFAULT_DEV_ENDOWMENT: Balance = 1_000_000 * pop_runtime_devnet::UNIT;
+
+/// The accounts endowed at genesis of the development chain specification.
+pub struct DevEndowments {
+	/// The number of the [well-known](accounts::WELL_KNOWN) development accounts to endow, in
+	/// order.
+	pub accounts: u8,
+	/// The balance each account is endowed with, in the smallest unit of the native token.
+	pub amount: Balance,
+	/// A file of further accounts to endow, read by [`addresses_from_file`].
+	pub addresses: Option<PathBuf>,
+}
+
+impl Default for DevEndowments {
+	fn default() -> Self {
+		Self { accounts: DEFAULT_DEV_ACCOUNTS, amount: DEFAULT_DEV_ENDOWMENT, addresses: None }
+	}
+}
+
+impl DevEndowments {
+	/// The accounts to endow along with their balances, failing if there are more accounts
+	/// requested than there are development accounts, if the balance is below the existential
+	/// deposit or if an account would be endowed twice.
+	pub fn endowed(&self) -> Result<Vec<(AccountId, Balance)>, String> {
+		let accounts = usize::from(self.accounts);
+		let well_known = accounts::well_known(accounts).map_err(|_| {
+			format!(
+				"Cannot endow {accounts} development accounts, there are {}",
+				accounts::WELL_KNOWN.len()
+			)
+		})?;
+		let mut endowed: Vec<AccountId> =
+			well_known.into_iter().map(|account| account.account_id).collect();
+		if let Some(path) = &self.addresses {
+			for account in addresses_from_file(path)? {
+				// Genesis is refused for accounts endowed twice.
+				if endowed.contains(&account) {
+					return Err(format!("{account} is endowed twice"));
+				}
+				endowed.push(account);
+			}
+		}
+		if !endowed.is_empty() && self.amount < pop_runtime_devnet::EXISTENTIAL_DEPOSIT {
+			return Err(format!(
+				"Endowment {} is below the existential deposit of {}",
+				self.amount,
+				pop_runtime_devnet::EXISTENTIAL_DEPOSIT
+			));
+		}
+		Ok(endowed.into_iter().map(|account| (account, self.amount)).collect())
+	}
+}
+
+/// Reads the accounts listed in the file at `path`, one SS58 address per line. Empty lines and
+/// lines starting with `#` are skipped. Errors point at the line of the first invalid address.
+pub fn addresses_from_file(path: &Path) -> Result<Vec<AccountId>, String> {
+	let file = std::fs::read_to_string(path)
+		.map_err(|e| format!("Failed to read addresses {}: {e}", path.display()))?;
+	let mut accounts = Vec::new();
+	for (index, line) in file.lines().enumerate() {
+		let address = line.trim();
+		if address.is_empty() || address.starts_with('#') {
+			continue;
+		}
+		let account = AccountId::from_ss58check(address).map_err(|e| {
+			format!("{}:{}: invalid address \"{address}\": {e:?}", path.display(), index + 1)
+		})?;
+		accounts.push(account);
+	}
+	Ok(accounts)
+}
+
+/// Builds the development chain specification, embedding the given `code` of the runtime. A
+/// custom `protocol_id` can be provided so that separate local deployments do not discover and
+/// peer with each other, the `endowed` accounts are endowed at genesis and `contract_codes` are
+/// uploaded at genesis, owned by the sudo account.
+pub fn development_config(
+	relay: Relay,
+	protocol_id: Option<&str>,
+	endowed: Vec<(AccountId, Balance)>,
+	contract_codes: Vec<Vec<u8>>,
+	code: Code,
+) -> DevnetChainSpec {
+	let params = ChainSpecParams::from(relay);
+	builder(
+		code.of(pop_runtime_devnet::WASM_BINARY, pop_runtime_devnet::WASM_BINARY_BLOATY),
+		&params,
+		"Pop Network Development",
+		"pop-devnet",
+		ChainType::Development,
 	)
-	.with_name("Pop Network Development")
-	.with_id("pop-devnet")
-	.with_chain_type(ChainType::Development)
 	.with_genesis_config_patch(devnet_genesis(
 		// initial collators.
 		vec![
@@ -142,62 +382,475 @@ pub fn development_config(relay: Relay) -> DevnetChainSpec {
 			),
 		],
 		get_account_id_from_seed::<sr25519::Public>("Alice"),
-		para_id.into(),
+		endowed,
+		contract_codes,
+		params.para_id.into(),
 	))
-	.with_protocol_id("pop-devnet")
-	.with_properties(properties)
+	.with_protocol_id(protocol_id.unwrap_or(DEFAULT_DEVNET_PROTOCOL_ID))
 	.build()
 }
 
-pub fn testnet_config(relay: Relay) -> TestnetChainSpec {
-	// Give your base currency a unit name and decimal places
-	let mut properties = sc_chain_spec::Properties::new();
-	let (extensions, para_id) = configure_for_relay(relay, &mut properties);
+/// Builds a minimal development chain specification for integration tests: a single collator
+/// (Alice), a fixed set of funded development accounts and no sudo. The specification only
+/// depends on the runtime, so its genesis is identical across runs.
+///
+/// Only available with the `test-helpers` feature, keeping it out of release builds.
+#[cfg(any(test, feature = "test-helpers"))]
+pub fn minimal_test_spec() -> DevnetChainSpec {
+	use pop_runtime_devnet::{EXISTENTIAL_DEPOSIT, UNIT};
 
-	let collator_0_account_id: AccountId =
-		AccountId::from_ss58check("5Gn9dVgCNUYtC5JVMBheQQv2x6Lpg5sAMcQVRupG1s3tP2gR").unwrap();
-	let collator_0_aura_id: AuraId =
-		AuraId::from_ss58check("5Gn9dVgCNUYtC5JVMBheQQv2x6Lpg5sAMcQVRupG1s3tP2gR").unwrap();
-	let collator_1_account_id: AccountId =
-		AccountId::from_ss58check("5FyVvcSvSXCkBwvBEHkUh1VWGGrwaR3zbYBkU3Rc5DqV75S4").unwrap();
-	let collator_1_aura_id: AuraId =
-		AuraId::from_ss58check("5FyVvcSvSXCkBwvBEHkUh1VWGGrwaR3zbYBkU3Rc5DqV75S4").unwrap();
-	let collator_2_account_id: AccountId =
-		AccountId::from_ss58check("5GMqrQuWpyyBBK7LAWXR5psWvKc1QMqtiyasjp23VNKZWgh6").unwrap();
-	let collator_2_aura_id: AuraId =
-		AuraId::from_ss58check("5GMqrQuWpyyBBK7LAWXR5psWvKc1QMqtiyasjp23VNKZWgh6").unwrap();
-	let sudo_account_id: AccountId =
-		AccountId::from_ss58check("5FPL3ZLqUk6MyBoZrQZ1Co29WAteX6T6N68TZ6jitHvhpyuD").unwrap();
-
-	#[allow(deprecated)]
-	TestnetChainSpec::builder(
-		pop_runtime_testnet::WASM_BINARY.expect("WASM binary was not built, please build it!"),
-		extensions,
+	let params = ChainSpecParams::from(Relay::PaseoLocal);
+	let alice = get_account_id_from_seed::<sr25519::Public>("Alice");
+	let endowed = ["Alice", "Bob", "Charlie", "Dave"]
+		.map(|seed| (get_account_id_from_seed::<sr25519::Public>(seed), 1_000 * UNIT));
+	let keys = pop_devnet_session_keys(get_collator_keys_from_seed("Alice"));
+
+	builder(
+		pop_runtime_devnet::WASM_BINARY.expect("WASM binary was not built, please build it!"),
+		&params,
+		"Pop Network Test",
+		"pop-test",
+		ChainType::Local,
 	)
-	.with_name("Pop Network Testnet")
-	.with_id("pop-testnet")
-	.with_chain_type(ChainType::Live)
-	.with_genesis_config_patch(testnet_genesis(
-		// initial collators.
-		vec![
-			// POP COLLATOR 0
-			(collator_0_account_id, collator_0_aura_id),
-			// POP COLLATOR 1
-			(collator_1_account_id, collator_1_aura_id),
-			// POP COLLATOR 2
-			(collator_2_account_id, collator_2_aura_id),
-		],
-		sudo_account_id,
-		para_id.into(),
-	))
-	.with_protocol_id("pop-testnet")
-	.with_properties(properties)
+	.with_genesis_config_patch(serde_json::json!({
+		"balances": {
+			"balances": endowed,
+		},
+		"parachainInfo": {
+			"parachainId": ParaId::from(params.para_id),
+		},
+		"collatorSelection": {
+			"invulnerables": [alice.clone()],
+			"candidacyBond": EXISTENTIAL_DEPOSIT * 16,
+		},
+		"session": {
+			"keys": [(
+				alice.clone(), // account id
+				alice,         // validator id
+				keys,          // session keys
+			)],
+		},
+		"polkadotXcm": polkadot_xcm_genesis(),
+	}))
+	.build()
+}
+
+/// Builds a local testnet chain specification for integration tests, with Alice and Bob as
+/// collators of the local `relay` chain, Alice as sudo and Alice, Bob and Charlie as council.
+///
+/// Only available with the `test-helpers` feature, keeping it out of release builds.
+#[cfg(any(test, feature = "test-helpers"))]
+pub fn local_testnet_config(relay: Relay) -> TestnetChainSpec {
+	let params = ChainSpecParams::from(relay);
+	let builder = builder(
+		pop_runtime_testnet::WASM_BINARY.expect("WASM binary was not built, please build it!"),
+		&params,
+		"Pop Network Local Testnet",
+		"pop-testnet-local",
+		ChainType::Local,
+	);
+	// The preset of the runtime is for Paseo's id of the testnet only.
+	if params.para_id == presets::PARA_ID {
+		builder.with_genesis_config_preset_name(presets::LOCAL_TESTNET)
+	} else {
+		builder.with_genesis_config_patch(presets::local_testnet_genesis(params.para_id.into()))
+	}
 	.build()
 }
 
-pub fn mainnet_config(relay: Relay) -> MainnetChainSpec {
-	let mut properties = sc_chain_spec::Properties::new();
-	let (extensions, para_id) = configure_for_relay(relay, &mut properties);
+/// Builds the testnet chain specification, with the timestamp of genesis set to
+/// `genesis_timestamp` if any.
+pub fn testnet_config(
+	relay: Relay,
+	code: Code,
+	genesis_timestamp: Option<u64>,
+) -> Result<TestnetChainSpec, String> {
+	let params = ChainSpecParams::from(relay);
+	let builder = builder(
+		code.of(pop_runtime_testnet::WASM_BINARY, pop_runtime_testnet::WASM_BINARY_BLOATY),
+		&params,
+		"Pop Network Testnet",
+		"pop-testnet",
+		ChainType::Live,
+	);
+	// The preset of the runtime is for Paseo's id of the testnet.
+	let builder = match params.para_id == presets::PARA_ID {
+		true => builder.with_genesis_config_preset_name(presets::LIVE),
+		false => builder.with_genesis_config_patch(presets::live_genesis(params.para_id.into())),
+	};
+	let mut spec = builder.with_telemetry_endpoints(live_telemetry_endpoints()).build();
+	write_raw_genesis(&mut spec, &[], genesis_timestamp)?;
+	Ok(spec)
+}
+
+/// The genesis of the testnet as described by a deployment, read from a JSON or TOML file by
+/// [`from_genesis_file`]. Accounts and keys are in SS58 format and balances in the smallest unit
+/// of the native token.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GenesisFile {
+	/// The relay chain, i.e. `paseo`, `paseo-local` or `polkadot`.
+	relay_chain: String,
+	/// The id of the parachain on the relay chain.
+	para_id: u32,
+	/// The sudo account, none unless specified, as for the live testnet.
+	#[serde(default)]
+	sudo: Option<AccountId>,
+	/// The members of the technical committee.
+	#[serde(default)]
+	technical_committee: Vec<AccountId>,
+	/// The members of the council.
+	#[serde(default)]
+	council: Vec<AccountId>,
+	/// The collators registered as invulnerable.
+	invulnerables: Vec<GenesisCollator>,
+	/// The bond candidates must reserve to register as collators, 16 times the existential
+	/// deposit unless specified.
+	#[serde(default)]
+	candidacy_bond: Option<Balance>,
+	/// The number of candidates selected as collators besides the invulnerables, none unless
+	/// specified.
+	#[serde(default)]
+	desired_candidates: Option<u32>,
+	/// The only accounts allowed to upload contract codes, e.g. for a chain only running audited
+	/// contracts. Anyone may upload codes unless specified.
+	#[serde(default)]
+	contract_uploaders: Option<Vec<AccountId>>,
+	/// The accounts endowed at genesis.
+	#[serde(default)]
+	endowments: Vec<GenesisEndowment>,
+	/// The assets created at genesis.
+	#[serde(default)]
+	assets: Vec<GenesisAsset>,
+	/// The vesting schedules of endowed accounts, e.g. of a launch distribution.
+	#[serde(default)]
+	vesting: Vec<GenesisVesting>,
+	/// How the collators are rewarded, as by the testnet presets unless specified.
+	#[serde(default)]
+	collator_rewards: Option<GenesisRewards>,
+}
+
+/// How the collators of a [`GenesisFile`] are rewarded: with the transaction fees which are neither
+/// burnt nor fund the treasury, along with the amount minted into the pot of the collator selection
+/// each block.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GenesisRewards {
+	/// The percentage of transaction fees which is burnt.
+	burn_percent: u8,
+	/// The percentage of transaction fees which funds the treasury.
+	treasury_percent: u8,
+	/// The amount minted into the pot each block, none unless specified.
+	#[serde(default)]
+	block_reward: Balance,
+}
+
+impl From<GenesisRewards> for presets::CollatorRewards {
+	fn from(rewards: GenesisRewards) -> Self {
+		Self {
+			burn: Percent::from_percent(rewards.burn_percent),
+			treasury: Percent::from_percent(rewards.treasury_percent),
+			block_reward: rewards.block_reward,
+		}
+	}
+}
+
+/// An invulnerable collator of a [`GenesisFile`].
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GenesisCollator {
+	account: AccountId,
+	/// The validator id the session keys are registered for, which must be the account, as it is
+	/// unless specified.
+	#[serde(default)]
+	validator: Option<AccountId>,
+	/// The keys the collator authors blocks with, i.e. its aura key.
+	keys: CollatorKeys,
+}
+
+/// An account endowed in a [`GenesisFile`].
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GenesisEndowment {
+	account: AccountId,
+	balance: Balance,
+	/// The nonce the account starts at.
+	#[serde(default)]
+	nonce: u64,
+}
+
+/// An asset of `pallet_assets` created at genesis, owned by `owner`, which is its admin, issuer and
+/// freezer as well.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GenesisAsset {
+	id: u32,
+	owner: AccountId,
+	/// Whether accounts may hold the asset without holding the native token.
+	#[serde(default)]
+	sufficient: bool,
+	/// The least balance of the asset an account may hold.
+	min_balance: Balance,
+	name: String,
+	symbol: String,
+	decimals: u8,
+	/// The accounts holding the asset at genesis.
+	#[serde(default)]
+	holders: Vec<GenesisAssetHolder>,
+	/// The balance of the asset charged per unit of native fees, if transaction fees can be paid
+	/// in the asset.
+	#[serde(default)]
+	fee_per_unit: Option<Balance>,
+}
+
+/// An account holding a [`GenesisAsset`] at genesis.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GenesisAssetHolder {
+	account: AccountId,
+	balance: Balance,
+}
+
+/// A vesting schedule of a [`GenesisFile`], locking part of the balance an account is endowed
+/// with, which then unlocks linearly.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GenesisVesting {
+	account: AccountId,
+	/// The balance locked, out of the balance the account is endowed with.
+	locked: Balance,
+	/// The balance unlocked per block, of which the locked balance is a multiple.
+	per_block: Balance,
+	/// The block the balance starts unlocking from, genesis unless specified.
+	#[serde(default)]
+	starting_block: BlockNumber,
+}
+
+/// The genesis config of `pallet_vesting` for the vesting `schedules` of the endowed `accounts`,
+/// i.e. the account, starting block, length in blocks and liquid balance of each schedule.
+/// Schedules which would fail to build the genesis storage, or which would not unlock as
+/// described, are refused.
+fn vesting_genesis(
+	schedules: &[GenesisVesting],
+	accounts: &[GenesisAccount],
+) -> Result<Vec<(AccountId, BlockNumber, BlockNumber, Balance)>, String> {
+	let mut vesting = Vec::with_capacity(schedules.len());
+	for (index, schedule) in schedules.iter().enumerate() {
+		let GenesisVesting { account, locked, per_block, starting_block } = schedule;
+		// The lock of an account only covers its last schedule.
+		if schedules[..index].iter().any(|vested| vested.account == *account) {
+			return Err(format!("\"{account}\" vests twice"));
+		}
+		let free = accounts
+			.iter()
+			.find(|endowed| endowed.account == *account)
+			.map(|endowed| endowed.free)
+			.ok_or_else(|| format!("\"{account}\" vests without being endowed"))?;
+		if *locked == 0 || *locked > free {
+			return Err(format!("\"{account}\" locks {locked} out of {free}"));
+		}
+		// The runtime derives the balance unlocked per block from the length of the schedule.
+		if *per_block == 0 || locked % per_block != 0 {
+			return Err(format!(
+				"\"{account}\" locks {locked}, which is not a multiple of {per_block} per block"
+			));
+		}
+		let length = BlockNumber::try_from(locked / per_block).map_err(|_| {
+			format!("\"{account}\" vests over more than {} blocks", BlockNumber::MAX)
+		})?;
+		vesting.push((account.clone(), *starting_block, length, free - locked));
+	}
+	Ok(vesting)
+}
+
+/// Ensure that the assets can be created at genesis along with their holders, as they would
+/// otherwise fail to build the genesis storage.
+fn check_assets(assets: &[GenesisAsset], accounts: &[GenesisAccount]) -> Result<(), String> {
+	for (index, asset) in assets.iter().enumerate() {
+		let id = asset.id;
+		if assets[..index].iter().any(|created| created.id == id) {
+			return Err(format!("asset {id} is created twice"));
+		}
+		if asset.min_balance == 0 {
+			return Err(format!("asset {id} has no minimum balance"));
+		}
+		// A zero rate would waive the fees paid in the asset.
+		if asset.fee_per_unit == Some(0) {
+			return Err(format!("asset {id} is charged no fees"));
+		}
+		for GenesisAssetHolder { account, balance } in &asset.holders {
+			if *balance < asset.min_balance {
+				return Err(format!(
+					"\"{account}\" holds less than the minimum balance of asset {id}"
+				));
+			}
+			// Accounts only exist for insufficient assets if they hold the native token.
+			if !asset.sufficient && !accounts.iter().any(|endowed| endowed.account == *account) {
+				return Err(format!("\"{account}\" holds asset {id} without being endowed"));
+			}
+		}
+	}
+	Ok(())
+}
+
+/// Builds a testnet chain specification whose invulnerables, endowments, vesting schedules, assets,
+/// sudo account if any and para id are read from the JSON or TOML file at `path`, so that release
+/// specifications can be cut without changing the node. The timestamp of genesis is set to
+/// `genesis_timestamp` if any.
+pub fn from_genesis_file(
+	path: &Path,
+	code: Code,
+	genesis_timestamp: Option<u64>,
+) -> Result<TestnetChainSpec, String> {
+	let contents = std::fs::read_to_string(path)
+		.map_err(|e| format!("Failed to read genesis config {}: {e}", path.display()))?;
+	let invalid = |e: String| format!("Invalid genesis config {}: {e}", path.display());
+	let file: GenesisFile = if crate::spec_toml::is_toml(&path.to_string_lossy()) {
+		toml::from_str(&contents).map_err(|e| invalid(e.to_string()))?
+	} else {
+		serde_json::from_str(&contents).map_err(|e| invalid(e.to_string()))?
+	};
+	let relay = match file.relay_chain.as_str() {
+		"paseo" => Relay::Paseo,
+		"paseo-local" => Relay::PaseoLocal,
+		"polkadot" => Relay::Polkadot,
+		other => return Err(invalid(format!("unknown relay chain \"{other}\""))),
+	};
+	// The chain would launch without producing any blocks.
+	if file.invulnerables.is_empty() {
+		return Err(invalid("no invulnerables".into()));
+	}
+	// Genesis is refused for more desired candidates than can register.
+	let max_candidates = pop_runtime_testnet::MaxCandidates::get();
+	if file.desired_candidates.is_some_and(|desired| desired > max_candidates) {
+		return Err(invalid(format!("more than {max_candidates} desired candidates")));
+	}
+	// Candidates could otherwise register while holding little more than dust.
+	if file
+		.candidacy_bond
+		.is_some_and(|bond| bond < pop_runtime_testnet::EXISTENTIAL_DEPOSIT)
+	{
+		return Err(invalid("candidacy bond below the existential deposit".into()));
+	}
+	let mut accounts: Vec<GenesisAccount> = Vec::with_capacity(file.endowments.len());
+	for GenesisEndowment { account, balance, nonce } in file.endowments {
+		// Genesis is refused for accounts endowed twice.
+		if accounts.iter().any(|endowed| endowed.account == account) {
+			return Err(invalid(format!("\"{account}\" is endowed twice")));
+		}
+		accounts.push(GenesisAccount::try_from((account, nonce, balance)).map_err(invalid)?);
+	}
+	check_assets(&file.assets, &accounts).map_err(invalid)?;
+	// The shares would add up to more than the fees.
+	if file.collator_rewards.as_ref().is_some_and(|rewards| {
+		u16::from(rewards.burn_percent) + u16::from(rewards.treasury_percent) > 100
+	}) {
+		return Err(invalid("fee shares of the collator rewards exceed 100%".into()));
+	}
+	let vesting = vesting_genesis(&file.vesting, &accounts).map_err(invalid)?;
+	let invulnerables = file
+		.invulnerables
+		.into_iter()
+		.map(|GenesisCollator { account, validator, keys }| {
+			let validator = validator.unwrap_or(account.clone());
+			Invulnerable::try_from((account, validator, keys)).map_err(invalid)
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let params = ChainSpecParams { para_id: file.para_id, ..relay.into() };
+	let invulnerables = invulnerables.into_iter().map(Into::into).collect();
+	let mut genesis = testnet_genesis(presets::GenesisParams {
+		tech_committee: file.technical_committee,
+		council: file.council,
+		endowed: accounts.iter().map(|endowed| (endowed.account.clone(), endowed.free)).collect(),
+		assets: file.assets.into_iter().map(Into::into).collect(),
+		// how the collators are rewarded, as by the presets unless specified.
+		rewards: file.collator_rewards.map(Into::into).unwrap_or_default(),
+		..presets::GenesisParams::new(invulnerables, file.sudo, file.para_id.into())
+	});
+	if let Some(bond) = file.candidacy_bond {
+		genesis["collatorSelection"]["candidacyBond"] = bond.into();
+	}
+	if let Some(desired) = file.desired_candidates {
+		genesis["collatorSelection"]["desiredCandidates"] = desired.into();
+	}
+	if let Some(uploaders) = file.contract_uploaders {
+		genesis["contractsUploadPolicy"] =
+			serde_json::json!({ "policy": "Permissioned", "uploaders": uploaders });
+	}
+	if !vesting.is_empty() {
+		genesis["vesting"] = serde_json::json!({ "vesting": vesting });
+	}
+
+	let mut spec = builder(
+		code.of(pop_runtime_testnet::WASM_BINARY, pop_runtime_testnet::WASM_BINARY_BLOATY),
+		&params,
+		"Pop Network Testnet",
+		"pop-testnet",
+		ChainType::Live,
+	)
+	.with_genesis_config_patch(genesis)
+	.with_telemetry_endpoints(live_telemetry_endpoints())
+	.build();
+	let nonces: Vec<_> = accounts.into_iter().map(|a| (a.account, a.nonce)).collect();
+	write_raw_genesis(&mut spec, &nonces, genesis_timestamp).map_err(invalid)?;
+	Ok(spec)
+}
+
+/// The accounts as stored by `frame_system`.
+pub(crate) type AccountInfo =
+	frame_system::AccountInfo<Nonce, pallet_balances::AccountData<Balance>>;
+
+/// The storage key of the storage value `item` of `pallet`.
+pub(crate) fn storage_key(pallet: &str, item: &str) -> Vec<u8> {
+	[twox_128(pallet.as_bytes()), twox_128(item.as_bytes())].concat()
+}
+
+/// The storage key of the entry of `System::Account` of `account`.
+pub(crate) fn account_key(account: &AccountId) -> Vec<u8> {
+	let account = account.encode();
+	[storage_key("System", "Account"), blake2_128(&account).to_vec(), account].concat()
+}
+
+/// Write the state of genesis which is not part of the genesis config of any pallet into the
+/// genesis storage of `spec`, making its genesis raw: the `nonces` the endowed accounts start at
+/// and the `timestamp` of genesis, if any.
+fn write_raw_genesis(
+	spec: &mut TestnetChainSpec,
+	nonces: &[(AccountId, Nonce)],
+	timestamp: Option<u64>,
+) -> Result<(), String> {
+	let nonces: Vec<_> = nonces.iter().filter(|(_, nonce)| *nonce != 0).collect();
+	if nonces.is_empty() && timestamp.is_none() {
+		return Ok(());
+	}
+	let mut storage = spec.build_storage()?;
+	for (account, nonce) in nonces {
+		let key = account_key(account);
+		let mut info = storage
+			.top
+			.get(&key)
+			.and_then(|info| AccountInfo::decode(&mut &info[..]).ok())
+			.ok_or_else(|| format!("\"{account}\" starting at nonce {nonce} is not endowed"))?;
+		info.nonce = *nonce;
+		storage.top.insert(key, info.encode());
+	}
+	// The timestamp of the first block must not be earlier, so it must not be later than the launch
+	// of the chain.
+	if let Some(timestamp) = timestamp {
+		storage.top.insert(storage_key("Timestamp", "Now"), timestamp.encode());
+	}
+	spec.set_storage(storage);
+	Ok(())
+}
+
+/// Builds the mainnet chain specification, endowing the `endowed` accounts at genesis.
+pub fn mainnet_config(
+	relay: Relay,
+	endowed: Vec<(AccountId, Balance)>,
+	code: Code,
+) -> MainnetChainSpec {
+	let params = ChainSpecParams::from(relay);
 
 	let collator_0_account_id: AccountId =
 		AccountId::from_ss58check("15B6eUkXgoLA3dWruCRYWeBGNC8SCwuqiMtMTM1Zh2auSg3w").unwrap();
@@ -212,14 +865,13 @@ pub fn mainnet_config(relay: Relay) -> MainnetChainSpec {
 	// - Threshold 2
 	let sudo_account_id: AccountId = SudoAddress::get();
 
-	#[allow(deprecated)]
-	MainnetChainSpec::builder(
-		pop_runtime_mainnet::WASM_BINARY.expect("WASM binary was not built, please build it!"),
-		extensions,
+	builder(
+		code.of(pop_runtime_mainnet::WASM_BINARY, pop_runtime_mainnet::WASM_BINARY_BLOATY),
+		&params,
+		"Pop Network",
+		"pop",
+		ChainType::Live,
 	)
-	.with_name("Pop Network")
-	.with_id("pop")
-	.with_chain_type(ChainType::Live)
 	.with_genesis_config_patch(mainnet_genesis(
 		// initial collators.
 		vec![
@@ -227,23 +879,44 @@ pub fn mainnet_config(relay: Relay) -> MainnetChainSpec {
 			(collator_0_account_id, collator_0_aura_id),
 		],
 		sudo_account_id,
-		para_id.into(),
+		endowed,
+		params.para_id.into(),
 	))
-	.with_protocol_id("pop")
-	.with_properties(properties)
+	.with_telemetry_endpoints(live_telemetry_endpoints())
 	.build()
 }
 
+/// The telemetry endpoints of live chains, reporting to the Polkadot telemetry at the lowest
+/// verbosity.
+fn live_telemetry_endpoints() -> TelemetryEndpoints {
+	TelemetryEndpoints::new(vec![(POLKADOT_TELEMETRY_URL.into(), 0)])
+		.expect("Polkadot telemetry url is valid; qed")
+}
+
+/// The genesis config of `pallet-xcm`, shared by all runtimes.
+///
+/// Only the XCM version used for chains whose version is not yet known can be configured at
+/// genesis. The reserves trusted for reserve transfers, such as asset hub, are runtime config
+/// (`TrustedReserves` in each runtime's `config::xcm`), whereas the versions supported by other
+/// chains (`SupportedVersion`) are discovered as soon as messages are exchanged. Together, these
+/// allow reserve transfers from asset hub from block zero.
+fn polkadot_xcm_genesis() -> serde_json::Value {
+	serde_json::json!({
+		"safeXcmVersion": Some(SAFE_XCM_VERSION),
+	})
+}
+
 fn mainnet_genesis(
-	invulnerables: Vec<(AccountId, AuraId)>,
+	invulnerables: Vec<(AccountId, CollatorKeys)>,
 	root: AccountId,
+	endowed: Vec<(AccountId, Balance)>,
 	id: ParaId,
 ) -> serde_json::Value {
 	use pop_runtime_mainnet::EXISTENTIAL_DEPOSIT;
 
-	serde_json::json!({
+	let genesis = serde_json::json!({
 		"balances": {
-			"balances": [],
+			"balances": endowed,
 		},
 		"parachainInfo": {
 			"parachainId": id,
@@ -256,70 +929,125 @@ fn mainnet_genesis(
 		"session": {
 			"keys": invulnerables
 				.into_iter()
-				.map(|(acc, aura)| {
+				.map(|(acc, keys)| {
 					(
 						acc.clone(),                 // account id
 						acc,                         // validator id
-						pop_mainnet_session_keys(aura),      // session keys
+						pop_mainnet_session_keys(keys),      // session keys
 					)
 				})
 			.collect::<Vec<_>>(),
 		},
-		"polkadotXcm": {
-			"safeXcmVersion": Some(SAFE_XCM_VERSION),
-		},
+		"polkadotXcm": polkadot_xcm_genesis(),
 		"sudo": { "key": Some(root) }
-	})
+	});
+	check_session_keys(&genesis);
+	genesis
 }
 
-fn testnet_genesis(
-	invulnerables: Vec<(AccountId, AuraId)>,
-	root: AccountId,
-	id: ParaId,
-) -> serde_json::Value {
-	use pop_runtime_testnet::EXISTENTIAL_DEPOSIT;
+/// An initial collator of the testnet.
+struct Invulnerable {
+	/// The account of the collator, registered as invulnerable.
+	account: AccountId,
+	/// The validator id the session keys of the collator are registered for.
+	validator: AccountId,
+	/// The keys the collator authors blocks with.
+	keys: CollatorKeys,
+}
 
-	serde_json::json!({
-		"balances": {
-			"balances": [],
-		},
-		"parachainInfo": {
-			"parachainId": id,
-		},
-		"collatorSelection": {
-			"invulnerables": invulnerables.iter().cloned().map(|(acc, _)| acc).collect::<Vec<_>>(),
-			"candidacyBond": EXISTENTIAL_DEPOSIT * 16,
-		},
-		"session": {
-			"keys": invulnerables
-				.into_iter()
-				.map(|(acc, aura)| {
-					(
-						acc.clone(),                 // account id
-						acc,                         // validator id
-						pop_testnet_session_keys(aura),      // session keys
-					)
-				})
-			.collect::<Vec<_>>(),
-		},
-		"polkadotXcm": {
-			"safeXcmVersion": Some(SAFE_XCM_VERSION),
-		},
-		"sudo": { "key": Some(root) }
-	})
+/// A collator whose account is its validator id as well.
+impl From<(AccountId, CollatorKeys)> for Invulnerable {
+	fn from((account, keys): (AccountId, CollatorKeys)) -> Self {
+		Self { validator: account.clone(), account, keys }
+	}
+}
+
+/// An initial collator of the testnet, as by the presets of the runtime.
+impl From<Invulnerable> for presets::Invulnerable {
+	fn from(Invulnerable { account, validator, keys }: Invulnerable) -> Self {
+		(account, validator, keys)
+	}
+}
+
+/// A collator given along with the validator id its session keys are registered for, failing
+/// unless it is the account: the runtime identifies collators by their account
+/// (`ValidatorIdOf = IdentityCollator`), so that keys registered for another validator id would
+/// never be used, leaving the chain without authorities.
+impl TryFrom<(AccountId, AccountId, CollatorKeys)> for Invulnerable {
+	type Error = String;
+
+	fn try_from(
+		(account, validator, keys): (AccountId, AccountId, CollatorKeys),
+	) -> Result<Self, String> {
+		if validator != account {
+			return Err(format!(
+				"validator id \"{validator}\" of \"{account}\" differs from its account"
+			));
+		}
+		Ok(Self { account, validator, keys })
+	}
+}
+
+/// An account of the testnet endowed at genesis, starting at a nonce.
+struct GenesisAccount {
+	account: AccountId,
+	nonce: Nonce,
+	free: Balance,
+}
+
+/// An account starting at a nonce with a free balance, failing if the nonce does not fit the
+/// `Nonce` of the runtime.
+impl TryFrom<(AccountId, u64, Balance)> for GenesisAccount {
+	type Error = String;
+
+	fn try_from((account, nonce, free): (AccountId, u64, Balance)) -> Result<Self, String> {
+		let nonce = Nonce::try_from(nonce)
+			.map_err(|_| format!("nonce {nonce} of \"{account}\" exceeds {}", Nonce::MAX))?;
+		Ok(Self { account, nonce, free })
+	}
+}
+
+/// The genesis of an asset of the testnet, whose name and symbol are stored as bytes.
+impl From<GenesisAsset> for presets::GenesisAsset {
+	fn from(asset: GenesisAsset) -> Self {
+		Self {
+			id: asset.id,
+			owner: asset.owner,
+			sufficient: asset.sufficient,
+			min_balance: asset.min_balance,
+			name: asset.name.into_bytes(),
+			symbol: asset.symbol.into_bytes(),
+			decimals: asset.decimals,
+			holders: asset.holders.into_iter().map(|h| (h.account, h.balance)).collect(),
+			fee_per_unit: asset.fee_per_unit,
+		}
+	}
+}
+
+/// The genesis config patch of the testnet, built by the runtime as its presets are.
+fn testnet_genesis(params: presets::GenesisParams) -> serde_json::Value {
+	let genesis = presets::testnet_genesis(params);
+	check_session_keys(&genesis);
+	genesis
 }
 
 fn devnet_genesis(
-	invulnerables: Vec<(AccountId, AuraId)>,
+	invulnerables: Vec<(AccountId, CollatorKeys)>,
 	root: AccountId,
+	mut endowed: Vec<(AccountId, Balance)>,
+	contract_codes: Vec<Vec<u8>>,
 	id: ParaId,
 ) -> serde_json::Value {
-	use pop_runtime_devnet::EXISTENTIAL_DEPOSIT;
+	use pop_runtime_devnet::{EXISTENTIAL_DEPOSIT, UNIT};
 	let asset_hub = ismp_parachain::ParachainData { id: 1000, slot_duration: 6000 };
+	// The owner of the contract codes uploaded at genesis must be able to pay their deposits.
+	if !contract_codes.is_empty() && !endowed.iter().any(|(account, _)| account == &root) {
+		endowed.push((root.clone(), 1_000 * UNIT));
+	}
 
-	serde_json::json!({
+	let genesis = serde_json::json!({
 		"balances": {
-			"balances": [],
+			"balances": endowed,
 		},
 		"parachainInfo": {
 			"parachainId": id,
@@ -331,26 +1059,790 @@ fn devnet_genesis(
 		"session": {
 			"keys": invulnerables
 				.into_iter()
-				.map(|(acc, aura)| {
+				.map(|(acc, keys)| {
 					(
 						acc.clone(),                 // account id
 						acc,                         // validator id
-						pop_devnet_session_keys(aura),      // session keys
+						pop_devnet_session_keys(keys),      // session keys
 					)
 				})
 			.collect::<Vec<_>>(),
 		},
-		"polkadotXcm": {
-			"safeXcmVersion": Some(SAFE_XCM_VERSION),
-		},
-		"sudo": { "key": Some(root) },
+		"polkadotXcm": polkadot_xcm_genesis(),
+		"sudo": { "key": Some(root.clone()) },
+		"contractsGenesis": { "owner": Some(root.clone()), "codes": contract_codes },
+		// A sample collection of NFTs, of id 0, for contracts to mint and transfer items of.
+		"nftsGenesis": { "collections": [root] },
 		// Set the following parachains to be tracked via ISMP.
 		"ismpParachain": pop_runtime_devnet::IsmpParachainConfig {
 			// Asset Hub
 			parachains: vec![asset_hub],
 			..Default::default()
 		},
-	})
+	});
+	check_session_keys(&genesis);
+	genesis
+}
+
+/// Panics unless every invulnerable of `genesis` has session keys, as otherwise the chain would
+/// launch without producing any blocks.
+fn check_session_keys(genesis: &serde_json::Value) {
+	if let Err(e) = ensure_session_keys(genesis) {
+		panic!("Invalid genesis: {e}");
+	}
+}
+
+/// Ensure every account of `collatorSelection.invulnerables` of `genesis` has an entry in
+/// `session.keys` registered for it as validator id, without which the invulnerable cannot author
+/// blocks.
+fn ensure_session_keys(genesis: &serde_json::Value) -> Result<(), String> {
+	// The entries are `(account, validator id, keys)`, the keys being used by the validator id.
+	let with_keys: Vec<_> = genesis["session"]["keys"]
+		.as_array()
+		.into_iter()
+		.flatten()
+		.map(|keys| &keys[1])
+		.collect();
+	for invulnerable in
+		genesis["collatorSelection"]["invulnerables"].as_array().into_iter().flatten()
+	{
+		if !with_keys.contains(&invulnerable) {
+			return Err(format!("invulnerable {invulnerable} has no session keys"));
+		}
+	}
+	Ok(())
+}
+
+#[test]
+fn development_config_uses_custom_protocol_id() {
+	assert_eq!(
+		development_config(Relay::PaseoLocal, None, vec![], vec![], Code::Compressed).protocol_id(),
+		Some(DEFAULT_DEVNET_PROTOCOL_ID)
+	);
+	assert_eq!(
+		development_config(Relay::PaseoLocal, Some("pop-custom"), vec![], vec![], Code::Compressed)
+			.protocol_id(),
+		Some("pop-custom")
+	);
+}
+
+#[test]
+fn live_configs_report_to_polkadot_telemetry() {
+	use sc_chain_spec::ChainSpec;
+
+	let specs: [Box<dyn ChainSpec>; 2] = [
+		Box::new(testnet_config(Relay::Paseo, Code::Compressed, None).unwrap()),
+		Box::new(mainnet_config(Relay::Polkadot, vec![], Code::Compressed)),
+	];
+	for spec in specs {
+		assert_eq!(spec.chain_type(), ChainType::Live);
+		assert_eq!(spec.properties()["ss58Format"], 0);
+		let json: serde_json::Value = serde_json::from_str(&spec.as_json(false).unwrap()).unwrap();
+		assert_eq!(json["telemetryEndpoints"], serde_json::json!([[POLKADOT_TELEMETRY_URL, 0]]));
+	}
+}
+
+#[test]
+fn local_configs_use_given_relay_chain_and_para_id() {
+	use sc_chain_spec::ChainSpec;
+
+	let relay = || Relay::Local { relay_chain: "rococo-local".into(), para_id: 2000 };
+	let specs: [Box<dyn ChainSpec>; 2] = [
+		Box::new(development_config(relay(), None, vec![], vec![], Code::Compressed)),
+		Box::new(local_testnet_config(relay())),
+	];
+	for spec in specs {
+		let extensions = Extensions::try_get(&*spec).unwrap();
+		assert_eq!((extensions.relay_chain.as_str(), extensions.para_id), ("rococo-local", 2000));
+		let json: serde_json::Value = serde_json::from_str(&spec.as_json(false).unwrap()).unwrap();
+		assert_eq!(
+			json["genesis"]["runtimeGenesis"]["patch"]["parachainInfo"]["parachainId"],
+			2000
+		);
+	}
+}
+
+#[test]
+fn runtime_code_is_compressed_by_default() {
+	use sc_chain_spec::ChainSpec;
+
+	let code = |code| {
+		let spec = development_config(Relay::PaseoLocal, None, vec![], vec![], code);
+		let json: serde_json::Value = serde_json::from_str(&spec.as_json(false).unwrap()).unwrap();
+		sp_core::bytes::from_hex(json["genesis"]["runtimeGenesis"]["code"].as_str().unwrap())
+			.unwrap()
+	};
+	let (compressed, uncompressed) = (code(Code::default()), code(Code::Uncompressed));
+	assert_eq!(compressed, pop_runtime_devnet::WASM_BINARY.unwrap());
+	assert!(!compressed.starts_with(b"\0asm"));
+	assert_eq!(uncompressed, pop_runtime_devnet::WASM_BINARY_BLOATY.unwrap());
+	assert!(uncompressed.starts_with(b"\0asm"));
+	assert!(compressed.len() < uncompressed.len());
+}
+
+#[test]
+fn minimal_test_spec_genesis_is_stable() {
+	use pop_runtime_common::Block;
+	use sc_chain_spec::ChainSpec;
+	use sp_runtime::{
+		traits::{BlakeTwo256, Block as BlockT, Hash},
+		BuildStorage, StateVersion,
+	};
+
+	fn genesis_hash() -> <Block as BlockT>::Hash {
+		let storage = minimal_test_spec().build_storage().unwrap();
+		assert!(storage.children_default.is_empty());
+		let state_root =
+			BlakeTwo256::trie_root(storage.top.into_iter().collect(), StateVersion::V1);
+		sc_chain_spec::construct_genesis_block::<Block>(state_root, StateVersion::V1).hash()
+	}
+
+	let json: serde_json::Value =
+		serde_json::from_str(&minimal_test_spec().as_json(false).unwrap()).unwrap();
+	assert!(json["genesis"]["runtimeGenesis"]["patch"].get("sudo").is_none());
+	assert_eq!(genesis_hash(), genesis_hash());
+}
+
+#[test]
+fn total_issuance_is_the_sum_of_genesis_balances() {
+	use frame_support::traits::{LockableCurrency, WithdrawReasons};
+	use pop_runtime_common::apis::runtime_decl_for_supply_api::SupplyApiV1;
+	use pop_runtime_devnet::{Block, Runtime};
+	use sc_chain_spec::ChainSpec;
+	use sp_runtime::BuildStorage;
+
+	let spec = minimal_test_spec();
+	let json: serde_json::Value = serde_json::from_str(&spec.as_json(false).unwrap()).unwrap();
+	let balances = json["genesis"]["runtimeGenesis"]["patch"]["balances"]["balances"]
+		.as_array()
+		.unwrap()
+		.iter()
+		.map(|balance| Balance::from(balance[1].as_u64().unwrap()))
+		.sum::<Balance>();
+
+	sp_io::TestExternalities::new(spec.build_storage().unwrap()).execute_with(|| {
+		let total_issuance = <Runtime as SupplyApiV1<Block, Balance>>::total_issuance;
+		let circulating_supply = <Runtime as SupplyApiV1<Block, Balance>>::circulating_supply;
+		assert_eq!(total_issuance(), balances);
+		assert_eq!(circulating_supply(), balances);
+
+		// Locked balances do not circulate.
+		let alice = get_account_id_from_seed::<sr25519::Public>("Alice");
+		pallet_balances::Pallet::<Runtime>::set_lock(
+			*b"testlock",
+			&alice,
+			100,
+			WithdrawReasons::all(),
+		);
+		assert_eq!(total_issuance(), balances);
+		assert_eq!(circulating_supply(), balances - 100);
+	});
+}
+
+#[test]
+fn deployment_metadata_extensions_are_optional() {
+	use sc_chain_spec::ChainSpec;
+
+	let spec = development_config(Relay::PaseoLocal, None, vec![], vec![], Code::Compressed);
+	let mut json: serde_json::Value = serde_json::from_str(&spec.as_json(false).unwrap()).unwrap();
+	// Specs without the metadata still parse.
+	assert!(json.get("environment").is_none());
+	let old = DevnetChainSpec::from_json_bytes(json.to_string().into_bytes()).unwrap();
+	let extensions = Extensions::try_get(&old).unwrap();
+	assert_eq!((&extensions.environment, &extensions.runtime_commit), (&None, &None));
+
+	json["environment"] = "staging".into();
+	json["runtimeCommit"] = "ae0778c".into();
+	let new = DevnetChainSpec::from_json_bytes(json.to_string().into_bytes()).unwrap();
+	let extensions = Extensions::try_get(&new).unwrap();
+	assert_eq!(extensions.environment.as_deref(), Some("staging"));
+	assert_eq!(extensions.runtime_commit.as_deref(), Some("ae0778c"));
+	// The metadata survives a round-trip.
+	let round_trip = DevnetChainSpec::from_json_bytes(new.as_json(false).unwrap().into_bytes());
+	assert_eq!(Extensions::try_get(&round_trip.unwrap()), Some(extensions));
+}
+
+#[test]
+fn token_properties_are_set_on_every_spec() {
+	use sc_chain_spec::ChainSpec;
+
+	let spec = development_config(Relay::Paseo, None, vec![], vec![], Code::Compressed);
+	let params = ChainSpecParams::from(Relay::Paseo);
+	assert_eq!(spec.properties(), params.properties());
+	assert_eq!(spec.protocol_id(), Some(DEFAULT_DEVNET_PROTOCOL_ID));
+	assert_eq!(spec.properties()["tokenSymbol"], "PAS");
+	assert_eq!(spec.properties()["ss58Format"], 0);
+
+	let params = ChainSpecParams::from(Relay::Polkadot);
+	assert_eq!((params.symbol, params.decimals, params.ss58, params.para_id), ("DOT", 10, 0, 3395));
+}
+
+#[test]
+fn contract_codes_are_read_once() {
+	let dir = std::env::temp_dir().join(format!("pop-contract-codes-{}", std::process::id()));
+	std::fs::create_dir_all(&dir).unwrap();
+	let (a, b, c) = (dir.join("a.wasm"), dir.join("b.wasm"), dir.join("c.wasm"));
+	std::fs::write(&a, [0, 1]).unwrap();
+	std::fs::write(&b, [2, 3]).unwrap();
+	std::fs::write(&c, [0, 1]).unwrap();
+
+	assert_eq!(read_contract_codes(&[a.clone(), b, c]).unwrap(), vec![vec![0, 1], vec![2, 3]]);
+	assert!(read_contract_codes(&[a, dir.join("missing.wasm")]).is_err());
+	std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn endowments_are_read_from_csv() {
+	let dir = std::env::temp_dir().join(format!("pop-endowments-{}", std::process::id()));
+	std::fs::create_dir_all(&dir).unwrap();
+	let alice = get_account_id_from_seed::<sr25519::Public>("Alice");
+	let bob = get_account_id_from_seed::<sr25519::Public>("Bob");
+	let csv = dir.join("endowments.csv");
+	std::fs::write(&csv, format!("address,amount\n{alice},100\n\n{bob}, 200 \n")).unwrap();
+	assert_eq!(endowments_from_csv(&csv).unwrap(), vec![(alice.clone(), 100), (bob.clone(), 200)]);
+
+	for (row, reason) in [
+		(bob.to_string(), "expected `address,amount`"),
+		("5Invalid,100".into(), "invalid address"),
+		(format!("{bob},1.5"), "invalid amount"),
+		(format!("{bob},-1"), "negative amount"),
+		(format!("{bob},0"), "zero amount"),
+		(format!("{alice},1"), "endowed twice"),
+	] {
+		std::fs::write(&csv, format!("address,amount\n{alice},100\n{row}\n{bob},200\n")).unwrap();
+		let error = endowments_from_csv(&csv).unwrap_err();
+		assert!(error.starts_with(&format!("{}:3: ", csv.display())), "{error}");
+		assert!(error.contains(reason), "{error}");
+	}
+	std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn dev_accounts_are_endowed_at_genesis() {
+	use sc_chain_spec::ChainSpec;
+
+	let dir = std::env::temp_dir().join(format!("pop-dev-endowments-{}", std::process::id()));
+	std::fs::create_dir_all(&dir).unwrap();
+	let alice = get_account_id_from_seed::<sr25519::Public>("Alice");
+	let ferdie = get_account_id_from_seed::<sr25519::Public>("Ferdie");
+	let other = AccountId::from([7; 32]);
+
+	let endowed = DevEndowments::default().endowed().unwrap();
+	assert_eq!(endowed.len(), 6);
+	assert_eq!(endowed[0], (alice.clone(), DEFAULT_DEV_ENDOWMENT));
+	assert_eq!(endowed[5], (ferdie, DEFAULT_DEV_ENDOWMENT));
+
+	let addresses = dir.join("addresses.txt");
+	std::fs::write(&addresses, format!("# Team\n{other}\n\n")).unwrap();
+	let endowments = DevEndowments { accounts: 1, amount: 500, addresses: Some(addresses.clone()) };
+	let endowed = endowments.endowed().unwrap();
+	assert_eq!(endowed, vec![(alice.clone(), 500), (other.clone(), 500)]);
+	// The sudo account owning the contract codes is not endowed twice.
+	let spec =
+		development_config(Relay::PaseoLocal, None, endowed, vec![vec![0, 1]], Code::Compressed);
+	let json: serde_json::Value = serde_json::from_str(&spec.as_json(false).unwrap()).unwrap();
+	let balances = &json["genesis"]["runtimeGenesis"]["patch"]["balances"]["balances"];
+	assert_eq!(balances, &serde_json::json!([[alice.clone(), 500], [other, 500]]));
+
+	assert!(DevEndowments { accounts: 13, ..Default::default() }.endowed().is_err());
+	assert!(DevEndowments { amount: 1, ..Default::default() }
+		.endowed()
+		.unwrap_err()
+		.contains("existential deposit"));
+	// Nothing is endowed, so any amount goes.
+	assert_eq!(DevEndowments { accounts: 0, amount: 0, addresses: None }.endowed(), Ok(vec![]));
+	std::fs::write(&addresses, format!("{alice}\n")).unwrap();
+	assert!(DevEndowments { addresses: Some(addresses.clone()), ..Default::default() }
+		.endowed()
+		.unwrap_err()
+		.contains("endowed twice"));
+	std::fs::write(&addresses, format!("{other}\n5Invalid\n")).unwrap();
+	let error = addresses_from_file(&addresses).unwrap_err();
+	assert!(error.starts_with(&format!("{}:2: invalid address", addresses.display())), "{error}");
+	std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn genesis_is_read_from_file() {
+	use pop_runtime_testnet::UNIT;
+	use sc_chain_spec::ChainSpec;
+	use sp_runtime::FixedU128;
+
+	let dir = std::env::temp_dir().join(format!("pop-genesis-config-{}", std::process::id()));
+	std::fs::create_dir_all(&dir).unwrap();
+	let alice = get_account_id_from_seed::<sr25519::Public>("Alice");
+	let bob = get_account_id_from_seed::<sr25519::Public>("Bob");
+	let aura = get_collator_keys_from_seed("Alice");
+	let patch = |path: &Path| {
+		let spec = from_genesis_file(path, Code::Compressed, None).unwrap();
+		assert_eq!(Extensions::try_get(&spec).unwrap().para_id, 4242);
+		let json: serde_json::Value = serde_json::from_str(&spec.as_json(false).unwrap()).unwrap();
+		json["genesis"]["runtimeGenesis"]["patch"].clone()
+	};
+
+	let toml = dir.join("genesis.toml");
+	std::fs::write(
+		&toml,
+		format!(
+			r#"
+relay_chain = "paseo"
+para_id = 4242
+sudo = "{alice}"
+council = ["{alice}", "{bob}"]
+candidacy_bond = 1000000000000
+desired_candidates = 3
+contract_uploaders = ["{bob}"]
+
+[[invulnerables]]
+account = "{alice}"
+keys = "{aura}"
+
+[[endowments]]
+account = "{bob}"
+balance = 100
+
+[[vesting]]
+account = "{bob}"
+locked = 60
+per_block = 20
+starting_block = 10
+
+[[assets]]
+id = 1
+owner = "{alice}"
+min_balance = 1
+name = "Tether"
+symbol = "USDT"
+decimals = 6
+fee_per_unit = 5000
+
+[[assets.holders]]
+account = "{bob}"
+balance = 10
+
+[collator_rewards]
+burn_percent = 10
+treasury_percent = 40
+block_reward = 1000000000
+"#
+		),
+	)
+	.unwrap();
+	let genesis = patch(&toml);
+	assert_eq!(genesis["parachainInfo"]["parachainId"], 4242);
+	assert_eq!(genesis["sudo"]["key"], serde_json::json!(alice));
+	assert_eq!(genesis["council"]["members"], serde_json::json!([alice, bob]));
+	assert_eq!(genesis["collatorSelection"]["invulnerables"], serde_json::json!([alice]));
+	assert_eq!(genesis["collatorSelection"]["candidacyBond"], 1_000_000_000_000u128);
+	assert_eq!(genesis["collatorSelection"]["desiredCandidates"], 3);
+	assert_eq!(
+		genesis["contractsUploadPolicy"],
+		serde_json::json!({ "policy": "Permissioned", "uploaders": [bob] })
+	);
+	assert_eq!(
+		genesis["session"]["keys"],
+		serde_json::json!([[alice, alice, pop_testnet_session_keys(aura.clone())]])
+	);
+	assert_eq!(genesis["balances"]["balances"], serde_json::json!([[bob, 100]]));
+	assert_eq!(genesis["vesting"]["vesting"], serde_json::json!([[bob, 10, 3, 40]]));
+	assert_eq!(
+		genesis["assets"],
+		serde_json::json!({
+			"assets": [[1, alice, false, 1]],
+			"metadata": [[1, b"Tether", b"USDT", 6]],
+			"accounts": [[1, bob, 10]],
+		})
+	);
+	// Fees are charged at 5,000 units of the asset per unit of native fees.
+	assert_eq!(
+		genesis["feeAssets"]["rates"],
+		serde_json::json!([[1, FixedU128::from_rational(5_000, UNIT).to_string()]])
+	);
+	assert_eq!(genesis["feeSplit"]["split"], serde_json::json!({ "burn": 10, "treasury": 40 }));
+	assert_eq!(genesis["collatorRewards"]["blockReward"], 1_000_000_000);
+
+	// The same genesis described as JSON.
+	let json = dir.join("genesis.json");
+	std::fs::write(
+		&json,
+		serde_json::json!({
+			"relay_chain": "paseo",
+			"para_id": 4242,
+			"sudo": alice,
+			"council": [alice, bob],
+			"candidacy_bond": 1_000_000_000_000u128,
+			"desired_candidates": 3,
+			"contract_uploaders": [bob],
+			"invulnerables": [{ "account": alice, "keys": aura }],
+			"endowments": [{ "account": bob, "balance": 100 }],
+			"vesting": [{ "account": bob, "locked": 60, "per_block": 20, "starting_block": 10 }],
+			"assets": [{
+				"id": 1,
+				"owner": alice,
+				"min_balance": 1,
+				"name": "Tether",
+				"symbol": "USDT",
+				"decimals": 6,
+				"holders": [{ "account": bob, "balance": 10 }],
+				"fee_per_unit": 5000,
+			}],
+			"collator_rewards": {
+				"burn_percent": 10,
+				"treasury_percent": 40,
+				"block_reward": 1_000_000_000,
+			},
+		})
+		.to_string(),
+	)
+	.unwrap();
+	assert_eq!(patch(&json), genesis);
+
+	let valid = serde_json::json!({
+		"relay_chain": "paseo",
+		"para_id": 1,
+		"sudo": alice,
+		"invulnerables": [{ "account": alice, "keys": aura }],
+	});
+	// The chain has no sudo unless specified.
+	let mut config = valid.clone();
+	config.as_object_mut().unwrap().remove("sudo");
+	std::fs::write(&json, config.to_string()).unwrap();
+	let spec = from_genesis_file(&json, Code::Compressed, None).unwrap();
+	let spec: serde_json::Value = serde_json::from_str(&spec.as_json(false).unwrap()).unwrap();
+	assert_eq!(spec["genesis"]["runtimeGenesis"]["patch"]["sudo"]["key"], serde_json::Value::Null);
+	// An asset with a minimum balance of `min_balance`, held by Bob with `held` if any.
+	let asset = |min_balance: Balance, held: Option<Balance>| {
+		let holders: Vec<_> = held
+			.into_iter()
+			.map(|balance| serde_json::json!({ "account": bob, "balance": balance }))
+			.collect();
+		serde_json::json!({
+			"id": 1,
+			"owner": alice,
+			"min_balance": min_balance,
+			"name": "Tether",
+			"symbol": "USDT",
+			"decimals": 6,
+			"holders": holders,
+		})
+	};
+	let mut uncharged = asset(1, None);
+	uncharged["fee_per_unit"] = serde_json::json!(0);
+	for (field, value, reason) in [
+		("relay_chain", serde_json::json!("kusama"), "unknown relay chain"),
+		("invulnerables", serde_json::json!([]), "no invulnerables"),
+		(
+			"endowments",
+			serde_json::json!([{ "account": bob, "balance": 1 }, { "account": bob, "balance": 2 }]),
+			"endowed twice",
+		),
+		("paused", serde_json::json!(true), "unknown field"),
+		("desired_candidates", serde_json::json!(101), "more than 100 desired candidates"),
+		("candidacy_bond", serde_json::json!(1), "below the existential deposit"),
+		("assets", serde_json::json!([asset(1, None), asset(1, None)]), "created twice"),
+		("assets", serde_json::json!([asset(0, None)]), "no minimum balance"),
+		("assets", serde_json::json!([asset(2, Some(1))]), "less than the minimum balance"),
+		("assets", serde_json::json!([asset(1, Some(1))]), "without being endowed"),
+		("assets", serde_json::json!([uncharged]), "charged no fees"),
+		(
+			"collator_rewards",
+			serde_json::json!({ "burn_percent": 60, "treasury_percent": 50 }),
+			"exceed 100%",
+		),
+	] {
+		let mut config = valid.clone();
+		config[field] = value;
+		std::fs::write(&json, config.to_string()).unwrap();
+		let error = from_genesis_file(&json, Code::Compressed, None).unwrap_err();
+		assert!(error.contains(reason), "{error}");
+	}
+	// Vesting schedules of Bob, endowed with 100.
+	for (vesting, reason) in [
+		(
+			serde_json::json!([{ "account": alice, "locked": 1, "per_block": 1 }]),
+			"without being endowed",
+		),
+		(
+			serde_json::json!([{ "account": bob, "locked": 101, "per_block": 1 }]),
+			"locks 101 out of 100",
+		),
+		(
+			serde_json::json!([{ "account": bob, "locked": 0, "per_block": 1 }]),
+			"locks 0 out of 100",
+		),
+		(serde_json::json!([{ "account": bob, "locked": 60, "per_block": 7 }]), "not a multiple"),
+		(serde_json::json!([{ "account": bob, "locked": 60, "per_block": 0 }]), "not a multiple"),
+		(
+			serde_json::json!([
+				{ "account": bob, "locked": 10, "per_block": 1 },
+				{ "account": bob, "locked": 20, "per_block": 1 },
+			]),
+			"vests twice",
+		),
+	] {
+		let mut config = valid.clone();
+		config["endowments"] = serde_json::json!([{ "account": bob, "balance": 100 }]);
+		config["vesting"] = vesting;
+		std::fs::write(&json, config.to_string()).unwrap();
+		let error = from_genesis_file(&json, Code::Compressed, None).unwrap_err();
+		assert!(error.contains(reason), "{error}");
+	}
+	assert!(from_genesis_file(&dir.join("missing.json"), Code::Compressed, None).is_err());
+	std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn contract_codes_are_uploaded_at_genesis() {
+	use sc_chain_spec::ChainSpec;
+
+	let spec =
+		development_config(Relay::PaseoLocal, None, vec![], vec![vec![0, 1]], Code::Compressed);
+	let json: serde_json::Value = serde_json::from_str(&spec.as_json(false).unwrap()).unwrap();
+	let patch = &json["genesis"]["runtimeGenesis"]["patch"];
+	assert_eq!(patch["contractsGenesis"]["codes"], serde_json::json!([[0, 1]]));
+	assert_eq!(patch["contractsGenesis"]["owner"], patch["sudo"]["key"]);
+	assert_eq!(patch["balances"]["balances"][0][0], patch["sudo"]["key"]);
+}
+
+#[test]
+fn sample_nft_collection_is_created_at_genesis() {
+	use codec::Decode;
+	use sp_core::{blake2_128, twox_128};
+	use sp_runtime::BuildStorage;
+
+	let spec = development_config(Relay::PaseoLocal, None, vec![], vec![], Code::Compressed);
+	let storage = spec.build_storage().unwrap();
+	// `pallet_nfts::Collection`, of the collection 0, whose details start with its owner.
+	let key = [
+		&twox_128(b"Nfts")[..],
+		&twox_128(b"Collection")[..],
+		&blake2_128(&0u32.to_le_bytes())[..],
+		&0u32.to_le_bytes()[..],
+	]
+	.concat();
+	let details = storage.top.get(&key).expect("the sample collection is created");
+	let owner = AccountId::decode(&mut &details[..]).unwrap();
+	assert_eq!(owner, get_account_id_from_seed::<sr25519::Public>("Alice"));
+}
+
+#[test]
+fn session_keys_hold_every_collator_key() {
+	use sp_core::crypto::KeyTypeId;
+	use sp_runtime::{traits::OpaqueKeys, RuntimeAppPublic};
+
+	/// The keys of a collator by key type. To be extended along with `CollatorKeys`.
+	fn by_key_type(keys: &CollatorKeys) -> Vec<(KeyTypeId, Vec<u8>)> {
+		vec![(AuraId::ID, RuntimeAppPublic::to_raw_vec(keys))]
+	}
+
+	fn check<Keys: FromCollatorKeys + OpaqueKeys>() {
+		let keys = get_collator_keys_from_seed("Alice");
+		let expected = by_key_type(&keys);
+		let session_keys = session_keys::<Keys>(keys);
+		// A session key added to the runtime without adding it to `CollatorKeys` is caught here.
+		assert_eq!(Keys::key_ids(), expected.iter().map(|(id, _)| *id).collect::<Vec<_>>());
+		for (id, key) in expected {
+			assert_eq!(session_keys.get_raw(id), &key[..]);
+		}
+	}
+	check::<pop_runtime_devnet::SessionKeys>();
+	check::<pop_runtime_testnet::SessionKeys>();
+	check::<pop_runtime_mainnet::SessionKeys>();
+}
+
+#[test]
+fn validator_ids_of_collators_are_their_accounts() {
+	use codec::Decode;
+	use sp_core::twox_128;
+	use sp_runtime::BuildStorage;
+
+	let params = ChainSpecParams::from(Relay::PaseoLocal);
+	let (extensions, para_id) = (params.extensions(), params.para_id);
+	let (account, validator) = (AccountId::from([1; 32]), AccountId::from([2; 32]));
+	let aura = get_collator_keys_from_seed("Alice");
+
+	// Keys registered for a validator id other than the account would never be used.
+	assert_eq!(
+		Invulnerable::try_from((account.clone(), validator.clone(), aura.clone())).err(),
+		Some(format!("validator id \"{validator}\" of \"{account}\" differs from its account"))
+	);
+	assert!(Invulnerable::try_from((account.clone(), account.clone(), aura.clone())).is_ok());
+
+	// The account is the validator id unless specified otherwise.
+	let invulnerable = Invulnerable::from((account.clone(), aura)).into();
+	let genesis = testnet_genesis(presets::GenesisParams::new(
+		vec![invulnerable],
+		Some(account.clone()),
+		para_id.into(),
+	));
+	assert_eq!(genesis["session"]["keys"][0][0], genesis["session"]["keys"][0][1]);
+
+	// The collator is a validator of the first session.
+	let spec = TestnetChainSpec::builder(pop_runtime_testnet::WASM_BINARY.unwrap(), extensions)
+		.with_genesis_config_patch(genesis)
+		.build();
+	let storage = spec.build_storage().unwrap();
+	// `pallet_session::Validators`.
+	let key = [twox_128(b"Session"), twox_128(b"Validators")].concat();
+	let validators = Vec::<AccountId>::decode(&mut &storage.top[&key][..]).unwrap();
+	assert_eq!(validators, vec![account]);
+}
+
+#[test]
+fn invulnerables_without_session_keys_are_rejected() {
+	let account = AccountId::from([1; 32]);
+	let invulnerable = (account.clone(), account.clone(), get_collator_keys_from_seed("Alice"));
+	let mut genesis = testnet_genesis(presets::GenesisParams::new(
+		vec![invulnerable],
+		Some(account.clone()),
+		ParaId::from(1000),
+	));
+	assert_eq!(ensure_session_keys(&genesis), Ok(()));
+
+	// An invulnerable is added without registering its session keys.
+	let other = AccountId::from([2; 32]);
+	genesis["collatorSelection"]["invulnerables"] = serde_json::json!([account, other]);
+	assert_eq!(
+		ensure_session_keys(&genesis),
+		Err(format!("invulnerable \"{other}\" has no session keys"))
+	);
+	assert!(std::panic::catch_unwind(|| check_session_keys(&genesis)).is_err());
+
+	// The keys of the invulnerable are registered for another validator id.
+	genesis["collatorSelection"]["invulnerables"] = serde_json::json!([account]);
+	genesis["session"]["keys"][0][1] = serde_json::json!(other);
+	assert_eq!(
+		ensure_session_keys(&genesis),
+		Err(format!("invulnerable \"{account}\" has no session keys"))
+	);
+}
+
+#[test]
+fn seeded_accounts_start_at_their_nonce() {
+	use pop_runtime_testnet::{Runtime, UNIT};
+	use sp_runtime::BuildStorage;
+
+	let params = ChainSpecParams::from(Relay::PaseoLocal);
+	let (extensions, para_id) = (params.extensions(), params.para_id);
+	let alice = get_account_id_from_seed::<sr25519::Public>("Alice");
+	let account = AccountId::from([1; 32]);
+	let mut spec = TestnetChainSpec::builder(pop_runtime_testnet::WASM_BINARY.unwrap(), extensions)
+		.with_genesis_config_patch(testnet_genesis(presets::GenesisParams {
+			endowed: vec![(account.clone(), UNIT)],
+			..presets::GenesisParams::new(
+				vec![(alice.clone(), alice.clone(), get_collator_keys_from_seed("Alice"))],
+				Some(alice),
+				para_id.into(),
+			)
+		}))
+		.build();
+	// Only endowed accounts can start at a nonce.
+	let other = AccountId::from([2; 32]);
+	assert_eq!(
+		write_raw_genesis(&mut spec.clone(), &[(other.clone(), 7)], None),
+		Err(format!("\"{other}\" starting at nonce 7 is not endowed"))
+	);
+	write_raw_genesis(&mut spec, &[(account.clone(), 7)], None).unwrap();
+
+	sp_io::TestExternalities::new(spec.build_storage().unwrap()).execute_with(|| {
+		assert_eq!(frame_system::Pallet::<Runtime>::account_nonce(&account), 7);
+		assert_eq!(pallet_balances::Pallet::<Runtime>::free_balance(&account), UNIT);
+	});
+
+	// The nonce must fit the `Nonce` of the runtime.
+	assert!(GenesisAccount::try_from((account, u64::from(Nonce::MAX) + 1, UNIT)).is_err());
+}
+
+#[test]
+fn pause_authority_is_set_at_genesis() {
+	use codec::Encode;
+	use sp_core::twox_128;
+	use sp_runtime::BuildStorage;
+
+	let params = ChainSpecParams::from(Relay::PaseoLocal);
+	let (extensions, para_id) = (params.extensions(), params.para_id);
+	let alice = get_account_id_from_seed::<sr25519::Public>("Alice");
+	let authority = AccountId::from([1; 32]);
+	let spec = TestnetChainSpec::builder(pop_runtime_testnet::WASM_BINARY.unwrap(), extensions)
+		.with_genesis_config_patch(testnet_genesis(presets::GenesisParams {
+			pause_authority: Some(authority.clone()),
+			..presets::GenesisParams::new(
+				vec![(alice.clone(), alice.clone(), get_collator_keys_from_seed("Alice"))],
+				Some(alice),
+				para_id.into(),
+			)
+		}))
+		.build();
+
+	// `pallet_maintenance_mode::PauseAuthority`.
+	let key = [twox_128(b"MaintenanceMode"), twox_128(b"PauseAuthority")].concat();
+	sp_io::TestExternalities::new(spec.build_storage().unwrap()).execute_with(|| {
+		assert_eq!(sp_io::storage::get(&key).as_deref(), Some(&authority.encode()[..]));
+	});
+}
+
+#[test]
+fn genesis_timestamp_is_only_set_if_given() {
+	let genesis = |genesis_timestamp| {
+		let spec = testnet_config(Relay::Paseo, Code::Compressed, genesis_timestamp).unwrap();
+		let json: serde_json::Value = serde_json::from_str(&spec.as_json(false).unwrap()).unwrap();
+		(json["genesis"].get("raw").is_some(), spec.build_storage().unwrap())
+	};
+	// `pallet_timestamp::Now`.
+	let key = storage_key("Timestamp", "Now");
+
+	let (raw, storage) = genesis(Some(1_700_000_000_000));
+	assert!(raw);
+	assert_eq!(storage.top.get(&key), Some(&1_700_000_000_000u64.to_le_bytes().to_vec()));
+
+	// The genesis is left unchanged otherwise.
+	let (raw, storage) = genesis(None);
+	assert!(!raw);
+	assert!(!storage.top.contains_key(&key));
+}
+
+#[test]
+fn live_testnet_has_no_sudo() {
+	let spec = testnet_config(Relay::Paseo, Code::Compressed, None).unwrap();
+	// `pallet_sudo::Key`.
+	assert!(!spec.build_storage().unwrap().top.contains_key(&storage_key("Sudo", "Key")));
+}
+
+#[test]
+fn testnet_genesis_presets_build() {
+	use sp_runtime::BuildStorage;
+
+	for name in presets::preset_names() {
+		let name: &str = (&name).try_into().unwrap();
+		let spec = TestnetChainSpec::builder(
+			pop_runtime_testnet::WASM_BINARY.unwrap(),
+			Extensions {
+				relay_chain: "paseo-local".into(),
+				para_id: presets::PARA_ID,
+				environment: None,
+				runtime_commit: None,
+			},
+		)
+		.with_genesis_config_preset_name(name)
+		.build();
+		assert!(spec.build_storage().is_ok(), "{name}");
+	}
+}
+
+#[test]
+fn safe_xcm_version_is_set_at_genesis() {
+	use sc_chain_spec::ChainSpec;
+
+	for spec in [
+		development_config(Relay::PaseoLocal, None, vec![], vec![], Code::Compressed),
+		testnet_config(Relay::Paseo, Code::Compressed, None).unwrap(),
+		local_testnet_config(Relay::PaseoLocal),
+		mainnet_config(Relay::Polkadot, vec![], Code::Compressed),
+	] {
+		let json: serde_json::Value = serde_json::from_str(&spec.as_json(false).unwrap()).unwrap();
+		let polkadot_xcm = &json["genesis"]["runtimeGenesis"]["patch"]["polkadotXcm"];
+		assert_eq!(polkadot_xcm["safeXcmVersion"], SAFE_XCM_VERSION, "{}", spec.id());
+	}
 }
 
 #[test]
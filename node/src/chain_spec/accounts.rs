@@ -0,0 +1,151 @@
+//! Deterministic development accounts: the well-known accounts of substrate, Alice to Ferdie along
+//! with their stash accounts, and numbered accounts like `//collator/0` for topologies with more
+//! collators or users than there are well-known accounts, e.g. large zombienet networks.
+//!
+//! The accounts are derived from public seeds and therefore only fit for development and tests.
+
+use pop_runtime_common::{AccountId, AuraId, Signature};
+use serde::Serialize;
+use sp_core::{hexdisplay::HexDisplay, sr25519, Pair, Public};
+use sp_runtime::traits::{IdentifyAccount, Verify};
+
+use super::CollatorKeys;
+
+type AccountPublic = <Signature as Verify>::Signer;
+
+/// The seeds of the well-known development accounts, in order.
+pub const WELL_KNOWN: [&str; 12] = [
+	"Alice",
+	"Bob",
+	"Charlie",
+	"Dave",
+	"Eve",
+	"Ferdie",
+	"Alice//stash",
+	"Bob//stash",
+	"Charlie//stash",
+	"Dave//stash",
+	"Eve//stash",
+	"Ferdie//stash",
+];
+
+/// Helper function to generate a crypto pair from seed
+pub fn get_from_seed<TPublic: Public>(seed: &str) -> <TPublic::Pair as Pair>::Public {
+	TPublic::Pair::from_string(&format!("//{}", seed), None)
+		.expect("static values are valid; qed")
+		.public()
+}
+
+/// Generate collator keys from seed.
+pub fn get_collator_keys_from_seed(seed: &str) -> CollatorKeys {
+	get_from_seed::<AuraId>(seed)
+}
+
+/// Helper function to generate an account ID from seed
+pub fn get_account_id_from_seed<TPublic: Public>(seed: &str) -> AccountId
+where
+	AccountPublic: From<<TPublic::Pair as Pair>::Public>,
+{
+	AccountPublic::from(get_from_seed::<TPublic>(seed)).into_account()
+}
+
+/// A development account along with the keys it collates with, as listed in the manifest read by
+/// test tooling.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DevAccount {
+	/// The seed the account is derived from, e.g. `Alice` or `collator/0`.
+	pub seed: String,
+	/// The secret URI of the account and its keys, e.g. `//Alice`.
+	pub suri: String,
+	/// The account, as SS58 address.
+	pub account_id: AccountId,
+	/// The public key of the account, hex encoded.
+	pub public_key: String,
+	/// The aura key the account collates with, as SS58 address.
+	pub aura: CollatorKeys,
+}
+
+impl DevAccount {
+	/// Derive the account and collator keys from `seed`, as `//<seed>`.
+	pub fn from_seed(seed: &str) -> Self {
+		let account_id = get_account_id_from_seed::<sr25519::Public>(seed);
+		let public: &[u8; 32] = account_id.as_ref();
+		Self {
+			seed: seed.into(),
+			suri: format!("//{seed}"),
+			public_key: format!("0x{}", HexDisplay::from(public)),
+			account_id,
+			aura: get_collator_keys_from_seed(seed),
+		}
+	}
+}
+
+/// The first `count` well-known development accounts, failing if there are fewer.
+pub fn well_known(count: usize) -> Result<Vec<DevAccount>, String> {
+	if count > WELL_KNOWN.len() {
+		return Err(format!(
+			"Cannot derive {count} well-known development accounts, there are {}",
+			WELL_KNOWN.len()
+		));
+	}
+	Ok(WELL_KNOWN[..count].iter().map(|seed| DevAccount::from_seed(seed)).collect())
+}
+
+/// The `count` accounts numbered under `name`, derived from `//<name>/0` to
+/// `//<name>/<count - 1>`, e.g. `//collator/0` for the first collator.
+pub fn numbered(name: &str, count: u32) -> Vec<DevAccount> {
+	(0..count)
+		.map(|index| DevAccount::from_seed(&format!("{name}/{index}")))
+		.collect()
+}
+
+/// The JSON manifest of `accounts`, listing the secret URI, account and keys of each account for
+/// test tooling to sign with.
+pub fn manifest(accounts: &[DevAccount]) -> serde_json::Value {
+	serde_json::json!({ "accounts": accounts })
+}
+
+#[cfg(test)]
+mod tests {
+	use sp_core::crypto::Ss58Codec;
+
+	use super::*;
+
+	#[test]
+	fn well_known_accounts_are_derived_in_order() {
+		let accounts = well_known(WELL_KNOWN.len()).unwrap();
+		assert_eq!(accounts[0].account_id, get_account_id_from_seed::<sr25519::Public>("Alice"));
+		assert_eq!(accounts[5].seed, "Ferdie");
+		assert_eq!(accounts[6].suri, "//Alice//stash");
+		assert_eq!(well_known(2).unwrap(), accounts[..2]);
+		assert!(well_known(WELL_KNOWN.len() + 1).is_err());
+	}
+
+	#[test]
+	fn numbered_accounts_are_deterministic() {
+		let collators = numbered("collator", 3);
+		assert_eq!(collators.len(), 3);
+		assert_eq!(collators[2].suri, "//collator/2");
+		assert_eq!(collators, numbered("collator", 3));
+		assert_eq!(numbered("collator", 2), collators[..2]);
+		// The keys of each account differ, as do those of another name.
+		assert_ne!(collators[0].aura, collators[1].aura);
+		assert_ne!(numbered("user", 1)[0].account_id, collators[0].account_id);
+		// The keys are those derived from the secret URI.
+		let pair = sr25519::Pair::from_string("//collator/1", None).unwrap();
+		assert_eq!(collators[1].aura.to_ss58check(), pair.public().to_ss58check());
+	}
+
+	#[test]
+	fn manifest_lists_the_keys_of_each_account() {
+		let alice = DevAccount::from_seed("Alice");
+		let manifest = manifest(&[alice.clone()]);
+		let entry = &manifest["accounts"][0];
+		assert_eq!(entry["seed"], "Alice");
+		assert_eq!(entry["suri"], "//Alice");
+		assert_eq!(entry["accountId"], alice.account_id.to_ss58check());
+		assert_eq!(entry["aura"], alice.aura.to_ss58check());
+		assert_eq!(entry["publicKey"].as_str().unwrap().len(), 2 + 64);
+	}
+}
@@ -1,10 +1,21 @@
-use std::path::PathBuf;
+use std::{
+	net::{IpAddr, Ipv4Addr},
+	path::PathBuf,
+};
+
+use pop_runtime_common::BlockNumber;
+use sc_cli::{Database, DatabasePruningMode, SyncMode};
+use sc_service::{config::RpcMethods, ChainType};
+use sp_core::{
+	crypto::{ByteArray, Ss58Codec},
+	sr25519,
+};
 
 /// Sub-commands supported by the collator.
 #[derive(Debug, clap::Subcommand)]
 pub enum Subcommand {
 	/// Build a chain specification.
-	BuildSpec(sc_cli::BuildSpecCmd),
+	BuildSpec(crate::commands::BuildSpecCmd),
 
 	/// Validate blocks.
 	CheckBlock(sc_cli::CheckBlockCmd),
@@ -18,6 +29,9 @@ pub enum Subcommand {
 	/// Import blocks.
 	ImportBlocks(sc_cli::ImportBlocksCmd),
 
+	/// Measure the throughput of importing blocks exported by `export-blocks`.
+	BenchmarkImport(crate::commands::BenchmarkImportCmd),
+
 	/// Revert the chain to a previous state.
 	Revert(sc_cli::RevertCmd),
 
@@ -38,21 +52,77 @@ pub enum Subcommand {
 	#[command(subcommand)]
 	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
 
-	/// Key management CLI utilities
+	/// Try-runtime has migrated to a standalone CLI
+	/// (<https://github.com/paritytech/try-runtime-cli>), which runs `on-runtime-upgrade` and
+	/// `follow-chain` against the RPC of a node. The subcommand exists as a pointer to it.
+	TryRuntime,
+
+	/// Key management CLI utilities, e.g. `key generate-session-keys` to generate the session keys
+	/// of a collator into its keystore, or `key insert` to insert a known key.
+	#[command(subcommand)]
+	Key(crate::commands::KeySubcommand),
+
+	/// Print a human-readable summary of a chain specification.
+	InspectSpec(crate::commands::InspectSpecCmd),
+
+	/// Print the differences between two chain specifications, flagging dangerous changes.
+	DiffSpec(crate::commands::DiffSpecCmd),
+
+	/// Replace the sudo key in the genesis of a chain specification, e.g. to control a fork.
+	RekeySudo(crate::commands::RekeySudoCmd),
+
+	/// Print a checksum of a chain specification file, to verify it is the one distributed.
+	SpecChecksum(crate::commands::SpecChecksumCmd),
+
+	/// Derive a node key from a seed and print its peer id.
+	GenerateNodeKey(crate::commands::GenerateNodeKeyCmd),
+
+	/// Check that the node is configured to collate, before launching it.
+	CheckCollator(crate::commands::CheckCollatorCmd),
+
+	/// Diagnose the environment and configuration of the node, before launching it.
+	Doctor(crate::commands::DoctorCmd),
+
+	/// Check that the PoV of a block is within the maximum PoV size of the relay chain.
+	CheckPov(crate::commands::CheckPovCmd),
+
+	/// Re-execute a range of stored blocks, checking that each produces its recorded state root.
+	VerifyReexecution(crate::commands::VerifyReexecutionCmd),
+
+	/// Export the balances of all accounts at a given block as CSV, e.g. for airdrops.
+	ExportBalances(crate::commands::ExportBalancesCmd),
+
+	/// Fork the state of pallets of a live chain into a raw development chain specification, with
+	/// a development account as sudo key and collator, e.g. to reproduce bugs with the real state.
+	ForkOff(crate::commands::ForkOffCmd),
+
+	/// Generate the node key, session keys, account and systemd unit of a new collator.
+	CollatorBundle(crate::commands::CollatorBundleCmd),
+
+	/// Runtime upgrade utilities, e.g. `upgrade submit` to enact an upgrade authorized by
+	/// governance, once checked.
 	#[command(subcommand)]
-	Key(sc_cli::KeySubcommand),
+	Upgrade(crate::commands::UpgradeSubcommand),
 }
 
 const AFTER_HELP_EXAMPLE: &str = color_print::cstr!(
 	r#"<bold><underline>Examples:</></>
    <bold>pop-node build-spec --disable-default-bootnode > plain-parachain-chainspec.json</>
            Export a chainspec for a local testnet in json format.
+   <bold>pop-node build-spec --chain testnet --raw --patch patch.json > raw-chainspec.json</>
+           Export the raw chainspec of the testnet, with the genesis patch of patch.json merged over its genesis.
    <bold>pop-node --chain plain-parachain-chainspec.json --tmp -- --chain rococo-local</>
            Launch a full node with chain specification loaded from plain-parachain-chainspec.json.
    <bold>pop-node</>
            Launch a full node with default parachain <italic>local-testnet</> and relay chain <italic>rococo-local</>.
    <bold>pop-node --collator</>
            Launch a collator with default parachain <italic>local-testnet</> and relay chain <italic>rococo-local</>.
+   <bold>pop-node --chain testnet --collator --sync warp</>
+           Launch a collator which warp syncs to the head included by the relay chain before collating, its embedded relay chain node warp syncing as well.
+   <bold>pop-node upgrade submit --chain testnet --wasm pop_runtime_testnet.compact.compressed.wasm</>
+           Enact the runtime upgrade authorized by governance via the node at http://127.0.0.1:9944, once checked against its authorization, its version and by try-runtime.
+   <bold>pop-node fork-off --chain testnet --rpc-url http://127.0.0.1:9944 --output fork.json</>
+           Fork the accounts, balances, assets and contracts of the testnet into a development chain with Alice as sudo and collator.
  "#
 );
 #[derive(Debug, clap::Parser)]
@@ -79,11 +149,620 @@ pub struct Cli {
 	#[arg(long)]
 	pub no_hardware_benchmarks: bool,
 
+	/// The number of seconds the node is given to shut down cleanly after receiving SIGTERM or
+	/// SIGINT, at most 60, after which the process exits with code 124.
+	///
+	/// Container orchestrators should be configured with a longer kill timeout, so that the
+	/// database is never interrupted while being flushed.
+	#[arg(
+		long,
+		value_name = "SECONDS",
+		default_value_t = crate::shutdown::DEFAULT_GRACE_SECS,
+		value_parser = clap::value_parser!(u64).range(1..=crate::shutdown::MAX_GRACE_SECS as i64),
+	)]
+	pub shutdown_grace_secs: u64,
+
+	/// The number of milliseconds a collator is given to build a block, at most half of a relay
+	/// chain slot.
+	///
+	/// A longer duration fits more transactions, e.g. contract calls, into a block. Setting it too
+	/// high risks missing slots, as the block may then not be imported and submitted in time.
+	#[arg(
+		long,
+		value_name = "MILLISECONDS",
+		default_value_t = crate::service::DEFAULT_BLOCK_PROPOSAL_DURATION_MS,
+		value_parser = clap::value_parser!(u64)
+			.range(1..=crate::service::MAX_BLOCK_PROPOSAL_DURATION_MS),
+	)]
+	pub max_block_proposal_duration_ms: u64,
+
+	/// The number of relay chain blocks without any parachain block being included after which a
+	/// warning is logged, and `pop_block_production_stalled` is set, as the parachain is then
+	/// likely no longer backed by the relay chain validators.
+	#[arg(
+		long,
+		value_name = "RELAY_BLOCKS",
+		default_value_t = crate::block_production::DEFAULT_MAX_BLOCK_LAG,
+		value_parser = clap::value_parser!(u32).range(1..),
+	)]
+	pub max_block_lag: u32,
+
+	/// The number of block announcements validated at once, at most 256. Unbounded by default,
+	/// i.e. bounded only by the 256 validations `sc-network-sync` runs at once.
+	///
+	/// A lower concurrency caps the CPU spent validating announcements, e.g. under heavy peer
+	/// churn, at the cost of latency: the other announcements wait for a validation to complete,
+	/// and once 256 are pending the new ones are dropped, so that blocks may be imported later.
+	#[arg(
+		long,
+		value_name = "COUNT",
+		value_parser = clap::value_parser!(u32).range(1..=crate::block_announce::MAX_CONCURRENCY as i64),
+	)]
+	pub block_announce_validation_concurrency: Option<u32>,
+
+	/// Override the network protocol id of the built-in development chain specification.
+	///
+	/// Nodes only discover and peer with nodes using the same protocol id, which keeps separate
+	/// local deployments from peering with each other. The other chain specifications are
+	/// rejected along with it, as their protocol id is the one they specify.
+	#[arg(long, value_name = "PROTOCOL_ID", value_parser = parse_protocol_id)]
+	pub protocol_id: Option<String>,
+
+	/// The id of the parachain on the relay chain of the built-in development and local chain
+	/// specifications, e.g. to launch several parachains from the same node in zombienet tests.
+	#[arg(long, value_name = "ID")]
+	pub para_id: Option<u32>,
+
+	/// The id of the local relay chain of the built-in development and local chain
+	/// specifications, e.g. `rococo-local` or `westend-local`. Defaults to `paseo-local`.
+	#[arg(long, value_name = "CHAIN")]
+	pub relay_chain: Option<String>,
+
+	/// The type of keystore to use.
+	///
+	/// An in-memory keystore only holds keys for the lifetime of the process and therefore cannot
+	/// be used by collators of live chains.
+	#[arg(long, value_enum, default_value_t = KeystoreType::File)]
+	pub keystore_type: KeystoreType,
+
+	/// An http endpoint to which `{ block_number, block_hash }` is posted as json for every
+	/// finalized block, e.g. to notify an external indexer.
+	///
+	/// Notifications are retried a few times and then dropped, so an unreachable endpoint never
+	/// affects the node.
+	#[arg(long, value_name = "URL", value_parser = crate::finality_webhook::parse_url)]
+	pub finality_webhook: Option<hyper::Uri>,
+
+	/// A file of newline-delimited bootnodes, which are added to those of the chain spec and
+	/// `--bootnodes`. Invalid lines are skipped with a warning.
+	#[arg(long, value_name = "PATH")]
+	pub bootnodes_file: Option<PathBuf>,
+
+	/// Expose the `pop_contractTrace` RPC, which dry-runs a contract call and returns the debug
+	/// messages emitted during its execution, along with the unsafe `pop_traceCall` and
+	/// `dev_traceContractCall` RPCs tracing the frames of a call, its events and storage deposits.
+	///
+	/// Only available on development and local chains.
+	#[arg(long)]
+	pub enable_contracts_debug: bool,
+
+	/// Record the time spent in each host function called by the runtime and log a summary of it
+	/// when the node shuts down, e.g. to prioritize runtime optimizations. Replaces the profiling
+	/// of `--tracing-targets`.
+	///
+	/// Only available on development and local chains.
+	#[arg(long, conflicts_with = "tracing_targets")]
+	pub profile_wasm: bool,
+
+	/// Author a block every given number of milliseconds with `--dev`, empty or not, rather than
+	/// as soon as a transaction is imported, e.g. for timing-sensitive tests independent of the
+	/// slot duration of the runtime.
+	///
+	/// Only available with `--dev`, on development and local chains.
+	#[arg(long, value_name = "MILLISECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+	pub dev_block_time: Option<u64>,
+
+	/// The format of the logs of the collation pipeline: the collation attempts
+	/// (`pop::collation`), the calls to the relay chain interface (`pop::relay-chain`) and the
+	/// processing of the inbound XCM queue (`pop::xcm-queue`). As `json`, they are also written
+	/// to stdout as one json object per line, along with the timings of their spans, e.g. for log
+	/// aggregation. `--detailed-log-output` includes their debug logs and those of the upstream
+	/// collator. Replaces the profiling of `--tracing-targets`.
+	#[arg(
+		long,
+		value_enum,
+		default_value_t = LogFormat::Text,
+		conflicts_with_all = ["tracing_targets", "profile_wasm"],
+	)]
+	pub log_format: LogFormat,
+
+	/// The maximum number of full node peers, which `--in-peers` and `--out-peers` combined must
+	/// not exceed. Useful to keep networking predictable on small, isolated testnets.
+	#[arg(long, value_name = "COUNT")]
+	pub max_peers: Option<u32>,
+
+	/// The number of well-known development accounts to endow at genesis of the built-in
+	/// development chain specification: Alice, Bob, Charlie, Dave, Eve and Ferdie, followed by
+	/// their stash accounts.
+	#[arg(
+		long,
+		value_name = "COUNT",
+		default_value_t = crate::chain_spec::DEFAULT_DEV_ACCOUNTS,
+		value_parser = clap::value_parser!(u8).range(0..=12),
+	)]
+	pub endow_accounts: u8,
+
+	/// The balance each account endowed at genesis of the built-in development chain
+	/// specification is endowed with, in the smallest unit of the native token.
+	#[arg(long, value_name = "AMOUNT", default_value_t = crate::chain_spec::DEFAULT_DEV_ENDOWMENT)]
+	pub endowment: u128,
+
+	/// A file of SS58 addresses, one per line, to endow at genesis of the built-in development
+	/// chain specification in addition to the development accounts, e.g. the accounts of a team.
+	/// Empty lines and lines starting with `#` are skipped.
+	#[arg(long, value_name = "PATH")]
+	pub endow_addresses: Option<PathBuf>,
+
+	/// A contract code (wasm blob) to upload at genesis of the built-in development chain, so that
+	/// contracts can be instantiated by code hash from the first block. Can be repeated; identical
+	/// codes are only uploaded once.
+	#[arg(long, value_name = "PATH")]
+	pub genesis_contract_code: Vec<PathBuf>,
+
+	/// A CSV file of `address,amount` rows, following a header, of the accounts to endow at
+	/// genesis of the built-in mainnet chain specification, e.g. the launch token distribution.
+	/// Amounts are in the smallest unit of the native token.
+	#[arg(long, value_name = "PATH")]
+	pub genesis_endowments: Option<PathBuf>,
+
+	/// A JSON or TOML file describing the invulnerables, endowments, sudo account and para id of
+	/// the built-in testnet chain specification, e.g. to cut a release specification, replacing
+	/// its built-in genesis.
+	#[arg(long, value_name = "PATH")]
+	pub genesis_config: Option<PathBuf>,
+
+	/// The timestamp of genesis of the built-in testnet chain specification, in milliseconds since
+	/// the unix epoch, e.g. to build byte-identical genesis states. Left unset otherwise.
+	///
+	/// The timestamp of the first block must not be earlier, so it must not be later than the
+	/// launch of the chain. The timestamp is written into the genesis storage, so the genesis of
+	/// the chain specification is raw.
+	#[arg(long, value_name = "MILLISECONDS")]
+	pub genesis_timestamp: Option<u64>,
+
+	/// Embed the uncompressed runtime in the genesis of the built-in chain specifications, e.g. to
+	/// debug the runtime with its symbol names.
+	///
+	/// The compressed runtime is embedded otherwise, as the uncompressed one is typically several
+	/// times larger, and so is the genesis embedding it. Chain specification files are loaded as
+	/// is.
+	#[arg(long)]
+	pub uncompressed_runtime: bool,
+
+	/// A file to which the current values of the prometheus metrics are written periodically, in
+	/// the prometheus text exposition format, e.g. for nodes which cannot be reached by a
+	/// prometheus scraper.
+	///
+	/// Nothing is written if prometheus is disabled.
+	#[arg(long, value_name = "PATH")]
+	pub metrics_snapshot_file: Option<PathBuf>,
+
+	/// The interval between snapshots written to `--metrics-snapshot-file`.
+	#[arg(
+		long,
+		value_name = "SECONDS",
+		default_value_t = crate::metrics_snapshot::DEFAULT_INTERVAL_SECS,
+		value_parser = clap::value_parser!(u64).range(1..),
+	)]
+	pub metrics_snapshot_interval_secs: u64,
+
+	/// Comma-separated numbers of blocks to keep, along with their state, regardless of the
+	/// pruning mode, e.g. to investigate an incident.
+	///
+	/// Blocks are pinned once finalized, for as long as the node is running. The state retained
+	/// by a pinned block grows towards a full copy of the state as of that block, so only a few
+	/// blocks should be pinned at a time.
+	#[arg(long, value_name = "NUMBERS", value_delimiter = ',')]
+	pub pin_blocks: Vec<BlockNumber>,
+
+	/// Use a temporary base path derived from `KEY`, which unlike `--tmp` is reused by every run
+	/// given the same key, e.g. by the steps of an integration test sharing state.
+	///
+	/// The directory is kept across runs; its databases are only removed by `purge-chain`, given
+	/// the same key.
+	#[arg(
+		long,
+		value_name = "KEY",
+		value_parser = parse_tmp_reuse_key,
+		conflicts_with_all = ["tmp", "base_path"],
+	)]
+	pub tmp_reuse: Option<String>,
+
+	/// The aura public key to author with, as SS58 address or hex, when the keystore holds
+	/// several aura keys, e.g. backup keys of a failover setup.
+	///
+	/// The node never authors with any other aura key of the keystore, and fails to start if the
+	/// keystore does not hold this one.
+	#[arg(long, value_name = "PUBLIC_KEY", value_parser = parse_collator_account)]
+	pub collator_account: Option<sr25519::Public>,
+
+	/// Run the collator as a hot standby of the other collators: the node runs the collation
+	/// machinery but only authors once no parachain block was backed for
+	/// `--standby-takeover-after` relay chain blocks, and stands by again once a block of another
+	/// collator is backed.
+	///
+	/// The standby needs the aura key of a collator of the active set, e.g. of the collator it
+	/// backs up, to author once it takes over.
+	#[arg(long)]
+	pub collator_standby: bool,
+
+	/// The number of relay chain blocks without any parachain block being backed after which a
+	/// `--collator-standby` collator takes over authoring.
+	#[arg(
+		long,
+		value_name = "RELAY_BLOCKS",
+		default_value_t = crate::standby::DEFAULT_TAKEOVER_AFTER,
+		value_parser = clap::value_parser!(u32).range(1..),
+	)]
+	pub standby_takeover_after: u32,
+
+	/// Generate new session keys into the keystore on start, as `author_rotateKeys` does, logging
+	/// the public keys to register via `session.setKeys`.
+	///
+	/// New keys are generated on every start, each set taking effect once registered. Use
+	/// `key generate-session-keys` instead to generate the keys once, ahead of deployment.
+	#[arg(long, conflicts_with = "rpc_only")]
+	pub rotate_keys_on_start: bool,
+
+	/// Run as a full node serving RPC queries only, which never authors blocks nor takes part in
+	/// consensus, e.g. for a fleet of RPC nodes separate from the collators.
+	///
+	/// The node runs with an empty in-memory keystore, so it holds no keys even if its base path
+	/// has a keystore.
+	#[arg(long)]
+	pub rpc_only: bool,
+
+	/// Acknowledge that unsafe RPC methods, e.g. `author_insertKey`, are exposed to anyone able to
+	/// connect, which the node refuses on live chains otherwise.
+	///
+	/// Unsafe RPC methods are exposed publicly by `--rpc-methods unsafe` along with
+	/// `--rpc-external` or `--unsafe-rpc-external`.
+	#[arg(long)]
+	pub i_know_this_is_unsafe: bool,
+
 	/// Relay chain arguments
 	#[arg(raw = true)]
 	pub relay_chain_args: Vec<String>,
 }
 
+/// The type of keystore used by the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum KeystoreType {
+	/// Keys are stored on disk, at `--keystore-path` if provided.
+	File,
+	/// Keys are kept in memory and lost on restart.
+	Memory,
+}
+
+/// The format of the logs of the collation pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum LogFormat {
+	/// Free-form text, as the other logs.
+	Text,
+	/// Json lines on stdout, besides the text logs.
+	Json,
+}
+
+impl Cli {
+	/// The build of the runtimes to embed in the genesis of the built-in chain specifications.
+	pub fn runtime_code(&self) -> crate::chain_spec::Code {
+		if self.uncompressed_runtime {
+			crate::chain_spec::Code::Uncompressed
+		} else {
+			crate::chain_spec::Code::Compressed
+		}
+	}
+
+	/// The accounts to endow at genesis of the built-in development chain specification, as set by
+	/// `--endow-accounts`, `--endowment` and `--endow-addresses`.
+	pub fn dev_endowments(&self) -> crate::chain_spec::DevEndowments {
+		crate::chain_spec::DevEndowments {
+			accounts: self.endow_accounts,
+			amount: self.endowment,
+			addresses: self.endow_addresses.clone(),
+		}
+	}
+
+	/// The local relay chain of the built-in development and local chain specifications, as
+	/// overridden by `--relay-chain` and `--para-id`.
+	pub(crate) fn local_relay(&self) -> crate::chain_spec::Relay {
+		use crate::chain_spec::{Relay, PASEO_PARA_ID};
+
+		match (&self.relay_chain, self.para_id) {
+			(None, None) => Relay::PaseoLocal,
+			(relay_chain, para_id) => Relay::Local {
+				relay_chain: relay_chain.clone().unwrap_or_else(|| "paseo-local".into()),
+				para_id: para_id.unwrap_or(PASEO_PARA_ID),
+			},
+		}
+	}
+
+	/// Ensure the requested keystore type can be used for a chain of the given type.
+	pub fn validate_keystore_type(&self, chain_type: &ChainType) -> Result<(), String> {
+		if self.keystore_type != KeystoreType::Memory {
+			return Ok(());
+		}
+		if self.run.base.keystore_params.keystore_path.is_some() {
+			return Err("`--keystore-path` cannot be used with an in-memory keystore".into());
+		}
+		let collator = self.run.collator || self.run.base.validator;
+		if collator && *chain_type == ChainType::Live {
+			return Err("collators of live chains cannot use an in-memory keystore, as their keys \
+			            would be lost on restart"
+				.into());
+		}
+		Ok(())
+	}
+
+	/// Whether the node runs with an in-memory keystore, as requested via `--keystore-type` or
+	/// implied by `--rpc-only`.
+	pub fn in_memory_keystore(&self) -> bool {
+		self.keystore_type == KeystoreType::Memory || self.rpc_only
+	}
+
+	/// Ensure `--rpc-only` is not combined with options to author blocks.
+	pub fn validate_rpc_only(&self) -> Result<(), String> {
+		if !self.rpc_only {
+			return Ok(());
+		}
+		if self.run.collator ||
+			self.run.base.validator ||
+			self.collator_account.is_some() ||
+			self.collator_standby
+		{
+			return Err("`--rpc-only` cannot be used with `--collator`, `--validator`, \
+			            `--collator-account` or `--collator-standby`, as RPC nodes never author \
+			            blocks"
+				.into());
+		}
+		Ok(())
+	}
+
+	/// The number of relay chain blocks without a block being backed after which the collator
+	/// takes over authoring, if it runs as `--collator-standby`.
+	pub fn collator_standby(&self) -> Option<u32> {
+		self.collator_standby.then_some(self.standby_takeover_after)
+	}
+
+	/// Ensure the transaction pool limits of `--pool-limit` and `--pool-kbytes` are not zero, which
+	/// would reject every transaction.
+	///
+	/// The pool is the single state pool, as the fork-aware one is not available in this version
+	/// of the node.
+	pub fn validate_pool_limits(&self) -> Result<(), String> {
+		let pool = &self.run.base.pool_config;
+		if pool.pool_limit == 0 || pool.pool_kbytes == 0 {
+			return Err("`--pool-limit` and `--pool-kbytes` must not be zero, as the transaction \
+			            pool would reject every transaction"
+				.into());
+		}
+		Ok(())
+	}
+
+	/// Ensure `--sync fast` and `--sync warp`, which import the state of a recent block instead of
+	/// executing every block since genesis, are not combined with an archive node, which keeps the
+	/// state of every block.
+	pub fn validate_sync_mode(&self) -> Result<(), String> {
+		let run = &self.run.base;
+		if matches!(run.network_params.sync, SyncMode::Full) {
+			return Ok(());
+		}
+		if matches!(
+			run.import_params.pruning_params.state_pruning,
+			Some(DatabasePruningMode::Archive | DatabasePruningMode::ArchiveCanonical)
+		) {
+			return Err("`--sync fast` and `--sync warp` cannot be used with `--state-pruning \
+			            archive`, as an archive node executes every block since genesis, use \
+			            `--sync full` instead"
+				.into());
+		}
+		Ok(())
+	}
+
+	/// Whether the relay chain arguments set the sync mode of the embedded relay chain node.
+	pub fn relay_chain_sync_is_set(&self) -> bool {
+		self.relay_chain_args
+			.iter()
+			.any(|arg| arg == "--sync" || arg.starts_with("--sync="))
+	}
+
+	/// Ensure the inbound and outbound peer limits do not exceed `--max-peers`.
+	pub fn validate_peers(&self) -> Result<(), String> {
+		let Some(max_peers) = self.max_peers else {
+			return Ok(());
+		};
+		let network = &self.run.base.network_params;
+		let peers = network.in_peers.saturating_add(network.out_peers);
+		if peers > max_peers {
+			return Err(format!(
+				"`--in-peers` ({}) and `--out-peers` ({}) exceed `--max-peers` ({max_peers}), \
+				 lower them accordingly",
+				network.in_peers, network.out_peers
+			));
+		}
+		Ok(())
+	}
+
+	/// Ensure unsafe RPC methods are only exposed on a public interface if forced via
+	/// `--unsafe-rpc-external`.
+	pub fn validate_rpc_methods(&self) -> Result<(), String> {
+		let run = &self.run.base;
+		if run.unsafe_rpc_external {
+			return Ok(());
+		}
+		let interface =
+			if run.rpc_external { Ipv4Addr::UNSPECIFIED } else { Ipv4Addr::LOCALHOST }.into();
+		if !denies_unsafe_rpc(run.rpc_methods.into(), interface) {
+			return Err("`--rpc-methods unsafe` cannot be used with `--rpc-external`, use \
+			            `--unsafe-rpc-external` to expose unsafe RPC methods publicly anyway"
+				.into());
+		}
+		Ok(())
+	}
+
+	/// Whether unsafe RPC methods are exposed on a public interface of a chain of the given type,
+	/// which is only the case for live chains if acknowledged via `--i-know-this-is-unsafe`.
+	pub fn exposes_unsafe_rpc(&self, chain_type: &ChainType) -> bool {
+		let run = &self.run.base;
+		*chain_type == ChainType::Live &&
+			(run.rpc_external || run.unsafe_rpc_external) &&
+			matches!(run.rpc_methods, sc_cli::RpcMethods::Unsafe)
+	}
+
+	/// Ensure unsafe RPC methods are only exposed on a public interface of a live chain if
+	/// acknowledged via `--i-know-this-is-unsafe`.
+	pub fn validate_unsafe_rpc_exposure(&self, chain_type: &ChainType) -> Result<(), String> {
+		if self.exposes_unsafe_rpc(chain_type) && !self.i_know_this_is_unsafe {
+			return Err("unsafe RPC methods cannot be exposed publicly on a live chain, pass \
+			            `--i-know-this-is-unsafe` to expose them anyway"
+				.into());
+		}
+		Ok(())
+	}
+
+	/// The time a collator is given to build a block.
+	pub fn block_proposal_duration(&self) -> std::time::Duration {
+		std::time::Duration::from_millis(self.max_block_proposal_duration_ms)
+	}
+
+	/// Where and how often to write snapshots of the metrics, if requested.
+	pub fn metrics_snapshots(&self) -> Option<crate::metrics_snapshot::Snapshots> {
+		self.metrics_snapshot_file
+			.clone()
+			.map(|path| crate::metrics_snapshot::Snapshots {
+				path,
+				interval: std::time::Duration::from_secs(self.metrics_snapshot_interval_secs),
+			})
+	}
+
+	/// Default `--database` to `auto` for the node and the sub-commands opening its database, so
+	/// that an existing database is opened regardless of its backend and new databases use
+	/// ParityDb.
+	pub fn default_database(&mut self) {
+		let params = match &mut self.subcommand {
+			None => &mut self.run.base.import_params.database_params,
+			Some(Subcommand::CheckBlock(cmd)) => &mut cmd.import_params.database_params,
+			Some(Subcommand::ImportBlocks(cmd)) => &mut cmd.import_params.database_params,
+			Some(Subcommand::ExportBlocks(cmd)) => &mut cmd.database_params,
+			Some(Subcommand::ExportState(cmd)) => &mut cmd.database_params,
+			Some(Subcommand::Revert(cmd)) => &mut cmd.database_params,
+			Some(Subcommand::ForkOff(cmd)) => &mut cmd.database_params,
+			// Purging requires the actual backend, as the path of an `auto` database is unknown.
+			_ => return,
+		};
+		params.database.get_or_insert(Database::Auto);
+	}
+
+	/// Resolve the base path of the node and of `purge-chain` to the directory derived from
+	/// `--tmp-reuse`, if given.
+	pub fn resolve_tmp_reuse(&mut self) {
+		let Some(key) = &self.tmp_reuse else {
+			return;
+		};
+		let base_path = tmp_reuse_path(key);
+		let shared_params = match &mut self.subcommand {
+			None => &mut self.run.base.shared_params,
+			Some(Subcommand::PurgeChain(cmd)) => &mut cmd.base.shared_params,
+			_ => return,
+		};
+		shared_params.base_path.get_or_insert(base_path);
+	}
+
+	/// Ensure contract debugging is only enabled for chains which are not live.
+	pub fn validate_contracts_debug(&self, chain_type: &ChainType) -> Result<(), String> {
+		if self.enable_contracts_debug && *chain_type == ChainType::Live {
+			return Err("`--enable-contracts-debug` cannot be used on live chains".into());
+		}
+		Ok(())
+	}
+
+	/// Ensure a block time is only set for development nodes of chains which are not live, as
+	/// collators author blocks on the slots of the relay chain instead.
+	pub fn validate_dev_block_time(&self, chain_type: &ChainType) -> Result<(), String> {
+		if self.dev_block_time.is_none() {
+			return Ok(());
+		}
+		if !self.run.base.shared_params.dev {
+			return Err("`--dev-block-time` can only be used with `--dev`".into());
+		}
+		if *chain_type == ChainType::Live {
+			return Err("`--dev-block-time` cannot be used on live chains".into());
+		}
+		Ok(())
+	}
+
+	/// Ensure host functions are only profiled on chains which are not live.
+	pub fn validate_profile_wasm(&self, chain_type: &ChainType) -> Result<(), String> {
+		if self.profile_wasm && *chain_type == ChainType::Live {
+			return Err("`--profile-wasm` cannot be used on live chains".into());
+		}
+		Ok(())
+	}
+}
+
+/// Whether the RPC server denies unsafe methods to connections on `interface` given
+/// `--rpc-methods`. Unless specified otherwise, unsafe methods are only allowed on loopback
+/// interfaces.
+pub fn denies_unsafe_rpc(methods: RpcMethods, interface: IpAddr) -> bool {
+	match methods {
+		RpcMethods::Auto => !interface.is_loopback(),
+		RpcMethods::Safe => true,
+		RpcMethods::Unsafe => false,
+	}
+}
+
+/// Parse a protocol id, which must be a non-empty ASCII string.
+fn parse_protocol_id(protocol_id: &str) -> Result<String, String> {
+	if protocol_id.is_empty() {
+		return Err("protocol id cannot be empty".into());
+	}
+	if !protocol_id.is_ascii() {
+		return Err("protocol id must be ascii".into());
+	}
+	Ok(protocol_id.into())
+}
+
+/// Parse an sr25519 public key, given as SS58 address or hex.
+fn parse_collator_account(public: &str) -> Result<sr25519::Public, String> {
+	match public.strip_prefix("0x") {
+		Some(hex) => {
+			let bytes = hex
+				.parse::<sp_core::Bytes>()
+				.map_err(|e| format!("invalid hex public key: {e}"))?;
+			sr25519::Public::from_slice(&bytes).map_err(|_| "public key must be 32 bytes".into())
+		},
+		None => sr25519::Public::from_ss58check(public)
+			.map_err(|e| format!("invalid SS58 public key: {e:?}")),
+	}
+}
+
+/// Parse a `--tmp-reuse` key, which must be a non-empty string of ASCII alphanumerics, `-` and
+/// `_` so that it can be used as a directory name.
+fn parse_tmp_reuse_key(key: &str) -> Result<String, String> {
+	if key.is_empty() {
+		return Err("key cannot be empty".into());
+	}
+	if !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+		return Err("key must only contain ascii alphanumerics, `-` and `_`".into());
+	}
+	Ok(key.into())
+}
+
+/// The temporary base path used for the `--tmp-reuse` key `key`.
+fn tmp_reuse_path(key: &str) -> PathBuf {
+	std::env::temp_dir().join(format!("pop-node-{key}"))
+}
+
 #[derive(Debug)]
 pub struct RelayChainCli {
 	/// The actual relay chain cli object.
@@ -112,3 +791,604 @@ impl RelayChainCli {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use clap::Parser;
+
+	use super::*;
+
+	#[test]
+	fn shutdown_grace_secs_parsing() {
+		let cli = Cli::try_parse_from(["pop-node"]).unwrap();
+		assert_eq!(cli.shutdown_grace_secs, crate::shutdown::DEFAULT_GRACE_SECS);
+		let cli = Cli::try_parse_from(["pop-node", "--shutdown-grace-secs", "10"]).unwrap();
+		assert_eq!(cli.shutdown_grace_secs, 10);
+		let cli = Cli::try_parse_from(["pop-node", "--shutdown-grace-secs", "60"]).unwrap();
+		assert_eq!(cli.shutdown_grace_secs, crate::shutdown::MAX_GRACE_SECS);
+		for invalid in ["0", "61", "-1", "soon"] {
+			assert!(Cli::try_parse_from(["pop-node", "--shutdown-grace-secs", invalid]).is_err());
+		}
+	}
+
+	#[test]
+	fn max_block_lag_parsing() {
+		let cli = Cli::try_parse_from(["pop-node"]).unwrap();
+		assert_eq!(cli.max_block_lag, crate::block_production::DEFAULT_MAX_BLOCK_LAG);
+		let cli = Cli::try_parse_from(["pop-node", "--max-block-lag", "30"]).unwrap();
+		assert_eq!(cli.max_block_lag, 30);
+		for invalid in ["0", "-1", "never"] {
+			assert!(Cli::try_parse_from(["pop-node", "--max-block-lag", invalid]).is_err());
+		}
+	}
+
+	#[test]
+	fn block_announce_validation_concurrency_parsing() {
+		let cli = Cli::try_parse_from(["pop-node"]).unwrap();
+		assert_eq!(cli.block_announce_validation_concurrency, None);
+		for (arg, concurrency) in [("1", 1), ("16", 16), ("256", 256)] {
+			let cli =
+				Cli::try_parse_from(["pop-node", "--block-announce-validation-concurrency", arg])
+					.unwrap();
+			assert_eq!(cli.block_announce_validation_concurrency, Some(concurrency));
+		}
+		for invalid in ["0", "257", "-1", "many"] {
+			assert!(Cli::try_parse_from([
+				"pop-node",
+				"--block-announce-validation-concurrency",
+				invalid
+			])
+			.is_err());
+		}
+	}
+
+	#[test]
+	fn collator_standby_parsing() {
+		let cli = Cli::try_parse_from(["pop-node"]).unwrap();
+		assert_eq!(cli.collator_standby(), None);
+		let cli = Cli::try_parse_from(["pop-node", "--collator-standby"]).unwrap();
+		assert_eq!(cli.collator_standby(), Some(crate::standby::DEFAULT_TAKEOVER_AFTER));
+		let cli = Cli::try_parse_from([
+			"pop-node",
+			"--collator-standby",
+			"--standby-takeover-after",
+			"3",
+		])
+		.unwrap();
+		assert_eq!(cli.collator_standby(), Some(3));
+		for invalid in ["0", "-1", "never"] {
+			assert!(Cli::try_parse_from(["pop-node", "--standby-takeover-after", invalid]).is_err());
+		}
+		let cli = Cli::try_parse_from(["pop-node", "--rpc-only", "--collator-standby"]).unwrap();
+		assert!(cli.validate_rpc_only().is_err());
+	}
+
+	#[test]
+	fn max_block_proposal_duration_ms_parsing() {
+		use std::time::Duration;
+
+		let cli = Cli::try_parse_from(["pop-node"]).unwrap();
+		assert_eq!(cli.block_proposal_duration(), Duration::from_secs(2));
+		let cli =
+			Cli::try_parse_from(["pop-node", "--max-block-proposal-duration-ms", "2500"]).unwrap();
+		assert_eq!(cli.block_proposal_duration(), Duration::from_millis(2500));
+		for invalid in ["0", "3001", "-1", "long"] {
+			assert!(Cli::try_parse_from(["pop-node", "--max-block-proposal-duration-ms", invalid])
+				.is_err());
+		}
+	}
+
+	#[test]
+	fn wasm_execution_parsing() {
+		use sc_cli::WasmExecutionMethod;
+
+		let wasm_method = |args: &[&str]| {
+			Cli::try_parse_from(["pop-node"].iter().chain(args))
+				.map(|cli| cli.run.base.import_params.wasm_method)
+		};
+		assert_eq!(wasm_method(&[]).unwrap(), WasmExecutionMethod::Compiled);
+		assert_eq!(
+			wasm_method(&["--wasm-execution", "compiled"]).unwrap(),
+			WasmExecutionMethod::Compiled
+		);
+		assert_eq!(
+			wasm_method(&["--wasm-execution", "interpreted-i-know-what-i-do"]).unwrap(),
+			WasmExecutionMethod::Interpreted
+		);
+		assert!(wasm_method(&["--wasm-execution", "jit"]).is_err());
+	}
+
+	#[test]
+	fn relay_chain_rpc_urls_parsing() {
+		use cumulus_client_cli::RelayChainMode;
+
+		let cli = Cli::try_parse_from(["pop-node"]).unwrap();
+		assert!(matches!(cli.run.collator_options().relay_chain_mode, RelayChainMode::Embedded));
+
+		let cli = Cli::try_parse_from([
+			"pop-node",
+			"--relay-chain-rpc-urls",
+			"ws://relay-0:9944",
+			"wss://relay-1:443",
+		])
+		.unwrap();
+		let RelayChainMode::ExternalRpc(urls) = cli.run.collator_options().relay_chain_mode else {
+			panic!("expected the relay chain to be accessed via rpc");
+		};
+		assert_eq!(
+			urls.iter().map(|url| url.as_str()).collect::<Vec<_>>(),
+			["ws://relay-0:9944/", "wss://relay-1/"]
+		);
+		assert!(Cli::try_parse_from(["pop-node", "--relay-chain-rpc-urls", "http://relay:9944"])
+			.is_err());
+	}
+
+	#[test]
+	fn relay_chain_light_client_parsing() {
+		use cumulus_client_cli::RelayChainMode;
+
+		let cli = Cli::try_parse_from(["pop-node", "--relay-chain-light-client"]).unwrap();
+		assert!(matches!(cli.run.collator_options().relay_chain_mode, RelayChainMode::LightClient));
+		assert!(Cli::try_parse_from([
+			"pop-node",
+			"--relay-chain-light-client",
+			"--relay-chain-rpc-urls",
+			"ws://relay:9944",
+		])
+		.is_err());
+		// Only full nodes are supported.
+		assert!(
+			Cli::try_parse_from(["pop-node", "--relay-chain-light-client", "--collator"]).is_err()
+		);
+	}
+
+	#[test]
+	fn max_runtime_instances_parsing() {
+		use sc_cli::CliConfiguration;
+
+		let cli = Cli::try_parse_from(["pop-node", "--max-runtime-instances", "32"]).unwrap();
+		assert_eq!(cli.run.normalize().max_runtime_instances().unwrap(), Some(32));
+		assert!(Cli::try_parse_from(["pop-node", "--max-runtime-instances", "33"]).is_err());
+	}
+
+	#[test]
+	fn keystore_type_parsing() {
+		let cli = Cli::try_parse_from(["pop-node", "--collator"]).unwrap();
+		assert_eq!(cli.keystore_type, KeystoreType::File);
+		assert!(cli.validate_keystore_type(&ChainType::Live).is_ok());
+
+		let cli = Cli::try_parse_from(["pop-node", "--keystore-type", "memory"]).unwrap();
+		assert!(cli.validate_keystore_type(&ChainType::Live).is_ok());
+
+		let cli =
+			Cli::try_parse_from(["pop-node", "--collator", "--keystore-type", "memory"]).unwrap();
+		assert!(cli.validate_keystore_type(&ChainType::Development).is_ok());
+		assert!(cli.validate_keystore_type(&ChainType::Live).is_err());
+
+		let cli = Cli::try_parse_from([
+			"pop-node",
+			"--keystore-type",
+			"memory",
+			"--keystore-path",
+			"/tmp/keystore",
+		])
+		.unwrap();
+		assert!(cli.validate_keystore_type(&ChainType::Development).is_err());
+
+		assert!(Cli::try_parse_from(["pop-node", "--keystore-type", "remote"]).is_err());
+	}
+
+	#[test]
+	fn rpc_only_parsing() {
+		let cli = Cli::try_parse_from(["pop-node"]).unwrap();
+		assert!(!cli.in_memory_keystore());
+
+		let cli = Cli::try_parse_from(["pop-node", "--rpc-only"]).unwrap();
+		assert!(cli.validate_rpc_only().is_ok());
+		// Starts without any keys, even on live chains.
+		assert!(cli.in_memory_keystore());
+		assert!(cli.validate_keystore_type(&ChainType::Live).is_ok());
+
+		for args in
+			[["pop-node", "--rpc-only", "--collator"], ["pop-node", "--rpc-only", "--validator"]]
+		{
+			let cli = Cli::try_parse_from(args).unwrap();
+			assert!(cli.validate_rpc_only().is_err(), "{args:?}");
+		}
+		let cli = Cli::try_parse_from([
+			"pop-node",
+			"--rpc-only",
+			"--collator-account",
+			"5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNmXhbzSzSs8xnAFi",
+		])
+		.unwrap();
+		assert!(cli.validate_rpc_only().is_err());
+	}
+
+	#[test]
+	fn uncompressed_runtime_parsing() {
+		use crate::chain_spec::Code;
+
+		let cli = Cli::try_parse_from(["pop-node"]).unwrap();
+		assert_eq!(cli.runtime_code(), Code::Compressed);
+		let cli = Cli::try_parse_from(["pop-node", "--uncompressed-runtime"]).unwrap();
+		assert_eq!(cli.runtime_code(), Code::Uncompressed);
+	}
+
+	#[test]
+	fn contracts_debug_parsing() {
+		let cli = Cli::try_parse_from(["pop-node"]).unwrap();
+		assert!(!cli.enable_contracts_debug);
+		assert!(cli.validate_contracts_debug(&ChainType::Live).is_ok());
+
+		let cli = Cli::try_parse_from(["pop-node", "--enable-contracts-debug"]).unwrap();
+		assert!(cli.enable_contracts_debug);
+		assert!(cli.validate_contracts_debug(&ChainType::Development).is_ok());
+		assert!(cli.validate_contracts_debug(&ChainType::Local).is_ok());
+		assert!(cli.validate_contracts_debug(&ChainType::Live).is_err());
+	}
+
+	#[test]
+	fn profile_wasm_parsing() {
+		let cli = Cli::try_parse_from(["pop-node"]).unwrap();
+		assert!(!cli.profile_wasm);
+		assert!(cli.validate_profile_wasm(&ChainType::Live).is_ok());
+
+		let cli = Cli::try_parse_from(["pop-node", "--profile-wasm"]).unwrap();
+		assert!(cli.profile_wasm);
+		assert!(cli.validate_profile_wasm(&ChainType::Local).is_ok());
+		assert!(cli.validate_profile_wasm(&ChainType::Live).is_err());
+		assert!(Cli::try_parse_from(["pop-node", "--profile-wasm", "--tracing-targets", "sp_io"])
+			.is_err());
+	}
+
+	#[test]
+	fn dev_block_time_parsing() {
+		let cli = Cli::try_parse_from(["pop-node"]).unwrap();
+		assert_eq!(cli.dev_block_time, None);
+		assert!(cli.validate_dev_block_time(&ChainType::Live).is_ok());
+
+		let cli = Cli::try_parse_from(["pop-node", "--dev", "--dev-block-time", "500"]).unwrap();
+		assert_eq!(cli.dev_block_time, Some(500));
+		assert!(cli.validate_dev_block_time(&ChainType::Development).is_ok());
+		assert!(cli.validate_dev_block_time(&ChainType::Local).is_ok());
+		assert!(cli.validate_dev_block_time(&ChainType::Live).is_err());
+		// Collators author blocks on the slots of the relay chain.
+		let cli = Cli::try_parse_from(["pop-node", "--dev-block-time", "500"]).unwrap();
+		assert!(cli.validate_dev_block_time(&ChainType::Development).is_err());
+		assert!(Cli::try_parse_from(["pop-node", "--dev", "--dev-block-time", "0"]).is_err());
+	}
+
+	#[test]
+	fn log_format_parsing() {
+		let cli = Cli::try_parse_from(["pop-node"]).unwrap();
+		assert_eq!(cli.log_format, LogFormat::Text);
+		let cli =
+			Cli::try_parse_from(["pop-node", "--log-format", "json", "--detailed-log-output"])
+				.unwrap();
+		assert_eq!(cli.log_format, LogFormat::Json);
+		assert!(cli.run.base.shared_params.detailed_log_output);
+		assert!(Cli::try_parse_from(["pop-node", "--log-format", "xml"]).is_err());
+		assert!(
+			Cli::try_parse_from(["pop-node", "--log-format", "json", "--profile-wasm"]).is_err()
+		);
+	}
+
+	#[test]
+	fn unsafe_rpc_methods_are_denied_on_public_interfaces_by_default() {
+		let (local, public) = (Ipv4Addr::LOCALHOST.into(), Ipv4Addr::UNSPECIFIED.into());
+		assert!(!denies_unsafe_rpc(RpcMethods::Auto, local));
+		assert!(denies_unsafe_rpc(RpcMethods::Auto, public));
+		assert!(denies_unsafe_rpc(RpcMethods::Safe, local));
+		assert!(!denies_unsafe_rpc(RpcMethods::Unsafe, public));
+	}
+
+	#[test]
+	fn rpc_methods_validation() {
+		for args in [
+			&["pop-node", "--rpc-external"][..],
+			&["pop-node", "--rpc-methods", "unsafe"],
+			&["pop-node", "--unsafe-rpc-external", "--rpc-methods", "unsafe"],
+		] {
+			let cli = Cli::try_parse_from(args).unwrap();
+			assert!(cli.validate_rpc_methods().is_ok(), "{args:?}");
+		}
+		let cli =
+			Cli::try_parse_from(["pop-node", "--rpc-external", "--rpc-methods", "unsafe"]).unwrap();
+		assert!(cli.validate_rpc_methods().is_err());
+	}
+
+	#[test]
+	fn unsafe_rpc_exposure_on_live_chains_is_refused() {
+		let args = ["pop-node", "--unsafe-rpc-external", "--rpc-methods", "unsafe"];
+		let cli = Cli::try_parse_from(args).unwrap();
+		assert!(cli.exposes_unsafe_rpc(&ChainType::Live));
+		assert!(cli.validate_unsafe_rpc_exposure(&ChainType::Live).is_err());
+		for chain_type in [ChainType::Development, ChainType::Local] {
+			assert!(!cli.exposes_unsafe_rpc(&chain_type));
+			assert!(cli.validate_unsafe_rpc_exposure(&chain_type).is_ok());
+		}
+
+		let cli = Cli::try_parse_from([&args[..], &["--i-know-this-is-unsafe"]].concat()).unwrap();
+		assert!(cli.validate_unsafe_rpc_exposure(&ChainType::Live).is_ok());
+		let cli = Cli::try_parse_from(["pop-node", "--unsafe-rpc-external"]).unwrap();
+		assert!(cli.validate_unsafe_rpc_exposure(&ChainType::Live).is_ok());
+	}
+
+	#[test]
+	fn metrics_snapshots_parsing() {
+		let cli = Cli::try_parse_from(["pop-node"]).unwrap();
+		assert!(cli.metrics_snapshots().is_none());
+
+		let cli = Cli::try_parse_from([
+			"pop-node",
+			"--metrics-snapshot-file",
+			"/tmp/metrics.prom",
+			"--metrics-snapshot-interval-secs",
+			"5",
+		])
+		.unwrap();
+		let snapshots = cli.metrics_snapshots().unwrap();
+		assert_eq!(snapshots.path, PathBuf::from("/tmp/metrics.prom"));
+		assert_eq!(snapshots.interval, std::time::Duration::from_secs(5));
+		assert!(Cli::try_parse_from(["pop-node", "--metrics-snapshot-interval-secs", "0"]).is_err());
+	}
+
+	#[test]
+	fn pin_blocks_parsing() {
+		assert!(Cli::try_parse_from(["pop-node"]).unwrap().pin_blocks.is_empty());
+
+		let cli = Cli::try_parse_from(["pop-node", "--pin-blocks", "10,2,10"]).unwrap();
+		assert_eq!(cli.pin_blocks, vec![10, 2, 10]);
+		assert!(Cli::try_parse_from(["pop-node", "--pin-blocks", "1,x"]).is_err());
+	}
+
+	#[test]
+	fn local_relay_is_overridden() {
+		use crate::chain_spec::Relay;
+
+		let local_relay = |args: &[&str]| {
+			Cli::try_parse_from(["pop-node"].iter().chain(args)).unwrap().local_relay()
+		};
+		assert!(matches!(local_relay(&[]), Relay::PaseoLocal));
+		assert!(matches!(
+			local_relay(&["--relay-chain", "rococo-local", "--para-id", "2000"]),
+			Relay::Local { relay_chain, para_id: 2000 } if relay_chain == "rococo-local"
+		));
+		assert!(matches!(
+			local_relay(&["--para-id", "2000"]),
+			Relay::Local { relay_chain, para_id: 2000 } if relay_chain == "paseo-local"
+		));
+		assert!(matches!(
+			local_relay(&["--relay-chain", "westend-local"]),
+			Relay::Local { relay_chain, para_id: 4001 } if relay_chain == "westend-local"
+		));
+	}
+
+	#[test]
+	fn genesis_timestamp_parsing() {
+		assert_eq!(Cli::try_parse_from(["pop-node"]).unwrap().genesis_timestamp, None);
+
+		let cli =
+			Cli::try_parse_from(["pop-node", "--genesis-timestamp", "1700000000000"]).unwrap();
+		assert_eq!(cli.genesis_timestamp, Some(1_700_000_000_000));
+		assert!(Cli::try_parse_from(["pop-node", "--genesis-timestamp", "-1"]).is_err());
+	}
+
+	#[test]
+	fn database_parsing() {
+		let database = |args: &[&str]| {
+			let mut cli = Cli::try_parse_from([&["pop-node"], args].concat()).unwrap();
+			cli.default_database();
+			cli.run.base.import_params.database_params.database
+		};
+		assert_eq!(database(&[]), Some(Database::Auto));
+		assert_eq!(database(&["--database", "auto"]), Some(Database::Auto));
+		assert_eq!(database(&["--database", "paritydb"]), Some(Database::ParityDb));
+		assert_eq!(database(&["--database", "rocksdb"]), Some(Database::RocksDb));
+		assert!(Cli::try_parse_from(["pop-node", "--database", "sleddb"]).is_err());
+	}
+
+	#[test]
+	fn collator_account_parsing() {
+		use sp_core::Pair;
+
+		let alice = sr25519::Pair::from_string("//Alice", None).unwrap().public();
+		let collator_account = |public: &str| {
+			Cli::try_parse_from(["pop-node", "--collator-account", public])
+				.map(|cli| cli.collator_account)
+		};
+		assert_eq!(collator_account(&alice.to_ss58check()).unwrap(), Some(alice));
+		let hex = format!("0x{}", sp_core::hexdisplay::HexDisplay::from(&alice.0));
+		assert_eq!(collator_account(&hex).unwrap(), Some(alice));
+		for invalid in ["alice", "0x01", "0xzz"] {
+			assert!(collator_account(invalid).is_err());
+		}
+	}
+
+	#[test]
+	fn key_subcommands_parsing() {
+		use crate::commands::KeySubcommand;
+
+		let key = |args: &[&str]| match Cli::try_parse_from([&["pop-node", "key"], args].concat())
+			.unwrap()
+			.subcommand
+		{
+			Some(Subcommand::Key(cmd)) => cmd,
+			_ => panic!("`key` is expected to parse as the key subcommand"),
+		};
+		assert!(matches!(
+			key(&["generate-session-keys", "--chain", "testnet-local"]),
+			KeySubcommand::GenerateSessionKeys(cmd) if cmd.chain_id() == "testnet-local"
+		));
+		assert!(matches!(
+			key(&["insert", "--key-type", "aura", "--scheme", "sr25519", "--suri", "//Alice"]),
+			KeySubcommand::Substrate(sc_cli::KeySubcommand::Insert(_))
+		));
+
+		assert!(
+			Cli::try_parse_from(["pop-node", "--rotate-keys-on-start"])
+				.unwrap()
+				.rotate_keys_on_start
+		);
+		assert!(Cli::try_parse_from(["pop-node", "--rotate-keys-on-start", "--rpc-only"]).is_err());
+	}
+
+	#[test]
+	fn tmp_reuse_parsing() {
+		let base_path = |args: &[&str]| {
+			let mut cli = Cli::try_parse_from([&["pop-node"], args].concat()).unwrap();
+			cli.resolve_tmp_reuse();
+			cli.run.base.shared_params.base_path
+		};
+		assert_eq!(base_path(&[]), None);
+		let session = base_path(&["--tmp-reuse", "session-1"]);
+		assert_eq!(session, Some(std::env::temp_dir().join("pop-node-session-1")));
+		assert_eq!(base_path(&["--tmp-reuse", "session-1"]), session);
+		assert_ne!(base_path(&["--tmp-reuse", "session-2"]), session);
+
+		let mut cli =
+			Cli::try_parse_from(["pop-node", "--tmp-reuse", "session-1", "purge-chain"]).unwrap();
+		cli.resolve_tmp_reuse();
+		let Some(Subcommand::PurgeChain(cmd)) = cli.subcommand else {
+			panic!("expected the purge-chain sub-command");
+		};
+		assert_eq!(cmd.base.shared_params.base_path, session);
+
+		for invalid in ["", "../session", "a/b"] {
+			assert!(Cli::try_parse_from(["pop-node", "--tmp-reuse", invalid]).is_err());
+		}
+		assert!(Cli::try_parse_from(["pop-node", "--tmp-reuse", "a", "--tmp"]).is_err());
+		assert!(Cli::try_parse_from(["pop-node", "--tmp-reuse", "a", "--base-path", "/a"]).is_err());
+	}
+
+	#[test]
+	fn peers_validation() {
+		let cli = Cli::try_parse_from(["pop-node"]).unwrap();
+		assert!(cli.validate_peers().is_ok());
+
+		let cli = Cli::try_parse_from(["pop-node", "--in-peers", "2", "--out-peers", "2"]).unwrap();
+		assert!(cli.validate_peers().is_ok());
+
+		let cli = Cli::try_parse_from([
+			"pop-node",
+			"--in-peers",
+			"2",
+			"--out-peers",
+			"2",
+			"--max-peers",
+			"4",
+		])
+		.unwrap();
+		assert!(cli.validate_peers().is_ok());
+
+		let cli = Cli::try_parse_from([
+			"pop-node",
+			"--in-peers",
+			"3",
+			"--out-peers",
+			"2",
+			"--max-peers",
+			"4",
+		])
+		.unwrap();
+		assert!(cli.validate_peers().is_err());
+
+		// The default peer limits exceed a small maximum.
+		let cli = Cli::try_parse_from(["pop-node", "--max-peers", "4"]).unwrap();
+		assert!(cli.validate_peers().is_err());
+	}
+
+	#[test]
+	fn sync_mode_validation() {
+		for args in [
+			&["pop-node"][..],
+			&["pop-node", "--sync", "warp"],
+			&["pop-node", "--sync", "fast", "--state-pruning", "256"],
+			&["pop-node", "--state-pruning", "archive"],
+		] {
+			assert!(Cli::try_parse_from(args).unwrap().validate_sync_mode().is_ok());
+		}
+		for args in [
+			["pop-node", "--sync", "warp", "--state-pruning", "archive"],
+			["pop-node", "--sync", "fast", "--state-pruning", "archive-canonical"],
+		] {
+			assert!(Cli::try_parse_from(args).unwrap().validate_sync_mode().is_err());
+		}
+
+		let cli = Cli::try_parse_from(["pop-node", "--sync", "warp"]).unwrap();
+		assert!(!cli.relay_chain_sync_is_set());
+		let cli = Cli::try_parse_from(["pop-node", "--", "--sync", "full"]).unwrap();
+		assert!(cli.relay_chain_sync_is_set());
+		let cli = Cli::try_parse_from(["pop-node", "--", "--sync=full"]).unwrap();
+		assert!(cli.relay_chain_sync_is_set());
+	}
+
+	#[test]
+	fn trie_cache_size_parsing() {
+		use sc_cli::CliConfiguration;
+
+		let cli = Cli::try_parse_from(["pop-node"]).unwrap();
+		assert_eq!(cli.run.normalize().trie_cache_maximum_size().unwrap(), Some(64 * 1024 * 1024));
+		let cli = Cli::try_parse_from(["pop-node", "--trie-cache-size", "1073741824"]).unwrap();
+		assert_eq!(
+			cli.run.normalize().trie_cache_maximum_size().unwrap(),
+			Some(1024 * 1024 * 1024)
+		);
+		let cli = Cli::try_parse_from(["pop-node", "--trie-cache-size", "0"]).unwrap();
+		assert_eq!(cli.run.normalize().trie_cache_maximum_size().unwrap(), None);
+	}
+
+	#[test]
+	fn pool_limits_parsing() {
+		use sc_cli::CliConfiguration;
+
+		let cli = Cli::try_parse_from(["pop-node", "--pool-limit", "100", "--pool-kbytes", "50"])
+			.unwrap();
+		assert!(cli.validate_pool_limits().is_ok());
+		let options = cli.run.normalize().transaction_pool(false).unwrap();
+		assert_eq!(options.ready.count, 100);
+		assert_eq!(options.ready.total_bytes, 50 * 1024);
+
+		for limit in ["--pool-limit", "--pool-kbytes"] {
+			let cli = Cli::try_parse_from(["pop-node", limit, "0"]).unwrap();
+			assert!(cli.validate_pool_limits().is_err(), "{limit}");
+		}
+		assert!(Cli::try_parse_from(["pop-node", "--pool-limit", "-1"]).is_err());
+	}
+
+	#[test]
+	fn dev_endowments_parsing() {
+		use crate::chain_spec::{DEFAULT_DEV_ACCOUNTS, DEFAULT_DEV_ENDOWMENT};
+
+		let endowments = Cli::try_parse_from(["pop-node"]).unwrap().dev_endowments();
+		assert_eq!(endowments.accounts, DEFAULT_DEV_ACCOUNTS);
+		assert_eq!(endowments.amount, DEFAULT_DEV_ENDOWMENT);
+		assert_eq!(endowments.addresses, None);
+
+		let cli = Cli::try_parse_from([
+			"pop-node",
+			"--endow-accounts",
+			"12",
+			"--endowment",
+			"1000",
+			"--endow-addresses",
+			"team.txt",
+		])
+		.unwrap();
+		let endowments = cli.dev_endowments();
+		assert_eq!((endowments.accounts, endowments.amount), (12, 1_000));
+		assert_eq!(endowments.addresses, Some("team.txt".into()));
+		assert!(Cli::try_parse_from(["pop-node", "--endow-accounts", "13"]).is_err());
+		assert!(Cli::try_parse_from(["pop-node", "--endowment", "-1"]).is_err());
+	}
+
+	#[test]
+	fn protocol_id_parsing() {
+		let cli = Cli::try_parse_from(["pop-node", "--protocol-id", "pop-custom"]).unwrap();
+		assert_eq!(cli.protocol_id.as_deref(), Some("pop-custom"));
+		for invalid in ["", "pöp"] {
+			assert!(Cli::try_parse_from(["pop-node", "--protocol-id", invalid]).is_err());
+		}
+	}
+}
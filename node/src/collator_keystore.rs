@@ -0,0 +1,218 @@
+//! Restricts authoring to the aura key given by `--collator-account`, so that a node holding
+//! backup keys, e.g. of a failover setup, never authors under another identity, and hides the
+//! aura keys of a `--collator-standby` collator until it takes over authoring.
+//!
+//! Only the keystore used for authoring is restricted: the other aura keys remain available to
+//! the rest of the node, e.g. for rotating session keys via RPC.
+
+use sp_core::{
+	crypto::{key_types::AURA, ByteArray, KeyTypeId},
+	ecdsa, ed25519, sr25519,
+};
+use sp_keystore::{Error, Keystore, KeystorePtr};
+
+use crate::standby::Authoring;
+
+/// A keystore exposing no other aura key than `account`, if any, and none while on standby.
+pub(crate) struct CollatorKeystore {
+	inner: KeystorePtr,
+	account: Option<sr25519::Public>,
+	standby: Option<Authoring>,
+}
+
+impl CollatorKeystore {
+	/// Restrict the aura keys of `inner` to `account`, if any, failing if `inner` does not hold it.
+	pub(crate) fn new(
+		inner: KeystorePtr,
+		account: Option<sr25519::Public>,
+	) -> Result<Self, String> {
+		if let Some(account) = account {
+			if !inner.has_keys(&[(account.to_raw_vec(), AURA)]) {
+				return Err(format!(
+					"The aura key of `--collator-account` {account} is not in the keystore, \
+					 insert it or omit `--collator-account`"
+				));
+			}
+		}
+		Ok(Self { inner, account, standby: None })
+	}
+
+	/// Hide the aura keys until `authoring` tells the standby collator took over.
+	pub(crate) fn standby(self, authoring: Authoring) -> Self {
+		Self { standby: Some(authoring), ..self }
+	}
+
+	/// Whether the key `public` of type `key_type` is exposed.
+	fn exposes(&self, key_type: KeyTypeId, public: &[u8]) -> bool {
+		key_type != AURA ||
+			(self.standby.as_ref().is_none_or(Authoring::get) &&
+				self.account.is_none_or(|account| public == account.as_slice()))
+	}
+}
+
+impl Keystore for CollatorKeystore {
+	fn sr25519_public_keys(&self, key_type: KeyTypeId) -> Vec<sr25519::Public> {
+		let mut keys = self.inner.sr25519_public_keys(key_type);
+		keys.retain(|public| self.exposes(key_type, public.as_slice()));
+		keys
+	}
+
+	fn sr25519_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<sr25519::Public, Error> {
+		self.inner.sr25519_generate_new(key_type, seed)
+	}
+
+	fn sr25519_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		msg: &[u8],
+	) -> Result<Option<sr25519::Signature>, Error> {
+		if !self.exposes(key_type, public.as_slice()) {
+			return Ok(None);
+		}
+		self.inner.sr25519_sign(key_type, public, msg)
+	}
+
+	fn sr25519_vrf_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		data: &sr25519::vrf::VrfSignData,
+	) -> Result<Option<sr25519::vrf::VrfSignature>, Error> {
+		if !self.exposes(key_type, public.as_slice()) {
+			return Ok(None);
+		}
+		self.inner.sr25519_vrf_sign(key_type, public, data)
+	}
+
+	fn sr25519_vrf_pre_output(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		input: &sr25519::vrf::VrfInput,
+	) -> Result<Option<sr25519::vrf::VrfPreOutput>, Error> {
+		if !self.exposes(key_type, public.as_slice()) {
+			return Ok(None);
+		}
+		self.inner.sr25519_vrf_pre_output(key_type, public, input)
+	}
+
+	fn ed25519_public_keys(&self, key_type: KeyTypeId) -> Vec<ed25519::Public> {
+		self.inner.ed25519_public_keys(key_type)
+	}
+
+	fn ed25519_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<ed25519::Public, Error> {
+		self.inner.ed25519_generate_new(key_type, seed)
+	}
+
+	fn ed25519_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &ed25519::Public,
+		msg: &[u8],
+	) -> Result<Option<ed25519::Signature>, Error> {
+		self.inner.ed25519_sign(key_type, public, msg)
+	}
+
+	fn ecdsa_public_keys(&self, key_type: KeyTypeId) -> Vec<ecdsa::Public> {
+		self.inner.ecdsa_public_keys(key_type)
+	}
+
+	fn ecdsa_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<ecdsa::Public, Error> {
+		self.inner.ecdsa_generate_new(key_type, seed)
+	}
+
+	fn ecdsa_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8],
+	) -> Result<Option<ecdsa::Signature>, Error> {
+		self.inner.ecdsa_sign(key_type, public, msg)
+	}
+
+	fn ecdsa_sign_prehashed(
+		&self,
+		key_type: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8; 32],
+	) -> Result<Option<ecdsa::Signature>, Error> {
+		self.inner.ecdsa_sign_prehashed(key_type, public, msg)
+	}
+
+	fn insert(&self, key_type: KeyTypeId, suri: &str, public: &[u8]) -> Result<(), ()> {
+		self.inner.insert(key_type, suri, public)
+	}
+
+	fn keys(&self, key_type: KeyTypeId) -> Result<Vec<Vec<u8>>, Error> {
+		let mut keys = self.inner.keys(key_type)?;
+		keys.retain(|public| self.exposes(key_type, public));
+		Ok(keys)
+	}
+
+	fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
+		public_keys.iter().all(|(public, key_type)| self.exposes(*key_type, public)) &&
+			self.inner.has_keys(public_keys)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use sp_core::Pair;
+	use sp_keystore::testing::MemoryKeystore;
+
+	use super::*;
+
+	#[test]
+	fn only_the_collator_account_authors() {
+		let inner: KeystorePtr = Arc::new(MemoryKeystore::new());
+		let account = inner.sr25519_generate_new(AURA, Some("//Alice")).unwrap();
+		let backup = inner.sr25519_generate_new(AURA, Some("//Bob")).unwrap();
+		let keystore = CollatorKeystore::new(inner, Some(account)).unwrap();
+
+		assert_eq!(keystore.sr25519_public_keys(AURA), vec![account]);
+		assert!(keystore.has_keys(&[(account.to_raw_vec(), AURA)]));
+		assert!(!keystore.has_keys(&[(backup.to_raw_vec(), AURA)]));
+		assert!(keystore.sr25519_sign(AURA, &account, b"block").unwrap().is_some());
+		assert!(keystore.sr25519_sign(AURA, &backup, b"block").unwrap().is_none());
+	}
+
+	#[test]
+	fn collator_account_must_be_in_keystore() {
+		let inner: KeystorePtr = Arc::new(MemoryKeystore::new());
+		inner.sr25519_generate_new(AURA, Some("//Bob")).unwrap();
+		let account = sr25519::Pair::from_string("//Alice", None).unwrap().public();
+		let Err(e) = CollatorKeystore::new(inner, Some(account)) else {
+			panic!("expected a key missing from the keystore to be rejected");
+		};
+		assert!(e.contains(&account.to_string()) && e.contains("is not in the keystore"), "{e}");
+	}
+
+	#[test]
+	fn standby_collator_authors_once_it_took_over() {
+		let inner: KeystorePtr = Arc::new(MemoryKeystore::new());
+		let account = inner.sr25519_generate_new(AURA, Some("//Alice")).unwrap();
+		let authoring = Authoring::default();
+		let keystore = CollatorKeystore::new(inner, None).unwrap().standby(authoring.clone());
+
+		assert!(keystore.sr25519_public_keys(AURA).is_empty());
+		assert!(keystore.sr25519_sign(AURA, &account, b"block").unwrap().is_none());
+		authoring.set(true);
+		assert_eq!(keystore.sr25519_public_keys(AURA), vec![account]);
+		assert!(keystore.sr25519_sign(AURA, &account, b"block").unwrap().is_some());
+	}
+}
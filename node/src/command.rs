@@ -1,4 +1,9 @@
-use std::{net::SocketAddr, path::PathBuf};
+use std::{
+	net::SocketAddr,
+	num::NonZeroU32,
+	path::{Path, PathBuf},
+	time::Duration,
+};
 
 use cumulus_client_service::storage_proof_size::HostFunctions as ReclaimHostFunctions;
 use cumulus_primitives_core::ParaId;
@@ -9,14 +14,17 @@ use sc_cli::{
 	ChainSpec, CliConfiguration, DefaultConfigurationValues, ImportParams, KeystoreParams,
 	NetworkParams, Result, SharedParams, SubstrateCli,
 };
-use sc_service::config::{BasePath, PrometheusConfig};
+use sc_service::config::{BasePath, KeystoreConfig, PrometheusConfig};
 use sp_runtime::traits::HashingFor;
 
 use crate::{
+	benchmarking::{inherent_benchmark_data, RemarkBuilder},
 	chain_spec,
-	chain_spec::Relay,
-	cli::{Cli, RelayChainCli, Subcommand},
-	service::new_partial,
+	chain_spec::{Code, Relay},
+	cli::{Cli, LogFormat, RelayChainCli, Subcommand},
+	commands::{KeySubcommand, UpgradeSubcommand},
+	service::{new_partial, NodeOptions},
+	structured_logs::JsonLogs,
 };
 
 #[derive(Debug, PartialEq)]
@@ -70,14 +78,75 @@ impl RuntimeResolver for PathBuf {
 	}
 }
 
-fn load_spec(id: &str) -> std::result::Result<Box<dyn ChainSpec>, String> {
+/// The options of the built-in chain specifications given on the command line.
+struct SpecOptions<'a> {
+	/// The relay chain of the development and local chains.
+	local_relay: Relay,
+	/// The protocol id of the development chain, if not the default one.
+	protocol_id: Option<&'a str>,
+	/// The accounts endowed on the development chain.
+	dev_endowments: chain_spec::DevEndowments,
+	/// The paths of the contract codes uploaded at the genesis of the development chain.
+	contract_codes: &'a [PathBuf],
+	/// The CSV file of the accounts endowed at the genesis of mainnet, if any.
+	endowments: Option<&'a Path>,
+	/// The file the genesis of the testnet is read from, if any.
+	genesis_config: Option<&'a Path>,
+	/// The timestamp of the genesis of the testnet, if any.
+	genesis_timestamp: Option<u64>,
+	/// The runtime embedded in the genesis of the chains.
+	code: Code,
+}
+
+fn load_spec(id: &str, options: SpecOptions) -> std::result::Result<Box<dyn ChainSpec>, String> {
+	let SpecOptions {
+		local_relay,
+		protocol_id,
+		dev_endowments,
+		contract_codes,
+		endowments,
+		genesis_config,
+		genesis_timestamp,
+		code,
+	} = options;
+	let development = matches!(id, "dev" | "devnet" | "dev-paseo" | "" | "local");
+	if let (Some(protocol_id), false) = (protocol_id, development) {
+		return Err(format!(
+			"Cannot use the protocol id `{protocol_id}` with the chain specification `{id}`, only \
+			 the development chain specification can be given a protocol id on the command line"
+		));
+	}
 	Ok(match id {
-		"dev" | "devnet" | "dev-paseo" =>
-			Box::new(chain_spec::development_config(Relay::PaseoLocal)),
-		"test" | "testnet" | "pop-paseo" => Box::new(chain_spec::testnet_config(Relay::Paseo)),
-		"pop" | "mainnet" | "pop-polkadot" | "pop-network" =>
-			Box::new(chain_spec::mainnet_config(Relay::Polkadot)),
-		"" | "local" => Box::new(chain_spec::development_config(Relay::PaseoLocal)),
+		_ if development => Box::new(chain_spec::development_config(
+			local_relay,
+			protocol_id,
+			dev_endowments.endowed()?,
+			chain_spec::read_contract_codes(contract_codes)?,
+			code,
+		)),
+		"test" | "testnet" | "pop-paseo" => match genesis_config {
+			Some(path) => Box::new(chain_spec::from_genesis_file(path, code, genesis_timestamp)?),
+			None => Box::new(chain_spec::testnet_config(Relay::Paseo, code, genesis_timestamp)?),
+		},
+		#[cfg(feature = "test-helpers")]
+		"testnet-local" => Box::new(chain_spec::local_testnet_config(local_relay)),
+		"pop" | "mainnet" | "pop-polkadot" | "pop-network" => Box::new(chain_spec::mainnet_config(
+			Relay::Polkadot,
+			endowments.map(chain_spec::endowments_from_csv).transpose()?.unwrap_or_default(),
+			code,
+		)),
+		path if crate::spec_toml::is_toml(path) => {
+			let toml =
+				std::fs::read_to_string(path).map_err(|e| format!("Failed to read {path}: {e}"))?;
+			let json = crate::spec_toml::from_toml(&toml)?;
+			let chain_runtime = runtime(json["id"].as_str().unwrap_or_default());
+			let json = serde_json::to_vec(&json).map_err(|e| e.to_string())?;
+			match chain_runtime {
+				Runtime::Devnet => Box::new(chain_spec::DevnetChainSpec::from_json_bytes(json)?),
+				Runtime::Testnet => Box::new(chain_spec::TestnetChainSpec::from_json_bytes(json)?),
+				Runtime::Mainnet => Box::new(chain_spec::MainnetChainSpec::from_json_bytes(json)?),
+			}
+		},
 		path => {
 			let path: PathBuf = path.into();
 			match path.runtime() {
@@ -120,7 +189,17 @@ impl SubstrateCli for Cli {
 	}
 
 	fn load_spec(&self, id: &str) -> std::result::Result<Box<dyn sc_service::ChainSpec>, String> {
-		load_spec(id)
+		let options = SpecOptions {
+			local_relay: self.local_relay(),
+			protocol_id: self.protocol_id.as_deref(),
+			dev_endowments: self.dev_endowments(),
+			contract_codes: &self.genesis_contract_code,
+			endowments: self.genesis_endowments.as_deref(),
+			genesis_config: self.genesis_config.as_deref(),
+			genesis_timestamp: self.genesis_timestamp,
+			code: self.runtime_code(),
+		};
+		load_spec(id, options)
 	}
 }
 
@@ -233,13 +312,25 @@ macro_rules! construct_benchmark_partials {
 	};
 }
 
+/// Warn loudly that unsafe RPC methods are exposed on a public interface of a live chain.
+fn warn_unsafe_rpc_exposure() {
+	let banner = "!".repeat(80);
+	log::warn!("{banner}");
+	log::warn!("Unsafe RPC methods are exposed on a public interface of a live chain.");
+	log::warn!("Anyone able to connect can e.g. insert keys via `author_insertKey` or rotate the");
+	log::warn!("session keys of this node. Put the node behind a proxy filtering unsafe methods.");
+	log::warn!("{banner}");
+}
+
 /// Parse command line arguments into service configuration.
 pub fn run() -> Result<()> {
-	let cli = Cli::from_args();
+	let mut cli = Cli::from_args();
+	cli.default_database();
+	cli.resolve_tmp_reuse();
 
 	match &cli.subcommand {
 		Some(Subcommand::BuildSpec(cmd)) => {
-			let runner = cli.create_runner(cmd)?;
+			let runner = cli.create_runner(&cmd.base)?;
 			runner.sync_run(|config| cmd.run(config.chain_spec, config.network))
 		},
 		Some(Subcommand::CheckBlock(cmd)) => {
@@ -262,6 +353,11 @@ pub fn run() -> Result<()> {
 				Ok(cmd.run(components.client, components.import_queue))
 			})
 		},
+		Some(Subcommand::BenchmarkImport(cmd)) => {
+			construct_async_run!(|components, cli, cmd, config| {
+				Ok(cmd.run(components.client, components.import_queue))
+			})
+		},
 		Some(Subcommand::Revert(cmd)) => {
 			construct_async_run!(|components, cli, cmd, config| {
 				Ok(cmd.run(components.client, components.backend, None))
@@ -318,6 +414,17 @@ pub fn run() -> Result<()> {
 				BenchmarkCmd::Block(cmd) => runner.sync_run(|config| {
 					construct_benchmark_partials!(config, |partials| cmd.run(partials.client))
 				}),
+				BenchmarkCmd::Overhead(cmd) => runner.sync_run(|config| {
+					let para_id = chain_spec::Extensions::try_get(&*config.chain_spec)
+						.map(|e| e.para_id)
+						.ok_or("Could not find parachain ID in chain-spec.")?;
+					construct_benchmark_partials!(config, |partials| {
+						let (inherent_data, digest) =
+							inherent_benchmark_data(&partials.client, para_id.into())?;
+						let remarks = RemarkBuilder::new(partials.client.clone());
+						cmd.run(config, partials.client, inherent_data, digest, &remarks)
+					})
+				}),
 				#[cfg(not(feature = "runtime-benchmarks"))]
 				BenchmarkCmd::Storage(_) => Err(sc_cli::Error::Input(
 					"Compile with --features=runtime-benchmarks to enable storage benchmarks."
@@ -339,10 +446,135 @@ pub fn run() -> Result<()> {
 				_ => Err("Benchmarking sub-command unsupported".into()),
 			}
 		},
-		Some(Subcommand::Key(cmd)) => cmd.run(&cli),
+		Some(Subcommand::TryRuntime) => Err("The `try-runtime` subcommand has been migrated to a \
+			 standalone CLI (https://github.com/paritytech/try-runtime-cli): run its \
+			 `on-runtime-upgrade` or `follow-chain` against the RPC of a node, with a runtime built \
+			 with the `try-runtime` feature."
+			.into()),
+		Some(Subcommand::Key(KeySubcommand::Substrate(cmd))) => cmd.run(&cli),
+		Some(Subcommand::Key(KeySubcommand::GenerateSessionKeys(cmd))) => {
+			let spec = cli.load_spec(&cmd.chain_id())?;
+			let name = Cli::executable_name();
+			match spec.runtime() {
+				Runtime::Devnet => cmd.run::<pop_runtime_devnet::SessionKeys>(&*spec, &name),
+				Runtime::Testnet => cmd.run::<pop_runtime_testnet::SessionKeys>(&*spec, &name),
+				Runtime::Mainnet => cmd.run::<pop_runtime_mainnet::SessionKeys>(&*spec, &name),
+			}
+		},
+		Some(Subcommand::Key(KeySubcommand::DevAccounts(cmd))) => cmd.run(),
+		Some(Subcommand::InspectSpec(cmd)) => {
+			let spec = cli.load_spec(&cmd.chain)?;
+			cmd.run(&*spec)
+		},
+		Some(Subcommand::DiffSpec(cmd)) => {
+			let (old, new) = (cli.load_spec(&cmd.old)?, cli.load_spec(&cmd.new)?);
+			cmd.run(&*old, &*new)
+		},
+		Some(Subcommand::RekeySudo(cmd)) => {
+			let spec = cli.load_spec(&cmd.chain)?;
+			cmd.run(&*spec)
+		},
+		Some(Subcommand::SpecChecksum(cmd)) => cmd.run(),
+		Some(Subcommand::GenerateNodeKey(cmd)) => cmd.run(),
+		Some(Subcommand::CheckCollator(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| cmd.run(&config))
+		},
+		Some(Subcommand::Doctor(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| cmd.run(&config))
+		},
+		Some(Subcommand::CheckPov(cmd)) => {
+			construct_async_run!(|components, cli, cmd, config| { Ok(cmd.run(components.client)) })
+		},
+		Some(Subcommand::VerifyReexecution(cmd)) => {
+			construct_async_run!(|components, cli, cmd, config| { Ok(cmd.run(components.client)) })
+		},
+		Some(Subcommand::ExportBalances(cmd)) => {
+			construct_async_run!(|components, cli, cmd, config| { Ok(cmd.run(components.client)) })
+		},
+		Some(Subcommand::ForkOff(cmd)) => match &cmd.rpc_url {
+			Some(url) => {
+				let spec = cli.load_spec(&cmd.shared_params.chain.clone().unwrap_or_default())?;
+				cmd.run_rpc(url, &*spec)
+			},
+			None => construct_async_run!(|components, cli, cmd, config| {
+				Ok(cmd.run(components.client, config.chain_spec))
+			}),
+		},
+		Some(Subcommand::CollatorBundle(cmd)) => {
+			let spec = cli.load_spec(&cmd.chain)?;
+			cmd.run(&*spec)
+		},
+		Some(Subcommand::Upgrade(UpgradeSubcommand::Submit(cmd))) => {
+			let spec = cli.load_spec(&cmd.chain)?;
+			match spec.runtime() {
+				Runtime::Devnet => cmd.run::<pop_runtime_devnet::Runtime>(),
+				Runtime::Testnet => cmd.run::<pop_runtime_testnet::Runtime>(),
+				Runtime::Mainnet => cmd.run::<pop_runtime_mainnet::Runtime>(),
+			}
+		},
 		None => {
-			let runner = cli.create_runner(&cli.run.normalize())?;
+			let wasm_profile =
+				cli.profile_wasm.then(crate::wasm_profile::HostFunctionProfile::default);
+			let json_logs = (cli.log_format == LogFormat::Json).then(|| {
+				JsonLogs::new(cli.run.base.shared_params.detailed_log_output)
+			});
+			let mut runner =
+				cli.create_runner_with_logger_hook(&cli.run.normalize(), |logger, _| {
+					if let Some(profile) = &wasm_profile {
+						profile.install(logger);
+					}
+					if let Some(json_logs) = json_logs {
+						json_logs.install(logger);
+					}
+				})?;
+			let chain_type = runner.config().chain_spec.chain_type();
+			cli.validate_keystore_type(&chain_type)?;
+			cli.validate_rpc_only()?;
+			cli.validate_contracts_debug(&chain_type)?;
+			cli.validate_profile_wasm(&chain_type)?;
+			cli.validate_dev_block_time(&chain_type)?;
+			cli.validate_peers()?;
+			cli.validate_sync_mode()?;
+			cli.validate_pool_limits()?;
+			cli.validate_rpc_methods()?;
+			// Only warn once the exposure is known to be acknowledged, rather than before refusing.
+			cli.validate_unsafe_rpc_exposure(&chain_type)?;
+			if cli.exposes_unsafe_rpc(&chain_type) {
+				warn_unsafe_rpc_exposure();
+			}
+			if cli.in_memory_keystore() {
+				runner.config_mut().keystore = KeystoreConfig::InMemory;
+			}
+			// Development chains imply authoring otherwise, which RPC nodes never do.
+			if cli.rpc_only {
+				runner.config_mut().role = sc_service::Role::Full;
+			}
+			if let Some(path) = &cli.bootnodes_file {
+				let bootnodes = crate::bootnodes::read(path)?;
+				crate::bootnodes::merge(&mut runner.config_mut().network.boot_nodes, bootnodes);
+			}
 			let collator_options = cli.run.collator_options();
+			let relay_chain_sync_is_set = cli.relay_chain_sync_is_set();
+			let dev = cli.run.base.shared_params.dev;
+			let options = NodeOptions {
+				shutdown_grace: Duration::from_secs(cli.shutdown_grace_secs),
+				block_proposal_duration: cli.block_proposal_duration(),
+				finality_webhook: cli.finality_webhook.clone(),
+				metrics_snapshots: cli.metrics_snapshots(),
+				pin_blocks: cli.pin_blocks.iter().copied().collect(),
+				contracts_debug: cli.enable_contracts_debug,
+				collator_account: cli.collator_account,
+				rotate_keys_on_start: cli.rotate_keys_on_start,
+				max_block_lag: cli.max_block_lag,
+				collator_standby: cli.collator_standby(),
+				block_announce_validation_concurrency: cli
+					.block_announce_validation_concurrency
+					.and_then(NonZeroU32::new),
+				wasm_profile,
+				dev_block_time: cli.dev_block_time.map(Duration::from_millis),
+			};
 
 			runner.run_node_until_exit(|config| async move {
 				let hwbench = (!cli.no_hardware_benchmarks)
@@ -356,17 +588,45 @@ pub fn run() -> Result<()> {
 					.map(|e| e.para_id)
 					.ok_or("Could not find parachain ID in chain-spec.")?;
 
+				let id = ParaId::from(para_id);
+
+				// With `--dev`, blocks are authored without any relay chain.
+				if dev {
+					return match config.chain_spec.runtime() {
+						Runtime::Devnet => {
+							sp_core::crypto::set_default_ss58_version(
+								pop_runtime_devnet::SS58Prefix::get().into(),
+							);
+							crate::service::start_dev_node::<pop_runtime_devnet::RuntimeApi>(
+								config,
+								id,
+								options,
+							)
+							.await
+							.map_err(Into::into)
+						},
+						_ => Err("Only the development chain can be run with `--dev`".into()),
+					};
+				}
+
 				let polkadot_cli = RelayChainCli::new(
 					&config,
 					[RelayChainCli::executable_name()].iter().chain(cli.relay_chain_args.iter()),
 				);
 
-				let id = ParaId::from(para_id);
-
 				let tokio_handle = config.tokio_handle.clone();
-				let polkadot_config =
+				let mut polkadot_config =
 					SubstrateCli::create_configuration(&polkadot_cli, &polkadot_cli, tokio_handle)
 						.map_err(|err| format!("Relay chain argument error: {}", err))?;
+				// A node syncing the state of the parachain warp syncs its embedded relay chain
+				// node as well unless told otherwise, as it would otherwise wait on the relay
+				// chain to sync from genesis. `--relay-chain-rpc-urls`, which runs no relay
+				// chain node, joins faster still.
+				if crate::state_sync::syncs_state(&config.network.sync_mode) &&
+					!relay_chain_sync_is_set
+				{
+					polkadot_config.network.sync_mode = sc_network::config::SyncMode::Warp;
+				}
 
 				info!("Is collating: {}", if config.role.is_authority() { "yes" } else { "no" });
 
@@ -381,6 +641,7 @@ pub fn run() -> Result<()> {
 							collator_options,
 							id,
 							hwbench,
+							options,
 						)
 						.await
 						.map(|r| r.0)
@@ -400,6 +661,7 @@ pub fn run() -> Result<()> {
 								collator_options,
 								id,
 								hwbench,
+								options,
 							)
 							.await
 							.map(|r| r.0)
@@ -420,6 +682,7 @@ pub fn run() -> Result<()> {
 								collator_options,
 								id,
 								hwbench,
+								options,
 							)
 							.await
 							.map(|r| r.0)
@@ -525,6 +788,18 @@ impl CliConfiguration<Self> for RelayChainCli {
 		self.base.base.rpc_cors(is_dev)
 	}
 
+	fn rpc_max_request_size(&self) -> Result<u32> {
+		self.base.base.rpc_max_request_size()
+	}
+
+	fn rpc_max_response_size(&self) -> Result<u32> {
+		self.base.base.rpc_max_response_size()
+	}
+
+	fn rpc_max_subscriptions_per_connection(&self) -> Result<u32> {
+		self.base.base.rpc_max_subscriptions_per_connection()
+	}
+
 	fn default_heap_pages(&self) -> Result<Option<u64>> {
 		self.base.base.default_heap_pages()
 	}
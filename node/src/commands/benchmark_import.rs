@@ -0,0 +1,190 @@
+use std::{
+	fmt, fs,
+	path::PathBuf,
+	sync::Arc,
+	time::{Duration, Instant},
+};
+
+use futures::{FutureExt, StreamExt};
+use sc_cli::{CliConfiguration, Error, ImportBlocksCmd, ImportParams, Result, SharedParams};
+use sc_client_api::{BlockBackend, BlockchainEvents, UsageProvider};
+use sc_service::ImportQueue;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+/// The percentiles of the import time of a block which are reported.
+const PERCENTILES: [usize; 3] = [50, 90, 99];
+
+/// The `benchmark-import` command used to measure the throughput of block import, by importing
+/// blocks previously exported by `export-blocks` like `import-blocks` does.
+///
+/// Blocks already in the database are skipped, so the command should be run against a fresh
+/// database, e.g. with `--tmp`.
+#[derive(Debug, clap::Parser)]
+pub struct BenchmarkImportCmd {
+	/// The file of blocks to import, as exported by `export-blocks`.
+	#[arg(value_name = "BLOCKS_FILE")]
+	pub blocks_file: PathBuf,
+
+	/// Whether the blocks were exported in binary rather than in json.
+	#[arg(long)]
+	pub binary: bool,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub import_params: ImportParams,
+}
+
+impl BenchmarkImportCmd {
+	/// Run the `benchmark-import` command, importing the blocks into `client` via `import_queue`
+	/// and printing the throughput of the import.
+	pub async fn run<B, C, IQ>(&self, client: Arc<C>, import_queue: IQ) -> Result<()>
+	where
+		B: BlockT + for<'de> serde::Deserialize<'de>,
+		C: BlockchainEvents<B>
+			+ HeaderBackend<B>
+			+ BlockBackend<B>
+			+ UsageProvider<B>
+			+ Send
+			+ Sync
+			+ 'static,
+		IQ: ImportQueue<B> + 'static,
+	{
+		let bytes = fs::metadata(&self.blocks_file)?.len();
+		let import = ImportBlocksCmd {
+			input: Some(self.blocks_file.clone()),
+			binary: self.binary,
+			shared_params: self.shared_params.clone(),
+			import_params: self.import_params.clone(),
+		};
+		// Notified of every block as it is imported, including blocks imported from a file.
+		let mut imported = client.every_import_notification_stream().fuse();
+		let mut times = Vec::new();
+		let start = Instant::now();
+		let mut last = start;
+		let mut record = || {
+			let now = Instant::now();
+			times.push(now - last);
+			last = now;
+		};
+
+		let import = import.run(client.clone(), import_queue).fuse();
+		futures::pin_mut!(import);
+		let result = loop {
+			futures::select! {
+				result = import => break result,
+				_ = imported.select_next_some() => record(),
+			}
+		};
+		result?;
+		// The notifications of the last blocks may not have been received yet.
+		while let Some(Some(_)) = imported.next().now_or_never() {
+			record();
+		}
+
+		let report = Report::new(times, bytes, start.elapsed()).ok_or_else(|| {
+			Error::Input(
+				"No blocks were imported, as they are already in the database: run the benchmark \
+				 against a fresh database, e.g. with `--tmp`"
+					.into(),
+			)
+		})?;
+		println!("{report}");
+		Ok(())
+	}
+}
+
+impl CliConfiguration for BenchmarkImportCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn import_params(&self) -> Option<&ImportParams> {
+		Some(&self.import_params)
+	}
+}
+
+/// The throughput of importing blocks, along with the distribution of the time taken to import
+/// each block.
+#[derive(Debug)]
+struct Report {
+	/// The time taken to import each block, in ascending order.
+	times: Vec<Duration>,
+	/// The size of the imported blocks.
+	bytes: u64,
+	/// The time taken to import all blocks.
+	elapsed: Duration,
+}
+
+impl Report {
+	/// The report of importing blocks of `bytes` in `elapsed`, each taking one of `times`, or
+	/// `None` if no blocks were imported.
+	fn new(mut times: Vec<Duration>, bytes: u64, elapsed: Duration) -> Option<Self> {
+		if times.is_empty() || elapsed.is_zero() {
+			return None;
+		}
+		times.sort();
+		Some(Self { times, bytes, elapsed })
+	}
+
+	/// The number of blocks imported per second.
+	fn blocks_per_sec(&self) -> f64 {
+		self.times.len() as f64 / self.elapsed.as_secs_f64()
+	}
+
+	/// The megabytes of blocks imported per second.
+	fn mb_per_sec(&self) -> f64 {
+		self.bytes as f64 / 1_000_000.0 / self.elapsed.as_secs_f64()
+	}
+
+	/// The time within which `percentile` percent of the blocks were imported.
+	fn percentile(&self, percentile: usize) -> Duration {
+		let rank = (percentile * self.times.len()).div_ceil(100);
+		self.times[rank.saturating_sub(1)]
+	}
+}
+
+impl fmt::Display for Report {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		writeln!(
+			f,
+			"Imported {} blocks ({:.2} MB) in {:.2?}: {:.2} blocks/sec, {:.2} MB/sec",
+			self.times.len(),
+			self.bytes as f64 / 1_000_000.0,
+			self.elapsed,
+			self.blocks_per_sec(),
+			self.mb_per_sec(),
+		)?;
+		write!(f, "Block import time:")?;
+		for percentile in PERCENTILES {
+			write!(f, " p{percentile} {:.2?},", self.percentile(percentile))?;
+		}
+		write!(f, " max {:.2?}", self.times[self.times.len() - 1])
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn report_has_positive_throughput_and_percentiles() {
+		let times = (1..=100).rev().map(Duration::from_millis).collect();
+		let report = Report::new(times, 2_000_000, Duration::from_secs(2)).unwrap();
+
+		assert_eq!(report.blocks_per_sec(), 50.0);
+		assert_eq!(report.mb_per_sec(), 1.0);
+		assert_eq!(report.percentile(50), Duration::from_millis(50));
+		assert_eq!(report.percentile(99), Duration::from_millis(99));
+		assert!(report.to_string().contains("max 100.00ms"));
+	}
+
+	#[test]
+	fn report_requires_imported_blocks() {
+		assert!(Report::new(vec![], 0, Duration::from_secs(1)).is_none());
+	}
+}
@@ -0,0 +1,225 @@
+use std::path::Path;
+
+use pop_runtime_common::Hash;
+use sc_cli::{Error, Result};
+use sc_service::{config::NetworkConfiguration, ChainSpec};
+use sp_core::storage::Storage;
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash as _},
+	BuildStorage, StateVersion,
+};
+
+/// The `build-spec` command used to build a chain specification, or only its genesis state to
+/// print the state root it produces, e.g. to verify that a rebuilt runtime yields the same
+/// genesis before launching a chain.
+#[derive(Debug, clap::Parser)]
+pub struct BuildSpecCmd {
+	#[allow(missing_docs)]
+	#[command(flatten)]
+	pub base: sc_cli::BuildSpecCmd,
+
+	/// Build the genesis state of the chain specification and print its state root, instead of
+	/// printing the chain specification.
+	#[arg(long)]
+	pub dry_run_genesis: bool,
+
+	/// The format to print the chain specification in. TOML chain specifications embed their
+	/// genesis as JSON and are printed without the default bootnode.
+	#[arg(long, value_enum, default_value_t = SpecFormat::Json)]
+	pub spec_format: SpecFormat,
+
+	/// A genesis patch, as inline JSON or the path of a JSON file, to deep-merge over the genesis
+	/// of the chain specification, e.g. to change the endowments or invulnerables of an ephemeral
+	/// test network. Objects are merged key by key, `null` removes a key and any other value,
+	/// including arrays, replaces the one patched.
+	///
+	/// Raw chain specifications cannot be patched, but `--raw` builds the raw genesis from the
+	/// patched one.
+	#[arg(long, value_name = "JSON")]
+	pub patch: Option<String>,
+}
+
+/// The format of a chain specification.
+#[derive(Clone, Copy, Debug, Default, PartialEq, clap::ValueEnum)]
+pub enum SpecFormat {
+	/// The JSON format of Substrate.
+	#[default]
+	Json,
+	/// TOML, for editing by hand, which is loaded by `--chain` from files with a `.toml`
+	/// extension.
+	Toml,
+}
+
+impl BuildSpecCmd {
+	/// Run the `build-spec` command.
+	pub fn run(&self, spec: Box<dyn ChainSpec>, network: NetworkConfiguration) -> Result<()> {
+		let spec = match &self.patch {
+			Some(patch) => patched(&*spec, read_patch(patch)?)?,
+			None => spec,
+		};
+		if self.dry_run_genesis {
+			println!("{:?}", genesis_state_root(&*spec)?);
+			return Ok(());
+		}
+		match self.spec_format {
+			SpecFormat::Json => self.base.run(spec, network),
+			SpecFormat::Toml => {
+				let json = spec.as_json(self.base.raw)?;
+				println!("{}", crate::spec_toml::to_toml(&json).map_err(Error::Input)?);
+				Ok(())
+			},
+		}
+	}
+}
+
+/// The genesis patch `patch`, given inline if it is a JSON object, read from the file at the path
+/// `patch` otherwise.
+fn read_patch(patch: &str) -> Result<serde_json::Value> {
+	let json = if patch.trim_start().starts_with('{') {
+		patch.to_owned()
+	} else {
+		std::fs::read_to_string(Path::new(patch))
+			.map_err(|e| Error::Input(format!("Failed to read the patch {patch}: {e}")))?
+	};
+	serde_json::from_str(&json).map_err(|e| Error::Input(format!("Invalid patch: {e}")))
+}
+
+/// Merge `patch` into `value`: objects are merged key by key, where `null` removes a key, and any
+/// other value replaces the one patched.
+fn merge(value: &mut serde_json::Value, patch: serde_json::Value) {
+	use serde_json::Value;
+
+	match (value, patch) {
+		(Value::Object(value), Value::Object(patch)) =>
+			for (key, patch) in patch {
+				if patch.is_null() {
+					value.remove(&key);
+				} else {
+					merge(value.entry(key).or_insert(Value::Null), patch);
+				}
+			},
+		(value, patch) => *value = patch,
+	}
+}
+
+/// The chain specification `spec` with `patch` merged over its genesis, whether the genesis is a
+/// patch of the default genesis of the runtime or a full one.
+fn patched(spec: &dyn ChainSpec, patch: serde_json::Value) -> Result<Box<dyn ChainSpec>> {
+	let mut json: serde_json::Value = serde_json::from_str(&spec.as_json(false)?)
+		.map_err(|e| Error::Input(format!("Invalid chain specification: {e}")))?;
+	let genesis = json
+		.pointer_mut("/genesis/runtimeGenesis")
+		.and_then(serde_json::Value::as_object_mut)
+		.and_then(|genesis| {
+			let key = if genesis.contains_key("patch") { "patch" } else { "config" };
+			genesis.get_mut(key)
+		})
+		.ok_or_else(|| {
+			Error::Input("Only the genesis of plain chain specifications can be patched".into())
+		})?;
+	merge(genesis, patch);
+	// The chain specifications of all runtimes are of the same type.
+	let json = serde_json::to_vec(&json).map_err(|e| Error::Input(e.to_string()))?;
+	Ok(Box::new(crate::chain_spec::TestnetChainSpec::from_json_bytes(json).map_err(Error::Input)?))
+}
+
+/// The state root of the genesis of `spec`.
+fn genesis_state_root(spec: &dyn ChainSpec) -> Result<Hash> {
+	let storage = spec.build_storage().map_err(Error::Input)?;
+	// All runtimes use the latest state version.
+	Ok(state_root(storage, StateVersion::V1))
+}
+
+/// The root of the trie of `storage`, whose child tries are stored under their prefixed storage
+/// keys as their roots.
+fn state_root(storage: Storage, state_version: StateVersion) -> Hash {
+	let mut top = storage.top;
+	for child in storage.children_default.into_values() {
+		let root = BlakeTwo256::trie_root(child.data.into_iter().collect(), state_version);
+		top.insert(child.child_info.prefixed_storage_key().into_inner(), root.as_ref().to_vec());
+	}
+	BlakeTwo256::trie_root(top.into_iter().collect(), state_version)
+}
+
+#[cfg(test)]
+mod tests {
+	use sp_core::storage::{ChildInfo, StorageChild};
+
+	use super::*;
+	use crate::chain_spec::{development_config, Code, Relay};
+
+	#[test]
+	fn dev_genesis_state_root_is_stable() {
+		let root = || {
+			let spec =
+				development_config(Relay::PaseoLocal, None, vec![], vec![], Code::Compressed);
+			genesis_state_root(&spec).unwrap()
+		};
+		assert_eq!(root(), root());
+		assert_ne!(root(), Hash::zero());
+	}
+
+	#[test]
+	fn patch_is_merged_over_genesis() {
+		let mut value = serde_json::json!({
+			"balances": { "balances": [["alice", 1]] },
+			"sudo": { "key": "alice" },
+			"maintenanceMode": { "paused": false },
+		});
+		merge(
+			&mut value,
+			serde_json::json!({
+				"balances": { "balances": [["bob", 2]] },
+				"sudo": null,
+				"maintenanceMode": { "pauseAuthority": "bob" },
+			}),
+		);
+		assert_eq!(
+			value,
+			serde_json::json!({
+				"balances": { "balances": [["bob", 2]] },
+				"maintenanceMode": { "paused": false, "pauseAuthority": "bob" },
+			})
+		);
+	}
+
+	#[test]
+	fn patched_spec_builds_with_patched_genesis() {
+		let spec = development_config(Relay::PaseoLocal, None, vec![], vec![], Code::Compressed);
+		let patch = read_patch(r#"{ "parachainInfo": { "parachainId": 2000 } }"#).unwrap();
+		let patched = patched(&spec, patch).unwrap();
+
+		let json: serde_json::Value =
+			serde_json::from_str(&patched.as_json(false).unwrap()).unwrap();
+		let genesis = &json["genesis"]["runtimeGenesis"]["patch"];
+		assert_eq!(genesis["parachainInfo"]["parachainId"], 2000);
+		// The rest of the genesis is unchanged.
+		assert!(genesis["balances"]["balances"].as_array().is_some_and(|b| !b.is_empty()));
+		assert_ne!(genesis_state_root(&*patched).unwrap(), genesis_state_root(&spec).unwrap());
+		// Raw specifications have no genesis to patch.
+		let raw = crate::chain_spec::DevnetChainSpec::from_json_bytes(
+			spec.as_json(true).unwrap().into_bytes(),
+		)
+		.unwrap();
+		assert!(patched(&raw, serde_json::json!({})).is_err());
+		assert!(read_patch("/nonexistent/patch.json").is_err());
+	}
+
+	#[test]
+	fn state_root_includes_child_tries() {
+		let storage = |value: &[u8]| {
+			let child_info = ChildInfo::new_default(b"child");
+			let child = StorageChild {
+				data: [(b"key".to_vec(), value.to_vec())].into(),
+				child_info: child_info.clone(),
+			};
+			Storage {
+				top: [(b"key".to_vec(), b"value".to_vec())].into(),
+				children_default: [(child_info.storage_key().to_vec(), child)].into(),
+			}
+		};
+		let root = |value| state_root(storage(value), StateVersion::V1);
+		assert_eq!(root(b"a"), root(b"a"));
+		assert_ne!(root(b"a"), root(b"b"));
+	}
+}
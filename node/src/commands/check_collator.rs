@@ -0,0 +1,162 @@
+use std::io::Write;
+
+use polkadot_primitives::{Id as ParaId, LOWEST_PUBLIC_ID};
+use sc_cli::{CliConfiguration, Error, KeystoreParams, NodeKeyParams, Result, SharedParams};
+use sc_network::config::{NodeKeyConfig, Secret};
+use sc_service::{config::KeystoreConfig, ChainSpec, Configuration, KeystoreContainer};
+use sp_core::crypto::key_types::AURA;
+use sp_keystore::Keystore;
+
+use crate::chain_spec::Extensions;
+
+/// The `check-collator` command used to verify that a node is configured to collate before it is
+/// launched, given the same chain, base path, keystore and node key options as the node.
+#[derive(Debug, clap::Parser)]
+pub struct CheckCollatorCmd {
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub keystore_params: KeystoreParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub node_key_params: NodeKeyParams,
+}
+
+impl CheckCollatorCmd {
+	/// Run the `check-collator` command, printing a checklist of the collator configuration and
+	/// failing if any check fails.
+	pub fn run(&self, config: &Configuration) -> Result<()> {
+		let checks = checks(&config.keystore, &config.network.node_key, &*config.chain_spec);
+		print_checklist(&checks, &mut std::io::stdout().lock())?;
+		if checks.iter().any(|(_, result)| result.is_err()) {
+			return Err(Error::Input("The node is not ready to collate".into()));
+		}
+		Ok(())
+	}
+}
+
+impl CliConfiguration for CheckCollatorCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn keystore_params(&self) -> Option<&KeystoreParams> {
+		Some(&self.keystore_params)
+	}
+
+	fn node_key_params(&self) -> Option<&NodeKeyParams> {
+		Some(&self.node_key_params)
+	}
+}
+
+/// A description of a check, along with why it failed if it did.
+pub(super) type Check = (&'static str, std::result::Result<(), String>);
+
+/// Check the keystore, node key and chain specification a collator would be launched with.
+pub(super) fn checks(
+	keystore: &KeystoreConfig,
+	node_key: &NodeKeyConfig,
+	spec: &dyn ChainSpec,
+) -> Vec<Check> {
+	vec![
+		("Aura key in keystore", check_aura_key(keystore)),
+		("Node key", check_node_key(node_key)),
+		("Parachain chain spec", check_para_id(spec)),
+	]
+}
+
+/// Ensure the keystore holds an aura key to author blocks with.
+fn check_aura_key(keystore: &KeystoreConfig) -> std::result::Result<(), String> {
+	let KeystoreConfig::Path { path, .. } = keystore else {
+		return Err("the keystore is in memory, so it holds no keys on launch".into());
+	};
+	let container = KeystoreContainer::new(keystore).map_err(|e| e.to_string())?;
+	if container.keystore().sr25519_public_keys(AURA).is_empty() {
+		return Err(format!(
+			"no aura key in {}, insert one with `pop-node key insert --key-type aura`",
+			path.display()
+		));
+	}
+	Ok(())
+}
+
+/// Ensure the node key is persisted, so that the peer id of the collator is stable.
+fn check_node_key(node_key: &NodeKeyConfig) -> std::result::Result<(), String> {
+	let NodeKeyConfig::Ed25519(secret) = node_key;
+	match secret {
+		Secret::Input(_) => Ok(()),
+		Secret::File(path) if path.exists() => Ok(()),
+		Secret::File(path) => Err(format!(
+			"{} does not exist, generate it with `pop-node key generate-node-key --file {0}`",
+			path.display()
+		)),
+		Secret::New => Err("a new node key would be generated on every launch".into()),
+	}
+}
+
+/// Ensure the chain specification is of a parachain with a para id open to public parachains.
+fn check_para_id(spec: &dyn ChainSpec) -> std::result::Result<(), String> {
+	let Some(extensions) = Extensions::try_get(spec) else {
+		return Err(format!("{} has no parachain extensions", spec.id()));
+	};
+	if ParaId::from(extensions.para_id) < LOWEST_PUBLIC_ID {
+		return Err(format!(
+			"para id {} is reserved for system parachains, the lowest public one is \
+			 {LOWEST_PUBLIC_ID}",
+			extensions.para_id
+		));
+	}
+	Ok(())
+}
+
+/// Write `checks` to `out` as a checklist.
+fn print_checklist(checks: &[Check], out: &mut impl Write) -> Result<()> {
+	for (description, result) in checks {
+		match result {
+			Ok(()) => writeln!(out, "{}", color_print::cformat!("<green>✔</> {}", description))?,
+			Err(e) =>
+				writeln!(out, "{}", color_print::cformat!("<red>✘</> {}: {}", description, e))?,
+		}
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use std::fs;
+
+	use super::*;
+	use crate::chain_spec::{development_config, Code, Relay};
+
+	#[test]
+	fn fails_without_aura_key() {
+		let dir = std::env::temp_dir().join(format!("pop-check-collator-{}", std::process::id()));
+		let node_key = dir.join("secret_ed25519");
+		fs::create_dir_all(&dir).unwrap();
+		fs::write(&node_key, [0; 32]).unwrap();
+		let keystore = KeystoreConfig::Path { path: dir.join("keystore"), password: None };
+		let node_key = NodeKeyConfig::Ed25519(Secret::File(node_key));
+		let spec = development_config(Relay::PaseoLocal, None, vec![], vec![], Code::Compressed);
+		let failed = |checks: &[Check]| -> Vec<&'static str> {
+			checks.iter().filter(|(_, result)| result.is_err()).map(|(d, _)| *d).collect()
+		};
+
+		let checklist = checks(&keystore, &node_key, &spec);
+		assert_eq!(failed(&checklist), ["Aura key in keystore"]);
+		let mut out = Vec::new();
+		print_checklist(&checklist, &mut out).unwrap();
+		assert!(String::from_utf8(out).unwrap().contains("no aura key in"));
+
+		KeystoreContainer::new(&keystore)
+			.unwrap()
+			.keystore()
+			.sr25519_generate_new(AURA, Some("//Alice"))
+			.unwrap();
+		assert!(failed(&checks(&keystore, &node_key, &spec)).is_empty());
+		fs::remove_dir_all(dir).unwrap();
+	}
+}
@@ -0,0 +1,234 @@
+use std::{fmt, sync::Arc};
+
+use codec::{Decode, Encode};
+use cumulus_primitives_core::{ParachainBlockData, PersistedValidationData};
+use polkadot_primitives::MAX_POV_SIZE;
+use pop_runtime_common::{Block, Hash};
+use sc_cli::{CliConfiguration, DatabaseParams, Error, PruningParams, Result, SharedParams};
+use sc_client_api::{Backend, BlockBackend, StorageProvider};
+use sp_api::{ApiExt, Core, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::{storage::StorageKey, twox_128};
+use sp_runtime::{
+	generic::DigestItem,
+	traits::{BlakeTwo256, Block as BlockT, Header as _},
+};
+use sp_trie::StorageProof;
+
+/// The most a PoV may decompress to, as enforced by the relay chain.
+const POV_BOMB_LIMIT: usize = (MAX_POV_SIZE * 4) as usize;
+
+/// The `check-pov` command used to check that the PoV of a block is within the limits of the
+/// relay chain, e.g. to find out why a block produced under heavy load was never included.
+///
+/// The PoV is reconstructed by re-executing the block on the state of its parent while recording
+/// the storage proof, so the state of the parent must not be pruned.
+#[derive(Debug, clap::Parser)]
+pub struct CheckPovCmd {
+	/// The hash of the block to check.
+	#[arg(value_name = "BLOCK_HASH")]
+	pub block: Hash,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl CheckPovCmd {
+	/// Run the `check-pov` command, printing the size of the PoV of the block against the
+	/// largest PoV the relay chain accepts.
+	pub async fn run<B, C>(&self, client: Arc<C>) -> Result<()>
+	where
+		B: Backend<Block>,
+		C: ProvideRuntimeApi<Block> + BlockBackend<Block> + HeaderBackend<Block>,
+		C: StorageProvider<Block, B>,
+		C::Api: Core<Block>,
+	{
+		let pov = pov_size(&*client, self.block)?;
+		println!("{pov}");
+		if !pov.accepted() {
+			return Err(Error::Input(format!(
+				"The PoV of {} exceeds the maximum PoV size of the relay chain",
+				self.block
+			)));
+		}
+		Ok(())
+	}
+}
+
+impl CliConfiguration for CheckPovCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
+
+/// The size of the PoV of a block.
+#[derive(Debug, PartialEq)]
+struct PovSize {
+	/// The size of the encoded block data, i.e. the block along with its storage proof.
+	encoded: usize,
+	/// The size of the PoV submitted to the relay chain, i.e. the compressed block data.
+	compressed: usize,
+	/// The maximum PoV size of the relay chain.
+	max: u32,
+	/// Whether `max` was read from the validation data of the block, rather than defaulted to
+	/// [`MAX_POV_SIZE`].
+	max_from_relay: bool,
+}
+
+impl PovSize {
+	/// The size of the PoV of the block with `header` and `extrinsics`, accessing the state with
+	/// root `parent_state_root` as recorded by `proof`.
+	fn new(
+		header: <Block as BlockT>::Header,
+		extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+		proof: StorageProof,
+		parent_state_root: Hash,
+		max_pov_size: Option<u32>,
+	) -> Result<Self> {
+		let proof = proof
+			.into_compact_proof::<BlakeTwo256>(parent_state_root)
+			.map_err(|e| Error::Application(format!("Invalid storage proof: {e:?}").into()))?;
+		let encoded = ParachainBlockData::<Block>::new(header, extrinsics, proof).encode();
+		let compressed = sp_maybe_compressed_blob::compress(&encoded, POV_BOMB_LIMIT)
+			.ok_or_else(|| Error::Input("The PoV exceeds the decompression limit".into()))?;
+		Ok(Self {
+			encoded: encoded.len(),
+			// As encoded in the PoV, prefixed by its length.
+			compressed: compressed.encoded_size(),
+			max: max_pov_size.unwrap_or(MAX_POV_SIZE),
+			max_from_relay: max_pov_size.is_some(),
+		})
+	}
+
+	/// Whether the relay chain accepts the PoV.
+	fn accepted(&self) -> bool {
+		self.compressed <= self.max as usize
+	}
+}
+
+impl fmt::Display for PovSize {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let percent = self.compressed as f64 * 100.0 / self.max as f64;
+		writeln!(f, "Block data: {} bytes", self.encoded)?;
+		writeln!(
+			f,
+			"PoV: {} bytes compressed, {percent:.1}% of the maximum PoV size of {} bytes{}",
+			self.compressed,
+			self.max,
+			if self.max_from_relay { "" } else { " (default)" },
+		)?;
+		write!(f, "{}", if self.accepted() { "Accepted" } else { "REJECTED: the PoV is too large" })
+	}
+}
+
+/// Reconstruct the PoV of the block `hash` by re-executing it on the state of its parent.
+fn pov_size<B, C>(client: &C, hash: Hash) -> Result<PovSize>
+where
+	B: Backend<Block>,
+	C: ProvideRuntimeApi<Block> + BlockBackend<Block> + HeaderBackend<Block>,
+	C: StorageProvider<Block, B>,
+	C::Api: Core<Block>,
+{
+	let block = client
+		.block(hash)?
+		.ok_or_else(|| Error::Input(format!("Block {hash} not found")))?
+		.block;
+	let (header, extrinsics) = block.deconstruct();
+	let parent = *header.parent_hash();
+	let parent_state_root = *client
+		.header(parent)?
+		.ok_or_else(|| Error::Input(format!("Parent {parent} of block {hash} not found")))?
+		.state_root();
+
+	// Blocks are executed without their seal, as on import.
+	let mut unsealed = header.clone();
+	if let Some(DigestItem::Seal(..)) = unsealed.digest().logs().last() {
+		unsealed.digest_mut().pop();
+	}
+	let mut api = client.runtime_api();
+	api.record_proof();
+	api.execute_block(parent, Block::new(unsealed, extrinsics.clone()))
+		.map_err(|e| Error::Application(Box::new(e)))?;
+	let proof = api
+		.extract_proof()
+		.ok_or_else(|| Error::Input("No storage proof was recorded".into()))?;
+
+	PovSize::new(header, extrinsics, proof, parent_state_root, max_pov_size(client, hash)?)
+}
+
+/// The maximum PoV size of the relay chain when the block `hash` was built, as stored in the
+/// validation data of `cumulus_pallet_parachain_system`.
+fn max_pov_size<B, C>(client: &C, hash: Hash) -> Result<Option<u32>>
+where
+	B: Backend<Block>,
+	C: StorageProvider<Block, B>,
+{
+	let key = StorageKey([twox_128(b"ParachainSystem"), twox_128(b"ValidationData")].concat());
+	let Some(data) = client.storage(hash, &key)? else { return Ok(None) };
+	let data = PersistedValidationData::decode(&mut &data.0[..])
+		.map_err(|e| Error::Input(format!("Invalid validation data: {e}")))?;
+	Ok(Some(data.max_pov_size))
+}
+
+#[cfg(test)]
+mod tests {
+	use sp_runtime::{traits::Hash as _, BuildStorage, StateVersion};
+	use sp_trie::{LayoutV1, MemoryDB, TrieDBMutBuilder, TrieMut};
+
+	use super::*;
+	use crate::chain_spec::{development_config, Code, Relay};
+
+	/// The header of the dev genesis block, along with a proof of the whole genesis state.
+	fn dev_genesis() -> (<Block as BlockT>::Header, StorageProof, Hash) {
+		let spec = development_config(Relay::PaseoLocal, None, vec![], vec![], Code::Compressed);
+		let storage = spec.build_storage().unwrap();
+		let mut db = MemoryDB::<BlakeTwo256>::default();
+		let mut root = Hash::default();
+		{
+			let mut trie =
+				TrieDBMutBuilder::<LayoutV1<BlakeTwo256>>::new(&mut db, &mut root).build();
+			for (key, value) in &storage.top {
+				trie.insert(key, value).unwrap();
+			}
+		}
+		let state_root =
+			BlakeTwo256::trie_root(storage.top.into_iter().collect(), StateVersion::V1);
+		assert_eq!(root, state_root);
+		let header = sc_chain_spec::construct_genesis_block::<Block>(root, StateVersion::V1).header;
+		let proof = StorageProof::new(db.drain().into_values().map(|(node, _)| node));
+		(header, proof, root)
+	}
+
+	#[test]
+	fn pov_size_of_dev_block_is_reported() {
+		let (header, proof, root) = dev_genesis();
+		let pov = PovSize::new(header.clone(), vec![], proof.clone(), root, None).unwrap();
+		assert!(pov.compressed > 0);
+		assert_eq!(pov.max, MAX_POV_SIZE);
+		assert!(pov.accepted());
+		assert!(pov.to_string().contains("(default)"));
+
+		// A relay chain with a smaller limit rejects the block.
+		let limit = Some(pov.compressed as u32 - 1);
+		let pov = PovSize::new(header, vec![], proof, root, limit).unwrap();
+		assert!(!pov.accepted());
+		assert!(pov.to_string().contains("REJECTED"));
+	}
+}
@@ -0,0 +1,156 @@
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+
+use codec::Encode;
+use pop_runtime_common::{AccountId, AuraId};
+use sc_cli::{Error, Result};
+use sc_network::PeerId;
+use sc_service::ChainSpec;
+use sp_core::{crypto::Ss58Codec, hexdisplay::HexDisplay, sr25519, Pair};
+
+use super::GenerateNodeKeyCmd;
+use crate::chain_spec::{pop_mainnet_session_keys, Extensions};
+
+/// The file of the bundle holding the hex encoded node key.
+const NODE_KEY: &str = "node-key";
+/// The file of the bundle holding the hex encoded session keys.
+const SESSION_KEYS: &str = "session-keys";
+/// The file of the bundle holding the address of the collator account.
+const ACCOUNT: &str = "account";
+/// The file of the bundle holding the systemd unit launching the collator.
+const SERVICE: &str = "pop-collator.service";
+
+/// The `collator-bundle` command used to generate everything a new collator needs in one go: its
+/// node key, session keys, account and a systemd unit launching it on the chain.
+#[derive(Debug, clap::Parser)]
+pub struct CollatorBundleCmd {
+	/// The secret seed, e.g. a mnemonic, to derive the aura key and account of the collator from,
+	/// as well as its node key. It must be kept secret, as it controls the account.
+	#[arg(long, value_name = "SEED")]
+	pub seed: String,
+
+	/// The directory to write the bundle to. It is created if missing.
+	#[arg(long, value_name = "DIR")]
+	pub out: PathBuf,
+
+	/// The chain the collator is launched on. Either one of the built-in chain ids or a path to a
+	/// chain specification file.
+	#[arg(long, value_name = "CHAIN_SPEC", default_value = "pop")]
+	pub chain: String,
+}
+
+/// What a collator is registered with.
+#[derive(Debug, PartialEq)]
+struct Bundle {
+	/// The peer id of the node key.
+	peer_id: PeerId,
+	/// The account of the collator.
+	account: AccountId,
+	/// The encoded session keys, to be set by the account.
+	session_keys: Vec<u8>,
+}
+
+impl CollatorBundleCmd {
+	/// Run the `collator-bundle` command against the loaded chain specification, printing the
+	/// peer id and account to register the collator with.
+	pub fn run(&self, spec: &dyn ChainSpec) -> Result<()> {
+		let bundle = self.generate(spec)?;
+		println!("Bundle written to {}", self.out.display());
+		println!("Peer id: {}", bundle.peer_id);
+		println!("Account: {}", bundle.account.to_ss58check());
+		println!("Session keys: 0x{}", HexDisplay::from(&bundle.session_keys));
+		Ok(())
+	}
+
+	/// Write the bundle of the collator to the output directory.
+	fn generate(&self, spec: &dyn ChainSpec) -> Result<Bundle> {
+		let relay_chain = Extensions::try_get(spec)
+			.map(|extensions| extensions.relay_chain.clone())
+			.ok_or_else(|| Error::Input("The chain specification is not a parachain".into()))?;
+		let pair = sr25519::Pair::from_string(&self.seed, None)
+			.map_err(|e| Error::Input(format!("Invalid seed: {e:?}")))?;
+		let account = AccountId::from(pair.public());
+		// The session keys of all runtimes only consist of the aura key.
+		let session_keys = pop_mainnet_session_keys(AuraId::from(pair.public())).encode();
+
+		fs::create_dir_all(&self.out)
+			.map_err(|e| Error::Input(format!("Failed to create {}: {e}", self.out.display())))?;
+		let node_key = self.out.join(NODE_KEY);
+		let peer_id = GenerateNodeKeyCmd { from_seed: self.seed.clone(), file: node_key.clone() }
+			.generate()?;
+		let write = |file: &str, contents: String| {
+			let path = self.out.join(file);
+			fs::write(&path, contents)
+				.map_err(|e| Error::Input(format!("Failed to write {}: {e}", path.display())))
+		};
+		write(SESSION_KEYS, format!("0x{}\n", HexDisplay::from(&session_keys)))?;
+		write(ACCOUNT, format!("{}\n", account.to_ss58check()))?;
+		write(SERVICE, service(&self.chain, &node_key, &relay_chain))?;
+		Ok(Bundle { peer_id, account, session_keys })
+	}
+}
+
+/// A systemd unit launching a collator of `chain` with the node key at `node_key`, along with the
+/// steps left to the operator.
+fn service(chain: &str, node_key: &Path, relay_chain: &str) -> String {
+	format!(
+		"# Insert the aura key of the collator into its keystore before starting the service:\n\
+		 #   pop-node key insert --chain {chain} --base-path /var/lib/pop --key-type aura \
+		 --scheme sr25519 --suri <SEED>\n\
+		 # Once synced, set the keys in `session-keys` with `session.setKeys` from the account in \
+		 `account` and register it as a candidate.\n\
+		 [Unit]\n\
+		 Description=Pop collator\n\
+		 After=network-online.target\n\
+		 Wants=network-online.target\n\
+		 \n\
+		 [Service]\n\
+		 ExecStart=/usr/local/bin/pop-node --chain {chain} --collator --base-path /var/lib/pop \
+		 --node-key-file {} -- --chain {relay_chain}\n\
+		 Restart=always\n\
+		 RestartSec=10\n\
+		 \n\
+		 [Install]\n\
+		 WantedBy=multi-user.target\n",
+		node_key.display()
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use codec::Decode;
+	use sc_network::config::ed25519;
+
+	use super::*;
+	use crate::chain_spec::{development_config, Code, Relay};
+
+	#[test]
+	fn bundle_holds_node_key_and_session_keys() {
+		let out = std::env::temp_dir().join(format!("pop-collator-bundle-{}", std::process::id()));
+		let spec = development_config(Relay::PaseoLocal, None, vec![], vec![], Code::Compressed);
+		let cmd =
+			CollatorBundleCmd { seed: "//Alice".into(), out: out.clone(), chain: "dev".into() };
+		let bundle = cmd.generate(&spec).unwrap();
+
+		let node_key = sp_core::bytes::from_hex(&fs::read_to_string(out.join(NODE_KEY)).unwrap());
+		let keypair =
+			ed25519::Keypair::from(ed25519::SecretKey::try_from_bytes(node_key.unwrap()).unwrap());
+		assert_eq!(keypair.public().to_peer_id(), bundle.peer_id);
+
+		let session_keys = fs::read_to_string(out.join(SESSION_KEYS)).unwrap();
+		let session_keys = sp_core::bytes::from_hex(session_keys.trim()).unwrap();
+		let keys = pop_runtime_mainnet::SessionKeys::decode(&mut &session_keys[..]).unwrap();
+		let alice = sr25519::Pair::from_string("//Alice", None).unwrap().public();
+		assert_eq!(keys.aura, AuraId::from(alice));
+		assert_eq!(bundle.account, AccountId::from(alice));
+
+		let service = fs::read_to_string(out.join(SERVICE)).unwrap();
+		assert!(service.contains("--chain dev --collator"));
+		assert!(service.contains("-- --chain paseo-local"));
+		// The bundle is deterministic.
+		assert_eq!(cmd.generate(&spec).unwrap(), bundle);
+		fs::remove_dir_all(out).unwrap();
+	}
+}
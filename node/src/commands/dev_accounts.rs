@@ -0,0 +1,86 @@
+use std::{fs, path::PathBuf};
+
+use sc_cli::{Error, Result};
+
+use crate::chain_spec::accounts::{self, DevAccount};
+
+/// The `key dev-accounts` command used to list deterministic development accounts, along with
+/// their secret URIs and keys, as a JSON manifest for test tooling, e.g. to sign with the keys of
+/// the collators of a large zombienet network.
+#[derive(Debug, clap::Parser)]
+pub struct DevAccountsCmd {
+	/// The number of well-known development accounts to list: Alice, Bob, Charlie, Dave, Eve and
+	/// Ferdie, followed by their stash accounts.
+	#[arg(
+		long,
+		value_name = "COUNT",
+		default_value_t = 6,
+		value_parser = clap::value_parser!(u8).range(0..=accounts::WELL_KNOWN.len() as i64),
+	)]
+	pub well_known: u8,
+
+	/// The number of accounts to list derived from `--name`, as `//<name>/0` and onwards.
+	#[arg(long, value_name = "COUNT", default_value_t = 0)]
+	pub numbered: u32,
+
+	/// The name the numbered accounts are derived from.
+	#[arg(long, value_name = "NAME", default_value = "collator")]
+	pub name: String,
+
+	/// The file to write the manifest to, instead of printing it.
+	#[arg(long, value_name = "PATH")]
+	pub output: Option<PathBuf>,
+}
+
+impl DevAccountsCmd {
+	/// Run the `key dev-accounts` command, printing or writing the manifest of the accounts.
+	pub fn run(&self) -> Result<()> {
+		let manifest = serde_json::to_string_pretty(&accounts::manifest(&self.accounts()?))
+			.map_err(|e| Error::Application(Box::new(e)))?;
+		match &self.output {
+			Some(path) => fs::write(path, manifest)
+				.map_err(|e| Error::Input(format!("Failed to write {}: {e}", path.display()))),
+			None => {
+				println!("{manifest}");
+				Ok(())
+			},
+		}
+	}
+
+	/// The well-known accounts followed by the numbered ones.
+	fn accounts(&self) -> Result<Vec<DevAccount>> {
+		let mut listed = accounts::well_known(self.well_known.into()).map_err(Error::Input)?;
+		listed.extend(accounts::numbered(&self.name, self.numbered));
+		Ok(listed)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use clap::Parser;
+
+	use super::*;
+
+	#[test]
+	fn well_known_accounts_are_followed_by_numbered_ones() {
+		let cmd = |args: &[&str]| {
+			DevAccountsCmd::try_parse_from([&["dev-accounts"][..], args].concat()).map(|cmd| {
+				cmd.accounts()
+					.unwrap()
+					.into_iter()
+					.map(|account| account.suri)
+					.collect::<Vec<_>>()
+			})
+		};
+		assert_eq!(cmd(&[]).unwrap().len(), 6);
+		assert_eq!(
+			cmd(&["--well-known", "1", "--numbered", "2"]).unwrap(),
+			["//Alice", "//collator/0", "//collator/1"]
+		);
+		assert_eq!(
+			cmd(&["--well-known", "0", "--numbered", "1", "--name", "user"]).unwrap(),
+			["//user/0"]
+		);
+		assert!(cmd(&["--well-known", "13"]).is_err());
+	}
+}
@@ -0,0 +1,236 @@
+use std::{collections::BTreeMap, fmt, io::Write};
+
+use sc_cli::{Error, Result};
+use sc_service::ChainSpec;
+use serde_json::Value;
+use sp_core::{
+	hashing::{blake2_256, twox_128},
+	hexdisplay::HexDisplay,
+};
+
+/// Strings longer than this, such as the runtime code, are displayed as their length and hash.
+const MAX_DISPLAYED_LEN: usize = 80;
+
+/// The `diff-spec` command used to print the differences between two chain specifications, e.g.
+/// before promoting a change to a chain specification.
+#[derive(Debug, clap::Parser)]
+pub struct DiffSpecCmd {
+	/// The chain specification to compare against. Either one of the built-in chain ids or a path
+	/// to a plain or raw chain specification file.
+	#[arg(value_name = "OLD")]
+	pub old: String,
+
+	/// The chain specification to compare. Either one of the built-in chain ids or a path to a
+	/// plain or raw chain specification file.
+	#[arg(value_name = "NEW")]
+	pub new: String,
+}
+
+impl DiffSpecCmd {
+	/// Run the `diff-spec` command against the already loaded chain specifications.
+	pub fn run(&self, old: &dyn ChainSpec, new: &dyn ChainSpec) -> Result<()> {
+		let differences = diff(&normalise(&to_json(old)?), &normalise(&to_json(new)?));
+		report(&differences, &mut std::io::stdout().lock())
+	}
+}
+
+/// A change of a value between two chain specifications.
+#[derive(Debug, PartialEq)]
+enum Change {
+	Added(Value),
+	Removed(Value),
+	Modified(Value, Value),
+}
+
+/// A change of the value at `path` between two chain specifications.
+#[derive(Debug, PartialEq)]
+struct Difference {
+	path: String,
+	change: Change,
+}
+
+impl Difference {
+	/// Whether the change alters the identity or control of the chain, i.e. its para id, relay
+	/// chain or sudo key.
+	fn is_dangerous(&self) -> bool {
+		let raw_sudo_key = format!(
+			"genesis.raw.top.0x{}",
+			HexDisplay::from(&[twox_128(b"Sudo"), twox_128(b"Key")].concat())
+		);
+		["para_id", "relay_chain", "genesis.sudo", &raw_sudo_key]
+			.iter()
+			.any(|prefix| self.path.starts_with(prefix))
+	}
+}
+
+impl fmt::Display for Difference {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let marker = if self.is_dangerous() { "!" } else { " " };
+		match &self.change {
+			Change::Added(new) => write!(f, "{marker}+ {}: {}", self.path, Displayed(new)),
+			Change::Removed(old) => write!(f, "{marker}- {}: {}", self.path, Displayed(old)),
+			Change::Modified(old, new) =>
+				write!(f, "{marker}~ {}: {} -> {}", self.path, Displayed(old), Displayed(new)),
+		}
+	}
+}
+
+/// A value displayed in a single line, abbreviating long strings.
+struct Displayed<'a>(&'a Value);
+
+impl fmt::Display for Displayed<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self.0 {
+			Value::String(s) if s.len() > MAX_DISPLAYED_LEN => write!(
+				f,
+				"<{} bytes, blake2 0x{}>",
+				s.len(),
+				HexDisplay::from(&blake2_256(s.as_bytes()))
+			),
+			value => write!(f, "{value}"),
+		}
+	}
+}
+
+/// The plain json of `spec`.
+fn to_json(spec: &dyn ChainSpec) -> Result<Value> {
+	serde_json::from_str(&spec.as_json(false)?)
+		.map_err(|e| Error::Input(format!("Invalid chain spec json: {e}")))
+}
+
+/// Flatten the chain specification `json` into its values by path, so that specifications can be
+/// compared regardless of their formatting, the casing of their extensions or whether their
+/// genesis is a patch or a full genesis config.
+fn normalise(json: &Value) -> BTreeMap<String, Value> {
+	let mut values = BTreeMap::new();
+	for (path, keys) in [
+		("name", &["name"][..]),
+		("id", &["id"]),
+		("chain_type", &["chainType"]),
+		("protocol_id", &["protocolId"]),
+		("boot_nodes", &["bootNodes"]),
+		("relay_chain", &["relay_chain", "relayChain", "RelayChain"]),
+		("para_id", &["para_id", "paraId", "ParaId"]),
+		("properties", &["properties"]),
+	] {
+		if let Some(value) = keys.iter().find_map(|key| json.get(key)) {
+			flatten(path.into(), value, &mut values);
+		}
+	}
+
+	let genesis = &json["genesis"];
+	let runtime_genesis = &genesis["runtimeGenesis"];
+	if let Some(code) = runtime_genesis.get("code") {
+		values.insert("code".into(), code.clone());
+	}
+	match ["patch", "config"].iter().find_map(|variant| runtime_genesis.get(variant)) {
+		Some(config) => flatten("genesis".into(), config, &mut values),
+		None => flatten("genesis".into(), genesis, &mut values),
+	}
+	values
+}
+
+/// Insert the values of `value` into `values`, keyed by their path below `path`. Arrays are
+/// compared as a whole, e.g. the list of invulnerables.
+fn flatten(path: String, value: &Value, values: &mut BTreeMap<String, Value>) {
+	match value {
+		Value::Object(object) if !object.is_empty() =>
+			for (key, value) in object {
+				flatten(format!("{path}.{key}"), value, values);
+			},
+		value => {
+			values.insert(path, value.clone());
+		},
+	}
+}
+
+/// The differences between the normalised chain specifications `old` and `new`, ordered by path.
+fn diff(old: &BTreeMap<String, Value>, new: &BTreeMap<String, Value>) -> Vec<Difference> {
+	let mut differences = Vec::new();
+	for (path, old_value) in old {
+		let change = match new.get(path) {
+			None => Change::Removed(old_value.clone()),
+			Some(new_value) if new_value != old_value =>
+				Change::Modified(old_value.clone(), new_value.clone()),
+			Some(_) => continue,
+		};
+		differences.push(Difference { path: path.clone(), change });
+	}
+	for (path, new_value) in new {
+		if !old.contains_key(path) {
+			differences
+				.push(Difference { path: path.clone(), change: Change::Added(new_value.clone()) });
+		}
+	}
+	differences.sort_by(|a, b| a.path.cmp(&b.path));
+	differences
+}
+
+/// Write the `differences` to `out`, followed by a warning if any of them is dangerous.
+fn report(differences: &[Difference], out: &mut impl Write) -> Result<()> {
+	if differences.is_empty() {
+		writeln!(out, "No differences.")?;
+		return Ok(());
+	}
+	for difference in differences {
+		writeln!(out, "{difference}")?;
+	}
+	let dangerous = differences.iter().filter(|d| d.is_dangerous()).count();
+	if dangerous > 0 {
+		writeln!(
+			out,
+			"\nWARNING: {dangerous} change(s) marked with `!` alter the para id, relay chain or \
+			 sudo key of the chain."
+		)?;
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::chain_spec::{development_config, Code, Relay};
+
+	#[test]
+	fn modified_candidacy_bond_is_reported() {
+		let old =
+			to_json(&development_config(Relay::PaseoLocal, None, vec![], vec![], Code::Compressed))
+				.unwrap();
+		let mut new = old.clone();
+		let collator_selection =
+			&mut new["genesis"]["runtimeGenesis"]["patch"]["collatorSelection"];
+		let old_bond = collator_selection["candidacyBond"].clone();
+		let new_bond: Value = (old_bond.as_u64().unwrap() * 2).into();
+		collator_selection["candidacyBond"] = new_bond.clone();
+
+		let differences = diff(&normalise(&old), &normalise(&new));
+		assert_eq!(
+			differences,
+			vec![Difference {
+				path: "genesis.collatorSelection.candidacyBond".into(),
+				change: Change::Modified(old_bond, new_bond),
+			}]
+		);
+		assert!(!differences[0].is_dangerous());
+		assert!(diff(&normalise(&old), &normalise(&old)).is_empty());
+	}
+
+	#[test]
+	fn para_id_and_sudo_changes_are_dangerous() {
+		let old =
+			to_json(&development_config(Relay::PaseoLocal, None, vec![], vec![], Code::Compressed))
+				.unwrap();
+		let mut new = old.clone();
+		new["para_id"] = 4002.into();
+		new["genesis"]["runtimeGenesis"]["patch"]["sudo"]["key"] = Value::Null;
+
+		let differences = diff(&normalise(&old), &normalise(&new));
+		let paths: Vec<_> = differences.iter().map(|d| d.path.as_str()).collect();
+		assert_eq!(paths, ["genesis.sudo.key", "para_id"]);
+		assert!(differences.iter().all(Difference::is_dangerous));
+
+		let mut out = Vec::new();
+		report(&differences, &mut out).unwrap();
+		assert!(String::from_utf8(out).unwrap().contains("WARNING: 2 change(s)"));
+	}
+}
@@ -0,0 +1,366 @@
+use std::{io, io::Write, path::Path, time::Duration};
+
+use hyper::{header::CONTENT_TYPE, Body, Client, Request, Uri};
+use sc_cli::{CliConfiguration, Error, KeystoreParams, NodeKeyParams, Result, SharedParams};
+use sc_network::config::NodeKeyConfig;
+use sc_service::{config::KeystoreConfig, ChainSpec, Configuration};
+use serde_json::Value;
+use sp_runtime::BuildStorage;
+
+use super::check_collator;
+use crate::finality_webhook::parse_url;
+
+/// The free disk space below which the database is bound to run out of space soon.
+const MIN_FREE_DISK_SPACE: u64 = 10 << 30;
+/// The free disk space recommended for the database to grow into.
+const RECOMMENDED_FREE_DISK_SPACE: u64 = 100 << 30;
+/// The open file limit recommended by Substrate, below which the database may run out of file
+/// descriptors.
+const RECOMMENDED_OPEN_FILES: u64 = 10_000;
+/// The longest the relay chain node is given to respond.
+const RELAY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The `doctor` command used to diagnose the environment and configuration of a node before it is
+/// launched, given the same chain, base path, keystore and node key options as the node.
+///
+/// The chain specification and keystore are checked as by `check-collator`, along with the disk
+/// space, open file limit and clock of the host and the connectivity to the relay chain.
+#[derive(Debug, clap::Parser)]
+pub struct DoctorCmd {
+	/// Whether the node is launched as a collator, in which case a missing collator configuration
+	/// fails rather than warns.
+	#[arg(long)]
+	pub collator: bool,
+
+	/// The plain `http` RPC endpoint of a relay chain node to check the connectivity to.
+	#[arg(long, value_name = "URL", value_parser = parse_url)]
+	pub relay_rpc_url: Option<Uri>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub keystore_params: KeystoreParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub node_key_params: NodeKeyParams,
+}
+
+impl DoctorCmd {
+	/// Run the `doctor` command, printing a pass/warn/fail report and failing if any check fails.
+	pub fn run(&self, config: &Configuration) -> Result<()> {
+		let relay_health = self
+			.relay_rpc_url
+			.as_ref()
+			.map(|url| config.tokio_handle.block_on(relay_health(url)));
+		let checks = self.checks(
+			config.base_path.path(),
+			&config.keystore,
+			&config.network.node_key,
+			&*config.chain_spec,
+			relay_health,
+		);
+		print_report(&checks, &mut io::stdout().lock())?;
+		if checks.iter().any(|(_, status)| matches!(status, Status::Fail(_))) {
+			return Err(Error::Input("The node is not ready to be launched".into()));
+		}
+		Ok(())
+	}
+
+	/// Check the host, chain specification, keystore and node key a node would be launched with,
+	/// along with the relay chain node reporting `relay_health`, if any.
+	fn checks(
+		&self,
+		base_path: &Path,
+		keystore: &KeystoreConfig,
+		node_key: &NodeKeyConfig,
+		spec: &dyn ChainSpec,
+		relay_health: Option<std::result::Result<Value, String>>,
+	) -> Vec<Check> {
+		let mut checks = vec![
+			("Free disk space", check_disk_space(base_path)),
+			("Open file limit", check_open_files()),
+			("Clock synchronized", check_clock()),
+			("Chain spec genesis", check_genesis(spec)),
+		];
+		checks.extend(check_collator::checks(keystore, node_key, spec).into_iter().map(
+			|(description, result)| {
+				let status = match result {
+					Ok(()) => Status::Pass,
+					Err(e) if self.collator => Status::Fail(e),
+					Err(e) => Status::Warn(e),
+				};
+				(description, status)
+			},
+		));
+		checks.push(("Relay chain connectivity", check_relay(relay_health)));
+		checks
+	}
+}
+
+impl CliConfiguration for DoctorCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn keystore_params(&self) -> Option<&KeystoreParams> {
+		Some(&self.keystore_params)
+	}
+
+	fn node_key_params(&self) -> Option<&NodeKeyParams> {
+		Some(&self.node_key_params)
+	}
+}
+
+/// The outcome of a check, along with why it did not pass if it did not.
+#[derive(Clone, Debug, PartialEq)]
+enum Status {
+	Pass,
+	Warn(String),
+	Fail(String),
+}
+
+/// A description of a check, along with its outcome.
+type Check = (&'static str, Status);
+
+/// Ensure there is enough free disk space for the database at `base_path`.
+fn check_disk_space(base_path: &Path) -> Status {
+	let gib = |bytes: u64| bytes >> 30;
+	match free_disk_space(base_path) {
+		Ok(free) if free < MIN_FREE_DISK_SPACE => Status::Fail(format!(
+			"{} GiB free at {}, the database needs at least {} GiB",
+			gib(free),
+			base_path.display(),
+			gib(MIN_FREE_DISK_SPACE)
+		)),
+		Ok(free) if free < RECOMMENDED_FREE_DISK_SPACE => Status::Warn(format!(
+			"{} GiB free at {}, {} GiB are recommended",
+			gib(free),
+			base_path.display(),
+			gib(RECOMMENDED_FREE_DISK_SPACE)
+		)),
+		Ok(_) => Status::Pass,
+		Err(e) => Status::Warn(format!("failed to query {}: {e}", base_path.display())),
+	}
+}
+
+/// The disk space available at `path`, or at its closest existing ancestor as the base path is
+/// only created on launch.
+#[cfg(unix)]
+#[allow(clippy::unnecessary_cast)]
+fn free_disk_space(path: &Path) -> io::Result<u64> {
+	use std::{ffi::CString, mem::MaybeUninit, os::unix::ffi::OsStrExt};
+
+	let path = path.ancestors().find(|path| path.exists()).unwrap_or(Path::new("."));
+	let path = CString::new(path.as_os_str().as_bytes()).map_err(io::Error::other)?;
+	let mut stat = MaybeUninit::<libc::statvfs>::uninit();
+	// SAFETY: `path` is a nul-terminated string and `stat` is only read once initialized.
+	if unsafe { libc::statvfs(path.as_ptr(), stat.as_mut_ptr()) } != 0 {
+		return Err(io::Error::last_os_error());
+	}
+	let stat = unsafe { stat.assume_init() };
+	Ok(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(not(unix))]
+fn free_disk_space(_: &Path) -> io::Result<u64> {
+	Err(io::ErrorKind::Unsupported.into())
+}
+
+/// Ensure the process may open as many files as recommended, as its open file limit is raised to
+/// the hard limit on launch.
+fn check_open_files() -> Status {
+	match open_file_limit() {
+		Ok(limit) if limit < RECOMMENDED_OPEN_FILES => Status::Warn(format!(
+			"{limit} open files allowed, {RECOMMENDED_OPEN_FILES} are recommended, raise it with \
+			 `ulimit -Hn`"
+		)),
+		Ok(_) => Status::Pass,
+		Err(e) => Status::Warn(format!("failed to query the open file limit: {e}")),
+	}
+}
+
+/// The hard limit of open files of the process.
+#[cfg(unix)]
+#[allow(clippy::unnecessary_cast)]
+fn open_file_limit() -> io::Result<u64> {
+	let mut limit = libc::rlimit { rlim_cur: 0, rlim_max: 0 };
+	// SAFETY: `limit` is a valid `rlimit` to be written to.
+	if unsafe { libc::getrlimit(libc::RLIMIT_NOFILE, &mut limit) } != 0 {
+		return Err(io::Error::last_os_error());
+	}
+	Ok(limit.rlim_max as u64)
+}
+
+#[cfg(not(unix))]
+fn open_file_limit() -> io::Result<u64> {
+	Err(io::ErrorKind::Unsupported.into())
+}
+
+/// Ensure the clock is synchronized, e.g. via NTP, as blocks are authored in slots of time.
+fn check_clock() -> Status {
+	match clock_synchronized() {
+		Ok(true) => Status::Pass,
+		Ok(false) => Status::Fail(
+			"the clock is not synchronized, so blocks may be authored in the wrong slots".into(),
+		),
+		Err(e) => Status::Warn(format!("failed to query the clock: {e}")),
+	}
+}
+
+/// Whether the kernel reports the clock as synchronized.
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+fn clock_synchronized() -> io::Result<bool> {
+	// SAFETY: a zeroed `timex` is valid and, as its `modes` are unset, only queries the clock.
+	let mut timex: libc::timex = unsafe { std::mem::zeroed() };
+	match unsafe { libc::ntp_adjtime(&mut timex) } {
+		-1 => Err(io::Error::last_os_error()),
+		state => Ok(state != libc::TIME_ERROR),
+	}
+}
+
+#[cfg(not(all(target_os = "linux", target_env = "gnu")))]
+fn clock_synchronized() -> io::Result<bool> {
+	Err(io::ErrorKind::Unsupported.into())
+}
+
+/// Ensure the genesis of `spec` can be built, i.e. that the chain specification is valid for its
+/// runtime.
+fn check_genesis(spec: &dyn ChainSpec) -> Status {
+	match spec.as_storage_builder().build_storage() {
+		Ok(_) => Status::Pass,
+		Err(e) => Status::Fail(format!("the genesis of {} cannot be built: {e}", spec.id())),
+	}
+}
+
+/// Query the health of the relay chain node at `url`.
+async fn relay_health(url: &Uri) -> std::result::Result<Value, String> {
+	let body = serde_json::json!({
+		"jsonrpc": "2.0",
+		"id": 1,
+		"method": "system_health",
+		"params": [],
+	});
+	let request = Request::post(url.clone())
+		.header(CONTENT_TYPE, "application/json")
+		.body(Body::from(body.to_string()))
+		.expect("request is valid; qed");
+	let response = tokio::time::timeout(RELAY_TIMEOUT, Client::new().request(request))
+		.await
+		.map_err(|_| format!("{url} did not respond within {RELAY_TIMEOUT:?}"))?
+		.map_err(|e| format!("{url} is unreachable: {e}"))?;
+	let body = hyper::body::to_bytes(response.into_body())
+		.await
+		.map_err(|e| format!("failed to read the response of {url}: {e}"))?;
+	let response: Value =
+		serde_json::from_slice(&body).map_err(|e| format!("invalid response of {url}: {e}"))?;
+	match response.get("result") {
+		Some(health) => Ok(health.clone()),
+		None => Err(format!("{url} failed to report its health: {}", response["error"])),
+	}
+}
+
+/// Ensure the relay chain node reporting `health` is synced with peers.
+fn check_relay(health: Option<std::result::Result<Value, String>>) -> Status {
+	let health = match health {
+		None => return Status::Warn("skipped, pass `--relay-rpc-url` to check it".into()),
+		Some(Err(e)) => return Status::Fail(e),
+		Some(Ok(health)) => health,
+	};
+	if health["peers"].as_u64().unwrap_or_default() == 0 {
+		return Status::Warn("the relay chain node has no peers".into());
+	}
+	if health["isSyncing"].as_bool().unwrap_or_default() {
+		return Status::Warn("the relay chain node is syncing".into());
+	}
+	Status::Pass
+}
+
+/// Write `checks` to `out` as a report, followed by the number of checks of each outcome.
+fn print_report(checks: &[Check], out: &mut impl Write) -> Result<()> {
+	for (description, status) in checks {
+		let line = match status {
+			Status::Pass => color_print::cformat!("<green>pass</> {}", description),
+			Status::Warn(e) => color_print::cformat!("<yellow>warn</> {}: {}", description, e),
+			Status::Fail(e) => color_print::cformat!("<red>fail</> {}: {}", description, e),
+		};
+		writeln!(out, "{line}")?;
+	}
+	let count = |outcome: fn(&Status) -> bool| checks.iter().filter(|(_, s)| outcome(s)).count();
+	writeln!(
+		out,
+		"{} passed, {} warned, {} failed",
+		count(|s| matches!(s, Status::Pass)),
+		count(|s| matches!(s, Status::Warn(_))),
+		count(|s| matches!(s, Status::Fail(_)))
+	)?;
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use std::fs;
+
+	use clap::Parser;
+	use sc_network::config::Secret;
+	use serde_json::json;
+
+	use super::*;
+	use crate::chain_spec::{development_config, Code, Relay};
+
+	#[test]
+	fn reports_every_check_of_dev_setup() {
+		let dir = std::env::temp_dir().join(format!("pop-doctor-{}", std::process::id()));
+		fs::create_dir_all(&dir).unwrap();
+		let keystore = KeystoreConfig::Path { path: dir.join("keystore"), password: None };
+		let node_key = NodeKeyConfig::Ed25519(Secret::New);
+		let spec = development_config(Relay::PaseoLocal, None, vec![], vec![], Code::Compressed);
+		let checks = |args: &[&str], relay_health| {
+			let cmd = DoctorCmd::try_parse_from([&["doctor"][..], args].concat()).unwrap();
+			cmd.checks(&dir, &keystore, &node_key, &spec, relay_health)
+		};
+		let status = |checks: &[Check], description| {
+			checks.iter().find(|(d, _)| *d == description).map(|(_, status)| status.clone())
+		};
+
+		let report = checks(&[], None);
+		assert_eq!(
+			report.iter().map(|(description, _)| *description).collect::<Vec<_>>(),
+			[
+				"Free disk space",
+				"Open file limit",
+				"Clock synchronized",
+				"Chain spec genesis",
+				"Aura key in keystore",
+				"Node key",
+				"Parachain chain spec",
+				"Relay chain connectivity",
+			]
+		);
+		assert_eq!(status(&report, "Chain spec genesis"), Some(Status::Pass));
+		assert_eq!(status(&report, "Parachain chain spec"), Some(Status::Pass));
+		// A node which is not a collator needs no aura key.
+		assert!(matches!(status(&report, "Aura key in keystore"), Some(Status::Warn(_))));
+		assert!(matches!(status(&report, "Relay chain connectivity"), Some(Status::Warn(_))));
+		let mut out = Vec::new();
+		print_report(&report, &mut out).unwrap();
+		let out = String::from_utf8(out).unwrap();
+		assert_eq!(out.lines().count(), report.len() + 1);
+		assert!(out.contains("Relay chain connectivity: skipped"));
+
+		let report = checks(&["--collator"], Some(Err("unreachable".into())));
+		assert!(matches!(status(&report, "Aura key in keystore"), Some(Status::Fail(_))));
+		assert_eq!(
+			status(&report, "Relay chain connectivity"),
+			Some(Status::Fail("unreachable".into()))
+		);
+		let report = checks(&[], Some(Ok(json!({ "peers": 8, "isSyncing": false }))));
+		assert_eq!(status(&report, "Relay chain connectivity"), Some(Status::Pass));
+		let report = checks(&[], Some(Ok(json!({ "peers": 0, "isSyncing": true }))));
+		assert!(matches!(status(&report, "Relay chain connectivity"), Some(Status::Warn(_))));
+		fs::remove_dir_all(dir).unwrap();
+	}
+}
@@ -0,0 +1,143 @@
+use std::{
+	fs::File,
+	io::{BufWriter, Write},
+	path::PathBuf,
+	sync::Arc,
+};
+
+use codec::Decode;
+use pop_runtime_common::{AccountId, Balance, Block, Hash, Nonce};
+use sc_cli::{CliConfiguration, DatabaseParams, Error, PruningParams, Result, SharedParams};
+use sc_client_api::{Backend, StorageProvider};
+use sp_core::{crypto::Ss58Codec, hexdisplay::HexDisplay, storage::StorageKey, twox_128};
+
+/// The accounts as stored by `frame_system`.
+type AccountInfo = frame_system::AccountInfo<Nonce, pallet_balances::AccountData<Balance>>;
+
+/// The length of the prefix of the keys of `System::Account`, followed by the `blake2_128` hash of
+/// the account.
+const ACCOUNT_KEY_PREFIX_LEN: usize = 32 + 16;
+
+/// The `export-balances` command used to snapshot the balances of all accounts at a block, e.g.
+/// for airdrops and audits.
+///
+/// The accounts are streamed from the state of the block into a CSV file of
+/// `address,free,reserved,frozen` rows, so the state of the block must not be pruned.
+#[derive(Debug, clap::Parser)]
+pub struct ExportBalancesCmd {
+	/// The hash of the block to snapshot the balances at.
+	#[arg(value_name = "BLOCK_HASH")]
+	pub block: Hash,
+
+	/// The CSV file to write the balances to.
+	#[arg(long, value_name = "FILE")]
+	pub out: PathBuf,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl ExportBalancesCmd {
+	/// Run the `export-balances` command, printing the number of accounts exported.
+	pub async fn run<B, C>(&self, client: Arc<C>) -> Result<()>
+	where
+		B: Backend<Block>,
+		C: StorageProvider<Block, B>,
+	{
+		let prefix = StorageKey([twox_128(b"System"), twox_128(b"Account")].concat());
+		let pairs = client
+			.storage_pairs(self.block, Some(&prefix), None)?
+			.map(|(key, value)| (key.0, value.0));
+		let file = File::create(&self.out)
+			.map_err(|e| Error::Input(format!("Failed to create {}: {e}", self.out.display())))?;
+		let accounts = write_balances(pairs, BufWriter::new(file))?;
+		println!("Exported the balances of {accounts} accounts to {}", self.out.display());
+		Ok(())
+	}
+}
+
+impl CliConfiguration for ExportBalancesCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
+
+/// Write the balances of the `System::Account` entries in `pairs` to `out` as CSV, one account at
+/// a time, returning the number of accounts written.
+fn write_balances(
+	pairs: impl Iterator<Item = (Vec<u8>, Vec<u8>)>,
+	mut out: impl Write,
+) -> Result<usize> {
+	writeln!(out, "address,free,reserved,frozen")?;
+	let mut accounts = 0;
+	for (key, value) in pairs {
+		let account = key
+			.get(ACCOUNT_KEY_PREFIX_LEN..)
+			.and_then(|mut account| AccountId::decode(&mut account).ok())
+			.ok_or_else(|| {
+				Error::Input(format!("Invalid account key: 0x{}", HexDisplay::from(&key)))
+			})?;
+		let info = AccountInfo::decode(&mut &value[..])
+			.map_err(|e| Error::Input(format!("Invalid account {account}: {e}")))?;
+		writeln!(
+			out,
+			"{},{},{},{}",
+			account.to_ss58check(),
+			info.data.free,
+			info.data.reserved,
+			info.data.frozen
+		)?;
+		accounts += 1;
+	}
+	out.flush()?;
+	Ok(accounts)
+}
+
+#[cfg(test)]
+mod tests {
+	use sp_core::{sr25519, Pair};
+	use sp_runtime::BuildStorage;
+
+	use super::*;
+	use crate::chain_spec::minimal_test_spec;
+
+	#[test]
+	fn exporting_genesis_lists_endowed_accounts() {
+		let storage = minimal_test_spec().build_storage().unwrap();
+		let prefix = [twox_128(b"System"), twox_128(b"Account")].concat();
+		let pairs = storage.top.into_iter().filter(|(key, _)| key.starts_with(&prefix));
+		let mut csv = Vec::new();
+		let accounts = write_balances(pairs, &mut csv).unwrap();
+
+		let csv = String::from_utf8(csv).unwrap();
+		let mut rows = csv.lines();
+		assert_eq!(rows.next(), Some("address,free,reserved,frozen"));
+		let rows: Vec<_> = rows.collect();
+		assert_eq!(rows.len(), accounts);
+		for seed in ["Alice", "Bob", "Charlie", "Dave"] {
+			let account = AccountId::from(
+				sr25519::Pair::from_string(&format!("//{seed}"), None).unwrap().public(),
+			);
+			let row =
+				format!("{},{},0,0", account.to_ss58check(), 1_000 * pop_runtime_devnet::UNIT);
+			assert!(rows.contains(&row.as_str()), "{seed} should be listed as endowed");
+		}
+	}
+}
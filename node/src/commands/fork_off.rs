@@ -0,0 +1,502 @@
+use std::{collections::BTreeMap, fs, path::PathBuf, sync::Arc};
+
+use codec::{Decode, Encode};
+use hyper::Uri;
+use pop_runtime_common::{AccountId, Balance, Block, Hash};
+use sc_cli::{CliConfiguration, DatabaseParams, Error, PruningParams, Result, SharedParams};
+use sc_client_api::{Backend, StorageProvider};
+use sc_service::ChainSpec;
+use serde_json::{json, Map, Value};
+use sp_blockchain::HeaderBackend;
+use sp_core::{
+	crypto::key_types::AURA,
+	hexdisplay::HexDisplay,
+	storage::{
+		well_known_keys, ChildInfo, Storage, StorageChangeSet, StorageChild, StorageData,
+		StorageKey,
+	},
+	twox_128, twox_64, Bytes,
+};
+
+use super::rpc_client;
+use crate::{
+	chain_spec::{
+		account_key, accounts::DevAccount, storage_key, AccountInfo, DEFAULT_DEV_ENDOWMENT,
+	},
+	finality_webhook::parse_url,
+};
+
+/// The number of keys listed, and of entries read, per request to the node.
+const PAGE_SIZE: u32 = 1_000;
+
+/// The `fork-off` command used to fork the state of a live chain into a development chain, e.g. to
+/// reproduce a contract bug of mainnet or testnet locally with the real state.
+///
+/// The state of the selected pallets is read as of a block, either from a running node or from the
+/// database of the node, and replaces the one in the genesis of the chain specification of the live
+/// chain. The development account then becomes the sudo key and the only collator of the fork, and
+/// is endowed. The chain specification of the fork is raw and ready to run on a local relay chain.
+#[derive(Debug, clap::Parser)]
+pub struct ForkOffCmd {
+	/// The pallets whose state is forked, by name in the runtime. Only the accounts of `System`
+	/// are forked, as the rest of its state describes the blocks of the live chain, while the
+	/// storage of the contracts is forked along with `Contracts`.
+	#[arg(
+		long,
+		value_name = "PALLETS",
+		value_delimiter = ',',
+		default_value = "System,Balances,Assets,Contracts"
+	)]
+	pub pallets: Vec<String>,
+
+	/// The plain `http` RPC endpoint of a running node of the chain to fork, instead of reading
+	/// the database of the node.
+	#[arg(long, value_name = "URL", value_parser = parse_url)]
+	pub rpc_url: Option<Uri>,
+
+	/// The hash of the block whose state is forked. Defaults to the last finalized block.
+	#[arg(long, value_name = "BLOCK_HASH")]
+	pub at: Option<Hash>,
+
+	/// Fork the runtime of the live chain too, rather than running the runtime of the chain
+	/// specification.
+	#[arg(long)]
+	pub live_runtime: bool,
+
+	/// The seed of the development account which becomes the sudo key and the only collator of
+	/// the fork, e.g. `Alice` for `//Alice`.
+	#[arg(long, value_name = "SEED", default_value = "Alice")]
+	pub dev_account: String,
+
+	/// The amount the development account is endowed with, on top of its balance on the live
+	/// chain.
+	#[arg(long, value_name = "AMOUNT", default_value_t = DEFAULT_DEV_ENDOWMENT)]
+	pub endowment: Balance,
+
+	/// The relay chain the fork runs on.
+	#[arg(long, value_name = "CHAIN", default_value = "paseo-local")]
+	pub relay_chain: String,
+
+	/// The file to write the chain specification of the fork to, instead of stdout.
+	#[arg(long, short)]
+	pub output: Option<PathBuf>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+/// The state forked off the live chain.
+#[derive(Debug, Default)]
+struct Snapshot {
+	/// The entries of the main trie.
+	top: BTreeMap<Vec<u8>, Vec<u8>>,
+	/// The entries of the default child tries, by the unprefixed storage key of each.
+	children: BTreeMap<Vec<u8>, BTreeMap<Vec<u8>, Vec<u8>>>,
+}
+
+impl ForkOffCmd {
+	/// Run the `fork-off` command against the database of the node of the chain of `spec`.
+	pub async fn run<B, C>(&self, client: Arc<C>, spec: Box<dyn ChainSpec>) -> Result<()>
+	where
+		B: Backend<Block>,
+		C: StorageProvider<Block, B> + HeaderBackend<Block>,
+	{
+		let at = self.at.unwrap_or_else(|| client.info().finalized_hash);
+		let mut snapshot = Snapshot::default();
+		for prefix in self.prefixes() {
+			let pairs = client.storage_pairs(at, Some(&StorageKey(prefix)), None)?;
+			snapshot.top.extend(pairs.map(|(key, value)| (key.0, value.0)));
+		}
+		if self.forks_contracts() {
+			let prefix = StorageKey(well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX.to_vec());
+			for child in client.storage_keys(at, Some(&prefix), None)? {
+				let child_info = ChildInfo::new_default(&child.0[prefix.0.len()..]);
+				let mut entries = BTreeMap::new();
+				for key in client.child_storage_keys(at, child_info.clone(), None, None)? {
+					if let Some(value) = client.child_storage(at, &child_info, &key)? {
+						entries.insert(key.0, value.0);
+					}
+				}
+				snapshot.children.insert(child_info.storage_key().to_vec(), entries);
+			}
+		}
+		self.write(&*spec, snapshot, at)
+	}
+
+	/// Run the `fork-off` command against the running node at `url` of the chain of `spec`.
+	pub fn run_rpc(&self, url: &Uri, spec: &dyn ChainSpec) -> Result<()> {
+		let runtime = tokio::runtime::Builder::new_current_thread()
+			.enable_all()
+			.build()
+			.map_err(|e| Error::Application(Box::new(e)))?;
+		let (at, snapshot) = runtime.block_on(self.fetch(url))?;
+		self.write(spec, snapshot, at)
+	}
+
+	/// Fetch the state to fork from the node at `url`, along with the block it is the state of.
+	async fn fetch(&self, url: &Uri) -> Result<(Hash, Snapshot)> {
+		let at = match self.at {
+			Some(at) => at,
+			None => rpc_client::call(url, "chain_getFinalizedHead", [(); 0]).await?,
+		};
+		let mut snapshot = Snapshot::default();
+		for prefix in self.prefixes() {
+			let keys = list_keys(url, None, &prefix, at).await?;
+			for keys in keys.chunks(PAGE_SIZE as usize) {
+				let changes: Vec<StorageChangeSet<Hash>> =
+					rpc_client::call(url, "state_queryStorageAt", (keys, at)).await?;
+				let entries = changes.into_iter().flat_map(|set| set.changes);
+				snapshot
+					.top
+					.extend(entries.filter_map(|(key, value)| value.map(|value| (key.0, value.0))));
+			}
+		}
+		if self.forks_contracts() {
+			let prefix = well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX;
+			for child in list_keys(url, None, prefix, at).await? {
+				let mut entries = BTreeMap::new();
+				let keys = list_keys(url, Some(&child), &[], at).await?;
+				for keys in keys.chunks(PAGE_SIZE as usize) {
+					let values: Vec<Option<StorageData>> =
+						rpc_client::call(url, "childstate_getStorageEntries", (&child, keys, at))
+							.await?;
+					entries.extend(
+						keys.iter()
+							.zip(values)
+							.filter_map(|(key, value)| value.map(|value| (key.0.clone(), value.0))),
+					);
+				}
+				snapshot.children.insert(child.0[prefix.len()..].to_vec(), entries);
+			}
+		}
+		Ok((at, snapshot))
+	}
+
+	/// Write the chain specification forking `snapshot`, the state as of block `at`, off the chain
+	/// of `spec`.
+	fn write(&self, spec: &dyn ChainSpec, snapshot: Snapshot, at: Hash) -> Result<()> {
+		let mut storage = spec.as_storage_builder().build_storage().map_err(Error::Input)?;
+		let (entries, children) = (snapshot.top.len(), snapshot.children.len());
+		fork(&mut storage, snapshot, &self.prefixes());
+		let dev = DevAccount::from_seed(&self.dev_account);
+		let rekeyed = rekey(&mut storage, &dev);
+		endow(&mut storage, &dev.account_id, self.endowment)?;
+		let json = serde_json::to_string_pretty(&fork_spec(spec, &storage, &self.relay_chain)?)
+			.map_err(|e| Error::Input(format!("Invalid chain spec json: {e}")))?;
+		match &self.output {
+			Some(path) => {
+				fs::write(path, json)?;
+				println!(
+					"Forked {entries} entries and {children} child tries as of block {at:?} to {}",
+					path.display()
+				);
+				println!("Rewrote {} to {}", rekeyed.join(", "), dev.suri);
+			},
+			None => println!("{json}"),
+		}
+		Ok(())
+	}
+
+	/// The prefixes of the state forked: the storage of each pallet, except for `System` whose
+	/// accounts only are forked, along with the runtime if forked.
+	fn prefixes(&self) -> Vec<Vec<u8>> {
+		let mut prefixes: Vec<_> = self
+			.pallets
+			.iter()
+			.map(|pallet| match pallet.as_str() {
+				"System" => storage_key("System", "Account"),
+				pallet => twox_128(pallet.as_bytes()).to_vec(),
+			})
+			.collect();
+		if self.live_runtime {
+			prefixes.push(well_known_keys::CODE.to_vec());
+		}
+		prefixes
+	}
+
+	/// Whether the storage of the contracts, in default child tries, is forked.
+	fn forks_contracts(&self) -> bool {
+		self.pallets.iter().any(|pallet| pallet == "Contracts")
+	}
+}
+
+impl CliConfiguration for ForkOffCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
+
+/// The keys starting with `prefix` in the state as of block `at`, either in the main trie or in
+/// the default `child` trie, as listed page by page by the node at `url`.
+async fn list_keys(
+	url: &Uri,
+	child: Option<&Bytes>,
+	prefix: &[u8],
+	at: Hash,
+) -> Result<Vec<Bytes>> {
+	let prefix = Bytes(prefix.to_vec());
+	let mut keys: Vec<Bytes> = Vec::new();
+	loop {
+		let start = keys.last();
+		let page: Vec<Bytes> = match child {
+			Some(child) => {
+				let params = (child, &prefix, PAGE_SIZE, start, at);
+				rpc_client::call(url, "childstate_getKeysPaged", params).await?
+			},
+			None => {
+				let params = (&prefix, PAGE_SIZE, start, at);
+				rpc_client::call(url, "state_getKeysPaged", params).await?
+			},
+		};
+		let last = page.len() < PAGE_SIZE as usize;
+		keys.extend(page);
+		if last {
+			return Ok(keys);
+		}
+	}
+}
+
+/// The storage key of the entry of the `Twox64Concat` map `item` of `pallet` for the encoded `key`.
+fn twox_64_concat_key(pallet: &str, item: &str, key: &[u8]) -> Vec<u8> {
+	[storage_key(pallet, item), twox_64(key).to_vec(), key.to_vec()].concat()
+}
+
+/// Replace the state under `prefixes` in the genesis `storage` with the state in `snapshot`, and
+/// add the child tries of `snapshot`.
+fn fork(storage: &mut Storage, snapshot: Snapshot, prefixes: &[Vec<u8>]) {
+	storage
+		.top
+		.retain(|key, _| !prefixes.iter().any(|prefix| key.starts_with(prefix)));
+	storage.top.extend(snapshot.top);
+	for (storage_key, data) in snapshot.children {
+		let child_info = ChildInfo::new_default(&storage_key);
+		storage.children_default.insert(storage_key, StorageChild { data, child_info });
+	}
+}
+
+/// Make `dev` the sudo key and the only collator in the genesis `storage`, returning the storage
+/// items rewritten. Only the items present are rewritten, e.g. the sudo key of a chain with sudo.
+fn rekey(storage: &mut Storage, dev: &DevAccount) -> Vec<String> {
+	let (account, aura) = (dev.account_id.encode(), dev.aura.encode());
+	// The session keys of every runtime are its aura key only.
+	let items = [
+		("Sudo", "Key", account.clone()),
+		("Aura", "Authorities", vec![dev.aura.clone()].encode()),
+		("AuraExt", "Authorities", vec![dev.aura.clone()].encode()),
+		("CollatorSelection", "Invulnerables", vec![dev.account_id.clone()].encode()),
+		("Session", "Validators", vec![dev.account_id.clone()].encode()),
+		("Session", "QueuedKeys", vec![(dev.account_id.clone(), dev.aura.clone())].encode()),
+	];
+	let mut rekeyed = Vec::new();
+	for (pallet, item, value) in items {
+		if let Some(current) = storage.top.get_mut(&storage_key(pallet, item)) {
+			*current = value;
+			rekeyed.push(format!("{pallet}::{item}"));
+		}
+	}
+	// The other candidates would be elected at the next session, without running a collator.
+	if storage.top.remove(&storage_key("CollatorSelection", "CandidateList")).is_some() {
+		rekeyed.push("CollatorSelection::CandidateList".into());
+	}
+	if storage.top.contains_key(&storage_key("Session", "Validators")) {
+		let maps = [storage_key("Session", "NextKeys"), storage_key("Session", "KeyOwner")];
+		storage.top.retain(|key, _| !maps.iter().any(|map| key.starts_with(map)));
+		storage
+			.top
+			.insert(twox_64_concat_key("Session", "NextKeys", &account), aura.clone());
+		let owned = (AURA, dev.aura.as_ref().to_vec()).encode();
+		storage.top.insert(twox_64_concat_key("Session", "KeyOwner", &owned), account);
+		storage.top.remove(&storage_key("Session", "DisabledValidators"));
+		rekeyed.extend(["Session::NextKeys".into(), "Session::KeyOwner".into()]);
+	}
+	rekeyed
+}
+
+/// Endow `account` with `amount` on top of its balance in the genesis `storage`, keeping the total
+/// issuance in line.
+fn endow(storage: &mut Storage, account: &AccountId, amount: Balance) -> Result<()> {
+	let invalid = |e: codec::Error| Error::Input(format!("Invalid state of {account}: {e}"));
+	let key = account_key(account);
+	let mut info = match storage.top.get(&key) {
+		Some(info) => AccountInfo::decode(&mut &info[..]).map_err(invalid)?,
+		None => AccountInfo::default(),
+	};
+	info.providers = info.providers.max(1);
+	info.data.free = info.data.free.saturating_add(amount);
+	storage.top.insert(key, info.encode());
+	let key = storage_key("Balances", "TotalIssuance");
+	let issuance = match storage.top.get(&key) {
+		Some(issuance) => Balance::decode(&mut &issuance[..]).map_err(invalid)?,
+		None => 0,
+	};
+	storage.top.insert(key, issuance.saturating_add(amount).encode());
+	Ok(())
+}
+
+/// The raw chain specification of the development chain forked off the chain of `spec`, with the
+/// genesis `storage` and running on `relay_chain`.
+fn fork_spec(spec: &dyn ChainSpec, storage: &Storage, relay_chain: &str) -> Result<Value> {
+	let mut json: Value = serde_json::from_str(&spec.as_json(false)?)
+		.map_err(|e| Error::Input(format!("Invalid chain spec json: {e}")))?;
+	json["name"] = format!("{} Fork", spec.name()).into();
+	json["id"] = format!("{}-fork", spec.id()).into();
+	json["chainType"] = "Development".into();
+	json["bootNodes"] = json!([]);
+	json["telemetryEndpoints"] = Value::Null;
+	json["relay_chain"] = relay_chain.into();
+	// The blocks of the live chain the code was substituted at are not part of the fork.
+	json["codeSubstitutes"] = json!({});
+	let children: Map<_, _> = storage
+		.children_default
+		.iter()
+		.map(|(storage_key, child)| (hex(storage_key), hex_entries(&child.data).into()))
+		.collect();
+	json["genesis"] = json!({
+		"raw": { "top": hex_entries(&storage.top), "childrenDefault": children },
+	});
+	Ok(json)
+}
+
+/// The storage `entries` as hex encoded json object, as in a raw genesis.
+fn hex_entries(entries: &BTreeMap<Vec<u8>, Vec<u8>>) -> Map<String, Value> {
+	entries.iter().map(|(key, value)| (hex(key), hex(value).into())).collect()
+}
+
+/// The `0x` prefixed hex encoding of `bytes`.
+fn hex(bytes: &[u8]) -> String {
+	format!("0x{}", HexDisplay::from(&bytes))
+}
+
+#[cfg(test)]
+mod tests {
+	use clap::Parser;
+	use pop_runtime_testnet::{Runtime, Session};
+	use sp_runtime::BuildStorage;
+
+	use super::*;
+	use crate::chain_spec::{local_testnet_config, Relay, TestnetChainSpec};
+
+	fn cmd(args: &[&str]) -> ForkOffCmd {
+		ForkOffCmd::try_parse_from([&["fork-off"][..], args].concat()).unwrap()
+	}
+
+	#[test]
+	fn pallets_are_forked_except_for_the_chain_state_of_system() {
+		let cmd = cmd(&[]);
+		assert_eq!(cmd.pallets, ["System", "Balances", "Assets", "Contracts"]);
+		let prefixes = cmd.prefixes();
+		assert_eq!(prefixes[0], storage_key("System", "Account"));
+		assert_eq!(prefixes[3], twox_128(b"Contracts"));
+		assert!(cmd.forks_contracts());
+
+		let cmd = self::cmd(&["--pallets", "Assets", "--live-runtime"]);
+		assert_eq!(cmd.prefixes(), [twox_128(b"Assets").to_vec(), b":code".to_vec()]);
+		assert!(!cmd.forks_contracts());
+	}
+
+	#[test]
+	fn forked_state_replaces_genesis() {
+		let mut storage = local_testnet_config(Relay::PaseoLocal).build_storage().unwrap();
+		let accounts = storage_key("System", "Account");
+		let genesis_accounts: Vec<_> =
+			storage.top.keys().filter(|key| key.starts_with(&accounts)).cloned().collect();
+		assert!(!genesis_accounts.is_empty());
+		let account = AccountId::from([7; 32]);
+		let contract = [twox_128(b"Contracts").to_vec(), vec![1]].concat();
+		let snapshot = Snapshot {
+			top: [
+				(account_key(&account), AccountInfo::default().encode()),
+				(contract.clone(), vec![2]),
+			]
+			.into(),
+			children: [(b"trie".to_vec(), [(vec![3], vec![4])].into())].into(),
+		};
+
+		fork(&mut storage, snapshot, &cmd(&[]).prefixes());
+		assert!(genesis_accounts.iter().all(|key| !storage.top.contains_key(key)));
+		assert!(storage.top.contains_key(&account_key(&account)));
+		assert_eq!(storage.top[&contract], [2]);
+		assert!(storage.top.contains_key(&storage_key("System", "BlockHash")));
+		let child = &storage.children_default[&b"trie".to_vec()];
+		assert_eq!(child.data[&vec![3]], [4]);
+		assert_eq!(child.child_info, ChildInfo::new_default(b"trie"));
+	}
+
+	#[test]
+	fn dev_account_becomes_sudo_and_only_collator() {
+		let mut storage = local_testnet_config(Relay::PaseoLocal).build_storage().unwrap();
+		let issuance = storage.top[&storage_key("Balances", "TotalIssuance")].clone();
+		let dev = DevAccount::from_seed("collator/0");
+		let rekeyed = rekey(&mut storage, &dev);
+		for item in ["Sudo::Key", "Aura::Authorities", "Session::Validators", "Session::KeyOwner"] {
+			assert!(rekeyed.iter().any(|rekeyed| rekeyed == item), "{item} should be rekeyed");
+		}
+		endow(&mut storage, &dev.account_id, 10).unwrap();
+
+		sp_io::TestExternalities::new(storage.clone()).execute_with(|| {
+			let sudo = sp_io::storage::get(&storage_key("Sudo", "Key")).unwrap();
+			assert_eq!(AccountId::decode(&mut &sudo[..]).unwrap(), dev.account_id);
+			assert_eq!(Session::validators(), [dev.account_id.clone()]);
+			assert_eq!(Session::key_owner(AURA, dev.aura.as_ref()), Some(dev.account_id.clone()));
+			let invulnerables =
+				sp_io::storage::get(&storage_key("CollatorSelection", "Invulnerables")).unwrap();
+			assert_eq!(
+				Vec::<AccountId>::decode(&mut &invulnerables[..]).unwrap(),
+				[dev.account_id.clone()]
+			);
+			assert_eq!(pop_runtime_testnet::Aura::authorities_len(), 1);
+			let account = frame_system::Account::<Runtime>::get(&dev.account_id);
+			assert_eq!((account.providers, account.data.free), (1, 10));
+			assert_eq!(
+				pallet_balances::TotalIssuance::<Runtime>::get(),
+				Balance::decode(&mut &issuance[..]).unwrap() + 10
+			);
+		});
+	}
+
+	#[test]
+	fn fork_spec_is_raw_development_spec() {
+		let spec = local_testnet_config(Relay::PaseoLocal);
+		let mut storage = spec.build_storage().unwrap();
+		storage.children_default.insert(
+			b"trie".to_vec(),
+			StorageChild {
+				data: [(vec![1], vec![2])].into(),
+				child_info: ChildInfo::new_default(b"trie"),
+			},
+		);
+		let json = fork_spec(&spec, &storage, "rococo-local").unwrap();
+		assert_eq!(json["id"], "pop-testnet-local-fork");
+		assert_eq!(json["chainType"], "Development");
+		assert_eq!(json["relay_chain"], "rococo-local");
+		assert_eq!(json["genesis"]["raw"]["top"].as_object().unwrap().len(), storage.top.len());
+		assert_eq!(json["genesis"]["raw"]["childrenDefault"]["0x74726965"]["0x01"], "0x02");
+		assert!(json["genesis"].get("runtimeGenesis").is_none());
+
+		// The fork builds the storage it was forked with.
+		let fork = TestnetChainSpec::from_json_bytes(json.to_string().into_bytes()).unwrap();
+		let built = fork.build_storage().unwrap();
+		assert_eq!(built.top, storage.top);
+		assert_eq!(
+			built.children_default[&b"trie".to_vec()].data,
+			BTreeMap::from([(vec![1], vec![2])])
+		);
+	}
+}
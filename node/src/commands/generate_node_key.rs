@@ -0,0 +1,66 @@
+use std::{fs, path::PathBuf};
+
+use sc_cli::{Error, Result};
+use sc_network::{config::ed25519, PeerId};
+use sp_core::{hashing::blake2_256, hexdisplay::HexDisplay};
+
+/// The `generate-node-key` command used to derive a node key from a seed, so that a node has a
+/// peer id which is stable across restarts and known ahead of deployment, e.g. for bootnodes.
+#[derive(Debug, clap::Parser)]
+pub struct GenerateNodeKeyCmd {
+	/// The seed to derive the node key from. The same seed always yields the same node key and
+	/// therefore peer id, so it must be kept as secret as the node key itself.
+	#[arg(long, value_name = "SEED")]
+	pub from_seed: String,
+
+	/// The file to write the hex encoded node key to, to be passed to `--node-key-file`.
+	#[arg(long, value_name = "PATH")]
+	pub file: PathBuf,
+}
+
+impl GenerateNodeKeyCmd {
+	/// Run the `generate-node-key` command, printing the peer id of the generated node key.
+	pub fn run(&self) -> Result<()> {
+		println!("{}", self.generate()?);
+		Ok(())
+	}
+
+	/// Write the node key derived from the seed to the file and return its peer id.
+	pub(super) fn generate(&self) -> Result<PeerId> {
+		let secret = derive_secret(&self.from_seed);
+		let keypair = ed25519::Keypair::from(
+			ed25519::SecretKey::try_from_bytes(secret)
+				.map_err(|e| Error::Input(format!("Invalid node key: {e}")))?,
+		);
+		fs::write(&self.file, HexDisplay::from(&secret).to_string())
+			.map_err(|e| Error::Input(format!("Failed to write {}: {e}", self.file.display())))?;
+		Ok(keypair.public().to_peer_id())
+	}
+}
+
+/// The ed25519 secret key derived from `seed`, which is its blake2b hash.
+fn derive_secret(seed: &str) -> [u8; 32] {
+	blake2_256(seed.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn node_key_is_derived_deterministically() {
+		let dir = std::env::temp_dir().join(format!("pop-node-key-{}", std::process::id()));
+		fs::create_dir_all(&dir).unwrap();
+		let cmd = |seed: &str, file: &str| GenerateNodeKeyCmd {
+			from_seed: seed.into(),
+			file: dir.join(file),
+		};
+
+		let (first, second) = (cmd("collator-0", "first"), cmd("collator-0", "second"));
+		assert_eq!(first.generate().unwrap(), second.generate().unwrap());
+		assert_eq!(fs::read(&first.file).unwrap(), fs::read(&second.file).unwrap());
+
+		assert_ne!(first.generate().unwrap(), cmd("collator-1", "other").generate().unwrap());
+		fs::remove_dir_all(dir).unwrap();
+	}
+}
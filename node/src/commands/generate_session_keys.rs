@@ -0,0 +1,127 @@
+use std::sync::Arc;
+
+use codec::Encode;
+use sc_cli::{Error, KeystoreParams, Result, SharedParams};
+use sc_keystore::LocalKeystore;
+use sc_service::{
+	config::{BasePath, KeystoreConfig},
+	ChainSpec,
+};
+use sp_core::{
+	crypto::{key_types::AURA, ByteArray, Ss58Codec},
+	hexdisplay::HexDisplay,
+	sr25519, Pair,
+};
+use sp_keystore::{Keystore, KeystorePtr};
+
+use crate::chain_spec::{session_keys, FromCollatorKeys};
+
+/// The `key` sub-commands: those of substrate, e.g. `key insert` to insert a known key into the
+/// keystore, along with `key generate-session-keys` and `key dev-accounts`.
+#[derive(Debug, clap::Subcommand)]
+pub enum KeySubcommand {
+	/// Generate the session keys of a collator into the keystore of the node, printing the
+	/// public keys to register via `session.setKeys`.
+	GenerateSessionKeys(GenerateSessionKeysCmd),
+
+	/// Print the JSON manifest of deterministic development accounts: the well-known ones and
+	/// accounts numbered like `//collator/0`, along with their secret URIs and keys.
+	DevAccounts(super::DevAccountsCmd),
+
+	#[allow(missing_docs)]
+	#[command(flatten)]
+	Substrate(sc_cli::KeySubcommand),
+}
+
+/// The `key generate-session-keys` command used to generate the session keys of a collator, as
+/// `author_rotateKeys` does, without running the node or exposing unsafe RPC methods.
+#[derive(Debug, clap::Parser)]
+pub struct GenerateSessionKeysCmd {
+	/// The secret URI to derive the keys from, e.g. a mnemonic phrase. A new mnemonic phrase is
+	/// generated and printed otherwise, being the only backup of the keys besides the keystore.
+	#[arg(long, value_name = "SURI")]
+	pub suri: Option<String>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub keystore_params: KeystoreParams,
+}
+
+impl GenerateSessionKeysCmd {
+	/// The id of the chain whose keystore the keys are written to.
+	pub fn chain_id(&self) -> String {
+		self.shared_params.chain_id(self.shared_params.is_dev())
+	}
+
+	/// Run the `key generate-session-keys` command for the chain `spec`, whose runtime has the
+	/// session keys `Keys`.
+	pub fn run<Keys: FromCollatorKeys + Encode>(
+		&self,
+		spec: &dyn ChainSpec,
+		executable_name: &str,
+	) -> Result<()> {
+		let base_path = self
+			.shared_params
+			.base_path()?
+			.unwrap_or_else(|| BasePath::from_project("", "", executable_name));
+		let (path, password) =
+			match self.keystore_params.keystore_config(&base_path.config_dir(spec.id()))? {
+				KeystoreConfig::Path { path, password } => (path, password),
+				KeystoreConfig::InMemory => unreachable!("keystore params are always on disk; qed"),
+			};
+		let keystore: KeystorePtr = Arc::new(LocalKeystore::open(&path, password)?);
+
+		let phrase;
+		let suri = match &self.suri {
+			Some(suri) => suri.as_str(),
+			None => {
+				phrase = sr25519::Pair::generate_with_phrase(None).1;
+				println!("Secret phrase: {phrase}");
+				&phrase
+			},
+		};
+		let keys = generate::<Keys>(&*keystore, suri)?;
+		println!("Aura key: {}", keys.0.to_ss58check());
+		println!("Session keys: 0x{}", HexDisplay::from(&keys.1));
+		println!("Written to the keystore at {}", path.display());
+		Ok(())
+	}
+}
+
+/// Insert the aura key of `suri` into `keystore`, returning it along with the encoded session
+/// keys holding it, as passed to `session.setKeys`.
+fn generate<Keys: FromCollatorKeys + Encode>(
+	keystore: &dyn Keystore,
+	suri: &str,
+) -> Result<(sr25519::Public, Vec<u8>)> {
+	let aura = sr25519::Pair::from_string(suri, None)
+		.map_err(|e| Error::Input(format!("Invalid secret URI: {e:?}")))?
+		.public();
+	keystore
+		.insert(AURA, suri, aura.as_slice())
+		.map_err(|_| Error::Input("Failed to insert the aura key into the keystore".into()))?;
+	Ok((aura, session_keys::<Keys>(aura.into()).encode()))
+}
+
+#[cfg(test)]
+mod tests {
+	use sp_keystore::testing::MemoryKeystore;
+
+	use super::*;
+
+	#[test]
+	fn session_keys_are_inserted_into_the_keystore() {
+		let keystore = MemoryKeystore::new();
+		let (aura, keys) =
+			generate::<pop_runtime_testnet::SessionKeys>(&keystore, "//Alice").unwrap();
+
+		assert_eq!(aura, sr25519::Pair::from_string("//Alice", None).unwrap().public());
+		assert!(keystore.has_keys(&[(aura.to_raw_vec(), AURA)]));
+		assert_eq!(keys, pop_runtime_testnet::SessionKeys { aura: aura.into() }.encode());
+		assert!(generate::<pop_runtime_testnet::SessionKeys>(&keystore, "not a suri").is_err());
+	}
+}
@@ -0,0 +1,86 @@
+use std::io::Write;
+
+use sc_cli::{Error, Result};
+use sc_service::ChainSpec;
+use serde_json::Value;
+use sp_core::{hashing::twox_128, hexdisplay::HexDisplay};
+
+use crate::chain_spec::Extensions;
+
+/// The `inspect-spec` command used to print a human-readable summary of a chain specification.
+#[derive(Debug, clap::Parser)]
+pub struct InspectSpecCmd {
+	/// The chain specification to inspect. Either one of the built-in chain ids or a path to a
+	/// plain or raw chain specification file.
+	#[arg(value_name = "CHAIN_SPEC")]
+	pub chain: String,
+}
+
+impl InspectSpecCmd {
+	/// Run the `inspect-spec` command against an already loaded chain specification.
+	pub fn run(&self, spec: &dyn ChainSpec) -> Result<()> {
+		summarise(spec, &mut std::io::stdout().lock())
+	}
+}
+
+/// Write a summary of `spec` to `out`.
+fn summarise(spec: &dyn ChainSpec, out: &mut impl Write) -> Result<()> {
+	let json: Value = serde_json::from_str(&spec.as_json(false)?)
+		.map_err(|e| Error::Input(format!("Invalid chain spec json: {e}")))?;
+	let genesis = json.get("genesis");
+	let raw = genesis.and_then(|g| g.get("raw")).is_some();
+
+	writeln!(out, "Name:        {}", spec.name())?;
+	writeln!(out, "Id:          {}", spec.id())?;
+	writeln!(out, "Chain type:  {:?}", spec.chain_type())?;
+	writeln!(out, "Format:      {}", if raw { "raw" } else { "plain" })?;
+	match Extensions::try_get(spec) {
+		Some(extensions) => {
+			writeln!(out, "Relay chain: {}", extensions.relay_chain)?;
+			writeln!(out, "Para id:     {}", extensions.para_id)?;
+			if let Some(environment) = &extensions.environment {
+				writeln!(out, "Environment: {environment}")?;
+			}
+			if let Some(commit) = &extensions.runtime_commit {
+				writeln!(out, "Commit:      {commit}")?;
+			}
+		},
+		None => writeln!(out, "Extensions:  missing")?,
+	}
+	writeln!(out, "Protocol id: {}", spec.protocol_id().unwrap_or("none"))?;
+	writeln!(out, "Bootnodes:   {}", spec.boot_nodes().len())?;
+	let sudo = genesis.map_or(false, |g| has_sudo(g, raw));
+	writeln!(out, "Sudo:        {}", if sudo { "present" } else { "absent" })?;
+	Ok(())
+}
+
+/// Whether the genesis contains a sudo key, either as an entry in the runtime genesis config or
+/// as the `Sudo::Key` storage item of a raw genesis.
+fn has_sudo(genesis: &Value, raw: bool) -> bool {
+	if raw {
+		let key = [twox_128(b"Sudo"), twox_128(b"Key")].concat();
+		let key = format!("0x{}", HexDisplay::from(&key));
+		return genesis["raw"]["top"].as_object().map_or(false, |top| top.contains_key(&key));
+	}
+	let runtime_genesis = &genesis["runtimeGenesis"];
+	["patch", "config"]
+		.iter()
+		.any(|variant| !runtime_genesis[variant]["sudo"]["key"].is_null())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::chain_spec::{development_config, Code, Relay};
+
+	#[test]
+	fn summarises_dev_spec() {
+		let spec = development_config(Relay::PaseoLocal, None, vec![], vec![], Code::Compressed);
+		let mut out = Vec::new();
+		summarise(&spec, &mut out).unwrap();
+		let out = String::from_utf8(out).unwrap();
+		assert!(out.contains("paseo-local"));
+		assert!(out.contains("4001"));
+		assert!(out.contains("Sudo:        present"));
+	}
+}
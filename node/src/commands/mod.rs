@@ -0,0 +1,38 @@
+//! Pop specific sub-commands.
+
+mod benchmark_import;
+mod build_spec;
+mod check_collator;
+mod check_pov;
+mod collator_bundle;
+mod dev_accounts;
+mod diff_spec;
+mod doctor;
+mod export_balances;
+mod fork_off;
+mod generate_node_key;
+mod generate_session_keys;
+mod inspect_spec;
+mod rekey_sudo;
+mod rpc_client;
+mod spec_checksum;
+mod upgrade;
+mod verify_reexecution;
+
+pub use benchmark_import::BenchmarkImportCmd;
+pub use build_spec::BuildSpecCmd;
+pub use check_collator::CheckCollatorCmd;
+pub use check_pov::CheckPovCmd;
+pub use collator_bundle::CollatorBundleCmd;
+pub use dev_accounts::DevAccountsCmd;
+pub use diff_spec::DiffSpecCmd;
+pub use doctor::DoctorCmd;
+pub use export_balances::ExportBalancesCmd;
+pub use fork_off::ForkOffCmd;
+pub use generate_node_key::GenerateNodeKeyCmd;
+pub use generate_session_keys::{GenerateSessionKeysCmd, KeySubcommand};
+pub use inspect_spec::InspectSpecCmd;
+pub use rekey_sudo::RekeySudoCmd;
+pub use spec_checksum::SpecChecksumCmd;
+pub use upgrade::UpgradeSubcommand;
+pub use verify_reexecution::VerifyReexecutionCmd;
@@ -0,0 +1,110 @@
+use std::{fs, path::PathBuf};
+
+use codec::Encode;
+use pop_runtime_common::AccountId;
+use sc_cli::{Error, Result};
+use sc_service::ChainSpec;
+use serde_json::Value;
+use sp_core::{hashing::twox_128, hexdisplay::HexDisplay};
+
+/// The `rekey-sudo` command used to replace the sudo key in the genesis of a chain specification,
+/// e.g. to take control of a fork of a live chain for testing.
+#[derive(Debug, clap::Parser)]
+pub struct RekeySudoCmd {
+	/// The chain specification to rekey. Either one of the built-in chain ids or a path to a
+	/// plain or raw chain specification file.
+	#[arg(value_name = "CHAIN_SPEC")]
+	pub chain: String,
+
+	/// The account to become the sudo key, as SS58 address or hex.
+	#[arg(long, value_name = "ACCOUNT")]
+	pub new_key: AccountId,
+
+	/// The file to write the rekeyed chain specification to, instead of stdout.
+	#[arg(long, short)]
+	pub output: Option<PathBuf>,
+}
+
+impl RekeySudoCmd {
+	/// Run the `rekey-sudo` command against an already loaded chain specification.
+	pub fn run(&self, spec: &dyn ChainSpec) -> Result<()> {
+		let mut json: Value = serde_json::from_str(&spec.as_json(false)?)
+			.map_err(|e| Error::Input(format!("Invalid chain spec json: {e}")))?;
+		rekey(&mut json, &self.new_key)?;
+		let json = serde_json::to_string_pretty(&json)
+			.map_err(|e| Error::Input(format!("Invalid chain spec json: {e}")))?;
+		match &self.output {
+			Some(path) => fs::write(path, json)?,
+			None => println!("{json}"),
+		}
+		Ok(())
+	}
+}
+
+/// Replace the sudo key in the genesis of the chain specification `json` with `new_key`, either
+/// in the sudo section of its runtime genesis config or as the `Sudo::Key` storage item of its
+/// raw genesis.
+fn rekey(json: &mut Value, new_key: &AccountId) -> Result<()> {
+	let no_sudo = || Error::Input("Chain spec has no sudo key to replace".into());
+	let genesis = json.get_mut("genesis").ok_or_else(no_sudo)?;
+	if let Some(top) = genesis.pointer_mut("/raw/top") {
+		let value = top.get_mut(&raw_sudo_key()).ok_or_else(no_sudo)?;
+		// `Sudo::Key` is only stored when set, as the encoded account.
+		*value = format!("0x{}", HexDisplay::from(&new_key.encode())).into();
+		return Ok(());
+	}
+	for variant in ["patch", "config"] {
+		let pointer = format!("/runtimeGenesis/{variant}/sudo/key");
+		if let Some(key) = genesis.pointer_mut(&pointer).filter(|key| !key.is_null()) {
+			*key = serde_json::to_value(new_key)
+				.map_err(|e| Error::Input(format!("Invalid account: {e}")))?;
+			return Ok(());
+		}
+	}
+	Err(no_sudo())
+}
+
+/// The storage key of `pallet_sudo::Key` as it appears in a raw genesis.
+fn raw_sudo_key() -> String {
+	format!("0x{}", HexDisplay::from(&[twox_128(b"Sudo"), twox_128(b"Key")].concat()))
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json::json;
+
+	use super::*;
+	use crate::chain_spec::{development_config, minimal_test_spec, Code, Relay};
+
+	fn to_json(spec: &dyn ChainSpec) -> Value {
+		serde_json::from_str(&spec.as_json(false).unwrap()).unwrap()
+	}
+
+	#[test]
+	fn dev_spec_sudo_key_is_replaced() {
+		let new_key = AccountId::from([7; 32]);
+		let mut json =
+			to_json(&development_config(Relay::PaseoLocal, None, vec![], vec![], Code::Compressed));
+		assert_ne!(json["genesis"]["runtimeGenesis"]["patch"]["sudo"]["key"], json!(new_key));
+
+		rekey(&mut json, &new_key).unwrap();
+		assert_eq!(json["genesis"]["runtimeGenesis"]["patch"]["sudo"]["key"], json!(new_key));
+	}
+
+	#[test]
+	fn raw_sudo_key_is_replaced() {
+		let key = raw_sudo_key();
+		let mut json = json!({ "genesis": { "raw": { "top": { &key: "0x00" } } } });
+
+		rekey(&mut json, &AccountId::from([7; 32])).unwrap();
+		assert_eq!(json["genesis"]["raw"]["top"][&key], format!("0x{}", "07".repeat(32)));
+	}
+
+	#[test]
+	fn spec_without_sudo_is_refused() {
+		let mut json = to_json(&minimal_test_spec());
+		assert!(rekey(&mut json, &AccountId::from([7; 32])).is_err());
+		let mut json = json!({ "genesis": { "raw": { "top": {} } } });
+		assert!(rekey(&mut json, &AccountId::from([7; 32])).is_err());
+	}
+}
@@ -0,0 +1,41 @@
+use std::time::Duration;
+
+use hyper::{header::CONTENT_TYPE, Body, Client, Request, Uri};
+use sc_cli::{Error, Result};
+use serde_json::Value;
+
+/// The longest a node is given to respond to a request.
+const RPC_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Call the RPC `method` of the node at the plain `http` endpoint `url` with `params`, returning
+/// its result.
+pub(super) async fn call<T: serde::de::DeserializeOwned>(
+	url: &Uri,
+	method: &str,
+	params: impl serde::Serialize,
+) -> Result<T> {
+	let body = serde_json::json!({
+		"jsonrpc": "2.0",
+		"id": 1,
+		"method": method,
+		"params": params,
+	});
+	let request = Request::post(url.clone())
+		.header(CONTENT_TYPE, "application/json")
+		.body(Body::from(body.to_string()))
+		.expect("request is valid; qed");
+	let response = tokio::time::timeout(RPC_TIMEOUT, Client::new().request(request))
+		.await
+		.map_err(|_| Error::Input(format!("{url} did not respond within {RPC_TIMEOUT:?}")))?
+		.map_err(|e| Error::Input(format!("{url} is unreachable: {e}")))?;
+	let body = hyper::body::to_bytes(response.into_body())
+		.await
+		.map_err(|e| Error::Input(format!("Failed to read the response of {url}: {e}")))?;
+	let mut response: Value = serde_json::from_slice(&body)
+		.map_err(|e| Error::Input(format!("Invalid response of {url}: {e}")))?;
+	match response.get_mut("result") {
+		Some(result) => serde_json::from_value(result.take())
+			.map_err(|e| Error::Input(format!("Invalid result of `{method}`: {e}"))),
+		None => Err(Error::Input(format!("`{method}` failed: {}", response["error"]))),
+	}
+}
@@ -0,0 +1,96 @@
+use std::{fs, path::PathBuf};
+
+use codec::Encode;
+use sc_cli::{Error, Result};
+use serde_json::{Map, Value};
+use sp_core::{hashing::blake2_256, hexdisplay::HexDisplay};
+
+/// The `spec-checksum` command used to print a checksum of a chain specification file, allowing
+/// operators to verify they are running the distributed chain specification.
+#[derive(Debug, clap::Parser)]
+pub struct SpecChecksumCmd {
+	/// Path to the plain or raw chain specification file.
+	#[arg(value_name = "PATH")]
+	pub path: PathBuf,
+}
+
+impl SpecChecksumCmd {
+	/// Run the `spec-checksum` command.
+	pub fn run(&self) -> Result<()> {
+		let json = fs::read_to_string(&self.path)
+			.map_err(|e| Error::Input(format!("Failed to read {}: {e}", self.path.display())))?;
+		println!("0x{}", HexDisplay::from(&checksum(&json)?));
+		Ok(())
+	}
+}
+
+/// The blake2b hash of the genesis and para id of the chain specification `json`.
+///
+/// The genesis is canonicalised by sorting the keys of all its objects, so the checksum does not
+/// depend on how the chain specification was formatted.
+fn checksum(json: &str) -> Result<[u8; 32]> {
+	let spec: Value = serde_json::from_str(json)
+		.map_err(|e| Error::Input(format!("Invalid chain spec json: {e}")))?;
+	let genesis = spec
+		.get("genesis")
+		.ok_or_else(|| Error::Input("Chain spec has no genesis".into()))?;
+	let para_id = ["para_id", "paraId", "ParaId"]
+		.iter()
+		.find_map(|key| spec.get(key))
+		.and_then(Value::as_u64)
+		.and_then(|para_id| u32::try_from(para_id).ok())
+		.ok_or_else(|| Error::Input("Chain spec has no valid para id".into()))?;
+	let genesis = serde_json::to_vec(&canonicalise(genesis))
+		.map_err(|e| Error::Input(format!("Invalid chain spec genesis: {e}")))?;
+	Ok((genesis, para_id).using_encoded(blake2_256))
+}
+
+/// Sort the keys of all objects within `value`.
+fn canonicalise(value: &Value) -> Value {
+	match value {
+		Value::Object(object) => {
+			let mut entries: Vec<_> = object.iter().collect();
+			entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+			Value::Object(
+				entries
+					.into_iter()
+					.map(|(k, v)| (k.clone(), canonicalise(v)))
+					.collect::<Map<_, _>>(),
+			)
+		},
+		Value::Array(values) => Value::Array(values.iter().map(canonicalise).collect()),
+		value => value.clone(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn checksum_ignores_key_order() {
+		let spec = r#"{
+			"name": "Pop",
+			"para_id": 4001,
+			"genesis": { "raw": { "top": { "0x01": "0x02", "0x03": "0x04" }, "childrenDefault": {} } }
+		}"#;
+		let reordered = r#"{
+			"genesis": { "raw": { "childrenDefault": {}, "top": { "0x03": "0x04", "0x01": "0x02" } } },
+			"para_id": 4001,
+			"name": "Pop"
+		}"#;
+		assert_eq!(checksum(spec).unwrap(), checksum(reordered).unwrap());
+
+		let other_para = spec.replace("4001", "4002");
+		assert_ne!(checksum(spec).unwrap(), checksum(&other_para).unwrap());
+		let other_genesis = spec.replace("0x04", "0x05");
+		assert_ne!(checksum(spec).unwrap(), checksum(&other_genesis).unwrap());
+	}
+
+	#[test]
+	fn checksum_requires_genesis_and_para_id() {
+		assert!(checksum(r#"{ "para_id": 4001 }"#).is_err());
+		assert!(checksum(r#"{ "genesis": {} }"#).is_err());
+		assert!(checksum("not json").is_err());
+	}
+}
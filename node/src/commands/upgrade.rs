@@ -0,0 +1,245 @@
+use std::{fs, path::PathBuf, process::Command};
+
+use codec::{Decode, Encode};
+use hyper::Uri;
+use sc_cli::{Error, Result};
+use serde_json::Value;
+use sp_core::{hashing::twox_128, Bytes};
+use sp_runtime::{
+	generic::UncheckedExtrinsic,
+	traits::Hash,
+	transaction_validity::{TransactionSource, TransactionValidity},
+};
+
+use super::rpc_client;
+use crate::finality_webhook::parse_url;
+
+/// The `upgrade` sub-commands, concerned with runtime upgrades authorized by governance.
+#[derive(Debug, clap::Subcommand)]
+pub enum UpgradeSubcommand {
+	/// Enact the runtime upgrade authorized via `system.authorizeUpgrade` or
+	/// `emergencyUpgrade.authorizeUpgrade`, by submitting its code as
+	/// `system.applyAuthorizedUpgrade`.
+	Submit(UpgradeSubmitCmd),
+}
+
+/// The `upgrade submit` command used to enact an authorized runtime upgrade, rather than setting
+/// the code via `sudo(system.set_code)`.
+///
+/// The code is checked against the authorized code hash and validated by the node as transaction,
+/// which checks its version, and its migrations are checked by `try-runtime` against the live state
+/// of the chain, before it is submitted. The upgrade is submitted as unsigned extrinsic, so no key
+/// is required.
+#[derive(Debug, clap::Parser)]
+pub struct UpgradeSubmitCmd {
+	/// The wasm blob of the runtime to upgrade to, as authorized.
+	#[arg(long, value_name = "PATH")]
+	pub wasm: PathBuf,
+
+	/// The plain `http` RPC endpoint of a node of the chain to upgrade.
+	#[arg(
+		long,
+		value_name = "URL",
+		value_parser = parse_url,
+		default_value = "http://127.0.0.1:9944",
+	)]
+	pub rpc_url: Uri,
+
+	/// The chain to upgrade, whose runtime the extrinsic is encoded for. Either one of the
+	/// built-in chain ids or a path to a chain specification file.
+	#[arg(long, value_name = "CHAIN_SPEC", default_value = "pop")]
+	pub chain: String,
+
+	/// The `try-runtime` executable of try-runtime-cli
+	/// (<https://github.com/paritytech/try-runtime-cli>), used to run the migrations of the upgrade
+	/// against the live state of the chain.
+	#[arg(long, value_name = "PATH", default_value = "try-runtime")]
+	pub try_runtime: PathBuf,
+
+	/// The wasm blob checked by `try-runtime`, which must be built with the `try-runtime` feature.
+	/// Defaults to the blob given by `--wasm`.
+	#[arg(long, value_name = "PATH")]
+	pub try_runtime_wasm: Option<PathBuf>,
+
+	/// Skip the `try-runtime` checks, e.g. when they have been run separately.
+	#[arg(long)]
+	pub skip_try_runtime: bool,
+
+	/// Only run the checks, without submitting the upgrade.
+	#[arg(long)]
+	pub dry_run: bool,
+}
+
+impl UpgradeSubmitCmd {
+	/// Run the `upgrade submit` command against a chain with the runtime `R`.
+	pub fn run<R: frame_system::Config>(&self) -> Result<()> {
+		let code = fs::read(&self.wasm)
+			.map_err(|e| Error::Input(format!("Failed to read {}: {e}", self.wasm.display())))?;
+		let code_hash = R::Hashing::hash(&code);
+		println!("Code hash: {code_hash:?}");
+		let runtime = tokio::runtime::Builder::new_current_thread()
+			.enable_all()
+			.build()
+			.map_err(|e| Error::Application(Box::new(e)))?;
+		runtime.block_on(self.submit::<R>(code, code_hash))
+	}
+
+	/// Check the upgrade to `code` with `code_hash` and submit it, unless only checking it.
+	async fn submit<R: frame_system::Config>(
+		&self,
+		code: Vec<u8>,
+		code_hash: R::Hash,
+	) -> Result<()> {
+		let authorization: Option<Bytes> =
+			rpc_client::call(&self.rpc_url, "state_getStorage", [authorized_upgrade_key()]).await?;
+		check_authorization(authorization.as_deref(), &code_hash)?;
+		println!("✅ The upgrade is authorized");
+
+		let extrinsic = apply_authorized_upgrade::<R>(code);
+		let best_hash: R::Hash =
+			rpc_client::call(&self.rpc_url, "chain_getBlockHash", [(); 0]).await?;
+		// The extrinsic is encoded already.
+		let call =
+			[TransactionSource::External.encode(), extrinsic.clone(), best_hash.encode()].concat();
+		let validity: Bytes = self
+			.rpc("state_call", ("TaggedTransactionQueue_validate_transaction", Bytes(call)))
+			.await?;
+		check_validity(&validity)?;
+		println!("✅ The upgrade is valid as of block {best_hash:?}");
+
+		if self.skip_try_runtime {
+			println!("⚠️  Skipped the try-runtime checks");
+		} else {
+			self.try_runtime()?;
+			println!("✅ The try-runtime checks passed");
+		}
+
+		if self.dry_run {
+			return Ok(());
+		}
+		let hash: Value =
+			rpc_client::call(&self.rpc_url, "author_submitExtrinsic", [Bytes(extrinsic)]).await?;
+		println!("🚀 Submitted the upgrade as extrinsic {hash}");
+		Ok(())
+	}
+
+	/// Run the migrations of the upgrade against the live state of the chain, with all checks.
+	fn try_runtime(&self) -> Result<()> {
+		let wasm = self.try_runtime_wasm.as_ref().unwrap_or(&self.wasm);
+		let status = Command::new(&self.try_runtime)
+			.arg("--runtime")
+			.arg(wasm)
+			.args(["on-runtime-upgrade", "--checks=all", "live", "--uri", &ws_url(&self.rpc_url)])
+			.status()
+			.map_err(|e| {
+				Error::Input(format!(
+					"Failed to run {}: {e}. Install try-runtime-cli or pass `--skip-try-runtime`",
+					self.try_runtime.display()
+				))
+			})?;
+		if !status.success() {
+			return Err(Error::Input(format!("The try-runtime checks failed: {status}")));
+		}
+		Ok(())
+	}
+}
+
+/// The storage key of the upgrade authorized via `system.authorize_upgrade`.
+fn authorized_upgrade_key() -> Bytes {
+	Bytes([twox_128(b"System"), twox_128(b"AuthorizedUpgrade")].concat())
+}
+
+/// Ensure that the upgrade whose encoded authorization is `authorization` is to the code with
+/// `code_hash`.
+fn check_authorization<H: Decode + PartialEq + std::fmt::Debug>(
+	authorization: Option<&[u8]>,
+	code_hash: &H,
+) -> Result<()> {
+	let authorization = authorization.ok_or_else(|| {
+		Error::Input(
+			"No upgrade is authorized, authorize it via `system.authorizeUpgrade` or \
+			 `emergencyUpgrade.authorizeUpgrade` first"
+				.into(),
+		)
+	})?;
+	let (authorized, _check_version) = <(H, bool)>::decode(&mut &authorization[..])
+		.map_err(|e| Error::Input(format!("Invalid upgrade authorization: {e}")))?;
+	if &authorized != code_hash {
+		return Err(Error::Input(format!(
+			"The upgrade authorized is to the code with hash {authorized:?}, not {code_hash:?}"
+		)));
+	}
+	Ok(())
+}
+
+/// Ensure that the encoded `validity` of the upgrade marks it as valid.
+fn check_validity(validity: &[u8]) -> Result<()> {
+	match TransactionValidity::decode(&mut &validity[..]) {
+		Ok(Ok(_)) => Ok(()),
+		Ok(Err(e)) => Err(Error::Input(format!("The upgrade is invalid: {e:?}"))),
+		Err(e) => Err(Error::Input(format!("Invalid transaction validity: {e}"))),
+	}
+}
+
+/// The unsigned extrinsic applying the authorized upgrade to `code` on a chain with the runtime
+/// `R`, which requires no key as the upgrade is validated against the authorization.
+fn apply_authorized_upgrade<R: frame_system::Config>(code: Vec<u8>) -> Vec<u8> {
+	let call: R::RuntimeCall = frame_system::Call::<R>::apply_authorized_upgrade { code }.into();
+	UncheckedExtrinsic::<(), _, (), ()>::new_unsigned(call).encode()
+}
+
+/// The websocket url of the RPC endpoint at `url`, which serves both http and websocket requests.
+fn ws_url(url: &Uri) -> String {
+	let authority = url.authority().map(|authority| authority.as_str()).unwrap_or_default();
+	format!("ws://{authority}{}", url.path())
+}
+
+#[cfg(test)]
+mod tests {
+	use pop_runtime_common::Hash as BlockHash;
+	use sp_runtime::transaction_validity::{InvalidTransaction, ValidTransaction};
+
+	use super::*;
+
+	#[test]
+	fn only_the_authorized_code_is_submitted() {
+		let code_hash = BlockHash::repeat_byte(1);
+		assert!(check_authorization(Some(&(code_hash, true).encode()), &code_hash).is_ok());
+		assert!(check_authorization(Some(&(code_hash, false).encode()), &code_hash).is_ok());
+		assert!(check_authorization(Some(&(BlockHash::repeat_byte(2), true).encode()), &code_hash)
+			.is_err());
+		assert!(check_authorization(None, &code_hash).is_err());
+		assert!(check_authorization(Some(&[1][..]), &code_hash).is_err());
+	}
+
+	#[test]
+	fn only_valid_upgrades_are_submitted() {
+		let valid: TransactionValidity = Ok(ValidTransaction::default());
+		assert!(check_validity(&valid.encode()).is_ok());
+		let invalid: TransactionValidity = Err(InvalidTransaction::Call.into());
+		assert!(check_validity(&invalid.encode()).is_err());
+		assert!(check_validity(&[]).is_err());
+	}
+
+	#[test]
+	fn upgrade_is_submitted_as_unsigned_extrinsic() {
+		use pop_runtime_testnet::{Runtime, RuntimeCall, UncheckedExtrinsic};
+		use sp_runtime::traits::Extrinsic;
+
+		let encoded = apply_authorized_upgrade::<Runtime>(b"code".to_vec());
+		let extrinsic = UncheckedExtrinsic::decode(&mut &encoded[..]).unwrap();
+		assert_eq!(extrinsic.is_signed(), Some(false));
+		assert_eq!(
+			extrinsic.function,
+			RuntimeCall::System(frame_system::Call::apply_authorized_upgrade {
+				code: b"code".to_vec()
+			})
+		);
+	}
+
+	#[test]
+	fn try_runtime_connects_over_websocket() {
+		assert_eq!(ws_url(&"http://127.0.0.1:9944".parse().unwrap()), "ws://127.0.0.1:9944/");
+		assert_eq!(ws_url(&"http://node:9944/rpc".parse().unwrap()), "ws://node:9944/rpc");
+	}
+}
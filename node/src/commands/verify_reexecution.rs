@@ -0,0 +1,122 @@
+use std::sync::Arc;
+
+use pop_runtime_common::{Block, BlockNumber, Hash};
+use sc_cli::{CliConfiguration, DatabaseParams, Error, PruningParams, Result, SharedParams};
+use sc_client_api::BlockBackend;
+use sp_api::{Core, ProvideRuntimeApi};
+use sp_block_builder::BlockBuilder;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{
+	generic::DigestItem,
+	traits::{Block as BlockT, Header as _},
+};
+
+/// The `verify-reexecution` command used to detect nondeterminism, by re-executing a range of
+/// stored blocks and comparing the state root each produces with the one recorded in its header.
+///
+/// Each block is re-executed extrinsic by extrinsic on the state of its parent, so the states of
+/// the parents of the blocks in the range must not be pruned.
+#[derive(Debug, clap::Parser)]
+pub struct VerifyReexecutionCmd {
+	/// The number of the first block to re-execute.
+	#[arg(value_name = "FROM", value_parser = clap::value_parser!(BlockNumber).range(1..))]
+	pub from: BlockNumber,
+
+	/// The number of the last block to re-execute.
+	#[arg(value_name = "TO")]
+	pub to: BlockNumber,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl VerifyReexecutionCmd {
+	/// Run the `verify-reexecution` command, failing at the first block whose re-execution
+	/// diverges from its recorded state root.
+	pub async fn run<C>(&self, client: Arc<C>) -> Result<()>
+	where
+		C: ProvideRuntimeApi<Block> + BlockBackend<Block> + HeaderBackend<Block>,
+		C::Api: Core<Block> + BlockBuilder<Block>,
+	{
+		if self.from > self.to {
+			return Err(Error::Input(format!("Block #{} is after block #{}", self.from, self.to)));
+		}
+		for number in self.from..=self.to {
+			let hash = client
+				.hash(number)?
+				.ok_or_else(|| Error::Input(format!("Block #{number} not found")))?;
+			let (recorded, computed) = state_roots(&*client, hash)
+				.map_err(|e| Error::Input(format!("Block #{number} ({hash}) diverges: {e}")))?;
+			if recorded != computed {
+				return Err(Error::Input(format!(
+					"Block #{number} ({hash}) diverges: its state root is recorded as {recorded} \
+					 but re-executes to {computed}"
+				)));
+			}
+		}
+		println!(
+			"Re-executed blocks #{} to #{}, all producing their recorded state roots",
+			self.from, self.to
+		);
+		Ok(())
+	}
+}
+
+impl CliConfiguration for VerifyReexecutionCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
+
+/// The state root recorded in the header of the block `hash`, along with the state root it
+/// produces when re-executed on the state of its parent.
+///
+/// Unlike on import, where the runtime only asserts that the state root matches, the block is
+/// re-built from its extrinsics so that the state root it produces can be reported.
+fn state_roots<C>(client: &C, hash: Hash) -> std::result::Result<(Hash, Hash), String>
+where
+	C: ProvideRuntimeApi<Block> + BlockBackend<Block>,
+	C::Api: Core<Block> + BlockBuilder<Block>,
+{
+	let block = client
+		.block(hash)
+		.map_err(|e| e.to_string())?
+		.ok_or("the block is not stored")?
+		.block;
+	let (header, extrinsics) = block.deconstruct();
+	let parent = *header.parent_hash();
+
+	// Blocks are executed without their seal, as on import.
+	let mut unsealed = header.clone();
+	if let Some(DigestItem::Seal(..)) = unsealed.digest().logs().last() {
+		unsealed.digest_mut().pop();
+	}
+	// The changes of each call are kept by the api, so the block is built up call by call.
+	let api = client.runtime_api();
+	api.initialize_block(parent, &unsealed)
+		.map_err(|e| format!("failed to initialize: {e}"))?;
+	for (index, extrinsic) in extrinsics.into_iter().enumerate() {
+		api.apply_extrinsic(parent, extrinsic)
+			.map_err(|e| format!("failed to apply extrinsic {index}: {e}"))?
+			.map_err(|e| format!("extrinsic {index} is invalid: {e:?}"))?;
+	}
+	let built = api.finalize_block(parent).map_err(|e| format!("failed to finalize: {e}"))?;
+	Ok((*header.state_root(), *built.state_root()))
+}
@@ -0,0 +1,252 @@
+//! Reports the gas consumed by contracts in each imported block, as the
+//! `pop_contract_gas_consumed` prometheus counter, labelled by the `ref_time` and `proof_size`
+//! the gas is made of. Comparing its rate with the weight limit of blocks shows how much of the
+//! block budget contracts actually consume.
+//!
+//! The gas consumed by a contract call is the actual weight of the extrinsic reported by
+//! `ExtrinsicSuccess` or `ExtrinsicFailed`, counted for every extrinsic during whose dispatch the
+//! contracts pallet emitted events. Calls reverting all their events, e.g. trapped calls, are
+//! therefore not counted.
+
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	sync::Arc,
+};
+
+use codec::Decode;
+use frame_system::Phase;
+use futures::StreamExt;
+use pop_runtime_common::{Block, Hash};
+use prometheus_endpoint::{register, CounterVec, Opts, PrometheusError, Registry, U64};
+use sc_client_api::{Backend, BlockchainEvents, StorageProvider};
+use sc_service::TaskManager;
+use sp_core::{storage::StorageKey, twox_128};
+use sp_weights::Weight;
+
+const LOG_TARGET: &str = "contract-gas";
+
+/// Decodes the gas consumed by contracts from the events of a block of a runtime.
+pub(crate) trait ContractGas {
+	/// Whether the runtime has contracts.
+	const CONTRACTS: bool = true;
+
+	/// The gas consumed by contracts in a block with the encoded `events`.
+	fn gas_consumed(events: &[u8]) -> Result<Weight, codec::Error>;
+}
+
+/// What an event reports about the extrinsic during whose dispatch it was emitted.
+enum Dispatch {
+	/// The event was emitted by the contracts pallet.
+	Contract,
+	/// The dispatch of the extrinsic completed, having consumed the weight.
+	Applied(Weight),
+	/// Any other event.
+	Other,
+}
+
+/// Implements [`ContractGas`] for the api of each of the runtimes, whose events share the same
+/// structure.
+macro_rules! impl_contract_gas {
+	($($runtime:ident),*) => {$(
+		impl ContractGas for $runtime::RuntimeApi {
+			fn gas_consumed(mut events: &[u8]) -> Result<Weight, codec::Error> {
+				use $runtime::RuntimeEvent;
+				type EventRecord = frame_system::EventRecord<RuntimeEvent, Hash>;
+				let events = Vec::<EventRecord>::decode(&mut events)?;
+				Ok(gas_consumed(events.into_iter().map(|record| {
+					let dispatch = match record.event {
+						RuntimeEvent::Contracts(_) => Dispatch::Contract,
+						RuntimeEvent::System(frame_system::Event::ExtrinsicSuccess {
+							dispatch_info,
+						}) |
+						RuntimeEvent::System(frame_system::Event::ExtrinsicFailed {
+							dispatch_info,
+							..
+						}) => Dispatch::Applied(dispatch_info.weight),
+						_ => Dispatch::Other,
+					};
+					(record.phase, dispatch)
+				})))
+			}
+		}
+	)*};
+}
+
+impl_contract_gas!(pop_runtime_devnet, pop_runtime_testnet);
+
+impl ContractGas for pop_runtime_mainnet::RuntimeApi {
+	const CONTRACTS: bool = false;
+
+	fn gas_consumed(_: &[u8]) -> Result<Weight, codec::Error> {
+		Ok(Weight::zero())
+	}
+}
+
+/// The weight consumed by the extrinsics during whose dispatch contract events were emitted,
+/// given the phase of each event of a block along with what it reports.
+fn gas_consumed(events: impl IntoIterator<Item = (Phase, Dispatch)>) -> Weight {
+	let mut contract_extrinsics = BTreeSet::new();
+	let mut weights = BTreeMap::new();
+	for (phase, dispatch) in events {
+		let Phase::ApplyExtrinsic(index) = phase else { continue };
+		match dispatch {
+			Dispatch::Contract => {
+				contract_extrinsics.insert(index);
+			},
+			Dispatch::Applied(weight) => {
+				weights.insert(index, weight);
+			},
+			Dispatch::Other => {},
+		}
+	}
+	weights
+		.into_iter()
+		.filter(|(index, _)| contract_extrinsics.contains(index))
+		.fold(Weight::zero(), |consumed, (_, weight)| consumed.saturating_add(weight))
+}
+
+/// The storage key of `frame_system::Events`.
+fn events_key() -> StorageKey {
+	StorageKey([twox_128(b"System"), twox_128(b"Events")].concat())
+}
+
+/// The `pop_contract_gas_consumed` counter.
+struct Metrics {
+	gas_consumed: CounterVec<U64>,
+}
+
+impl Metrics {
+	/// Register the metrics with `registry`.
+	fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		let gas_consumed = register(
+			CounterVec::new(
+				Opts::new(
+					"pop_contract_gas_consumed",
+					"The gas consumed by contracts in imported blocks",
+				),
+				&["resource"],
+			)?,
+			registry,
+		)?;
+		Ok(Self { gas_consumed })
+	}
+
+	/// Count `gas` as consumed by contracts.
+	fn record(&self, gas: Weight) {
+		self.gas_consumed.with_label_values(&["ref_time"]).inc_by(gas.ref_time());
+		self.gas_consumed.with_label_values(&["proof_size"]).inc_by(gas.proof_size());
+	}
+}
+
+/// Spawn the task updating the `pop_contract_gas_consumed` counter registered with `registry`,
+/// unless the runtime `R` has no contracts.
+pub(crate) fn spawn<R, C, B>(
+	task_manager: &TaskManager,
+	client: Arc<C>,
+	registry: &Registry,
+) -> Result<(), PrometheusError>
+where
+	R: ContractGas,
+	B: Backend<Block> + 'static,
+	C: BlockchainEvents<Block> + StorageProvider<Block, B> + Send + Sync + 'static,
+{
+	if !R::CONTRACTS {
+		return Ok(());
+	}
+	let metrics = Metrics::register(registry)?;
+	task_manager
+		.spawn_handle()
+		.spawn("contract-gas", None, run::<R, _, _>(client, metrics));
+	Ok(())
+}
+
+/// Count the gas consumed by contracts in each block imported by `client`.
+async fn run<R, C, B>(client: Arc<C>, metrics: Metrics)
+where
+	R: ContractGas,
+	B: Backend<Block>,
+	C: BlockchainEvents<Block> + StorageProvider<Block, B>,
+{
+	let key = events_key();
+	let mut imported = client.import_notification_stream();
+	while let Some(notification) = imported.next().await {
+		let events = match client.storage(notification.hash, &key) {
+			Ok(events) => events,
+			Err(e) => {
+				log::debug!(
+					target: LOG_TARGET,
+					"Unable to read the events of {}: {e}",
+					notification.hash
+				);
+				continue;
+			},
+		};
+		let Some(events) = events else { continue };
+		match R::gas_consumed(&events.0) {
+			Ok(gas) => metrics.record(gas),
+			Err(e) => log::debug!(
+				target: LOG_TARGET,
+				"Unable to decode the events of {}: {e}",
+				notification.hash
+			),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use frame_support::dispatch::{DispatchInfo, PostDispatchInfo};
+	use pop_runtime_testnet::{AccountId, Runtime, RuntimeEvent};
+
+	use super::*;
+
+	/// The encoded events of a block with a contract call consuming `gas`, followed by a transfer.
+	fn events(gas: Weight) -> Vec<u8> {
+		sp_io::TestExternalities::default().execute_with(|| {
+			type System = frame_system::Pallet<Runtime>;
+			System::set_block_number(1);
+			System::note_finished_initialize();
+			let applied = |weight| {
+				let info = DispatchInfo { weight, ..Default::default() };
+				System::note_applied_extrinsic(&Ok(PostDispatchInfo::default()), info);
+			};
+			System::deposit_event(RuntimeEvent::Contracts(pallet_contracts::Event::Called {
+				caller: pallet_contracts::Origin::Signed(AccountId::from([1; 32])),
+				contract: AccountId::from([2; 32]),
+			}));
+			applied(gas);
+			applied(Weight::from_parts(1_000, 10));
+			sp_io::storage::get(&events_key().0).unwrap().to_vec()
+		})
+	}
+
+	#[test]
+	fn contract_call_increases_gas_consumed() {
+		let gas = Weight::from_parts(1_000_000, 1_000);
+		let gas_consumed =
+			<pop_runtime_testnet::RuntimeApi as ContractGas>::gas_consumed(&events(gas)).unwrap();
+		assert_eq!(gas_consumed, gas);
+
+		let registry = Registry::new();
+		let metrics = Metrics::register(&registry).unwrap();
+		metrics.record(gas_consumed);
+		for resource in ["ref_time", "proof_size"] {
+			assert!(metrics.gas_consumed.with_label_values(&[resource]).get() > 0);
+		}
+	}
+
+	#[test]
+	fn only_contract_extrinsics_consume_gas() {
+		let weight = Weight::from_parts(10, 1);
+		let events = [
+			(Phase::Initialization, Dispatch::Contract),
+			(Phase::ApplyExtrinsic(0), Dispatch::Applied(weight)),
+			(Phase::ApplyExtrinsic(1), Dispatch::Contract),
+			(Phase::ApplyExtrinsic(1), Dispatch::Other),
+			(Phase::ApplyExtrinsic(1), Dispatch::Applied(weight * 2)),
+			(Phase::Finalization, Dispatch::Applied(weight)),
+		];
+		assert_eq!(gas_consumed(events), weight * 2);
+		assert_eq!(gas_consumed([]), Weight::zero());
+	}
+}
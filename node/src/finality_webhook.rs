@@ -0,0 +1,151 @@
+//! Notifies an external service, such as an indexer, of finalized blocks via a webhook.
+//!
+//! Notifications are queued in a bounded channel and posted one at a time, so a slow or
+//! unreachable endpoint only ever causes notifications to be dropped and never stalls block
+//! import or consensus.
+
+use std::{sync::Arc, time::Duration};
+
+use futures::{channel::mpsc, Stream, StreamExt};
+use hyper::{client::HttpConnector, header::CONTENT_TYPE, Body, Client, Request, Uri};
+use pop_runtime_common::{Block, BlockNumber, Hash};
+use sc_client_api::BlockchainEvents;
+use sc_service::TaskManager;
+use serde::Serialize;
+
+const LOG_TARGET: &str = "finality-webhook";
+/// The maximum number of notifications waiting to be posted.
+const QUEUE_SIZE: usize = 1024;
+/// The maximum number of attempts at posting a notification.
+const MAX_ATTEMPTS: u32 = 3;
+/// The delay before the first retry, doubling on each subsequent retry.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+/// The longest a single request may take.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The body posted to the webhook for each finalized block.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+struct FinalizedBlock {
+	block_number: BlockNumber,
+	block_hash: Hash,
+}
+
+/// Parse the webhook url, only plain `http` endpoints are supported.
+pub fn parse_url(url: &str) -> Result<Uri, String> {
+	let url: Uri = url.parse().map_err(|e| format!("invalid url: {e}"))?;
+	if url.scheme_str() != Some("http") {
+		return Err("only http urls are supported".into());
+	}
+	Ok(url)
+}
+
+/// Spawn the tasks posting each block finalized by `client` to `url`.
+pub(crate) fn spawn<C>(task_manager: &TaskManager, client: Arc<C>, url: Uri)
+where
+	C: BlockchainEvents<Block>,
+{
+	let (mut sender, receiver) = mpsc::channel(QUEUE_SIZE);
+	let notifications = client.finality_notification_stream().for_each(move |notification| {
+		let block_number = notification.header.number;
+		let block = FinalizedBlock { block_number, block_hash: notification.hash };
+		if let Err(e) = sender.try_send(block) {
+			log::warn!(
+				target: LOG_TARGET,
+				"Dropping notification of finalized block #{block_number}: {e}"
+			);
+		}
+		futures::future::ready(())
+	});
+	task_manager.spawn_handle().spawn("finality-webhook-queue", None, notifications);
+	task_manager.spawn_handle().spawn("finality-webhook", None, run(url, receiver));
+}
+
+/// Post each of `blocks` to `url`.
+async fn run(url: Uri, mut blocks: impl Stream<Item = FinalizedBlock> + Unpin) {
+	let client = Client::new();
+	while let Some(block) = blocks.next().await {
+		notify(&client, &url, &block).await;
+	}
+}
+
+/// Post `block` to `url`, retrying with exponential backoff.
+async fn notify(client: &Client<HttpConnector>, url: &Uri, block: &FinalizedBlock) {
+	let body = serde_json::to_vec(block).expect("finalized block is serializable; qed");
+	let mut backoff = INITIAL_BACKOFF;
+	for attempt in 1..=MAX_ATTEMPTS {
+		let request = Request::post(url.clone())
+			.header(CONTENT_TYPE, "application/json")
+			.body(Body::from(body.clone()))
+			.expect("request is valid; qed");
+		let error = match tokio::time::timeout(REQUEST_TIMEOUT, client.request(request)).await {
+			Ok(Ok(response)) if response.status().is_success() => return,
+			Ok(Ok(response)) => format!("unexpected status {}", response.status()),
+			Ok(Err(e)) => e.to_string(),
+			Err(_) => "request timed out".into(),
+		};
+		log::debug!(
+			target: LOG_TARGET,
+			"Attempt {attempt} at notifying finalized block #{} failed: {error}",
+			block.block_number
+		);
+		if attempt < MAX_ATTEMPTS {
+			tokio::time::sleep(backoff).await;
+			backoff *= 2;
+		}
+	}
+	log::warn!(
+		target: LOG_TARGET,
+		"Dropping notification of finalized block #{} after {MAX_ATTEMPTS} attempts",
+		block.block_number
+	);
+}
+
+#[cfg(test)]
+mod tests {
+	use tokio::{
+		io::{AsyncReadExt, AsyncWriteExt},
+		net::TcpListener,
+	};
+
+	use super::*;
+
+	#[test]
+	fn only_http_urls_are_accepted() {
+		assert!(parse_url("http://127.0.0.1:8080/blocks").is_ok());
+		assert!(parse_url("https://indexer.example/blocks").is_err());
+		assert!(parse_url("not a url").is_err());
+	}
+
+	#[tokio::test]
+	async fn posts_finalized_blocks() {
+		let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let url = parse_url(&format!("http://{}/blocks", listener.local_addr().unwrap())).unwrap();
+		let block = FinalizedBlock { block_number: 42, block_hash: Hash::repeat_byte(1) };
+
+		let server = tokio::spawn(async move {
+			let (mut stream, _) = listener.accept().await.unwrap();
+			let mut request = Vec::new();
+			let expected = serde_json::to_vec(&block).unwrap();
+			while !request.ends_with(&expected) {
+				let mut buf = [0; 1024];
+				let read = stream.read(&mut buf).await.unwrap();
+				assert_ne!(read, 0, "connection closed before the body was received");
+				request.extend_from_slice(&buf[..read]);
+			}
+			stream.write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n").await.unwrap();
+			String::from_utf8(request).unwrap()
+		});
+
+		let blocks = futures::stream::iter([FinalizedBlock {
+			block_number: 42,
+			block_hash: Hash::repeat_byte(1),
+		}]);
+		tokio::time::timeout(REQUEST_TIMEOUT, run(url, blocks)).await.unwrap();
+
+		let request = server.await.unwrap();
+		assert!(request.starts_with("POST /blocks HTTP/1.1"));
+		assert!(request.contains("application/json"));
+		assert!(request
+			.contains(&format!("\"block_number\":42,\"block_hash\":\"0x{}\"", "01".repeat(32))));
+	}
+}
@@ -0,0 +1,211 @@
+//! Reports the XCM messages received but not processed yet, as of each best block, so that
+//! operators can see when the chain back-pressures the relay chain or its siblings. The following
+//! prometheus metrics are reported:
+//! - `pop_inbound_xcm_messages` and `pop_inbound_xcm_bytes`: the messages queued for processing, by
+//!   `origin`: `relay` or the para id of the sibling.
+//! - `pop_inbound_xcm_channel_suspended`: whether the channel of the sibling given by `sibling` was
+//!   suspended, asking it to hold back its messages, as its queue filled up.
+//! - `pop_inbound_xcm_processing_suspended`: whether the processing of XCMP messages is suspended.
+//!
+//! The queue is also logged under the `pop::xcm-queue` target, within a span timing its read.
+
+use std::sync::Arc;
+
+use futures::StreamExt;
+use pop_runtime_common::{
+	apis::{InboundXcmQueue, InboundXcmQueueApi},
+	Block,
+};
+use prometheus_endpoint::{register, Gauge, GaugeVec, Opts, PrometheusError, Registry, U64};
+use sc_client_api::BlockchainEvents;
+use sc_service::TaskManager;
+use sp_api::ProvideRuntimeApi;
+
+use crate::structured_logs::XCM_QUEUE;
+
+const LOG_TARGET: &str = "inbound-xcm";
+
+/// The `origin` label of the messages received from the relay chain.
+const RELAY_ORIGIN: &str = "relay";
+
+/// The metrics of the inbound XCM queue.
+struct Metrics {
+	messages: GaugeVec<U64>,
+	bytes: GaugeVec<U64>,
+	channel_suspended: GaugeVec<U64>,
+	processing_suspended: Gauge<U64>,
+}
+
+impl Metrics {
+	/// Register the metrics with `registry`.
+	fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			messages: register(
+				GaugeVec::new(
+					Opts::new(
+						"pop_inbound_xcm_messages",
+						"The XCM messages received but not processed yet",
+					),
+					&["origin"],
+				)?,
+				registry,
+			)?,
+			bytes: register(
+				GaugeVec::new(
+					Opts::new(
+						"pop_inbound_xcm_bytes",
+						"The size of the XCM messages received but not processed yet",
+					),
+					&["origin"],
+				)?,
+				registry,
+			)?,
+			channel_suspended: register(
+				GaugeVec::new(
+					Opts::new(
+						"pop_inbound_xcm_channel_suspended",
+						"Whether the inbound XCMP channel of a sibling is suspended as its queue \
+						 filled up",
+					),
+					&["sibling"],
+				)?,
+				registry,
+			)?,
+			processing_suspended: register(
+				Gauge::new(
+					"pop_inbound_xcm_processing_suspended",
+					"Whether the processing of XCMP messages is suspended",
+				)?,
+				registry,
+			)?,
+		})
+	}
+
+	/// Report `queue`, forgetting the labels of origins no longer reported.
+	fn record(&self, queue: &InboundXcmQueue) {
+		self.messages.reset();
+		self.bytes.reset();
+		self.channel_suspended.reset();
+		self.messages.with_label_values(&[RELAY_ORIGIN]).set(queue.downward.count);
+		self.bytes.with_label_values(&[RELAY_ORIGIN]).set(queue.downward.bytes);
+		for channel in &queue.horizontal {
+			let sibling = channel.sender.to_string();
+			self.messages.with_label_values(&[&sibling]).set(channel.queued.count);
+			self.bytes.with_label_values(&[&sibling]).set(channel.queued.bytes);
+			self.channel_suspended
+				.with_label_values(&[&sibling])
+				.set(channel.suspended.into());
+		}
+		self.processing_suspended.set(queue.suspended.into());
+	}
+}
+
+/// Spawn the task reporting the inbound XCM queue, updating the metrics registered with `registry`
+/// if any.
+pub(crate) fn spawn<C>(
+	task_manager: &TaskManager,
+	client: Arc<C>,
+	registry: Option<&Registry>,
+) -> Result<(), PrometheusError>
+where
+	C: ProvideRuntimeApi<Block> + BlockchainEvents<Block> + Send + Sync + 'static,
+	C::Api: InboundXcmQueueApi<Block>,
+{
+	let metrics = registry.map(Metrics::register).transpose()?;
+	task_manager.spawn_handle().spawn("inbound-xcm", None, run(client, metrics));
+	Ok(())
+}
+
+/// Report the inbound XCM queue as of each new best block imported by `client`.
+async fn run<C>(client: Arc<C>, metrics: Option<Metrics>)
+where
+	C: ProvideRuntimeApi<Block> + BlockchainEvents<Block>,
+	C::Api: InboundXcmQueueApi<Block>,
+{
+	let mut imported = client.import_notification_stream();
+	while let Some(notification) = imported.next().await {
+		if !notification.is_new_best {
+			continue;
+		}
+		let span =
+			tracing::info_span!(target: XCM_QUEUE, "inbound_xcm_queue", block = %notification.hash);
+		let queue = span.in_scope(|| client.runtime_api().inbound_xcm_queue(notification.hash));
+		match queue {
+			Ok(queue) => {
+				log_queue(&queue);
+				if let Some(metrics) = &metrics {
+					metrics.record(&queue);
+				}
+			},
+			Err(e) => log::debug!(
+				target: LOG_TARGET,
+				"Unable to read the inbound XCM queue as of {}: {e}",
+				notification.hash
+			),
+		}
+	}
+}
+
+/// Log `queue` under the `pop::xcm-queue` target, a debug event per sibling with messages queued.
+fn log_queue(queue: &InboundXcmQueue) {
+	tracing::info!(
+		target: XCM_QUEUE,
+		downward_messages = queue.downward.count,
+		downward_bytes = queue.downward.bytes,
+		channels = queue.horizontal.len(),
+		suspended = queue.suspended,
+		"Inbound XCM queue",
+	);
+	for channel in queue.horizontal.iter().filter(|channel| channel.queued.count > 0) {
+		tracing::debug!(
+			target: XCM_QUEUE,
+			sibling = channel.sender,
+			messages = channel.queued.count,
+			bytes = channel.queued.bytes,
+			suspended = channel.suspended,
+			"Inbound XCMP channel",
+		);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use pop_runtime_common::apis::{InboundChannel, InboundMessages};
+
+	use super::*;
+
+	#[test]
+	fn queues_are_reported_by_origin() {
+		let registry = Registry::new();
+		let metrics = Metrics::register(&registry).unwrap();
+		let channel = |sender, suspended, count| InboundChannel {
+			sender,
+			suspended,
+			queued: InboundMessages { pages: 1, count, bytes: count * 10 },
+		};
+		metrics.record(&InboundXcmQueue {
+			downward: InboundMessages { pages: 1, count: 2, bytes: 20 },
+			horizontal: vec![channel(1_000, false, 3), channel(2_000, true, 40)],
+			suspended: true,
+		});
+		assert_eq!(metrics.messages.with_label_values(&["relay"]).get(), 2);
+		assert_eq!(metrics.bytes.with_label_values(&["1000"]).get(), 30);
+		assert_eq!(metrics.messages.with_label_values(&["2000"]).get(), 40);
+		assert_eq!(metrics.channel_suspended.with_label_values(&["1000"]).get(), 0);
+		assert_eq!(metrics.channel_suspended.with_label_values(&["2000"]).get(), 1);
+		assert_eq!(metrics.processing_suspended.get(), 1);
+
+		// Siblings no longer reported are forgotten.
+		metrics.record(&InboundXcmQueue {
+			horizontal: vec![channel(1_000, false, 0)],
+			..Default::default()
+		});
+		let families = registry.gather();
+		let suspended = families
+			.iter()
+			.find(|family| family.get_name() == "pop_inbound_xcm_channel_suspended")
+			.unwrap();
+		assert_eq!(suspended.get_metric().len(), 1);
+		assert_eq!(metrics.processing_suspended.get(), 0);
+	}
+}
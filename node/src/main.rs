@@ -2,11 +2,29 @@
 
 #![warn(missing_docs)]
 
+mod benchmarking;
+mod block_announce;
+mod block_production;
+mod bootnodes;
 mod chain_spec;
 mod cli;
+mod collator_keystore;
 mod command;
+mod commands;
+mod contract_gas;
+mod finality_webhook;
+mod inbound_xcm;
+mod metrics_snapshot;
+mod pinned_blocks;
+mod relay_parent_lag;
 mod rpc;
 mod service;
+mod shutdown;
+mod spec_toml;
+mod standby;
+mod state_sync;
+mod structured_logs;
+mod wasm_profile;
 
 fn main() -> sc_cli::Result<()> {
 	command::run()
@@ -0,0 +1,95 @@
+//! Periodically writes the current values of the prometheus metrics of the node to a file, for
+//! nodes without a prometheus scraper able to reach them.
+//!
+//! Each snapshot is written to a temporary file first and then renamed, so readers never see a
+//! partially written snapshot.
+
+use std::{
+	io,
+	path::{Path, PathBuf},
+	time::Duration,
+};
+
+use prometheus_endpoint::{
+	prometheus::{Encoder, TextEncoder},
+	Registry,
+};
+use sc_service::TaskManager;
+
+const LOG_TARGET: &str = "metrics-snapshot";
+/// The default interval between snapshots, in seconds.
+pub(crate) const DEFAULT_INTERVAL_SECS: u64 = 60;
+
+/// Where and how often to write snapshots of the metrics.
+#[derive(Clone, Debug)]
+pub(crate) struct Snapshots {
+	/// The file to write the snapshots to, overwritten by each snapshot.
+	pub path: PathBuf,
+	/// The interval between snapshots.
+	pub interval: Duration,
+}
+
+/// Spawn the task writing snapshots of the metrics of `registry`.
+pub(crate) fn spawn(task_manager: &TaskManager, registry: Registry, snapshots: Snapshots) {
+	task_manager
+		.spawn_handle()
+		.spawn("metrics-snapshot", None, run(registry, snapshots));
+}
+
+/// Write a snapshot of the metrics of `registry` every `interval`.
+async fn run(registry: Registry, Snapshots { path, interval }: Snapshots) {
+	let mut interval = tokio::time::interval(interval);
+	loop {
+		interval.tick().await;
+		if let Err(e) = write(&registry, &path) {
+			log::warn!(
+				target: LOG_TARGET,
+				"Unable to write metrics snapshot to {}: {e}",
+				path.display()
+			);
+		}
+	}
+}
+
+/// Write the current values of the metrics of `registry` to `path`, in the prometheus text
+/// exposition format.
+fn write(registry: &Registry, path: &Path) -> io::Result<()> {
+	let mut buffer = Vec::new();
+	TextEncoder::new()
+		.encode(&registry.gather(), &mut buffer)
+		.map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+	let mut temporary = path.as_os_str().to_owned();
+	temporary.push(".tmp");
+	std::fs::write(&temporary, buffer)?;
+	std::fs::rename(temporary, path)
+}
+
+#[cfg(test)]
+mod tests {
+	use prometheus_endpoint::{register, Gauge, U64};
+
+	use super::*;
+
+	#[test]
+	fn snapshot_contains_registered_metrics() {
+		let registry = Registry::new_custom(Some("substrate".into()), None).unwrap();
+		let gauge =
+			register(Gauge::<U64>::new("block_height", "Block height info").unwrap(), &registry)
+				.unwrap();
+		gauge.set(42);
+		let dir = std::env::temp_dir().join(format!("pop-metrics-snapshot-{}", std::process::id()));
+		std::fs::create_dir_all(&dir).unwrap();
+		let path = dir.join("metrics.prom");
+
+		write(&registry, &path).unwrap();
+		let snapshot = std::fs::read_to_string(&path).unwrap();
+		assert!(snapshot.contains("# TYPE substrate_block_height gauge"));
+		assert!(snapshot.contains("substrate_block_height 42"));
+
+		// Subsequent snapshots replace the previous one.
+		gauge.set(43);
+		write(&registry, &path).unwrap();
+		assert!(std::fs::read_to_string(&path).unwrap().contains("substrate_block_height 43"));
+		std::fs::remove_dir_all(dir).unwrap();
+	}
+}
@@ -0,0 +1,123 @@
+//! Keeps the blocks given by `--pin-blocks` and their state from being pruned, e.g. to investigate
+//! an incident without running an archive node.
+//!
+//! Blocks are pinned in the backend once finalized, or at startup if already finalized. Pins only
+//! last while the node is running: a block pruned while the node was stopped cannot be pinned
+//! again, in which case a warning is logged at startup.
+//!
+//! Each pinned block retains its body, justifications and the trie nodes of its state. As the
+//! state diverges from the pinned one, the nodes retained grow towards a full copy of the state as
+//! of the pinned block, so only a few blocks should be pinned at a time.
+
+use std::{collections::BTreeSet, iter, sync::Arc};
+
+use futures::StreamExt;
+use pop_runtime_common::{Block, BlockNumber, Hash};
+use sc_client_api::{Backend, BlockchainEvents, FinalityNotifications};
+use sc_service::TaskManager;
+use sp_blockchain::HeaderBackend;
+
+const LOG_TARGET: &str = "pinned-blocks";
+
+/// Pin the already finalized blocks of `pins` and spawn the task pinning the others once
+/// finalized by `client`.
+pub(crate) fn spawn<C, B>(
+	task_manager: &TaskManager,
+	client: Arc<C>,
+	backend: Arc<B>,
+	mut pins: BTreeSet<BlockNumber>,
+) where
+	B: Backend<Block> + 'static,
+	C: BlockchainEvents<Block> + HeaderBackend<Block>,
+{
+	// Subscribe before pinning the finalized blocks, so that no block is finalized in between.
+	let notifications = client.finality_notification_stream();
+	let finalized = client.info().finalized_number;
+	let already_finalized: Vec<_> = pins.range(..=finalized).copied().collect();
+	for number in already_finalized {
+		pins.remove(&number);
+		match client.hash(number) {
+			Ok(Some(hash)) if backend.have_state_at(hash, number) => pin(&*backend, number, hash),
+			_ => log::warn!(
+				target: LOG_TARGET,
+				"Block #{number} is already pruned and cannot be pinned"
+			),
+		}
+	}
+	if !pins.is_empty() {
+		task_manager
+			.spawn_handle()
+			.spawn("pinned-blocks", None, run(backend, notifications, pins));
+	}
+}
+
+/// Pin the blocks of `pins` as they are finalized, until all of them are pinned.
+async fn run<B: Backend<Block>>(
+	backend: Arc<B>,
+	mut notifications: FinalityNotifications<Block>,
+	mut pins: BTreeSet<BlockNumber>,
+) {
+	while let Some(notification) = notifications.next().await {
+		let finalized = finalized_blocks(
+			notification.header.number,
+			&notification.tree_route,
+			notification.hash,
+		);
+		for (number, hash) in take_pinned(&mut pins, finalized) {
+			pin(&*backend, number, hash);
+		}
+		if pins.is_empty() {
+			break;
+		}
+	}
+}
+
+/// Pin the block `hash` at `number` in `backend`.
+fn pin<B: Backend<Block>>(backend: &B, number: BlockNumber, hash: Hash) {
+	match backend.pin_block(hash) {
+		Ok(()) => log::info!(target: LOG_TARGET, "Pinned block #{number} ({hash})"),
+		Err(e) => log::warn!(target: LOG_TARGET, "Unable to pin block #{number} ({hash}): {e}"),
+	}
+}
+
+/// The numbers and hashes of the blocks finalized by a notification of the block `hash` at
+/// `number`, which implicitly finalizes the blocks of its `tree_route` as well.
+fn finalized_blocks(
+	number: BlockNumber,
+	tree_route: &[Hash],
+	hash: Hash,
+) -> impl Iterator<Item = (BlockNumber, Hash)> + '_ {
+	let first = number.saturating_sub(tree_route.len() as BlockNumber);
+	(first..).zip(tree_route.iter().copied().chain(iter::once(hash)))
+}
+
+/// Remove the blocks of `finalized` from `pins`, returning the ones which are to be pinned.
+fn take_pinned(
+	pins: &mut BTreeSet<BlockNumber>,
+	finalized: impl Iterator<Item = (BlockNumber, Hash)>,
+) -> Vec<(BlockNumber, Hash)> {
+	finalized.filter(|(number, _)| pins.remove(number)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn pinned_blocks_are_taken_once_finalized() {
+		let hash = |n: u8| Hash::repeat_byte(n);
+		let mut pins = BTreeSet::from([2, 4, 10]);
+
+		// Blocks 1 to 3 are finalized by a notification of block 3.
+		let tree_route = [hash(1), hash(2)];
+		let finalized = finalized_blocks(3, &tree_route, hash(3));
+		assert_eq!(take_pinned(&mut pins, finalized), vec![(2, hash(2))]);
+		assert_eq!(pins, BTreeSet::from([4, 10]));
+
+		let finalized = finalized_blocks(4, &[], hash(4));
+		assert_eq!(take_pinned(&mut pins, finalized), vec![(4, hash(4))]);
+		let finalized = finalized_blocks(4, &[], hash(4));
+		assert!(take_pinned(&mut pins, finalized).is_empty());
+		assert_eq!(pins, BTreeSet::from([10]));
+	}
+}
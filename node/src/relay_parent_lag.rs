@@ -0,0 +1,116 @@
+//! Reports how far the relay parent of the best parachain block lags behind the best relay chain
+//! block known to the node, as the `pop_relay_parent_lag` prometheus gauge. A growing lag signals
+//! that the node cannot keep up with the relay chain.
+
+use std::sync::Arc;
+
+use codec::Decode;
+use cumulus_relay_chain_interface::RelayChainInterface;
+use futures::{stream, StreamExt};
+use pop_runtime_common::Block;
+use prometheus_endpoint::{register, Gauge, PrometheusError, Registry, U64};
+use sc_client_api::{Backend, BlockchainEvents, StorageProvider};
+use sc_service::TaskManager;
+use sp_core::{storage::StorageKey, twox_128};
+
+const LOG_TARGET: &str = "relay-parent-lag";
+
+/// An update of either of the block numbers the lag is computed from.
+enum Update {
+	/// The best relay chain block number known to the node.
+	BestRelay(u32),
+	/// The relay parent number of the best parachain block.
+	RelayParent(u32),
+}
+
+/// The number of relay chain blocks the relay parent `relay_parent` lags behind `best_relay`.
+fn lag(best_relay: u32, relay_parent: u32) -> u64 {
+	best_relay.saturating_sub(relay_parent).into()
+}
+
+/// The storage key of the relay parent number, as stored by `cumulus-pallet-parachain-system`.
+fn relay_parent_number_key() -> StorageKey {
+	StorageKey([twox_128(b"ParachainSystem"), twox_128(b"LastRelayChainBlockNumber")].concat())
+}
+
+/// Spawn the task updating the `pop_relay_parent_lag` gauge registered with `registry`.
+pub(crate) fn spawn<C, B>(
+	task_manager: &TaskManager,
+	client: Arc<C>,
+	relay_chain_interface: Arc<dyn RelayChainInterface>,
+	registry: &Registry,
+) -> Result<(), PrometheusError>
+where
+	B: Backend<Block> + 'static,
+	C: BlockchainEvents<Block> + StorageProvider<Block, B> + Send + Sync + 'static,
+{
+	let gauge = register(
+		Gauge::<U64>::new(
+			"pop_relay_parent_lag",
+			"The number of relay chain blocks the relay parent of the best parachain block lags \
+			 behind the best relay chain block",
+		)?,
+		registry,
+	)?;
+	task_manager.spawn_handle().spawn(
+		"relay-parent-lag",
+		None,
+		run(client, relay_chain_interface, gauge),
+	);
+	Ok(())
+}
+
+/// Update `gauge` whenever the best relay chain block or the best parachain block changes.
+async fn run<C, B>(
+	client: Arc<C>,
+	relay_chain_interface: Arc<dyn RelayChainInterface>,
+	gauge: Gauge<U64>,
+) where
+	B: Backend<Block>,
+	C: BlockchainEvents<Block> + StorageProvider<Block, B>,
+{
+	let best_relay = match relay_chain_interface.new_best_notification_stream().await {
+		Ok(headers) => headers.map(|header| Update::BestRelay(header.number)),
+		Err(e) => {
+			log::warn!(target: LOG_TARGET, "Unable to follow the best relay chain block: {e}");
+			return;
+		},
+	};
+	let key = relay_parent_number_key();
+	let relay_parent = client
+		.import_notification_stream()
+		.filter(|notification| futures::future::ready(notification.is_new_best))
+		.filter_map(move |notification| {
+			let relay_parent = client
+				.storage(notification.hash, &key)
+				.ok()
+				.flatten()
+				.and_then(|data| u32::decode(&mut &data.0[..]).ok());
+			futures::future::ready(relay_parent.map(Update::RelayParent))
+		});
+
+	let (mut best_relay_number, mut relay_parent_number) = (None, None);
+	let mut updates = stream::select(best_relay, relay_parent);
+	while let Some(update) = updates.next().await {
+		match update {
+			Update::BestRelay(number) => best_relay_number = Some(number),
+			Update::RelayParent(number) => relay_parent_number = Some(number),
+		}
+		if let (Some(best_relay), Some(relay_parent)) = (best_relay_number, relay_parent_number) {
+			gauge.set(lag(best_relay, relay_parent));
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn lag_is_difference_between_best_relay_block_and_relay_parent() {
+		assert_eq!(lag(105, 100), 5);
+		assert_eq!(lag(100, 100), 0);
+		// The relay parent may briefly be ahead of the best relay block known to the node.
+		assert_eq!(lag(99, 100), 0);
+	}
+}
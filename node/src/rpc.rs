@@ -5,20 +5,83 @@
 
 #![warn(missing_docs)]
 
+mod account_conversion;
+mod address;
+mod block_weight;
+mod collator_registration;
+mod collator_set;
+mod contract_address;
+mod contract_cost;
+mod contract_dry_run;
+mod contract_instantiation;
+mod contract_metadata;
+mod contract_result;
+mod contract_trace;
+mod contracts;
+mod fee;
+mod finality;
+mod genesis_config;
+mod inspect;
+mod metadata;
+mod next_author;
+mod pallets;
+mod proxies;
+mod replay;
+mod storage_batch;
+mod storage_deposit;
+mod submit;
+mod supply;
+mod upgrade_status;
+mod xcm_account;
+mod xcm_queue;
+mod xcm_version;
+
 use std::sync::Arc;
 
 use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
-use pop_runtime_common::{AccountId, Balance, Block, Nonce};
-use sc_client_api::{AuxStore, BlockBackend, ProofProvider};
+use pop_runtime_common::{proxy::ProxyType, AccountId, AssetId, Balance, Block, Hash, Nonce};
+use sc_client_api::{AuxStore, BlockBackend, ExecutorProvider, ProofProvider, StorageProvider};
 pub use sc_rpc::DenyUnsafe;
 use sc_transaction_pool_api::TransactionPool;
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiError, ApiExt, ProvideRuntimeApi, RuntimeApiInfo};
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
 #[cfg(feature = "ismp")]
 use sp_core::H256;
 use substrate_frame_rpc_system::{System, SystemApiServer};
 
+use self::{
+	account_conversion::{AccountConversion, AccountConversionApiServer},
+	address::{Address, AddressApiServer},
+	block_weight::{BlockWeight, BlockWeightApiServer},
+	collator_registration::{CollatorRegistration, CollatorRegistrationApiServer},
+	collator_set::{CollatorSetApiServer, CollatorSets},
+	contract_address::{ContractAddress, ContractAddressApiServer},
+	contract_cost::{ContractCost, ContractCostApiServer},
+	contract_dry_run::{ContractDryRun, ContractDryRunApiServer},
+	contract_instantiation::{ContractInstantiation, ContractInstantiationApiServer},
+	contract_metadata::{ContractMetadata, ContractMetadataApiServer},
+	contract_trace::{ContractTrace, ContractTraceApiServer},
+	contracts::{Contracts, ContractsApiServer},
+	fee::{Fee, FeeApiServer},
+	finality::{Finality, FinalityApiServer},
+	genesis_config::{GenesisConfig, GenesisConfigApiServer},
+	inspect::{Inspect, InspectApiServer},
+	metadata::{Metadata, MetadataApiServer},
+	next_author::{NextAuthor, NextAuthorApiServer},
+	pallets::{Pallets, PalletsApiServer},
+	proxies::{Proxies, ProxiesApiServer},
+	replay::{Replay, ReplayApiServer},
+	storage_batch::{StorageBatch, StorageBatchApiServer},
+	storage_deposit::{StorageDeposit, StorageDepositApiServer},
+	submit::{Submit, SubmitApiServer},
+	supply::{Supply, SupplyApiServer},
+	upgrade_status::{UpgradeStatus, UpgradeStatusApiServer},
+	xcm_account::{XcmAccount, XcmAccountApiServer},
+	xcm_queue::{XcmQueue, XcmQueueApiServer},
+	xcm_version::{XcmVersion, XcmVersionApiServer},
+};
+
 /// A type representing all RPC extensions.
 pub type RpcExtension = jsonrpsee::RpcModule<()>;
 
@@ -32,42 +95,129 @@ pub struct FullDeps<C, P, B> {
 	pub deny_unsafe: DenyUnsafe,
 	/// Backend used by the node.
 	pub backend: Arc<B>,
+	/// Whether to expose the `pop_contractTrace`, `pop_traceCall` and `dev_traceContractCall`
+	/// RPCs.
+	pub contracts_debug: bool,
+	/// The chain specification the node was started with.
+	pub chain_spec: Box<dyn sc_chain_spec::ChainSpec>,
 }
 
-/// Instantiate all RPC extensions.
+/// The runtime API of ISMP, required by the RPCs of nodes built with the `ismp` feature only.
+#[cfg(feature = "ismp")]
+pub trait IsmpApi: pallet_ismp_runtime_api::IsmpRuntimeApi<Block, H256> {}
+#[cfg(feature = "ismp")]
+impl<T: pallet_ismp_runtime_api::IsmpRuntimeApi<Block, H256>> IsmpApi for T {}
+
+/// The runtime API of ISMP, required by the RPCs of nodes built with the `ismp` feature only.
 #[cfg(not(feature = "ismp"))]
-pub fn create_full<C, P, B>(
-	deps: FullDeps<C, P, B>,
-) -> Result<RpcExtension, Box<dyn std::error::Error + Send + Sync>>
+pub trait IsmpApi {}
+#[cfg(not(feature = "ismp"))]
+impl<T> IsmpApi for T {}
+
+/// The RPCs of the runtime APIs of contracts, which only the runtimes supporting contracts
+/// provide. Implemented for the api of each runtime, so that the RPCs call the runtime APIs the
+/// runtime implements.
+pub trait ContractRpcs<C> {
+	/// Merge the RPCs into `module`, for the runtime APIs the runtime of the best block of
+	/// `client` provides.
+	fn merge(
+		module: &mut RpcExtension,
+		client: &Arc<C>,
+	) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+}
+
+/// Whether the runtime of the best block of `client` provides the runtime API `Api`.
+fn has_api<Api, C>(client: &C) -> Result<bool, ApiError>
 where
-	C: ProvideRuntimeApi<Block>
-		+ HeaderBackend<Block>
-		+ AuxStore
-		+ BlockBackend<Block>
-		+ ProofProvider<Block>
-		+ HeaderMetadata<Block, Error = BlockChainError>
-		+ Send
-		+ Sync
-		+ 'static,
-	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
-	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
-	C::Api: BlockBuilder<Block>,
-	P: TransactionPool + Sync + Send + 'static,
-	B: sc_client_api::Backend<Block> + Send + Sync + 'static,
-	B::State: sc_client_api::StateBackend<sp_runtime::traits::HashingFor<Block>>,
+	Api: RuntimeApiInfo + ?Sized,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
 {
-	let mut module = RpcExtension::new(());
-	let FullDeps { client, pool, deny_unsafe, backend: _ } = deps;
+	client.runtime_api().has_api::<Api>(client.info().best_hash)
+}
 
-	module.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
-	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+/// Merge the RPCs of the runtime APIs of contracts provided by both devnet and testnet.
+fn merge_contract_rpcs<C>(
+	module: &mut RpcExtension,
+	client: &Arc<C>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: pop_runtime_common::apis::StorageDepositApi<Block, Balance>,
+	C::Api: pop_runtime_common::apis::ContractAddressApi<Block, AccountId, Hash>,
+	C::Api: pop_runtime_common::apis::ContractCostApi<Block, AccountId, Balance>,
+	C::Api: pop_runtime_common::apis::ContractInstantiationApi<Block, AccountId, Balance, Hash>,
+{
+	use pop_runtime_common::apis::{
+		ContractAddressApi, ContractCostApi, ContractInstantiationApi, StorageDepositApi,
+	};
 
-	Ok(module)
+	if has_api::<dyn StorageDepositApi<Block, Balance>, _>(&**client)? {
+		module.merge(StorageDeposit::new(client.clone()).into_rpc())?;
+	}
+	if has_api::<dyn ContractAddressApi<Block, AccountId, Hash>, _>(&**client)? {
+		module.merge(ContractAddress::new(client.clone()).into_rpc())?;
+	}
+	if has_api::<dyn ContractCostApi<Block, AccountId, Balance>, _>(&**client)? {
+		module.merge(ContractCost::new(client.clone()).into_rpc())?;
+	}
+	if has_api::<dyn ContractInstantiationApi<Block, AccountId, Balance, Hash>, _>(&**client)? {
+		module.merge(ContractInstantiation::new(client.clone()).into_rpc())?;
+	}
+	Ok(())
 }
 
-/// Instantiate all RPC extensions.
-#[cfg(feature = "ismp")]
-pub fn create_full<C, P, B>(
+impl<C> ContractRpcs<C> for pop_runtime_devnet::RuntimeApi
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: pop_runtime_common::apis::StorageDepositApi<Block, Balance>,
+	C::Api: pop_runtime_common::apis::ContractAddressApi<Block, AccountId, Hash>,
+	C::Api: pop_runtime_common::apis::ContractCostApi<Block, AccountId, Balance>,
+	C::Api: pop_runtime_common::apis::ContractInstantiationApi<Block, AccountId, Balance, Hash>,
+{
+	fn merge(
+		module: &mut RpcExtension,
+		client: &Arc<C>,
+	) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+		merge_contract_rpcs(module, client)
+	}
+}
+
+impl<C> ContractRpcs<C> for pop_runtime_testnet::RuntimeApi
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: pop_runtime_common::apis::StorageDepositApi<Block, Balance>,
+	C::Api: pop_runtime_common::apis::ContractAddressApi<Block, AccountId, Hash>,
+	C::Api: pop_runtime_common::apis::ContractCostApi<Block, AccountId, Balance>,
+	C::Api: pop_runtime_common::apis::ContractInstantiationApi<Block, AccountId, Balance, Hash>,
+	C::Api: pop_runtime_common::apis::ContractMetadataApi<Block, AccountId, Hash>,
+{
+	fn merge(
+		module: &mut RpcExtension,
+		client: &Arc<C>,
+	) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+		use pop_runtime_common::apis::ContractMetadataApi;
+
+		merge_contract_rpcs(module, client)?;
+		// Only testnet stores the metadata of contracts.
+		if has_api::<dyn ContractMetadataApi<Block, AccountId, Hash>, _>(&**client)? {
+			module.merge(ContractMetadata::new(client.clone()).into_rpc())?;
+		}
+		Ok(())
+	}
+}
+
+/// Mainnet has no contracts.
+impl<C> ContractRpcs<C> for pop_runtime_mainnet::RuntimeApi {
+	fn merge(
+		_: &mut RpcExtension,
+		_: &Arc<C>,
+	) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+		Ok(())
+	}
+}
+
+/// Instantiate all RPC extensions, those of contracts as provided by the runtime `R`.
+pub fn create_full<R, C, P, B>(
 	deps: FullDeps<C, P, B>,
 ) -> Result<RpcExtension, Box<dyn std::error::Error + Send + Sync>>
 where
@@ -76,6 +226,8 @@ where
 		+ AuxStore
 		+ BlockBackend<Block>
 		+ ProofProvider<Block>
+		+ ExecutorProvider<Block>
+		+ StorageProvider<Block, B>
 		+ HeaderMetadata<Block, Error = BlockChainError>
 		+ Send
 		+ Sync
@@ -83,19 +235,69 @@ where
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
 	C::Api: BlockBuilder<Block>,
-	C::Api: pallet_ismp_runtime_api::IsmpRuntimeApi<Block, H256>,
-	P: TransactionPool + Sync + Send + 'static,
+	C::Api: sp_api::Metadata<Block>,
+	C::Api: pop_runtime_common::apis::NextAuthorApi<Block, AccountId>,
+	C::Api: pop_runtime_common::apis::UpgradeStatusApi<Block, Hash>,
+	C::Api: pop_runtime_common::apis::CollatorRegistrationApi<Block, AccountId, Balance>,
+	C::Api: pop_runtime_common::apis::CollatorSetApi<Block, AccountId, Balance>,
+	C::Api: pop_runtime_common::apis::ProxyApi<Block, AccountId, ProxyType>,
+	C::Api: pop_runtime_common::apis::XcmConversionApi<Block, AccountId>,
+	C::Api: pop_runtime_common::apis::AccountConversionApi<Block, AccountId>,
+	C::Api: pop_runtime_common::apis::XcmQueueApi<Block>,
+	C::Api: pop_runtime_common::apis::XcmVersionApi<Block>,
+	C::Api: pop_runtime_common::apis::SupplyApi<Block, Balance>,
+	C::Api: pop_runtime_common::apis::FeeApi<Block>,
+	C::Api: pop_runtime_common::apis::AssetFeeApi<Block, AssetId, Balance>,
+	C::Api: pop_runtime_common::apis::BlockUsageApi<Block>,
+	C::Api: pop_runtime_common::apis::RuntimeInfoApi<Block>,
+	C::Api: IsmpApi,
+	R: ContractRpcs<C>,
+	P: TransactionPool<Block = Block> + Sync + Send + 'static,
 	B: sc_client_api::Backend<Block> + Send + Sync + 'static,
 	B::State: sc_client_api::StateBackend<sp_runtime::traits::HashingFor<Block>>,
 {
 	let mut module = RpcExtension::new(());
-	let FullDeps { client, pool, deny_unsafe, backend } = deps;
+	let FullDeps { client, pool, deny_unsafe, backend, contracts_debug, chain_spec } = deps;
 
-	module.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
+	module.merge(System::new(client.clone(), pool.clone(), deny_unsafe).into_rpc())?;
 	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	module.merge(Submit::new(client.clone(), pool).into_rpc())?;
+	module.merge(NextAuthor::new(client.clone()).into_rpc())?;
+	module.merge(Metadata::new(client.clone()).into_rpc())?;
+	module.merge(UpgradeStatus::new(client.clone()).into_rpc())?;
+	module.merge(CollatorRegistration::new(client.clone()).into_rpc())?;
+	module.merge(CollatorSets::new(client.clone()).into_rpc())?;
+	module.merge(Proxies::new(client.clone()).into_rpc())?;
+	module.merge(XcmAccount::new(client.clone()).into_rpc())?;
+	module.merge(AccountConversion::new(client.clone()).into_rpc())?;
+	module.merge(XcmQueue::new(client.clone()).into_rpc())?;
+	module.merge(XcmVersion::new(client.clone()).into_rpc())?;
+	module.merge(Supply::new(client.clone(), deny_unsafe).into_rpc())?;
+	module.merge(Fee::new(client.clone()).into_rpc())?;
+	module.merge(Pallets::new(client.clone()).into_rpc())?;
+	module.merge(Finality::new(client.clone()).into_rpc())?;
+	module.merge(BlockWeight::new(client.clone()).into_rpc())?;
+	module.merge(Inspect::new(client.clone()).into_rpc())?;
+	module.merge(StorageBatch::new(client.clone()).into_rpc())?;
+	module.merge(GenesisConfig::new(&*chain_spec).into_rpc())?;
+	module.merge(Address::new(&*chain_spec).into_rpc())?;
+	module.merge(Replay::new(client.clone(), backend.clone(), deny_unsafe).into_rpc())?;
+	// The methods dry-running contracts via the `ContractsApi` are only served if the runtime
+	// provides it as the node starts, rather than failing every call.
+	if contract_result::has_contracts_api(&*client)? {
+		module.merge(ContractDryRun::new(client.clone()).into_rpc())?;
+		module.merge(Contracts::new(client.clone()).into_rpc())?;
+		if contracts_debug {
+			module.merge(ContractTrace::new(client.clone(), deny_unsafe).into_rpc())?;
+		}
+	}
+	R::merge(&mut module, &client)?;
 
-	use pallet_ismp_rpc::{IsmpApiServer, IsmpRpcHandler};
-	module.merge(IsmpRpcHandler::new(client, backend.clone())?.into_rpc())?;
+	#[cfg(feature = "ismp")]
+	{
+		use pallet_ismp_rpc::{IsmpApiServer, IsmpRpcHandler};
+		module.merge(IsmpRpcHandler::new(client, backend)?.into_rpc())?;
+	}
 
 	Ok(module)
 }
@@ -0,0 +1,136 @@
+//! RPC methods for converting between accounts and the locations they belong to, e.g. for dApps
+//! to compute the sovereign accounts of other chains without reimplementing their derivation
+//! off-chain.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::ErrorObject,
+};
+use pop_runtime_common::{apis::AccountConversionApi as AccountConversionRuntimeApi, AccountId};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+use xcm::{latest::Location, VersionedLocation};
+
+/// Account conversion RPC methods.
+#[rpc(server)]
+pub trait AccountConversionApi<BlockHash> {
+	/// Returns the location of the local `account` as seen from this chain, as of the block `at`
+	/// or the best block.
+	#[method(name = "pop_accountLocation")]
+	async fn account_location(
+		&self,
+		account: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Location>;
+
+	/// Returns the sovereign account of the sibling parachain `para_id` on this chain, as of the
+	/// block `at` or the best block.
+	#[method(name = "pop_siblingAccount")]
+	async fn sibling_account(&self, para_id: u32, at: Option<BlockHash>) -> RpcResult<AccountId>;
+
+	/// Returns the sovereign account of the relay chain on this chain, as of the block `at` or the
+	/// best block.
+	#[method(name = "pop_relayChainAccount")]
+	async fn relay_chain_account(&self, at: Option<BlockHash>) -> RpcResult<AccountId>;
+
+	/// Returns the account of `location`, given in the latest version of XCM as seen from the
+	/// chain deriving the account, derived from the hash of its description as chains like Asset
+	/// Hub derive the accounts of remote locations, as of the block `at` or the best block.
+	/// Returns `null` if the location cannot be described.
+	#[method(name = "pop_hashedAccount")]
+	async fn hashed_account(
+		&self,
+		location: Location,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<AccountId>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The result of the call was not decodable.
+	DecodeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::DecodeError => 2,
+		}
+	}
+}
+
+/// An implementation of account conversion RPC methods.
+pub struct AccountConversion<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> AccountConversion<C, Block> {
+	/// Create new `AccountConversion` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// The error of a failed call to the runtime.
+fn runtime_error(e: impl ToString) -> ErrorObject<'static> {
+	ErrorObject::owned(
+		Error::RuntimeError.into(),
+		"Unable to convert account.",
+		Some(e.to_string()),
+	)
+}
+
+#[async_trait]
+impl<C, Block> AccountConversionApiServer<Block::Hash> for AccountConversion<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: AccountConversionRuntimeApi<Block, AccountId>,
+{
+	async fn account_location(
+		&self,
+		account: AccountId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Location> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let location =
+			self.client.runtime_api().account_location(at, account).map_err(runtime_error)?;
+		Location::try_from(location).map_err(|()| {
+			ErrorObject::owned(
+				Error::DecodeError.into(),
+				"Unable to convert the location to the latest version.",
+				None::<()>,
+			)
+		})
+	}
+
+	async fn sibling_account(&self, para_id: u32, at: Option<Block::Hash>) -> RpcResult<AccountId> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().sibling_account(at, para_id).map_err(runtime_error)
+	}
+
+	async fn relay_chain_account(&self, at: Option<Block::Hash>) -> RpcResult<AccountId> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().relay_chain_account(at).map_err(runtime_error)
+	}
+
+	async fn hashed_account(
+		&self,
+		location: Location,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<AccountId>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.hashed_account(at, VersionedLocation::V4(location))
+			.map_err(runtime_error)
+	}
+}
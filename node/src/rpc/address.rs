@@ -0,0 +1,123 @@
+//! RPC methods for validating SS58 addresses against the address format of the chain, e.g. for
+//! wallets to warn before sending funds to an address of another network.
+
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::ErrorObject,
+};
+use pop_runtime_common::AccountId;
+use sc_chain_spec::ChainSpec;
+use serde::Serialize;
+use sp_core::crypto::Ss58Codec;
+
+/// The address format used if the chain specification does not configure one.
+const DEFAULT_SS58_FORMAT: u16 = 42;
+
+/// A decoded SS58 address.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressValidation {
+	/// Whether the address is encoded in the address format of the chain.
+	pub valid: bool,
+	/// The address format the address is encoded in.
+	pub prefix: u16,
+	/// The address format of the chain.
+	pub expected_prefix: u16,
+	/// The account of the address.
+	pub account: AccountId,
+}
+
+/// Address RPC methods.
+#[rpc(server)]
+pub trait AddressApi {
+	/// Decodes the SS58 `address` and returns whether it is encoded in the address format of the
+	/// chain, i.e. its `ss58Format` property, along with its account. Returns an error if the
+	/// address is not an SS58 address at all.
+	#[method(name = "pop_validateAddress")]
+	async fn validate_address(&self, address: String) -> RpcResult<AddressValidation>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The address is not an SS58 address.
+	InvalidAddress,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::InvalidAddress => 1,
+		}
+	}
+}
+
+/// The address format configured by the `ss58Format` property of `chain_spec`.
+fn ss58_format(chain_spec: &dyn ChainSpec) -> u16 {
+	chain_spec
+		.properties()
+		.get("ss58Format")
+		.and_then(|format| format.as_u64())
+		.and_then(|format| u16::try_from(format).ok())
+		.unwrap_or(DEFAULT_SS58_FORMAT)
+}
+
+/// Decode `address`, checking it against the address format `expected`.
+fn validate(address: &str, expected: u16) -> Result<AddressValidation, String> {
+	let (account, format) =
+		AccountId::from_ss58check_with_version(address.trim()).map_err(|e| format!("{e:?}"))?;
+	let prefix = u16::from(format);
+	Ok(AddressValidation { valid: prefix == expected, prefix, expected_prefix: expected, account })
+}
+
+/// An implementation of address RPC methods.
+pub struct Address {
+	ss58_format: u16,
+}
+
+impl Address {
+	/// Create new `Address` validating addresses against the address format of the given chain
+	/// specification.
+	pub fn new(chain_spec: &dyn ChainSpec) -> Self {
+		Self { ss58_format: ss58_format(chain_spec) }
+	}
+}
+
+#[async_trait]
+impl AddressApiServer for Address {
+	async fn validate_address(&self, address: String) -> RpcResult<AddressValidation> {
+		validate(&address, self.ss58_format).map_err(|e| {
+			ErrorObject::owned(Error::InvalidAddress.into(), "Invalid SS58 address.", Some(e))
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use sp_core::crypto::Ss58AddressFormat;
+
+	use super::*;
+	use crate::chain_spec::{development_config, Code, Relay};
+
+	#[test]
+	fn addresses_of_other_networks_are_flagged() {
+		let spec = development_config(Relay::PaseoLocal, None, vec![], vec![], Code::Compressed);
+		let expected = ss58_format(&spec);
+		assert_eq!(expected, 42);
+		let account = AccountId::from([1; 32]);
+
+		let address = account.to_ss58check_with_version(Ss58AddressFormat::custom(42));
+		let validation = validate(&address, expected).unwrap();
+		assert!(validation.valid);
+		assert_eq!(validation.account, account);
+
+		// The same account on the Polkadot relay chain.
+		let address = account.to_ss58check_with_version(Ss58AddressFormat::custom(0));
+		let validation = validate(&address, expected).unwrap();
+		assert!(!validation.valid);
+		assert_eq!((validation.prefix, validation.expected_prefix), (0, 42));
+		assert_eq!(validation.account, account);
+
+		assert!(validate("not an address", expected).is_err());
+	}
+}
@@ -0,0 +1,173 @@
+//! RPC methods for inspecting the weight consumed by blocks, e.g. to investigate how close blocks
+//! run to the weight limit under load.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Decode;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::ErrorObject,
+};
+use pop_runtime_common::MAXIMUM_BLOCK_WEIGHT;
+use sc_client_api::{Backend, StorageProvider};
+use serde::Serialize;
+use sp_blockchain::HeaderBackend;
+use sp_core::{hashing::twox_128, storage::StorageKey};
+use sp_runtime::traits::Block as BlockT;
+use sp_weights::Weight;
+
+/// The weight consumed by a block, per dispatch class.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ConsumedWeight {
+	/// The weight consumed by normal dispatches, e.g. transfers and contract calls.
+	pub normal: ClassWeight,
+	/// The weight consumed by operational dispatches, e.g. governance.
+	pub operational: ClassWeight,
+	/// The weight consumed by mandatory dispatches, i.e. inherents and block initialization.
+	pub mandatory: ClassWeight,
+}
+
+/// The weight consumed by the dispatches of a class.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ClassWeight {
+	/// The weight consumed.
+	pub consumed: Weight,
+	/// The reference time consumed, as a fraction of the reference time limit of a block.
+	pub ref_time: f64,
+	/// The proof size consumed, as a fraction of the proof size limit of a block.
+	pub proof_size: f64,
+}
+
+impl From<Weight> for ClassWeight {
+	fn from(consumed: Weight) -> Self {
+		let fraction = |consumed: u64, limit: u64| consumed as f64 / limit as f64;
+		Self {
+			consumed,
+			ref_time: fraction(consumed.ref_time(), MAXIMUM_BLOCK_WEIGHT.ref_time()),
+			proof_size: fraction(consumed.proof_size(), MAXIMUM_BLOCK_WEIGHT.proof_size()),
+		}
+	}
+}
+
+/// Block weight RPC methods.
+#[rpc(server)]
+pub trait BlockWeightApi<BlockHash> {
+	/// Returns the weight consumed by the block `at` or the best block, per dispatch class.
+	#[method(name = "pop_blockWeight")]
+	async fn block_weight(&self, at: Option<BlockHash>) -> RpcResult<ConsumedWeight>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The storage could not be read.
+	StorageError,
+	/// The consumed weight was not decodable.
+	DecodeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::StorageError => 1,
+			Error::DecodeError => 2,
+		}
+	}
+}
+
+/// The storage key of `frame_system::BlockWeight`.
+fn block_weight_key() -> StorageKey {
+	StorageKey([twox_128(b"System"), twox_128(b"BlockWeight")].concat())
+}
+
+/// Decode the value of `frame_system::BlockWeight`, which is zero for every class if unset.
+fn consumed_weight(value: Option<&[u8]>) -> Result<ConsumedWeight, codec::Error> {
+	// `frame_support::dispatch::PerDispatchClass<Weight>`.
+	let (normal, operational, mandatory) = match value {
+		Some(mut value) => <(Weight, Weight, Weight)>::decode(&mut value)?,
+		None => Default::default(),
+	};
+	Ok(ConsumedWeight {
+		normal: normal.into(),
+		operational: operational.into(),
+		mandatory: mandatory.into(),
+	})
+}
+
+/// An implementation of block weight RPC methods.
+pub struct BlockWeight<C, B, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<(B, Block)>,
+}
+
+impl<C, B, Block> BlockWeight<C, B, Block> {
+	/// Create new `BlockWeight` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+#[async_trait]
+impl<C, B, Block> BlockWeightApiServer<Block::Hash> for BlockWeight<C, B, Block>
+where
+	Block: BlockT,
+	B: Backend<Block> + Send + Sync + 'static,
+	C: StorageProvider<Block, B> + HeaderBackend<Block> + Send + Sync + 'static,
+{
+	async fn block_weight(&self, at: Option<Block::Hash>) -> RpcResult<ConsumedWeight> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let value = self.client.storage(at, &block_weight_key()).map_err(|e| {
+			ErrorObject::owned(
+				Error::StorageError.into(),
+				"Unable to query block weight.",
+				Some(e.to_string()),
+			)
+		})?;
+		consumed_weight(value.as_ref().map(|value| &value.0[..])).map_err(|e| {
+			ErrorObject::owned(
+				Error::DecodeError.into(),
+				"Unable to decode block weight.",
+				Some(e.to_string()),
+			)
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use frame_support::dispatch::DispatchClass;
+	use pallet_balances::WeightInfo;
+	use pop_runtime_testnet::Runtime;
+
+	use super::*;
+
+	#[test]
+	fn transfers_consume_normal_weight() {
+		let transfer = <Runtime as pallet_balances::Config>::WeightInfo::transfer_allow_death();
+		let value = sp_io::TestExternalities::default().execute_with(|| {
+			for _ in 0..3 {
+				frame_system::Pallet::<Runtime>::register_extra_weight_unchecked(
+					transfer,
+					DispatchClass::Normal,
+				);
+			}
+			sp_io::storage::get(&block_weight_key().0).unwrap()
+		});
+
+		let weight = consumed_weight(Some(&value[..])).unwrap();
+		assert_eq!(weight.normal.consumed, transfer * 3);
+		assert!(weight.normal.ref_time > 0.0 && weight.normal.ref_time < 1.0);
+		assert_eq!(weight.operational, Weight::zero().into());
+		assert_eq!(weight.mandatory, Weight::zero().into());
+	}
+
+	#[test]
+	fn unset_block_weight_is_zero() {
+		let weight = consumed_weight(None).unwrap();
+		assert_eq!(
+			weight.normal,
+			ClassWeight { consumed: Weight::zero(), ref_time: 0.0, proof_size: 0.0 }
+		);
+		assert!(consumed_weight(Some(&[0][..])).is_err());
+	}
+}
@@ -0,0 +1,104 @@
+//! RPC methods for previewing collator registration.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::ErrorObject,
+};
+use pop_runtime_common::apis::{
+	CollatorIneligibility, CollatorRegistrationApi as CollatorRegistrationRuntimeApi,
+};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+/// Whether an account can register as a collator candidate.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Registration<Balance> {
+	/// Whether the account would be accepted as a candidate.
+	pub can_register: bool,
+	/// The reason why the account would not be accepted, if it would not.
+	pub reason: Option<CollatorIneligibility<Balance>>,
+}
+
+impl<Balance> From<Result<(), CollatorIneligibility<Balance>>> for Registration<Balance> {
+	fn from(result: Result<(), CollatorIneligibility<Balance>>) -> Self {
+		Self { can_register: result.is_ok(), reason: result.err() }
+	}
+}
+
+/// Collator registration RPC methods.
+#[rpc(server)]
+pub trait CollatorRegistrationApi<BlockHash, AccountId, Balance> {
+	/// Returns whether `account` would be accepted as a collator candidate as of the block `at`
+	/// or the best block, i.e. whether it can cover the candidacy bond, has registered its
+	/// session keys, is not a collator already and a candidate slot is available.
+	#[method(name = "pop_canRegisterCollator")]
+	async fn can_register_collator(
+		&self,
+		account: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Registration<Balance>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+/// An implementation of collator registration RPC methods.
+pub struct CollatorRegistration<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> CollatorRegistration<C, Block> {
+	/// Create new `CollatorRegistration` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+#[async_trait]
+impl<C, Block, AccountId, Balance> CollatorRegistrationApiServer<Block::Hash, AccountId, Balance>
+	for CollatorRegistration<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: CollatorRegistrationRuntimeApi<Block, AccountId, Balance>,
+	AccountId: Codec + Send + Sync + 'static,
+	Balance: Codec + Serialize + Send + Sync + 'static,
+{
+	async fn can_register_collator(
+		&self,
+		account: AccountId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Registration<Balance>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.can_register_collator(at, account)
+			.map(Into::into)
+			.map_err(|e| {
+				ErrorObject::owned(
+					Error::RuntimeError.into(),
+					"Unable to query collator registration.",
+					Some(e.to_string()),
+				)
+			})
+	}
+}
@@ -0,0 +1,81 @@
+//! RPC methods for querying the collators, e.g. for operators onboarding community collators to
+//! see the candidates they compete with and the bond they must reserve.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::ErrorObject,
+};
+use pop_runtime_common::apis::{CollatorSet, CollatorSetApi as CollatorSetRuntimeApi};
+use serde::Serialize;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+/// Collator set RPC methods.
+#[rpc(server)]
+pub trait CollatorSetApi<BlockHash, AccountId, Balance> {
+	/// Returns the collators of the session as of the block `at` or the best block, along with
+	/// the invulnerables, the candidates and their deposits, the desired number of candidates and
+	/// the candidacy bond.
+	#[method(name = "pop_collatorSet")]
+	async fn collator_set(
+		&self,
+		at: Option<BlockHash>,
+	) -> RpcResult<CollatorSet<AccountId, Balance>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+/// An implementation of collator set RPC methods.
+pub struct CollatorSets<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> CollatorSets<C, Block> {
+	/// Create new `CollatorSets` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+#[async_trait]
+impl<C, Block, AccountId, Balance> CollatorSetApiServer<Block::Hash, AccountId, Balance>
+	for CollatorSets<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: CollatorSetRuntimeApi<Block, AccountId, Balance>,
+	AccountId: Codec + Serialize + Send + Sync + 'static,
+	Balance: Codec + Serialize + Send + Sync + 'static,
+{
+	async fn collator_set(
+		&self,
+		at: Option<Block::Hash>,
+	) -> RpcResult<CollatorSet<AccountId, Balance>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().collator_set(at).map_err(|e| {
+			ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Unable to query the collator set.",
+				Some(e.to_string()),
+			)
+		})
+	}
+}
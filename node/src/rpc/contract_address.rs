@@ -0,0 +1,87 @@
+//! RPC methods for deriving the addresses of contracts, e.g. for dApps to fund a contract before
+//! instantiating it, without reimplementing the derivation of its address off-chain.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::ErrorObject,
+};
+use pop_runtime_common::{apis::ContractAddressApi as ContractAddressRuntimeApi, AccountId, Hash};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::traits::Block as BlockT;
+
+/// Contract address RPC methods.
+#[rpc(server)]
+pub trait ContractAddressApi<BlockHash> {
+	/// Returns the address of the contract `deployer` instantiates from the code `code_hash` with
+	/// `input_data` and `salt`, as of the block `at` or the best block.
+	#[method(name = "pop_contractAddress")]
+	async fn contract_address(
+		&self,
+		deployer: AccountId,
+		code_hash: Hash,
+		input_data: Bytes,
+		salt: Bytes,
+		at: Option<BlockHash>,
+	) -> RpcResult<AccountId>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+/// An implementation of contract address RPC methods.
+pub struct ContractAddress<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> ContractAddress<C, Block> {
+	/// Create new `ContractAddress` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+#[async_trait]
+impl<C, Block> ContractAddressApiServer<Block::Hash> for ContractAddress<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ContractAddressRuntimeApi<Block, AccountId, Hash>,
+{
+	async fn contract_address(
+		&self,
+		deployer: AccountId,
+		code_hash: Hash,
+		input_data: Bytes,
+		salt: Bytes,
+		at: Option<Block::Hash>,
+	) -> RpcResult<AccountId> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.contract_address(at, deployer, code_hash, input_data.0, salt.0)
+			.map_err(|e| {
+				ErrorObject::owned(
+					Error::RuntimeError.into(),
+					"Unable to derive contract address.",
+					Some(e.to_string()),
+				)
+			})
+	}
+}
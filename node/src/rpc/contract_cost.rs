@@ -0,0 +1,102 @@
+//! RPC methods for estimating the full cost of contract calls, e.g. for wallets to show the fee
+//! and the storage deposit of a call in one request before submitting the transaction.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::ErrorObject,
+};
+use pop_runtime_common::{
+	apis::{ContractCallCost, ContractCostApi as ContractCostRuntimeApi},
+	AccountId, Balance,
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::traits::Block as BlockT;
+
+/// Contract cost RPC methods.
+#[rpc(server)]
+pub trait ContractCostApi<BlockHash> {
+	/// Returns the estimated cost of a call of the contract at `dest` by `origin` with
+	/// `input_data` transferring `value`, as of the block `at` or the best block: the gas it
+	/// requires, the storage deposit it charges and the fee of a transaction making it, along
+	/// with their total. Fails if the call would fail, e.g. if the contract reverts.
+	#[method(name = "pop_estimateContractCallCost")]
+	async fn estimate_contract_call_cost(
+		&self,
+		origin: AccountId,
+		dest: AccountId,
+		value: Balance,
+		input_data: Bytes,
+		at: Option<BlockHash>,
+	) -> RpcResult<ContractCallCost<Balance>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The contract call failed.
+	CallError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::CallError => 3,
+		}
+	}
+}
+
+/// An implementation of contract cost RPC methods.
+pub struct ContractCost<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> ContractCost<C, Block> {
+	/// Create new `ContractCost` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+#[async_trait]
+impl<C, Block> ContractCostApiServer<Block::Hash> for ContractCost<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ContractCostRuntimeApi<Block, AccountId, Balance>,
+{
+	async fn estimate_contract_call_cost(
+		&self,
+		origin: AccountId,
+		dest: AccountId,
+		value: Balance,
+		input_data: Bytes,
+		at: Option<Block::Hash>,
+	) -> RpcResult<ContractCallCost<Balance>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.estimate_call_cost(at, origin, dest, value, input_data.0)
+			.map_err(|e| {
+				ErrorObject::owned(
+					Error::RuntimeError.into(),
+					"Unable to estimate contract call cost.",
+					Some(e.to_string()),
+				)
+			})?
+			.map_err(|e| {
+				ErrorObject::owned(
+					Error::CallError.into(),
+					"Unable to execute contract call.",
+					Some(format!("{e:?}")),
+				)
+			})
+	}
+}
@@ -0,0 +1,210 @@
+//! RPC methods for dry-running contract calls with weight v2 gas limits, i.e. limiting the
+//! reference time and the proof size of a call separately.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::{Codec, Decode, Encode};
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::{error::ErrorObject, ErrorObjectOwned},
+};
+use pallet_contracts::ExecReturnValue;
+use pop_runtime_common::Balance;
+use sc_client_api::{CallExecutor, ExecutorProvider};
+use serde::Serialize;
+use sp_blockchain::HeaderBackend;
+use sp_core::{traits::CallContext, Bytes};
+use sp_runtime::traits::Block as BlockT;
+use sp_weights::Weight;
+
+use super::contract_result::{ContractResultPrefix, Deposit, CONTRACTS_API_CALL};
+
+/// The result of a dry-run contract call.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DryRun {
+	/// The gas consumed by the call.
+	pub gas_consumed: Weight,
+	/// The gas limit required for the call to succeed, which may exceed the gas consumed.
+	pub gas_required: Weight,
+	/// The balance charged for the storage added by the call, or refunded for the storage it
+	/// removed.
+	pub storage_deposit: Deposit,
+	/// The data returned by the contract.
+	pub data: Bytes,
+	/// Whether the contract reverted, in which case its changes are rolled back.
+	pub reverted: bool,
+}
+
+/// Contract dry-run RPC methods.
+#[rpc(server)]
+pub trait ContractDryRunApi<BlockHash, AccountId> {
+	/// Dry-run a call of the contract at `dest` by `origin` with `input_data`, transferring
+	/// `value`, as of the block `at` or the best block.
+	///
+	/// The call is limited to `gas_limit`, given as `{ "ref_time", "proof_size" }`. Without a
+	/// limit, the call may consume up to the block weight, so that the returned `gasRequired` is
+	/// an estimate of the limit to submit the call with.
+	#[method(name = "pop_dryRunCall")]
+	async fn dry_run_call(
+		&self,
+		origin: AccountId,
+		dest: AccountId,
+		value: Balance,
+		input_data: Bytes,
+		gas_limit: Option<Weight>,
+		storage_deposit_limit: Option<Balance>,
+		at: Option<BlockHash>,
+	) -> RpcResult<DryRun>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The result of the call was not decodable.
+	DecodeError,
+	/// The contract call could not be executed.
+	CallError,
+	/// The reference time of the gas limit is insufficient.
+	OutOfRefTime,
+	/// The proof size of the gas limit is insufficient.
+	OutOfProofSize,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::DecodeError => 2,
+			Error::CallError => 3,
+			Error::OutOfRefTime => 4,
+			Error::OutOfProofSize => 5,
+		}
+	}
+}
+
+/// The error of a call limited to `limit` which requires `required`, if `limit` is insufficient
+/// in either dimension.
+fn out_of_gas(limit: Weight, required: Weight) -> Option<ErrorObjectOwned> {
+	let error = |error: Error, dimension: &str, required: u64, limit: u64| {
+		ErrorObject::owned(
+			error.into(),
+			format!("Out of gas: insufficient {dimension}."),
+			Some(format!("The call requires a {dimension} of {required}, the limit is {limit}.")),
+		)
+	};
+	if required.ref_time() > limit.ref_time() {
+		Some(error(Error::OutOfRefTime, "ref time", required.ref_time(), limit.ref_time()))
+	} else if required.proof_size() > limit.proof_size() {
+		Some(error(Error::OutOfProofSize, "proof size", required.proof_size(), limit.proof_size()))
+	} else {
+		None
+	}
+}
+
+/// An implementation of contract dry-run RPC methods.
+pub struct ContractDryRun<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> ContractDryRun<C, Block> {
+	/// Create new `ContractDryRun` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+
+	/// Dry-run the contract call with the encoded `args` as of the block `at`.
+	fn call(&self, at: Block::Hash, args: &[u8]) -> RpcResult<ContractResultPrefix<ExecReturnValue>>
+	where
+		Block: BlockT,
+		C: ExecutorProvider<Block>,
+	{
+		let result = self
+			.client
+			.executor()
+			.call(at, CONTRACTS_API_CALL, args, CallContext::Offchain)
+			.map_err(|e| {
+				ErrorObject::owned(
+					Error::RuntimeError.into(),
+					"Unable to dry-run contract call.",
+					Some(e.to_string()),
+				)
+			})?;
+		ContractResultPrefix::decode(&mut &result[..]).map_err(|e| {
+			ErrorObject::owned(
+				Error::DecodeError.into(),
+				"Unable to decode contract call result.",
+				Some(e.to_string()),
+			)
+		})
+	}
+}
+
+#[async_trait]
+impl<C, Block, AccountId> ContractDryRunApiServer<Block::Hash, AccountId>
+	for ContractDryRun<C, Block>
+where
+	Block: BlockT,
+	C: ExecutorProvider<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	AccountId: Codec + Send + Sync + 'static,
+{
+	async fn dry_run_call(
+		&self,
+		origin: AccountId,
+		dest: AccountId,
+		value: Balance,
+		input_data: Bytes,
+		gas_limit: Option<Weight>,
+		storage_deposit_limit: Option<Balance>,
+		at: Option<Block::Hash>,
+	) -> RpcResult<DryRun> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let args = |gas_limit: Option<Weight>| {
+			(&origin, &dest, value, gas_limit, storage_deposit_limit, &input_data.0).encode()
+		};
+		let result = self.call(at, &args(gas_limit))?;
+		let error = match (result.result, gas_limit) {
+			(Ok(returned), _) =>
+				return Ok(DryRun {
+					gas_consumed: result.gas_consumed,
+					gas_required: result.gas_required,
+					storage_deposit: result.storage_deposit.into(),
+					reverted: returned.did_revert(),
+					data: returned.data.into(),
+				}),
+			// The limit may be insufficient, in which case the gas required without a limit
+			// tells which dimension falls short.
+			(Err(error), Some(limit)) => {
+				let estimate = self.call(at, &args(None))?;
+				if let Some(out_of_gas) = out_of_gas(limit, estimate.gas_required) {
+					return Err(out_of_gas);
+				}
+				error
+			},
+			(Err(error), None) => error,
+		};
+		Err(ErrorObject::owned(
+			Error::CallError.into(),
+			"Unable to execute contract call.",
+			Some(format!("{error:?}")),
+		))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn insufficient_proof_size_is_distinct_from_ref_time() {
+		let required = Weight::from_parts(1_000, 100);
+		let code = |limit| out_of_gas(limit, required).map(|e| e.code());
+		assert_eq!(code(Weight::from_parts(999, 100)), Some(Error::OutOfRefTime.into()));
+		assert_eq!(code(Weight::from_parts(1_000, 99)), Some(Error::OutOfProofSize.into()));
+		assert_eq!(code(Weight::from_parts(999, 99)), Some(Error::OutOfRefTime.into()));
+		assert_eq!(code(required), None);
+	}
+}
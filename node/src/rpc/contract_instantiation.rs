@@ -0,0 +1,110 @@
+//! RPC methods for previewing contract instantiations, e.g. for wallets to check that an account
+//! can cover the cost of instantiating a contract before submitting the transaction.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::ErrorObject,
+};
+use pop_runtime_common::{
+	apis::{
+		ContractInstantiationApi as ContractInstantiationRuntimeApi, InstantiationUnaffordable,
+	},
+	AccountId, Balance, Hash,
+};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+/// Whether an account can afford to instantiate a contract.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Affordability {
+	/// Whether the account can cover the cost of the instantiation.
+	pub can_afford: bool,
+	/// The reason why the account cannot cover the cost, if it cannot.
+	pub reason: Option<InstantiationUnaffordable<Balance>>,
+}
+
+impl From<Result<(), InstantiationUnaffordable<Balance>>> for Affordability {
+	fn from(result: Result<(), InstantiationUnaffordable<Balance>>) -> Self {
+		Self { can_afford: result.is_ok(), reason: result.err() }
+	}
+}
+
+/// Contract instantiation RPC methods.
+#[rpc(server)]
+pub trait ContractInstantiationApi<BlockHash> {
+	/// Returns whether `account` can afford to instantiate a contract of the code `code_hash`
+	/// transferring `endowment` to it as of the block `at` or the best block, i.e. whether its
+	/// free balance less the existential deposit covers the endowment, the deposits and the fee
+	/// of the instantiation. The deposits of the storage written by the constructor are not
+	/// included.
+	#[method(name = "pop_canAffordInstantiate")]
+	async fn can_afford_instantiate(
+		&self,
+		account: AccountId,
+		code_hash: Hash,
+		endowment: Balance,
+		at: Option<BlockHash>,
+	) -> RpcResult<Affordability>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+/// An implementation of contract instantiation RPC methods.
+pub struct ContractInstantiation<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> ContractInstantiation<C, Block> {
+	/// Create new `ContractInstantiation` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+#[async_trait]
+impl<C, Block> ContractInstantiationApiServer<Block::Hash> for ContractInstantiation<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ContractInstantiationRuntimeApi<Block, AccountId, Balance, Hash>,
+{
+	async fn can_afford_instantiate(
+		&self,
+		account: AccountId,
+		code_hash: Hash,
+		endowment: Balance,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Affordability> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.can_afford_instantiate(at, account, code_hash, endowment)
+			.map(Into::into)
+			.map_err(|e| {
+				ErrorObject::owned(
+					Error::RuntimeError.into(),
+					"Unable to query instantiation affordability.",
+					Some(e.to_string()),
+				)
+			})
+	}
+}
@@ -0,0 +1,103 @@
+//! RPC methods for fetching the metadata of contracts stored on-chain, e.g. for wallets to decode
+//! the calls of a contract using its ABI without relying on an external registry.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::ErrorObject,
+};
+use pop_runtime_common::{
+	apis::{self, ContractMetadataApi as ContractMetadataRuntimeApi},
+	AccountId, Hash,
+};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::traits::Block as BlockT;
+
+/// The code of a contract along with the metadata stored on-chain for it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractCodeMetadata {
+	/// The hash of the code of the contract.
+	pub code_hash: Hash,
+	/// The metadata stored for the code, e.g. its ABI, if any.
+	pub metadata: Option<Bytes>,
+}
+
+impl From<apis::ContractMetadata<Hash>> for ContractCodeMetadata {
+	fn from(metadata: apis::ContractMetadata<Hash>) -> Self {
+		Self { code_hash: metadata.code_hash, metadata: metadata.metadata.map(Into::into) }
+	}
+}
+
+/// Contract metadata RPC methods.
+#[rpc(server)]
+pub trait ContractMetadataApi<BlockHash> {
+	/// Returns the code hash of the contract at `address` as of the block `at` or the best block,
+	/// along with the metadata set for its code by the owner of the code, if any. Returns `None`
+	/// if there is no contract at `address`.
+	#[method(name = "pop_contractMetadata")]
+	async fn contract_metadata(
+		&self,
+		address: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<ContractCodeMetadata>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+/// An implementation of contract metadata RPC methods.
+pub struct ContractMetadata<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> ContractMetadata<C, Block> {
+	/// Create new `ContractMetadata` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+#[async_trait]
+impl<C, Block> ContractMetadataApiServer<Block::Hash> for ContractMetadata<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ContractMetadataRuntimeApi<Block, AccountId, Hash>,
+{
+	async fn contract_metadata(
+		&self,
+		address: AccountId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<ContractCodeMetadata>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.contract_metadata(at, address)
+			.map(|metadata| metadata.map(Into::into))
+			.map_err(|e| {
+				ErrorObject::owned(
+					Error::RuntimeError.into(),
+					"Unable to query contract metadata.",
+					Some(e.to_string()),
+				)
+			})
+	}
+}
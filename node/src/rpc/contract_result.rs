@@ -0,0 +1,91 @@
+//! The results of the contract dry-runs of the `ContractsApi`, shared by the RPC methods
+//! dry-running or tracing contract calls.
+
+use codec::Decode;
+use pallet_contracts::{ContractsApi, StorageDeposit};
+use pop_runtime_common::{AccountId, Balance, Block, BlockNumber, Hash};
+use serde::Serialize;
+use sp_api::{ApiError, ApiExt, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::DispatchError;
+use sp_weights::Weight;
+
+/// The runtime api method dry-running a contract call, only provided by runtimes supporting
+/// contracts. The runtimes request the debug message of such calls via
+/// `pallet_contracts::DebugInfo::UnsafeDebug`.
+pub const CONTRACTS_API_CALL: &str = "ContractsApi_call";
+
+/// Whether the runtime of the best block of `client` provides the `ContractsApi`, which the
+/// runtimes without contracts do not.
+pub fn has_contracts_api<C>(client: &C) -> Result<bool, ApiError>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+{
+	// The id of a runtime api does not depend on its type parameters, e.g. the event type.
+	client
+		.runtime_api()
+		.has_api::<dyn ContractsApi<Block, AccountId, Balance, BlockNumber, Hash, ()>>(
+			client.info().best_hash,
+		)
+}
+
+/// A storage deposit, as `pallet_contracts::StorageDeposit` is not serializable.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Deposit {
+	/// The balance charged.
+	Charge(Balance),
+	/// The balance refunded.
+	Refund(Balance),
+}
+
+impl From<StorageDeposit<Balance>> for Deposit {
+	fn from(deposit: StorageDeposit<Balance>) -> Self {
+		match deposit {
+			StorageDeposit::Charge(amount) => Self::Charge(amount),
+			StorageDeposit::Refund(amount) => Self::Refund(amount),
+		}
+	}
+}
+
+/// The fields of `pallet_contracts::ContractResult` up to its result, which is all that is needed
+/// regardless of the event type of the runtime. Trailing data is ignored, as recommended by
+/// `pallet_contracts`.
+#[derive(Decode)]
+pub struct ContractResultPrefix<R> {
+	pub gas_consumed: Weight,
+	pub gas_required: Weight,
+	pub storage_deposit: StorageDeposit<Balance>,
+	pub debug_message: Vec<u8>,
+	pub result: Result<R, DispatchError>,
+}
+
+#[cfg(test)]
+mod tests {
+	use codec::Encode;
+	use pallet_contracts::{ContractResult, ExecReturnValue};
+
+	use super::*;
+
+	#[test]
+	fn result_is_decoded_regardless_of_events() {
+		// Returned `[6]` and reverted, as `pallet_contracts_uapi::ReturnFlags::REVERT` is set.
+		let reverted = ExecReturnValue::decode(&mut &(1u32, vec![6u8]).encode()[..]).unwrap();
+		assert!(reverted.did_revert());
+		let result = ContractResult::<Result<_, DispatchError>, Balance, u32> {
+			gas_consumed: Weight::from_parts(1, 2),
+			gas_required: Weight::from_parts(3, 4),
+			storage_deposit: StorageDeposit::Refund(5),
+			debug_message: b"Hello Pop".to_vec(),
+			result: Ok(reverted),
+			events: Some(vec![7, 8]),
+		};
+		let prefix =
+			ContractResultPrefix::<ExecReturnValue>::decode(&mut &result.encode()[..]).unwrap();
+		assert_eq!(prefix.gas_consumed, Weight::from_parts(1, 2));
+		assert_eq!(prefix.gas_required, Weight::from_parts(3, 4));
+		assert_eq!(prefix.storage_deposit, StorageDeposit::Refund(5));
+		assert_eq!(prefix.debug_message, b"Hello Pop".to_vec());
+		assert_eq!(prefix.result, result.result);
+	}
+}
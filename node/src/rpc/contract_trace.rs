@@ -0,0 +1,354 @@
+//! RPC methods for tracing the execution of contracts, only exposed when the node is started with
+//! `--enable-contracts-debug`. Tracing the frames of a call and debugging it are unsafe and
+//! unavailable on nodes denying unsafe RPC methods.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::{Codec, Decode, Encode};
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::ErrorObject,
+};
+use pallet_contracts::ExecReturnValue;
+use pop_runtime_common::{
+	apis::{CallFrame, ContractEvent, DebuggedCall, FrameDeposit},
+	Balance, Hash,
+};
+use sc_client_api::{CallExecutor, ExecutorProvider};
+use sc_rpc::DenyUnsafe;
+use serde::Serialize;
+use sp_blockchain::HeaderBackend;
+use sp_core::{traits::CallContext, Bytes};
+use sp_runtime::{traits::Block as BlockT, DispatchError};
+use sp_weights::Weight;
+
+use super::contract_result::{ContractResultPrefix, Deposit, CONTRACTS_API_CALL};
+
+/// The runtime api method tracing the frames of a contract call, only provided by runtimes
+/// supporting contracts.
+const CONTRACT_TRACING_API_TRACE_CALL: &str = "ContractTracingApi_trace_call";
+/// The runtime api method debugging a contract call, only provided by runtimes supporting
+/// contracts.
+const CONTRACT_DEBUG_API_DEBUG_CALL: &str = "ContractDebugApi_debug_call";
+
+/// An event emitted by a contract during a debugged call.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmittedEvent<AccountId> {
+	/// The contract that emitted the event.
+	pub contract: AccountId,
+	/// The data of the event, to be decoded with the metadata of the contract.
+	pub data: Bytes,
+	/// The topics of the event.
+	pub topics: Vec<Hash>,
+}
+
+impl<AccountId> From<ContractEvent<AccountId>> for EmittedEvent<AccountId> {
+	fn from(event: ContractEvent<AccountId>) -> Self {
+		Self { contract: event.contract, data: event.data.into(), topics: event.topics }
+	}
+}
+
+/// The storage deposit of a frame of a debugged call, along with the frames of the calls it made.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrameStorageDeposit<AccountId> {
+	/// The contract executed by the frame.
+	pub contract: AccountId,
+	/// The storage deposit of the frame, including the frames of the calls it made. `None` for
+	/// nested instantiations, which are not measured.
+	pub storage_deposit: Option<Deposit>,
+	/// The deposits of the frames of the calls made by the contract, in order.
+	pub calls: Vec<FrameStorageDeposit<AccountId>>,
+}
+
+impl<AccountId> From<FrameDeposit<AccountId, Balance>> for FrameStorageDeposit<AccountId> {
+	fn from(frame: FrameDeposit<AccountId, Balance>) -> Self {
+		Self {
+			contract: frame.contract,
+			storage_deposit: frame.storage_deposit.map(Into::into),
+			calls: frame.calls.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+/// A contract call debugged.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractCallDebug<AccountId> {
+	/// The frames executed by the call, along with the gas consumed by each.
+	pub trace: CallFrame<AccountId>,
+	/// The storage deposit of each frame.
+	pub storage_deposits: FrameStorageDeposit<AccountId>,
+	/// The debug messages emitted by the contracts executed.
+	pub debug_message: String,
+	/// The events emitted by the contracts executed, in order.
+	pub events: Vec<EmittedEvent<AccountId>>,
+}
+
+impl<AccountId> From<DebuggedCall<AccountId, Balance>> for ContractCallDebug<AccountId> {
+	fn from(call: DebuggedCall<AccountId, Balance>) -> Self {
+		Self {
+			trace: call.trace,
+			storage_deposits: call.deposits.into(),
+			debug_message: String::from_utf8_lossy(&call.debug_message).into_owned(),
+			events: call.events.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+/// Contract trace RPC methods.
+#[rpc(server)]
+pub trait ContractTraceApi<BlockHash, AccountId> {
+	/// Dry-run a call of the contract at `dest` by `origin` with `input_data`, as of the block
+	/// `at` or the best block, and return the debug messages emitted during its execution.
+	#[method(name = "pop_contractTrace")]
+	async fn contract_trace(
+		&self,
+		origin: AccountId,
+		dest: AccountId,
+		input_data: Bytes,
+		at: Option<BlockHash>,
+	) -> RpcResult<String>;
+
+	/// Dry-run a call of the contract at `dest` by `origin` with `input_data`, transferring
+	/// `value`, as of the block `at` or the best block, and return the tree of frames executed
+	/// by the call along with the gas consumed by each.
+	#[method(name = "pop_traceCall")]
+	async fn trace_call(
+		&self,
+		origin: AccountId,
+		dest: AccountId,
+		input_data: Bytes,
+		value: Balance,
+		at: Option<BlockHash>,
+	) -> RpcResult<CallFrame<AccountId>>;
+
+	/// Dry-run a call of the contract at `dest` by `origin` with `input_data`, transferring
+	/// `value`, as of the block `at` or the best block, and return the tree of frames executed
+	/// by the call along with the debug messages and events emitted and the storage deposit
+	/// charged or refunded by each frame.
+	#[method(name = "dev_traceContractCall")]
+	async fn trace_contract_call(
+		&self,
+		origin: AccountId,
+		dest: AccountId,
+		input_data: Bytes,
+		value: Balance,
+		at: Option<BlockHash>,
+	) -> RpcResult<ContractCallDebug<AccountId>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The result of the call was not decodable.
+	DecodeError,
+	/// The contract call could not be executed.
+	CallError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::DecodeError => 2,
+			Error::CallError => 3,
+		}
+	}
+}
+
+/// Extract the debug message from the encoded result of a `ContractsApi_call`.
+fn debug_message(mut result: &[u8]) -> Result<String, codec::Error> {
+	let prefix = ContractResultPrefix::<ExecReturnValue>::decode(&mut result)?;
+	Ok(String::from_utf8_lossy(&prefix.debug_message).into_owned())
+}
+
+/// An implementation of contract trace RPC methods.
+pub struct ContractTrace<C, Block> {
+	client: Arc<C>,
+	deny_unsafe: DenyUnsafe,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> ContractTrace<C, Block> {
+	/// Create new `ContractTrace` with the given reference to the client.
+	pub fn new(client: Arc<C>, deny_unsafe: DenyUnsafe) -> Self {
+		Self { client, deny_unsafe, _marker: Default::default() }
+	}
+
+	/// Call the runtime api `method` with the encoded `args` as of the block `at`.
+	fn call(&self, at: Block::Hash, method: &str, args: &[u8]) -> RpcResult<Vec<u8>>
+	where
+		Block: BlockT,
+		C: ExecutorProvider<Block>,
+	{
+		self.client
+			.executor()
+			.call(at, method, args, CallContext::Offchain)
+			.map_err(|e| {
+				ErrorObject::owned(
+					Error::RuntimeError.into(),
+					"Unable to dry-run contract call.",
+					Some(e.to_string()),
+				)
+			})
+	}
+}
+
+#[async_trait]
+impl<C, Block, AccountId> ContractTraceApiServer<Block::Hash, AccountId> for ContractTrace<C, Block>
+where
+	Block: BlockT,
+	C: ExecutorProvider<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	AccountId: Codec + Serialize + Send + Sync + 'static,
+{
+	async fn contract_trace(
+		&self,
+		origin: AccountId,
+		dest: AccountId,
+		input_data: Bytes,
+		at: Option<Block::Hash>,
+	) -> RpcResult<String> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		// No value is transferred and neither gas nor storage deposit are limited.
+		let args =
+			(origin, dest, Balance::default(), None::<Weight>, None::<Balance>, input_data.0)
+				.encode();
+		let result = self.call(at, CONTRACTS_API_CALL, &args)?;
+		debug_message(&result).map_err(|e| {
+			ErrorObject::owned(
+				Error::DecodeError.into(),
+				"Unable to decode contract call result.",
+				Some(e.to_string()),
+			)
+		})
+	}
+
+	async fn trace_call(
+		&self,
+		origin: AccountId,
+		dest: AccountId,
+		input_data: Bytes,
+		value: Balance,
+		at: Option<Block::Hash>,
+	) -> RpcResult<CallFrame<AccountId>> {
+		self.deny_unsafe.check_if_safe()?;
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let args = (origin, dest, value, input_data.0).encode();
+		let result = self.call(at, CONTRACT_TRACING_API_TRACE_CALL, &args)?;
+		Result::<CallFrame<AccountId>, DispatchError>::decode(&mut &result[..])
+			.map_err(|e| {
+				ErrorObject::owned(
+					Error::DecodeError.into(),
+					"Unable to decode contract call trace.",
+					Some(e.to_string()),
+				)
+			})?
+			.map_err(|e| {
+				ErrorObject::owned(
+					Error::CallError.into(),
+					"Unable to execute contract call.",
+					Some(format!("{e:?}")),
+				)
+			})
+	}
+
+	async fn trace_contract_call(
+		&self,
+		origin: AccountId,
+		dest: AccountId,
+		input_data: Bytes,
+		value: Balance,
+		at: Option<Block::Hash>,
+	) -> RpcResult<ContractCallDebug<AccountId>> {
+		self.deny_unsafe.check_if_safe()?;
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let args = (origin, dest, value, input_data.0).encode();
+		let result = self.call(at, CONTRACT_DEBUG_API_DEBUG_CALL, &args)?;
+		Result::<DebuggedCall<AccountId, Balance>, DispatchError>::decode(&mut &result[..])
+			.map_err(|e| {
+				ErrorObject::owned(
+					Error::DecodeError.into(),
+					"Unable to decode debugged contract call.",
+					Some(e.to_string()),
+				)
+			})?
+			.map(Into::into)
+			.map_err(|e| {
+				ErrorObject::owned(
+					Error::CallError.into(),
+					"Unable to execute contract call.",
+					Some(format!("{e:?}")),
+				)
+			})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use pallet_contracts::{ContractResult, StorageDeposit};
+
+	use super::*;
+
+	#[test]
+	fn debug_message_is_extracted_from_result() {
+		let result = ContractResult::<Result<ExecReturnValue, DispatchError>, Balance, u32> {
+			gas_consumed: Weight::from_parts(1, 2),
+			gas_required: Weight::from_parts(3, 4),
+			storage_deposit: StorageDeposit::Charge(5),
+			debug_message: b"Hello Pop".to_vec(),
+			result: Err(DispatchError::Other("trapped")),
+			events: Some(vec![6, 7]),
+		};
+		assert_eq!(debug_message(&result.encode()).unwrap(), "Hello Pop");
+		assert!(debug_message(&[0]).is_err());
+	}
+	#[test]
+	fn debugged_call_is_serialized() {
+		use pop_runtime_common::apis::{FrameKind, FrameOutcome};
+
+		let frame = |contract, calls| CallFrame {
+			contract,
+			kind: FrameKind::Call,
+			input: vec![],
+			output: vec![],
+			outcome: FrameOutcome::Returned,
+			gas_consumed: None,
+			calls,
+		};
+		let debugged = DebuggedCall::<u64, Balance> {
+			trace: frame(1, vec![frame(2, vec![])]),
+			deposits: FrameDeposit {
+				contract: 1,
+				storage_deposit: Some(StorageDeposit::Charge(10)),
+				calls: vec![FrameDeposit {
+					contract: 2,
+					storage_deposit: Some(StorageDeposit::Refund(5)),
+					calls: vec![],
+				}],
+			},
+			debug_message: b"Hello Pop".to_vec(),
+			events: vec![ContractEvent {
+				contract: 2,
+				data: vec![1, 2],
+				topics: vec![Hash::repeat_byte(3)],
+			}],
+		};
+		// The result decodes as returned by the runtime.
+		let encoded = Ok::<_, DispatchError>(debugged.clone()).encode();
+		let decoded =
+			Result::<DebuggedCall<u64, Balance>, DispatchError>::decode(&mut &encoded[..]);
+		assert_eq!(decoded.unwrap().unwrap(), debugged);
+
+		let json = serde_json::to_value(ContractCallDebug::from(debugged)).unwrap();
+		assert_eq!(json["trace"]["calls"][0]["contract"], 2);
+		assert_eq!(json["debugMessage"], "Hello Pop");
+		assert_eq!(json["storageDeposits"]["storageDeposit"]["charge"], 10);
+		assert_eq!(json["storageDeposits"]["calls"][0]["storageDeposit"]["refund"], 5);
+		assert_eq!(json["events"][0]["data"], "0x0102");
+		assert_eq!(json["events"][0]["topics"][0], format!("0x{}", "03".repeat(32)));
+	}
+}
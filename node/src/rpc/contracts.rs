@@ -0,0 +1,462 @@
+//! The RPC methods of `pallet_contracts`, i.e. `contracts_call`, `contracts_instantiate` and
+//! `contracts_upload_code`, for tooling still dry-running contracts with them rather than with
+//! `state_call`s of the `ContractsApi`, as `pallet-contracts-rpc` is no longer published.
+//!
+//! The requests and results are as formerly served by `pallet-contracts-rpc`, except for the
+//! events emitted during a dry-run, which are not included.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::{Decode, Encode};
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::{error::ErrorObject, ErrorObjectOwned},
+};
+use pallet_contracts::{Code, Determinism, ExecReturnValue};
+use pop_runtime_common::{AccountId, Balance, Hash};
+use sc_client_api::{CallExecutor, ExecutorProvider};
+use serde::{Deserialize, Serialize};
+use sp_blockchain::HeaderBackend;
+use sp_core::{traits::CallContext, Bytes, U256};
+use sp_runtime::{traits::Block as BlockT, DispatchError};
+use sp_weights::Weight;
+
+use super::contract_result::{ContractResultPrefix, Deposit, CONTRACTS_API_CALL};
+
+/// The runtime api methods instantiating contracts and uploading their code, besides
+/// [`CONTRACTS_API_CALL`], only provided by runtimes supporting contracts.
+const CONTRACTS_API_INSTANTIATE: &str = "ContractsApi_instantiate";
+const CONTRACTS_API_UPLOAD_CODE: &str = "ContractsApi_upload_code";
+
+/// A balance, given as a number or, if it exceeds the numbers of JSON, as a hex string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum NumberOrHex {
+	/// A number.
+	Number(u64),
+	/// A hex string, e.g. `"0x10"`.
+	Hex(U256),
+}
+
+impl TryFrom<NumberOrHex> for Balance {
+	type Error = ErrorObjectOwned;
+
+	fn try_from(value: NumberOrHex) -> Result<Self, Self::Error> {
+		match value {
+			NumberOrHex::Number(value) => Ok(value.into()),
+			NumberOrHex::Hex(value) => value.try_into().map_err(|_| {
+				ErrorObject::owned(
+					Error::InvalidBalance.into(),
+					"Balance exceeds the maximum balance.",
+					Some(format!("{value:#x} exceeds {:#x}.", Balance::MAX)),
+				)
+			}),
+		}
+	}
+}
+
+/// A request to dry-run a contract call.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CallRequest {
+	/// The account calling the contract.
+	pub origin: AccountId,
+	/// The contract called.
+	pub dest: AccountId,
+	/// The balance transferred to the contract.
+	pub value: NumberOrHex,
+	/// The gas limit of the call, or the block weight without a limit.
+	pub gas_limit: Option<Weight>,
+	/// The limit of the storage deposit charged for the call, or none without a limit.
+	pub storage_deposit_limit: Option<NumberOrHex>,
+	/// The input of the call, e.g. the selector of the message and its arguments.
+	pub input_data: Bytes,
+}
+
+/// The code of a contract to instantiate.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum InstantiateCode {
+	/// Code to upload, as a wasm module.
+	Upload(Bytes),
+	/// The hash of code already uploaded.
+	Existing(Hash),
+}
+
+impl From<InstantiateCode> for Code<Hash> {
+	fn from(code: InstantiateCode) -> Self {
+		match code {
+			InstantiateCode::Upload(code) => Self::Upload(code.0),
+			InstantiateCode::Existing(hash) => Self::Existing(hash),
+		}
+	}
+}
+
+/// A request to dry-run a contract instantiation.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct InstantiateRequest {
+	/// The account instantiating the contract.
+	pub origin: AccountId,
+	/// The balance transferred to the contract.
+	pub value: NumberOrHex,
+	/// The gas limit of the instantiation, or the block weight without a limit.
+	pub gas_limit: Option<Weight>,
+	/// The limit of the storage deposit charged for the instantiation, or none without a limit.
+	pub storage_deposit_limit: Option<NumberOrHex>,
+	/// The code of the contract.
+	pub code: InstantiateCode,
+	/// The input of the constructor, e.g. its selector and arguments.
+	pub data: Bytes,
+	/// The salt of the address of the contract.
+	pub salt: Bytes,
+}
+
+/// The determinism required of uploaded code, as `pallet_contracts::Determinism` is not
+/// deserializable.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+pub enum CodeDeterminism {
+	/// The code must be deterministic, so that it can be called on-chain.
+	#[default]
+	Enforced,
+	/// The code may be nondeterministic, so that it can only be called off-chain.
+	Relaxed,
+}
+
+impl From<CodeDeterminism> for Determinism {
+	fn from(determinism: CodeDeterminism) -> Self {
+		match determinism {
+			CodeDeterminism::Enforced => Self::Enforced,
+			CodeDeterminism::Relaxed => Self::Relaxed,
+		}
+	}
+}
+
+/// A request to dry-run uploading the code of a contract.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CodeUploadRequest {
+	/// The account uploading the code.
+	pub origin: AccountId,
+	/// The code, as a wasm module.
+	pub code: Bytes,
+	/// The limit of the storage deposit charged for the code, or none without a limit.
+	pub storage_deposit_limit: Option<NumberOrHex>,
+	/// The determinism required of the code.
+	#[serde(default)]
+	pub determinism: CodeDeterminism,
+}
+
+/// The output of a contract, as `pallet_contracts::ExecReturnValue` is not serializable.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReturnValue {
+	/// The flags returned by the contract, e.g. `1` if it reverted.
+	pub flags: u32,
+	/// The data returned by the contract.
+	pub data: Bytes,
+}
+
+impl From<ExecReturnValue> for ReturnValue {
+	fn from(value: ExecReturnValue) -> Self {
+		Self { flags: value.flags.bits(), data: value.data.into() }
+	}
+}
+
+/// The output of a contract instantiation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstantiateReturnValue {
+	/// The output of the constructor.
+	pub result: ReturnValue,
+	/// The address of the contract.
+	pub account_id: AccountId,
+}
+
+/// The uploaded code of a contract.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Decode)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeUploadReturnValue {
+	/// The hash of the code.
+	pub code_hash: Hash,
+	/// The deposit charged for the code, or zero if it was already uploaded.
+	pub deposit: Balance,
+}
+
+/// The result of a dry-run, as `pallet_contracts::ContractResult` is not serializable.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractResult<R> {
+	/// The gas consumed by the dry-run.
+	pub gas_consumed: Weight,
+	/// The gas limit required for the dry-run to succeed, which may exceed the gas consumed.
+	pub gas_required: Weight,
+	/// The balance charged for the storage added, or refunded for the storage removed.
+	pub storage_deposit: Deposit,
+	/// The debug messages of the contract, if the runtime collects them.
+	pub debug_message: Bytes,
+	/// The output of the contract, or why it could not be executed.
+	pub result: Result<R, DispatchError>,
+}
+
+impl<R> ContractResultPrefix<R> {
+	fn map<T>(self, f: impl FnOnce(R) -> T) -> ContractResult<T> {
+		ContractResult {
+			gas_consumed: self.gas_consumed,
+			gas_required: self.gas_required,
+			storage_deposit: self.storage_deposit.into(),
+			debug_message: self.debug_message.into(),
+			result: self.result.map(f),
+		}
+	}
+}
+
+/// Contracts RPC methods, as formerly provided by `pallet-contracts-rpc`.
+#[rpc(server)]
+pub trait ContractsApi<BlockHash> {
+	/// Dry-run the contract call `call_request` as of the block `at` or the best block.
+	#[method(name = "contracts_call")]
+	async fn call(
+		&self,
+		call_request: CallRequest,
+		at: Option<BlockHash>,
+	) -> RpcResult<ContractResult<ReturnValue>>;
+
+	/// Dry-run the contract instantiation `instantiate_request` as of the block `at` or the best
+	/// block.
+	#[method(name = "contracts_instantiate")]
+	async fn instantiate(
+		&self,
+		instantiate_request: InstantiateRequest,
+		at: Option<BlockHash>,
+	) -> RpcResult<ContractResult<InstantiateReturnValue>>;
+
+	/// Dry-run uploading the code of `upload_request` as of the block `at` or the best block.
+	#[method(name = "contracts_upload_code")]
+	async fn upload_code(
+		&self,
+		upload_request: CodeUploadRequest,
+		at: Option<BlockHash>,
+	) -> RpcResult<Result<CodeUploadReturnValue, DispatchError>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The result of the call was not decodable.
+	DecodeError,
+	/// A balance of the request exceeds the maximum balance.
+	InvalidBalance,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::DecodeError => 2,
+			Error::InvalidBalance => 3,
+		}
+	}
+}
+
+/// An implementation of contracts RPC methods.
+pub struct Contracts<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Contracts<C, Block> {
+	/// Create new `Contracts` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+
+	/// Call the runtime api `method` with the encoded `args` as of the block `at` or the best
+	/// block, decoding its result.
+	fn dry_run<R: Decode>(&self, method: &str, args: &[u8], at: Option<Block::Hash>) -> RpcResult<R>
+	where
+		Block: BlockT,
+		C: ExecutorProvider<Block> + HeaderBackend<Block>,
+	{
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let result =
+			self.client
+				.executor()
+				.call(at, method, args, CallContext::Offchain)
+				.map_err(|e| {
+					ErrorObject::owned(
+						Error::RuntimeError.into(),
+						"Unable to dry-run contract.",
+						Some(e.to_string()),
+					)
+				})?;
+		R::decode(&mut &result[..]).map_err(|e| {
+			ErrorObject::owned(
+				Error::DecodeError.into(),
+				"Unable to decode contract dry-run result.",
+				Some(e.to_string()),
+			)
+		})
+	}
+}
+
+/// The storage deposit limit `limit` of a request, as a balance.
+fn deposit_limit(limit: Option<NumberOrHex>) -> RpcResult<Option<Balance>> {
+	limit.map(Balance::try_from).transpose()
+}
+
+#[async_trait]
+impl<C, Block> ContractsApiServer<Block::Hash> for Contracts<C, Block>
+where
+	Block: BlockT,
+	C: ExecutorProvider<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+{
+	async fn call(
+		&self,
+		call_request: CallRequest,
+		at: Option<Block::Hash>,
+	) -> RpcResult<ContractResult<ReturnValue>> {
+		let CallRequest { origin, dest, value, gas_limit, storage_deposit_limit, input_data } =
+			call_request;
+		let args = (
+			origin,
+			dest,
+			Balance::try_from(value)?,
+			gas_limit,
+			deposit_limit(storage_deposit_limit)?,
+			input_data.0,
+		);
+		let result: ContractResultPrefix<ExecReturnValue> =
+			self.dry_run(CONTRACTS_API_CALL, &args.encode(), at)?;
+		Ok(result.map(Into::into))
+	}
+
+	async fn instantiate(
+		&self,
+		instantiate_request: InstantiateRequest,
+		at: Option<Block::Hash>,
+	) -> RpcResult<ContractResult<InstantiateReturnValue>> {
+		let InstantiateRequest {
+			origin,
+			value,
+			gas_limit,
+			storage_deposit_limit,
+			code,
+			data,
+			salt,
+		} = instantiate_request;
+		let args = (
+			origin,
+			Balance::try_from(value)?,
+			gas_limit,
+			deposit_limit(storage_deposit_limit)?,
+			Code::from(code),
+			data.0,
+			salt.0,
+		);
+		let result: ContractResultPrefix<pallet_contracts::InstantiateReturnValue<AccountId>> =
+			self.dry_run(CONTRACTS_API_INSTANTIATE, &args.encode(), at)?;
+		Ok(result.map(|instantiated| InstantiateReturnValue {
+			result: instantiated.result.into(),
+			account_id: instantiated.account_id,
+		}))
+	}
+
+	async fn upload_code(
+		&self,
+		upload_request: CodeUploadRequest,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Result<CodeUploadReturnValue, DispatchError>> {
+		let CodeUploadRequest { origin, code, storage_deposit_limit, determinism } = upload_request;
+		let args =
+			(origin, code.0, deposit_limit(storage_deposit_limit)?, Determinism::from(determinism));
+		self.dry_run(CONTRACTS_API_UPLOAD_CODE, &args.encode(), at)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use pallet_contracts::StorageDeposit;
+	use serde_json::json;
+
+	use super::*;
+
+	#[test]
+	fn balances_are_numbers_or_hex() {
+		let balance = |value| {
+			Balance::try_from(serde_json::from_value::<NumberOrHex>(value).unwrap())
+				.map_err(|e| e.code())
+		};
+		assert_eq!(balance(json!(10)), Ok(10));
+		assert_eq!(balance(json!("0xffffffffffffffffffff")), Ok(0xffff_ffff_ffff_ffff_ffff));
+		assert_eq!(
+			balance(json!("0x100000000000000000000000000000000")),
+			Err(Error::InvalidBalance.into())
+		);
+	}
+
+	#[test]
+	fn requests_are_deserialized_as_by_pallet_contracts_rpc() {
+		let alice = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+		let request: CodeUploadRequest = serde_json::from_value(
+			json!({ "origin": alice, "code": "0x0061736d", "storageDepositLimit": null }),
+		)
+		.unwrap();
+		assert_eq!(request.code.0, b"\0asm");
+		assert_eq!(request.determinism, CodeDeterminism::Enforced);
+
+		let request: InstantiateRequest = serde_json::from_value(json!({
+			"origin": alice,
+			"value": 0,
+			"gasLimit": { "ref_time": 1, "proof_size": 2 },
+			"storageDepositLimit": "0x10",
+			"code": { "existing": Hash::repeat_byte(1) },
+			"data": "0x9bae9d5e",
+			"salt": "0x",
+		}))
+		.unwrap();
+		assert_eq!(request.gas_limit, Some(Weight::from_parts(1, 2)));
+		assert_eq!(deposit_limit(request.storage_deposit_limit).unwrap(), Some(16));
+		assert_eq!(Code::from(request.code), Code::Existing(Hash::repeat_byte(1)));
+
+		let unknown_field = json!({
+			"origin": alice,
+			"dest": alice,
+			"value": 0,
+			"gasLimit": null,
+			"storageDepositLimit": null,
+			"inputData": "0x",
+			"debug": true,
+		});
+		assert!(serde_json::from_value::<CallRequest>(unknown_field).is_err());
+	}
+
+	#[test]
+	fn results_are_decoded_regardless_of_events() {
+		// Returned `[6]` and reverted, as `pallet_contracts_uapi::ReturnFlags::REVERT` is set.
+		let reverted = ExecReturnValue::decode(&mut &(1u32, vec![6u8]).encode()[..]).unwrap();
+		let result = pallet_contracts::ContractResult::<Result<_, DispatchError>, Balance, u32> {
+			gas_consumed: Weight::from_parts(1, 2),
+			gas_required: Weight::from_parts(3, 4),
+			storage_deposit: StorageDeposit::Charge(5),
+			debug_message: b"panicked".to_vec(),
+			result: Ok(reverted),
+			events: Some(vec![7, 8]),
+		};
+		let result = ContractResultPrefix::<ExecReturnValue>::decode(&mut &result.encode()[..])
+			.unwrap()
+			.map(ReturnValue::from);
+		assert_eq!(
+			serde_json::to_value(result).unwrap(),
+			json!({
+				"gasConsumed": { "ref_time": 1, "proof_size": 2 },
+				"gasRequired": { "ref_time": 3, "proof_size": 4 },
+				"storageDeposit": { "charge": 5 },
+				"debugMessage": "0x70616e69636b6564",
+				"result": { "Ok": { "flags": 1, "data": "0x06" } },
+			})
+		);
+	}
+}
@@ -0,0 +1,167 @@
+//! RPC methods for querying transaction fees.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Decode;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::ErrorObject,
+};
+use pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi;
+use pop_runtime_common::{
+	apis::{AssetFeeApi as AssetFeeRuntimeApi, FeeApi as FeeRuntimeApi},
+	AssetId, Balance,
+};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{traits::Block as BlockT, FixedU128};
+
+/// The fee of an extrinsic paid in an asset.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetFee {
+	/// The asset the fee is paid in.
+	pub asset: AssetId,
+	/// The fee in the native token, excluding any tip.
+	pub native_fee: Balance,
+	/// The fee charged in the asset for the native fee.
+	pub fee: Balance,
+}
+
+/// Fee RPC methods.
+#[rpc(server)]
+pub trait FeeApi<BlockHash> {
+	/// Returns the multiplier applied to the fees of transactions included after the block `at`
+	/// or the best block, which rises above one while blocks are congested.
+	#[method(name = "pop_feeMultiplier")]
+	async fn fee_multiplier(&self, at: Option<BlockHash>) -> RpcResult<FixedU128>;
+
+	/// Returns the assets transaction fees can be paid in as of the block `at` or the best block,
+	/// along with the amount of each charged per unit of native fees.
+	#[method(name = "pop_feeAssets")]
+	async fn fee_assets(&self, at: Option<BlockHash>) -> RpcResult<Vec<(AssetId, FixedU128)>>;
+
+	/// Returns the fee of the encoded `extrinsic` when paid in `asset`, as of the block `at` or
+	/// the best block, along with the fee in the native token it is converted from. Any tip is
+	/// excluded, as for `payment_queryInfo`.
+	#[method(name = "pop_queryFeeInAsset")]
+	async fn query_fee_in_asset(
+		&self,
+		extrinsic: Bytes,
+		asset: AssetId,
+		at: Option<BlockHash>,
+	) -> RpcResult<AssetFee>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The extrinsic could not be decoded.
+	DecodeError,
+	/// Fees cannot be paid in the asset.
+	UnsupportedAsset,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::DecodeError => 2,
+			Error::UnsupportedAsset => 3,
+		}
+	}
+}
+
+/// An implementation of fee RPC methods.
+pub struct Fee<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Fee<C, Block> {
+	/// Create new `Fee` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// The error of a failed call to the runtime.
+fn runtime_error(message: &'static str, e: impl ToString) -> ErrorObject<'static> {
+	ErrorObject::owned(Error::RuntimeError.into(), message, Some(e.to_string()))
+}
+
+#[async_trait]
+impl<C, Block> FeeApiServer<Block::Hash> for Fee<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: FeeRuntimeApi<Block>,
+	C::Api: AssetFeeRuntimeApi<Block, AssetId, Balance>,
+	C::Api: TransactionPaymentRuntimeApi<Block, Balance>,
+{
+	async fn fee_multiplier(&self, at: Option<Block::Hash>) -> RpcResult<FixedU128> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.current_multiplier(at)
+			.map_err(|e| runtime_error("Unable to query fee multiplier.", e))
+	}
+
+	async fn fee_assets(&self, at: Option<Block::Hash>) -> RpcResult<Vec<(AssetId, FixedU128)>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.fee_assets(at)
+			.map_err(|e| runtime_error("Unable to query fee assets.", e))
+	}
+
+	async fn query_fee_in_asset(
+		&self,
+		extrinsic: Bytes,
+		asset: AssetId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<AssetFee> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let len = extrinsic.len() as u32;
+		let xt = Block::Extrinsic::decode(&mut &*extrinsic).map_err(|e| {
+			ErrorObject::owned(
+				Error::DecodeError.into(),
+				"Unable to decode extrinsic.",
+				Some(e.to_string()),
+			)
+		})?;
+		let api = self.client.runtime_api();
+		let native_fee = api
+			.query_info(at, xt, len)
+			.map_err(|e| runtime_error("Unable to query fee.", e))?
+			.partial_fee;
+		let fee = api
+			.fee_in_asset(at, native_fee, asset)
+			.map_err(|e| runtime_error("Unable to convert fee.", e))?
+			.ok_or_else(|| {
+				ErrorObject::owned(
+					Error::UnsupportedAsset.into(),
+					"Fees cannot be paid in asset.",
+					Some(format!("asset {asset} has no fee rate")),
+				)
+			})?;
+		Ok(AssetFee { asset, native_fee, fee })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn asset_fee_is_serialized_in_camel_case() {
+		let fee = AssetFee { asset: 1, native_fee: 2, fee: 3 };
+		let json = serde_json::to_value(&fee).unwrap();
+		assert_eq!(json, serde_json::json!({ "asset": 1, "nativeFee": 2, "fee": 3 }));
+		assert_eq!(serde_json::from_value::<AssetFee>(json).unwrap(), fee);
+	}
+}
@@ -0,0 +1,124 @@
+//! RPC methods for estimating when blocks are finalized, e.g. for dApps to show the confirmation
+//! status of transactions without hardcoding assumptions about finality.
+//!
+//! Blocks of the parachain are finalized once the relay chain finalizes the relay chain block
+//! including them, so they are finalized at the rate they are produced, lagging the best block by
+//! the time the relay chain takes to include and finalize them. The estimates assume the lag
+//! currently observed between the best and the finalized block persists.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::ErrorObject,
+};
+use pop_runtime_common::MILLISECS_PER_BLOCK;
+use serde::Serialize;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{traits::Block as BlockT, SaturatedConversion};
+
+/// An estimate of when a block is finalized.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct FinalityEstimate {
+	/// Whether the block is already finalized.
+	pub finalized: bool,
+	/// The number of blocks to be finalized until the block is.
+	pub blocks: u64,
+	/// The number of seconds until the block is finalized.
+	pub seconds: u64,
+	/// The number of blocks the finalized block currently lags behind the best block.
+	pub lag: u64,
+}
+
+impl FinalityEstimate {
+	/// Estimate when the block `number` is finalized, given the numbers of the `best` and the
+	/// `finalized` block.
+	fn new(number: u64, best: u64, finalized: u64) -> Self {
+		let blocks = number.saturating_sub(finalized);
+		Self {
+			finalized: blocks == 0,
+			blocks,
+			seconds: blocks * MILLISECS_PER_BLOCK / 1_000,
+			lag: best.saturating_sub(finalized),
+		}
+	}
+}
+
+/// Finality RPC methods.
+#[rpc(server)]
+pub trait FinalityApi<BlockHash> {
+	/// Returns an estimate of when the block `block_hash` is finalized, from the lag currently
+	/// observed between the best and the finalized block.
+	#[method(name = "pop_finalityEstimate")]
+	async fn finality_estimate(&self, block_hash: BlockHash) -> RpcResult<FinalityEstimate>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The block is unknown.
+	UnknownBlock,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::UnknownBlock => 1,
+		}
+	}
+}
+
+/// An implementation of finality RPC methods.
+pub struct Finality<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Finality<C, Block> {
+	/// Create new `Finality` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+#[async_trait]
+impl<C, Block> FinalityApiServer<Block::Hash> for Finality<C, Block>
+where
+	Block: BlockT,
+	C: HeaderBackend<Block> + Send + Sync + 'static,
+{
+	async fn finality_estimate(&self, block_hash: Block::Hash) -> RpcResult<FinalityEstimate> {
+		let unknown = |data: Option<String>| {
+			ErrorObject::owned(Error::UnknownBlock.into(), "Unknown block.", data)
+		};
+		let number = self
+			.client
+			.number(block_hash)
+			.map_err(|e| unknown(Some(e.to_string())))?
+			.ok_or_else(|| unknown(None))?;
+		let info = self.client.info();
+		Ok(FinalityEstimate::new(
+			number.saturated_into(),
+			info.best_number.saturated_into(),
+			info.finalized_number.saturated_into(),
+		))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn best_block_is_finalized_after_the_lag() {
+		let estimate = FinalityEstimate::new(10, 10, 7);
+		assert_eq!(estimate, FinalityEstimate { finalized: false, blocks: 3, seconds: 18, lag: 3 });
+	}
+
+	#[test]
+	fn finalized_block_has_no_estimate() {
+		let estimate = FinalityEstimate::new(5, 10, 7);
+		assert!(estimate.finalized);
+		assert_eq!((estimate.blocks, estimate.seconds), (0, 0));
+	}
+}
@@ -0,0 +1,90 @@
+//! RPC methods for retrieving the genesis config of the chain specification the node was started
+//! with, e.g. to reproduce a network elsewhere or to debug genesis mismatches between nodes.
+
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::ErrorObject,
+};
+use sc_chain_spec::ChainSpec;
+use serde_json::Value;
+
+/// Genesis config RPC methods.
+#[rpc(server)]
+pub trait GenesisConfigApi {
+	/// Returns the genesis config patch of the chain specification the node was started with,
+	/// e.g. as produced by `testnet_genesis`.
+	///
+	/// The config is returned as is and may therefore contain the sudo key of the chain.
+	#[method(name = "pop_genesisConfig")]
+	async fn genesis_config(&self) -> RpcResult<Value>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The chain specification has no genesis config, e.g. as it is raw.
+	Unavailable,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::Unavailable => 1,
+		}
+	}
+}
+
+/// The genesis config patch of `chain_spec`, or the full genesis config if it has no patch.
+fn genesis_patch(chain_spec: &dyn ChainSpec) -> Result<Value, String> {
+	let json: Value = serde_json::from_str(&chain_spec.as_json(false)?)
+		.map_err(|e| format!("Invalid chain spec json: {e}"))?;
+	let runtime_genesis = &json["genesis"]["runtimeGenesis"];
+	["patch", "config"]
+		.iter()
+		.find_map(|variant| runtime_genesis.get(variant))
+		.cloned()
+		.ok_or_else(|| "The chain spec has no genesis config, as it is raw.".into())
+}
+
+/// An implementation of genesis config RPC methods.
+pub struct GenesisConfig {
+	genesis_config: Result<Value, String>,
+}
+
+impl GenesisConfig {
+	/// Create new `GenesisConfig` with the genesis config of the given chain specification.
+	pub fn new(chain_spec: &dyn ChainSpec) -> Self {
+		Self { genesis_config: genesis_patch(chain_spec) }
+	}
+}
+
+#[async_trait]
+impl GenesisConfigApiServer for GenesisConfig {
+	async fn genesis_config(&self) -> RpcResult<Value> {
+		self.genesis_config.clone().map_err(|e| {
+			ErrorObject::owned(Error::Unavailable.into(), "Genesis config unavailable.", Some(e))
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::chain_spec::{testnet_config, Code, Relay, TestnetChainSpec};
+
+	#[test]
+	fn genesis_config_contains_para_id() {
+		let chain_spec = testnet_config(Relay::PaseoLocal, Code::Compressed, None).unwrap();
+		let genesis_config = genesis_patch(&chain_spec).unwrap();
+		assert_eq!(
+			genesis_config["parachainInfo"]["parachainId"],
+			serde_json::json!(chain_spec.extensions().para_id)
+		);
+		assert!(genesis_config["sudo"]["key"].is_string());
+
+		// A raw chain spec has no genesis config to return.
+		let raw = chain_spec.as_json(true).unwrap().into_bytes();
+		let raw = TestnetChainSpec::from_json_bytes(raw).unwrap();
+		assert!(genesis_patch(&raw).is_err());
+	}
+}
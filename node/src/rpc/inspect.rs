@@ -0,0 +1,165 @@
+//! RPC methods for inspecting the space consumed by ranges of blocks, e.g. to tune the maximum
+//! block weight or to investigate incidents of full blocks after the fact.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::ErrorObject,
+};
+use pop_runtime_common::{
+	apis::{BlockUsage, BlockUsageApi as BlockUsageRuntimeApi},
+	BlockNumber,
+};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+/// The most blocks inspected by a single call.
+const MAX_BLOCK_RANGE: BlockNumber = 256;
+
+/// The space consumed by a block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InspectedBlock<BlockHash> {
+	/// The number of the block.
+	pub number: BlockNumber,
+	/// The hash of the block.
+	pub hash: BlockHash,
+	/// The weight and proof size consumed by the block, along with its contract calls.
+	#[serde(flatten)]
+	pub usage: BlockUsage,
+}
+
+/// Inspect RPC methods.
+#[rpc(server)]
+pub trait InspectApi<BlockHash> {
+	/// Returns the weight consumed per dispatch class, the proof size consumed and the number of
+	/// contract calls of each block of the best chain from `from` up to and including `to`, at
+	/// most 256 blocks. Blocks beyond the best block are omitted.
+	#[method(name = "pop_blockUsage")]
+	async fn block_usage(
+		&self,
+		from: BlockNumber,
+		to: BlockNumber,
+	) -> RpcResult<Vec<InspectedBlock<BlockHash>>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The range of blocks is empty or too large.
+	InvalidRange,
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The block could not be looked up.
+	BlockchainError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::InvalidRange => 1,
+			Error::RuntimeError => 2,
+			Error::BlockchainError => 3,
+		}
+	}
+}
+
+/// Whether `from..=to` is a non-empty range of at most `MAX_BLOCK_RANGE` blocks.
+fn valid_range(from: BlockNumber, to: BlockNumber) -> bool {
+	from <= to && to - from < MAX_BLOCK_RANGE
+}
+
+/// An implementation of inspect RPC methods.
+pub struct Inspect<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Inspect<C, Block> {
+	/// Create new `Inspect` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+#[async_trait]
+impl<C, Block> InspectApiServer<Block::Hash> for Inspect<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: BlockUsageRuntimeApi<Block>,
+{
+	async fn block_usage(
+		&self,
+		from: BlockNumber,
+		to: BlockNumber,
+	) -> RpcResult<Vec<InspectedBlock<Block::Hash>>> {
+		if !valid_range(from, to) {
+			return Err(ErrorObject::owned(
+				Error::InvalidRange.into(),
+				"Invalid block range.",
+				Some(format!("expected `from` <= `to` spanning at most {MAX_BLOCK_RANGE} blocks")),
+			));
+		}
+		let api = self.client.runtime_api();
+		let mut blocks = Vec::new();
+		for number in from..=to {
+			let hash = self.client.hash(number.into()).map_err(|e| {
+				ErrorObject::owned(
+					Error::BlockchainError.into(),
+					"Unable to look up block.",
+					Some(e.to_string()),
+				)
+			})?;
+			// Blocks beyond the best block are not known yet.
+			let Some(hash) = hash else { break };
+			let usage = api.block_usage(hash).map_err(|e| {
+				ErrorObject::owned(
+					Error::RuntimeError.into(),
+					"Unable to inspect block.",
+					Some(e.to_string()),
+				)
+			})?;
+			blocks.push(InspectedBlock { number, hash, usage });
+		}
+		Ok(blocks)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use sp_weights::Weight;
+
+	use super::*;
+
+	#[test]
+	fn ranges_are_bounded() {
+		assert!(valid_range(0, 0));
+		assert!(valid_range(10, 10 + MAX_BLOCK_RANGE - 1));
+		assert!(!valid_range(10, 10 + MAX_BLOCK_RANGE));
+		assert!(!valid_range(11, 10));
+	}
+
+	#[test]
+	fn usage_is_flattened_into_inspected_block() {
+		let block = InspectedBlock {
+			number: 7,
+			hash: 1u64,
+			usage: BlockUsage {
+				normal: Weight::from_parts(1, 2),
+				pov_size: 2,
+				contract_calls: 3,
+				..Default::default()
+			},
+		};
+		let json = serde_json::to_value(&block).unwrap();
+		assert_eq!(json["number"], 7);
+		assert_eq!(json["normal"]["ref_time"], 1);
+		assert_eq!(json["povSize"], 2);
+		assert_eq!(json["contractCalls"], 3);
+		assert_eq!(serde_json::from_value::<InspectedBlock<u64>>(json).unwrap(), block);
+	}
+}
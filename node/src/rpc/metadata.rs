@@ -0,0 +1,70 @@
+//! RPC methods for fetching historical runtime metadata.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::ErrorObject,
+};
+use sp_api::{Metadata as MetadataRuntimeApi, ProvideRuntimeApi};
+use sp_core::Bytes;
+use sp_runtime::traits::Block as BlockT;
+
+/// Metadata RPC methods.
+#[rpc(server)]
+pub trait MetadataApi<BlockHash> {
+	/// Returns the SCALE encoded metadata of the runtime as of the given block, e.g. to decode
+	/// the extrinsics of blocks produced before a runtime upgrade.
+	#[method(name = "pop_metadataAt")]
+	async fn metadata_at(&self, block_hash: BlockHash) -> RpcResult<Bytes>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+/// An implementation of metadata RPC methods.
+pub struct Metadata<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Metadata<C, Block> {
+	/// Create new `Metadata` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+#[async_trait]
+impl<C, Block> MetadataApiServer<Block::Hash> for Metadata<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + Send + Sync + 'static,
+	C::Api: MetadataRuntimeApi<Block>,
+{
+	async fn metadata_at(&self, block_hash: Block::Hash) -> RpcResult<Bytes> {
+		self.client
+			.runtime_api()
+			.metadata(block_hash)
+			.map(|metadata| metadata.to_vec().into())
+			.map_err(|e| {
+				ErrorObject::owned(
+					Error::RuntimeError.into(),
+					"Unable to query metadata.",
+					Some(e.to_string()),
+				)
+			})
+	}
+}
@@ -0,0 +1,71 @@
+//! RPC methods for previewing block production.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::ErrorObject,
+};
+use pop_runtime_common::apis::NextAuthorApi as NextAuthorRuntimeApi;
+use serde::Serialize;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+/// Next author RPC methods.
+#[rpc(server)]
+pub trait NextAuthorApi<AccountId> {
+	/// Returns the account of the collator scheduled to author the block in the next slot, as of
+	/// the best block.
+	#[method(name = "pop_nextAuthor")]
+	async fn next_author(&self) -> RpcResult<Option<AccountId>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+/// An implementation of next author RPC methods.
+pub struct NextAuthor<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> NextAuthor<C, Block> {
+	/// Create new `NextAuthor` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+#[async_trait]
+impl<C, Block, AccountId> NextAuthorApiServer<AccountId> for NextAuthor<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: NextAuthorRuntimeApi<Block, AccountId>,
+	AccountId: Codec + Serialize + Send + Sync + 'static,
+{
+	async fn next_author(&self) -> RpcResult<Option<AccountId>> {
+		let best_hash = self.client.info().best_hash;
+		self.client.runtime_api().next_author(best_hash).map_err(|e| {
+			ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Unable to query next author.",
+				Some(e.to_string()),
+			)
+		})
+	}
+}
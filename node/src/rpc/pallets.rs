@@ -0,0 +1,70 @@
+//! RPC methods for listing the pallets of the runtime along with their indices, e.g. for tooling
+//! building type registries without parsing the metadata.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::ErrorObject,
+};
+use pop_runtime_common::apis::RuntimeInfoApi;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+/// Pallets RPC methods.
+#[rpc(server)]
+pub trait PalletsApi<BlockHash> {
+	/// Returns the index and name of each pallet of the runtime as of the block `at` or the best
+	/// block, ordered by index.
+	#[method(name = "pop_pallets")]
+	async fn pallets(&self, at: Option<BlockHash>) -> RpcResult<Vec<(u8, String)>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+/// An implementation of pallets RPC methods.
+pub struct Pallets<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Pallets<C, Block> {
+	/// Create new `Pallets` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+#[async_trait]
+impl<C, Block> PalletsApiServer<Block::Hash> for Pallets<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: RuntimeInfoApi<Block>,
+{
+	async fn pallets(&self, at: Option<Block::Hash>) -> RpcResult<Vec<(u8, String)>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let pallets = self.client.runtime_api().pallets(at).map_err(|e| {
+			ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Unable to query the pallets.",
+				Some(e.to_string()),
+			)
+		})?;
+		Ok(pallets.into_iter().map(|(index, name)| (index, name.into())).collect())
+	}
+}
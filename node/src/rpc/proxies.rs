@@ -0,0 +1,100 @@
+//! RPC methods for querying the proxies of accounts, e.g. for wallets to show the accounts a user
+//! has delegated to without decoding the storage of the proxy pallet.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::ErrorObject,
+};
+use pop_runtime_common::apis::ProxyApi as ProxyRuntimeApi;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+/// An account delegated to as a proxy.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Proxy<AccountId, ProxyType> {
+	/// The account which may make calls on behalf of the delegating account.
+	pub delegate: AccountId,
+	/// The type of calls the proxy may make.
+	pub proxy_type: ProxyType,
+}
+
+/// Proxy RPC methods.
+#[rpc(server)]
+pub trait ProxiesApi<BlockHash, AccountId, ProxyType> {
+	/// Returns the proxies `account` has delegated to as of the block `at` or the best block.
+	#[method(name = "pop_proxies")]
+	async fn proxies(
+		&self,
+		account: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<Proxy<AccountId, ProxyType>>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+/// An implementation of proxy RPC methods.
+pub struct Proxies<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Proxies<C, Block> {
+	/// Create new `Proxies` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+#[async_trait]
+impl<C, Block, AccountId, ProxyType> ProxiesApiServer<Block::Hash, AccountId, ProxyType>
+	for Proxies<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ProxyRuntimeApi<Block, AccountId, ProxyType>,
+	AccountId: Codec + Serialize + Send + Sync + 'static,
+	ProxyType: Codec + Serialize + Send + Sync + 'static,
+{
+	async fn proxies(
+		&self,
+		account: AccountId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Vec<Proxy<AccountId, ProxyType>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.proxies(at, account)
+			.map(|proxies| {
+				proxies
+					.into_iter()
+					.map(|(delegate, proxy_type)| Proxy { delegate, proxy_type })
+					.collect()
+			})
+			.map_err(|e| {
+				ErrorObject::owned(
+					Error::RuntimeError.into(),
+					"Unable to query proxies.",
+					Some(e.to_string()),
+				)
+			})
+	}
+}
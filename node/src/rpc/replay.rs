@@ -0,0 +1,222 @@
+//! RPC methods for replaying extrinsics against the state of past blocks, e.g. to investigate a
+//! failure reported by a user or to check that a fix resolves it. Replaying is unsafe and
+//! unavailable on nodes denying unsafe RPC methods.
+//!
+//! The extrinsic is applied on top of the block in a new block which is never built, so that the
+//! state change is discarded along with the block.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Decode;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::{error::ErrorObject, ErrorObjectOwned},
+};
+use sc_client_api::{Backend, StateBackend};
+use sc_rpc::DenyUnsafe;
+use sp_api::{ApiExt, Core, ProvideRuntimeApi};
+use sp_block_builder::BlockBuilder;
+use sp_blockchain::HeaderBackend;
+use sp_core::{hashing::twox_128, Bytes};
+use sp_runtime::traits::{Block as BlockT, HashingFor, Header as HeaderT, One};
+
+/// Replay RPC methods.
+#[rpc(server)]
+pub trait ReplayApi<BlockHash> {
+	/// Apply the encoded `extrinsic` to the state of the block `at` or the best block and return
+	/// the events it emitted, encoded like `System::Events`. The resulting state is discarded.
+	#[method(name = "pop_replayExtrinsic")]
+	async fn replay_extrinsic(&self, extrinsic: Bytes, at: Option<BlockHash>) -> RpcResult<Bytes>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The extrinsic was not decodable.
+	DecodeError,
+	/// The block could not be found.
+	UnknownBlock,
+	/// The extrinsic could not be applied.
+	InvalidExtrinsic,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::DecodeError => 2,
+			Error::UnknownBlock => 3,
+			Error::InvalidExtrinsic => 4,
+		}
+	}
+}
+
+/// The storage key of `frame_system::Events`.
+fn events_key() -> Vec<u8> {
+	[twox_128(b"System"), twox_128(b"Events")].concat()
+}
+
+/// The encoded events within the changes to the main storage made by replaying an extrinsic. As
+/// the events are reset when initializing a block, there are none if they are left unchanged.
+fn events(changes: &[(Vec<u8>, Option<Vec<u8>>)]) -> Vec<u8> {
+	let key = events_key();
+	changes
+		.iter()
+		.find(|(changed, _)| *changed == key)
+		.and_then(|(_, value)| value.clone())
+		// The encoding of an empty `Vec`.
+		.unwrap_or_else(|| vec![0])
+}
+
+/// An error of the call to the runtime.
+fn runtime_error(e: impl ToString) -> ErrorObjectOwned {
+	ErrorObject::owned(
+		Error::RuntimeError.into(),
+		"Unable to replay extrinsic.",
+		Some(e.to_string()),
+	)
+}
+
+/// An implementation of replay RPC methods.
+pub struct Replay<C, B, Block> {
+	client: Arc<C>,
+	backend: Arc<B>,
+	deny_unsafe: DenyUnsafe,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, B, Block> Replay<C, B, Block> {
+	/// Create new `Replay` with the given reference to the client and backend.
+	pub fn new(client: Arc<C>, backend: Arc<B>, deny_unsafe: DenyUnsafe) -> Self {
+		Self { client, backend, deny_unsafe, _marker: Default::default() }
+	}
+}
+
+#[async_trait]
+impl<C, B, Block> ReplayApiServer<Block::Hash> for Replay<C, B, Block>
+where
+	Block: BlockT,
+	B: Backend<Block> + Send + Sync + 'static,
+	B::State: StateBackend<HashingFor<Block>>,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: BlockBuilder<Block>,
+{
+	async fn replay_extrinsic(
+		&self,
+		extrinsic: Bytes,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Bytes> {
+		self.deny_unsafe.check_if_safe()?;
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let extrinsic = Block::Extrinsic::decode(&mut &extrinsic[..]).map_err(|e| {
+			ErrorObject::owned(
+				Error::DecodeError.into(),
+				"Unable to decode extrinsic.",
+				Some(e.to_string()),
+			)
+		})?;
+		let parent = self.client.header(at).map_err(runtime_error)?.ok_or_else(|| {
+			ErrorObject::owned(
+				Error::UnknownBlock.into(),
+				"Block not found.",
+				Some(format!("{at:?}")),
+			)
+		})?;
+		let header = Block::Header::new(
+			*parent.number() + One::one(),
+			Default::default(),
+			Default::default(),
+			at,
+			Default::default(),
+		);
+
+		let api = self.client.runtime_api();
+		api.initialize_block(at, &header).map_err(runtime_error)?;
+		api.apply_extrinsic(at, extrinsic).map_err(runtime_error)?.map_err(|e| {
+			ErrorObject::owned(
+				Error::InvalidExtrinsic.into(),
+				"Unable to apply extrinsic.",
+				Some(format!("{e:?}")),
+			)
+		})?;
+		let state = self.backend.state_at(at).map_err(runtime_error)?;
+		let changes = api.into_storage_changes(&state, at).map_err(runtime_error)?;
+		Ok(events(&changes.main_storage_changes).into())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use codec::Encode;
+	use frame_system::EventRecord;
+	use pop_runtime_common::{Hash, UNIT};
+	use pop_runtime_testnet::{
+		AccountId, Executive, Header, Runtime, RuntimeCall, RuntimeEvent, RuntimeGenesisConfig,
+		SignedExtra, UncheckedExtrinsic,
+	};
+	use sp_core::{sr25519, Pair};
+	use sp_runtime::{generic::SignedPayload, BuildStorage, MultiAddress, StateVersion};
+
+	use super::*;
+
+	#[test]
+	fn replayed_transfer_emits_transfer_event() {
+		let signer = sr25519::Pair::from_string("//Alice", None).unwrap();
+		let (account, dest) = (AccountId::from(signer.public()), AccountId::from([2; 32]));
+		let storage = RuntimeGenesisConfig {
+			balances: pallet_balances::GenesisConfig {
+				balances: vec![(account.clone(), 10 * UNIT)],
+			},
+			..Default::default()
+		}
+		.build_storage()
+		.unwrap();
+		let mut ext = sp_io::TestExternalities::new(storage);
+		let backend = ext.as_backend();
+
+		let call = RuntimeCall::Balances(pallet_balances::Call::transfer_allow_death {
+			dest: MultiAddress::Id(dest.clone()),
+			value: UNIT,
+		});
+		// Immortal, with a nonce of zero, no tip and without a metadata hash.
+		let extra = SignedExtra::decode(&mut &[0; 5][..]).unwrap();
+		ext.execute_with(|| {
+			let payload = SignedPayload::new(call.clone(), extra.clone()).unwrap();
+			let signature = payload.using_encoded(|payload| signer.sign(payload));
+			let extrinsic = UncheckedExtrinsic::new_signed(
+				call,
+				MultiAddress::Id(account.clone()),
+				signature.into(),
+				extra,
+			);
+			// What replaying the extrinsic on top of the genesis block amounts to.
+			let genesis = frame_system::BlockHash::<Runtime>::get(0);
+			Executive::initialize_block(&Header::new(
+				1,
+				Default::default(),
+				Default::default(),
+				genesis,
+				Default::default(),
+			));
+			Executive::apply_extrinsic(extrinsic).unwrap().unwrap();
+		});
+		let changes = ext
+			.overlayed_changes()
+			.clone()
+			.drain_storage_changes(&backend, StateVersion::V1)
+			.unwrap();
+
+		let replayed = events(&changes.main_storage_changes);
+		let replayed = Vec::<EventRecord<RuntimeEvent, Hash>>::decode(&mut &replayed[..]).unwrap();
+		assert!(replayed.iter().any(|record| record.event ==
+			RuntimeEvent::Balances(pallet_balances::Event::Transfer {
+				from: account.clone(),
+				to: dest.clone(),
+				amount: UNIT,
+			})));
+		// The events of extrinsics leaving them unchanged.
+		assert_eq!(events(&[]), Vec::<u8>::new().encode());
+	}
+}
@@ -0,0 +1,139 @@
+//! RPC methods for querying many storage items in a single round trip.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::ErrorObject,
+};
+use sc_client_api::{Backend, StorageProvider};
+use sp_blockchain::HeaderBackend;
+use sp_core::storage::{StorageData, StorageKey};
+use sp_runtime::traits::Block as BlockT;
+
+/// The most keys which can be queried by a single `pop_queryStorageBatch` request.
+pub const MAX_KEYS: usize = 1000;
+
+/// Storage batch RPC methods.
+#[rpc(server)]
+pub trait StorageBatchApi<BlockHash> {
+	/// Returns the values of the storage items at `keys` as of the block `at` or the best block,
+	/// in the order of `keys`. Values of items which do not exist are `null`.
+	#[method(name = "pop_queryStorageBatch")]
+	async fn query_storage_batch(
+		&self,
+		keys: Vec<StorageKey>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<Option<StorageData>>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The storage could not be read.
+	StorageError,
+	/// More than [`MAX_KEYS`] keys were requested.
+	TooManyKeys,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::StorageError => 1,
+			Error::TooManyKeys => 2,
+		}
+	}
+}
+
+/// Look up the values of `keys` with `lookup`, failing if there are more than [`MAX_KEYS`] of
+/// them.
+fn query_batch<E: ToString>(
+	keys: &[StorageKey],
+	lookup: impl FnMut(&StorageKey) -> Result<Option<StorageData>, E>,
+) -> RpcResult<Vec<Option<StorageData>>> {
+	if keys.len() > MAX_KEYS {
+		return Err(ErrorObject::owned(
+			Error::TooManyKeys.into(),
+			"Too many storage keys requested.",
+			Some(format!("At most {MAX_KEYS} keys can be queried, got {}.", keys.len())),
+		));
+	}
+	keys.iter().map(lookup).collect::<Result<_, _>>().map_err(|e| {
+		ErrorObject::owned(
+			Error::StorageError.into(),
+			"Unable to query storage.",
+			Some(e.to_string()),
+		)
+	})
+}
+
+/// An implementation of storage batch RPC methods.
+pub struct StorageBatch<C, B, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<(B, Block)>,
+}
+
+impl<C, B, Block> StorageBatch<C, B, Block> {
+	/// Create new `StorageBatch` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+#[async_trait]
+impl<C, B, Block> StorageBatchApiServer<Block::Hash> for StorageBatch<C, B, Block>
+where
+	Block: BlockT,
+	B: Backend<Block> + Send + Sync + 'static,
+	C: StorageProvider<Block, B> + HeaderBackend<Block> + Send + Sync + 'static,
+{
+	async fn query_storage_batch(
+		&self,
+		keys: Vec<StorageKey>,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Vec<Option<StorageData>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		query_batch(&keys, |key| self.client.storage(at, key))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use sp_core::{hashing::twox_128, storage::well_known_keys};
+	use sp_runtime::BuildStorage;
+
+	use super::*;
+	use crate::chain_spec::minimal_test_spec;
+
+	#[test]
+	fn batch_matches_individual_lookups() {
+		let storage = minimal_test_spec().build_storage().unwrap().top;
+		let lookup = |key: &StorageKey| {
+			Ok::<_, String>(storage.get(&key.0).map(|value| StorageData(value.clone())))
+		};
+		let keys: Vec<_> = [
+			well_known_keys::CODE.to_vec(),
+			[twox_128(b"Balances"), twox_128(b"TotalIssuance")].concat(),
+			[twox_128(b"ParachainInfo"), twox_128(b"ParachainId")].concat(),
+			b"unknown".to_vec(),
+		]
+		.into_iter()
+		.map(StorageKey)
+		.collect();
+
+		let values = query_batch(&keys, lookup).unwrap();
+		assert_eq!(values, keys.iter().map(|key| lookup(key).unwrap()).collect::<Vec<_>>());
+		assert!(values[..3].iter().all(Option::is_some));
+		assert_eq!(values[3], None);
+	}
+
+	#[test]
+	fn batch_is_capped() {
+		let lookup = |_: &StorageKey| Ok::<_, String>(None);
+		let keys = vec![StorageKey(vec![]); MAX_KEYS];
+		assert_eq!(query_batch(&keys, lookup).unwrap().len(), MAX_KEYS);
+		let keys = vec![StorageKey(vec![]); MAX_KEYS + 1];
+		let error = query_batch(&keys, lookup).unwrap_err();
+		assert_eq!(error.code(), i32::from(Error::TooManyKeys));
+	}
+}
@@ -0,0 +1,78 @@
+//! RPC methods for estimating the storage deposits of contracts.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::ErrorObject,
+};
+use pop_runtime_common::{apis::StorageDepositApi as StorageDepositRuntimeApi, Balance};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+/// Storage deposit RPC methods.
+#[rpc(server)]
+pub trait StorageDepositApi<BlockHash> {
+	/// Returns the deposit a contract must hold for storing `bytes` bytes across `items` storage
+	/// items, as of the block `at` or the best block.
+	#[method(name = "pop_storageDeposit")]
+	async fn storage_deposit(
+		&self,
+		bytes: u32,
+		items: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Balance>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+/// An implementation of storage deposit RPC methods.
+pub struct StorageDeposit<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> StorageDeposit<C, Block> {
+	/// Create new `StorageDeposit` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+#[async_trait]
+impl<C, Block> StorageDepositApiServer<Block::Hash> for StorageDeposit<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: StorageDepositRuntimeApi<Block, Balance>,
+{
+	async fn storage_deposit(
+		&self,
+		bytes: u32,
+		items: u32,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Balance> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().deposit_for(at, bytes, items).map_err(|e| {
+			ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Unable to query storage deposit.",
+				Some(e.to_string()),
+			)
+		})
+	}
+}
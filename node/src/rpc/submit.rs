@@ -0,0 +1,240 @@
+//! RPC methods for submitting extrinsics and proving their inclusion.
+
+use std::{marker::PhantomData, sync::Arc, time::Duration};
+
+use codec::{Decode, Encode};
+use futures::StreamExt;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::ErrorObject,
+};
+use sc_client_api::BlockBackend;
+use sc_transaction_pool_api::{TransactionPool, TransactionSource, TransactionStatus};
+use serde::{Deserialize, Serialize};
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::traits::{BlakeTwo256, Block as BlockT};
+use sp_trie::{
+	generate_trie_proof, LayoutV0, MemoryDB, TrieConfiguration, TrieDBMutBuilder, TrieMut,
+};
+use tokio::sync::Semaphore;
+
+/// The longest an extrinsic submitted via `pop_submitAndProve` is waited upon to be included.
+pub const INCLUSION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The most calls of `pop_submitAndProve` waiting for their extrinsic to be included at once,
+/// beyond which further calls are rejected rather than holding on to connections and watchers of
+/// the pool for up to [`INCLUSION_TIMEOUT`] each.
+pub const MAX_PENDING: usize = 128;
+
+/// The inclusion of an extrinsic in a block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InclusionProof<BlockHash> {
+	/// The hash of the block the extrinsic was included in.
+	pub block_hash: BlockHash,
+	/// The index of the extrinsic within the block.
+	pub index: u32,
+	/// Merkle proof of the extrinsic at `index` against the extrinsics root of the block header,
+	/// where the key is the SCALE compact encoded `index` and the value the encoded extrinsic.
+	pub proof: Vec<Bytes>,
+}
+
+/// Submit RPC methods.
+#[rpc(server)]
+pub trait SubmitApi<BlockHash> {
+	/// Submit an extrinsic and wait until it is included in a block, returning the hash of that
+	/// block along with a proof of the inclusion.
+	#[method(name = "pop_submitAndProve")]
+	async fn submit_and_prove(&self, extrinsic: Bytes) -> RpcResult<InclusionProof<BlockHash>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The extrinsic was not decodable.
+	DecodeError,
+	/// The extrinsic could not be submitted to the pool.
+	PoolError,
+	/// The extrinsic was not included before it left the pool.
+	NotIncluded,
+	/// The extrinsic was not included within [`INCLUSION_TIMEOUT`].
+	Timeout,
+	/// The proof could not be generated.
+	ProofError,
+	/// [`MAX_PENDING`] extrinsics are already waited upon to be included.
+	Busy,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::DecodeError => 1,
+			Error::PoolError => 2,
+			Error::NotIncluded => 3,
+			Error::Timeout => 4,
+			Error::ProofError => 5,
+			Error::Busy => 6,
+		}
+	}
+}
+
+/// An implementation of submit RPC methods.
+pub struct Submit<C, P, Block> {
+	client: Arc<C>,
+	pool: Arc<P>,
+	pending: Semaphore,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, P, Block> Submit<C, P, Block> {
+	/// Create new `Submit` with the given reference to the client and transaction pool.
+	pub fn new(client: Arc<C>, pool: Arc<P>) -> Self {
+		Self { client, pool, pending: Semaphore::new(MAX_PENDING), _marker: Default::default() }
+	}
+}
+
+#[async_trait]
+impl<C, P, Block> SubmitApiServer<Block::Hash> for Submit<C, P, Block>
+where
+	Block: BlockT,
+	C: HeaderBackend<Block> + BlockBackend<Block> + Send + Sync + 'static,
+	P: TransactionPool<Block = Block> + 'static,
+{
+	async fn submit_and_prove(&self, extrinsic: Bytes) -> RpcResult<InclusionProof<Block::Hash>> {
+		let _pending = self.pending.try_acquire().map_err(|_| {
+			ErrorObject::owned(
+				Error::Busy.into(),
+				"Too many extrinsics waiting to be included.",
+				Some(format!("At most {MAX_PENDING} are waited upon at once, retry later.")),
+			)
+		})?;
+		let xt = Block::Extrinsic::decode(&mut &*extrinsic).map_err(|e| {
+			ErrorObject::owned(
+				Error::DecodeError.into(),
+				"Unable to decode extrinsic.",
+				Some(e.to_string()),
+			)
+		})?;
+
+		let best_hash = self.client.info().best_hash;
+		let mut status = self
+			.pool
+			.submit_and_watch(best_hash, TransactionSource::External, xt)
+			.await
+			.map_err(|e| {
+				ErrorObject::owned(
+					Error::PoolError.into(),
+					"Unable to submit extrinsic.",
+					Some(e.to_string()),
+				)
+			})?;
+
+		let inclusion = async {
+			while let Some(status) = status.next().await {
+				match status {
+					TransactionStatus::InBlock(inclusion) => return Some(inclusion),
+					status if status.is_final() => return None,
+					_ => continue,
+				}
+			}
+			None
+		};
+		let (block_hash, index) = tokio::time::timeout(INCLUSION_TIMEOUT, inclusion)
+			.await
+			.map_err(|_| {
+				ErrorObject::owned(
+					Error::Timeout.into(),
+					"Extrinsic was not included in time.",
+					None::<()>,
+				)
+			})?
+			.ok_or_else(|| {
+				ErrorObject::owned(
+					Error::NotIncluded.into(),
+					"Extrinsic was not included.",
+					None::<()>,
+				)
+			})?;
+
+		let proof_error = |e: String| {
+			ErrorObject::owned(Error::ProofError.into(), "Unable to generate proof.", Some(e))
+		};
+		let extrinsics = self
+			.client
+			.block_body(block_hash)
+			.map_err(|e| proof_error(e.to_string()))?
+			.ok_or_else(|| proof_error(format!("Body of block `{block_hash:?}` not found.")))?;
+		let proof = prove_extrinsic(&extrinsics, index).map_err(proof_error)?;
+
+		Ok(InclusionProof {
+			block_hash,
+			index: index as u32,
+			proof: proof.into_iter().map(Into::into).collect(),
+		})
+	}
+}
+
+/// Generate a proof of the extrinsic at `index`, against the extrinsics root of the block
+/// containing `extrinsics`.
+fn prove_extrinsic<E: Encode>(extrinsics: &[E], index: usize) -> Result<Vec<Vec<u8>>, String> {
+	type Layout = LayoutV0<BlakeTwo256>;
+
+	if index >= extrinsics.len() {
+		return Err(format!("Extrinsic index {index} out of bounds."));
+	}
+	let mut db = MemoryDB::<BlakeTwo256>::default();
+	let mut root = Default::default();
+	{
+		let mut trie = TrieDBMutBuilder::<Layout>::new(&mut db, &mut root).build();
+		for (i, xt) in extrinsics.iter().enumerate() {
+			trie.insert(&Layout::encode_index(i as u32), &xt.encode())
+				.map_err(|e| e.to_string())?;
+		}
+	}
+	generate_trie_proof::<Layout, _, _, _>(&db, root, &[Layout::encode_index(index as u32)])
+		.map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+	use pop_runtime_testnet::{Runtime, RuntimeCall, UncheckedExtrinsic};
+	use sp_core::storage::StateVersion;
+	use sp_runtime::{traits::Hash, MultiAddress, OpaqueExtrinsic};
+	use sp_trie::verify_trie_proof;
+
+	use super::*;
+
+	fn transfer(amount: u128) -> OpaqueExtrinsic {
+		let call = RuntimeCall::Balances(pallet_balances::Call::<Runtime>::transfer_allow_death {
+			dest: MultiAddress::Id([1; 32].into()),
+			value: amount,
+		});
+		OpaqueExtrinsic::from_bytes(&UncheckedExtrinsic::new_unsigned(call).encode()).unwrap()
+	}
+
+	#[test]
+	fn proof_verifies_against_extrinsics_root() {
+		let extrinsics: Vec<_> = (1..=5).map(transfer).collect();
+		let root = BlakeTwo256::ordered_trie_root(
+			extrinsics.iter().map(Encode::encode).collect(),
+			StateVersion::V0,
+		);
+
+		let index = 3;
+		let proof = prove_extrinsic(&extrinsics, index).unwrap();
+		let key = LayoutV0::<BlakeTwo256>::encode_index(index as u32);
+		let included = [(key.clone(), Some(extrinsics[index].encode()))];
+		assert!(
+			verify_trie_proof::<LayoutV0<BlakeTwo256>, _, _, _>(&root, &proof, &included).is_ok()
+		);
+
+		// The proof does not hold for any other extrinsic.
+		let other = [(key, Some(extrinsics[0].encode()))];
+		assert!(verify_trie_proof::<LayoutV0<BlakeTwo256>, _, _, _>(&root, &proof, &other).is_err());
+	}
+
+	#[test]
+	fn proving_missing_extrinsic_fails() {
+		assert!(prove_extrinsic(&[transfer(1)], 1).is_err());
+	}
+}
@@ -0,0 +1,94 @@
+//! RPC methods for querying the supply of the native token, so that explorers and exchanges
+//! report the same figures instead of each computing them from the storage.
+//!
+//! Computing the circulating supply reads every account of the chain, so that querying the supply
+//! is unsafe and unavailable on nodes denying unsafe RPC methods, e.g. public RPC nodes.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::ErrorObject,
+};
+use pop_runtime_common::apis::SupplyApi as SupplyRuntimeApi;
+use sc_rpc::DenyUnsafe;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+/// The supply of the native token.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenSupply<Balance> {
+	/// The total issuance of the native token.
+	pub total_issuance: Balance,
+	/// The native tokens in circulation, i.e. the total issuance less the balances which cannot
+	/// be transferred.
+	pub circulating_supply: Balance,
+}
+
+/// Supply RPC methods.
+#[rpc(server)]
+pub trait SupplyApi<BlockHash, Balance> {
+	/// Returns the supply of the native token as of the block `at` or the best block. Unsafe, as
+	/// the cost of computing it grows with the number of accounts.
+	#[method(name = "pop_supply")]
+	async fn supply(&self, at: Option<BlockHash>) -> RpcResult<TokenSupply<Balance>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+/// An implementation of supply RPC methods.
+pub struct Supply<C, Block> {
+	client: Arc<C>,
+	deny_unsafe: DenyUnsafe,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Supply<C, Block> {
+	/// Create new `Supply` with the given reference to the client.
+	pub fn new(client: Arc<C>, deny_unsafe: DenyUnsafe) -> Self {
+		Self { client, deny_unsafe, _marker: Default::default() }
+	}
+}
+
+#[async_trait]
+impl<C, Block, Balance> SupplyApiServer<Block::Hash, Balance> for Supply<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: SupplyRuntimeApi<Block, Balance>,
+	Balance: Codec + Serialize + Send + Sync + 'static,
+{
+	async fn supply(&self, at: Option<Block::Hash>) -> RpcResult<TokenSupply<Balance>> {
+		self.deny_unsafe.check_if_safe()?;
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let api = self.client.runtime_api();
+		let error = |e: sp_api::ApiError| {
+			ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Unable to query the supply.",
+				Some(e.to_string()),
+			)
+		};
+		Ok(TokenSupply {
+			total_issuance: api.total_issuance(at).map_err(error)?,
+			circulating_supply: api.circulating_supply(at).map_err(error)?,
+		})
+	}
+}
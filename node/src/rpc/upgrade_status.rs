@@ -0,0 +1,73 @@
+//! RPC methods for previewing runtime upgrades.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::ErrorObject,
+};
+use pop_runtime_common::apis::{
+	UpgradeStatus as Status, UpgradeStatusApi as UpgradeStatusRuntimeApi,
+};
+use serde::Serialize;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+/// Upgrade status RPC methods.
+#[rpc(server)]
+pub trait UpgradeStatusApi<BlockHash, Hash> {
+	/// Returns the status of a runtime upgrade which is authorized or scheduled but not applied
+	/// yet, as of the block `at` or the best block.
+	#[method(name = "pop_upgradeStatus")]
+	async fn upgrade_status(&self, at: Option<BlockHash>) -> RpcResult<Status<Hash>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+/// An implementation of upgrade status RPC methods.
+pub struct UpgradeStatus<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> UpgradeStatus<C, Block> {
+	/// Create new `UpgradeStatus` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+#[async_trait]
+impl<C, Block, Hash> UpgradeStatusApiServer<Block::Hash, Hash> for UpgradeStatus<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: UpgradeStatusRuntimeApi<Block, Hash>,
+	Hash: Codec + Serialize + Send + Sync + 'static,
+{
+	async fn upgrade_status(&self, at: Option<Block::Hash>) -> RpcResult<Status<Hash>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().upgrade_status(at).map_err(|e| {
+			ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Unable to query upgrade status.",
+				Some(e.to_string()),
+			)
+		})
+	}
+}
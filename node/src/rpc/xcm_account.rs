@@ -0,0 +1,85 @@
+//! RPC methods for converting XCM locations to accounts, e.g. for relayers and UIs to know where
+//! the funds reserved by a sibling parachain land without reimplementing the conversion.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::ErrorObject,
+};
+use pop_runtime_common::apis::XcmConversionApi;
+use serde::Serialize;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+use xcm::{latest::Location, VersionedLocation};
+
+/// XCM account RPC methods.
+#[rpc(server)]
+pub trait XcmAccountApi<BlockHash, AccountId> {
+	/// Returns the local account of `location`, given in the latest version of XCM as
+	/// `{ "parents", "interior" }`, as of the block `at` or the best block. Returns `null` if the
+	/// location has no local account.
+	#[method(name = "pop_xcmAccount")]
+	async fn xcm_account(
+		&self,
+		location: Location,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<AccountId>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+/// An implementation of XCM account RPC methods.
+pub struct XcmAccount<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> XcmAccount<C, Block> {
+	/// Create new `XcmAccount` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+#[async_trait]
+impl<C, Block, AccountId> XcmAccountApiServer<Block::Hash, AccountId> for XcmAccount<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: XcmConversionApi<Block, AccountId>,
+	AccountId: Codec + Serialize + Send + Sync + 'static,
+{
+	async fn xcm_account(
+		&self,
+		location: Location,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<AccountId>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.location_to_account(at, VersionedLocation::V4(location))
+			.map_err(|e| {
+				ErrorObject::owned(
+					Error::RuntimeError.into(),
+					"Unable to convert location to account.",
+					Some(e.to_string()),
+				)
+			})
+	}
+}
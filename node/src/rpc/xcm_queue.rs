@@ -0,0 +1,69 @@
+//! RPC methods for inspecting the XCM messages queued for delivery, e.g. for operators to notice
+//! messages to the relay chain or to siblings which are stuck.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::ErrorObject,
+};
+use pop_runtime_common::apis::{XcmQueue as Queue, XcmQueueApi as XcmQueueRuntimeApi};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+/// XCM queue RPC methods.
+#[rpc(server)]
+pub trait XcmQueueApi<BlockHash> {
+	/// Returns the XCM messages queued for delivery to the relay chain and to each sibling
+	/// parachain, as of the block `at` or the best block.
+	#[method(name = "pop_xcmQueue")]
+	async fn xcm_queue(&self, at: Option<BlockHash>) -> RpcResult<Queue>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+/// An implementation of XCM queue RPC methods.
+pub struct XcmQueue<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> XcmQueue<C, Block> {
+	/// Create new `XcmQueue` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+#[async_trait]
+impl<C, Block> XcmQueueApiServer<Block::Hash> for XcmQueue<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: XcmQueueRuntimeApi<Block>,
+{
+	async fn xcm_queue(&self, at: Option<Block::Hash>) -> RpcResult<Queue> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().xcm_queue(at).map_err(|e| {
+			ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Unable to query the XCM queue.",
+				Some(e.to_string()),
+			)
+		})
+	}
+}
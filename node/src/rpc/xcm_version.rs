@@ -0,0 +1,82 @@
+//! RPC methods for inspecting XCM version negotiation, e.g. to diagnose why messages to a
+//! destination are not delivered, as messages are not sent to destinations of unknown version.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::ErrorObject,
+};
+use pop_runtime_common::apis::XcmVersionApi as XcmVersionRuntimeApi;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+use xcm::{latest::Location, VersionedLocation};
+
+/// XCM version RPC methods.
+#[rpc(server)]
+pub trait XcmVersionApi<BlockHash> {
+	/// Returns the XCM version negotiated with the destination `location`, given in the latest
+	/// version of XCM as `{ "parents", "interior" }`, as of the block `at` or the best block.
+	/// Returns `null` if negotiation with the destination is incomplete.
+	#[method(name = "pop_xcmVersion")]
+	async fn xcm_version(
+		&self,
+		location: Location,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<u32>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+/// An implementation of XCM version RPC methods.
+pub struct XcmVersion<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> XcmVersion<C, Block> {
+	/// Create new `XcmVersion` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+#[async_trait]
+impl<C, Block> XcmVersionApiServer<Block::Hash> for XcmVersion<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: XcmVersionRuntimeApi<Block>,
+{
+	async fn xcm_version(
+		&self,
+		location: Location,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<u32>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.negotiated_version(at, VersionedLocation::V4(location))
+			.map_err(|e| {
+				ErrorObject::owned(
+					Error::RuntimeError.into(),
+					"Unable to query the negotiated XCM version.",
+					Some(e.to_string()),
+				)
+			})
+	}
+}
@@ -1,17 +1,24 @@
 //! Service and ServiceFactory implementation. Specialized wrapper over substrate service.
 
 // std
-use std::{sync::Arc, time::Duration};
+use std::{
+	collections::BTreeSet,
+	num::NonZeroU32,
+	path::{Path, PathBuf},
+	sync::Arc,
+	time::Duration,
+};
 
-use cumulus_client_cli::CollatorOptions;
+use cumulus_client_cli::{CollatorOptions, RelayChainMode};
 // Cumulus Imports
 use cumulus_client_collator::service::CollatorService;
 use cumulus_client_consensus_aura::collators::lookahead::{self as aura, Params as AuraParams};
 use cumulus_client_consensus_common::ParachainBlockImport as TParachainBlockImport;
 use cumulus_client_consensus_proposer::Proposer;
+use cumulus_client_network::AssumeSybilResistance;
 use cumulus_client_service::{
-	build_network, build_relay_chain_interface, prepare_node_config, start_relay_chain_tasks,
-	BuildNetworkParams, CollatorSybilResistance, DARecoveryProfile, StartRelayChainTasksParams,
+	build_relay_chain_interface, prepare_node_config, start_relay_chain_tasks, DARecoveryProfile,
+	StartRelayChainTasksParams,
 };
 use cumulus_primitives_core::{
 	relay_chain::{CollatorPair, ValidationCode},
@@ -21,17 +28,20 @@ use cumulus_relay_chain_interface::{OverseerHandle, RelayChainInterface};
 // Substrate Imports
 use frame_benchmarking_cli::SUBSTRATE_REFERENCE_HARDWARE;
 // Local Runtime Types
-use pop_runtime_common::{Block, Hash};
+use pop_runtime_common::{Block, BlockNumber, Hash};
 use prometheus_endpoint::Registry;
 use sc_client_api::Backend;
 use sc_consensus::ImportQueue;
 use sc_executor::{HeapAllocStrategy, WasmExecutor, DEFAULT_HEAP_ALLOC_STRATEGY};
-use sc_network::NetworkBlock;
-use sc_network_sync::SyncingService;
-use sc_service::{Configuration, PartialComponents, TFullBackend, TFullClient, TaskManager};
+use sc_network::{config::SyncMode, NetworkBackend, NetworkBlock};
+use sc_network_sync::{warp::WarpSyncParams, SyncingService};
+use sc_service::{
+	Configuration, DatabaseSource, PartialComponents, TFullBackend, TFullClient, TaskManager,
+};
 use sc_telemetry::{Telemetry, TelemetryHandle, TelemetryWorker, TelemetryWorkerHandle};
 use sc_transaction_pool_api::OffchainTransactionPoolFactory;
 use sp_api::ConstructRuntimeApi;
+use sp_core::sr25519;
 use sp_keystore::KeystorePtr;
 use sp_runtime::traits::BlakeTwo256;
 
@@ -46,13 +56,55 @@ type HostFunctions = (
 
 type ParachainExecutor = WasmExecutor<HostFunctions>;
 
-type ParachainClient<RuntimeApi> = TFullClient<Block, RuntimeApi, ParachainExecutor>;
+pub(crate) type ParachainClient<RuntimeApi> = TFullClient<Block, RuntimeApi, ParachainExecutor>;
 
 type ParachainBackend = TFullBackend<Block>;
 
 type ParachainBlockImport<RuntimeApi> =
 	TParachainBlockImport<Block, Arc<ParachainClient<RuntimeApi>>, ParachainBackend>;
 
+/// The duration of relay chain slots, in milliseconds.
+const RELAY_CHAIN_SLOT_DURATION_MS: u64 = 6_000;
+
+/// The default time the proposer is given to build a block, in milliseconds.
+pub const DEFAULT_BLOCK_PROPOSAL_DURATION_MS: u64 = 2_000;
+
+/// The most time the proposer may be given to build a block, in milliseconds: half a relay chain
+/// slot, leaving the rest of the slot to import, announce and submit the block.
+pub const MAX_BLOCK_PROPOSAL_DURATION_MS: u64 = RELAY_CHAIN_SLOT_DURATION_MS / 2;
+
+/// The options the node is started with, as given on the command line.
+pub struct NodeOptions {
+	/// The time the node is given to shut down cleanly, see `--shutdown-grace-secs`.
+	pub shutdown_grace: Duration,
+	/// The time the proposer is given to build a block.
+	pub block_proposal_duration: Duration,
+	/// The URL notified of finalized blocks, if any.
+	pub finality_webhook: Option<hyper::Uri>,
+	/// Where the prometheus metrics are written periodically, if anywhere.
+	pub metrics_snapshots: Option<crate::metrics_snapshot::Snapshots>,
+	/// The blocks kept along with their state regardless of the pruning mode.
+	pub pin_blocks: BTreeSet<BlockNumber>,
+	/// Whether the contract debugging RPCs are exposed.
+	pub contracts_debug: bool,
+	/// The account whose aura key blocks are authored with, if restricted to one.
+	pub collator_account: Option<sr25519::Public>,
+	/// Whether the session keys are rotated before the node starts authoring.
+	pub rotate_keys_on_start: bool,
+	/// The number of relay chain blocks without a new parachain block after which the block
+	/// production is reported as stalled.
+	pub max_block_lag: u32,
+	/// The number of relay chain blocks without a new parachain block after which a standby
+	/// collator takes over authoring, if it is one.
+	pub collator_standby: Option<u32>,
+	/// The number of block announcements validated at once, if bounded.
+	pub block_announce_validation_concurrency: Option<NonZeroU32>,
+	/// The profile of the host functions called by the runtime, if profiled.
+	pub wasm_profile: Option<crate::wasm_profile::HostFunctionProfile>,
+	/// The interval development nodes author blocks at, if not as transactions are imported.
+	pub dev_block_time: Option<Duration>,
+}
+
 /// Assembly of PartialComponents (enough to run chain ops subcommands)
 type Service<RuntimeApi> = PartialComponents<
 	ParachainClient<RuntimeApi>,
@@ -70,6 +122,27 @@ type Service<RuntimeApi> = PartialComponents<
 pub fn new_partial<RuntimeApi>(
 	config: &Configuration,
 ) -> Result<Service<RuntimeApi>, sc_service::Error>
+where
+	RuntimeApi: ConstructRuntimeApi<Block, ParachainClient<RuntimeApi>> + Send + Sync + 'static,
+	RuntimeApi::RuntimeApi: RuntimeApiExt<RuntimeApi>,
+	sc_client_api::StateBackendFor<TFullBackend<Block>, Block>:
+		sc_client_api::StateBackend<BlakeTwo256>,
+{
+	new_partial_with_import_queue(config, build_import_queue)
+}
+
+/// Starts a `ServiceBuilder` for a full service, importing blocks with the import queue built by
+/// `build_import_queue`, e.g. one which does not verify aura seals.
+fn new_partial_with_import_queue<RuntimeApi>(
+	config: &Configuration,
+	build_import_queue: impl FnOnce(
+		Arc<ParachainClient<RuntimeApi>>,
+		ParachainBlockImport<RuntimeApi>,
+		&Configuration,
+		Option<TelemetryHandle>,
+		&TaskManager,
+	) -> sc_consensus::DefaultImportQueue<Block>,
+) -> Result<Service<RuntimeApi>, sc_service::Error>
 where
 	RuntimeApi: ConstructRuntimeApi<Block, ParachainClient<RuntimeApi>> + Send + Sync + 'static,
 	RuntimeApi::RuntimeApi: RuntimeApiExt<RuntimeApi>,
@@ -91,6 +164,15 @@ where
 		.default_heap_pages
 		.map_or(DEFAULT_HEAP_ALLOC_STRATEGY, |h| HeapAllocStrategy::Static { extra_pages: h as _ });
 
+	// `--wasm-execution` selects how the runtime is executed, `compiled` by default. Substrate
+	// dropped its wasm interpreter, so `interpreted-i-know-what-i-do` is only accepted for
+	// backwards compatibility and falls back to `compiled` with a warning. Compiled execution is
+	// deterministic across machines; execution discrepancies are rather to be bisected by pinning
+	// `--wasmtime-instantiation-strategy`.
+	// `--max-runtime-instances` (max 32) bounds how many runtime calls, such as contract dry-runs
+	// served over RPC, can execute concurrently. Every instance holds its own linear memory, so
+	// raising it on RPC nodes trades memory (up to the runtime's heap size per instance) for
+	// throughput.
 	let executor = ParachainExecutor::builder()
 		.with_execution_method(config.wasm_method)
 		.with_onchain_heap_alloc_strategy(heap_pages)
@@ -99,6 +181,9 @@ where
 		.with_runtime_cache_size(config.runtime_cache_size)
 		.build();
 
+	// The backend caches trie nodes and values in a shared cache of up to `--trie-cache-size` bytes
+	// (64 MiB by default, `0` disables it). A larger cache avoids database reads for hot state,
+	// such as contract code and storage, at the cost of resident memory on read-heavy RPC nodes.
 	let (client, backend, keystore_container, task_manager) =
 		sc_service::new_full_parts_record_import::<Block, RuntimeApi, _>(
 			config,
@@ -115,6 +200,8 @@ where
 		telemetry
 	});
 
+	// Sized by `--pool-limit` and `--pool-kbytes`, for the ready transactions and a tenth of it for
+	// future ones.
 	let transaction_pool = sc_transaction_pool::BasicPool::new_full(
 		config.transaction_pool.clone(),
 		config.role.is_authority().into(),
@@ -145,43 +232,123 @@ where
 	})
 }
 
+/// Warn if the selected database backend differs from the one of an existing database of the
+/// chain, which is then ignored and requires the chain to be synced again.
+fn warn_on_database_switch(database: &DatabaseSource) {
+	let (path, selected, other) = match database {
+		DatabaseSource::RocksDb { path, .. } => (path, "RocksDb", "paritydb"),
+		DatabaseSource::ParityDb { path } => (path, "ParityDb", "db"),
+		_ => return,
+	};
+	if path.exists() {
+		return;
+	}
+	if let Some(existing) = sibling_database_path(path, other).filter(|p| p.exists()) {
+		log::warn!(
+			"Ignoring the existing database at {}, as the {selected} backend is selected. The \
+			 chain will be synced again, use `--database auto` to open the existing database \
+			 instead.",
+			existing.display()
+		);
+	}
+}
+
+/// The path of the database in the directory `dir` next to the one at `path`, e.g.
+/// `<chain>/paritydb/full` for `<chain>/db/full`.
+fn sibling_database_path(path: &Path, dir: &str) -> Option<PathBuf> {
+	let role = path.file_name()?;
+	Some(path.parent()?.parent()?.join(dir).join(role))
+}
+
+/// Generate new session keys into `keystore`, as `author_rotateKeys` does, logging the public keys
+/// to register via `session.setKeys`.
+fn rotate_session_keys<RuntimeApi>(
+	client: &ParachainClient<RuntimeApi>,
+	keystore: KeystorePtr,
+) -> Result<(), sc_service::Error>
+where
+	RuntimeApi: ConstructRuntimeApi<Block, ParachainClient<RuntimeApi>> + Send + Sync + 'static,
+	RuntimeApi::RuntimeApi: RuntimeApiExt<RuntimeApi>,
+{
+	use sp_api::{ApiExt, ProvideRuntimeApi};
+	use sp_blockchain::HeaderBackend;
+	use sp_session::SessionKeys;
+
+	let mut api = client.runtime_api();
+	api.register_extension(sp_keystore::KeystoreExt::from(keystore));
+	let keys = api
+		.generate_session_keys(client.info().best_hash, None)
+		.map_err(|e| sc_service::Error::Other(format!("Failed to rotate the session keys: {e}")))?;
+	log::info!(
+		"🔑 Rotated the session keys, to be registered via `session.setKeys`: 0x{}",
+		sp_core::hexdisplay::HexDisplay::from(&keys)
+	);
+	Ok(())
+}
+
 /// Start a node with the given parachain `Configuration` and relay chain `Configuration`.
 ///
 /// This is the actual implementation that is abstract over the executor and the runtime api.
 #[sc_tracing::logging::prefix_logs_with("Parachain")]
-async fn start_node_impl<RuntimeApi, SC>(
+async fn start_node_impl<RuntimeApi>(
 	parachain_config: Configuration,
 	polkadot_config: Configuration,
 	collator_options: CollatorOptions,
 	para_id: ParaId,
-	start_consensus: SC,
 	hwbench: Option<sc_sysinfo::HwBench>,
+	options: NodeOptions,
 ) -> sc_service::error::Result<(TaskManager, Arc<ParachainClient<RuntimeApi>>)>
 where
 	RuntimeApi: ConstructRuntimeApi<Block, ParachainClient<RuntimeApi>> + Send + Sync + 'static,
 	RuntimeApi::RuntimeApi: RuntimeApiExt<RuntimeApi>,
-	SC: FnOnce(
-		Arc<ParachainClient<RuntimeApi>>,
-		Arc<ParachainBackend>,
-		ParachainBlockImport<RuntimeApi>,
-		Option<&Registry>,
-		Option<TelemetryHandle>,
-		&TaskManager,
-		Arc<dyn RelayChainInterface>,
-		Arc<sc_transaction_pool::FullPool<Block, ParachainClient<RuntimeApi>>>,
-		Arc<SyncingService<Block>>,
-		KeystorePtr,
-		Duration,
-		ParaId,
-		CollatorPair,
-		OverseerHandle,
-		Arc<dyn Fn(Hash, Option<Vec<u8>>) + Send + Sync>,
-	) -> Result<(), sc_service::Error>,
+	RuntimeApi: crate::contract_gas::ContractGas,
+	RuntimeApi: crate::rpc::ContractRpcs<ParachainClient<RuntimeApi>>,
 {
+	let NodeOptions {
+		shutdown_grace,
+		block_proposal_duration,
+		finality_webhook,
+		metrics_snapshots,
+		pin_blocks,
+		contracts_debug,
+		collator_account,
+		rotate_keys_on_start,
+		max_block_lag,
+		collator_standby,
+		block_announce_validation_concurrency,
+		wasm_profile,
+		// Only development nodes author blocks at a fixed interval.
+		dev_block_time: _,
+	} = options;
 	let parachain_config = prepare_node_config(parachain_config);
+	warn_on_database_switch(&parachain_config.database);
+	// With `--sync fast` or `--sync warp`, the state of a recent block is imported instead of
+	// executing every block since genesis, which the collator waits for. The target block of a
+	// warp sync is the head included by the relay chain, see `state_sync::warp_sync_target`.
+	let wait_for_state_sync = crate::state_sync::syncs_state(&parachain_config.network.sync_mode);
 
 	let params = new_partial::<RuntimeApi>(&parachain_config)?;
 	let (block_import, mut telemetry, telemetry_worker_handle) = params.other;
+	// With `--collator-account`, blocks are only authored with the given aura key of the keystore,
+	// and with `--collator-standby` only once the standby took over authoring.
+	let standby = collator_standby
+		.map(|takeover_after| (takeover_after, crate::standby::Authoring::default()));
+	let authoring_keystore: KeystorePtr = if collator_account.is_some() || standby.is_some() {
+		let keystore = crate::collator_keystore::CollatorKeystore::new(
+			params.keystore_container.keystore(),
+			collator_account,
+		)
+		.map_err(sc_service::Error::Other)?;
+		match &standby {
+			Some((_, authoring)) => Arc::new(keystore.standby(authoring.clone())),
+			None => Arc::new(keystore),
+		}
+	} else {
+		params.keystore_container.keystore()
+	};
+
+	// The peer limits of `--in-peers` and `--out-peers`, which are validated against
+	// `--max-peers`, are applied via the default peer set of the network configuration.
 	let net_config = sc_network::config::FullNetworkConfiguration::<
 		_,
 		_,
@@ -191,7 +358,22 @@ where
 	let client = params.client.clone();
 	let backend = params.backend.clone();
 	let mut task_manager = params.task_manager;
+	// With `--rotate-keys-on-start`, the keys are generated before the node starts authoring.
+	if rotate_keys_on_start {
+		rotate_session_keys(&*client, params.keystore_container.keystore())?;
+	}
 
+	// With `--relay-chain-rpc-urls`, the relay chain is accessed via the given RPC endpoints, which
+	// are tried in order, instead of an embedded relay chain full node. With
+	// `--relay-chain-light-client`, an embedded light client is used instead, which `cumulus`
+	// only supports for full nodes.
+	if let RelayChainMode::LightClient = collator_options.relay_chain_mode {
+		log::warn!(
+			"The relay chain is accessed via a light client. Its view of the relay chain is only \
+			 as trustworthy as the peers it connects to, which could withhold or delay relay \
+			 chain blocks and thereby the finality of this node."
+		);
+	}
 	let (relay_chain_interface, collator_key) = build_relay_chain_interface(
 		polkadot_config,
 		&parachain_config,
@@ -201,26 +383,62 @@ where
 		hwbench.clone(),
 	)
 	.await
-	.map_err(|e| sc_service::Error::Application(Box::new(e) as Box<_>))?;
+	.map_err(|e| match &collator_options.relay_chain_mode {
+		RelayChainMode::ExternalRpc(urls) => sc_service::Error::Other(format!(
+			"Unable to connect to any of the relay chain RPC urls [{}]: {e}",
+			urls.iter().map(|url| url.as_str()).collect::<Vec<_>>().join(", ")
+		)),
+		RelayChainMode::LightClient => sc_service::Error::Other(format!(
+			"Unable to start the relay chain light client, which requires a relay chain \
+			 specification with bootnodes: {e}"
+		)),
+		_ => sc_service::Error::Application(Box::new(e) as Box<_>),
+	})?;
 
+	// Only authorities collate, which excludes the full nodes run with `--rpc-only`: the consensus
+	// is not even set up for them.
 	let validator = parachain_config.role.is_authority();
 	let prometheus_registry = parachain_config.prometheus_registry().cloned();
 	let transaction_pool = params.transaction_pool.clone();
 	let import_queue_service = params.import_queue.service();
 
+	// Being sybil resistant, the collators are trusted to author blocks, so announcements are
+	// validated without waiting on the relay chain. With `--block-announce-validation-concurrency`,
+	// at most the given number of them are validated at once, on top of the bounds of
+	// `sc-network-sync` (256 validations, 4 per peer).
+	let block_announce_validator =
+		Box::new(AssumeSybilResistance::allow_seconded_messages()) as Box<_>;
+	let block_announce_validator = match block_announce_validation_concurrency {
+		Some(concurrency) => Box::new(crate::block_announce::BoundedValidator::new(
+			block_announce_validator,
+			concurrency,
+		)) as Box<_>,
+		None => block_announce_validator,
+	};
+	let warp_sync_params =
+		matches!(parachain_config.network.sync_mode, SyncMode::Warp).then(|| {
+			WarpSyncParams::WaitForTarget(crate::state_sync::warp_sync_target(
+				relay_chain_interface.clone(),
+				para_id,
+				&task_manager.spawn_handle(),
+			))
+		});
+	let metrics = sc_network::NetworkWorker::<Block, Hash>::register_notification_metrics(
+		parachain_config.prometheus_registry(),
+	);
 	let (network, system_rpc_tx, tx_handler_controller, start_network, sync_service) =
-		build_network(BuildNetworkParams {
-			parachain_config: &parachain_config,
+		sc_service::build_network(sc_service::BuildNetworkParams {
+			config: &parachain_config,
 			net_config,
 			client: client.clone(),
 			transaction_pool: transaction_pool.clone(),
-			para_id,
 			spawn_handle: task_manager.spawn_handle(),
-			relay_chain_interface: relay_chain_interface.clone(),
 			import_queue: params.import_queue,
-			sybil_resistance_level: CollatorSybilResistance::Resistant, // because of Aura
-		})
-		.await?;
+			block_announce_validator_builder: Some(Box::new(move |_| block_announce_validator)),
+			warp_sync_params,
+			block_relay: None,
+			metrics,
+		})?;
 
 	if parachain_config.offchain_worker.enabled {
 		use futures::FutureExt;
@@ -237,7 +455,8 @@ where
 				)),
 				network_provider: Arc::new(network.clone()),
 				is_validator: parachain_config.role.is_authority(),
-				enable_http_requests: false,
+				// Allows offchain workers to fetch data from outside the chain, e.g. oracles.
+				enable_http_requests: true,
 				custom_extensions: move |_| vec![],
 			})
 			.run(client.clone(), task_manager.spawn_handle())
@@ -249,6 +468,7 @@ where
 		let client = client.clone();
 		let backend = backend.clone();
 		let transaction_pool = transaction_pool.clone();
+		let chain_spec = parachain_config.chain_spec.cloned_box();
 
 		Box::new(move |deny_unsafe, _| {
 			let deps = crate::rpc::FullDeps {
@@ -256,9 +476,11 @@ where
 				pool: transaction_pool.clone(),
 				backend: backend.clone(),
 				deny_unsafe,
+				contracts_debug,
+				chain_spec: chain_spec.cloned_box(),
 			};
 
-			crate::rpc::create_full(deps).map_err(Into::into)
+			crate::rpc::create_full::<RuntimeApi, _, _, _>(deps).map_err(Into::into)
 		})
 	};
 
@@ -303,7 +525,7 @@ where
 		Arc::new(move |hash, data| sync_service.announce_block(hash, data))
 	};
 
-	let relay_chain_slot_duration = Duration::from_secs(6);
+	let relay_chain_slot_duration = Duration::from_millis(RELAY_CHAIN_SLOT_DURATION_MS);
 
 	let overseer_handle = relay_chain_interface
 		.overseer_handle()
@@ -326,28 +548,85 @@ where
 		sync_service: sync_service.clone(),
 	})?;
 
-	if validator {
-		start_consensus(
+	if let Some(registry) = prometheus_registry.as_ref() {
+		crate::relay_parent_lag::spawn(
+			&task_manager,
 			client.clone(),
-			backend.clone(),
-			block_import,
-			prometheus_registry.as_ref(),
-			telemetry.as_ref().map(|t| t.handle()),
+			relay_chain_interface.clone(),
+			registry,
+		)?;
+		crate::contract_gas::spawn::<RuntimeApi, _, _>(&task_manager, client.clone(), registry)?;
+	}
+	crate::inbound_xcm::spawn(&task_manager, client.clone(), prometheus_registry.as_ref())?;
+	crate::block_production::spawn(
+		&task_manager,
+		client.clone(),
+		relay_chain_interface.clone(),
+		para_id,
+		max_block_lag,
+		prometheus_registry.as_ref(),
+	)?;
+	if let Some((takeover_after, authoring)) = standby.filter(|_| validator) {
+		crate::standby::spawn(
 			&task_manager,
+			client.clone(),
+			relay_chain_interface.clone(),
+			para_id,
+			takeover_after,
+			authoring,
+		);
+	}
+
+	if validator {
+		start_consensus(ConsensusParams {
+			client: client.clone(),
+			backend: backend.clone(),
+			block_import,
+			prometheus_registry: prometheus_registry.as_ref(),
+			telemetry: telemetry.as_ref().map(|t| t.handle()),
+			task_manager: &task_manager,
 			relay_chain_interface,
 			transaction_pool,
-			sync_service,
-			params.keystore_container.keystore(),
+			sync_oracle: sync_service,
+			keystore: authoring_keystore,
 			relay_chain_slot_duration,
+			authoring_duration: block_proposal_duration,
 			para_id,
-			collator_key.expect("Command line arguments do not allow this. qed"),
+			collator_key: collator_key.expect("Command line arguments do not allow this. qed"),
 			overseer_handle,
 			announce_block,
-		)?;
+			wait_for_state_sync,
+		})?;
 	}
 
 	start_network.start_network();
 
+	if let Some(url) = finality_webhook {
+		crate::finality_webhook::spawn(&task_manager, client.clone(), url);
+	}
+
+	if !pin_blocks.is_empty() {
+		crate::pinned_blocks::spawn(&task_manager, client.clone(), backend.clone(), pin_blocks);
+	}
+
+	match (metrics_snapshots, prometheus_registry) {
+		(Some(snapshots), Some(registry)) =>
+			crate::metrics_snapshot::spawn(&task_manager, registry, snapshots),
+		(Some(_), None) =>
+			log::warn!("Prometheus is disabled, `--metrics-snapshot-file` has no effect"),
+		_ => {},
+	}
+
+	if let Some(profile) = wasm_profile {
+		crate::wasm_profile::spawn(&task_manager, profile);
+	}
+
+	task_manager.spawn_handle().spawn(
+		"shutdown-watchdog",
+		None,
+		crate::shutdown::watchdog(shutdown_grace),
+	);
+
 	Ok((task_manager, client))
 }
 
@@ -383,28 +662,53 @@ where
 	)
 }
 
-#[allow(clippy::too_many_arguments)]
-fn start_consensus<RuntimeApi>(
+/// The components the collation of blocks is started with by [`start_consensus`].
+struct ConsensusParams<'a, RuntimeApi> {
 	client: Arc<ParachainClient<RuntimeApi>>,
 	backend: Arc<ParachainBackend>,
 	block_import: ParachainBlockImport<RuntimeApi>,
-	prometheus_registry: Option<&Registry>,
+	prometheus_registry: Option<&'a Registry>,
 	telemetry: Option<TelemetryHandle>,
-	task_manager: &TaskManager,
+	task_manager: &'a TaskManager,
 	relay_chain_interface: Arc<dyn RelayChainInterface>,
 	transaction_pool: Arc<sc_transaction_pool::FullPool<Block, ParachainClient<RuntimeApi>>>,
 	sync_oracle: Arc<SyncingService<Block>>,
 	keystore: KeystorePtr,
 	relay_chain_slot_duration: Duration,
+	/// The time the proposer is given to build a block.
+	authoring_duration: Duration,
 	para_id: ParaId,
 	collator_key: CollatorPair,
 	overseer_handle: OverseerHandle,
 	announce_block: Arc<dyn Fn(Hash, Option<Vec<u8>>) + Send + Sync>,
-) -> Result<(), sc_service::Error>
+	/// Whether to wait for the state of the parachain to be synced before collating.
+	wait_for_state_sync: bool,
+}
+
+fn start_consensus<RuntimeApi>(params: ConsensusParams<RuntimeApi>) -> Result<(), sc_service::Error>
 where
 	RuntimeApi: ConstructRuntimeApi<Block, ParachainClient<RuntimeApi>> + Send + Sync + 'static,
 	RuntimeApi::RuntimeApi: RuntimeApiExt<RuntimeApi>,
 {
+	let ConsensusParams {
+		client,
+		backend,
+		block_import,
+		prometheus_registry,
+		telemetry,
+		task_manager,
+		relay_chain_interface,
+		transaction_pool,
+		sync_oracle,
+		keystore,
+		relay_chain_slot_duration,
+		authoring_duration,
+		para_id,
+		collator_key,
+		overseer_handle,
+		announce_block,
+		wait_for_state_sync,
+	} = params;
 	let proposer_factory = sc_basic_authorship::ProposerFactory::with_proof_recording(
 		task_manager.spawn_handle(),
 		client.clone(),
@@ -446,6 +750,7 @@ where
 		}
 	};
 
+	let state_sync = (backend.clone(), relay_chain_interface.clone());
 	let params = AuraParams {
 		create_inherent_data_providers,
 		block_import,
@@ -463,7 +768,7 @@ where
 		relay_chain_slot_duration,
 		proposer,
 		collator_service,
-		authoring_duration: Duration::from_millis(2000),
+		authoring_duration,
 		reinitialize: false,
 	};
 
@@ -471,6 +776,13 @@ where
 		aura::run::<Block, sp_consensus_aura::sr25519::AuthorityPair, _, _, _, _, _, _, _, _, _>(
 			params,
 		);
+	let fut = async move {
+		if wait_for_state_sync {
+			let (backend, relay_chain_interface) = state_sync;
+			crate::state_sync::wait(&*backend, &*relay_chain_interface, para_id).await;
+		}
+		fut.await
+	};
 	task_manager.spawn_essential_handle().spawn("aura", None, fut);
 
 	Ok(())
@@ -483,25 +795,260 @@ pub async fn start_parachain_node<RuntimeApi>(
 	collator_options: CollatorOptions,
 	para_id: ParaId,
 	hwbench: Option<sc_sysinfo::HwBench>,
+	options: NodeOptions,
 ) -> sc_service::error::Result<(TaskManager, Arc<ParachainClient<RuntimeApi>>)>
 where
 	RuntimeApi: ConstructRuntimeApi<Block, ParachainClient<RuntimeApi>> + Send + Sync + 'static,
 	RuntimeApi::RuntimeApi: RuntimeApiExt<RuntimeApi>,
+	RuntimeApi: crate::contract_gas::ContractGas,
+	RuntimeApi: crate::rpc::ContractRpcs<ParachainClient<RuntimeApi>>,
 {
-	start_node_impl::<RuntimeApi, _>(
+	start_node_impl::<RuntimeApi>(
 		parachain_config,
 		polkadot_config,
 		collator_options,
 		para_id,
-		start_consensus::<RuntimeApi>,
 		hwbench,
+		options,
 	)
 	.await
 }
 
+/// Start a development node which authors blocks with manual seal instead of collating them for
+/// a relay chain, so that no relay chain is required, e.g. to iterate on contracts locally.
+///
+/// A block is authored every `--dev-block-time` if given, or as soon as a transaction is imported
+/// into the pool otherwise, as well as when requested via the `engine_createBlock` RPC, and
+/// finalized right away. The inherent of the relay chain is mocked, with a relay chain block per
+/// block and a relay chain slot matching the slot of the block, as the consensus hook of the
+/// runtimes requires. Of the other `options`, only the grace period of the shutdown and the
+/// debugging of contracts apply.
+pub async fn start_dev_node<RuntimeApi>(
+	config: Configuration,
+	para_id: ParaId,
+	options: NodeOptions,
+) -> sc_service::error::Result<TaskManager>
+where
+	RuntimeApi: ConstructRuntimeApi<Block, ParachainClient<RuntimeApi>> + Send + Sync + 'static,
+	RuntimeApi::RuntimeApi: RuntimeApiExt<RuntimeApi>,
+	RuntimeApi: crate::rpc::ContractRpcs<ParachainClient<RuntimeApi>>,
+{
+	let NodeOptions { shutdown_grace, contracts_debug, dev_block_time, .. } = options;
+	use codec::Encode;
+	use cumulus_client_parachain_inherent::{
+		MockValidationDataInherentDataProvider, MockXcmConfig,
+	};
+	use cumulus_primitives_core::relay_chain::{well_known_keys, HeadData};
+	use futures::StreamExt;
+	use sc_consensus_manual_seal::{
+		consensus::aura::AuraConsensusDataProvider,
+		rpc::{ManualSeal, ManualSealApiServer},
+		EngineCommand, ManualSealParams,
+	};
+	use sc_transaction_pool_api::TransactionPool;
+	use sp_api::ProvideRuntimeApi;
+	use sp_blockchain::HeaderBackend;
+	use sp_consensus_aura::{AuraApi, Slot};
+
+	// Blocks are sealed without aura, so their seals are not verified.
+	let params = new_partial_with_import_queue::<RuntimeApi>(
+		&config,
+		|client, _, config, _, task_manager| {
+			sc_consensus_manual_seal::import_queue(
+				Box::new(client),
+				&task_manager.spawn_essential_handle(),
+				config.prometheus_registry(),
+			)
+		},
+	)?;
+	let (_, mut telemetry, _) = params.other;
+	let client = params.client.clone();
+	let backend = params.backend.clone();
+	let mut task_manager = params.task_manager;
+	let transaction_pool = params.transaction_pool.clone();
+
+	let net_config = sc_network::config::FullNetworkConfiguration::<
+		_,
+		_,
+		sc_network::NetworkWorker<Block, Hash>,
+	>::new(&config.network);
+	let metrics = sc_network::NetworkWorker::<Block, Hash>::register_notification_metrics(
+		config.prometheus_registry(),
+	);
+	let (network, system_rpc_tx, tx_handler_controller, start_network, sync_service) =
+		sc_service::build_network(sc_service::BuildNetworkParams {
+			config: &config,
+			net_config,
+			client: client.clone(),
+			transaction_pool: transaction_pool.clone(),
+			spawn_handle: task_manager.spawn_handle(),
+			import_queue: params.import_queue,
+			block_announce_validator_builder: None,
+			warp_sync_params: None,
+			block_relay: None,
+			metrics,
+		})?;
+
+	// Blocks are requested via RPC, or authored every `dev_block_time` if given, as transactions
+	// are imported otherwise.
+	let (seal_requests, requested_seals) = futures::channel::mpsc::channel(1024);
+	let seal = |create_empty| EngineCommand::SealNewBlock {
+		create_empty,
+		finalize: true,
+		parent_hash: None,
+		sender: None,
+	};
+	let authored_seals = match dev_block_time {
+		Some(block_time) => {
+			let mut interval = tokio::time::interval(block_time);
+			// Ticks missed while a block is authored are delayed rather than authored in a burst.
+			interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+			futures::stream::unfold(interval, move |mut interval| async move {
+				interval.tick().await;
+				Some((seal(true), interval))
+			})
+			.boxed()
+		},
+		None => transaction_pool.import_notification_stream().map(move |_| seal(false)).boxed(),
+	};
+
+	let rpc_builder = {
+		let client = client.clone();
+		let backend = backend.clone();
+		let transaction_pool = transaction_pool.clone();
+		let chain_spec = config.chain_spec.cloned_box();
+
+		Box::new(move |deny_unsafe, _| {
+			let deps = crate::rpc::FullDeps {
+				client: client.clone(),
+				pool: transaction_pool.clone(),
+				backend: backend.clone(),
+				deny_unsafe,
+				contracts_debug,
+				chain_spec: chain_spec.cloned_box(),
+			};
+			let mut module = crate::rpc::create_full::<RuntimeApi, _, _, _>(deps)?;
+			module
+				.merge(ManualSeal::new(seal_requests.clone()).into_rpc())
+				.map_err(|e| sc_service::Error::Application(Box::new(e)))?;
+			Ok(module)
+		})
+	};
+
+	let prometheus_registry = config.prometheus_registry().cloned();
+	sc_service::spawn_tasks(sc_service::SpawnTasksParams {
+		rpc_builder,
+		client: client.clone(),
+		transaction_pool: transaction_pool.clone(),
+		task_manager: &mut task_manager,
+		config,
+		keystore: params.keystore_container.keystore(),
+		backend: backend.clone(),
+		network,
+		sync_service,
+		system_rpc_tx,
+		tx_handler_controller,
+		telemetry: telemetry.as_mut(),
+	})?;
+
+	let slot_duration = client
+		.runtime_api()
+		.slot_duration(client.info().best_hash)
+		.map_err(|e| sc_service::Error::Application(Box::new(e)))?;
+	let slots = Arc::new(DevSlots::new(slot_duration.as_millis()));
+	let create_inherent_data_providers = {
+		let client = client.clone();
+		move |parent: Hash, ()| {
+			let client = client.clone();
+			let slots = slots.clone();
+			async move {
+				let parent_header =
+					client.header(parent)?.ok_or("The parent of the block is not stored")?;
+				let timestamp =
+					slots.next_timestamp(sp_timestamp::Timestamp::current().as_millis());
+				let relay_chain_slot = Slot::from(timestamp / RELAY_CHAIN_SLOT_DURATION_MS);
+				let relay_chain = MockValidationDataInherentDataProvider {
+					current_para_block: parent_header.number + 1,
+					para_id,
+					current_para_block_head: Some(HeadData(parent_header.encode())),
+					relay_offset: 1000,
+					relay_blocks_per_para_block: 1,
+					para_blocks_per_relay_epoch: 10,
+					relay_randomness_config: (),
+					xcm_config: MockXcmConfig::new(&*client, parent, Default::default()),
+					raw_downward_messages: vec![],
+					raw_horizontal_messages: vec![],
+					additional_key_values: Some(vec![(
+						well_known_keys::CURRENT_SLOT.to_vec(),
+						relay_chain_slot.encode(),
+					)]),
+				};
+				let timestamp = sp_timestamp::InherentDataProvider::new(timestamp.into());
+				Ok::<_, Box<dyn std::error::Error + Send + Sync>>((timestamp, relay_chain))
+			}
+		}
+	};
+
+	let proposer = sc_basic_authorship::ProposerFactory::new(
+		task_manager.spawn_handle(),
+		client.clone(),
+		transaction_pool.clone(),
+		prometheus_registry.as_ref(),
+		telemetry.as_ref().map(|t| t.handle()),
+	);
+	let authorship = sc_consensus_manual_seal::run_manual_seal(ManualSealParams {
+		block_import: client.clone(),
+		env: proposer,
+		client: client.clone(),
+		pool: transaction_pool,
+		commands_stream: futures::stream::select(requested_seals, authored_seals),
+		select_chain: sc_consensus::LongestChain::new(backend),
+		consensus_data_provider: Some(Box::new(AuraConsensusDataProvider::new(client))),
+		create_inherent_data_providers,
+	});
+	task_manager
+		.spawn_essential_handle()
+		.spawn_blocking("manual-seal", None, authorship);
+	task_manager.spawn_handle().spawn(
+		"shutdown-watchdog",
+		None,
+		crate::shutdown::watchdog(shutdown_grace),
+	);
+
+	start_network.start_network();
+
+	Ok(task_manager)
+}
+
+/// The slots of the blocks authored by a development node, strictly increasing as required by
+/// aura even when blocks are authored faster than the slot duration, in which case the slots of
+/// the blocks get ahead of the time.
+struct DevSlots {
+	/// The duration of a slot, in milliseconds.
+	duration: u64,
+	/// The slot of the last block authored.
+	last: std::sync::Mutex<u64>,
+}
+
+impl DevSlots {
+	fn new(duration: u64) -> Self {
+		Self { duration, last: Default::default() }
+	}
+
+	/// The timestamp of the next block authored at `now`, in milliseconds: the start of the
+	/// current slot, or of the one following the slot of the last block if not later.
+	fn next_timestamp(&self, now: u64) -> u64 {
+		let mut last = self.last.lock().unwrap_or_else(|e| e.into_inner());
+		*last = (now / self.duration).max(*last + 1);
+		*last * self.duration
+	}
+}
+
 #[cfg(not(feature = "ismp"))]
 mod runtime_api_ext {
-	use pop_runtime_common::{AccountId, AuraId, Balance, Block, Nonce};
+	use pop_runtime_common::{
+		proxy::ProxyType, AccountId, AssetId, AuraId, Balance, Block, Hash, Nonce,
+	};
 	use sp_core::Pair;
 	use sp_runtime::app_crypto::AppCrypto;
 
@@ -518,6 +1065,21 @@ mod runtime_api_ext {
 		+ pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
 		+ substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>
 		+ cumulus_primitives_aura::AuraUnincludedSegmentApi<Block>
+		+ pop_runtime_common::apis::NextAuthorApi<Block, AccountId>
+		+ pop_runtime_common::apis::UpgradeStatusApi<Block, Hash>
+		+ pop_runtime_common::apis::CollatorRegistrationApi<Block, AccountId, Balance>
+		+ pop_runtime_common::apis::CollatorSetApi<Block, AccountId, Balance>
+		+ pop_runtime_common::apis::ProxyApi<Block, AccountId, ProxyType>
+		+ pop_runtime_common::apis::XcmConversionApi<Block, AccountId>
+		+ pop_runtime_common::apis::AccountConversionApi<Block, AccountId>
+		+ pop_runtime_common::apis::XcmQueueApi<Block>
+		+ pop_runtime_common::apis::InboundXcmQueueApi<Block>
+		+ pop_runtime_common::apis::XcmVersionApi<Block>
+		+ pop_runtime_common::apis::SupplyApi<Block, Balance>
+		+ pop_runtime_common::apis::FeeApi<Block>
+		+ pop_runtime_common::apis::AssetFeeApi<Block, AssetId, Balance>
+		+ pop_runtime_common::apis::BlockUsageApi<Block>
+		+ pop_runtime_common::apis::RuntimeInfoApi<Block>
 	{
 	}
 	impl<
@@ -531,7 +1093,22 @@ mod runtime_api_ext {
 				+ sp_consensus_aura::AuraApi<Block, <<AuraId as AppCrypto>::Pair as Pair>::Public>
 				+ pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
 				+ substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>
-				+ cumulus_primitives_aura::AuraUnincludedSegmentApi<Block>,
+				+ cumulus_primitives_aura::AuraUnincludedSegmentApi<Block>
+				+ pop_runtime_common::apis::NextAuthorApi<Block, AccountId>
+				+ pop_runtime_common::apis::UpgradeStatusApi<Block, Hash>
+				+ pop_runtime_common::apis::CollatorRegistrationApi<Block, AccountId, Balance>
+				+ pop_runtime_common::apis::CollatorSetApi<Block, AccountId, Balance>
+				+ pop_runtime_common::apis::ProxyApi<Block, AccountId, ProxyType>
+				+ pop_runtime_common::apis::XcmConversionApi<Block, AccountId>
+				+ pop_runtime_common::apis::AccountConversionApi<Block, AccountId>
+				+ pop_runtime_common::apis::XcmQueueApi<Block>
+				+ pop_runtime_common::apis::InboundXcmQueueApi<Block>
+				+ pop_runtime_common::apis::XcmVersionApi<Block>
+				+ pop_runtime_common::apis::SupplyApi<Block, Balance>
+				+ pop_runtime_common::apis::FeeApi<Block>
+				+ pop_runtime_common::apis::AssetFeeApi<Block, AssetId, Balance>
+				+ pop_runtime_common::apis::BlockUsageApi<Block>
+				+ pop_runtime_common::apis::RuntimeInfoApi<Block>,
 			RuntimeApi,
 		> RuntimeApiExt<RuntimeApi> for T
 	{
@@ -540,7 +1117,9 @@ mod runtime_api_ext {
 
 #[cfg(feature = "ismp")]
 mod runtime_api_ext {
-	use pop_runtime_common::{AccountId, AuraId, Balance, Block, Nonce};
+	use pop_runtime_common::{
+		proxy::ProxyType, AccountId, AssetId, AuraId, Balance, Block, Hash, Nonce,
+	};
 	use sp_core::{Pair, H256};
 	use sp_runtime::app_crypto::AppCrypto;
 
@@ -557,6 +1136,21 @@ mod runtime_api_ext {
 		+ pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
 		+ substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>
 		+ cumulus_primitives_aura::AuraUnincludedSegmentApi<Block>
+		+ pop_runtime_common::apis::NextAuthorApi<Block, AccountId>
+		+ pop_runtime_common::apis::UpgradeStatusApi<Block, Hash>
+		+ pop_runtime_common::apis::CollatorRegistrationApi<Block, AccountId, Balance>
+		+ pop_runtime_common::apis::CollatorSetApi<Block, AccountId, Balance>
+		+ pop_runtime_common::apis::ProxyApi<Block, AccountId, ProxyType>
+		+ pop_runtime_common::apis::XcmConversionApi<Block, AccountId>
+		+ pop_runtime_common::apis::AccountConversionApi<Block, AccountId>
+		+ pop_runtime_common::apis::XcmQueueApi<Block>
+		+ pop_runtime_common::apis::InboundXcmQueueApi<Block>
+		+ pop_runtime_common::apis::XcmVersionApi<Block>
+		+ pop_runtime_common::apis::SupplyApi<Block, Balance>
+		+ pop_runtime_common::apis::FeeApi<Block>
+		+ pop_runtime_common::apis::AssetFeeApi<Block, AssetId, Balance>
+		+ pop_runtime_common::apis::BlockUsageApi<Block>
+		+ pop_runtime_common::apis::RuntimeInfoApi<Block>
 		+ ismp_parachain_runtime_api::IsmpParachainApi<Block>
 		+ pallet_ismp_runtime_api::IsmpRuntimeApi<Block, H256>
 	{
@@ -573,6 +1167,21 @@ mod runtime_api_ext {
 				+ pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
 				+ substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>
 				+ cumulus_primitives_aura::AuraUnincludedSegmentApi<Block>
+				+ pop_runtime_common::apis::NextAuthorApi<Block, AccountId>
+				+ pop_runtime_common::apis::UpgradeStatusApi<Block, Hash>
+				+ pop_runtime_common::apis::CollatorRegistrationApi<Block, AccountId, Balance>
+				+ pop_runtime_common::apis::CollatorSetApi<Block, AccountId, Balance>
+				+ pop_runtime_common::apis::ProxyApi<Block, AccountId, ProxyType>
+				+ pop_runtime_common::apis::XcmConversionApi<Block, AccountId>
+				+ pop_runtime_common::apis::AccountConversionApi<Block, AccountId>
+				+ pop_runtime_common::apis::XcmQueueApi<Block>
+				+ pop_runtime_common::apis::InboundXcmQueueApi<Block>
+				+ pop_runtime_common::apis::XcmVersionApi<Block>
+				+ pop_runtime_common::apis::SupplyApi<Block, Balance>
+				+ pop_runtime_common::apis::FeeApi<Block>
+				+ pop_runtime_common::apis::AssetFeeApi<Block, AssetId, Balance>
+				+ pop_runtime_common::apis::BlockUsageApi<Block>
+				+ pop_runtime_common::apis::RuntimeInfoApi<Block>
 				+ ismp_parachain_runtime_api::IsmpParachainApi<Block>
 				+ pallet_ismp_runtime_api::IsmpRuntimeApi<Block, H256>,
 			RuntimeApi,
@@ -582,3 +1191,19 @@ mod runtime_api_ext {
 }
 
 use runtime_api_ext::RuntimeApiExt;
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn dev_slots_strictly_increase() {
+		let slots = DevSlots::new(6_000);
+		assert_eq!(slots.next_timestamp(60_500), 60_000);
+		// Blocks authored within the same slot are authored in the following slots.
+		assert_eq!(slots.next_timestamp(61_000), 66_000);
+		assert_eq!(slots.next_timestamp(62_000), 72_000);
+		// Until the time catches up with the slots.
+		assert_eq!(slots.next_timestamp(90_000), 90_000);
+	}
+}
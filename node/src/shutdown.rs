@@ -0,0 +1,80 @@
+//! Bounded graceful shutdown of the node.
+//!
+//! On SIGTERM/SIGINT the substrate runner stops the task manager, which cancels block import,
+//! networking and authoring, and then drops the client and backend so that the database is closed
+//! cleanly. The watchdog below makes sure that this process cannot take longer than the configured
+//! grace period, so that orchestrators can use a kill timeout longer than the grace period and
+//! never interrupt a database that is still being written to.
+
+use std::{thread, time::Duration};
+
+/// The longest grace period, which is also the longest the runner waits for tasks to finish.
+pub const MAX_GRACE_SECS: u64 = 60;
+
+/// The default grace period.
+pub const DEFAULT_GRACE_SECS: u64 = 30;
+
+/// The exit code of a node which did not shut down within its grace period, as of the `timeout`
+/// utility, so that orchestrators can tell it from a node failing otherwise.
+pub const TIMED_OUT_EXIT_CODE: i32 = 124;
+
+/// Waits until the node shuts down, at which point the returned future is dropped and the
+/// watchdog is armed. If the node has not exited within `grace`, the process is terminated.
+pub(crate) async fn watchdog(grace: Duration) {
+	let _guard = Watchdog { grace, expired: terminate };
+	futures::future::pending::<()>().await
+}
+
+fn terminate(grace: Duration) {
+	log::error!("Node did not shut down within {} seconds, exiting.", grace.as_secs());
+	// The process exits without flushing the logs otherwise.
+	log::logger().flush();
+	std::process::exit(TIMED_OUT_EXIT_CODE);
+}
+
+/// Arms a timer when dropped, calling `expired` once `grace` has elapsed unless the process has
+/// exited in the meantime.
+struct Watchdog {
+	grace: Duration,
+	expired: fn(Duration),
+}
+
+impl Drop for Watchdog {
+	fn drop(&mut self) {
+		let (grace, expired) = (self.grace, self.expired);
+		log::info!("Shutting down, allowing up to {} seconds to flush state.", grace.as_secs());
+		thread::spawn(move || {
+			thread::sleep(grace);
+			expired(grace)
+		});
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{
+		sync::atomic::{AtomicBool, Ordering},
+		time::Instant,
+	};
+
+	use super::*;
+
+	static EXPIRED: AtomicBool = AtomicBool::new(false);
+
+	#[test]
+	fn watchdog_only_fires_after_grace_period() {
+		let grace = Duration::from_millis(200);
+		let watchdog = Watchdog { grace, expired: |_| EXPIRED.store(true, Ordering::SeqCst) };
+		// Not armed while the node is running.
+		thread::sleep(grace * 2);
+		assert!(!EXPIRED.load(Ordering::SeqCst));
+
+		let start = Instant::now();
+		drop(watchdog);
+		while !EXPIRED.load(Ordering::SeqCst) {
+			assert!(start.elapsed() < grace * 10, "watchdog did not fire");
+			thread::sleep(Duration::from_millis(10));
+		}
+		assert!(start.elapsed() >= grace);
+	}
+}
@@ -0,0 +1,86 @@
+//! Chain specifications as TOML, for editing their network parameters by hand.
+//!
+//! All fields of a chain specification but its genesis are written as TOML, while the genesis is
+//! embedded as a JSON string, as it is the runtime which defines its format. Fields without a
+//! value are omitted, as TOML has no null, which is equivalent for a chain specification.
+
+use serde_json::Value;
+
+/// The field of the chain specification holding its genesis.
+const GENESIS: &str = "genesis";
+
+/// Whether `path` is a TOML chain specification, by its extension.
+pub fn is_toml(path: &str) -> bool {
+	path.ends_with(".toml")
+}
+
+/// Convert the JSON chain specification `json`, e.g. as produced by `ChainSpec::as_json`, to
+/// TOML.
+pub fn to_toml(json: &str) -> Result<String, String> {
+	let mut spec: serde_json::Map<String, Value> =
+		serde_json::from_str(json).map_err(|e| format!("Invalid chain spec json: {e}"))?;
+	let genesis = spec.remove(GENESIS).ok_or("The chain spec has no genesis")?;
+	let mut spec = without_nulls(Value::Object(spec));
+	spec[GENESIS] = serde_json::to_string_pretty(&genesis)
+		.map_err(|e| format!("Failed to serialize genesis: {e}"))?
+		.into();
+	toml::to_string_pretty(&spec).map_err(|e| format!("Failed to serialize chain spec: {e}"))
+}
+
+/// Convert the TOML chain specification `toml`, as produced by [`to_toml`], to JSON.
+pub fn from_toml(toml: &str) -> Result<Value, String> {
+	let mut spec: Value =
+		toml::from_str(toml).map_err(|e| format!("Invalid chain spec toml: {e}"))?;
+	let genesis = spec
+		.get(GENESIS)
+		.and_then(Value::as_str)
+		.ok_or("The chain spec has no genesis json")?;
+	spec[GENESIS] =
+		serde_json::from_str(genesis).map_err(|e| format!("Invalid genesis json: {e}"))?;
+	Ok(spec)
+}
+
+/// `value` without any null, recursively.
+fn without_nulls(value: Value) -> Value {
+	match value {
+		Value::Object(fields) => fields
+			.into_iter()
+			.filter(|(_, value)| !value.is_null())
+			.map(|(field, value)| (field, without_nulls(value)))
+			.collect(),
+		Value::Array(values) =>
+			values.into_iter().filter(|value| !value.is_null()).map(without_nulls).collect(),
+		value => value,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use sc_service::ChainSpec;
+
+	use super::*;
+	use crate::chain_spec::{development_config, Code, DevnetChainSpec, Relay};
+
+	#[test]
+	fn toml_round_trips_to_equivalent_spec() {
+		let spec = development_config(Relay::PaseoLocal, None, vec![], vec![], Code::Compressed);
+		let json = spec.as_json(false).unwrap();
+		let toml = to_toml(&json).unwrap();
+		assert!(toml.contains(r#"relay_chain = "paseo-local""#));
+		assert!(!toml.contains("telemetryEndpoints"));
+
+		let round_tripped = DevnetChainSpec::from_json_bytes(
+			serde_json::to_vec(&from_toml(&toml).unwrap()).unwrap(),
+		)
+		.unwrap();
+		let as_value = |json: String| serde_json::from_str::<Value>(&json).unwrap();
+		assert_eq!(as_value(round_tripped.as_json(false).unwrap()), as_value(json));
+		assert_eq!(round_tripped.as_json(true).unwrap(), spec.as_json(true).unwrap());
+	}
+
+	#[test]
+	fn toml_without_genesis_is_rejected() {
+		assert!(from_toml(r#"name = "Pop""#).is_err());
+		assert!(from_toml("genesis = \"not json\"").is_err());
+	}
+}
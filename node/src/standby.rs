@@ -0,0 +1,262 @@
+//! Runs a collator as a hot standby with `--collator-standby`: the node runs the collation
+//! machinery but only authors once the other collators stopped producing, so that a single
+//! collator failing no longer halts a deployment until someone intervenes.
+//!
+//! The head of the parachain backed as of each best relay chain block is tracked. The node takes
+//! over once the backed head has not advanced for `--standby-takeover-after` relay chain blocks,
+//! and stands by again once a block it did not author is backed, i.e. once the other collators
+//! recovered. While standing by, the aura keys are hidden from authoring by the
+//! [`CollatorKeystore`](crate::collator_keystore::CollatorKeystore).
+
+use std::{
+	collections::BTreeMap,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc,
+	},
+};
+
+use codec::Decode;
+use cumulus_primitives_core::{relay_chain::OccupiedCoreAssumption, ParaId};
+use cumulus_relay_chain_interface::RelayChainInterface;
+use futures::{stream, StreamExt};
+use pop_runtime_common::{Block, BlockNumber, Hash};
+use sc_client_api::BlockchainEvents;
+use sc_service::TaskManager;
+use sp_consensus::BlockOrigin;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+
+use crate::structured_logs::relay_chain_call;
+
+const LOG_TARGET: &str = "standby";
+
+/// The default number of relay chain blocks without a parachain block being backed after which a
+/// standby collator takes over: half a minute of relay chain blocks.
+pub const DEFAULT_TAKEOVER_AFTER: u32 = 5;
+
+/// Whether a standby collator authors, shared with the keystore it authors with.
+#[derive(Clone, Default)]
+pub(crate) struct Authoring(Arc<AtomicBool>);
+
+impl Authoring {
+	/// Whether the collator took over authoring.
+	pub(crate) fn get(&self) -> bool {
+		self.0.load(Ordering::Relaxed)
+	}
+
+	/// Set whether the collator authors.
+	pub(crate) fn set(&self, authoring: bool) {
+		self.0.store(authoring, Ordering::Relaxed)
+	}
+}
+
+/// The blocks authored by the node which may still be backed.
+#[derive(Default)]
+struct Authored {
+	blocks: BTreeMap<BlockNumber, Vec<Hash>>,
+}
+
+impl Authored {
+	/// Note the block `hash` authored by the node at `number`.
+	fn author(&mut self, number: BlockNumber, hash: Hash) {
+		self.blocks.entry(number).or_default().push(hash);
+	}
+
+	/// Whether the block `hash` backed at `number` was authored by the node, forgetting the blocks
+	/// below it, which can no longer be backed.
+	fn backed(&mut self, number: BlockNumber, hash: Hash) -> bool {
+		self.blocks = self.blocks.split_off(&number);
+		self.blocks.get(&number).is_some_and(|hashes| hashes.contains(&hash))
+	}
+}
+
+/// Decides when a standby collator takes over authoring and when it stands by again.
+struct Failover {
+	/// The number of relay chain blocks without a block being backed after which to take over.
+	takeover_after: u32,
+	/// The backed head, along with the relay chain block it was first seen backed by.
+	backed: Option<(Hash, u32)>,
+	authoring: bool,
+}
+
+impl Failover {
+	fn new(takeover_after: u32) -> Self {
+		Self { takeover_after, backed: None, authoring: false }
+	}
+
+	/// Note that the relay chain block `relay_number` backs the parachain head `head`, which was
+	/// authored by this node if `own`. Returns whether the node authors if that changed.
+	fn note_relay_block(&mut self, relay_number: u32, head: Hash, own: bool) -> Option<bool> {
+		let authoring = match self.backed {
+			Some((backed, since)) if backed == head =>
+				self.authoring || relay_number.saturating_sub(since) >= self.takeover_after,
+			_ => {
+				// The first head seen is not a sign of the other collators recovering either.
+				let recovered = self.backed.is_some() && !own;
+				self.backed = Some((head, relay_number));
+				self.authoring && !recovered
+			},
+		};
+		(authoring != self.authoring).then(|| {
+			self.authoring = authoring;
+			authoring
+		})
+	}
+}
+
+/// An update of the state followed.
+enum Update {
+	/// The best relay chain block, by number and hash.
+	BestRelay(u32, Hash),
+	/// A block authored by the node, by number and hash.
+	Authored(BlockNumber, Hash),
+}
+
+/// Spawn the task deciding whether the standby collator of the parachain `para_id` authors,
+/// taking over after `takeover_after` relay chain blocks without a block being backed.
+pub(crate) fn spawn<C>(
+	task_manager: &TaskManager,
+	client: Arc<C>,
+	relay_chain_interface: Arc<dyn RelayChainInterface>,
+	para_id: ParaId,
+	takeover_after: u32,
+	authoring: Authoring,
+) where
+	C: BlockchainEvents<Block> + Send + Sync + 'static,
+{
+	log::info!(
+		target: LOG_TARGET,
+		"💤 Standing by, authoring once no block was backed for {takeover_after} relay chain \
+		 blocks"
+	);
+	// Essential, as a standby no longer following the relay chain would never take over.
+	task_manager.spawn_essential_handle().spawn(
+		"collator-standby",
+		None,
+		run(client, relay_chain_interface, para_id, Failover::new(takeover_after), authoring),
+	);
+}
+
+/// Follow the best relay chain blocks and the blocks authored by the node, updating `authoring`
+/// as decided by `failover`.
+async fn run<C>(
+	client: Arc<C>,
+	relay_chain_interface: Arc<dyn RelayChainInterface>,
+	para_id: ParaId,
+	mut failover: Failover,
+	authoring: Authoring,
+) where
+	C: BlockchainEvents<Block>,
+{
+	let best_relay = match relay_chain_interface.new_best_notification_stream().await {
+		Ok(headers) => headers.map(|header| Update::BestRelay(header.number, header.hash())),
+		Err(e) => {
+			log::error!(target: LOG_TARGET, "Unable to follow the best relay chain block: {e}");
+			return;
+		},
+	};
+	let own = client
+		.import_notification_stream()
+		.filter(|notification| futures::future::ready(notification.origin == BlockOrigin::Own))
+		.map(|notification| Update::Authored(*notification.header.number(), notification.hash));
+
+	let mut authored = Authored::default();
+	let mut updates = stream::select(best_relay, own);
+	while let Some(update) = updates.next().await {
+		let (relay_number, relay_hash) = match update {
+			Update::BestRelay(number, hash) => (number, hash),
+			Update::Authored(number, hash) => {
+				authored.author(number, hash);
+				continue;
+			},
+		};
+		// The head backed by the relay chain block, assuming the candidates pending availability
+		// are included.
+		let data = relay_chain_interface.persisted_validation_data(
+			relay_hash,
+			para_id,
+			OccupiedCoreAssumption::Included,
+		);
+		let head = match relay_chain_call("persisted_validation_data", relay_hash, data).await {
+			Ok(Some(data)) => <Block as BlockT>::Header::decode(&mut &data.parent_head.0[..]),
+			Ok(None) => {
+				log::debug!(target: LOG_TARGET, "No parachain head as of relay block {relay_hash}");
+				continue;
+			},
+			Err(e) => {
+				log::debug!(target: LOG_TARGET, "Unable to read the head as of {relay_hash}: {e}");
+				continue;
+			},
+		};
+		let Ok(head) = head else {
+			log::debug!(target: LOG_TARGET, "Unable to decode the head as of {relay_hash}");
+			continue;
+		};
+
+		let (number, hash) = (*head.number(), head.hash());
+		let own = authored.backed(number, hash);
+		match failover.note_relay_block(relay_number, hash, own) {
+			Some(true) => log::warn!(
+				target: LOG_TARGET,
+				"⚠️  No parachain block was backed for {} relay chain blocks, the last being \
+				 #{number}. Taking over authoring.",
+				failover.takeover_after,
+			),
+			Some(false) => log::info!(
+				target: LOG_TARGET,
+				"💤 Parachain block #{number} of another collator was backed, standing by again"
+			),
+			None => continue,
+		}
+		authoring.set(failover.authoring);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn standby_takes_over_once_no_block_is_backed() {
+		let head = |n: u8| Hash::repeat_byte(n);
+		let mut failover = Failover::new(2);
+		assert_eq!(failover.note_relay_block(100, head(1), false), None);
+		// The head backed by the next relay chain blocks does not advance.
+		assert_eq!(failover.note_relay_block(101, head(1), false), None);
+		assert_eq!(failover.note_relay_block(102, head(1), false), Some(true));
+		assert_eq!(failover.note_relay_block(103, head(1), false), None);
+		// Blocks of the standby being backed keep it authoring.
+		assert_eq!(failover.note_relay_block(104, head(2), true), None);
+		assert_eq!(failover.note_relay_block(105, head(3), true), None);
+		// A block of another collator being backed means it recovered.
+		assert_eq!(failover.note_relay_block(106, head(4), false), Some(false));
+		assert_eq!(failover.note_relay_block(107, head(5), false), None);
+		// And the standby takes over again once it stalls again.
+		assert_eq!(failover.note_relay_block(109, head(5), false), Some(true));
+	}
+
+	#[test]
+	fn only_blocks_authored_by_the_node_are_its_own() {
+		let hash = |n: u8| Hash::repeat_byte(n);
+		let mut authored = Authored::default();
+		authored.author(1, hash(1));
+		authored.author(2, hash(2));
+		authored.author(3, hash(3));
+
+		assert!(authored.backed(2, hash(2)));
+		// A fork of the block authored by the node.
+		assert!(!authored.backed(3, hash(4)));
+		assert!(authored.backed(3, hash(3)));
+		// Blocks below the backed head are forgotten.
+		assert!(!authored.backed(1, hash(1)));
+	}
+
+	#[test]
+	fn authoring_is_shared() {
+		let authoring = Authoring::default();
+		let keystore_view = authoring.clone();
+		assert!(!keystore_view.get());
+		authoring.set(true);
+		assert!(keystore_view.get());
+	}
+}
@@ -0,0 +1,150 @@
+//! Holds back collation on nodes started with `--sync fast` or `--sync warp` until their state sync
+//! completes, so that a fresh collator only builds blocks once it holds the state of the blocks it
+//! builds upon, instead of failing to build every slot until then. Also provides the target block
+//! of warp syncs.
+
+use std::{sync::Arc, time::Duration};
+
+use codec::Decode;
+use cumulus_primitives_core::{relay_chain::OccupiedCoreAssumption, ParaId};
+use cumulus_relay_chain_interface::RelayChainInterface;
+use futures::{channel::oneshot, StreamExt};
+use pop_runtime_common::Block;
+use sc_client_api::Backend;
+use sc_network::config::SyncMode;
+use sc_service::SpawnTaskHandle;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+
+const LOG_TARGET: &str = "state-sync";
+
+/// The interval between checks of whether the state sync has completed, about a relay chain slot.
+const POLL_INTERVAL: Duration = Duration::from_secs(6);
+
+/// Whether a node syncing in `mode` imports the state of a recent block rather than executing
+/// every block since genesis.
+pub fn syncs_state(mode: &SyncMode) -> bool {
+	!matches!(mode, SyncMode::Full)
+}
+
+/// Wait until `backend` holds the state of the parachain head included by the best relay chain
+/// block, which is where the collator of a fresh node starts building, i.e. until the state sync
+/// of the node has completed.
+///
+/// Returns right away on chains whose included head is still genesis, which the node always
+/// holds the state of.
+pub async fn wait<B: Backend<Block>>(
+	backend: &B,
+	relay_chain_interface: &dyn RelayChainInterface,
+	para_id: ParaId,
+) {
+	let mut waiting = false;
+	loop {
+		match included_head(relay_chain_interface, para_id).await {
+			Ok(head) if backend.have_state_at(head.hash(), *head.number()) => {
+				if waiting {
+					log::info!(
+						target: LOG_TARGET,
+						"✅ State synced as of #{}, starting to collate",
+						head.number()
+					);
+				}
+				return;
+			},
+			Ok(head) if !waiting => {
+				log::info!(
+					target: LOG_TARGET,
+					"⏳ Waiting for the state sync to reach #{} before collating",
+					head.number()
+				);
+				waiting = true;
+			},
+			Ok(_) => {},
+			Err(e) => log::debug!(target: LOG_TARGET, "Unable to read the included head: {e}"),
+		}
+		tokio::time::sleep(POLL_INTERVAL).await;
+	}
+}
+
+/// The target block of a warp sync: the parachain head included by the finalized relay chain block
+/// once the relay chain is synced, as `cumulus_client_service::build_network` waits for.
+pub fn warp_sync_target(
+	relay_chain_interface: Arc<dyn RelayChainInterface>,
+	para_id: ParaId,
+	spawn_handle: &SpawnTaskHandle,
+) -> oneshot::Receiver<<Block as BlockT>::Header> {
+	let (sender, receiver) = oneshot::channel();
+	spawn_handle.spawn("warp-sync-target", None, async move {
+		match finalized_head(&*relay_chain_interface, para_id).await {
+			Ok(head) => {
+				log::info!(
+					target: LOG_TARGET,
+					"🎉 Warp syncing to #{} ({}), included by the relay chain",
+					head.number(),
+					head.hash()
+				);
+				let _ = sender.send(head);
+			},
+			Err(e) =>
+				log::error!(target: LOG_TARGET, "Unable to determine the warp sync target: {e}"),
+		}
+	});
+	receiver
+}
+
+/// The parachain head included by the finalized relay chain block, once the relay chain is synced.
+async fn finalized_head(
+	relay_chain_interface: &dyn RelayChainInterface,
+	para_id: ParaId,
+) -> Result<<Block as BlockT>::Header, String> {
+	let mut imported = relay_chain_interface
+		.import_notification_stream()
+		.await
+		.map_err(|e| e.to_string())?;
+	while imported.next().await.is_some() {
+		if relay_chain_interface.is_major_syncing().await.map_err(|e| e.to_string())? {
+			continue;
+		}
+		let relay_hash =
+			relay_chain_interface.finalized_block_hash().await.map_err(|e| e.to_string())?;
+		return head_at(relay_chain_interface, relay_hash, para_id).await;
+	}
+	Err("the relay chain stopped importing blocks".into())
+}
+
+/// The parachain head included, rather than pending availability, by the best relay chain block.
+async fn included_head(
+	relay_chain_interface: &dyn RelayChainInterface,
+	para_id: ParaId,
+) -> Result<<Block as BlockT>::Header, String> {
+	let relay_hash = relay_chain_interface.best_block_hash().await.map_err(|e| e.to_string())?;
+	head_at(relay_chain_interface, relay_hash, para_id).await
+}
+
+/// The parachain head included by the relay chain block `relay_hash`.
+async fn head_at(
+	relay_chain_interface: &dyn RelayChainInterface,
+	relay_hash: cumulus_primitives_core::relay_chain::Hash,
+	para_id: ParaId,
+) -> Result<<Block as BlockT>::Header, String> {
+	let data = relay_chain_interface
+		.persisted_validation_data(relay_hash, para_id, OccupiedCoreAssumption::TimedOut)
+		.await
+		.map_err(|e| e.to_string())?
+		.ok_or_else(|| format!("no parachain head as of relay block {relay_hash}"))?;
+	<Block as BlockT>::Header::decode(&mut &data.parent_head.0[..]).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn only_full_sync_executes_every_block() {
+		assert!(!syncs_state(&SyncMode::Full));
+		assert!(syncs_state(&SyncMode::LightState {
+			skip_proofs: false,
+			storage_chain_mode: false
+		}));
+		assert!(syncs_state(&SyncMode::Warp));
+	}
+}
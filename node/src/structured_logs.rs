@@ -0,0 +1,223 @@
+//! Structured logs of the collation pipeline, enabled with `--log-format json` so that log
+//! aggregation can follow collation attempts, e.g. when diagnosing missed slots.
+//!
+//! The node logs the collation pipeline under dedicated targets:
+//! - `pop::collation`: the blocks authored by the node and whether they were included by the relay
+//!   chain or dropped.
+//! - `pop::relay-chain`: the calls to the relay chain interface, along with their duration.
+//! - `pop::xcm-queue`: the inbound XCM queue as of each best block, within a span timing its read.
+//!
+//! With `--log-format json`, the events and spans of these targets are written to stdout as one
+//! json object per line, spans along with the time spent within them, while the other logs remain
+//! on stderr. With `--detailed-log-output`, their debug events are included too, along with the
+//! logs of the upstream collator.
+
+use std::{
+	future::Future,
+	io::Write,
+	time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use pop_runtime_common::Hash;
+use sc_tracing::{
+	logging::LoggerBuilder, SpanDatum, TraceEvent, TraceHandler, TracingReceiver, Values,
+};
+use serde_json::{json, Map, Value};
+
+/// The target of the collation attempts.
+pub(crate) const COLLATION: &str = "pop::collation";
+/// The target of the calls to the relay chain interface.
+pub(crate) const RELAY_CHAIN: &str = "pop::relay-chain";
+/// The target of the processing of the inbound XCM queue.
+pub(crate) const XCM_QUEUE: &str = "pop::xcm-queue";
+
+/// The upstream targets of the collation pipeline, only logged with `--detailed-log-output`: the
+/// slot claims of aura, the proposal of blocks and the submission of collations.
+const UPSTREAM_TARGETS: [&str; 4] =
+	["aura::cumulus", "basic-authorship", "cumulus-collator", "parachain::collation-generation"];
+
+/// Writes the events and spans of the collation pipeline to stdout as json lines.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct JsonLogs {
+	/// Whether debug events and the upstream targets are logged too.
+	detailed: bool,
+}
+
+impl JsonLogs {
+	/// The json logs, including debug events and the upstream targets if `detailed`.
+	pub fn new(detailed: bool) -> Self {
+		Self { detailed }
+	}
+
+	/// Install the json logs into `logger`, enabling the spans and events of their targets.
+	pub fn install(self, logger: &mut LoggerBuilder) {
+		logger.with_profiling(TracingReceiver::Log, self.targets());
+		logger.with_custom_profiling(Box::new(self));
+	}
+
+	/// The targets logged, as directives of their level.
+	fn targets(&self) -> String {
+		let targets = [COLLATION, RELAY_CHAIN, XCM_QUEUE].map(|target| match self.detailed {
+			true => format!("{target}=debug"),
+			false => format!("{target}=info"),
+		});
+		let upstream = self
+			.detailed
+			.then_some(UPSTREAM_TARGETS.map(|target| format!("{target}=debug")))
+			.into_iter()
+			.flatten();
+		targets.into_iter().chain(upstream).collect::<Vec<_>>().join(",")
+	}
+
+	/// Write `line` to stdout, ignoring failures as logging never affects the node.
+	fn write(&self, line: Value) {
+		let _ = writeln!(std::io::stdout().lock(), "{line}");
+	}
+}
+
+impl TraceHandler for JsonLogs {
+	fn handle_span(&self, span: &SpanDatum) {
+		self.write(span_line(span, timestamp()));
+	}
+
+	fn handle_event(&self, event: &TraceEvent) {
+		self.write(event_line(event, timestamp()));
+	}
+}
+
+/// Await the relay chain interface `call` as of `relay_block`, logging its duration under the
+/// `pop::relay-chain` target.
+pub(crate) async fn relay_chain_call<T, E>(
+	call: &'static str,
+	relay_block: Hash,
+	result: impl Future<Output = Result<T, E>>,
+) -> Result<T, E> {
+	let started = Instant::now();
+	let result = result.await;
+	tracing::debug!(
+		target: RELAY_CHAIN,
+		call,
+		relay_block = %relay_block,
+		duration_us = started.elapsed().as_micros() as u64,
+		ok = result.is_ok(),
+		"Relay chain interface call",
+	);
+	result
+}
+
+/// The milliseconds since the unix epoch.
+fn timestamp() -> u128 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map(|now| now.as_millis())
+		.unwrap_or_default()
+}
+
+/// The json line of `span`, closed at `timestamp`.
+fn span_line(span: &SpanDatum, timestamp: u128) -> Value {
+	json!({
+		"timestamp": timestamp,
+		"level": span.level.as_str(),
+		"target": span.target,
+		"span": span.name,
+		"duration_us": span.overall_time.as_micros(),
+		"fields": fields(&span.values),
+	})
+}
+
+/// The json line of `event`, emitted at `timestamp`. The message is taken out of the fields.
+fn event_line(event: &TraceEvent, timestamp: u128) -> Value {
+	let mut fields = fields(&event.values);
+	let message = fields.remove("message").unwrap_or_default();
+	json!({
+		"timestamp": timestamp,
+		"level": event.level.as_str(),
+		"target": event.target,
+		"message": message,
+		"fields": fields,
+	})
+}
+
+/// The recorded `values` as json object.
+fn fields(values: &Values) -> Map<String, Value> {
+	let mut fields = Map::new();
+	fields.extend(values.bool_values.iter().map(|(name, value)| (name.clone(), json!(value))));
+	fields.extend(values.i64_values.iter().map(|(name, value)| (name.clone(), json!(value))));
+	fields.extend(values.u64_values.iter().map(|(name, value)| (name.clone(), json!(value))));
+	fields.extend(values.string_values.iter().map(|(name, value)| (name.clone(), json!(value))));
+	fields
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::Duration;
+
+	use tracing::{span::Id, Level};
+
+	use super::*;
+
+	#[test]
+	fn detailed_logs_include_debug_events_and_upstream_targets() {
+		assert_eq!(
+			JsonLogs::new(false).targets(),
+			"pop::collation=info,pop::relay-chain=info,pop::xcm-queue=info"
+		);
+		let detailed = JsonLogs::new(true).targets();
+		assert!(detailed.starts_with("pop::collation=debug,pop::relay-chain=debug"));
+		assert!(detailed.ends_with("cumulus-collator=debug,parachain::collation-generation=debug"));
+	}
+
+	#[test]
+	fn events_are_logged_with_their_fields() {
+		let mut values = Values::new();
+		values.string_values.insert("message".into(), "Authored block".into());
+		values.string_values.insert("hash".into(), "0x01".into());
+		values.u64_values.insert("number".into(), 7);
+		values.bool_values.insert("best".into(), true);
+		let event = TraceEvent {
+			name: "event".into(),
+			target: COLLATION.into(),
+			level: Level::INFO,
+			values,
+			parent_id: None,
+		};
+		assert_eq!(
+			event_line(&event, 1_000),
+			json!({
+				"timestamp": 1_000,
+				"level": "INFO",
+				"target": "pop::collation",
+				"message": "Authored block",
+				"fields": { "hash": "0x01", "number": 7, "best": true },
+			})
+		);
+	}
+
+	#[test]
+	fn spans_are_logged_with_their_duration() {
+		let mut values = Values::new();
+		values.i64_values.insert("messages".into(), -1);
+		let span = SpanDatum {
+			id: Id::from_u64(1),
+			parent_id: None,
+			name: "read_queue".into(),
+			target: XCM_QUEUE.into(),
+			level: Level::DEBUG,
+			line: 1,
+			start_time: Instant::now(),
+			overall_time: Duration::from_micros(1_500),
+			values,
+		};
+		assert_eq!(
+			span_line(&span, 2_000),
+			json!({
+				"timestamp": 2_000,
+				"level": "DEBUG",
+				"target": "pop::xcm-queue",
+				"span": "read_queue",
+				"duration_us": 1_500,
+				"fields": { "messages": -1 },
+			})
+		);
+	}
+}
@@ -0,0 +1,120 @@
+//! Profiling of the time spent in each host function called by the runtime, enabled with
+//! `--profile-wasm` and summarized in the logs when the node shuts down.
+//!
+//! Every host function is executed within a trace span named after it, e.g.
+//! `ext_storage_get_version_1`, so the spans of the host functions are handled by the profiling
+//! layer of the logger. Without `--profile-wasm`, neither the spans are enabled nor is the profile
+//! installed, so host functions are executed without any overhead.
+
+use std::{
+	collections::BTreeMap,
+	sync::{Arc, Mutex},
+	time::Duration,
+};
+
+use sc_service::TaskManager;
+use sc_tracing::{logging::LoggerBuilder, SpanDatum, TraceEvent, TraceHandler, TracingReceiver};
+
+const LOG_TARGET: &str = "wasm-profile";
+/// The targets of the spans of the host functions, i.e. the crates declaring runtime interfaces.
+const TARGETS: &str = "sp_io=trace,cumulus_primitives_proof_size_hostfunction=trace";
+
+/// The calls of a host function.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Timing {
+	/// The number of calls.
+	calls: u64,
+	/// The time spent in all calls.
+	total: Duration,
+}
+
+/// The time spent in each host function, shared between the logger recording it and the node
+/// summarizing it.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct HostFunctionProfile {
+	timings: Arc<Mutex<BTreeMap<String, Timing>>>,
+}
+
+impl HostFunctionProfile {
+	/// Install the profile into `logger`, enabling the spans of the host functions.
+	pub fn install(&self, logger: &mut LoggerBuilder) {
+		logger.with_profiling(TracingReceiver::Log, TARGETS);
+		logger.with_custom_profiling(Box::new(self.clone()));
+	}
+
+	/// Record a call of `host_function` which took `time`.
+	fn record(&self, host_function: &str, time: Duration) {
+		let mut timings = self.timings.lock().unwrap_or_else(|e| e.into_inner());
+		let timing = timings.entry(host_function.into()).or_default();
+		timing.calls += 1;
+		timing.total += time;
+	}
+
+	/// A line per host function called, the ones the most time was spent in first.
+	fn summary(&self) -> Vec<String> {
+		let timings = self.timings.lock().unwrap_or_else(|e| e.into_inner());
+		let mut timings: Vec<_> = timings.iter().collect();
+		timings.sort_by(|(_, a), (_, b)| b.total.cmp(&a.total));
+		timings
+			.into_iter()
+			.map(|(host_function, Timing { calls, total })| {
+				format!(
+					"{host_function}: {calls} calls, {total:?} in total, {:?} on average",
+					*total / (*calls).max(1) as u32
+				)
+			})
+			.collect()
+	}
+}
+
+impl TraceHandler for HostFunctionProfile {
+	fn handle_span(&self, span: &SpanDatum) {
+		self.record(&span.name, span.overall_time);
+	}
+
+	fn handle_event(&self, _: &TraceEvent) {}
+}
+
+/// Spawn the task logging the summary of `profile` once the node shuts down.
+pub(crate) fn spawn(task_manager: &TaskManager, profile: HostFunctionProfile) {
+	task_manager.spawn_handle().spawn("wasm-profile", None, async move {
+		let _summary = Summary(profile);
+		futures::future::pending::<()>().await
+	});
+}
+
+/// Logs the summary of the profile when dropped, i.e. when the tasks of the node are stopped.
+struct Summary(HostFunctionProfile);
+
+impl Drop for Summary {
+	fn drop(&mut self) {
+		let summary = self.0.summary();
+		log::info!(target: LOG_TARGET, "Time spent in {} host functions:", summary.len());
+		for line in summary {
+			log::info!(target: LOG_TARGET, "{line}");
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn summary_lists_slowest_host_functions_first() {
+		let profile = HostFunctionProfile::default();
+		assert!(profile.summary().is_empty());
+
+		let shared = profile.clone();
+		shared.record("ext_storage_get_version_1", Duration::from_micros(10));
+		shared.record("ext_storage_get_version_1", Duration::from_micros(30));
+		shared.record("ext_crypto_sr25519_verify_version_2", Duration::from_micros(50));
+		assert_eq!(
+			profile.summary(),
+			[
+				"ext_crypto_sr25519_verify_version_2: 1 calls, 50µs in total, 50µs on average",
+				"ext_storage_get_version_1: 2 calls, 40µs in total, 20µs on average",
+			]
+		);
+	}
+}
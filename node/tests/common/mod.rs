@@ -0,0 +1,343 @@
+//! A harness launching a local network of relay chain validators and collators as subprocesses,
+//! for integration tests of the node.
+//!
+//! The relay chain is run by the `polkadot` binary at `POLKADOT_BINARY`, or on the `PATH`
+//! otherwise, whose workers must be next to it.
+
+// Each test only uses part of the harness.
+#![allow(dead_code)]
+
+use std::{
+	fs::{self, File},
+	io::{Read, Write},
+	net::{TcpListener, TcpStream},
+	path::{Path, PathBuf},
+	process::{Child, Command, ExitStatus, Stdio},
+	thread,
+	time::{Duration, Instant},
+};
+
+use serde_json::{json, Value};
+
+/// The node under test.
+const POP_NODE: &str = env!("CARGO_BIN_EXE_pop-node");
+
+/// The built-in relay chain specification the parachain is registered on at genesis.
+const RELAY_CHAIN: &str = "rococo-local";
+
+/// The names of the validators and of the collators of the network.
+const NAMES: [&str; 2] = ["alice", "bob"];
+
+/// A running node, which is killed when dropped.
+pub struct Node {
+	rpc_port: u16,
+	process: Child,
+}
+
+impl Node {
+	/// Call the RPC `method` with `params` and return its result.
+	pub fn rpc(&self, method: &str, params: Value) -> Result<Value, String> {
+		let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+		let body = body.to_string();
+		let mut stream =
+			TcpStream::connect(("127.0.0.1", self.rpc_port)).map_err(|e| e.to_string())?;
+		stream
+			.set_read_timeout(Some(Duration::from_secs(10)))
+			.map_err(|e| e.to_string())?;
+		write!(
+			stream,
+			"POST / HTTP/1.1\r\nHost: 127.0.0.1\r\nContent-Type: \
+			 application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+			body.len()
+		)
+		.map_err(|e| e.to_string())?;
+		let mut response = String::new();
+		stream.read_to_string(&mut response).map_err(|e| e.to_string())?;
+		let (_, body) = response.split_once("\r\n\r\n").ok_or("Malformed http response")?;
+		let mut response: Value = serde_json::from_str(body).map_err(|e| e.to_string())?;
+		match response.get("error") {
+			Some(error) => Err(error.to_string()),
+			None => Ok(response["result"].take()),
+		}
+	}
+
+	/// The number of the best block of the node.
+	pub fn best_block(&self) -> Result<u64, String> {
+		let header = self.rpc("chain_getHeader", json!([]))?;
+		let number = header["number"].as_str().ok_or("Header without a number")?;
+		u64::from_str_radix(number.trim_start_matches("0x"), 16).map_err(|e| e.to_string())
+	}
+
+	/// The hash of the block `number` on the best chain of the node.
+	pub fn block_hash(&self, number: u64) -> Result<Value, String> {
+		self.rpc("chain_getBlockHash", json!([number]))
+	}
+
+	/// Shut the node down gracefully, as on SIGTERM, and wait for it to exit.
+	fn stop(&mut self) {
+		let pid = self.process.id().to_string();
+		output(Command::new("kill").args(["-TERM", &pid]));
+		self.process.wait().unwrap();
+	}
+
+	/// Launch a development node with `--dev` and `args`, authoring blocks without any relay
+	/// chain, whose data is removed when it exits.
+	pub fn launch_dev(args: &[&str]) -> Self {
+		let rpc_port = free_port();
+		let process = Command::new(POP_NODE)
+			.args(["--dev", "--tmp"])
+			.args(["--port", &free_port().to_string(), "--rpc-port", &rpc_port.to_string()])
+			.args(["--no-prometheus", "--no-telemetry", "--no-hardware-benchmarks", "--no-mdns"])
+			.args(args)
+			.stdin(Stdio::null())
+			.stdout(Stdio::null())
+			.stderr(Stdio::null())
+			.spawn()
+			.unwrap_or_else(|e| panic!("Failed to spawn the development node: {e}"));
+		Self { rpc_port, process }
+	}
+
+	/// Wait until the node serves RPC requests, failing after `timeout`.
+	pub fn wait_for_rpc(&self, timeout: Duration) {
+		let deadline = Instant::now() + timeout;
+		while self.rpc("system_health", json!([])).is_err() {
+			assert!(Instant::now() < deadline, "The node did not serve RPC within {timeout:?}");
+			thread::sleep(Duration::from_millis(500));
+		}
+	}
+
+	/// Send SIGTERM to the node and return its exit status along with the time it took to exit,
+	/// failing if it did not exit within `timeout`.
+	pub fn terminate(&mut self, timeout: Duration) -> (ExitStatus, Duration) {
+		let pid = self.process.id().to_string();
+		output(Command::new("kill").args(["-TERM", &pid]));
+		let start = Instant::now();
+		loop {
+			if let Some(status) = self.process.try_wait().unwrap() {
+				return (status, start.elapsed());
+			}
+			assert!(start.elapsed() < timeout, "The node did not exit within {timeout:?}");
+			thread::sleep(Duration::from_millis(100));
+		}
+	}
+}
+
+impl Drop for Node {
+	fn drop(&mut self) {
+		let _ = self.process.kill();
+		let _ = self.process.wait();
+	}
+}
+
+/// A local network of two relay chain validators and two collators, Alice and Bob, with the
+/// parachain registered on the relay chain at genesis.
+///
+/// The nodes are killed and their data is removed when the network is dropped, except for the
+/// logs of a failed test, which are kept in the directory of the network.
+pub struct Network {
+	dir: PathBuf,
+	/// The collators, Alice and Bob.
+	pub collators: Vec<Node>,
+	validators: Vec<Node>,
+}
+
+impl Network {
+	/// Launch a network of the parachain of the chain specification `chain` of the node, e.g.
+	/// `testnet-local`, whose collators must be Alice and Bob.
+	pub fn launch(chain: &str) -> Self {
+		Self::launch_with_args(chain, &[])
+	}
+
+	/// Launch a network like [`Network::launch`], passing `collator_args` to the collators.
+	pub fn launch_with_args(chain: &str, collator_args: &[&str]) -> Self {
+		let dir = std::env::temp_dir().join(format!("pop-network-{chain}-{}", std::process::id()));
+		let _ = fs::remove_dir_all(&dir);
+		fs::create_dir_all(&dir).unwrap();
+		let para_spec = dir.join("parachain.json");
+		let spec = output(Command::new(POP_NODE).args(["build-spec", "--chain", chain]));
+		fs::write(&para_spec, spec).unwrap();
+		let relay_spec = relay_spec(&dir, &para_spec);
+		let mut network = Self { dir, collators: vec![], validators: vec![] };
+
+		let mut bootnode: Option<String> = None;
+		for name in NAMES {
+			let mut args = vec![
+				"--chain".into(),
+				path_arg(&relay_spec),
+				format!("--{name}"),
+				"--insecure-validator-i-know-what-i-do".into(),
+			];
+			args.extend(bootnode.iter().flat_map(|b| ["--bootnodes".into(), b.clone()]));
+			let (validator, address) = network.spawn(&polkadot(), &format!("relay-{name}"), args);
+			bootnode.get_or_insert(address);
+			network.validators.push(validator);
+		}
+
+		let relay_bootnode = bootnode.take().expect("the network has validators; qed");
+		for name in NAMES {
+			let mut args = vec![
+				"--chain".into(),
+				path_arg(&para_spec),
+				"--collator".into(),
+				format!("--{name}"),
+			];
+			args.extend(collator_args.iter().map(|arg| arg.to_string()));
+			args.extend(bootnode.iter().flat_map(|b| ["--bootnodes".into(), b.clone()]));
+			// The arguments of the relay chain node embedded in the collator.
+			args.extend([
+				"--".into(),
+				"--chain".into(),
+				path_arg(&relay_spec),
+				"--port".into(),
+				free_port().to_string(),
+				"--rpc-port".into(),
+				free_port().to_string(),
+				"--bootnodes".into(),
+				relay_bootnode.clone(),
+			]);
+			let (collator, address) = network.spawn(Path::new(POP_NODE), name, args);
+			bootnode.get_or_insert(address);
+			network.collators.push(collator);
+		}
+		network
+	}
+
+	/// Spawn the node `program` as `name` with `args`, returning the node along with its address
+	/// to be used as bootnode by the other nodes.
+	fn spawn(&self, program: &Path, name: &str, args: Vec<String>) -> (Node, String) {
+		let (port, rpc_port) = (free_port(), free_port());
+		let node_key = self.dir.join(format!("{name}.key"));
+		let peer_id = output(Command::new(POP_NODE).args([
+			"generate-node-key",
+			"--from-seed",
+			name,
+			"--file",
+			&path_arg(&node_key),
+		]));
+		let peer_id = String::from_utf8(peer_id).unwrap().trim().to_string();
+		let log = File::create(self.dir.join(format!("{name}.log"))).unwrap();
+		let mut command = Command::new(program);
+		command
+			.args(["--base-path", &path_arg(&self.dir.join(name))])
+			.args(["--port", &port.to_string(), "--rpc-port", &rpc_port.to_string()])
+			.args(["--node-key-file", &path_arg(&node_key)])
+			.args(["--no-prometheus", "--no-telemetry", "--no-hardware-benchmarks", "--no-mdns"])
+			.args(args)
+			.stdout(log.try_clone().unwrap())
+			.stderr(log);
+		let process = command.spawn().unwrap_or_else(|e| panic!("Failed to spawn {name}: {e}"));
+		(Node { rpc_port, process }, format!("/ip4/127.0.0.1/tcp/{port}/p2p/{peer_id}"))
+	}
+
+	/// Wait until all collators have a best block past `number`, failing after `timeout`.
+	pub fn wait_for_best_block(&self, number: u64, timeout: Duration) {
+		let deadline = Instant::now() + timeout;
+		while !self.collators.iter().all(|c| c.best_block().is_ok_and(|best| best > number)) {
+			assert!(
+				Instant::now() < deadline,
+				"The collators did not produce block {number} within {timeout:?}"
+			);
+			thread::sleep(Duration::from_secs(1));
+		}
+	}
+
+	/// Shut the collator `index` down gracefully and return its logs.
+	pub fn stop_collator(&mut self, index: usize) -> String {
+		self.collators[index].stop();
+		fs::read_to_string(self.dir.join(format!("{}.log", NAMES[index]))).unwrap()
+	}
+
+	/// Run the `subcommand` of the node with `args` on the database of the collator `index`, which
+	/// must be stopped, returning whether it succeeded along with its standard output and error.
+	pub fn run_on_collator(&self, index: usize, subcommand: &str, args: &[&str]) -> (bool, String) {
+		let output = Command::new(POP_NODE)
+			.arg(subcommand)
+			.args(args)
+			.args(["--chain", &path_arg(&self.dir.join("parachain.json"))])
+			.args(["--base-path", &path_arg(&self.dir.join(NAMES[index]))])
+			.stdin(Stdio::null())
+			.output()
+			.unwrap_or_else(|e| panic!("Failed to run {subcommand}: {e}"));
+		let out = [output.stdout, output.stderr].concat();
+		(output.status.success(), String::from_utf8_lossy(&out).into_owned())
+	}
+}
+
+impl Drop for Network {
+	fn drop(&mut self) {
+		// The nodes are killed before their data is removed.
+		self.collators.clear();
+		self.validators.clear();
+		if thread::panicking() {
+			eprintln!("The logs of the nodes are kept in {}", self.dir.display());
+		} else {
+			let _ = fs::remove_dir_all(&self.dir);
+		}
+	}
+}
+
+/// Build the raw specification of the relay chain, with the parachain of the specification at
+/// `para_spec` registered at genesis.
+fn relay_spec(dir: &Path, para_spec: &Path) -> PathBuf {
+	let export = |command: &str| {
+		let output =
+			output(Command::new(POP_NODE).args([command, "--chain", &path_arg(para_spec)]));
+		String::from_utf8(output).unwrap().trim().to_string()
+	};
+	let para: Value = serde_json::from_slice(&fs::read(para_spec).unwrap()).unwrap();
+	let paras = json!([[
+		para["para_id"],
+		{
+			"genesis_head": export("export-genesis-head"),
+			"validation_code": export("export-genesis-wasm"),
+			"para_kind": true,
+		}
+	]]);
+
+	let spec = output(Command::new(polkadot()).args(["build-spec", "--chain", RELAY_CHAIN]));
+	let mut spec: Value = serde_json::from_slice(&spec).unwrap();
+	let genesis = &mut spec["genesis"]["runtimeGenesis"];
+	let config = if genesis.get("patch").is_some() { "patch" } else { "config" };
+	genesis[config]["paras"]["paras"] = paras;
+	let plain = dir.join("relay-plain.json");
+	fs::write(&plain, spec.to_string()).unwrap();
+
+	let raw = dir.join("relay.json");
+	let spec = output(Command::new(polkadot()).args([
+		"build-spec",
+		"--chain",
+		&path_arg(&plain),
+		"--raw",
+	]));
+	fs::write(&raw, spec).unwrap();
+	raw
+}
+
+/// The `polkadot` binary running the relay chain.
+fn polkadot() -> PathBuf {
+	std::env::var_os("POLKADOT_BINARY").map_or_else(|| "polkadot".into(), Into::into)
+}
+
+/// Run `command` to completion and return its standard output.
+fn output(command: &mut Command) -> Vec<u8> {
+	let output = command
+		.stdin(Stdio::null())
+		.output()
+		.unwrap_or_else(|e| panic!("Failed to run {command:?}: {e}"));
+	assert!(
+		output.status.success(),
+		"{command:?} failed: {}",
+		String::from_utf8_lossy(&output.stderr)
+	);
+	output.stdout
+}
+
+/// A port which is free to listen on.
+fn free_port() -> u16 {
+	TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port()
+}
+
+/// `path` as command line argument.
+fn path_arg(path: &Path) -> String {
+	path.to_str().expect("temporary paths are valid unicode").into()
+}
@@ -0,0 +1,23 @@
+//! Test of `--shutdown-grace-secs`: a development node shuts down cleanly within its grace period
+//! on SIGTERM, rather than being terminated by the watchdog.
+//!
+//! Unlike the other tests, it requires no `polkadot` binary, as development nodes run without any
+//! relay chain.
+
+mod common;
+
+use std::time::Duration;
+
+use common::Node;
+
+#[test]
+fn dev_node_shuts_down_within_the_grace_period() {
+	let grace = Duration::from_secs(10);
+	let mut node = Node::launch_dev(&["--shutdown-grace-secs", &grace.as_secs().to_string()]);
+	node.wait_for_rpc(Duration::from_secs(120));
+
+	// The watchdog would exit with the code of a timeout after the grace period.
+	let (status, elapsed) = node.terminate(grace * 2);
+	assert!(status.success(), "the node exited with {status}");
+	assert!(elapsed < grace, "the node took {elapsed:?} to shut down");
+}
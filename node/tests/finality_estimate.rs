@@ -0,0 +1,23 @@
+//! Test of the `pop_finalityEstimate` RPC against the best block of a running local testnet.
+//!
+//! Requires the `test-helpers` feature and a `polkadot` binary, see [`common`].
+
+mod common;
+
+use std::time::Duration;
+
+use common::Network;
+use serde_json::json;
+
+#[test]
+fn best_block_has_positive_finality_estimate() {
+	let network = Network::launch("testnet-local");
+	network.wait_for_best_block(3, Duration::from_secs(300));
+
+	let alice = &network.collators[0];
+	let best = alice.block_hash(alice.best_block().unwrap()).unwrap();
+	let estimate = alice.rpc("pop_finalityEstimate", json!([best])).unwrap();
+	assert_eq!(estimate["finalized"], false, "{estimate}");
+	assert!(estimate["blocks"].as_u64().unwrap() > 0, "{estimate}");
+	assert!(estimate["seconds"].as_u64().unwrap() > 0, "{estimate}");
+}
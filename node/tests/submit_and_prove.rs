@@ -0,0 +1,74 @@
+//! Test of `pop_submitAndProve`: the proof returned for a signed extrinsic submitted to a
+//! development node verifies against the extrinsics root of the header of the block it was
+//! included in.
+//!
+//! Like the shutdown test, it requires no `polkadot` binary.
+
+mod common;
+
+use std::time::Duration;
+
+use codec::{Compact, Decode, Encode};
+use common::Node;
+use pop_runtime_common::{Hash, Nonce};
+use pop_runtime_devnet::{RuntimeCall, SignedExtra, UncheckedExtrinsic, VERSION};
+use serde_json::{json, Value};
+use sp_core::{sr25519, Bytes, Pair};
+use sp_runtime::{generic::SignedPayload, traits::BlakeTwo256, MultiAddress};
+use sp_trie::{verify_trie_proof, LayoutV0, TrieConfiguration};
+
+/// A remark of Alice with `nonce`, immortal on the chain of the genesis block `genesis`.
+fn remark(genesis: Hash, nonce: Nonce) -> Vec<u8> {
+	let call = RuntimeCall::System(frame_system::Call::remark { remark: b"Hello Pop".to_vec() });
+	// Immortal, with the nonce, no tip and without a metadata hash.
+	let extra = SignedExtra::decode(&mut &(0u8, Compact(nonce), [0u8; 2]).encode()[..]).unwrap();
+	let additional = (
+		(),
+		VERSION.spec_version,
+		VERSION.transaction_version,
+		genesis,
+		genesis,
+		(),
+		(),
+		(),
+		(),
+		None,
+	);
+	let payload = SignedPayload::from_raw(call.clone(), extra.clone(), additional);
+	let alice = sr25519::Pair::from_string("//Alice", None).unwrap();
+	let signature = payload.using_encoded(|payload| alice.sign(payload));
+	UncheckedExtrinsic::new_signed(
+		call,
+		MultiAddress::Id(alice.public().into()),
+		signature.into(),
+		extra,
+	)
+	.encode()
+}
+
+fn from_value<T: serde::de::DeserializeOwned>(value: &Value) -> T {
+	serde_json::from_value(value.clone()).unwrap_or_else(|e| panic!("{e}: {value}"))
+}
+
+#[test]
+fn inclusion_proof_verifies_against_the_header() {
+	let node = Node::launch_dev(&[]);
+	node.wait_for_rpc(Duration::from_secs(120));
+	let genesis: Hash = from_value(&node.block_hash(0).unwrap());
+
+	let extrinsic = remark(genesis, 0);
+	let inclusion = node.rpc("pop_submitAndProve", json!([Bytes(extrinsic.clone())])).unwrap();
+	let header = node.rpc("chain_getHeader", json!([inclusion["block_hash"]])).unwrap();
+	let root: Hash = from_value(&header["extrinsicsRoot"]);
+	let index: u32 = from_value(&inclusion["index"]);
+	let proof: Vec<Bytes> = from_value(&inclusion["proof"]);
+	let proof: Vec<Vec<u8>> = proof.into_iter().map(|node| node.0).collect();
+
+	type Layout = LayoutV0<BlakeTwo256>;
+	let key = Layout::encode_index(index);
+	let included = [(key.clone(), Some(extrinsic))];
+	assert!(verify_trie_proof::<Layout, _, _, _>(&root, &proof, &included).is_ok());
+	// The proof does not hold for any other extrinsic at the index.
+	let other = [(key, Some(remark(genesis, 1)))];
+	assert!(verify_trie_proof::<Layout, _, _, _>(&root, &proof, &other).is_err());
+}
@@ -0,0 +1,24 @@
+//! Smoke test of multi-collator consensus: the two collators of a local testnet both produce
+//! blocks and agree on them.
+//!
+//! Requires the `test-helpers` feature and a `polkadot` binary, see [`common`].
+
+mod common;
+
+use std::time::Duration;
+
+use common::Network;
+
+/// The block both collators must have imported.
+const BLOCK: u64 = 5;
+
+#[test]
+fn two_collators_produce_blocks_in_consensus() {
+	let network = Network::launch("testnet-local");
+	network.wait_for_best_block(BLOCK, Duration::from_secs(300));
+
+	let [alice, bob] = &network.collators[..] else {
+		panic!("the network has two collators");
+	};
+	assert_eq!(alice.block_hash(BLOCK).unwrap(), bob.block_hash(BLOCK).unwrap());
+}
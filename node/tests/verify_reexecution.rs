@@ -0,0 +1,26 @@
+//! Test of `verify-reexecution`: the blocks imported by a collator re-execute to their recorded
+//! state roots.
+//!
+//! Requires the `test-helpers` feature and a `polkadot` binary, see [`common`].
+
+mod common;
+
+use std::time::Duration;
+
+use common::Network;
+
+#[test]
+fn imported_blocks_reexecute_to_their_state_roots() {
+	let mut network = Network::launch("testnet-local");
+	network.wait_for_best_block(3, Duration::from_secs(300));
+	network.stop_collator(0);
+
+	let (success, output) = network.run_on_collator(0, "verify-reexecution", &["1", "3"]);
+	assert!(success, "re-execution failed: {output}");
+	assert!(output.contains("Re-executed blocks #1 to #3"), "unexpected output: {output}");
+
+	// Blocks which are not stored cannot be re-executed.
+	let (success, output) = network.run_on_collator(0, "verify-reexecution", &["1", "1000000"]);
+	assert!(!success);
+	assert!(output.contains("Block #1000000 not found"), "unexpected output: {output}");
+}
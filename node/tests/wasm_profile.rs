@@ -0,0 +1,21 @@
+//! Test of `--profile-wasm`: a collator which imported blocks logs the time spent in host
+//! functions when it shuts down.
+//!
+//! Requires the `test-helpers` feature and a `polkadot` binary, see [`common`].
+
+mod common;
+
+use std::time::Duration;
+
+use common::Network;
+
+#[test]
+fn host_function_timings_are_logged_on_shutdown() {
+	let mut network = Network::launch_with_args("testnet-local", &["--profile-wasm"]);
+	network.wait_for_best_block(1, Duration::from_secs(300));
+
+	let logs = network.stop_collator(0);
+	assert!(logs.contains("host functions:"), "no profile was logged");
+	// Every block reads the storage.
+	assert!(logs.contains("ext_storage_get_version_1: "), "storage reads were not profiled");
+}
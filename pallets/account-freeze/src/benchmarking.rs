@@ -0,0 +1,63 @@
+//! Benchmarks of `pallet_account_freeze`.
+
+use frame_benchmarking::v2::*;
+use frame_support::traits::{Currency, EnsureOrigin, Get};
+use sp_runtime::traits::{Bounded, Zero};
+
+use super::*;
+
+type ProxyBalanceOf<T> = <<T as pallet_proxy::Config>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::Balance;
+
+/// An account funded to hold the deposits of `proxies` proxies, which it delegates to.
+fn account_with_proxies<T: Config>(proxies: u32) -> Result<T::AccountId, BenchmarkError> {
+	let frozen: T::AccountId = account("frozen", 0, 0);
+	<T as pallet_proxy::Config>::Currency::make_free_balance_be(
+		&frozen,
+		ProxyBalanceOf::<T>::max_value() / 2u32.into(),
+	);
+	for i in 0..proxies {
+		pallet_proxy::Pallet::<T>::add_proxy_delegate(
+			&frozen,
+			account("delegate", i, 0),
+			T::ProxyType::default(),
+			Zero::zero(),
+		)?;
+	}
+	Ok(frozen)
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn freeze_account(p: Linear<0, { T::MaxProxies::get() }>) -> Result<(), BenchmarkError> {
+		let origin =
+			T::FreezeOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let account = account_with_proxies::<T>(p)?;
+
+		#[extrinsic_call]
+		_(origin as <T as frame_system::Config>::RuntimeOrigin, account.clone());
+
+		assert!(Pallet::<T>::is_frozen(&account));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn unfreeze_account() -> Result<(), BenchmarkError> {
+		let origin =
+			T::FreezeOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let account = account_with_proxies::<T>(0)?;
+		Pallet::<T>::freeze_account(origin.clone(), account.clone())?;
+
+		#[extrinsic_call]
+		_(origin as <T as frame_system::Config>::RuntimeOrigin, account.clone());
+
+		assert!(!Pallet::<T>::is_frozen(&account));
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,189 @@
+//! Allows specific accounts to be frozen, e.g. for compliance on permissioned deployments, so that
+//! they can neither transfer funds nor call contracts until they are unfrozen.
+//!
+//! The transactions signed by frozen accounts are rejected by [`CheckNotFrozen`],
+//! as the `BaseCallFilter` of the runtime cannot tell which account dispatches a call. Freezing an
+//! account also removes its proxies, so that its delegates can no longer dispatch calls on its
+//! behalf. As frozen accounts may still be dispatched as by other means, e.g. multisig accounts via
+//! `multisig.as_multi`, contracts by their callers or sovereign accounts via XCM, their funds are
+//! frozen too: the native tokens by a freeze of `Fungible`, which no transfer or withdrawal can
+//! bypass, and the assets via the `Freezer` of `pallet_assets`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use core::{fmt, marker::PhantomData};
+
+	use frame_support::{
+		dispatch::DispatchInfo,
+		pallet_prelude::*,
+		traits::fungible::{Inspect, MutateFreeze},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{Bounded, DispatchInfoOf, Dispatchable, SignedExtension};
+
+	use super::WeightInfo;
+
+	type BalanceOf<T> =
+		<<T as Config>::Fungible as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_proxy::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The origin allowed to freeze and unfreeze accounts.
+		type FreezeOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// The native currency, whose balances of frozen accounts are frozen.
+		type Fungible: MutateFreeze<Self::AccountId, Id = Self::RuntimeFreezeReason>;
+		/// The overarching freeze reason.
+		type RuntimeFreezeReason: From<FreezeReason>;
+		/// The weights of the calls of the pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// A reason for the pallet freezing funds.
+	#[pallet::composite_enum]
+	pub enum FreezeReason {
+		/// The native tokens of a frozen account.
+		AccountFrozen,
+	}
+
+	/// The frozen accounts.
+	#[pallet::storage]
+	pub type FrozenAccounts<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, ()>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The account was frozen.
+		AccountFrozen { account: T::AccountId },
+		/// The account was unfrozen.
+		AccountUnfrozen { account: T::AccountId },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account is already frozen.
+		AlreadyFrozen,
+		/// The account is not frozen.
+		NotFrozen,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Freeze `account` along with its native tokens, removing its proxies.
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as Config>::WeightInfo::freeze_account(T::MaxProxies::get()))]
+		pub fn freeze_account(origin: OriginFor<T>, account: T::AccountId) -> DispatchResult {
+			T::FreezeOrigin::ensure_origin(origin)?;
+			ensure!(!FrozenAccounts::<T>::contains_key(&account), Error::<T>::AlreadyFrozen);
+			// Frozen beyond what the account holds, so that the tokens it receives are frozen too.
+			T::Fungible::set_freeze(
+				&FreezeReason::AccountFrozen.into(),
+				&account,
+				BalanceOf::<T>::max_value(),
+			)?;
+			FrozenAccounts::<T>::insert(&account, ());
+			pallet_proxy::Pallet::<T>::remove_all_proxy_delegates(&account);
+			Self::deposit_event(Event::AccountFrozen { account });
+			Ok(())
+		}
+
+		/// Unfreeze `account` along with its native tokens. Its removed proxies are not restored.
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as Config>::WeightInfo::unfreeze_account())]
+		pub fn unfreeze_account(origin: OriginFor<T>, account: T::AccountId) -> DispatchResult {
+			T::FreezeOrigin::ensure_origin(origin)?;
+			ensure!(FrozenAccounts::<T>::take(&account).is_some(), Error::<T>::NotFrozen);
+			T::Fungible::thaw(&FreezeReason::AccountFrozen.into(), &account)?;
+			Self::deposit_event(Event::AccountUnfrozen { account });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Whether `account` is frozen.
+		pub fn is_frozen(account: &T::AccountId) -> bool {
+			FrozenAccounts::<T>::contains_key(account)
+		}
+	}
+
+	/// Rejects the transactions signed by frozen accounts.
+	#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	pub struct CheckNotFrozen<T>(PhantomData<T>);
+
+	impl<T> CheckNotFrozen<T> {
+		/// Create new `CheckNotFrozen`.
+		pub fn new() -> Self {
+			Self(PhantomData)
+		}
+	}
+
+	impl<T> Default for CheckNotFrozen<T> {
+		fn default() -> Self {
+			Self::new()
+		}
+	}
+
+	impl<T> fmt::Debug for CheckNotFrozen<T> {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			write!(f, "CheckNotFrozen")
+		}
+	}
+
+	impl<T> SignedExtension for CheckNotFrozen<T>
+	where
+		T: Config + Clone + Eq + Send + Sync,
+		<T as frame_system::Config>::RuntimeCall: Dispatchable<Info = DispatchInfo>,
+	{
+		type AccountId = T::AccountId;
+		type AdditionalSigned = ();
+		type Call = <T as frame_system::Config>::RuntimeCall;
+		type Pre = ();
+
+		const IDENTIFIER: &'static str = "CheckNotFrozen";
+
+		fn additional_signed(&self) -> Result<Self::AdditionalSigned, TransactionValidityError> {
+			Ok(())
+		}
+
+		fn validate(
+			&self,
+			who: &Self::AccountId,
+			_call: &Self::Call,
+			_info: &DispatchInfoOf<Self::Call>,
+			_len: usize,
+		) -> TransactionValidity {
+			if Pallet::<T>::is_frozen(who) {
+				return Err(InvalidTransaction::BadSigner.into());
+			}
+			Ok(ValidTransaction::default())
+		}
+
+		fn pre_dispatch(
+			self,
+			who: &Self::AccountId,
+			call: &Self::Call,
+			info: &DispatchInfoOf<Self::Call>,
+			len: usize,
+		) -> Result<Self::Pre, TransactionValidityError> {
+			self.validate(who, call, info, len).map(|_| ())
+		}
+	}
+}
@@ -0,0 +1,100 @@
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::DispatchInfo,
+	traits::{fungible::Mutate, tokens::Preservation},
+};
+use sp_runtime::{
+	traits::SignedExtension,
+	transaction_validity::{InvalidTransaction, TransactionValidityError},
+	DispatchError, TokenError,
+};
+
+use crate::{mock::*, CheckNotFrozen, Error, Event, FrozenAccounts};
+
+fn transfer(from: u64, to: u64, amount: u64) -> Result<u64, DispatchError> {
+	<Balances as Mutate<u64>>::transfer(&from, &to, amount, Preservation::Expendable)
+}
+
+#[test]
+fn frozen_accounts_cannot_move_their_funds() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AccountFreeze::freeze_account(RuntimeOrigin::root(), ALICE));
+		assert!(FrozenAccounts::<Test>::contains_key(ALICE));
+		System::assert_last_event(Event::AccountFrozen { account: ALICE }.into());
+		assert_noop!(transfer(ALICE, BOB, 1), TokenError::Frozen);
+
+		// The tokens received while frozen are frozen too.
+		assert_ok!(transfer(BOB, ALICE, 10));
+		assert_noop!(transfer(ALICE, BOB, 1), TokenError::Frozen);
+
+		assert_ok!(AccountFreeze::unfreeze_account(RuntimeOrigin::root(), ALICE));
+		assert!(!FrozenAccounts::<Test>::contains_key(ALICE));
+		System::assert_last_event(Event::AccountUnfrozen { account: ALICE }.into());
+		assert_ok!(transfer(ALICE, BOB, 110));
+	});
+}
+
+#[test]
+fn freezing_removes_proxies() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Proxy::add_proxy(RuntimeOrigin::signed(ALICE), BOB, (), 0));
+		assert_eq!(Proxy::proxies(ALICE).0.len(), 1);
+
+		assert_ok!(AccountFreeze::freeze_account(RuntimeOrigin::root(), ALICE));
+		assert!(Proxy::proxies(ALICE).0.is_empty());
+		// Nor are they restored once unfrozen.
+		assert_ok!(AccountFreeze::unfreeze_account(RuntimeOrigin::root(), ALICE));
+		assert!(Proxy::proxies(ALICE).0.is_empty());
+	});
+}
+
+#[test]
+fn accounts_are_frozen_and_unfrozen_once() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AccountFreeze::unfreeze_account(RuntimeOrigin::root(), ALICE),
+			Error::<Test>::NotFrozen
+		);
+		assert_ok!(AccountFreeze::freeze_account(RuntimeOrigin::root(), ALICE));
+		assert_noop!(
+			AccountFreeze::freeze_account(RuntimeOrigin::root(), ALICE),
+			Error::<Test>::AlreadyFrozen
+		);
+	});
+}
+
+#[test]
+fn only_freeze_origin_freezes_accounts() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AccountFreeze::freeze_account(RuntimeOrigin::signed(BOB), ALICE),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(AccountFreeze::freeze_account(RuntimeOrigin::root(), ALICE));
+		assert_noop!(
+			AccountFreeze::unfreeze_account(RuntimeOrigin::signed(ALICE), ALICE),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn transactions_of_frozen_accounts_are_rejected() {
+	let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+	let info = DispatchInfo::default();
+	new_test_ext().execute_with(|| {
+		assert_ok!(CheckNotFrozen::<Test>::new().validate(&ALICE, &call, &info, 0));
+		assert_ok!(AccountFreeze::freeze_account(RuntimeOrigin::root(), ALICE));
+
+		let bad_signer = TransactionValidityError::Invalid(InvalidTransaction::BadSigner);
+		assert_eq!(
+			CheckNotFrozen::<Test>::new().validate(&ALICE, &call, &info, 0),
+			Err(bad_signer)
+		);
+		assert_eq!(
+			CheckNotFrozen::<Test>::new().pre_dispatch(&ALICE, &call, &info, 0),
+			Err(bad_signer)
+		);
+		assert_ok!(CheckNotFrozen::<Test>::new().pre_dispatch(&BOB, &call, &info, 0));
+	});
+}
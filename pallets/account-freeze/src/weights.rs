@@ -0,0 +1,54 @@
+//! Weights for `pallet_account_freeze`.
+//!
+//! Not measured: these are estimates written by hand from the storage each call accesses, to be
+//! replaced with the weights generated on the reference hardware by
+//! `scripts/benchmark-pallets.sh account-freeze` before relying on them on a live chain.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use core::marker::PhantomData;
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions needed for `pallet_account_freeze`.
+pub trait WeightInfo {
+	fn freeze_account(p: u32) -> Weight;
+	fn unfreeze_account() -> Weight;
+}
+
+/// Estimated weights for `pallet_account_freeze`.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn freeze_account(p: u32) -> Weight {
+		Weight::from_parts(45_000_000, 4_764)
+			.saturating_add(Weight::from_parts(60_000, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	fn unfreeze_account() -> Weight {
+		Weight::from_parts(35_000_000, 4_764)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	fn freeze_account(p: u32) -> Weight {
+		Weight::from_parts(45_000_000, 4_764)
+			.saturating_add(Weight::from_parts(60_000, 0).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	fn unfreeze_account() -> Weight {
+		Weight::from_parts(35_000_000, 4_764)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+}
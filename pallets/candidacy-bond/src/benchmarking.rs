@@ -0,0 +1,56 @@
+//! Benchmarks of `pallet_candidacy_bond`.
+
+use frame_benchmarking::v2::*;
+use frame_support::traits::{
+	schedule::{v3::Named, DispatchTime, LOWEST_PRIORITY},
+	EnsureOrigin, EstimateNextSessionRotation, Get, StorePreimage,
+};
+use frame_system::RawOrigin;
+
+use super::*;
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	// The change is scheduled at a block whose agenda is nearly full.
+	#[benchmark]
+	fn schedule_candidacy_bond(
+		s: Linear<0, { <T as pallet_scheduler::Config>::MaxScheduledPerBlock::get() - 1 }>,
+	) -> Result<(), BenchmarkError> {
+		let origin =
+			T::BondChangeOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let bond = 1_000u32.into();
+		// Sessions are estimated to start at genesis until the first block.
+		frame_system::Pallet::<T>::set_block_number(1u32.into());
+		let session = pallet_session::CurrentIndex::<T>::get() + 1;
+		let (at, _) = T::NextSessionRotation::estimate_next_session_rotation(
+			frame_system::Pallet::<T>::block_number(),
+		);
+		let at = at.ok_or(BenchmarkError::Weightless)?;
+		for i in 0..s {
+			// Named after later sessions, so that they do not clash with the change benchmarked.
+			let call: <T as pallet_scheduler::Config>::RuntimeCall =
+				<T as Config>::RuntimeCall::from(Call::<T>::apply_candidacy_bond {
+					bond,
+					session: session + 1 + i,
+				})
+				.into();
+			<pallet_scheduler::Pallet<T> as Named<_, _, _>>::schedule_named(
+				task_name(session + 1 + i),
+				DispatchTime::At(at),
+				None,
+				LOWEST_PRIORITY,
+				RawOrigin::Root.into(),
+				<T as pallet_scheduler::Config>::Preimages::bound(call)?,
+			)?;
+		}
+
+		#[extrinsic_call]
+		_(origin as <T as frame_system::Config>::RuntimeOrigin, bond, session);
+
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,152 @@
+//! Schedules changes of the candidacy bond of `pallet_collator_selection` to be applied as a
+//! future session starts, so that candidates are given notice to update their bonds rather than
+//! being kicked by an abrupt change.
+//!
+//! The changes are applied by tasks of `pallet_scheduler`, at most one per session, named by
+//! [`task_name`]. A scheduled change can thus be cancelled via
+//! `scheduler.cancel_named`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use codec::Encode;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{
+			schedule::{
+				v3::{Named, TaskName},
+				DispatchTime, HIGHEST_PRIORITY,
+			},
+			Currency, EstimateNextSessionRotation, StorePreimage,
+		},
+	};
+	use frame_system::pallet_prelude::*;
+	use pallet_collator_selection::WeightInfo as _;
+	use sp_core::hashing::blake2_256;
+	use sp_runtime::traits::Saturating;
+
+	use super::WeightInfo;
+
+	type BalanceOf<T> = <<T as pallet_collator_selection::Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::Balance;
+
+	/// The name of the scheduler task applying the candidacy bond change at `session`.
+	pub fn task_name(session: u32) -> TaskName {
+		(b"pop/candidacy-bond", session).using_encoded(blake2_256)
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config:
+		frame_system::Config
+		+ pallet_collator_selection::Config
+		+ pallet_session::Config
+		+ pallet_scheduler::Config
+	{
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The overarching call type, as scheduled.
+		type RuntimeCall: From<Call<Self>> + IsType<<Self as pallet_scheduler::Config>::RuntimeCall>;
+		/// The origin which may schedule candidacy bond changes.
+		type BondChangeOrigin: EnsureOrigin<<Self as frame_system::Config>::RuntimeOrigin>;
+		/// The weights of the calls of the pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The candidacy bond is changed to `bond` at block `at`, as `session` starts.
+		CandidacyBondChangeScheduled { bond: BalanceOf<T>, session: u32, at: BlockNumberFor<T> },
+		/// The candidacy bond was changed to `bond` as `session` started.
+		CandidacyBondChangeApplied { bond: BalanceOf<T>, session: u32 },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The session of the change has already started.
+		SessionNotInFuture,
+		/// When the session starts is unknown.
+		UnknownSessionStart,
+		/// A change is already scheduled for the session.
+		AlreadyScheduled,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Schedule the candidacy bond to be changed to `bond` as `session` starts.
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as Config>::WeightInfo::schedule_candidacy_bond(
+			<T as pallet_scheduler::Config>::MaxScheduledPerBlock::get().saturating_sub(1),
+		))]
+		pub fn schedule_candidacy_bond(
+			origin: OriginFor<T>,
+			bond: BalanceOf<T>,
+			session: u32,
+		) -> DispatchResult {
+			T::BondChangeOrigin::ensure_origin(origin)?;
+			let current = pallet_session::CurrentIndex::<T>::get();
+			ensure!(session > current, Error::<T>::SessionNotInFuture);
+			let now = frame_system::Pallet::<T>::block_number();
+			let (next, _) = T::NextSessionRotation::estimate_next_session_rotation(now);
+			let sessions_after_next: BlockNumberFor<T> = (session - current - 1).into();
+			let at = next.ok_or(Error::<T>::UnknownSessionStart)?.saturating_add(
+				T::NextSessionRotation::average_session_length()
+					.saturating_mul(sessions_after_next),
+			);
+
+			let call: <T as pallet_scheduler::Config>::RuntimeCall =
+				<T as Config>::RuntimeCall::from(Call::<T>::apply_candidacy_bond { bond, session })
+					.into();
+			let call = <T as pallet_scheduler::Config>::Preimages::bound(call)?;
+			<pallet_scheduler::Pallet<T> as Named<_, _, _>>::schedule_named(
+				task_name(session),
+				DispatchTime::At(at),
+				None,
+				HIGHEST_PRIORITY,
+				frame_system::RawOrigin::Root.into(),
+				call,
+			)
+			.map_err(|_| Error::<T>::AlreadyScheduled)?;
+			Self::deposit_event(Event::CandidacyBondChangeScheduled { bond, session, at });
+			Ok(())
+		}
+
+		/// Change the candidacy bond to `bond`, as scheduled for `session`. Only dispatched by the
+		/// scheduler.
+		#[pallet::call_index(1)]
+		// Weighs as the change of the bond by the collator selection, which it dispatches.
+		#[pallet::weight(
+			<T as pallet_collator_selection::Config>::WeightInfo::set_candidacy_bond(
+				T::MaxCandidates::get(),
+				T::MaxCandidates::get(),
+			)
+		)]
+		pub fn apply_candidacy_bond(
+			origin: OriginFor<T>,
+			bond: BalanceOf<T>,
+			session: u32,
+		) -> DispatchResult {
+			ensure_root(origin.clone())?;
+			pallet_collator_selection::Pallet::<T>::set_candidacy_bond(origin, bond)
+				.map_err(|e| e.error)?;
+			Self::deposit_event(Event::CandidacyBondChangeApplied { bond, session });
+			Ok(())
+		}
+	}
+}
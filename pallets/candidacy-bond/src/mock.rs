@@ -0,0 +1,147 @@
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{ConstU32, ConstU64, EqualPrivilegeOnly},
+	weights::Weight,
+	PalletId,
+};
+use frame_system::EnsureRoot;
+use pallet_collator_selection::IdentityCollator;
+use sp_runtime::{
+	impl_opaque_keys,
+	testing::UintAuthorityId,
+	traits::{ConvertInto, OpaqueKeys},
+	BuildStorage, KeyTypeId, RuntimeAppPublic,
+};
+
+use crate as pallet_candidacy_bond;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub(crate) const INVULNERABLE: u64 = 1;
+pub(crate) const ALICE: u64 = 2;
+pub(crate) const OLD_BOND: u64 = 100;
+
+frame_support::construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		Balances: pallet_balances,
+		CollatorSelection: pallet_collator_selection,
+		Session: pallet_session,
+		Preimage: pallet_preimage,
+		Scheduler: pallet_scheduler,
+		CandidacyBond: pallet_candidacy_bond,
+	}
+);
+
+impl_opaque_keys! {
+	pub struct SessionKeys {
+		pub aura: UintAuthorityId,
+	}
+}
+
+parameter_types! {
+	pub const Period: u64 = 10;
+	pub const PotId: PalletId = PalletId(*b"PotStake");
+	pub MaximumSchedulerWeight: Weight = Weight::MAX;
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type AccountData = pallet_balances::AccountData<u64>;
+	type Block = Block;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type AccountStore = System;
+}
+
+impl pallet_collator_selection::Config for Test {
+	type Currency = Balances;
+	type KickThreshold = Period;
+	type MaxCandidates = ConstU32<4>;
+	type MaxInvulnerables = ConstU32<4>;
+	type MinEligibleCollators = ConstU32<1>;
+	type PotId = PotId;
+	type RuntimeEvent = RuntimeEvent;
+	type UpdateOrigin = EnsureRoot<u64>;
+	type ValidatorId = u64;
+	type ValidatorIdOf = IdentityCollator;
+	type ValidatorRegistration = Session;
+	type WeightInfo = ();
+}
+
+pub struct TestSessionHandler;
+
+impl pallet_session::SessionHandler<u64> for TestSessionHandler {
+	const KEY_TYPE_IDS: &'static [KeyTypeId] = &[UintAuthorityId::ID];
+
+	fn on_genesis_session<Ks: OpaqueKeys>(_: &[(u64, Ks)]) {}
+
+	fn on_new_session<Ks: OpaqueKeys>(_: bool, _: &[(u64, Ks)], _: &[(u64, Ks)]) {}
+
+	fn on_disabled(_: u32) {}
+}
+
+impl pallet_session::Config for Test {
+	type Keys = SessionKeys;
+	type NextSessionRotation = pallet_session::PeriodicSessions<Period, ConstU64<0>>;
+	type RuntimeEvent = RuntimeEvent;
+	type SessionHandler = TestSessionHandler;
+	type SessionManager = CollatorSelection;
+	type ShouldEndSession = pallet_session::PeriodicSessions<Period, ConstU64<0>>;
+	type ValidatorId = u64;
+	type ValidatorIdOf = ConvertInto;
+	type WeightInfo = ();
+}
+
+impl pallet_preimage::Config for Test {
+	type Consideration = ();
+	type Currency = Balances;
+	type ManagerOrigin = EnsureRoot<u64>;
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+}
+
+impl pallet_scheduler::Config for Test {
+	type MaxScheduledPerBlock = ConstU32<10>;
+	type MaximumWeight = MaximumSchedulerWeight;
+	type OriginPrivilegeCmp = EqualPrivilegeOnly;
+	type PalletsOrigin = OriginCaller;
+	type Preimages = Preimage;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeOrigin = RuntimeOrigin;
+	type ScheduleOrigin = EnsureRoot<u64>;
+	type WeightInfo = ();
+}
+
+impl pallet_candidacy_bond::Config for Test {
+	type BondChangeOrigin = EnsureRoot<u64>;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+}
+
+pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_collator_selection::GenesisConfig::<Test> {
+		invulnerables: vec![INVULNERABLE],
+		candidacy_bond: OLD_BOND,
+		desired_candidates: 1,
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+	pallet_session::GenesisConfig::<Test> {
+		keys: vec![(
+			INVULNERABLE,
+			INVULNERABLE,
+			SessionKeys { aura: UintAuthorityId(INVULNERABLE) },
+		)],
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
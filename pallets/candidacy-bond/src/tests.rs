@@ -0,0 +1,76 @@
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use sp_runtime::DispatchError;
+
+use crate::{mock::*, task_name, Error, Event};
+
+const NEW_BOND: u64 = 200;
+
+/// Run the blocks up to `until`, rotating the sessions and dispatching the scheduled calls.
+fn run_to_block(until: u64) {
+	for n in System::block_number() + 1..=until {
+		System::set_block_number(n);
+		Session::on_initialize(n);
+		Scheduler::on_initialize(n);
+	}
+}
+
+#[test]
+fn candidacy_bond_changes_as_scheduled_session_starts() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CandidacyBond::schedule_candidacy_bond(RuntimeOrigin::root(), NEW_BOND, 2));
+		System::assert_last_event(
+			Event::CandidacyBondChangeScheduled { bond: NEW_BOND, session: 2, at: 20 }.into(),
+		);
+
+		run_to_block(19);
+		assert_eq!(Session::current_index(), 1);
+		assert_eq!(pallet_collator_selection::CandidacyBond::<Test>::get(), OLD_BOND);
+		run_to_block(20);
+		assert_eq!(Session::current_index(), 2);
+		assert_eq!(pallet_collator_selection::CandidacyBond::<Test>::get(), NEW_BOND);
+		System::assert_has_event(
+			Event::CandidacyBondChangeApplied { bond: NEW_BOND, session: 2 }.into(),
+		);
+	});
+}
+
+#[test]
+fn changes_are_scheduled_once_per_future_session() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			CandidacyBond::schedule_candidacy_bond(RuntimeOrigin::root(), NEW_BOND, 0),
+			Error::<Test>::SessionNotInFuture
+		);
+		assert_ok!(CandidacyBond::schedule_candidacy_bond(RuntimeOrigin::root(), NEW_BOND, 2));
+		assert_noop!(
+			CandidacyBond::schedule_candidacy_bond(RuntimeOrigin::root(), OLD_BOND, 2),
+			Error::<Test>::AlreadyScheduled
+		);
+	});
+}
+
+#[test]
+fn scheduled_change_can_be_cancelled_by_name() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CandidacyBond::schedule_candidacy_bond(RuntimeOrigin::root(), NEW_BOND, 1));
+		assert_ok!(Scheduler::cancel_named(RuntimeOrigin::root(), task_name(1)));
+
+		run_to_block(10);
+		assert_eq!(Session::current_index(), 1);
+		assert_eq!(pallet_collator_selection::CandidacyBond::<Test>::get(), OLD_BOND);
+	});
+}
+
+#[test]
+fn changes_require_privileged_origins() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			CandidacyBond::schedule_candidacy_bond(RuntimeOrigin::signed(ALICE), NEW_BOND, 2),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			CandidacyBond::apply_candidacy_bond(RuntimeOrigin::signed(ALICE), NEW_BOND, 2),
+			DispatchError::BadOrigin
+		);
+	});
+}
@@ -0,0 +1,43 @@
+//! Weights for `pallet_candidacy_bond`.
+//!
+//! Not measured: these are estimates written by hand from the storage each call accesses, to be
+//! replaced with the weights generated on the reference hardware by
+//! `scripts/benchmark-pallets.sh candidacy-bond` before relying on them on a live chain.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use core::marker::PhantomData;
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions needed for `pallet_candidacy_bond`.
+pub trait WeightInfo {
+	fn schedule_candidacy_bond(s: u32) -> Weight;
+}
+
+/// Estimated weights for `pallet_candidacy_bond`.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn schedule_candidacy_bond(s: u32) -> Weight {
+		Weight::from_parts(25_000_000, 13_928)
+			.saturating_add(Weight::from_parts(400_000, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	fn schedule_candidacy_bond(s: u32) -> Weight {
+		Weight::from_parts(25_000_000, 13_928)
+			.saturating_add(Weight::from_parts(400_000, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+}
@@ -0,0 +1,86 @@
+//! Benchmarks of `pallet_chunked_uploads`.
+
+use frame_benchmarking::v2::*;
+use frame_support::{
+	traits::{
+		fungible::{Inspect, Mutate, MutateHold},
+		EnsureOrigin, Get,
+	},
+	BoundedVec,
+};
+use frame_system::RawOrigin;
+use sp_runtime::Saturating;
+use sp_std::vec;
+
+use super::*;
+
+type BalanceOf<T> =
+	<<T as Config>::Fungible as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// Funds `who` to hold the deposit of the longest code, then hands it a pending upload of `len`
+/// bytes, holding its deposit.
+fn pending_upload<T: Config>(who: &T::AccountId, len: u32) -> Result<(), BenchmarkError>
+where
+	T::Fungible: Mutate<T::AccountId>,
+{
+	let deposit_per_byte: BalanceOf<T> = <T as Config>::DepositPerByte::get();
+	let max_deposit =
+		deposit_per_byte.saturating_mul(<T as pallet_contracts::Config>::MaxCodeLen::get().into());
+	T::Fungible::set_balance(
+		who,
+		T::Fungible::minimum_balance().saturating_add(max_deposit.saturating_mul(2u32.into())),
+	);
+	let code = BoundedVec::try_from(vec![0u8; len as usize])
+		.map_err(|_| BenchmarkError::Stop("the pending upload exceeds the maximum code length"))?;
+	PendingUploads::<T>::insert(who, code);
+	T::Fungible::hold(
+		&HoldReason::PendingUpload.into(),
+		who,
+		deposit_per_byte.saturating_mul(len.into()),
+	)?;
+	Ok(())
+}
+
+#[benchmarks(where T::Fungible: Mutate<T::AccountId>)]
+mod benchmarks {
+	use super::*;
+
+	// The chunk of `c` bytes completes a pending upload of the maximum code length, which is read
+	// in full.
+	#[benchmark]
+	fn upload_chunk(
+		c: Linear<
+			1,
+			{ T::MaxChunkLen::get().min(<T as pallet_contracts::Config>::MaxCodeLen::get()) },
+		>,
+	) -> Result<(), BenchmarkError> {
+		let origin = <T as pallet_contracts::Config>::UploadOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let who = <T as pallet_contracts::Config>::UploadOrigin::ensure_origin(origin.clone())
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let max_code_len = <T as pallet_contracts::Config>::MaxCodeLen::get();
+		pending_upload::<T>(&who, max_code_len - c)?;
+		let chunk = BoundedVec::try_from(vec![0u8; c as usize])
+			.map_err(|_| BenchmarkError::Stop("the chunk exceeds the maximum chunk length"))?;
+
+		#[extrinsic_call]
+		_(origin as <T as frame_system::Config>::RuntimeOrigin, chunk);
+
+		assert_eq!(PendingUploads::<T>::get(&who).len() as u32, max_code_len);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn cancel_upload() -> Result<(), BenchmarkError> {
+		let who: T::AccountId = whitelisted_caller();
+		pending_upload::<T>(&who, <T as pallet_contracts::Config>::MaxCodeLen::get())?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(who.clone()));
+
+		assert!(!PendingUploads::<T>::contains_key(&who));
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
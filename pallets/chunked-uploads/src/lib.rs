@@ -0,0 +1,173 @@
+//! Uploads contract codes in chunks across multiple extrinsics, for codes too large to be
+//! uploaded by a single extrinsic. The chunks are appended to the pending upload of the signer,
+//! for which a deposit is held until the upload is either finished or cancelled.
+//!
+//! Finishing an upload uploads the assembled code to `pallet_contracts`, which enforces its
+//! `MaxCodeLen`, so that contracts can then be instantiated by code hash.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{
+			fungible::{Inspect, MutateHold},
+			tokens::Precision,
+		},
+	};
+	use frame_system::pallet_prelude::*;
+	use pallet_contracts::{Determinism, WeightInfo as _};
+	use sp_runtime::Saturating;
+
+	use super::WeightInfo;
+
+	type BalanceOf<T> =
+		<<T as Config>::Fungible as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+	type ContractsBalanceOf<T> = <<T as pallet_contracts::Config>::Currency as Inspect<
+		<T as frame_system::Config>::AccountId,
+	>>::Balance;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_contracts::Config {
+		/// The deposit held per byte of a pending upload.
+		#[pallet::constant]
+		type DepositPerByte: Get<BalanceOf<Self>>;
+		/// The currency in which the deposits of pending uploads are held.
+		type Fungible: MutateHold<Self::AccountId, Reason = <Self as Config>::RuntimeHoldReason>;
+		/// The maximum length of a single chunk.
+		#[pallet::constant]
+		type MaxChunkLen: Get<u32>;
+		/// The overarching hold reason.
+		type RuntimeHoldReason: From<HoldReason>;
+		/// The weights of the calls of the pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// A reason for the pallet placing a hold on funds.
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// The deposit of a pending upload.
+		PendingUpload,
+	}
+
+	/// The code uploaded so far by each account.
+	#[pallet::storage]
+	pub type PendingUploads<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<u8, <T as pallet_contracts::Config>::MaxCodeLen>,
+		ValueQuery,
+	>;
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The assembled code would exceed the maximum code length.
+		CodeTooLarge,
+		/// The account has no pending upload.
+		NoPendingUpload,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Each pending upload has the deposit of its bytes held, so a change of the deposit per
+		/// byte must migrate the holds of the uploads pending.
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+			use frame_support::traits::fungible::InspectHold;
+			for (who, code) in PendingUploads::<T>::iter() {
+				let deposit =
+					<T as Config>::DepositPerByte::get().saturating_mul((code.len() as u32).into());
+				ensure!(
+					T::Fungible::balance_on_hold(&HoldReason::PendingUpload.into(), &who) ==
+						deposit,
+					"The deposit held for a pending upload does not match its length"
+				);
+			}
+			Ok(())
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Append `chunk` to the pending upload of the signer, holding a deposit for its bytes.
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as Config>::WeightInfo::upload_chunk(chunk.len() as u32))]
+		pub fn upload_chunk(
+			origin: OriginFor<T>,
+			chunk: BoundedVec<u8, T::MaxChunkLen>,
+		) -> DispatchResult {
+			let who = <T as pallet_contracts::Config>::UploadOrigin::ensure_origin(origin)?;
+			let deposit =
+				<T as Config>::DepositPerByte::get().saturating_mul((chunk.len() as u32).into());
+			PendingUploads::<T>::try_mutate(&who, |code| -> DispatchResult {
+				code.try_extend(chunk.into_iter()).map_err(|_| Error::<T>::CodeTooLarge)?;
+				T::Fungible::hold(&HoldReason::PendingUpload.into(), &who, deposit)
+			})
+		}
+
+		/// Upload the pending upload of the signer as contract code, releasing its deposit.
+		#[pallet::call_index(1)]
+		// Taking the pending upload weighs as much as cancelling it, on top of uploading the code.
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_upload().saturating_add(
+			<T as pallet_contracts::Config>::WeightInfo::upload_code_determinism_enforced(
+				<T as pallet_contracts::Config>::MaxCodeLen::get(),
+			),
+		))]
+		pub fn finish_upload(
+			origin: OriginFor<T>,
+			storage_deposit_limit: Option<ContractsBalanceOf<T>>,
+		) -> DispatchResult {
+			let who = <T as pallet_contracts::Config>::UploadOrigin::ensure_origin(origin)?;
+			let code = Self::take_pending_upload(&who)?;
+			pallet_contracts::Pallet::<T>::bare_upload_code(
+				who,
+				code.into_inner(),
+				storage_deposit_limit,
+				Determinism::Enforced,
+			)?;
+			Ok(())
+		}
+
+		/// Discard the pending upload of the signer, releasing its deposit.
+		#[pallet::call_index(2)]
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_upload())]
+		pub fn cancel_upload(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::take_pending_upload(&who)?;
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Remove the pending upload of `who` and release its deposit, so that it can be used
+		/// towards the deposit of the uploaded code.
+		fn take_pending_upload(
+			who: &T::AccountId,
+		) -> Result<BoundedVec<u8, <T as pallet_contracts::Config>::MaxCodeLen>, DispatchError> {
+			let code = PendingUploads::<T>::take(who);
+			ensure!(!code.is_empty(), Error::<T>::NoPendingUpload);
+			T::Fungible::release_all(
+				&HoldReason::PendingUpload.into(),
+				who,
+				Precision::BestEffort,
+			)?;
+			Ok(code)
+		}
+	}
+}
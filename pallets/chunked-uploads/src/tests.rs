@@ -0,0 +1,125 @@
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{fungible::InspectHold, Get},
+	weights::Weight,
+	BoundedVec,
+};
+use pallet_contracts::{Code, CollectEvents, DebugInfo};
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash},
+	DispatchError,
+};
+
+use crate::{mock::*, Error, HoldReason, PendingUploads};
+
+type MaxChunkLen = <Test as crate::Config>::MaxChunkLen;
+
+const MAX_CHUNK_LEN: usize = 1024;
+
+fn chunks(code: &[u8]) -> Vec<BoundedVec<u8, MaxChunkLen>> {
+	code.chunks(MAX_CHUNK_LEN).map(|c| c.to_vec().try_into().unwrap()).collect()
+}
+
+fn held(who: u64) -> u64 {
+	Balances::balance_on_hold(&HoldReason::PendingUpload.into(), &who)
+}
+
+/// `CONTRACT` padded to more than two chunks by an unnamed custom section, prefixed by its LEB128
+/// encoded size.
+fn padded_contract() -> Vec<u8> {
+	let padding = 2 * MAX_CHUNK_LEN;
+	let mut code = CONTRACT.to_vec();
+	code.push(0x00);
+	let mut size = padding + 1;
+	while size >= 0x80 {
+		code.push((size as u8 & 0x7f) | 0x80);
+		size >>= 7;
+	}
+	code.extend([size as u8, 0x00]);
+	code.extend(vec![0; padding]);
+	code
+}
+
+#[test]
+fn chunks_are_uploaded_as_contract_code() {
+	new_test_ext().execute_with(|| {
+		let code = padded_contract();
+		let origin = RuntimeOrigin::signed(ALICE);
+		assert_noop!(
+			ContractsChunkedUploads::finish_upload(origin.clone(), None),
+			Error::<Test>::NoPendingUpload
+		);
+
+		for chunk in chunks(&code) {
+			assert_ok!(ContractsChunkedUploads::upload_chunk(origin.clone(), chunk));
+		}
+		assert_eq!(PendingUploads::<Test>::get(ALICE).into_inner(), code);
+		assert_eq!(held(ALICE), DEPOSIT_PER_BYTE * code.len() as u64);
+
+		assert_ok!(ContractsChunkedUploads::finish_upload(origin, None));
+		assert!(!PendingUploads::<Test>::contains_key(ALICE));
+		assert_eq!(held(ALICE), 0);
+		let instantiated = Contracts::bare_instantiate(
+			ALICE,
+			0,
+			Weight::MAX,
+			None,
+			Code::Existing(BlakeTwo256::hash(&code)),
+			vec![],
+			vec![],
+			DebugInfo::Skip,
+			CollectEvents::Skip,
+		);
+		assert!(instantiated.result.is_ok());
+	});
+}
+
+#[test]
+fn assembled_code_cannot_exceed_maximum_code_length() {
+	new_test_ext().execute_with(|| {
+		let max_code_len: u32 = <Test as pallet_contracts::Config>::MaxCodeLen::get();
+		let origin = RuntimeOrigin::signed(ALICE);
+		let too_large = vec![0; max_code_len as usize + 1];
+		let mut chunks = chunks(&too_large).into_iter();
+		let last = chunks.next_back().unwrap();
+		for chunk in chunks {
+			assert_ok!(ContractsChunkedUploads::upload_chunk(origin.clone(), chunk));
+		}
+		assert_noop!(
+			ContractsChunkedUploads::upload_chunk(origin, last),
+			Error::<Test>::CodeTooLarge
+		);
+	});
+}
+
+#[test]
+fn cancelling_upload_releases_deposit() {
+	new_test_ext().execute_with(|| {
+		let origin = RuntimeOrigin::signed(ALICE);
+		assert_noop!(
+			ContractsChunkedUploads::cancel_upload(origin.clone()),
+			Error::<Test>::NoPendingUpload
+		);
+		for chunk in chunks(&CONTRACT) {
+			assert_ok!(ContractsChunkedUploads::upload_chunk(origin.clone(), chunk));
+		}
+		assert_eq!(held(ALICE), DEPOSIT_PER_BYTE * CONTRACT.len() as u64);
+
+		assert_ok!(ContractsChunkedUploads::cancel_upload(origin));
+		assert!(!PendingUploads::<Test>::contains_key(ALICE));
+		assert_eq!(held(ALICE), 0);
+	});
+}
+
+#[test]
+fn chunks_require_upload_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ContractsChunkedUploads::upload_chunk(
+				RuntimeOrigin::root(),
+				chunks(&CONTRACT).remove(0)
+			),
+			DispatchError::BadOrigin
+		);
+	});
+}
@@ -0,0 +1,54 @@
+//! Weights for `pallet_chunked_uploads`.
+//!
+//! Not measured: these are estimates written by hand from the storage each call accesses, to be
+//! replaced with the weights generated on the reference hardware by
+//! `scripts/benchmark-pallets.sh chunked-uploads` before relying on them on a live chain.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use core::marker::PhantomData;
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions needed for `pallet_chunked_uploads`.
+pub trait WeightInfo {
+	fn upload_chunk(c: u32, ) -> Weight;
+	fn cancel_upload() -> Weight;
+}
+
+/// Estimated weights for `pallet_chunked_uploads`.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn upload_chunk(c: u32, ) -> Weight {
+		Weight::from_parts(30_000_000, 266_190)
+			.saturating_add(Weight::from_parts(1_400, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn cancel_upload() -> Weight {
+		Weight::from_parts(90_000_000, 266_190)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	fn upload_chunk(c: u32, ) -> Weight {
+		Weight::from_parts(30_000_000, 266_190)
+			.saturating_add(Weight::from_parts(1_400, 0).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn cancel_upload() -> Weight {
+		Weight::from_parts(90_000_000, 266_190)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+}
@@ -0,0 +1,58 @@
+//! Benchmarks of `pallet_collator_rewards`.
+
+use frame_benchmarking::v2::*;
+use frame_support::traits::{EnsureOrigin, Hooks};
+use sp_runtime::traits::Zero;
+
+use super::*;
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn set_mode() -> Result<(), BenchmarkError> {
+		let origin =
+			T::RewardOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, RewardMode::ImmediatePayout);
+
+		assert_eq!(Mode::<T>::get(), RewardMode::ImmediatePayout);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_block_reward() -> Result<(), BenchmarkError> {
+		let origin =
+			T::RewardOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let reward = T::Balance::from(u32::MAX);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, reward);
+
+		assert_eq!(BlockReward::<T>::get(), reward);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn on_initialize_without_reward() {
+		#[block]
+		{
+			Pallet::<T>::on_initialize(Zero::zero());
+		}
+	}
+
+	// The reward is large enough to create the pot.
+	#[benchmark]
+	fn on_initialize_with_reward() {
+		BlockReward::<T>::put(T::Balance::from(u32::MAX));
+
+		#[block]
+		{
+			Pallet::<T>::on_initialize(Zero::zero());
+		}
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
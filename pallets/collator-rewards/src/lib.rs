@@ -0,0 +1,152 @@
+//! Selects how collators are rewarded with transaction fees, along with the amount minted into the
+//! pot of the collator selection each block on top of them, so that collator incentive models can
+//! be experimented with without changing the runtime.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		pallet_prelude::*, traits::fungible::Mutate, DefaultNoBound, Deserialize, Serialize,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{AtLeast32BitUnsigned, MaybeSerializeDeserialize, Zero};
+
+	use super::WeightInfo;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The balance of an account.
+		type Balance: Member
+			+ Parameter
+			+ AtLeast32BitUnsigned
+			+ Copy
+			+ Default
+			+ MaybeSerializeDeserialize
+			+ MaxEncodedLen;
+		/// The currency the block reward is minted in.
+		type Currency: Mutate<Self::AccountId, Balance = Self::Balance>;
+		/// The pot the block reward is minted into.
+		type Pot: Get<Self::AccountId>;
+		/// The origin allowed to set the reward mode and the block reward.
+		type RewardOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// The weights of the calls of the pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// How collators are rewarded with transaction fees.
+	#[derive(
+		Clone,
+		Copy,
+		Debug,
+		Default,
+		PartialEq,
+		Eq,
+		Encode,
+		Decode,
+		MaxEncodedLen,
+		TypeInfo,
+		Serialize,
+		Deserialize,
+	)]
+	// Serialized via the serde re-exported by `frame_support`, as for the genesis config itself.
+	#[serde(crate = "frame_support::__private::serde")]
+	pub enum RewardMode {
+		/// Fees are paid to the author of the block including the transaction.
+		ImmediatePayout,
+		/// Fees accumulate in the pot of the collator selection, which pays the collators at the
+		/// end of each session.
+		#[default]
+		PotAccumulation,
+	}
+
+	/// The current reward mode.
+	#[pallet::storage]
+	pub type Mode<T> = StorageValue<_, RewardMode, ValueQuery>;
+
+	/// The amount minted into the pot each block, none unless set.
+	#[pallet::storage]
+	pub type BlockReward<T: Config> = StorageValue<_, T::Balance, ValueQuery>;
+
+	#[pallet::genesis_config]
+	#[derive(DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		/// The reward mode the chain starts with.
+		pub mode: RewardMode,
+		/// The block reward the chain starts with.
+		pub block_reward: T::Balance,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			Mode::<T>::put(self.mode);
+			// Left unset otherwise, so that the genesis is unchanged.
+			if !self.block_reward.is_zero() {
+				BlockReward::<T>::put(self.block_reward);
+			}
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The reward mode was changed.
+		ModeSet { mode: RewardMode },
+		/// The block reward was changed.
+		BlockRewardSet { reward: T::Balance },
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+			let reward = BlockReward::<T>::get();
+			if reward.is_zero() {
+				return T::WeightInfo::on_initialize_without_reward();
+			}
+			// A reward too small to create the pot is not minted.
+			let _ = T::Currency::mint_into(&T::Pot::get(), reward);
+			T::WeightInfo::on_initialize_with_reward()
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set how collators are rewarded with transaction fees.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::set_mode())]
+		pub fn set_mode(origin: OriginFor<T>, mode: RewardMode) -> DispatchResult {
+			T::RewardOrigin::ensure_origin(origin)?;
+			Mode::<T>::put(mode);
+			Self::deposit_event(Event::ModeSet { mode });
+			Ok(())
+		}
+
+		/// Set the amount minted into the pot each block, rewarding the collators on top of
+		/// transaction fees.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::set_block_reward())]
+		pub fn set_block_reward(origin: OriginFor<T>, reward: T::Balance) -> DispatchResult {
+			T::RewardOrigin::ensure_origin(origin)?;
+			BlockReward::<T>::put(reward);
+			Self::deposit_event(Event::BlockRewardSet { reward });
+			Ok(())
+		}
+	}
+}
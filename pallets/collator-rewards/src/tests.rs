@@ -0,0 +1,68 @@
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{fungible::Inspect, Hooks},
+};
+use sp_runtime::DispatchError;
+
+use crate::{mock::*, BlockReward, Event, Mode, RewardMode};
+
+#[test]
+fn fees_accumulate_in_the_pot_unless_set() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Mode::<Test>::get(), RewardMode::PotAccumulation);
+		assert_eq!(BlockReward::<Test>::get(), 0);
+	});
+}
+
+#[test]
+fn mode_is_set_by_reward_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			CollatorRewards::set_mode(RuntimeOrigin::signed(1), RewardMode::ImmediatePayout),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(CollatorRewards::set_mode(RuntimeOrigin::root(), RewardMode::ImmediatePayout));
+		assert_eq!(Mode::<Test>::get(), RewardMode::ImmediatePayout);
+		System::assert_last_event(Event::ModeSet { mode: RewardMode::ImmediatePayout }.into());
+	});
+}
+
+#[test]
+fn block_reward_is_minted_into_pot() {
+	new_test_ext().execute_with(|| {
+		let issuance = Balances::total_issuance();
+		assert_noop!(
+			CollatorRewards::set_block_reward(RuntimeOrigin::signed(1), 20),
+			DispatchError::BadOrigin
+		);
+		// A reward too small to create the pot is not minted.
+		assert_ok!(CollatorRewards::set_block_reward(
+			RuntimeOrigin::root(),
+			ExistentialDeposit::get() - 1
+		));
+		CollatorRewards::on_initialize(2);
+		assert_eq!(Balances::total_issuance(), issuance);
+
+		assert_ok!(CollatorRewards::set_block_reward(RuntimeOrigin::root(), 20));
+		System::assert_last_event(Event::BlockRewardSet { reward: 20 }.into());
+		for n in 3..5 {
+			CollatorRewards::on_initialize(n);
+		}
+		assert_eq!(Balances::balance(&Pot::get()), 40);
+		assert_eq!(Balances::total_issuance(), issuance + 40);
+	});
+}
+
+#[test]
+fn genesis_sets_mode_and_block_reward() {
+	use sp_runtime::BuildStorage;
+	let storage =
+		crate::GenesisConfig::<Test> { mode: RewardMode::ImmediatePayout, block_reward: 20 }
+			.build_storage()
+			.unwrap();
+	sp_io::TestExternalities::new(storage).execute_with(|| {
+		assert_eq!(Mode::<Test>::get(), RewardMode::ImmediatePayout);
+		assert_eq!(BlockReward::<Test>::get(), 20);
+	});
+}
@@ -0,0 +1,68 @@
+//! Weights for `pallet_collator_rewards`.
+//!
+//! Not measured: these are estimates written by hand from the storage each call accesses, to be
+//! replaced with the weights generated on the reference hardware by
+//! `scripts/benchmark-pallets.sh collator-rewards` before relying on them on a live chain.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use core::marker::PhantomData;
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions needed for `pallet_collator_rewards`.
+pub trait WeightInfo {
+	fn set_mode() -> Weight;
+	fn set_block_reward() -> Weight;
+	fn on_initialize_without_reward() -> Weight;
+	fn on_initialize_with_reward() -> Weight;
+}
+
+/// Estimated weights for `pallet_collator_rewards`.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn set_mode() -> Weight {
+		Weight::from_parts(8_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn set_block_reward() -> Weight {
+		Weight::from_parts(8_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn on_initialize_without_reward() -> Weight {
+		Weight::from_parts(3_000_000, 1_501)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+	fn on_initialize_with_reward() -> Weight {
+		Weight::from_parts(30_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	fn set_mode() -> Weight {
+		Weight::from_parts(8_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn set_block_reward() -> Weight {
+		Weight::from_parts(8_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn on_initialize_without_reward() -> Weight {
+		Weight::from_parts(3_000_000, 1_501)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+	fn on_initialize_with_reward() -> Weight {
+		Weight::from_parts(30_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+}
@@ -0,0 +1,73 @@
+//! Reports the changes of the collator set between sessions, so that they can be monitored
+//! without diffing the validators of the session pallet across blocks.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::BlockNumberFor;
+	use sp_std::prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The maximum number of collators of a session.
+		#[pallet::constant]
+		type MaxCollators: Get<u32>;
+	}
+
+	/// The collators of the current session.
+	#[pallet::storage]
+	pub type Collators<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxCollators>, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The collator set changed as `session` started.
+		CollatorSetChanged { session: u32, added: Vec<T::AccountId>, removed: Vec<T::AccountId> },
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// The collators noted are distinct.
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+			let collators = Collators::<T>::get();
+			ensure!(
+				collators.iter().enumerate().all(|(i, c)| !collators[..i].contains(c)),
+				"A collator is noted more than once"
+			);
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Note `collators` as the collators of `session`, reporting how they differ from the
+		/// collators of the previous session.
+		pub fn note_collators(session: u32, collators: Vec<T::AccountId>) {
+			let previous = Collators::<T>::get();
+			let added: Vec<_> =
+				collators.iter().filter(|c| !previous.contains(c)).cloned().collect();
+			let removed: Vec<_> =
+				previous.iter().filter(|c| !collators.contains(c)).cloned().collect();
+			if added.is_empty() && removed.is_empty() {
+				return;
+			}
+			Collators::<T>::put(BoundedVec::truncate_from(collators));
+			Self::deposit_event(Event::CollatorSetChanged { session, added, removed });
+		}
+	}
+}
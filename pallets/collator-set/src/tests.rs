@@ -0,0 +1,31 @@
+use crate::{mock::*, Collators, Event};
+
+#[test]
+fn changes_of_the_collator_set_are_reported() {
+	new_test_ext().execute_with(|| {
+		CollatorSet::note_collators(1, vec![1, 2]);
+		assert_eq!(Collators::<Test>::get().into_inner(), vec![1, 2]);
+		System::assert_last_event(
+			Event::CollatorSetChanged { session: 1, added: vec![1, 2], removed: vec![] }.into(),
+		);
+
+		CollatorSet::note_collators(2, vec![2, 3]);
+		assert_eq!(Collators::<Test>::get().into_inner(), vec![2, 3]);
+		System::assert_last_event(
+			Event::CollatorSetChanged { session: 2, added: vec![3], removed: vec![1] }.into(),
+		);
+	});
+}
+
+#[test]
+fn unchanged_collator_set_is_not_reported() {
+	new_test_ext().execute_with(|| {
+		CollatorSet::note_collators(1, vec![1, 2]);
+		System::reset_events();
+
+		// The order of the collators does not matter.
+		CollatorSet::note_collators(2, vec![2, 1]);
+		assert!(System::events().is_empty());
+		assert_eq!(Collators::<Test>::get().into_inner(), vec![1, 2]);
+	});
+}
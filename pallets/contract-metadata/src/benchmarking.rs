@@ -0,0 +1,83 @@
+//! Benchmarks of `pallet_contract_metadata`.
+
+use frame_benchmarking::v2::*;
+use frame_support::{
+	traits::{
+		fungible::{Inspect, Mutate},
+		Get,
+	},
+	BoundedVec,
+};
+use frame_system::RawOrigin;
+use sp_runtime::Saturating;
+use sp_std::vec;
+
+use super::*;
+
+type BalanceOf<T> =
+	<<T as Config>::Fungible as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// A code owned by `who`, funded to hold the deposits of the longest metadata.
+fn owned_code<T: Config>(who: &T::AccountId) -> T::Hash
+where
+	T::Fungible: Mutate<T::AccountId>,
+{
+	let max_deposit: BalanceOf<T> = <T as Config>::DepositPerByte::get()
+		.saturating_mul(<T as Config>::MaxMetadataLen::get().into());
+	T::Fungible::set_balance(
+		who,
+		T::Fungible::minimum_balance().saturating_add(max_deposit.saturating_mul(2u32.into())),
+	);
+	let code_hash = T::Hash::default();
+	CodeInfoOf::<T>::insert(code_hash, who);
+	code_hash
+}
+
+/// Metadata of `len` bytes.
+fn metadata<T: Config>(len: u32) -> BoundedVec<u8, <T as Config>::MaxMetadataLen> {
+	BoundedVec::truncate_from(vec![0u8; len as usize])
+}
+
+#[benchmarks(where T::Fungible: Mutate<T::AccountId>)]
+mod benchmarks {
+	use super::*;
+
+	// Replaces the longest metadata, whose deposit is released.
+	#[benchmark]
+	fn set_metadata(
+		m: Linear<0, { <T as Config>::MaxMetadataLen::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let who: T::AccountId = whitelisted_caller();
+		let code_hash = owned_code::<T>(&who);
+		Pallet::<T>::set_metadata(
+			RawOrigin::Signed(who.clone()).into(),
+			code_hash,
+			metadata::<T>(<T as Config>::MaxMetadataLen::get()),
+		)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(who), code_hash, metadata::<T>(m));
+
+		assert_eq!(Pallet::<T>::metadata(code_hash).map(|metadata| metadata.len() as u32), Some(m));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn clear_metadata() -> Result<(), BenchmarkError> {
+		let who: T::AccountId = whitelisted_caller();
+		let code_hash = owned_code::<T>(&who);
+		Pallet::<T>::set_metadata(
+			RawOrigin::Signed(who.clone()).into(),
+			code_hash,
+			metadata::<T>(<T as Config>::MaxMetadataLen::get()),
+		)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(who), code_hash);
+
+		assert!(Pallet::<T>::metadata(code_hash).is_none());
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
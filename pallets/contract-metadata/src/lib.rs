@@ -0,0 +1,187 @@
+//! Stores the metadata of contract codes on-chain, e.g. their ABI, so that wallets can decode
+//! the calls of contracts without relying on an external registry.
+//!
+//! Only the owner of a code, i.e. the account which uploaded it to `pallet_contracts`, may set
+//! its metadata, for which a deposit is held until the metadata is cleared.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{
+			fungible::{Inspect, MutateHold},
+			tokens::Precision,
+		},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::Saturating;
+	use sp_std::vec::Vec;
+
+	use super::WeightInfo;
+
+	type BalanceOf<T> =
+		<<T as Config>::Fungible as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_contracts::Config {
+		/// The deposit held per byte of metadata.
+		#[pallet::constant]
+		type DepositPerByte: Get<BalanceOf<Self>>;
+		/// The currency in which the deposits of metadata are held.
+		type Fungible: MutateHold<Self::AccountId, Reason = <Self as Config>::RuntimeHoldReason>;
+		/// The maximum length of the metadata of a code.
+		#[pallet::constant]
+		type MaxMetadataLen: Get<u32>;
+		/// The overarching hold reason.
+		type RuntimeHoldReason: From<HoldReason>;
+		/// The weights of the calls of the pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// A reason for the pallet placing a hold on funds.
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// The deposit of the metadata of a code.
+		Metadata,
+	}
+
+	/// The metadata of a code, along with the deposit held for it.
+	#[derive(
+		CloneNoBound,
+		PartialEqNoBound,
+		EqNoBound,
+		RuntimeDebugNoBound,
+		Encode,
+		Decode,
+		TypeInfo,
+		MaxEncodedLen,
+	)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct CodeMetadata<T: Config> {
+		/// The account holding the deposit of the metadata.
+		pub depositor: T::AccountId,
+		/// The deposit held.
+		pub deposit: BalanceOf<T>,
+		/// The metadata, e.g. the ABI of the contracts of the code.
+		pub metadata: BoundedVec<u8, <T as Config>::MaxMetadataLen>,
+	}
+
+	/// The metadata of each code, by code hash.
+	#[pallet::storage]
+	pub type Metadata<T: Config> = StorageMap<_, Identity, T::Hash, CodeMetadata<T>>;
+
+	/// The owner of each code, i.e. the first field of the information `pallet_contracts` stores
+	/// about it, which is not public in the pallet.
+	#[frame_support::storage_alias(verbatim)]
+	pub(crate) type CodeInfoOf<T: Config> = StorageMap<
+		Contracts,
+		Identity,
+		<T as frame_system::Config>::Hash,
+		<T as frame_system::Config>::AccountId,
+	>;
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// No code is stored under the code hash.
+		CodeNotFound,
+		/// The signer is not the owner of the code.
+		NotCodeOwner,
+		/// The code has no metadata.
+		NoMetadata,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// The deposits held by each depositor add up to the deposits of its metadata.
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+			use frame_support::traits::fungible::InspectHold;
+			use sp_std::collections::btree_map::BTreeMap;
+			let mut deposits = BTreeMap::<T::AccountId, BalanceOf<T>>::new();
+			for (_, metadata) in Metadata::<T>::iter() {
+				let deposit = deposits.entry(metadata.depositor).or_default();
+				*deposit = deposit.saturating_add(metadata.deposit);
+			}
+			for (depositor, deposit) in deposits {
+				ensure!(
+					T::Fungible::balance_on_hold(&HoldReason::Metadata.into(), &depositor) ==
+						deposit,
+					"The deposit held by a depositor does not match the deposits of its metadata"
+				);
+			}
+			Ok(())
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the metadata of the code `code_hash`, owned by the signer, holding a deposit for
+		/// its bytes. Any previous metadata of the code is replaced and its deposit released.
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_metadata(metadata.len() as u32))]
+		pub fn set_metadata(
+			origin: OriginFor<T>,
+			code_hash: T::Hash,
+			metadata: BoundedVec<u8, <T as Config>::MaxMetadataLen>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let owner = CodeInfoOf::<T>::get(code_hash).ok_or(Error::<T>::CodeNotFound)?;
+			ensure!(owner == who, Error::<T>::NotCodeOwner);
+			if let Some(previous) = Metadata::<T>::take(code_hash) {
+				Self::release(&previous)?;
+			}
+			let deposit =
+				<T as Config>::DepositPerByte::get().saturating_mul((metadata.len() as u32).into());
+			T::Fungible::hold(&HoldReason::Metadata.into(), &who, deposit)?;
+			Metadata::<T>::insert(code_hash, CodeMetadata { depositor: who, deposit, metadata });
+			Ok(())
+		}
+
+		/// Clear the metadata of the code `code_hash`, releasing its deposit. Only the depositor
+		/// of the metadata may clear it, even once the code is removed.
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as Config>::WeightInfo::clear_metadata())]
+		pub fn clear_metadata(origin: OriginFor<T>, code_hash: T::Hash) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let metadata = Metadata::<T>::get(code_hash).ok_or(Error::<T>::NoMetadata)?;
+			ensure!(metadata.depositor == who, Error::<T>::NotCodeOwner);
+			Metadata::<T>::remove(code_hash);
+			Self::release(&metadata)
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The metadata of the code `code_hash`, if any.
+		pub fn metadata(code_hash: T::Hash) -> Option<Vec<u8>> {
+			Metadata::<T>::get(code_hash).map(|metadata| metadata.metadata.into_inner())
+		}
+
+		/// Release the deposit held for `metadata`.
+		fn release(metadata: &CodeMetadata<T>) -> DispatchResult {
+			T::Fungible::release(
+				&HoldReason::Metadata.into(),
+				&metadata.depositor,
+				metadata.deposit,
+				Precision::BestEffort,
+			)?;
+			Ok(())
+		}
+	}
+}
@@ -0,0 +1,90 @@
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{ConstU32, ConstU64},
+};
+use frame_system::EnsureSigned;
+use sp_runtime::BuildStorage;
+
+use crate as pallet_contract_metadata;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub(crate) const ALICE: u64 = 1;
+pub(crate) const BOB: u64 = 2;
+pub(crate) const DEPOSIT_PER_BYTE: u64 = 2;
+
+// A contract emitting a debug message when called:
+// (module
+//   (import "seal0" "debug_message" (func $debug_message (param i32 i32) (result i32)))
+//   (import "env" "memory" (memory 1 1))
+//   (data (i32.const 0) "Hello Pop")
+//   (func (export "deploy"))
+//   (func (export "call") (drop (call $debug_message (i32.const 0) (i32.const 9)))))
+pub(crate) const CONTRACT: [u8; 117] = [
+	0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x0a, 0x02, 0x60, 0x02, 0x7f, 0x7f, 0x01,
+	0x7f, 0x60, 0x00, 0x00, 0x02, 0x26, 0x02, 0x05, 0x73, 0x65, 0x61, 0x6c, 0x30, 0x0d, 0x64, 0x65,
+	0x62, 0x75, 0x67, 0x5f, 0x6d, 0x65, 0x73, 0x73, 0x61, 0x67, 0x65, 0x00, 0x00, 0x03, 0x65, 0x6e,
+	0x76, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x01, 0x01, 0x01, 0x03, 0x03, 0x02, 0x01,
+	0x01, 0x07, 0x11, 0x02, 0x06, 0x64, 0x65, 0x70, 0x6c, 0x6f, 0x79, 0x00, 0x01, 0x04, 0x63, 0x61,
+	0x6c, 0x6c, 0x00, 0x02, 0x0a, 0x0e, 0x02, 0x02, 0x00, 0x0b, 0x09, 0x00, 0x41, 0x00, 0x41, 0x09,
+	0x10, 0x00, 0x1a, 0x0b, 0x0b, 0x0f, 0x01, 0x00, 0x41, 0x00, 0x0b, 0x09, 0x48, 0x65, 0x6c, 0x6c,
+	0x6f, 0x20, 0x50, 0x6f, 0x70,
+];
+
+frame_support::construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		Balances: pallet_balances,
+		Timestamp: pallet_timestamp,
+		Contracts: pallet_contracts,
+		ContractsMetadata: pallet_contract_metadata,
+	}
+);
+
+parameter_types! {
+	pub Schedule: pallet_contracts::Schedule<Test> = Default::default();
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type AccountData = pallet_balances::AccountData<u64>;
+	type Block = Block;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type AccountStore = System;
+}
+
+#[derive_impl(pallet_timestamp::config_preludes::TestDefaultConfig)]
+impl pallet_timestamp::Config for Test {}
+
+#[derive_impl(pallet_contracts::config_preludes::TestDefaultConfig)]
+impl pallet_contracts::Config for Test {
+	type CallStack = [pallet_contracts::Frame<Self>; 5];
+	type Currency = Balances;
+	type InstantiateOrigin = EnsureSigned<u64>;
+	type Schedule = Schedule;
+	type Time = Timestamp;
+	type UploadOrigin = EnsureSigned<u64>;
+}
+
+impl pallet_contract_metadata::Config for Test {
+	type DepositPerByte = ConstU64<DEPOSIT_PER_BYTE>;
+	type Fungible = Balances;
+	type MaxMetadataLen = ConstU32<1024>;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type WeightInfo = ();
+}
+
+pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(ALICE, 1_000_000_000_000_000), (BOB, 1_000_000_000_000_000)],
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
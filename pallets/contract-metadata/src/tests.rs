@@ -0,0 +1,97 @@
+use frame_support::{
+	assert_noop, assert_ok, traits::fungible::InspectHold, weights::Weight, BoundedVec,
+};
+use pallet_contracts::{Code, CollectEvents, DebugInfo};
+use sp_core::H256;
+
+use crate::{mock::*, Error, HoldReason};
+
+fn held(who: u64) -> u64 {
+	Balances::balance_on_hold(&HoldReason::Metadata.into(), &who)
+}
+
+/// Instantiate `CONTRACT` by `ALICE`, returning the hash of its code.
+fn upload_code() -> H256 {
+	let contract = Contracts::bare_instantiate(
+		ALICE,
+		0,
+		Weight::MAX,
+		None,
+		Code::Upload(CONTRACT.to_vec()),
+		vec![],
+		vec![],
+		DebugInfo::Skip,
+		CollectEvents::Skip,
+	)
+	.result
+	.unwrap()
+	.account_id;
+	Contracts::code_hash(&contract).unwrap()
+}
+
+#[test]
+fn metadata_is_set_and_cleared_by_code_owner() {
+	let abi = br#"{"spec":{"messages":[]}}"#.to_vec();
+	new_test_ext().execute_with(|| {
+		let code_hash = upload_code();
+		assert_eq!(ContractsMetadata::metadata(code_hash), None);
+
+		let metadata = BoundedVec::truncate_from(abi.clone());
+		assert_noop!(
+			ContractsMetadata::set_metadata(
+				RuntimeOrigin::signed(BOB),
+				code_hash,
+				metadata.clone()
+			),
+			Error::<Test>::NotCodeOwner
+		);
+		assert_noop!(
+			ContractsMetadata::set_metadata(
+				RuntimeOrigin::signed(ALICE),
+				H256::repeat_byte(1),
+				metadata.clone()
+			),
+			Error::<Test>::CodeNotFound
+		);
+		assert_ok!(ContractsMetadata::set_metadata(
+			RuntimeOrigin::signed(ALICE),
+			code_hash,
+			metadata
+		));
+		assert_eq!(ContractsMetadata::metadata(code_hash), Some(abi.clone()));
+		assert_eq!(held(ALICE), DEPOSIT_PER_BYTE * abi.len() as u64);
+
+		assert_noop!(
+			ContractsMetadata::clear_metadata(RuntimeOrigin::signed(BOB), code_hash),
+			Error::<Test>::NotCodeOwner
+		);
+		assert_ok!(ContractsMetadata::clear_metadata(RuntimeOrigin::signed(ALICE), code_hash));
+		assert_eq!(ContractsMetadata::metadata(code_hash), None);
+		assert_eq!(held(ALICE), 0);
+		assert_noop!(
+			ContractsMetadata::clear_metadata(RuntimeOrigin::signed(ALICE), code_hash),
+			Error::<Test>::NoMetadata
+		);
+	});
+}
+
+#[test]
+fn replaced_metadata_releases_previous_deposit() {
+	new_test_ext().execute_with(|| {
+		let code_hash = upload_code();
+		assert_ok!(ContractsMetadata::set_metadata(
+			RuntimeOrigin::signed(ALICE),
+			code_hash,
+			BoundedVec::truncate_from(vec![0; 100])
+		));
+		assert_eq!(held(ALICE), DEPOSIT_PER_BYTE * 100);
+
+		assert_ok!(ContractsMetadata::set_metadata(
+			RuntimeOrigin::signed(ALICE),
+			code_hash,
+			BoundedVec::truncate_from(vec![0; 10])
+		));
+		assert_eq!(ContractsMetadata::metadata(code_hash), Some(vec![0; 10]));
+		assert_eq!(held(ALICE), DEPOSIT_PER_BYTE * 10);
+	});
+}
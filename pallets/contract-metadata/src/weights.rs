@@ -0,0 +1,54 @@
+//! Weights for `pallet_contract_metadata`.
+//!
+//! Not measured: these are estimates written by hand from the storage each call accesses, to be
+//! replaced with the weights generated on the reference hardware by
+//! `scripts/benchmark-pallets.sh contract-metadata` before relying on them on a live chain.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use core::marker::PhantomData;
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions needed for `pallet_contract_metadata`.
+pub trait WeightInfo {
+	fn set_metadata(m: u32) -> Weight;
+	fn clear_metadata() -> Weight;
+}
+
+/// Estimated weights for `pallet_contract_metadata`.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn set_metadata(m: u32) -> Weight {
+		Weight::from_parts(60_000_000, 134_748)
+			.saturating_add(Weight::from_parts(1_200, 0).saturating_mul(m.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn clear_metadata() -> Weight {
+		Weight::from_parts(55_000_000, 134_748)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	fn set_metadata(m: u32) -> Weight {
+		Weight::from_parts(60_000_000, 134_748)
+			.saturating_add(Weight::from_parts(1_200, 0).saturating_mul(m.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn clear_metadata() -> Weight {
+		Weight::from_parts(55_000_000, 134_748)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+}
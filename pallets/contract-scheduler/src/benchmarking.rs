@@ -0,0 +1,39 @@
+//! Benchmarks of `pallet_contract_scheduler`.
+
+use frame_benchmarking::v2::*;
+use frame_support::weights::Weight;
+use frame_system::RawOrigin;
+
+use super::*;
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn schedule_call() {
+		let address: T::AccountId = account("contract", 0, 0);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, address, [0; 4], 10u32.into(), Weight::zero());
+	}
+
+	#[benchmark]
+	fn cancel_call() -> Result<(), BenchmarkError> {
+		let address: T::AccountId = account("contract", 0, 0);
+		Pallet::<T>::schedule_call(
+			RawOrigin::Root.into(),
+			address.clone(),
+			[0; 4],
+			10u32.into(),
+			Weight::zero(),
+		)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, address, [0; 4]);
+
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,146 @@
+//! Recurrent contract calls, scheduled by root via the scheduler, e.g. for the upkeep of contracts
+//! without relying on an off-chain keeper.
+//!
+//! A contract call is scheduled as a task named by [`task_name`], which calls `selector` on the
+//! contract at `address` every `period` blocks, from `period` blocks after being scheduled until
+//! cancelled. The contract is called by root without any value and with up to `gas_limit`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::traits::schedule::v3::TaskName;
+pub use pallet::*;
+use sp_runtime::traits::{BlakeTwo256, Hash};
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+/// The name of the scheduler task which recurrently calls `selector` on the contract at `address`.
+pub fn task_name<AccountId: codec::Encode>(address: &AccountId, selector: [u8; 4]) -> TaskName {
+	BlakeTwo256::hash_of(&(b"pop/contract-call", address, selector)).0
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use core::fmt;
+
+	use codec::HasCompact;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{
+			fungible::Inspect,
+			schedule::{v3::Named, DispatchTime, LOWEST_PRIORITY},
+			StorePreimage,
+		},
+	};
+	use frame_system::{pallet_prelude::*, RawOrigin};
+	use sp_runtime::traits::{One, Saturating, StaticLookup, Zero};
+
+	use super::{task_name, WeightInfo};
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config:
+		frame_system::Config
+		// The bounds `pallet_contracts::Call` requires of the balance of the contracts.
+		+ pallet_contracts::Config<
+			Currency: Inspect<
+				Self::AccountId,
+				Balance: HasCompact<Type: Clone + Eq + fmt::Debug + TypeInfo + Encode>,
+			>,
+		>
+	{
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The aggregated call, in which the contract calls are scheduled.
+		type RuntimeCall: From<pallet_contracts::Call<Self>> + Encode;
+		/// The origin the contract calls are dispatched from by the scheduler.
+		type PalletsOrigin: From<RawOrigin<Self::AccountId>>;
+		/// The scheduler of the contract calls.
+		type Scheduler: Named<
+			BlockNumberFor<Self>,
+			<Self as Config>::RuntimeCall,
+			Self::PalletsOrigin,
+			Hasher = Self::Hashing,
+		>;
+		/// The preimages of the contract calls too large to be scheduled inline.
+		type Preimages: StorePreimage<H = Self::Hashing>;
+		/// The weights of the calls of the pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// `selector` is called on the contract at `address` every `period` blocks.
+		CallScheduled { address: T::AccountId, selector: [u8; 4], period: BlockNumberFor<T> },
+		/// `selector` is no longer called on the contract at `address`.
+		CallCancelled { address: T::AccountId, selector: [u8; 4] },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Contract calls recur at least every block.
+		ZeroPeriod,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Schedule `selector` to be called on the contract at `address` every `period` blocks,
+		/// until cancelled. Fails for an already scheduled contract call, until it is cancelled.
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as Config>::WeightInfo::schedule_call())]
+		pub fn schedule_call(
+			origin: OriginFor<T>,
+			address: T::AccountId,
+			selector: [u8; 4],
+			period: BlockNumberFor<T>,
+			gas_limit: Weight,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!(!period.is_zero(), Error::<T>::ZeroPeriod);
+
+			let call = pallet_contracts::Call::<T>::call {
+				dest: T::Lookup::unlookup(address.clone()),
+				value: Zero::zero(),
+				gas_limit,
+				storage_deposit_limit: None,
+				data: selector.to_vec(),
+			};
+			T::Scheduler::schedule_named(
+				task_name(&address, selector),
+				// The scheduler runs tasks `after + 1` blocks from now.
+				DispatchTime::After(period.saturating_sub(One::one())),
+				Some((period, u32::MAX)),
+				LOWEST_PRIORITY,
+				RawOrigin::Root.into(),
+				T::Preimages::bound(<T as Config>::RuntimeCall::from(call))?,
+			)?;
+
+			Self::deposit_event(Event::CallScheduled { address, selector, period });
+			Ok(())
+		}
+
+		/// Cancel the recurrent call of `selector` on the contract at `address`.
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_call())]
+		pub fn cancel_call(
+			origin: OriginFor<T>,
+			address: T::AccountId,
+			selector: [u8; 4],
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			T::Scheduler::cancel_named(task_name(&address, selector))?;
+
+			Self::deposit_event(Event::CallCancelled { address, selector });
+			Ok(())
+		}
+	}
+}
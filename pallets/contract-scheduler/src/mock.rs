@@ -0,0 +1,93 @@
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{ConstU32, EqualPrivilegeOnly},
+	weights::Weight,
+};
+use frame_system::{EnsureRoot, EnsureSigned};
+use sp_runtime::BuildStorage;
+
+use crate as pallet_contract_scheduler;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub(crate) const ALICE: u64 = 1;
+frame_support::construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		Balances: pallet_balances,
+		Timestamp: pallet_timestamp,
+		Preimage: pallet_preimage,
+		Scheduler: pallet_scheduler,
+		Contracts: pallet_contracts,
+		ContractsScheduler: pallet_contract_scheduler,
+	}
+);
+
+parameter_types! {
+	pub MaximumSchedulerWeight: Weight = Weight::MAX;
+	pub Schedule: pallet_contracts::Schedule<Test> = Default::default();
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type AccountData = pallet_balances::AccountData<u64>;
+	type Block = Block;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type AccountStore = System;
+}
+
+#[derive_impl(pallet_timestamp::config_preludes::TestDefaultConfig)]
+impl pallet_timestamp::Config for Test {}
+
+impl pallet_preimage::Config for Test {
+	type Consideration = ();
+	type Currency = Balances;
+	type ManagerOrigin = EnsureRoot<u64>;
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+}
+
+impl pallet_scheduler::Config for Test {
+	type MaxScheduledPerBlock = ConstU32<10>;
+	type MaximumWeight = MaximumSchedulerWeight;
+	type OriginPrivilegeCmp = EqualPrivilegeOnly;
+	type PalletsOrigin = OriginCaller;
+	type Preimages = Preimage;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeOrigin = RuntimeOrigin;
+	type ScheduleOrigin = EnsureRoot<u64>;
+	type WeightInfo = ();
+}
+
+#[derive_impl(pallet_contracts::config_preludes::TestDefaultConfig)]
+impl pallet_contracts::Config for Test {
+	type CallStack = [pallet_contracts::Frame<Self>; 5];
+	type Currency = Balances;
+	type InstantiateOrigin = EnsureSigned<u64>;
+	type Schedule = Schedule;
+	type Time = Timestamp;
+	type UploadOrigin = EnsureSigned<u64>;
+}
+
+impl pallet_contract_scheduler::Config for Test {
+	type PalletsOrigin = OriginCaller;
+	type Preimages = Preimage;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type Scheduler = Scheduler;
+	type WeightInfo = ();
+}
+
+pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(ALICE, 1_000_000_000_000_000)] }
+		.assimilate_storage(&mut storage)
+		.unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,127 @@
+use frame_support::{assert_noop, assert_ok, traits::Hooks, weights::Weight};
+use sp_runtime::DispatchError;
+
+use crate::{mock::*, task_name, Error, Event};
+
+const ADDRESS: u64 = 10;
+const SELECTOR: [u8; 4] = [1, 2, 3, 4];
+
+/// The blocks up to `until` in which the call of `SELECTOR` on `ADDRESS` is dispatched.
+fn dispatched_until(until: u64) -> Vec<u64> {
+	let task = task_name(&ADDRESS, SELECTOR);
+	let mut blocks = Vec::new();
+	for n in System::block_number() + 1..=until {
+		System::set_block_number(n);
+		Scheduler::on_initialize(n);
+		let dispatched = System::events().into_iter().any(|record| {
+			matches!(
+				record.event,
+				RuntimeEvent::Scheduler(pallet_scheduler::Event::Dispatched {
+					id: Some(id), ..
+				}) if id == task
+			)
+		});
+		if dispatched {
+			blocks.push(n);
+		}
+		System::reset_events();
+	}
+	blocks
+}
+
+#[test]
+fn scheduled_call_recurs_until_cancelled() {
+	new_test_ext().execute_with(|| {
+		let gas_limit = Weight::from_parts(1, 1);
+		assert_ok!(ContractsScheduler::schedule_call(
+			RuntimeOrigin::root(),
+			ADDRESS,
+			SELECTOR,
+			5,
+			gas_limit
+		));
+		System::assert_last_event(
+			Event::CallScheduled { address: ADDRESS, selector: SELECTOR, period: 5 }.into(),
+		);
+		assert_eq!(dispatched_until(16), vec![6, 11, 16]);
+
+		assert_ok!(ContractsScheduler::cancel_call(RuntimeOrigin::root(), ADDRESS, SELECTOR));
+		System::assert_last_event(
+			Event::CallCancelled { address: ADDRESS, selector: SELECTOR }.into(),
+		);
+		assert!(dispatched_until(30).is_empty());
+	});
+}
+
+#[test]
+fn scheduled_call_is_unique() {
+	new_test_ext().execute_with(|| {
+		let gas_limit = Weight::from_parts(1, 1);
+		assert_ok!(ContractsScheduler::schedule_call(
+			RuntimeOrigin::root(),
+			ADDRESS,
+			SELECTOR,
+			5,
+			gas_limit
+		));
+		assert_noop!(
+			ContractsScheduler::schedule_call(
+				RuntimeOrigin::root(),
+				ADDRESS,
+				SELECTOR,
+				3,
+				gas_limit
+			),
+			pallet_scheduler::Error::<Test>::FailedToSchedule
+		);
+		// Another selector of the same contract is another call.
+		assert_ok!(ContractsScheduler::schedule_call(
+			RuntimeOrigin::root(),
+			ADDRESS,
+			[0; 4],
+			3,
+			gas_limit
+		));
+	});
+}
+
+#[test]
+fn calls_recur_at_least_every_block() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ContractsScheduler::schedule_call(
+				RuntimeOrigin::root(),
+				ADDRESS,
+				SELECTOR,
+				0,
+				Weight::from_parts(1, 1)
+			),
+			Error::<Test>::ZeroPeriod
+		);
+	});
+}
+
+#[test]
+fn calls_are_scheduled_and_cancelled_by_root() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ContractsScheduler::schedule_call(
+				RuntimeOrigin::signed(ALICE),
+				ADDRESS,
+				SELECTOR,
+				5,
+				Weight::from_parts(1, 1)
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			ContractsScheduler::cancel_call(RuntimeOrigin::signed(ALICE), ADDRESS, SELECTOR),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			ContractsScheduler::cancel_call(RuntimeOrigin::root(), ADDRESS, SELECTOR),
+			// The scheduler does not tell why a task could not be cancelled.
+			DispatchError::Unavailable
+		);
+	});
+}
@@ -0,0 +1,36 @@
+//! Benchmarks of `pallet_emergency_upgrade`.
+
+use frame_benchmarking::v2::*;
+use frame_support::traits::EnsureOrigin;
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
+use sp_std::vec;
+
+use super::*;
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn authorize_upgrade() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AuthorizeOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, T::Hash::default());
+
+		Ok(())
+	}
+
+	// Runtime codes stay below the maximum length of a preimage, of 4 MiB.
+	#[benchmark]
+	fn schedule_upgrade(c: Linear<1, { 3 * 1024 * 1024 }>) {
+		let code = vec![0u8; c as usize];
+		let when = frame_system::Pallet::<T>::block_number() + BlockNumberFor::<T>::from(10u32);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, code, when);
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
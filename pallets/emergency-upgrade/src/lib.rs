@@ -0,0 +1,126 @@
+//! Allows runtime upgrades to be authorized by an origin other than root, so that governance
+//! upgrades the runtime in two steps rather than via `sudo(system.set_code)`: the hash of the code
+//! is authorized, after which anyone can apply the code via `system.apply_authorized_upgrade`, e.g.
+//! with `pop-node upgrade submit`. The technical committee can thereby upgrade the runtime in an
+//! emergency.
+//!
+//! Root can also schedule an upgrade at a given block, so that it lands in a maintenance window
+//! announced in advance.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{
+			schedule::{
+				v3::{Named, TaskName},
+				DispatchTime, HIGHEST_PRIORITY,
+			},
+			StorePreimage,
+		},
+	};
+	use frame_system::{pallet_prelude::*, RawOrigin, WeightInfo as _};
+	use sp_runtime::traits::Hash;
+	use sp_std::vec::Vec;
+
+	use super::WeightInfo;
+
+	/// The name of the scheduler task upgrading the runtime to the code with `code_hash`. Used to
+	/// cancel the upgrade via `scheduler.cancel_named`.
+	pub fn upgrade_task_name<H: Encode>(code_hash: &H) -> TaskName {
+		(b"pop/upgrade", code_hash).using_encoded(sp_io::hashing::blake2_256)
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The origin allowed to authorize a runtime upgrade.
+		type AuthorizeOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// The aggregated call, in which the upgrades are scheduled.
+		type RuntimeCall: From<Call<Self>> + Encode;
+		/// The origin the scheduled upgrades are applied from.
+		type PalletsOrigin: From<RawOrigin<Self::AccountId>>;
+		/// The scheduler of the upgrades.
+		type Scheduler: Named<
+			BlockNumberFor<Self>,
+			<Self as Config>::RuntimeCall,
+			Self::PalletsOrigin,
+			Hasher = Self::Hashing,
+		>;
+		/// The preimages of the codes of the scheduled upgrades.
+		type Preimages: StorePreimage<H = Self::Hashing>;
+		/// The weights of the calls of the pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Authorize an upgrade to the runtime with the given `code_hash`, which can then be
+		/// applied by anyone via `system.apply_authorized_upgrade`.
+		///
+		/// Equivalent to `system.authorize_upgrade`, but requiring `AuthorizeOrigin` rather than
+		/// root.
+		#[pallet::call_index(0)]
+		#[pallet::weight((T::WeightInfo::authorize_upgrade(), DispatchClass::Operational))]
+		pub fn authorize_upgrade(origin: OriginFor<T>, code_hash: T::Hash) -> DispatchResult {
+			T::AuthorizeOrigin::ensure_origin(origin)?;
+			frame_system::Pallet::<T>::do_authorize_upgrade(code_hash, true);
+			Ok(())
+		}
+
+		/// Schedule the runtime upgrade to `code` at block `when`, via `apply_upgrade`.
+		///
+		/// The upgrade is only authorized at `when`, as an authorized upgrade could be applied by
+		/// anyone before then. The task is named by [`upgrade_task_name`] and `code` is noted as
+		/// preimage.
+		#[pallet::call_index(1)]
+		#[pallet::weight((T::WeightInfo::schedule_upgrade(code.len() as u32), DispatchClass::Operational))]
+		pub fn schedule_upgrade(
+			origin: OriginFor<T>,
+			code: Vec<u8>,
+			when: BlockNumberFor<T>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let id = upgrade_task_name(&T::Hashing::hash(&code));
+			let call = <T as Config>::RuntimeCall::from(Call::<T>::apply_upgrade { code });
+			T::Scheduler::schedule_named(
+				id,
+				DispatchTime::At(when),
+				None,
+				HIGHEST_PRIORITY,
+				RawOrigin::Root.into(),
+				T::Preimages::bound(call)?,
+			)?;
+			Ok(())
+		}
+
+		/// Authorize and apply the upgrade to `code`, as a scheduled upgrade. The version of `code`
+		/// is checked like for `system.authorize_upgrade`.
+		#[pallet::call_index(2)]
+		#[pallet::weight((
+			T::SystemWeightInfo::authorize_upgrade()
+				.saturating_add(T::SystemWeightInfo::apply_authorized_upgrade()),
+			DispatchClass::Operational,
+		))]
+		pub fn apply_upgrade(origin: OriginFor<T>, code: Vec<u8>) -> DispatchResultWithPostInfo {
+			ensure_root(origin.clone())?;
+			frame_system::Pallet::<T>::do_authorize_upgrade(T::Hashing::hash(&code), true);
+			frame_system::Pallet::<T>::apply_authorized_upgrade(origin, code)
+		}
+	}
+}
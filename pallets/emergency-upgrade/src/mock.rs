@@ -0,0 +1,93 @@
+use codec::Encode;
+use frame_support::{
+	derive_impl, ord_parameter_types, parameter_types,
+	traits::{ConstU32, EqualPrivilegeOnly},
+	weights::Weight,
+};
+use frame_system::{EnsureRoot, EnsureSignedBy};
+use sp_core::traits::{Externalities, ReadRuntimeVersion, ReadRuntimeVersionExt};
+use sp_runtime::BuildStorage;
+use sp_version::RuntimeVersion;
+
+use crate as pallet_emergency_upgrade;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub(crate) const AUTHORITY: u64 = 1;
+pub(crate) const ALICE: u64 = 2;
+
+frame_support::construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		Balances: pallet_balances,
+		Preimage: pallet_preimage,
+		Scheduler: pallet_scheduler,
+		EmergencyUpgrade: pallet_emergency_upgrade,
+	}
+);
+
+ord_parameter_types! {
+	pub const Authority: u64 = AUTHORITY;
+}
+
+parameter_types! {
+	pub MaximumSchedulerWeight: Weight = Weight::MAX;
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type AccountData = pallet_balances::AccountData<u64>;
+	type Block = Block;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type AccountStore = System;
+}
+
+impl pallet_preimage::Config for Test {
+	type Consideration = ();
+	type Currency = Balances;
+	type ManagerOrigin = EnsureRoot<u64>;
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+}
+
+impl pallet_scheduler::Config for Test {
+	type MaxScheduledPerBlock = ConstU32<10>;
+	type MaximumWeight = MaximumSchedulerWeight;
+	type OriginPrivilegeCmp = EqualPrivilegeOnly;
+	type PalletsOrigin = OriginCaller;
+	type Preimages = Preimage;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeOrigin = RuntimeOrigin;
+	type ScheduleOrigin = EnsureRoot<u64>;
+	type WeightInfo = ();
+}
+
+impl pallet_emergency_upgrade::Config for Test {
+	type AuthorizeOrigin = EnsureSignedBy<Authority, u64>;
+	type PalletsOrigin = OriginCaller;
+	type Preimages = Preimage;
+	type RuntimeCall = RuntimeCall;
+	type Scheduler = Scheduler;
+	type WeightInfo = ();
+}
+
+/// Reads any code as a runtime with a higher spec version than the current one.
+struct NextVersion;
+
+impl ReadRuntimeVersion for NextVersion {
+	fn read_runtime_version(&self, _: &[u8], _: &mut dyn Externalities) -> Result<Vec<u8>, String> {
+		Ok(RuntimeVersion { spec_version: 1, ..Default::default() }.encode())
+	}
+}
+
+pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
+	let storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.register_extension(ReadRuntimeVersionExt::new(NextVersion));
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,93 @@
+use frame_support::{assert_noop, assert_ok, storage::unhashed, traits::Hooks};
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash},
+	DispatchError,
+};
+
+use crate::{mock::*, upgrade_task_name};
+
+#[test]
+fn authorize_upgrade_requires_authorize_origin() {
+	new_test_ext().execute_with(|| {
+		let code_hash = BlakeTwo256::hash(b"code");
+		assert_noop!(
+			EmergencyUpgrade::authorize_upgrade(RuntimeOrigin::signed(ALICE), code_hash),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(EmergencyUpgrade::authorize_upgrade(
+			RuntimeOrigin::signed(AUTHORITY),
+			code_hash
+		));
+		System::assert_last_event(
+			frame_system::Event::UpgradeAuthorized { code_hash, check_version: true }.into(),
+		);
+		assert_ok!(System::apply_authorized_upgrade(
+			RuntimeOrigin::signed(ALICE),
+			b"code".to_vec()
+		));
+		assert_eq!(
+			unhashed::get_raw(sp_core::storage::well_known_keys::CODE),
+			Some(b"code".to_vec())
+		);
+	});
+}
+
+#[test]
+fn apply_upgrade_requires_root() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			EmergencyUpgrade::apply_upgrade(RuntimeOrigin::signed(AUTHORITY), b"code".to_vec()),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn scheduled_upgrade_is_applied_at_target_block() {
+	new_test_ext().execute_with(|| {
+		let code = b"code".to_vec();
+		let task = upgrade_task_name(&BlakeTwo256::hash(&code));
+		assert_noop!(
+			EmergencyUpgrade::schedule_upgrade(RuntimeOrigin::signed(AUTHORITY), code.clone(), 5),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(EmergencyUpgrade::schedule_upgrade(RuntimeOrigin::root(), code.clone(), 5));
+
+		for n in 2..=5 {
+			// Nothing is authorized, so the upgrade cannot be applied before the target block.
+			assert_noop!(
+				System::apply_authorized_upgrade(RuntimeOrigin::signed(ALICE), code.clone()),
+				frame_system::Error::<Test>::NothingAuthorized
+			);
+			assert_ne!(
+				unhashed::get_raw(sp_core::storage::well_known_keys::CODE),
+				Some(code.clone())
+			);
+
+			System::set_block_number(n);
+			Scheduler::on_initialize(n);
+		}
+		System::assert_has_event(
+			pallet_scheduler::Event::Dispatched { task: (5, 0), id: Some(task), result: Ok(()) }
+				.into(),
+		);
+		assert_eq!(unhashed::get_raw(sp_core::storage::well_known_keys::CODE), Some(code));
+		assert!(System::authorized_upgrade().is_none());
+	});
+}
+
+#[test]
+fn scheduled_upgrade_can_be_cancelled_by_name() {
+	new_test_ext().execute_with(|| {
+		let code = b"code".to_vec();
+		assert_ok!(EmergencyUpgrade::schedule_upgrade(RuntimeOrigin::root(), code.clone(), 5));
+		assert_ok!(Scheduler::cancel_named(
+			RuntimeOrigin::root(),
+			upgrade_task_name(&BlakeTwo256::hash(&code))
+		));
+
+		System::set_block_number(5);
+		Scheduler::on_initialize(5);
+		assert_ne!(unhashed::get_raw(sp_core::storage::well_known_keys::CODE), Some(code));
+	});
+}
@@ -0,0 +1,52 @@
+//! Weights for `pallet_emergency_upgrade`.
+//!
+//! Not measured: these are estimates written by hand from the storage each call accesses, to be
+//! replaced with the weights generated on the reference hardware by
+//! `scripts/benchmark-pallets.sh emergency-upgrade` before relying on them on a live chain.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use core::marker::PhantomData;
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions needed for `pallet_emergency_upgrade`.
+pub trait WeightInfo {
+	fn authorize_upgrade() -> Weight;
+	fn schedule_upgrade(c: u32) -> Weight;
+}
+
+/// Estimated weights for `pallet_emergency_upgrade`.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn authorize_upgrade() -> Weight {
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn schedule_upgrade(c: u32) -> Weight {
+		Weight::from_parts(40_000_000, 13_928)
+			.saturating_add(Weight::from_parts(1_500, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	fn authorize_upgrade() -> Weight {
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn schedule_upgrade(c: u32) -> Weight {
+		Weight::from_parts(40_000_000, 13_928)
+			.saturating_add(Weight::from_parts(1_500, 0).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+}
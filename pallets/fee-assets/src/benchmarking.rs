@@ -0,0 +1,30 @@
+//! Benchmarks of `pallet_fee_assets`.
+
+use codec::Decode;
+use frame_benchmarking::v2::*;
+use frame_support::traits::EnsureOrigin;
+use sp_runtime::{traits::TrailingZeroInput, FixedU128};
+
+use super::*;
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn set_rate() -> Result<(), BenchmarkError> {
+		let origin =
+			T::RateOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let asset = T::AssetId::decode(&mut TrailingZeroInput::zeroes())
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let rate = FixedU128::from_u32(2);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, asset, Some(rate));
+
+		assert_eq!(Rates::<T>::get(asset), Some(rate));
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
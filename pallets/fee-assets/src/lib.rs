@@ -0,0 +1,108 @@
+//! Sets the assets transaction fees can be paid in, along with the rate at which native fees are
+//! converted to fees in each, so that governance can accept e.g. stablecoins for fees and follow
+//! their price without changing the runtime.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{pallet_prelude::*, DefaultNoBound};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::{
+		traits::{MaybeSerializeDeserialize, Zero},
+		FixedU128,
+	};
+	use sp_std::vec::Vec;
+
+	use super::WeightInfo;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The identifier of the assets fees can be paid in.
+		type AssetId: Member + Parameter + Copy + MaybeSerializeDeserialize + MaxEncodedLen;
+		/// The origin allowed to set the rates.
+		type RateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// The weights of the calls of the pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The assets fees can be paid in, along with the amount of each charged per unit of native
+	/// fees.
+	#[pallet::storage]
+	pub type Rates<T: Config> = StorageMap<_, Blake2_128Concat, T::AssetId, FixedU128>;
+
+	#[pallet::genesis_config]
+	#[derive(DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		/// The assets fees can be paid in when the chain starts, along with their rates.
+		pub rates: Vec<(T::AssetId, FixedU128)>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			for (asset, rate) in &self.rates {
+				assert!(!rate.is_zero(), "fee asset rates must not be zero");
+				Rates::<T>::insert(asset, rate);
+			}
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Fees can be paid in `asset`, at `rate` per unit of native fees.
+		RateSet { asset: T::AssetId, rate: FixedU128 },
+		/// Fees can no longer be paid in `asset`.
+		RateRemoved { asset: T::AssetId },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// A zero rate would waive the fees paid in the asset.
+		ZeroRate,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the rate at which fees paid in `asset` are converted from native fees, or stop
+		/// accepting the asset for fees without a rate.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::set_rate())]
+		pub fn set_rate(
+			origin: OriginFor<T>,
+			asset: T::AssetId,
+			rate: Option<FixedU128>,
+		) -> DispatchResult {
+			T::RateOrigin::ensure_origin(origin)?;
+			match rate {
+				Some(rate) => {
+					ensure!(!rate.is_zero(), Error::<T>::ZeroRate);
+					Rates::<T>::insert(asset, rate);
+					Self::deposit_event(Event::RateSet { asset, rate });
+				},
+				None => {
+					Rates::<T>::remove(asset);
+					Self::deposit_event(Event::RateRemoved { asset });
+				},
+			}
+			Ok(())
+		}
+	}
+}
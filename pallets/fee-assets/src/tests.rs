@@ -0,0 +1,53 @@
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::{traits::Zero, DispatchError, FixedU128};
+
+use crate::{mock::*, Error, Event, Rates};
+
+const ASSET: u32 = 1;
+
+#[test]
+fn rates_are_set_and_removed_by_rate_origin() {
+	let half = FixedU128::from_rational(1, 2);
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			FeeAssets::set_rate(RuntimeOrigin::signed(1), ASSET, Some(half)),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(FeeAssets::set_rate(RuntimeOrigin::root(), ASSET, Some(half)));
+		assert_eq!(Rates::<Test>::get(ASSET), Some(half));
+		System::assert_last_event(Event::RateSet { asset: ASSET, rate: half }.into());
+
+		assert_ok!(FeeAssets::set_rate(RuntimeOrigin::root(), ASSET, None));
+		assert_eq!(Rates::<Test>::get(ASSET), None);
+		System::assert_last_event(Event::RateRemoved { asset: ASSET }.into());
+	});
+}
+
+#[test]
+fn rates_are_not_zero() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			FeeAssets::set_rate(RuntimeOrigin::root(), ASSET, Some(FixedU128::zero())),
+			Error::<Test>::ZeroRate
+		);
+	});
+}
+
+#[test]
+fn genesis_sets_rates() {
+	use sp_runtime::BuildStorage;
+	let storage = crate::GenesisConfig::<Test> { rates: vec![(ASSET, FixedU128::from_u32(2))] }
+		.build_storage()
+		.unwrap();
+	sp_io::TestExternalities::new(storage)
+		.execute_with(|| assert_eq!(Rates::<Test>::get(ASSET), Some(FixedU128::from_u32(2))));
+}
+
+#[test]
+#[should_panic(expected = "fee asset rates must not be zero")]
+fn genesis_rejects_zero_rates() {
+	use sp_runtime::BuildStorage;
+	let _ =
+		crate::GenesisConfig::<Test> { rates: vec![(ASSET, FixedU128::zero())] }.build_storage();
+}
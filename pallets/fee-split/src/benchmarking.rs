@@ -0,0 +1,27 @@
+//! Benchmarks of `pallet_fee_split`.
+
+use frame_benchmarking::v2::*;
+use frame_support::traits::EnsureOrigin;
+use sp_runtime::Percent;
+
+use super::*;
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn set_split() -> Result<(), BenchmarkError> {
+		let origin =
+			T::SplitOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let split = Shares { burn: Percent::from_percent(20), treasury: Percent::from_percent(30) };
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, split);
+
+		assert_eq!(Split::<T>::get(), split);
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,117 @@
+//! Sets the shares of transaction fees which are burnt and which fund the treasury, the rest
+//! rewarding the collators, so that the fee policy can be changed by governance without changing
+//! the runtime.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{pallet_prelude::*, DefaultNoBound, Deserialize, Serialize};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::Percent;
+
+	use super::WeightInfo;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The origin allowed to set the split.
+		type SplitOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// The weights of the calls of the pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The shares of transaction fees which are burnt and which fund the treasury, which add up
+	/// to at most 100%. The collators are rewarded with the rest.
+	#[derive(
+		Clone,
+		Copy,
+		Debug,
+		Default,
+		PartialEq,
+		Eq,
+		Encode,
+		Decode,
+		MaxEncodedLen,
+		TypeInfo,
+		Serialize,
+		Deserialize,
+	)]
+	#[serde(crate = "frame_support::__private::serde")]
+	pub struct Shares {
+		/// The share of the fees which is burnt.
+		pub burn: Percent,
+		/// The share of the fees paid to the treasury.
+		pub treasury: Percent,
+	}
+
+	impl Shares {
+		/// Whether the shares add up to at most 100%.
+		pub fn is_valid(&self) -> bool {
+			self.burn.deconstruct().saturating_add(self.treasury.deconstruct()) <= 100
+		}
+	}
+
+	/// The current split, rewarding the collators with all fees unless set.
+	#[pallet::storage]
+	pub type Split<T> = StorageValue<_, Shares, ValueQuery>;
+
+	#[pallet::genesis_config]
+	#[derive(DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		/// The split the chain starts with.
+		pub split: Shares,
+		#[serde(skip)]
+		pub _config: PhantomData<T>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			assert!(self.split.is_valid(), "the shares of the fee split exceed 100%");
+			Split::<T>::put(self.split);
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The fee split was changed.
+		SplitSet { split: Shares },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The shares of the split add up to more than 100%.
+		InvalidSplit,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the shares of transaction fees which are burnt and which fund the treasury.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::set_split())]
+		pub fn set_split(origin: OriginFor<T>, split: Shares) -> DispatchResult {
+			T::SplitOrigin::ensure_origin(origin)?;
+			ensure!(split.is_valid(), Error::<T>::InvalidSplit);
+			Split::<T>::put(split);
+			Self::deposit_event(Event::SplitSet { split });
+			Ok(())
+		}
+	}
+}
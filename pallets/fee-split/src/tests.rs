@@ -0,0 +1,48 @@
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::{DispatchError, Percent};
+
+use crate::{mock::*, Error, Event, Shares, Split};
+
+fn shares(burn: u8, treasury: u8) -> Shares {
+	Shares { burn: Percent::from_percent(burn), treasury: Percent::from_percent(treasury) }
+}
+
+#[test]
+fn collators_are_rewarded_with_all_fees_unless_set() {
+	new_test_ext().execute_with(|| assert_eq!(Split::<Test>::get(), shares(0, 0)));
+}
+
+#[test]
+fn split_is_set_by_split_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			FeeSplit::set_split(RuntimeOrigin::signed(1), shares(20, 30)),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(FeeSplit::set_split(RuntimeOrigin::root(), shares(20, 30)));
+		assert_eq!(Split::<Test>::get(), shares(20, 30));
+		System::assert_last_event(Event::SplitSet { split: shares(20, 30) }.into());
+	});
+}
+
+#[test]
+fn shares_add_up_to_at_most_100_percent() {
+	assert!(shares(100, 0).is_valid());
+	assert!(shares(40, 60).is_valid());
+	assert!(!shares(80, 30).is_valid());
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			FeeSplit::set_split(RuntimeOrigin::root(), shares(80, 30)),
+			Error::<Test>::InvalidSplit
+		);
+	});
+}
+
+#[test]
+#[should_panic(expected = "the shares of the fee split exceed 100%")]
+fn genesis_rejects_invalid_split() {
+	use sp_runtime::BuildStorage;
+	let _ = crate::GenesisConfig::<Test> { split: shares(80, 30), _config: Default::default() }
+		.build_storage();
+}
@@ -0,0 +1,49 @@
+//! Benchmarks of `pallet_forced_candidates`.
+
+use codec::Decode;
+use frame_benchmarking::v2::*;
+use frame_support::traits::{EnsureOrigin, Get};
+use frame_system::RawOrigin;
+use pallet_collator_selection::{CandidateInfo, CandidateList};
+use sp_runtime::traits::{TrailingZeroInput, Zero};
+use sp_std::vec::Vec;
+
+use super::*;
+
+#[benchmarks(where T: pallet_session::Config)]
+mod benchmarks {
+	use super::*;
+
+	// The forced candidate is inserted before `c` bonded candidates.
+	#[benchmark]
+	fn force_register_candidate(
+		c: Linear<0, { <T as pallet_collator_selection::Config>::MaxCandidates::get() - 1 }>,
+	) -> Result<(), BenchmarkError> {
+		let origin =
+			T::ForceOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		CandidateList::<T>::try_mutate(|candidates| {
+			(0..c).try_for_each(|i| {
+				candidates
+					.try_push(CandidateInfo { who: account("bonded", i, 0), deposit: Zero::zero() })
+			})
+		})
+		.map_err(|_| BenchmarkError::Stop("too many candidates"))?;
+		let who: T::AccountId = account("forced", 0, 0);
+		let keys = <T as pallet_session::Config>::Keys::decode(&mut TrailingZeroInput::zeroes())
+			.map_err(|_| BenchmarkError::Weightless)?;
+		frame_system::Pallet::<T>::inc_providers(&who);
+		pallet_session::Pallet::<T>::set_keys(
+			RawOrigin::Signed(who.clone()).into(),
+			keys,
+			Vec::new(),
+		)?;
+
+		#[extrinsic_call]
+		_(origin as <T as frame_system::Config>::RuntimeOrigin, who.clone());
+
+		assert_eq!(CandidateList::<T>::get().first().map(|candidate| &candidate.who), Some(&who));
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
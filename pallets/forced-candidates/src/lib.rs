@@ -0,0 +1,94 @@
+//! Lets a privileged origin register collators as candidates of `pallet_collator_selection`
+//! without a bond, e.g. to bootstrap the collators of a testnet without funding their accounts.
+//!
+//! Forced candidates are ordered before all bonded candidates, so they are only selected as
+//! collators while there are fewer bonded candidates than desired. Like any candidate, they are
+//! kicked once they stop authoring blocks.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{pallet_prelude::*, traits::ValidatorRegistration};
+	use frame_system::pallet_prelude::*;
+	use pallet_collator_selection::{
+		CandidateInfo, CandidateList, Error as CollatorSelectionError, Invulnerables,
+		LastAuthoredBlock,
+	};
+	use sp_runtime::traits::{Convert, Zero};
+
+	use super::WeightInfo;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_collator_selection::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The origin which may register candidates without a bond.
+		type ForceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// The weights of the calls of the pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// `account` was registered as candidate without a bond.
+		CandidateForced { account: T::AccountId },
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register `who` as candidate without reserving a bond. The session keys of `who` must be
+		/// registered already.
+		#[pallet::call_index(0)]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::force_register_candidate(T::MaxCandidates::get())
+		)]
+		pub fn force_register_candidate(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			ensure!(
+				!Invulnerables::<T>::get().contains(&who),
+				CollatorSelectionError::<T>::AlreadyInvulnerable
+			);
+			let validator = T::ValidatorIdOf::convert(who.clone())
+				.ok_or(CollatorSelectionError::<T>::NoAssociatedValidatorId)?;
+			ensure!(
+				T::ValidatorRegistration::is_registered(&validator),
+				CollatorSelectionError::<T>::ValidatorNotRegistered
+			);
+			CandidateList::<T>::try_mutate(|candidates| -> DispatchResult {
+				ensure!(
+					!candidates.iter().any(|candidate| candidate.who == who),
+					CollatorSelectionError::<T>::AlreadyCandidate
+				);
+				// The candidate list is ordered by ascending deposit.
+				candidates
+					.try_insert(0, CandidateInfo { who: who.clone(), deposit: Zero::zero() })
+					.map_err(|_| CollatorSelectionError::<T>::TooManyCandidates)?;
+				// Like bonded candidates, forced candidates are given until the kick threshold
+				// passes to author their first block.
+				LastAuthoredBlock::<T>::insert(
+					&who,
+					frame_system::Pallet::<T>::block_number() + T::KickThreshold::get(),
+				);
+				Ok(())
+			})?;
+			Self::deposit_event(Event::CandidateForced { account: who });
+			Ok(())
+		}
+	}
+}
@@ -0,0 +1,70 @@
+use frame_support::{assert_noop, assert_ok};
+use pallet_collator_selection::{CandidateInfo, CandidateList, Error, LastAuthoredBlock};
+use sp_runtime::DispatchError;
+
+use crate::{mock::*, Event};
+
+#[test]
+fn forced_candidate_becomes_collator_without_bond() {
+	new_test_ext().execute_with(|| {
+		// The session keys must be registered first.
+		assert_noop!(
+			ForcedCandidates::force_register_candidate(RuntimeOrigin::root(), CANDIDATE),
+			Error::<Test>::ValidatorNotRegistered
+		);
+		assert_ok!(Session::set_keys(RuntimeOrigin::signed(CANDIDATE), keys(CANDIDATE), vec![]));
+		assert_noop!(
+			ForcedCandidates::force_register_candidate(RuntimeOrigin::signed(CANDIDATE), CANDIDATE),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(ForcedCandidates::force_register_candidate(RuntimeOrigin::root(), CANDIDATE));
+		System::assert_last_event(Event::CandidateForced { account: CANDIDATE }.into());
+		assert_eq!(
+			CandidateList::<Test>::get().to_vec(),
+			vec![CandidateInfo { who: CANDIDATE, deposit: 0 }]
+		);
+		assert_eq!(Balances::reserved_balance(CANDIDATE), 0);
+		// Given until the kick threshold passes to author its first block.
+		assert_eq!(LastAuthoredBlock::<Test>::get(CANDIDATE), 1 + Period::get());
+
+		// The candidate is queued in the next session and collates from the one after.
+		Session::rotate_session();
+		assert!(!Session::validators().contains(&CANDIDATE));
+		Session::rotate_session();
+		assert!(Session::validators().contains(&CANDIDATE));
+	});
+}
+
+#[test]
+fn forced_candidates_are_ordered_before_bonded_candidates() {
+	new_test_ext().execute_with(|| {
+		CandidateList::<Test>::mutate(|candidates| {
+			candidates.try_push(CandidateInfo { who: 3, deposit: 100 }).unwrap()
+		});
+		assert_ok!(Session::set_keys(RuntimeOrigin::signed(CANDIDATE), keys(CANDIDATE), vec![]));
+		assert_ok!(ForcedCandidates::force_register_candidate(RuntimeOrigin::root(), CANDIDATE));
+		assert_eq!(
+			CandidateList::<Test>::get().to_vec(),
+			vec![
+				CandidateInfo { who: CANDIDATE, deposit: 0 },
+				CandidateInfo { who: 3, deposit: 100 }
+			]
+		);
+	});
+}
+
+#[test]
+fn candidates_and_invulnerables_cannot_be_forced() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ForcedCandidates::force_register_candidate(RuntimeOrigin::root(), INVULNERABLE),
+			Error::<Test>::AlreadyInvulnerable
+		);
+		assert_ok!(Session::set_keys(RuntimeOrigin::signed(CANDIDATE), keys(CANDIDATE), vec![]));
+		assert_ok!(ForcedCandidates::force_register_candidate(RuntimeOrigin::root(), CANDIDATE));
+		assert_noop!(
+			ForcedCandidates::force_register_candidate(RuntimeOrigin::root(), CANDIDATE),
+			Error::<Test>::AlreadyCandidate
+		);
+	});
+}
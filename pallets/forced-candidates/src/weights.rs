@@ -0,0 +1,43 @@
+//! Weights for `pallet_forced_candidates`.
+//!
+//! Not measured: these are estimates written by hand from the storage each call accesses, to be
+//! replaced with the weights generated on the reference hardware by
+//! `scripts/benchmark-pallets.sh forced-candidates` before relying on them on a live chain.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use core::marker::PhantomData;
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions needed for `pallet_forced_candidates`.
+pub trait WeightInfo {
+	fn force_register_candidate(c: u32) -> Weight;
+}
+
+/// Estimated weights for `pallet_forced_candidates`.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn force_register_candidate(c: u32) -> Weight {
+		Weight::from_parts(40_000_000, 6_287)
+			.saturating_add(Weight::from_parts(130_000, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	fn force_register_candidate(c: u32) -> Weight {
+		Weight::from_parts(40_000_000, 6_287)
+			.saturating_add(Weight::from_parts(130_000, 0).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+}
@@ -0,0 +1,59 @@
+//! Uploads contract codes at genesis, so that contracts can be instantiated by code hash from the
+//! first block.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+	use pallet_contracts::Determinism;
+	use sp_runtime::traits::Hash;
+	use sp_std::{collections::btree_set::BTreeSet, vec::Vec};
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_contracts::Config {}
+
+	#[pallet::genesis_config]
+	#[derive(frame_support::DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		/// The account uploading the codes, which pays their storage deposits.
+		pub owner: Option<T::AccountId>,
+		/// The wasm codes to upload. Identical codes are only uploaded once.
+		pub codes: Vec<Vec<u8>>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			let mut uploaded = BTreeSet::new();
+			for code in &self.codes {
+				let code_hash = T::Hashing::hash(code);
+				if !uploaded.insert(code_hash) {
+					continue;
+				}
+				let owner = self.owner.clone().expect("an owner is required to upload codes");
+				if let Err(e) = pallet_contracts::Pallet::<T>::bare_upload_code(
+					owner,
+					code.clone(),
+					None,
+					Determinism::Enforced,
+				) {
+					panic!(
+						"unable to upload the contract code {code_hash:?}, either invalid or its \
+						 deposit unaffordable: {e:?}"
+					);
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,34 @@
+use frame_support::{traits::fungible::InspectHold, weights::Weight};
+use pallet_contracts::{Code, CollectEvents, DebugInfo};
+use sp_runtime::traits::{BlakeTwo256, Hash};
+
+use crate::mock::*;
+
+#[test]
+fn genesis_codes_are_uploaded_once() {
+	let code = CONTRACT.to_vec();
+	let held = |codes| new_test_ext(codes).execute_with(|| Balances::total_balance_on_hold(&ALICE));
+	assert!(held(vec![code.clone()]) > 0);
+	assert_eq!(held(vec![code.clone(), code.clone()]), held(vec![code.clone()]));
+
+	new_test_ext(vec![code.clone()]).execute_with(|| {
+		let instantiated = Contracts::bare_instantiate(
+			ALICE,
+			0,
+			Weight::MAX,
+			None,
+			Code::Existing(BlakeTwo256::hash(&code)),
+			vec![],
+			vec![],
+			DebugInfo::Skip,
+			CollectEvents::Skip,
+		);
+		assert!(instantiated.result.is_ok());
+	});
+}
+
+#[test]
+#[should_panic(expected = "unable to upload the contract code")]
+fn invalid_genesis_code_panics() {
+	new_test_ext(vec![b"not wasm".to_vec()]);
+}
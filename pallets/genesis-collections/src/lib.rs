@@ -0,0 +1,52 @@
+//! Creates NFT collections at genesis, e.g. a sample collection for contracts to mint and transfer
+//! items of from the first block.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+	use frame_system::RawOrigin;
+	use pallet_nfts::{CollectionConfig, CollectionSettings, MintSettings};
+	use sp_runtime::traits::StaticLookup;
+	use sp_std::vec::Vec;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_nfts::Config {}
+
+	#[pallet::genesis_config]
+	#[derive(frame_support::DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		/// The owners of the collections to create, in order of their ids. Each owner is the
+		/// admin, issuer and freezer of its collection as well, and pays no deposit for it.
+		pub collections: Vec<T::AccountId>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			for owner in &self.collections {
+				pallet_nfts::Pallet::<T>::force_create(
+					RawOrigin::Root.into(),
+					T::Lookup::unlookup(owner.clone()),
+					CollectionConfig {
+						settings: CollectionSettings::all_enabled(),
+						max_supply: None,
+						mint_settings: MintSettings::default(),
+					},
+				)
+				.expect("root is able to create collections");
+			}
+		}
+	}
+}
@@ -0,0 +1,89 @@
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{AsEnsureOriginWithArg, ConstU32, ConstU64},
+};
+use frame_system::{EnsureRoot, EnsureSigned};
+use pallet_nfts::PalletFeatures;
+use sp_runtime::{
+	traits::{IdentifyAccount, IdentityLookup, Verify},
+	AccountId32, BuildStorage, MultiSignature,
+};
+
+use crate as pallet_genesis_collections;
+
+type AccountPublic = <MultiSignature as Verify>::Signer;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub(crate) const ALICE: AccountId32 = AccountId32::new([1; 32]);
+pub(crate) const BOB: AccountId32 = AccountId32::new([2; 32]);
+
+frame_support::construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		Balances: pallet_balances,
+		Nfts: pallet_nfts,
+		NftsGenesis: pallet_genesis_collections,
+	}
+);
+
+parameter_types! {
+	pub Features: PalletFeatures = PalletFeatures::all_enabled();
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type AccountData = pallet_balances::AccountData<u64>;
+	type AccountId = <AccountPublic as IdentifyAccount>::AccountId;
+	type Block = Block;
+	type Lookup = IdentityLookup<Self::AccountId>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type AccountStore = System;
+}
+
+impl pallet_nfts::Config for Test {
+	type ApprovalsLimit = ConstU32<10>;
+	type AttributeDepositBase = ConstU64<1>;
+	type CollectionDeposit = ConstU64<2>;
+	type CollectionId = u32;
+	type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<Self::AccountId>>;
+	type Currency = Balances;
+	type DepositPerByte = ConstU64<1>;
+	type Features = Features;
+	type ForceOrigin = EnsureRoot<Self::AccountId>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type Helper = ();
+	type ItemAttributesApprovalsLimit = ConstU32<2>;
+	type ItemDeposit = ConstU64<1>;
+	type ItemId = u32;
+	type KeyLimit = ConstU32<50>;
+	type Locker = ();
+	type MaxAttributesPerCall = ConstU32<2>;
+	type MaxDeadlineDuration = ConstU64<10000>;
+	type MaxTips = ConstU32<10>;
+	type MetadataDepositBase = ConstU64<1>;
+	type OffchainPublic = AccountPublic;
+	type OffchainSignature = MultiSignature;
+	type RuntimeEvent = RuntimeEvent;
+	type StringLimit = ConstU32<50>;
+	type ValueLimit = ConstU32<50>;
+	type WeightInfo = ();
+}
+
+impl pallet_genesis_collections::Config for Test {}
+
+/// The externalities of a chain creating a collection for each of `owners` at genesis.
+pub(crate) fn new_test_ext(owners: Vec<AccountId32>) -> sp_io::TestExternalities {
+	let storage = RuntimeGenesisConfig {
+		balances: pallet_balances::GenesisConfig { balances: vec![(ALICE, 100), (BOB, 100)] },
+		nfts_genesis: pallet_genesis_collections::GenesisConfig { collections: owners },
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,32 @@
+use frame_support::{assert_noop, assert_ok};
+
+use crate::mock::*;
+
+#[test]
+fn collections_are_created_in_order_of_owners() {
+	new_test_ext(vec![ALICE, BOB]).execute_with(|| {
+		assert_eq!(Nfts::collection_owner(0), Some(ALICE));
+		assert_eq!(Nfts::collection_owner(1), Some(BOB));
+		assert_eq!(Nfts::collection_owner(2), None);
+		// No deposit is paid for the collections.
+		assert_eq!(Balances::reserved_balance(&ALICE), 0);
+	});
+}
+
+#[test]
+fn owners_administer_their_collections() {
+	new_test_ext(vec![ALICE]).execute_with(|| {
+		// The owner is the issuer of its collection...
+		assert_ok!(Nfts::mint(RuntimeOrigin::signed(ALICE), 0, 1, ALICE, None));
+		assert_noop!(
+			Nfts::mint(RuntimeOrigin::signed(BOB), 0, 2, BOB, None),
+			pallet_nfts::Error::<Test>::NoPermission
+		);
+		// ...and its freezer.
+		assert_ok!(Nfts::lock_item_transfer(RuntimeOrigin::signed(ALICE), 0, 1));
+		assert_noop!(
+			Nfts::transfer(RuntimeOrigin::signed(ALICE), 0, 1, BOB),
+			pallet_nfts::Error::<Test>::ItemLocked
+		);
+	});
+}
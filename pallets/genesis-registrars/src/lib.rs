@@ -0,0 +1,46 @@
+//! Adds registrars of `pallet_identity` at genesis, e.g. so that identities can be judged on
+//! development chains without passing a motion of the council first.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+	use frame_system::RawOrigin;
+	use sp_runtime::traits::StaticLookup;
+	use sp_std::vec::Vec;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_identity::Config {}
+
+	#[pallet::genesis_config]
+	#[derive(frame_support::DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		/// The accounts of the registrars to add, in order of their indices. Each charges no fee
+		/// for its judgements until it sets one.
+		pub registrars: Vec<T::AccountId>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			for registrar in &self.registrars {
+				pallet_identity::Pallet::<T>::add_registrar(
+					RawOrigin::Root.into(),
+					T::Lookup::unlookup(registrar.clone()),
+				)
+				.expect("root is able to add registrars, up to `MaxRegistrars`");
+			}
+		}
+	}
+}
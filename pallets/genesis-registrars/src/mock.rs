@@ -0,0 +1,81 @@
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{ConstU32, ConstU64},
+};
+use frame_system::EnsureRoot;
+use pallet_identity::legacy::IdentityInfo;
+use sp_runtime::{
+	traits::{IdentifyAccount, IdentityLookup, Verify},
+	AccountId32, BuildStorage, MultiSignature,
+};
+
+use crate as pallet_genesis_registrars;
+
+type AccountPublic = <MultiSignature as Verify>::Signer;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub(crate) const ALICE: AccountId32 = AccountId32::new([1; 32]);
+pub(crate) const BOB: AccountId32 = AccountId32::new([2; 32]);
+
+frame_support::construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		Balances: pallet_balances,
+		Identity: pallet_identity,
+		IdentityGenesis: pallet_genesis_registrars,
+	}
+);
+
+parameter_types! {
+	pub const MaxRegistrars: u32 = 2;
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type AccountData = pallet_balances::AccountData<u64>;
+	type AccountId = <AccountPublic as IdentifyAccount>::AccountId;
+	type Block = Block;
+	type Lookup = IdentityLookup<Self::AccountId>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type AccountStore = System;
+}
+
+impl pallet_identity::Config for Test {
+	type BasicDeposit = ConstU64<10>;
+	type ByteDeposit = ConstU64<1>;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRoot<Self::AccountId>;
+	type IdentityInformation = IdentityInfo<ConstU32<2>>;
+	type MaxRegistrars = MaxRegistrars;
+	type MaxSubAccounts = ConstU32<2>;
+	type MaxSuffixLength = ConstU32<7>;
+	type MaxUsernameLength = ConstU32<32>;
+	type OffchainSignature = MultiSignature;
+	type PendingUsernameExpiration = ConstU64<100>;
+	type RegistrarOrigin = EnsureRoot<Self::AccountId>;
+	type RuntimeEvent = RuntimeEvent;
+	type SigningPublicKey = AccountPublic;
+	type Slashed = ();
+	type SubAccountDeposit = ConstU64<10>;
+	type UsernameAuthorityOrigin = EnsureRoot<Self::AccountId>;
+	type WeightInfo = ();
+}
+
+impl pallet_genesis_registrars::Config for Test {}
+
+/// The externalities of a chain adding `registrars` at genesis.
+pub(crate) fn new_test_ext(registrars: Vec<AccountId32>) -> sp_io::TestExternalities {
+	let storage = RuntimeGenesisConfig {
+		balances: pallet_balances::GenesisConfig { balances: vec![(BOB, 100)] },
+		identity_genesis: pallet_genesis_registrars::GenesisConfig { registrars },
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,47 @@
+use frame_support::assert_ok;
+use pallet_identity::{legacy::IdentityInfo, Data, Judgement};
+use sp_runtime::traits::{BlakeTwo256, Hash};
+
+use crate::mock::*;
+
+#[test]
+fn genesis_registrars_judge_identities() {
+	new_test_ext(vec![ALICE]).execute_with(|| {
+		let registrars = Identity::registrars();
+		assert_eq!(registrars.len(), 1);
+		assert_eq!(registrars[0].as_ref().map(|r| (&r.account, r.fee)), Some((&ALICE, 0)));
+
+		let info = IdentityInfo {
+			display: Data::Raw(b"bob".to_vec().try_into().unwrap()),
+			..Default::default()
+		};
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(BOB), Box::new(info.clone())));
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(BOB), 0, 0));
+		assert_ok!(Identity::provide_judgement(
+			RuntimeOrigin::signed(ALICE),
+			0,
+			BOB,
+			Judgement::Reasonable,
+			BlakeTwo256::hash_of(&info),
+		));
+		let (registration, _) = Identity::identity(&BOB).unwrap();
+		assert_eq!(registration.judgements.into_inner(), vec![(0, Judgement::Reasonable)]);
+	});
+}
+
+#[test]
+fn registrars_are_indexed_in_order() {
+	new_test_ext(vec![BOB, ALICE]).execute_with(|| {
+		let accounts = Identity::registrars()
+			.into_iter()
+			.map(|registrar| registrar.map(|r| r.account))
+			.collect::<Vec<_>>();
+		assert_eq!(accounts, vec![Some(BOB), Some(ALICE)]);
+	});
+}
+
+#[test]
+#[should_panic(expected = "root is able to add registrars")]
+fn registrars_beyond_maximum_panic() {
+	new_test_ext(vec![ALICE, BOB, ALICE]);
+}
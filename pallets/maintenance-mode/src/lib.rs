@@ -0,0 +1,124 @@
+//! Allows the chain to be paused, e.g. from genesis for a coordinated launch, so that only root
+//! and the calls allowed by `AllowedWhilePaused` can be dispatched until it is unpaused. Unlike
+//! safe mode, which the technical committee enters for a limited time, the chain stays paused
+//! until unpaused, and the pause authority can pause it without being a member of governance.
+//!
+//! Calls are filtered by including the pallet in the `BaseCallFilter` of the runtime, which root
+//! bypasses.
+//!
+//! A pause authority, e.g. a multisig, can be set at genesis and used via
+//! [`EnsurePauseAuthority`], so that the chain can be paused in an emergency
+//! without the full power of root.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{pallet_prelude::*, traits::Contains, DefaultNoBound};
+	use frame_system::{pallet_prelude::*, RawOrigin};
+
+	use super::WeightInfo;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The origin allowed to pause and unpause the chain.
+		type PauseOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// The calls which can still be dispatched while the chain is paused.
+		type AllowedWhilePaused: Contains<Self::RuntimeCall>;
+		/// The weights of the calls of the pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Whether the chain is paused.
+	#[pallet::storage]
+	pub type Paused<T> = StorageValue<_, bool, ValueQuery>;
+
+	/// The account allowed to pause and unpause the chain, if any.
+	#[pallet::storage]
+	pub type PauseAuthority<T: Config> = StorageValue<_, T::AccountId>;
+
+	#[pallet::genesis_config]
+	#[derive(DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		/// Whether the chain starts paused.
+		pub paused: bool,
+		/// The account allowed to pause and unpause the chain, if any.
+		pub pause_authority: Option<T::AccountId>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			Paused::<T>::put(self.paused);
+			if let Some(authority) = &self.pause_authority {
+				PauseAuthority::<T>::put(authority);
+			}
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The chain was paused.
+		Paused,
+		/// The chain was unpaused.
+		Unpaused,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Pause or unpause the chain.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::set_paused())]
+		pub fn set_paused(origin: OriginFor<T>, paused: bool) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+			Paused::<T>::put(paused);
+			Self::deposit_event(if paused { Event::Paused } else { Event::Unpaused });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Contains<T::RuntimeCall> for Pallet<T> {
+		fn contains(call: &T::RuntimeCall) -> bool {
+			!Paused::<T>::get() || T::AllowedWhilePaused::contains(call)
+		}
+	}
+
+	/// Ensures that the origin is signed by the pause authority, which is not granted any other
+	/// privilege.
+	pub struct EnsurePauseAuthority<T>(PhantomData<T>);
+	impl<T: Config> EnsureOrigin<OriginFor<T>> for EnsurePauseAuthority<T> {
+		type Success = T::AccountId;
+
+		fn try_origin(o: OriginFor<T>) -> Result<Self::Success, OriginFor<T>> {
+			o.into().and_then(|o| match o {
+				RawOrigin::Signed(who) if PauseAuthority::<T>::get().as_ref() == Some(&who) =>
+					Ok(who),
+				o => Err(o.into()),
+			})
+		}
+
+		#[cfg(feature = "runtime-benchmarks")]
+		fn try_successful_origin() -> Result<OriginFor<T>, ()> {
+			PauseAuthority::<T>::get()
+				.map(|authority| RawOrigin::Signed(authority).into())
+				.ok_or(())
+		}
+	}
+}
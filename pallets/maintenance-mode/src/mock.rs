@@ -0,0 +1,50 @@
+use frame_support::{
+	derive_impl,
+	traits::{Contains, EitherOfDiverse},
+};
+use frame_system::EnsureRoot;
+use sp_runtime::BuildStorage;
+
+use crate::{self as pallet_maintenance_mode, EnsurePauseAuthority};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub(crate) const AUTHORITY: u64 = 1;
+pub(crate) const ALICE: u64 = 2;
+
+frame_support::construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		MaintenanceMode: pallet_maintenance_mode,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+}
+
+/// Remarks can still be made while paused, unlike any other call.
+pub struct AllowedWhilePaused;
+impl Contains<RuntimeCall> for AllowedWhilePaused {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(call, RuntimeCall::System(frame_system::Call::remark { .. }))
+	}
+}
+
+impl pallet_maintenance_mode::Config for Test {
+	type AllowedWhilePaused = AllowedWhilePaused;
+	type PauseOrigin = EitherOfDiverse<EnsureRoot<u64>, EnsurePauseAuthority<Test>>;
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+}
+
+pub(crate) fn new_test_ext(paused: bool) -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_maintenance_mode::GenesisConfig::<Test> { paused, pause_authority: Some(AUTHORITY) }
+		.assimilate_storage(&mut storage)
+		.unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,59 @@
+use frame_support::{assert_noop, assert_ok, traits::Contains};
+use sp_runtime::DispatchError;
+
+use crate::{mock::*, Event, PauseAuthority, Paused};
+
+fn remark() -> RuntimeCall {
+	RuntimeCall::System(frame_system::Call::remark { remark: vec![] })
+}
+
+fn remark_with_event() -> RuntimeCall {
+	RuntimeCall::System(frame_system::Call::remark_with_event { remark: vec![] })
+}
+
+#[test]
+fn genesis_sets_paused_state_and_authority() {
+	new_test_ext(true).execute_with(|| {
+		assert!(Paused::<Test>::get());
+		assert_eq!(PauseAuthority::<Test>::get(), Some(AUTHORITY));
+	});
+	new_test_ext(false).execute_with(|| assert!(!Paused::<Test>::get()));
+}
+
+#[test]
+fn only_allowed_calls_pass_while_paused() {
+	new_test_ext(true).execute_with(|| {
+		assert!(MaintenanceMode::contains(&remark()));
+		assert!(!MaintenanceMode::contains(&remark_with_event()));
+
+		assert_ok!(MaintenanceMode::set_paused(RuntimeOrigin::root(), false));
+		assert!(MaintenanceMode::contains(&remark_with_event()));
+		System::assert_last_event(Event::Unpaused.into());
+	});
+}
+
+#[test]
+fn pause_authority_can_pause_and_unpause() {
+	new_test_ext(false).execute_with(|| {
+		assert_ok!(MaintenanceMode::set_paused(RuntimeOrigin::signed(AUTHORITY), true));
+		assert!(Paused::<Test>::get());
+		System::assert_last_event(Event::Paused.into());
+
+		assert_ok!(MaintenanceMode::set_paused(RuntimeOrigin::signed(AUTHORITY), false));
+		assert!(!Paused::<Test>::get());
+	});
+}
+
+#[test]
+fn others_cannot_pause() {
+	new_test_ext(false).execute_with(|| {
+		assert_noop!(
+			MaintenanceMode::set_paused(RuntimeOrigin::signed(ALICE), true),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			MaintenanceMode::set_paused(RuntimeOrigin::none(), true),
+			DispatchError::BadOrigin
+		);
+	});
+}
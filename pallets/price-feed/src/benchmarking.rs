@@ -0,0 +1,42 @@
+//! Benchmarks of `pallet_price_feed`.
+
+use frame_benchmarking::v2::*;
+use frame_support::{traits::Get, BoundedVec};
+use frame_system::RawOrigin;
+use sp_std::vec;
+
+use super::*;
+
+/// Keep as many prices as `MaxPrices`, so that the oldest is dropped by the next submission.
+fn fill_prices<T: Config>() {
+	Prices::<T>::put(BoundedVec::truncate_from(vec![1; T::MaxPrices::get() as usize]));
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn submit_price() {
+		fill_prices::<T>();
+		let who: T::AccountId = whitelisted_caller();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(who), 2);
+
+		assert_eq!(Prices::<T>::get().last(), Some(&2));
+	}
+
+	#[benchmark]
+	fn submit_price_unsigned() {
+		fill_prices::<T>();
+		let block_number = frame_system::Pallet::<T>::block_number();
+
+		#[extrinsic_call]
+		_(RawOrigin::None, block_number, 2);
+
+		assert_eq!(Prices::<T>::get().last(), Some(&2));
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
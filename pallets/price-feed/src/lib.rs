@@ -0,0 +1,251 @@
+//! An example of an offchain worker, feeding a price from an HTTP source to the chain, as a
+//! template for oracles built on Pop.
+//!
+//! The worker of every node with a source set fetches the price at each block it imports, then
+//! submits it by either a signed transaction, on even blocks, or an unsigned one, on odd blocks:
+//! - The source is the URL of a plain decimal number, set in the persistent offchain storage of the
+//!   node under [`SOURCE_KEY`], e.g. via the `offchain_localStorageSet` RPC.
+//! - Signed transactions are signed by a key of type [`KEY_TYPE`] of the keystore, e.g. inserted
+//!   via the `author_insertKey` RPC, whose account pays their fees.
+//! - Unsigned transactions pay no fees, so only one is accepted per [`Config::UnsignedInterval`].
+//!
+//! The pallet keeps the latest prices submitted, of which it averages the price.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+	use frame_system::{
+		offchain::{
+			AppCrypto, CreateSignedTransaction, SendSignedTransaction, Signer, SubmitTransaction,
+		},
+		pallet_prelude::*,
+	};
+	use sp_core::crypto::KeyTypeId;
+	use sp_runtime::{
+		offchain::{http, Duration, StorageKind},
+		traits::Zero,
+		Saturating,
+	};
+	use sp_std::vec::Vec;
+
+	use super::WeightInfo;
+
+	/// The key type of the keys signing the transactions of the worker.
+	pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"pric");
+
+	/// The key of the URL of the price source in the persistent offchain storage.
+	pub const SOURCE_KEY: &[u8] = b"pop::price-feed::source";
+
+	/// The time the worker waits for the source to respond, in milliseconds.
+	const FETCH_TIMEOUT: u64 = 2_000;
+
+	const LOG_TARGET: &str = "runtime::price-feed";
+
+	/// The keys signing the transactions of the worker.
+	pub mod crypto {
+		use sp_core::sr25519::{Public as Sr25519Public, Signature as Sr25519Signature};
+		use sp_runtime::{
+			app_crypto::{app_crypto, sr25519},
+			MultiSignature, MultiSigner,
+		};
+
+		use super::KEY_TYPE;
+
+		app_crypto!(sr25519, KEY_TYPE);
+
+		/// The keys of the keystore signing the transactions of the worker.
+		pub struct AuthorityId;
+
+		impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for AuthorityId {
+			type GenericPublic = Sr25519Public;
+			type GenericSignature = Sr25519Signature;
+			type RuntimeAppPublic = Public;
+		}
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: CreateSignedTransaction<Call<Self>> + frame_system::Config {
+		/// The keys signing the transactions of the worker.
+		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+		/// The number of latest prices kept.
+		#[pallet::constant]
+		type MaxPrices: Get<u32>;
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The number of blocks after an unsigned submission before the next is accepted.
+		#[pallet::constant]
+		type UnsignedInterval: Get<BlockNumberFor<Self>>;
+		/// The priority of unsigned submissions in the transaction pool.
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+		/// The weights of the calls of the pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The latest prices submitted, from the oldest to the newest.
+	#[pallet::storage]
+	pub type Prices<T: Config> = StorageValue<_, BoundedVec<u32, T::MaxPrices>, ValueQuery>;
+
+	/// The block from which the next unsigned submission is accepted.
+	#[pallet::storage]
+	pub type NextUnsignedAt<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A price was submitted, by `who` unless unsigned.
+		PriceSubmitted { price: u32, who: Option<T::AccountId> },
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn offchain_worker(block_number: BlockNumberFor<T>) {
+			let submitted = Self::fetch_price().and_then(|price| {
+				if block_number % 2u32.into() == Zero::zero() {
+					Self::submit_signed(price)
+				} else {
+					Self::submit_unsigned(block_number, price)
+				}
+			});
+			if let Err(e) = submitted {
+				log::debug!(target: LOG_TARGET, "No price submitted at #{block_number:?}: {e}");
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Submit `price` as the latest price, paying the fee of the transaction.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::submit_price())]
+		pub fn submit_price(origin: OriginFor<T>, price: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::add_price(price, Some(who));
+			Ok(())
+		}
+
+		/// Submit `price` as the latest price without paying any fee, as fetched by an offchain
+		/// worker at `block_number`. Only accepted once per [`Config::UnsignedInterval`].
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::submit_price_unsigned())]
+		pub fn submit_price_unsigned(
+			origin: OriginFor<T>,
+			_block_number: BlockNumberFor<T>,
+			price: u32,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+			Self::add_price(price, None);
+			let now = frame_system::Pallet::<T>::block_number();
+			NextUnsignedAt::<T>::put(now.saturating_add(T::UnsignedInterval::get()));
+			Ok(())
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let Call::submit_price_unsigned { block_number, .. } = call else {
+				return InvalidTransaction::Call.into();
+			};
+			let next_unsigned_at = NextUnsignedAt::<T>::get();
+			if *block_number < next_unsigned_at {
+				return InvalidTransaction::Stale.into();
+			}
+			if *block_number > frame_system::Pallet::<T>::block_number() {
+				return InvalidTransaction::Future.into();
+			}
+			ValidTransaction::with_tag_prefix("PriceFeed")
+				.priority(T::UnsignedPriority::get())
+				// Only one unsigned submission is kept in the pool per interval.
+				.and_provides(next_unsigned_at)
+				.longevity(5)
+				.propagate(true)
+				.build()
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The average of the latest prices, if any were submitted.
+		pub fn average_price() -> Option<u32> {
+			let prices = Prices::<T>::get();
+			let sum = prices.iter().map(|price| *price as u64).sum::<u64>();
+			(!prices.is_empty()).then(|| (sum / prices.len() as u64) as u32)
+		}
+
+		/// Keep `price` as the latest price, dropping the oldest one if as many as `MaxPrices`
+		/// are kept already.
+		fn add_price(price: u32, who: Option<T::AccountId>) {
+			Prices::<T>::mutate(|prices| {
+				if prices.is_full() {
+					prices.remove(0);
+				}
+				let _ = prices.try_push(price);
+			});
+			Self::deposit_event(Event::PriceSubmitted { price, who });
+		}
+
+		/// Fetch the price from the source set in the offchain storage of the node.
+		fn fetch_price() -> Result<u32, &'static str> {
+			let source = sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, SOURCE_KEY)
+				.ok_or("no price source set")?;
+			let source = core::str::from_utf8(&source).map_err(|_| "invalid price source")?;
+			let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(FETCH_TIMEOUT));
+			let response = http::Request::get(source)
+				.deadline(deadline)
+				.send()
+				.map_err(|_| "unable to request the price")?
+				.try_wait(deadline)
+				.map_err(|_| "the price source timed out")?
+				.map_err(|_| "unable to fetch the price")?;
+			if response.code != 200 {
+				return Err("the price source failed");
+			}
+			let body = response.body().collect::<Vec<u8>>();
+			core::str::from_utf8(&body)
+				.ok()
+				.and_then(|body| body.trim().parse().ok())
+				.ok_or("the price is not a number")
+		}
+
+		/// Submit `price` by a transaction signed by a key of the keystore.
+		fn submit_signed(price: u32) -> Result<(), &'static str> {
+			let signer = Signer::<T, T::AuthorityId>::any_account();
+			match signer.send_signed_transaction(|_| Call::submit_price { price }) {
+				Some((_, Ok(()))) => Ok(()),
+				Some((_, Err(()))) => Err("unable to submit the signed transaction"),
+				None => Err("no key in the keystore to sign with"),
+			}
+		}
+
+		/// Submit `price`, fetched at `block_number`, by an unsigned transaction.
+		fn submit_unsigned(
+			block_number: BlockNumberFor<T>,
+			price: u32,
+		) -> Result<(), &'static str> {
+			if NextUnsignedAt::<T>::get() > block_number {
+				return Err("too early for an unsigned submission");
+			}
+			let call = Call::submit_price_unsigned { block_number, price };
+			SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into())
+				.map_err(|()| "unable to submit the unsigned transaction")
+		}
+	}
+}
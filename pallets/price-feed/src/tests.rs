@@ -0,0 +1,131 @@
+use codec::Decode;
+use frame_support::{
+	assert_ok, pallet_prelude::TransactionSource, traits::Hooks, unsigned::ValidateUnsigned,
+};
+use sp_core::offchain::{
+	testing::{PendingRequest, TestOffchainExt, TestTransactionPoolExt},
+	OffchainDbExt, OffchainWorkerExt, StorageKind, TransactionPoolExt,
+};
+use sp_keystore::{testing::MemoryKeystore, Keystore, KeystoreExt};
+use sp_runtime::{traits::BadOrigin, AccountId32};
+
+use crate::{mock::*, Call, Event, NextUnsignedAt, Prices, KEY_TYPE, SOURCE_KEY};
+
+const SOURCE: &str = "https://prices.example/dot-usd";
+
+#[test]
+fn worker_submits_fetched_prices() {
+	let (offchain, offchain_state) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let keystore = MemoryKeystore::new();
+	keystore.sr25519_generate_new(KEY_TYPE, None).unwrap();
+	let mut ext = new_test_ext();
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+	ext.register_extension(OffchainWorkerExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+	ext.register_extension(KeystoreExt::new(keystore));
+	let respond = |price: &[u8]| {
+		offchain_state.write().expect_request(PendingRequest {
+			method: "GET".into(),
+			uri: SOURCE.into(),
+			response: Some(price.to_vec()),
+			sent: true,
+			..Default::default()
+		})
+	};
+	let submitted = || {
+		let tx = pool_state.write().transactions.pop().expect("a price is submitted");
+		Extrinsic::decode(&mut &*tx).unwrap()
+	};
+
+	ext.execute_with(|| {
+		// Nothing is submitted until a source is set.
+		PriceFeed::offchain_worker(1);
+		assert!(pool_state.read().transactions.is_empty());
+		sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, SOURCE_KEY, SOURCE.as_bytes());
+
+		// Odd blocks submit unsigned transactions.
+		respond(b"1234\n");
+		PriceFeed::offchain_worker(1);
+		let tx = submitted();
+		assert!(tx.signature.is_none());
+		assert_eq!(
+			tx.call,
+			RuntimeCall::PriceFeed(Call::submit_price_unsigned { block_number: 1, price: 1234 })
+		);
+
+		// Even blocks submit transactions signed by the key of the keystore.
+		System::set_block_number(2);
+		respond(b"4321");
+		PriceFeed::offchain_worker(2);
+		let tx = submitted();
+		assert!(tx.signature.is_some());
+		assert_eq!(tx.call, RuntimeCall::PriceFeed(Call::submit_price { price: 4321 }));
+	});
+}
+
+#[test]
+fn worker_skips_prices_which_are_not_numbers() {
+	let (offchain, offchain_state) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let mut ext = new_test_ext();
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+	ext.register_extension(OffchainWorkerExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+
+	ext.execute_with(|| {
+		sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, SOURCE_KEY, SOURCE.as_bytes());
+		offchain_state.write().expect_request(PendingRequest {
+			method: "GET".into(),
+			uri: SOURCE.into(),
+			response: Some(b"not a price".to_vec()),
+			sent: true,
+			..Default::default()
+		});
+		PriceFeed::offchain_worker(1);
+		assert!(pool_state.read().transactions.is_empty());
+	});
+}
+
+#[test]
+fn unsigned_submissions_are_accepted_once_per_interval() {
+	new_test_ext().execute_with(|| {
+		let call = Call::submit_price_unsigned { block_number: 1, price: 1234 };
+		assert!(PriceFeed::validate_unsigned(TransactionSource::Local, &call).is_ok());
+		// Fetched at a block yet to be imported.
+		let future = Call::submit_price_unsigned { block_number: 2, price: 1234 };
+		assert!(PriceFeed::validate_unsigned(TransactionSource::Local, &future).is_err());
+
+		assert_ok!(PriceFeed::submit_price_unsigned(RuntimeOrigin::none(), 1, 1234));
+		System::assert_last_event(Event::PriceSubmitted { price: 1234, who: None }.into());
+		assert_eq!(NextUnsignedAt::<Test>::get(), 1 + UnsignedInterval::get());
+		assert!(PriceFeed::validate_unsigned(TransactionSource::Local, &call).is_err());
+
+		System::set_block_number(1 + UnsignedInterval::get());
+		let next =
+			Call::submit_price_unsigned { block_number: 1 + UnsignedInterval::get(), price: 1 };
+		assert!(PriceFeed::validate_unsigned(TransactionSource::Local, &next).is_ok());
+	});
+}
+
+#[test]
+fn latest_prices_are_averaged() {
+	new_test_ext().execute_with(|| {
+		let who = AccountId32::new([1; 32]);
+		assert_eq!(PriceFeed::average_price(), None);
+		assert_eq!(PriceFeed::submit_price(RuntimeOrigin::none(), 1), Err(BadOrigin.into()));
+
+		for price in [10, 20, 30, 40] {
+			assert_ok!(PriceFeed::submit_price(RuntimeOrigin::signed(who.clone()), price));
+		}
+		System::assert_last_event(
+			Event::PriceSubmitted { price: 40, who: Some(who.clone()) }.into(),
+		);
+		assert_eq!(PriceFeed::average_price(), Some(25));
+
+		// Only `MaxPrices` prices are kept, dropping the oldest.
+		assert_ok!(PriceFeed::submit_price(RuntimeOrigin::signed(who), 50));
+		assert_eq!(Prices::<Test>::get().into_inner(), vec![20, 30, 40, 50]);
+		assert_eq!(PriceFeed::average_price(), Some(35));
+	});
+}
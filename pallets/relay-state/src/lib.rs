@@ -0,0 +1,78 @@
+//! Records the state of the relay chain block each block is built on, read from the relay chain
+//! state proof as the validation data of the block is set, so that contracts can read the
+//! randomness and time of the relay chain without relying on an oracle.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use cumulus_pallet_parachain_system::{OnSystemEvent, RelayChainStateProof, RelayStateProof};
+	use cumulus_primitives_core::{relay_chain::well_known_keys, PersistedValidationData};
+	use frame_support::{dispatch::DispatchClass, pallet_prelude::*};
+	use pop_runtime_common::{apis::RelayChainState, RELAY_CHAIN_SLOT_DURATION_MILLIS};
+
+	const LOG_TARGET: &str = "runtime::relay-state";
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + cumulus_pallet_parachain_system::Config {}
+
+	/// The state of the relay chain block the last block is built on.
+	#[pallet::storage]
+	pub type State<T> = StorageValue<_, RelayChainState, OptionQuery>;
+
+	impl<T: Config> OnSystemEvent for Pallet<T> {
+		fn on_validation_data(data: &PersistedValidationData) {
+			// The proof is stored by the parachain system before the event is emitted.
+			let Some(proof) = RelayStateProof::<T>::get() else { return };
+			let state = RelayChainStateProof::new(
+				T::SelfParaId::get(),
+				data.relay_parent_storage_root,
+				proof,
+			)
+			.and_then(|proof| {
+				let randomness =
+					proof.read_entry(well_known_keys::CURRENT_BLOCK_RANDOMNESS, Some(None))?;
+				let slot = proof.read_slot()?;
+				Ok(RelayChainState {
+					block_number: data.relay_parent_number,
+					randomness,
+					timestamp: u64::from(slot)
+						.saturating_mul(RELAY_CHAIN_SLOT_DURATION_MILLIS.into()),
+				})
+			});
+			match state {
+				Ok(state) => State::<T>::put(state),
+				// The proof was verified by the parachain system already, so that the keys of any
+				// relay chain lacking them are the only reason for failing.
+				Err(e) => {
+					log::warn!(target: LOG_TARGET, "Unable to read the relay chain state: {e:?}");
+					State::<T>::kill();
+				},
+			}
+			// The weight of the mandatory inherent setting the validation data excludes this.
+			frame_system::Pallet::<T>::register_extra_weight_unchecked(
+				T::DbWeight::get().reads_writes(1, 1),
+				DispatchClass::Mandatory,
+			);
+		}
+
+		fn on_validation_code_applied() {}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The state of the relay chain block the last block is built on, if any.
+		pub fn relay_chain_state() -> Option<RelayChainState> {
+			State::<T>::get()
+		}
+	}
+}
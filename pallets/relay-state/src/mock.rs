@@ -0,0 +1,51 @@
+use cumulus_pallet_parachain_system::{consensus_hook::ExpectParentIncluded, AnyRelayNumber};
+use cumulus_primitives_core::{AggregateMessageOrigin, ParaId};
+use frame_support::{derive_impl, parameter_types, traits::EnqueueWithOrigin, weights::Weight};
+use sp_runtime::BuildStorage;
+
+use crate as pallet_relay_state;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub(crate) const PARA_ID: u32 = 200;
+
+frame_support::construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		ParachainSystem: cumulus_pallet_parachain_system,
+		RelayState: pallet_relay_state,
+	}
+);
+
+parameter_types! {
+	pub const ParachainId: ParaId = ParaId::new(PARA_ID);
+	pub const RelayOrigin: AggregateMessageOrigin = AggregateMessageOrigin::Parent;
+	pub const ReservedWeight: Weight = Weight::zero();
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+	type OnSetCode = cumulus_pallet_parachain_system::ParachainSetCode<Self>;
+}
+
+impl cumulus_pallet_parachain_system::Config for Test {
+	type CheckAssociatedRelayNumber = AnyRelayNumber;
+	type ConsensusHook = ExpectParentIncluded;
+	type DmpQueue = EnqueueWithOrigin<(), RelayOrigin>;
+	type OnSystemEvent = RelayState;
+	type OutboundXcmpMessageSource = ();
+	type ReservedDmpWeight = ReservedWeight;
+	type ReservedXcmpWeight = ReservedWeight;
+	type RuntimeEvent = RuntimeEvent;
+	type SelfParaId = ParachainId;
+	type WeightInfo = ();
+	type XcmpMessageHandler = ();
+}
+
+impl pallet_relay_state::Config for Test {}
+
+pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
+	let storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	sp_io::TestExternalities::new(storage)
+}
@@ -0,0 +1,77 @@
+use codec::Encode;
+use cumulus_pallet_parachain_system::{OnSystemEvent, RelayStateProof};
+use cumulus_primitives_core::{
+	relay_chain::{well_known_keys, Hash},
+	PersistedValidationData,
+};
+use cumulus_test_relay_sproof_builder::RelayStateSproofBuilder;
+use pop_runtime_common::{apis::RelayChainState, RELAY_CHAIN_SLOT_DURATION_MILLIS};
+
+use crate::{mock::*, State};
+
+const RANDOMNESS: [u8; 32] = [7; 32];
+
+/// A relay chain state proof of the given current block randomness at slot 42.
+fn sproof(randomness: Option<Option<[u8; 32]>>) -> RelayStateSproofBuilder {
+	let mut sproof = RelayStateSproofBuilder {
+		para_id: PARA_ID.into(),
+		current_slot: 42.into(),
+		..Default::default()
+	};
+	if let Some(randomness) = randomness {
+		sproof
+			.additional_key_values
+			.push((well_known_keys::CURRENT_BLOCK_RANDOMNESS.to_vec(), randomness.encode()));
+	}
+	sproof
+}
+
+/// Set the validation data of a block built on relay chain block 5, with the state `proven`.
+fn set_validation_data(proven: RelayStateSproofBuilder, root: Option<Hash>) {
+	let (relay_parent_storage_root, proof) = proven.into_state_root_and_proof();
+	RelayStateProof::<Test>::put(proof);
+	RelayState::on_validation_data(&PersistedValidationData {
+		relay_parent_number: 5,
+		relay_parent_storage_root: root.unwrap_or(relay_parent_storage_root),
+		..Default::default()
+	});
+}
+
+#[test]
+fn relay_chain_state_is_read_from_proof() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(RelayState::relay_chain_state(), None);
+
+		set_validation_data(sproof(Some(Some(RANDOMNESS))), None);
+		assert_eq!(
+			RelayState::relay_chain_state(),
+			Some(RelayChainState {
+				block_number: 5,
+				randomness: Some(RANDOMNESS),
+				timestamp: 42 * RELAY_CHAIN_SLOT_DURATION_MILLIS as u64,
+			})
+		);
+	});
+}
+
+#[test]
+fn randomness_is_optional() {
+	new_test_ext().execute_with(|| {
+		set_validation_data(sproof(None), None);
+		assert_eq!(State::<Test>::get().unwrap().randomness, None);
+		set_validation_data(sproof(Some(None)), None);
+		assert_eq!(State::<Test>::get().unwrap().randomness, None);
+	});
+}
+
+#[test]
+fn unreadable_state_is_cleared() {
+	new_test_ext().execute_with(|| {
+		set_validation_data(sproof(Some(Some(RANDOMNESS))), None);
+		assert!(State::<Test>::get().is_some());
+
+		// The state of the last block must not be kept for a block whose state is unreadable.
+		set_validation_data(sproof(Some(Some(RANDOMNESS))), Some(Hash::repeat_byte(1)));
+		assert_eq!(State::<Test>::get(), None);
+	});
+}
@@ -0,0 +1,25 @@
+//! Benchmarks of `pallet_transfer_freeze`.
+
+use frame_benchmarking::v2::*;
+use frame_support::traits::EnsureOrigin;
+
+use super::*;
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn set_frozen() -> Result<(), BenchmarkError> {
+		let origin =
+			T::FreezeOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, true);
+
+		assert!(Frozen::<T>::get());
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
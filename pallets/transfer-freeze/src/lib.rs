@@ -0,0 +1,74 @@
+//! Allows plain transfers to be frozen in an emergency, rejecting the calls of `FrozenCalls` while
+//! all other calls, such as those of contracts and governance, can still be dispatched. Unlike
+//! the maintenance mode, the chain otherwise keeps operating as usual.
+//!
+//! Calls are filtered by including the pallet in the `BaseCallFilter` of the runtime, which root
+//! bypasses.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{pallet_prelude::*, traits::Contains};
+	use frame_system::pallet_prelude::*;
+
+	use super::WeightInfo;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The origin allowed to freeze and unfreeze transfers.
+		type FreezeOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// The calls which are rejected while transfers are frozen.
+		type FrozenCalls: Contains<Self::RuntimeCall>;
+		/// The weights of the calls of the pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Whether transfers are frozen.
+	#[pallet::storage]
+	pub type Frozen<T> = StorageValue<_, bool, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Transfers were frozen.
+		Frozen,
+		/// Transfers were unfrozen.
+		Unfrozen,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Freeze or unfreeze transfers.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::set_frozen())]
+		pub fn set_frozen(origin: OriginFor<T>, frozen: bool) -> DispatchResult {
+			T::FreezeOrigin::ensure_origin(origin)?;
+			Frozen::<T>::put(frozen);
+			Self::deposit_event(if frozen { Event::Frozen } else { Event::Unfrozen });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Contains<T::RuntimeCall> for Pallet<T> {
+		fn contains(call: &T::RuntimeCall) -> bool {
+			!Frozen::<T>::get() || !T::FrozenCalls::contains(call)
+		}
+	}
+}
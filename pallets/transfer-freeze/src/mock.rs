@@ -0,0 +1,41 @@
+use frame_support::{derive_impl, traits::Contains};
+use frame_system::EnsureRoot;
+use sp_runtime::BuildStorage;
+
+use crate as pallet_transfer_freeze;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		TransferFreeze: pallet_transfer_freeze,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+}
+
+/// Remarks with events stand for the transfers rejected while frozen.
+pub struct RemarksWithEvent;
+impl Contains<RuntimeCall> for RemarksWithEvent {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(call, RuntimeCall::System(frame_system::Call::remark_with_event { .. }))
+	}
+}
+
+impl pallet_transfer_freeze::Config for Test {
+	type FreezeOrigin = EnsureRoot<u64>;
+	type FrozenCalls = RemarksWithEvent;
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+}
+
+pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
+	let storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
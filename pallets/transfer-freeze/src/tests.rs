@@ -0,0 +1,39 @@
+use frame_support::{assert_noop, assert_ok, traits::Contains};
+use sp_runtime::DispatchError;
+
+use crate::{mock::*, Event, Frozen};
+
+fn remark() -> RuntimeCall {
+	RuntimeCall::System(frame_system::Call::remark { remark: vec![] })
+}
+
+fn remark_with_event() -> RuntimeCall {
+	RuntimeCall::System(frame_system::Call::remark_with_event { remark: vec![] })
+}
+
+#[test]
+fn only_frozen_calls_are_rejected_while_frozen() {
+	new_test_ext().execute_with(|| {
+		assert!(TransferFreeze::contains(&remark_with_event()));
+
+		assert_ok!(TransferFreeze::set_frozen(RuntimeOrigin::root(), true));
+		assert!(Frozen::<Test>::get());
+		System::assert_last_event(Event::Frozen.into());
+		assert!(!TransferFreeze::contains(&remark_with_event()));
+		assert!(TransferFreeze::contains(&remark()));
+
+		assert_ok!(TransferFreeze::set_frozen(RuntimeOrigin::root(), false));
+		System::assert_last_event(Event::Unfrozen.into());
+		assert!(TransferFreeze::contains(&remark_with_event()));
+	});
+}
+
+#[test]
+fn only_freeze_origin_can_freeze() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TransferFreeze::set_frozen(RuntimeOrigin::signed(1), true),
+			DispatchError::BadOrigin
+		);
+	});
+}
@@ -0,0 +1,183 @@
+//! Permissions the upload of contract codes, which is open to anyone unless governance restricts
+//! it to the uploaders it whitelists, e.g. so that a chain which launches contracts only allows
+//! audited codes. The policy a chain starts with is set by its chain specification.
+//!
+//! Applies to every upload via `pallet_contracts`, including instantiations with code and
+//! chunked uploads, but not to the codes uploaded at genesis.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{pallet_prelude::*, DefaultNoBound, Deserialize, Serialize};
+	use frame_system::{pallet_prelude::*, RawOrigin};
+	use sp_std::{marker::PhantomData, vec::Vec};
+
+	use super::WeightInfo;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The origin allowed to set the policy and to whitelist uploaders.
+		type PolicyOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The weights of the calls of the pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Who may upload contract codes.
+	#[derive(
+		Clone,
+		Copy,
+		Debug,
+		Default,
+		PartialEq,
+		Eq,
+		Encode,
+		Decode,
+		MaxEncodedLen,
+		TypeInfo,
+		Serialize,
+		Deserialize,
+	)]
+	#[serde(crate = "frame_support::__private::serde")]
+	pub enum Policy {
+		/// Any signed account.
+		#[default]
+		Permissionless,
+		/// Only the whitelisted uploaders.
+		Permissioned,
+	}
+
+	/// The current policy, permissionless unless set.
+	#[pallet::storage]
+	pub type UploadPolicy<T> = StorageValue<_, Policy, ValueQuery>;
+
+	/// The accounts allowed to upload codes while uploads are permissioned.
+	#[pallet::storage]
+	pub type Uploaders<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, ()>;
+
+	#[pallet::genesis_config]
+	#[derive(DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		/// The policy the chain starts with.
+		pub policy: Policy,
+		/// The whitelisted uploaders.
+		pub uploaders: Vec<T::AccountId>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			UploadPolicy::<T>::put(self.policy);
+			for uploader in &self.uploaders {
+				Uploaders::<T>::insert(uploader, ());
+			}
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The upload policy was changed.
+		PolicySet { policy: Policy },
+		/// An account was whitelisted as an uploader.
+		UploaderAdded { who: T::AccountId },
+		/// An account was removed from the whitelisted uploaders.
+		UploaderRemoved { who: T::AccountId },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account is whitelisted as an uploader already.
+		AlreadyUploader,
+		/// The account is not a whitelisted uploader.
+		NotUploader,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set who may upload contract codes.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::set_policy())]
+		pub fn set_policy(origin: OriginFor<T>, policy: Policy) -> DispatchResult {
+			T::PolicyOrigin::ensure_origin(origin)?;
+			UploadPolicy::<T>::put(policy);
+			Self::deposit_event(Event::PolicySet { policy });
+			Ok(())
+		}
+
+		/// Whitelist `who` as an uploader, allowing it to upload codes while uploads are
+		/// permissioned.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::add_uploader())]
+		pub fn add_uploader(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::PolicyOrigin::ensure_origin(origin)?;
+			ensure!(!Uploaders::<T>::contains_key(&who), Error::<T>::AlreadyUploader);
+			Uploaders::<T>::insert(&who, ());
+			Self::deposit_event(Event::UploaderAdded { who });
+			Ok(())
+		}
+
+		/// Remove `who` from the whitelisted uploaders. Codes it uploaded already are kept.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::remove_uploader())]
+		pub fn remove_uploader(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::PolicyOrigin::ensure_origin(origin)?;
+			ensure!(Uploaders::<T>::take(&who).is_some(), Error::<T>::NotUploader);
+			Self::deposit_event(Event::UploaderRemoved { who });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Whether `who` may upload codes under the current policy.
+		pub fn can_upload(who: &T::AccountId) -> bool {
+			match UploadPolicy::<T>::get() {
+				Policy::Permissionless => true,
+				Policy::Permissioned => Uploaders::<T>::contains_key(who),
+			}
+		}
+	}
+
+	/// Ensures that the origin is signed by an account which may upload codes under the current
+	/// policy, succeeding with the account.
+	pub struct EnsureUploader<T>(PhantomData<T>);
+
+	impl<T: Config, O> EnsureOrigin<O> for EnsureUploader<T>
+	where
+		O: Into<Result<RawOrigin<T::AccountId>, O>> + From<RawOrigin<T::AccountId>>,
+	{
+		type Success = T::AccountId;
+
+		fn try_origin(o: O) -> Result<Self::Success, O> {
+			o.into().and_then(|o| match o {
+				RawOrigin::Signed(who) if Pallet::<T>::can_upload(&who) => Ok(who),
+				r => Err(O::from(r)),
+			})
+		}
+
+		/// Benchmarks run with permissionless uploads.
+		#[cfg(feature = "runtime-benchmarks")]
+		fn try_successful_origin() -> Result<O, ()> {
+			let zero_account_id =
+				T::AccountId::decode(&mut sp_runtime::traits::TrailingZeroInput::zeroes())
+					.expect("infinite length input; no invalid inputs for type; qed");
+			Ok(O::from(RawOrigin::Signed(zero_account_id)))
+		}
+	}
+}
@@ -0,0 +1,81 @@
+use frame_support::{assert_noop, assert_ok, traits::EnsureOrigin};
+use sp_runtime::DispatchError;
+
+use crate::{mock::*, EnsureUploader, Error, Event, Policy, UploadPolicy, Uploaders};
+
+fn ensure_uploader(who: u64) -> Option<u64> {
+	EnsureUploader::<Test>::try_origin(RuntimeOrigin::signed(who)).ok()
+}
+
+#[test]
+fn genesis_sets_policy_and_uploaders() {
+	new_test_ext(Policy::Permissioned).execute_with(|| {
+		assert_eq!(UploadPolicy::<Test>::get(), Policy::Permissioned);
+		assert!(Uploaders::<Test>::contains_key(UPLOADER));
+		assert!(!Uploaders::<Test>::contains_key(OTHER));
+	});
+}
+
+#[test]
+fn anyone_uploads_while_permissionless() {
+	new_test_ext(Policy::Permissionless).execute_with(|| {
+		assert_eq!(ensure_uploader(OTHER), Some(OTHER));
+		assert!(EnsureUploader::<Test>::try_origin(RuntimeOrigin::root()).is_err());
+	});
+}
+
+#[test]
+fn only_uploaders_upload_while_permissioned() {
+	new_test_ext(Policy::Permissioned).execute_with(|| {
+		assert_eq!(ensure_uploader(UPLOADER), Some(UPLOADER));
+		assert_eq!(ensure_uploader(OTHER), None);
+
+		assert_ok!(ContractsUploadPolicy::add_uploader(RuntimeOrigin::root(), OTHER));
+		System::assert_last_event(Event::UploaderAdded { who: OTHER }.into());
+		assert_eq!(ensure_uploader(OTHER), Some(OTHER));
+
+		assert_ok!(ContractsUploadPolicy::remove_uploader(RuntimeOrigin::root(), UPLOADER));
+		System::assert_last_event(Event::UploaderRemoved { who: UPLOADER }.into());
+		assert_eq!(ensure_uploader(UPLOADER), None);
+
+		assert_ok!(ContractsUploadPolicy::set_policy(
+			RuntimeOrigin::root(),
+			Policy::Permissionless
+		));
+		System::assert_last_event(Event::PolicySet { policy: Policy::Permissionless }.into());
+		assert_eq!(ensure_uploader(UPLOADER), Some(UPLOADER));
+	});
+}
+
+#[test]
+fn uploaders_are_added_and_removed_once() {
+	new_test_ext(Policy::Permissioned).execute_with(|| {
+		assert_noop!(
+			ContractsUploadPolicy::add_uploader(RuntimeOrigin::root(), UPLOADER),
+			Error::<Test>::AlreadyUploader
+		);
+		assert_noop!(
+			ContractsUploadPolicy::remove_uploader(RuntimeOrigin::root(), OTHER),
+			Error::<Test>::NotUploader
+		);
+	});
+}
+
+#[test]
+fn only_policy_origin_sets_policy_and_uploaders() {
+	new_test_ext(Policy::Permissioned).execute_with(|| {
+		let origin = || RuntimeOrigin::signed(UPLOADER);
+		assert_noop!(
+			ContractsUploadPolicy::set_policy(origin(), Policy::Permissionless),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			ContractsUploadPolicy::add_uploader(origin(), OTHER),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			ContractsUploadPolicy::remove_uploader(origin(), UPLOADER),
+			DispatchError::BadOrigin
+		);
+	});
+}
@@ -0,0 +1,29 @@
+//! Benchmarks of `pallet_weight_overrides`.
+
+use frame_benchmarking::v2::*;
+use frame_support::traits::{EnsureOrigin, Get};
+use sp_std::vec;
+
+use super::*;
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn set_weight_multiplier() -> Result<(), BenchmarkError> {
+		let origin =
+			T::OverrideOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		// The longest pallet name.
+		let pallet = PalletName::truncate_from(vec![b'p'; PalletName::bound()]);
+		let multiplier = T::MaxMultiplier::get();
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, pallet.clone(), Some(multiplier));
+
+		assert_eq!(Multipliers::<T>::get(pallet), Some(multiplier));
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
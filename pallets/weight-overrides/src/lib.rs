@@ -0,0 +1,302 @@
+//! Allows the weight of the calls of a pallet to be multiplied, as a safety valve for calls whose
+//! benchmarked weights prove too low in production, until they are benchmarked again.
+//!
+//! The multipliers are applied by wrapping the signed extensions charging and reclaiming weight,
+//! as a tuple, in a single [`WithWeightMultiplier`], which looks the multiplier of a transaction up
+//! once. The calls dispatching other calls, e.g. `utility.batch`, are multiplied by the highest
+//! multiplier of the calls they dispatch, so that wrapping a call does not escape its multiplier.
+//! Only the calls dispatched right away are covered, see [`InnerCalls`].
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use core::{fmt, marker::PhantomData};
+
+	use frame_support::{
+		dispatch::{DispatchInfo, PostDispatchInfo},
+		pallet_prelude::*,
+		traits::GetCallMetadata,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::{
+		traits::{
+			DispatchInfoOf, Dispatchable, One, PostDispatchInfoOf, SignedExtension,
+			SignedExtensionMetadata,
+		},
+		transaction_validity::TransactionValidityError,
+		FixedPointNumber, FixedU64,
+	};
+	use sp_std::{collections::btree_set::BTreeSet, vec::Vec};
+
+	use super::WeightInfo;
+
+	/// The name of a pallet, as configured in the runtime.
+	pub type PalletName = BoundedVec<u8, ConstU32<64>>;
+
+	/// How deep the calls dispatched by other calls are looked into for their multipliers. The
+	/// calls nesting others deeper are multiplied by `MaxMultiplier` instead.
+	pub const MAX_DEPTH: u32 = 8;
+
+	/// The calls dispatched by other calls, e.g. by `utility.batch`.
+	///
+	/// The calls stored to be dispatched later, e.g. by the scheduler or once a proposal of a
+	/// collective is closed, are not covered: they are dispatched outside of any transaction.
+	pub trait InnerCalls<Call> {
+		/// The calls `call` dispatches right away, excluding those they dispatch in turn.
+		fn inner_calls(call: &Call) -> Vec<&Call>;
+	}
+
+	/// The weight multiplier of a call, if any, along with the weight of looking it up.
+	#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+	pub struct Adjustment {
+		/// The highest multiplier of the pallets of the call and of the calls it dispatches.
+		pub multiplier: Option<FixedU64>,
+		/// The weight of reading the multipliers of those pallets.
+		pub lookup: Weight,
+	}
+
+	impl Adjustment {
+		/// The weight of a call weighing `weight` once adjusted.
+		pub fn apply(&self, weight: Weight) -> Weight {
+			let weight = match self.multiplier {
+				Some(multiplier) => Weight::from_parts(
+					multiplier.saturating_mul_int(weight.ref_time()),
+					multiplier.saturating_mul_int(weight.proof_size()),
+				),
+				None => weight,
+			};
+			weight.saturating_add(self.lookup)
+		}
+
+		fn apply_info(&self, info: &DispatchInfo) -> DispatchInfo {
+			DispatchInfo { weight: self.apply(info.weight), ..*info }
+		}
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The origin allowed to set weight multipliers.
+		type OverrideOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// The calls dispatched by other calls, whose multipliers apply to the calls dispatching
+		/// them as well.
+		type InnerCalls: InnerCalls<Self::RuntimeCall>;
+		/// The highest weight multiplier which can be set.
+		#[pallet::constant]
+		type MaxMultiplier: Get<FixedU64>;
+		/// The weights of the calls of the pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The multipliers applied to the weight of the calls of pallets, by pallet name.
+	#[pallet::storage]
+	pub type Multipliers<T> = StorageMap<_, Twox64Concat, PalletName, FixedU64>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The weight multiplier of the calls of `pallet` was set, or removed if `None`.
+		WeightMultiplierSet { pallet: PalletName, multiplier: Option<FixedU64> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The multiplier is below one, which would underprice calls, or above `MaxMultiplier`.
+		MultiplierOutOfBounds,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Multiply the weight of the calls of `pallet` by `multiplier`, or stop doing so if
+		/// `None`.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::set_weight_multiplier())]
+		pub fn set_weight_multiplier(
+			origin: OriginFor<T>,
+			pallet: PalletName,
+			multiplier: Option<FixedU64>,
+		) -> DispatchResult {
+			T::OverrideOrigin::ensure_origin(origin)?;
+			if let Some(multiplier) = multiplier {
+				ensure!(
+					FixedU64::one() <= multiplier && multiplier <= T::MaxMultiplier::get(),
+					Error::<T>::MultiplierOutOfBounds
+				);
+			}
+			Multipliers::<T>::set(&pallet, multiplier);
+			Self::deposit_event(Event::WeightMultiplierSet { pallet, multiplier });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The highest weight multiplier of the pallet of `call` and of the pallets of the calls
+		/// it dispatches, reading the multiplier of each pallet once, or `MaxMultiplier` if they
+		/// are nested deeper than [`MAX_DEPTH`].
+		pub fn adjustment(call: &T::RuntimeCall) -> Adjustment
+		where
+			T::RuntimeCall: GetCallMetadata,
+		{
+			let mut pallets = BTreeSet::new();
+			if !Self::collect_pallets(call, 0, &mut pallets) {
+				return Adjustment {
+					multiplier: Some(T::MaxMultiplier::get()),
+					lookup: Weight::zero(),
+				}
+			}
+			// Pallets with names exceeding the bound cannot have a multiplier.
+			let pallets: Vec<_> = pallets
+				.into_iter()
+				.filter_map(|pallet| PalletName::try_from(pallet.as_bytes().to_vec()).ok())
+				.collect();
+			Adjustment {
+				multiplier: pallets.iter().filter_map(Multipliers::<T>::get).max(),
+				lookup: Self::lookup_weight(pallets.len() as u64),
+			}
+		}
+
+		/// The weight of reading the multipliers of `pallets` pallets, whose proof is at most the
+		/// hashed key and the value of each entry.
+		fn lookup_weight(pallets: u64) -> Weight {
+			let entry = 8 + PalletName::max_encoded_len() + FixedU64::max_encoded_len();
+			T::DbWeight::get()
+				.reads(pallets)
+				.saturating_add(Weight::from_parts(0, pallets.saturating_mul(entry as u64)))
+		}
+
+		/// Collect the pallets of `call` and of the calls it dispatches, nested `depth` calls
+		/// deep, returning whether none is nested deeper than [`MAX_DEPTH`].
+		fn collect_pallets(
+			call: &T::RuntimeCall,
+			depth: u32,
+			pallets: &mut BTreeSet<&'static str>,
+		) -> bool
+		where
+			T::RuntimeCall: GetCallMetadata,
+		{
+			pallets.insert(call.get_call_metadata().pallet_name);
+			let inner_calls = T::InnerCalls::inner_calls(call);
+			inner_calls.is_empty() ||
+				(depth < MAX_DEPTH &&
+					inner_calls
+						.into_iter()
+						.all(|call| Self::collect_pallets(call, depth + 1, pallets)))
+		}
+	}
+
+	/// Wraps the signed extension `S`, so that the weight of calls it is given is adjusted by the
+	/// multiplier of their pallet (see [`Pallet::adjustment`]). It encodes and identifies as `S`,
+	/// so the format of transactions is unchanged. `S` is meant to be a tuple of all the signed
+	/// extensions using the weight of calls, for the multiplier to be looked up once.
+	#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	pub struct WithWeightMultiplier<T, S>(pub S, PhantomData<T>);
+
+	impl<T, S> From<S> for WithWeightMultiplier<T, S> {
+		fn from(extension: S) -> Self {
+			Self(extension, PhantomData)
+		}
+	}
+
+	impl<T, S: fmt::Debug> fmt::Debug for WithWeightMultiplier<T, S> {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			self.0.fmt(f)
+		}
+	}
+
+	impl<T, S> SignedExtension for WithWeightMultiplier<T, S>
+	where
+		T: Config + Clone + Eq + Send + Sync,
+		T::RuntimeCall:
+			Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo> + GetCallMetadata,
+		S: SignedExtension<Call = T::RuntimeCall>,
+	{
+		type AccountId = S::AccountId;
+		type AdditionalSigned = S::AdditionalSigned;
+		type Call = S::Call;
+		/// The adjustment applied, which is required again after dispatch.
+		type Pre = (Adjustment, S::Pre);
+
+		const IDENTIFIER: &'static str = S::IDENTIFIER;
+
+		fn additional_signed(&self) -> Result<Self::AdditionalSigned, TransactionValidityError> {
+			self.0.additional_signed()
+		}
+
+		fn metadata() -> Vec<SignedExtensionMetadata> {
+			S::metadata()
+		}
+
+		fn validate(
+			&self,
+			who: &Self::AccountId,
+			call: &Self::Call,
+			info: &DispatchInfoOf<Self::Call>,
+			len: usize,
+		) -> TransactionValidity {
+			let info = Pallet::<T>::adjustment(call).apply_info(info);
+			self.0.validate(who, call, &info, len)
+		}
+
+		fn pre_dispatch(
+			self,
+			who: &Self::AccountId,
+			call: &Self::Call,
+			info: &DispatchInfoOf<Self::Call>,
+			len: usize,
+		) -> Result<Self::Pre, TransactionValidityError> {
+			let adjustment = Pallet::<T>::adjustment(call);
+			let info = adjustment.apply_info(info);
+			Ok((adjustment, self.0.pre_dispatch(who, call, &info, len)?))
+		}
+
+		fn validate_unsigned(
+			call: &Self::Call,
+			info: &DispatchInfoOf<Self::Call>,
+			len: usize,
+		) -> TransactionValidity {
+			S::validate_unsigned(call, info, len)
+		}
+
+		fn pre_dispatch_unsigned(
+			call: &Self::Call,
+			info: &DispatchInfoOf<Self::Call>,
+			len: usize,
+		) -> Result<(), TransactionValidityError> {
+			S::pre_dispatch_unsigned(call, info, len)
+		}
+
+		fn post_dispatch(
+			pre: Option<Self::Pre>,
+			info: &DispatchInfoOf<Self::Call>,
+			post_info: &PostDispatchInfoOf<Self::Call>,
+			len: usize,
+			result: &DispatchResult,
+		) -> Result<(), TransactionValidityError> {
+			let Some((adjustment, pre)) = pre else {
+				return S::post_dispatch(None, info, post_info, len, result);
+			};
+			let post_info = PostDispatchInfo {
+				actual_weight: post_info.actual_weight.map(|weight| adjustment.apply(weight)),
+				pays_fee: post_info.pays_fee,
+			};
+			S::post_dispatch(Some(pre), &adjustment.apply_info(info), &post_info, len, result)
+		}
+	}
+}
@@ -0,0 +1,65 @@
+use frame_support::{derive_impl, parameter_types};
+use frame_system::EnsureRoot;
+use sp_runtime::{BuildStorage, FixedU64};
+use sp_std::vec::Vec;
+
+use crate::{self as pallet_weight_overrides, InnerCalls, PalletName};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub(crate) const ALICE: u64 = 1;
+
+frame_support::construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		Utility: pallet_utility,
+		WeightOverrides: pallet_weight_overrides,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+}
+
+impl pallet_utility::Config for Test {
+	type PalletsOrigin = OriginCaller;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+}
+
+/// The calls dispatched by batches.
+pub struct BatchedCalls;
+impl InnerCalls<RuntimeCall> for BatchedCalls {
+	fn inner_calls(call: &RuntimeCall) -> Vec<&RuntimeCall> {
+		match call {
+			RuntimeCall::Utility(pallet_utility::Call::batch { calls }) => calls.iter().collect(),
+			_ => Vec::new(),
+		}
+	}
+}
+
+parameter_types! {
+	pub MaxMultiplier: FixedU64 = FixedU64::from_u32(10);
+}
+
+impl pallet_weight_overrides::Config for Test {
+	type InnerCalls = BatchedCalls;
+	type MaxMultiplier = MaxMultiplier;
+	type OverrideOrigin = EnsureRoot<u64>;
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+}
+
+/// The name of the system pallet, e.g. to multiply the weight of remarks.
+pub(crate) fn system() -> PalletName {
+	PalletName::truncate_from(b"System".to_vec())
+}
+
+pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
+	let storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
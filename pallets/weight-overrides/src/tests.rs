@@ -0,0 +1,128 @@
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::{DispatchClass, GetDispatchInfo, PostDispatchInfo},
+	traits::Get,
+	weights::Weight,
+};
+use frame_system::limits::BlockWeights;
+use sp_runtime::{traits::SignedExtension, DispatchError, FixedU64};
+
+use crate::{mock::*, Adjustment, Error, Event, Multipliers, WithWeightMultiplier, MAX_DEPTH};
+
+fn remark() -> RuntimeCall {
+	RuntimeCall::System(frame_system::Call::remark { remark: vec![] })
+}
+
+fn batch(calls: Vec<RuntimeCall>) -> RuntimeCall {
+	RuntimeCall::Utility(pallet_utility::Call::batch { calls })
+}
+
+fn set_multiplier(multiplier: Option<u32>) {
+	assert_ok!(WeightOverrides::set_weight_multiplier(
+		RuntimeOrigin::root(),
+		system(),
+		multiplier.map(FixedU64::from_u32)
+	));
+}
+
+/// The weight of the normal calls dispatched in the block, excluding their base weight.
+fn consumed() -> Weight {
+	let weights: BlockWeights = <Test as frame_system::Config>::BlockWeights::get();
+	let base = weights.get(DispatchClass::Normal).base_extrinsic;
+	*frame_system::BlockWeight::<Test>::get().get(DispatchClass::Normal) - base
+}
+
+#[test]
+fn governance_sets_multipliers_within_bounds() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			WeightOverrides::set_weight_multiplier(
+				RuntimeOrigin::signed(ALICE),
+				system(),
+				Some(FixedU64::from_u32(2))
+			),
+			DispatchError::BadOrigin
+		);
+		for out_of_bounds in [FixedU64::from_rational(1, 2), FixedU64::from_u32(11)] {
+			assert_noop!(
+				WeightOverrides::set_weight_multiplier(
+					RuntimeOrigin::root(),
+					system(),
+					Some(out_of_bounds)
+				),
+				Error::<Test>::MultiplierOutOfBounds
+			);
+		}
+
+		set_multiplier(Some(2));
+		assert_eq!(Multipliers::<Test>::get(system()), Some(FixedU64::from_u32(2)));
+		System::assert_last_event(
+			Event::WeightMultiplierSet {
+				pallet: system(),
+				multiplier: Some(FixedU64::from_u32(2)),
+			}
+			.into(),
+		);
+		set_multiplier(None);
+		assert_eq!(Multipliers::<Test>::get(system()), None);
+	});
+}
+
+#[test]
+fn calls_dispatching_others_are_multiplied_too() {
+	new_test_ext().execute_with(|| {
+		set_multiplier(Some(2));
+		let multiplier = |call| WeightOverrides::adjustment(&call).multiplier;
+		assert_eq!(multiplier(remark()), Some(FixedU64::from_u32(2)));
+		assert_eq!(multiplier(batch(vec![])), None);
+		assert_eq!(multiplier(batch(vec![batch(vec![remark()])])), Some(FixedU64::from_u32(2)));
+	});
+}
+
+#[test]
+fn multiplier_of_each_pallet_is_read_once() {
+	new_test_ext().execute_with(|| {
+		let lookup = |call| WeightOverrides::adjustment(&call).lookup;
+		assert!(lookup(remark()).proof_size() > 0);
+		assert_eq!(lookup(batch(vec![])), lookup(remark()));
+		assert_eq!(lookup(batch(vec![remark(), batch(vec![remark()])])), lookup(remark()) * 2);
+	});
+}
+
+#[test]
+fn calls_nested_too_deep_are_multiplied_by_max_multiplier() {
+	new_test_ext().execute_with(|| {
+		let nested = |depth| (0..depth).fold(remark(), |call, _| batch(vec![call]));
+		assert_eq!(WeightOverrides::adjustment(&nested(MAX_DEPTH)).multiplier, None);
+		assert_eq!(
+			WeightOverrides::adjustment(&nested(MAX_DEPTH + 1)),
+			Adjustment { multiplier: Some(MaxMultiplier::get()), lookup: Weight::zero() }
+		);
+	});
+}
+
+#[test]
+fn multiplied_weight_is_charged_and_reclaimed() {
+	new_test_ext().execute_with(|| {
+		set_multiplier(Some(3));
+		let call = remark();
+		let info = call.get_dispatch_info();
+		let lookup = WeightOverrides::adjustment(&call).lookup;
+		let extension: WithWeightMultiplier<Test, _> =
+			frame_system::CheckWeight::<Test>::new().into();
+
+		let pre = extension.pre_dispatch(&ALICE, &call, &info, 0).unwrap();
+		assert_eq!(consumed(), info.weight * 3 + lookup);
+
+		let post_info =
+			PostDispatchInfo { actual_weight: Some(info.weight / 2), ..Default::default() };
+		assert_ok!(WithWeightMultiplier::<Test, frame_system::CheckWeight<Test>>::post_dispatch(
+			Some(pre),
+			&info,
+			&post_info,
+			0,
+			&Ok(())
+		));
+		assert_eq!(consumed(), info.weight / 2 * 3 + lookup);
+	});
+}
@@ -0,0 +1,68 @@
+//! Benchmarks of `pallet_xcm_gateway`.
+
+use frame_benchmarking::v2::*;
+use frame_support::traits::EnsureOrigin;
+use frame_system::RawOrigin;
+use sp_std::{boxed::Box, vec};
+use xcm::{latest::prelude::*, VersionedLocation, VersionedXcm};
+
+use super::*;
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn allow_destination() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AllowlistOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let contract: T::AccountId = account("contract", 0, 0);
+		let destination = VersionedLocation::from(Location::parent());
+
+		#[extrinsic_call]
+		_(
+			origin as <T as frame_system::Config>::RuntimeOrigin,
+			contract.clone(),
+			Box::new(destination.clone()),
+		);
+
+		assert!(Allowlist::<T>::contains_key(&contract, &destination));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn disallow_destination() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AllowlistOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let contract: T::AccountId = account("contract", 0, 0);
+		let destination = VersionedLocation::from(Location::parent());
+		Allowlist::<T>::insert(&contract, &destination, ());
+
+		#[extrinsic_call]
+		_(
+			origin as <T as frame_system::Config>::RuntimeOrigin,
+			contract.clone(),
+			Box::new(destination.clone()),
+		);
+
+		assert!(!Allowlist::<T>::contains_key(&contract, &destination));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn send() -> Result<(), BenchmarkError> {
+		let contract = T::BenchmarkHelper::contract();
+		let destination = VersionedLocation::from(
+			T::BenchmarkHelper::reachable_dest().ok_or(BenchmarkError::Skip)?,
+		);
+		Allowlist::<T>::insert(&contract, &destination, ());
+		let message = VersionedXcm::from(Xcm::<()>(vec![ClearOrigin]));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(contract), Box::new(destination), Box::new(message));
+
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,255 @@
+//! Sends XCM messages on behalf of contracts, e.g. via the `SendXcmMessage` function of the Pop
+//! API, to the destinations governance allows each contract to send messages to.
+//!
+//! Messages are sent from the location of the contract, i.e. prefixed with `DescendOrigin`, so
+//! that the destination sees the contract as their origin. Sending a message requires it to be
+//! weighable by the `Weigher` of `pallet_xcm`, and the contract to pay the fee charged for its
+//! delivery, if any, in `FeeAsset`. The fees paid are deposited to `FeeDestination`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+/// Prepares the state the benchmarks of the pallet require.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<AccountId> {
+	/// The account of a contract, funded to pay the delivery fees of messages to
+	/// [`Self::reachable_dest`].
+	fn contract() -> AccountId;
+	/// A destination messages can be delivered to, if any.
+	fn reachable_dest() -> Option<xcm::latest::Location>;
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{
+			fungible::{Inspect, Mutate},
+			tokens::Preservation,
+			Contains,
+		},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{CheckedAdd, Zero};
+	use sp_std::boxed::Box;
+	use xcm::{latest::prelude::*, IntoVersion, VersionedLocation, VersionedXcm};
+	use xcm_executor::traits::WeightBounds;
+
+	#[cfg(feature = "runtime-benchmarks")]
+	use super::BenchmarkHelper;
+	use super::WeightInfo;
+
+	type BalanceOf<T> =
+		<<T as Config>::Fungible as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_xcm::Config {
+		/// The origin allowed to set the destinations contracts may send messages to.
+		type AllowlistOrigin: EnsureOrigin<<Self as frame_system::Config>::RuntimeOrigin>;
+		/// The accounts of contracts, which are the only ones allowed to send messages.
+		type Contracts: Contains<Self::AccountId>;
+		/// The asset in which the delivery fees of messages are paid, as transacted by
+		/// `Fungible`.
+		type FeeAsset: Get<Location>;
+		/// The account the delivery fees of messages are deposited to.
+		type FeeDestination: Get<Self::AccountId>;
+		/// The currency in which the delivery fees of messages are paid.
+		type Fungible: Mutate<Self::AccountId>;
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The origin of the contracts sending messages, as their location as a local origin.
+		type SendOrigin: EnsureOrigin<
+			<Self as frame_system::Config>::RuntimeOrigin,
+			Success = Location,
+		>;
+		/// The weights of the calls of the pallet.
+		type WeightInfo: WeightInfo;
+		/// Prepares the contracts and the destinations of the benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<Self::AccountId>;
+	}
+
+	/// The destinations each contract may send messages to.
+	#[pallet::storage]
+	pub type Allowlist<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		VersionedLocation,
+		(),
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// `contract` may send messages to `destination`.
+		DestinationAllowed { contract: T::AccountId, destination: VersionedLocation },
+		/// `contract` may no longer send messages to `destination`.
+		DestinationDisallowed { contract: T::AccountId, destination: VersionedLocation },
+		/// A message weighing `weight` locally was sent to `destination` on behalf of `contract`,
+		/// which paid `fee` for its delivery.
+		Sent {
+			contract: T::AccountId,
+			destination: VersionedLocation,
+			message_id: XcmHash,
+			weight: Weight,
+			fee: BalanceOf<T>,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The destination may be sent messages already.
+		AlreadyAllowed,
+		/// The version of the destination or the message is not supported.
+		BadVersion,
+		/// The destination is not allowed for the contract.
+		DestinationNotAllowed,
+		/// The delivery fee could not be paid by the contract.
+		FeesNotMet,
+		/// Messages can only be sent on behalf of contracts.
+		NotContract,
+		/// The message could not be sent, e.g. because the queue of the destination is full.
+		SendFailure,
+		/// The delivery fee is charged in assets other than `FeeAsset`.
+		UnsupportedFeeAsset,
+		/// There is no route to the destination.
+		Unreachable,
+		/// The message could not be weighed, e.g. because it has too many instructions.
+		UnweighableMessage,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Allow `contract` to send messages to `destination`.
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as Config>::WeightInfo::allow_destination())]
+		pub fn allow_destination(
+			origin: OriginFor<T>,
+			contract: T::AccountId,
+			destination: Box<VersionedLocation>,
+		) -> DispatchResult {
+			T::AllowlistOrigin::ensure_origin(origin)?;
+			let destination = Self::latest(*destination)?;
+			ensure!(
+				!Allowlist::<T>::contains_key(&contract, &destination),
+				Error::<T>::AlreadyAllowed
+			);
+			Allowlist::<T>::insert(&contract, &destination, ());
+			Self::deposit_event(Event::DestinationAllowed { contract, destination });
+			Ok(())
+		}
+
+		/// Disallow `contract` from sending messages to `destination`.
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as Config>::WeightInfo::disallow_destination())]
+		pub fn disallow_destination(
+			origin: OriginFor<T>,
+			contract: T::AccountId,
+			destination: Box<VersionedLocation>,
+		) -> DispatchResult {
+			T::AllowlistOrigin::ensure_origin(origin)?;
+			let destination = Self::latest(*destination)?;
+			ensure!(
+				Allowlist::<T>::take(&contract, &destination).is_some(),
+				Error::<T>::DestinationNotAllowed
+			);
+			Self::deposit_event(Event::DestinationDisallowed { contract, destination });
+			Ok(())
+		}
+
+		/// Send `message` to `destination` on behalf of the signing contract, which pays the fee
+		/// of its delivery.
+		#[pallet::call_index(2)]
+		#[pallet::weight(<T as Config>::WeightInfo::send())]
+		pub fn send(
+			origin: OriginFor<T>,
+			destination: Box<VersionedLocation>,
+			message: Box<VersionedXcm<()>>,
+		) -> DispatchResult {
+			// The location of the contract as a local origin, as for sends via `pallet_xcm`.
+			let origin_location = T::SendOrigin::ensure_origin(origin.clone())?;
+			let contract = ensure_signed(origin)?;
+			ensure!(T::Contracts::contains(&contract), Error::<T>::NotContract);
+			let destination = Self::latest(*destination)?;
+			ensure!(
+				Allowlist::<T>::contains_key(&contract, &destination),
+				Error::<T>::DestinationNotAllowed
+			);
+			let dest =
+				Location::try_from(destination.clone()).map_err(|()| Error::<T>::BadVersion)?;
+			let mut message = Xcm::<()>::try_from(*message).map_err(|()| Error::<T>::BadVersion)?;
+			let weight = <T as pallet_xcm::Config>::Weigher::weight(&mut message.clone().into())
+				.map_err(|()| Error::<T>::UnweighableMessage)?;
+
+			// The destination sees the contract as the origin of the message.
+			message.0.insert(0, DescendOrigin(origin_location.interior().clone()));
+			let (ticket, price) =
+				validate_send::<<T as pallet_xcm::Config>::XcmRouter>(dest, message)
+					.map_err(Error::<T>::from)?;
+			let fee = Self::charge_fee(&contract, price)?;
+			let message_id =
+				<T as pallet_xcm::Config>::XcmRouter::deliver(ticket).map_err(Error::<T>::from)?;
+
+			Self::deposit_event(Event::Sent { contract, destination, message_id, weight, fee });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// `location` in the latest version, so that destinations are allowed regardless of the
+		/// version they are given in.
+		fn latest(location: VersionedLocation) -> Result<VersionedLocation, Error<T>> {
+			location.into_version(xcm::latest::VERSION).map_err(|()| Error::<T>::BadVersion)
+		}
+
+		/// Transfer the delivery fee `price` from `contract` to `FeeDestination`, returning the
+		/// fee paid.
+		fn charge_fee(contract: &T::AccountId, price: Assets) -> Result<BalanceOf<T>, Error<T>> {
+			let mut fee = BalanceOf::<T>::zero();
+			for asset in price.into_inner() {
+				let (AssetId(location), Fungible(amount)) = (asset.id, asset.fun) else {
+					return Err(Error::<T>::UnsupportedFeeAsset);
+				};
+				ensure!(location == T::FeeAsset::get(), Error::<T>::UnsupportedFeeAsset);
+				let amount =
+					BalanceOf::<T>::try_from(amount).map_err(|_| Error::<T>::FeesNotMet)?;
+				fee = fee.checked_add(&amount).ok_or(Error::<T>::FeesNotMet)?;
+			}
+			if !fee.is_zero() {
+				T::Fungible::transfer(
+					contract,
+					&T::FeeDestination::get(),
+					fee,
+					Preservation::Preserve,
+				)
+				.map_err(|_| Error::<T>::FeesNotMet)?;
+			}
+			Ok(fee)
+		}
+	}
+
+	impl<T> From<SendError> for Error<T> {
+		fn from(error: SendError) -> Self {
+			match error {
+				SendError::NotApplicable | SendError::Unroutable => Error::<T>::Unreachable,
+				SendError::Fees => Error::<T>::FeesNotMet,
+				_ => Error::<T>::SendFailure,
+			}
+		}
+	}
+}
@@ -0,0 +1,164 @@
+use codec::Encode;
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{ConstU32, Contains, Nothing},
+	weights::Weight,
+};
+use frame_system::EnsureRoot;
+use sp_runtime::{
+	traits::{IdentifyAccount, IdentityLookup, Verify},
+	AccountId32, BuildStorage, MultiSignature,
+};
+use xcm::latest::prelude::*;
+use xcm_builder::{EnsureXcmOrigin, FixedWeightBounds, SignedToAccountId32};
+
+use crate as pallet_xcm_gateway;
+
+type AccountPublic = <MultiSignature as Verify>::Signer;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub(crate) const ALICE: AccountId32 = AccountId32::new([1; 32]);
+pub(crate) const CONTRACT: AccountId32 = AccountId32::new([2; 32]);
+/// A contract not holding enough to pay the delivery fees.
+pub(crate) const POOR_CONTRACT: AccountId32 = AccountId32::new([3; 32]);
+pub(crate) const TREASURY: AccountId32 = AccountId32::new([4; 32]);
+/// The fee charged for the delivery of messages to the relay chain.
+pub(crate) const FEE: u64 = 10;
+/// A sibling charging the delivery fees of messages in its own asset.
+pub(crate) const SIBLING: u32 = 1000;
+
+frame_support::construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		Balances: pallet_balances,
+		XcmPallet: pallet_xcm,
+		XcmGateway: pallet_xcm_gateway,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type AccountData = pallet_balances::AccountData<u64>;
+	type AccountId = <AccountPublic as IdentifyAccount>::AccountId;
+	type Block = Block;
+	type Lookup = IdentityLookup<Self::AccountId>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type AccountStore = System;
+}
+
+parameter_types! {
+	pub static Sent: Vec<(Location, Xcm<()>)> = Vec::new();
+}
+
+/// Delivers messages to the relay chain, charging [`FEE`] of its token, and to [`SIBLING`],
+/// charging in the token of the sibling.
+pub struct TestRouter;
+impl SendXcm for TestRouter {
+	type Ticket = (Location, Xcm<()>);
+
+	fn validate(
+		dest: &mut Option<Location>,
+		message: &mut Option<Xcm<()>>,
+	) -> SendResult<Self::Ticket> {
+		let price: Asset = match dest.as_ref().map(|dest| dest.unpack()) {
+			Some((1, [])) => (Parent, FEE as u128).into(),
+			Some((1, [Parachain(SIBLING)])) => ((Parent, Parachain(SIBLING)), 1u128).into(),
+			_ => return Err(SendError::NotApplicable),
+		};
+		Ok(((dest.take().unwrap(), message.take().unwrap()), price.into()))
+	}
+
+	fn deliver((dest, message): Self::Ticket) -> Result<XcmHash, SendError> {
+		let message_id = sp_io::hashing::blake2_256(&message.encode());
+		Sent::mutate(|sent| sent.push((dest, message)));
+		Ok(message_id)
+	}
+}
+
+parameter_types! {
+	pub const MaxInstructions: u32 = 4;
+	pub const UnitWeightCost: Weight = Weight::from_parts(1_000, 1_000);
+	pub UniversalLocation: InteriorLocation = [GlobalConsensus(Polkadot), Parachain(2000)].into();
+}
+
+type LocalOriginToLocation = SignedToAccountId32<RuntimeOrigin, AccountId32, ()>;
+
+impl pallet_xcm::Config for Test {
+	type AdminOrigin = EnsureRoot<AccountId32>;
+	type AdvertisedXcmVersion = pallet_xcm::CurrentXcmVersion;
+	type Currency = Balances;
+	type CurrencyMatcher = ();
+	type ExecuteXcmOrigin = EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
+	type MaxLockers = ConstU32<0>;
+	type MaxRemoteLockConsumers = ConstU32<0>;
+	type RemoteLockConsumerIdentifier = ();
+	type RuntimeCall = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeOrigin = RuntimeOrigin;
+	type SendXcmOrigin = EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
+	type SovereignAccountOf = ();
+	type TrustedLockers = ();
+	type UniversalLocation = UniversalLocation;
+	type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
+	type WeightInfo = pallet_xcm::TestWeightInfo;
+	type XcmExecuteFilter = Nothing;
+	type XcmExecutor = ();
+	type XcmReserveTransferFilter = Nothing;
+	type XcmRouter = TestRouter;
+	type XcmTeleportFilter = Nothing;
+
+	const VERSION_DISCOVERY_QUEUE_SIZE: u32 = 100;
+}
+
+pub struct IsContract;
+impl Contains<AccountId32> for IsContract {
+	fn contains(account: &AccountId32) -> bool {
+		*account == CONTRACT || *account == POOR_CONTRACT
+	}
+}
+
+parameter_types! {
+	pub RelayLocation: Location = Location::parent();
+	pub const Treasury: AccountId32 = TREASURY;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct BenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl crate::BenchmarkHelper<AccountId32> for BenchmarkHelper {
+	fn contract() -> AccountId32 {
+		CONTRACT
+	}
+
+	fn reachable_dest() -> Option<Location> {
+		Some(Location::parent())
+	}
+}
+
+impl pallet_xcm_gateway::Config for Test {
+	type AllowlistOrigin = EnsureRoot<AccountId32>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = BenchmarkHelper;
+	type Contracts = IsContract;
+	type FeeAsset = RelayLocation;
+	type FeeDestination = Treasury;
+	type Fungible = Balances;
+	type RuntimeEvent = RuntimeEvent;
+	type SendOrigin = EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
+	type WeightInfo = ();
+}
+
+pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(ALICE, 100), (CONTRACT, 100), (POOR_CONTRACT, FEE), (TREASURY, 1)],
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,140 @@
+use codec::Encode;
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::{AccountId32, DispatchError};
+use xcm::{latest::prelude::*, v3, VersionedLocation, VersionedXcm};
+
+use crate::{mock::*, Allowlist, Error, Event};
+
+fn relay() -> Box<VersionedLocation> {
+	Box::new(Location::parent().into())
+}
+
+fn message(instructions: usize) -> Box<VersionedXcm<()>> {
+	Box::new(VersionedXcm::from(Xcm::<()>(vec![ClearOrigin; instructions])))
+}
+
+fn allow(contract: AccountId32, destination: Box<VersionedLocation>) {
+	assert_ok!(XcmGateway::allow_destination(RuntimeOrigin::root(), contract, destination));
+}
+
+#[test]
+fn governance_sets_the_allowed_destinations() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			XcmGateway::allow_destination(RuntimeOrigin::signed(ALICE), CONTRACT, relay()),
+			DispatchError::BadOrigin
+		);
+		allow(CONTRACT, relay());
+		System::assert_last_event(
+			Event::DestinationAllowed { contract: CONTRACT, destination: *relay() }.into(),
+		);
+		assert_noop!(
+			XcmGateway::allow_destination(RuntimeOrigin::root(), CONTRACT, relay()),
+			Error::<Test>::AlreadyAllowed
+		);
+
+		assert_ok!(XcmGateway::disallow_destination(RuntimeOrigin::root(), CONTRACT, relay()));
+		System::assert_last_event(
+			Event::DestinationDisallowed { contract: CONTRACT, destination: *relay() }.into(),
+		);
+		assert_noop!(
+			XcmGateway::disallow_destination(RuntimeOrigin::root(), CONTRACT, relay()),
+			Error::<Test>::DestinationNotAllowed
+		);
+	});
+}
+
+#[test]
+fn destinations_are_allowed_regardless_of_their_version() {
+	new_test_ext().execute_with(|| {
+		allow(CONTRACT, Box::new(VersionedLocation::V3(v3::MultiLocation::parent())));
+		assert!(Allowlist::<Test>::contains_key(CONTRACT, *relay()));
+		assert_noop!(
+			XcmGateway::allow_destination(RuntimeOrigin::root(), CONTRACT, relay()),
+			Error::<Test>::AlreadyAllowed
+		);
+	});
+}
+
+#[test]
+fn contracts_send_messages_from_their_location() {
+	new_test_ext().execute_with(|| {
+		allow(CONTRACT, relay());
+		assert_ok!(XcmGateway::send(RuntimeOrigin::signed(CONTRACT), relay(), message(1)));
+
+		let delivered = Xcm(vec![
+			DescendOrigin(Junction::AccountId32 { network: None, id: CONTRACT.into() }.into()),
+			ClearOrigin,
+		]);
+		assert_eq!(Sent::get(), vec![(Location::parent(), delivered.clone())]);
+		// The delivery fee is paid to the treasury.
+		assert_eq!(Balances::free_balance(CONTRACT), 100 - FEE);
+		assert_eq!(Balances::free_balance(TREASURY), 1 + FEE);
+		System::assert_last_event(
+			Event::Sent {
+				contract: CONTRACT,
+				destination: *relay(),
+				message_id: sp_io::hashing::blake2_256(&delivered.encode()),
+				weight: UnitWeightCost::get(),
+				fee: FEE,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn only_contracts_send_to_allowed_destinations() {
+	new_test_ext().execute_with(|| {
+		allow(CONTRACT, relay());
+		assert_noop!(
+			XcmGateway::send(RuntimeOrigin::root(), relay(), message(1)),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			XcmGateway::send(RuntimeOrigin::signed(ALICE), relay(), message(1)),
+			Error::<Test>::NotContract
+		);
+		assert_noop!(
+			XcmGateway::send(
+				RuntimeOrigin::signed(CONTRACT),
+				Box::new(Location::new(1, Parachain(SIBLING)).into()),
+				message(1)
+			),
+			Error::<Test>::DestinationNotAllowed
+		);
+		assert!(Sent::get().is_empty());
+	});
+}
+
+#[test]
+fn unsendable_messages_are_rejected() {
+	new_test_ext().execute_with(|| {
+		let unreachable: Box<VersionedLocation> =
+			Box::new(Location::new(1, Parachain(SIBLING + 1)).into());
+		let sibling: Box<VersionedLocation> = Box::new(Location::new(1, Parachain(SIBLING)).into());
+		allow(CONTRACT, relay());
+		allow(CONTRACT, unreachable.clone());
+		allow(CONTRACT, sibling.clone());
+		allow(POOR_CONTRACT, relay());
+
+		assert_noop!(
+			XcmGateway::send(RuntimeOrigin::signed(CONTRACT), relay(), message(5)),
+			Error::<Test>::UnweighableMessage
+		);
+		assert_noop!(
+			XcmGateway::send(RuntimeOrigin::signed(CONTRACT), unreachable, message(1)),
+			Error::<Test>::Unreachable
+		);
+		assert_noop!(
+			XcmGateway::send(RuntimeOrigin::signed(CONTRACT), sibling, message(1)),
+			Error::<Test>::UnsupportedFeeAsset
+		);
+		// Paying the fee would reap the account of the contract.
+		assert_noop!(
+			XcmGateway::send(RuntimeOrigin::signed(POOR_CONTRACT), relay(), message(1)),
+			Error::<Test>::FeesNotMet
+		);
+		assert!(Sent::get().is_empty());
+	});
+}
@@ -0,0 +1,63 @@
+//! Weights for `pallet_xcm_gateway`.
+//!
+//! Not measured: these are estimates written by hand from the storage each call accesses, to be
+//! replaced with the weights generated on the reference hardware by
+//! `scripts/benchmark-pallets.sh xcm-gateway` before relying on them on a live chain.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use core::marker::PhantomData;
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions needed for `pallet_xcm_gateway`.
+pub trait WeightInfo {
+	fn allow_destination() -> Weight;
+	fn disallow_destination() -> Weight;
+	fn send() -> Weight;
+}
+
+/// Estimated weights for `pallet_xcm_gateway`.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn allow_destination() -> Weight {
+		Weight::from_parts(15_000_000, 3_574)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn disallow_destination() -> Weight {
+		Weight::from_parts(15_000_000, 3_574)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn send() -> Weight {
+		Weight::from_parts(60_000_000, 3_574)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	fn allow_destination() -> Weight {
+		Weight::from_parts(15_000_000, 3_574)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn disallow_destination() -> Weight {
+		Weight::from_parts(15_000_000, 3_574)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn send() -> Weight {
+		Weight::from_parts(60_000_000, 3_574)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+}
@@ -3,12 +3,15 @@
 pub mod primitives;
 pub mod v0;
 
-use crate::PopApiError::{Balances, Nfts, UnknownStatusCode};
+use crate::PopApiError::{Balances, Nfts, UnknownStatusCode, Xcm, XcmGateway};
 use ink::{prelude::vec::Vec, ChainExtensionInstance};
 use primitives::{cross_chain::*, storage_keys::*};
 pub use sp_runtime::{BoundedVec, MultiAddress, MultiSignature};
 use v0::RuntimeCall;
-pub use v0::{balances, cross_chain, nfts, relay_chain_block_number, state};
+pub use v0::{
+	assets, balances, cross_chain, nfts, relay_chain_block_number, relay_chain_randomness,
+	relay_chain_timestamp, state,
+};
 
 type AccountId = <Environment as ink::env::Environment>::AccountId;
 type Balance = <Environment as ink::env::Environment>::Balance;
@@ -27,6 +30,7 @@ pub enum PopApiError {
 	Balances(balances::Error),
 	Nfts(nfts::Error),
 	Xcm(cross_chain::Error),
+	XcmGateway(cross_chain::gateway::Error),
 }
 
 impl ink::env::chain_extension::FromStatusCode for PopApiError {
@@ -36,6 +40,8 @@ impl ink::env::chain_extension::FromStatusCode for PopApiError {
 			// CallFiltered originates from `frame_system` with pallet-index 0. The CallFiltered error is at index 5
 			5 => Err(PopApiError::SystemCallFiltered),
 			10_000..=10_999 => Err(Balances((status_code - 10_000).try_into()?)),
+			31_000..=31_999 => Err(Xcm((status_code - 31_000).try_into()?)),
+			35_000..=35_999 => Err(XcmGateway((status_code - 35_000).try_into()?)),
 			50_000..=50_999 => Err(Nfts((status_code - 50_000).try_into()?)),
 			_ => Err(UnknownStatusCode(status_code)),
 		}
@@ -80,6 +86,9 @@ pub trait PopApi {
 	#[ink(function = 2)]
 	#[allow(private_interfaces)]
 	fn send_xcm(xcm: CrossChainMessage) -> Result<()>;
+
+	#[ink(function = 3)]
+	fn send_xcm_message(destination: Vec<u8>, message: Vec<u8>) -> Result<()>;
 }
 
 fn dispatch(call: RuntimeCall) -> Result<()> {
@@ -99,3 +108,9 @@ fn send_xcm(xcm: CrossChainMessage) -> Result<()> {
 	)
 	.send_xcm(xcm)
 }
+
+fn send_xcm_message(destination: Vec<u8>, message: Vec<u8>) -> Result<()> {
+	<<Environment as ink::env::Environment>::ChainExtension as ChainExtensionInstance>::instantiate(
+	)
+	.send_xcm_message(destination, message)
+}
@@ -0,0 +1,22 @@
+use crate::{
+	primitives::{
+		storage_keys::{AssetsKeys, RuntimeStateKeys},
+		AssetId,
+	},
+	state, AccountId, Balance, Result,
+};
+
+/// Whether the asset `id` exists.
+pub fn asset_exists(id: AssetId) -> Result<bool> {
+	state::read(RuntimeStateKeys::Assets(AssetsKeys::AssetExists(id)))
+}
+
+/// The total supply of the asset `id`.
+pub fn total_supply(id: AssetId) -> Result<Balance> {
+	state::read(RuntimeStateKeys::Assets(AssetsKeys::TotalSupply(id)))
+}
+
+/// The balance of the asset `id` held by `owner`.
+pub fn balance_of(id: AssetId, owner: AccountId) -> Result<Balance> {
+	state::read(RuntimeStateKeys::Assets(AssetsKeys::BalanceOf(id, *owner.as_ref())))
+}
@@ -0,0 +1,65 @@
+use ink::prelude::vec::Vec;
+
+use crate::{send_xcm_message, PopApiError, PopApiError::UnknownStatusCode};
+
+type Result<T> = core::result::Result<T, Error>;
+
+/// Send an XCM message on behalf of the contract, given as the SCALE encoded `VersionedLocation`
+/// of its destination and the SCALE encoded `VersionedXcm<()>` to send. The message is prefixed
+/// with a `DescendOrigin` to the contract, which first needs to be allowed to send messages to
+/// the destination by governance, and any delivery fee is charged to the contract.
+pub fn send(destination: Vec<u8>, message: Vec<u8>) -> Result<()> {
+	Ok(send_xcm_message(destination, message)?)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Error {
+	/// The destination is already allowed for the contract.
+	AlreadyAllowed,
+	/// The destination or the message could not be decoded or converted to the latest version.
+	BadVersion,
+	/// The contract is not allowed to send messages to the destination.
+	DestinationNotAllowed,
+	/// The contract could not pay the delivery fee.
+	FeesNotMet,
+	/// The origin is not a contract.
+	NotContract,
+	/// The message could not be sent, e.g. because the queue towards the destination is full.
+	SendFailure,
+	/// The delivery fee is charged in an asset other than the native one.
+	UnsupportedFeeAsset,
+	/// There is no route to the destination.
+	Unreachable,
+	/// The weight of the message could not be determined.
+	UnweighableMessage,
+}
+
+impl TryFrom<u32> for Error {
+	type Error = PopApiError;
+
+	fn try_from(status_code: u32) -> core::result::Result<Self, Self::Error> {
+		use Error::*;
+		match status_code {
+			0 => Ok(AlreadyAllowed),
+			1 => Ok(BadVersion),
+			2 => Ok(DestinationNotAllowed),
+			3 => Ok(FeesNotMet),
+			4 => Ok(NotContract),
+			5 => Ok(SendFailure),
+			6 => Ok(UnsupportedFeeAsset),
+			7 => Ok(Unreachable),
+			8 => Ok(UnweighableMessage),
+			_ => Err(UnknownStatusCode(status_code)),
+		}
+	}
+}
+
+impl From<PopApiError> for Error {
+	fn from(error: PopApiError) -> Self {
+		match error {
+			PopApiError::XcmGateway(e) => e,
+			_ => panic!("expected xcm gateway error"),
+		}
+	}
+}
@@ -1,4 +1,5 @@
 pub mod coretime;
+pub mod gateway;
 
 use crate::{PopApiError::UnknownStatusCode, *};
 
@@ -1,8 +1,9 @@
 use crate::{
-	primitives::storage_keys::{ParachainSystemKeys, RuntimeStateKeys},
+	primitives::storage_keys::{ParachainSystemKeys, RelayChainKeys, RuntimeStateKeys},
 	BlockNumber, PopApiError,
 };
 
+pub mod assets;
 pub mod balances;
 pub mod cross_chain;
 pub mod nfts;
@@ -12,6 +13,19 @@ pub fn relay_chain_block_number() -> Result<BlockNumber, PopApiError> {
 	state::read(RuntimeStateKeys::ParachainSystem(ParachainSystemKeys::LastRelayChainBlockNumber))
 }
 
+/// The BABE randomness of the relay chain block the current block is built on, derived from the
+/// VRF output of its author. As the author could withhold its block, it is only suitable where
+/// influencing the outcome is not worth forgoing a block.
+pub fn relay_chain_randomness() -> Result<Option<[u8; 32]>, PopApiError> {
+	state::read(RuntimeStateKeys::RelayChain(RelayChainKeys::Randomness))
+}
+
+/// The start of the slot of the relay chain block the current block is built on, in milliseconds
+/// since the unix epoch.
+pub fn relay_chain_timestamp() -> Result<Option<u64>, PopApiError> {
+	state::read(RuntimeStateKeys::RelayChain(RelayChainKeys::Timestamp))
+}
+
 #[derive(scale::Encode)]
 pub(crate) enum RuntimeCall {
 	#[codec(index = 10)]
@@ -9,6 +9,8 @@ pub mod storage_keys;
 // /// Some way of identifying an account on the chain.
 // #[derive(Encode, Decode, Debug, MaxEncodedLen)]
 // pub struct AccountId([u8; 32]);
+/// Id used for identifying fungible assets.
+pub type AssetId = u32;
 // Id used for identifying non-fungible collections.
 pub type CollectionId = u32;
 // Id used for identifying non-fungible items.
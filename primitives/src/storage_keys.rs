@@ -6,6 +6,8 @@ use super::*;
 pub enum RuntimeStateKeys {
 	Nfts(NftsKeys),
 	ParachainSystem(ParachainSystemKeys),
+	Assets(AssetsKeys),
+	RelayChain(RelayChainKeys),
 }
 
 #[derive(Encode, Decode, Debug, MaxEncodedLen)]
@@ -14,6 +16,18 @@ pub enum ParachainSystemKeys {
 	LastRelayChainBlockNumber,
 }
 
+/// The state of the relay chain block the current block is built on, read from the relay chain
+/// state proof of the block. Each is `None` if the state could not be read.
+#[derive(Encode, Decode, Debug, MaxEncodedLen)]
+pub enum RelayChainKeys {
+	/// Get the number of the relay chain block.
+	BlockNumber,
+	/// Get the BABE randomness of the relay chain block, if any.
+	Randomness,
+	/// Get the start of the slot of the relay chain block, in milliseconds since the unix epoch.
+	Timestamp,
+}
+
 // https://github.com/paritytech/polkadot-sdk/blob/master/substrate/frame/nfts/src/impl_nonfungibles.rs
 #[derive(Encode, Decode, Debug, MaxEncodedLen)]
 pub enum NftsKeys {
@@ -34,3 +48,13 @@ pub enum NftsKeys {
 	/// Get the attribute value of `item` of `collection` corresponding to `key`.
 	CollectionAttribute(CollectionId, BoundedVec<u8, KeyLimit>),
 }
+
+#[derive(Encode, Decode, Debug, MaxEncodedLen)]
+pub enum AssetsKeys {
+	/// Whether the asset exists.
+	AssetExists(AssetId),
+	/// Get the total supply of the asset.
+	TotalSupply(AssetId),
+	/// Get the balance of the asset held by an account, given by its 32 bytes.
+	BalanceOf(AssetId, [u8; 32]),
+}
@@ -0,0 +1,278 @@
+//! The logic of the Pop API chain extension shared by the runtimes, which only provide the state
+//! contracts read and the calls they are allowed to dispatch.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	dispatch::{GetDispatchInfo, PostDispatchInfo, RawOrigin},
+	traits::{Contains, Get, OriginTrait, StorageInfo, StorageInfoTrait},
+	weights::Weight,
+};
+use pallet_contracts::{
+	chain_extension::{BufInBufOutState, ChargedAmount, Environment, Ext, InitState, RetVal},
+	WeightInfo,
+};
+use pop_primitives::cross_chain::CrossChainMessage;
+use sp_runtime::{traits::Dispatchable, DispatchError};
+use sp_std::{vec, vec::Vec};
+use xcm::latest::{
+	Asset,
+	AssetFilter::Wild,
+	Instruction::{BuyExecution, DepositAsset, RefundSurplus, Transact, WithdrawAsset},
+	Junction::{AccountId32, Parachain},
+	Junctions::Here,
+	Location, OriginKind,
+	WeightLimit::Unlimited,
+	WildAsset::AllCounted,
+	Xcm,
+};
+
+use crate::{AccountId, MAX_POV_SIZE};
+
+pub const LOG_TARGET: &str = "pop-api::extension";
+
+type RuntimeCallOf<T> = <T as frame_system::Config>::RuntimeCall;
+
+/// The relay chain tokens withdrawn from the sovereign account of a contract on the relay chain to
+/// pay for the execution of the messages it sends, the surplus of which is refunded to it.
+pub const RELAY_EXECUTION_FEE: u128 = 10_000_000_000;
+/// The weight at most required by the calls contracts send to the relay chain.
+pub const RELAY_CALL_WEIGHT: Weight = Weight::from_parts(250_000_000, 10_000);
+
+/// The functions of the first version of the Pop API. Functions are only ever added, so that the
+/// `func_id` of a function stays the same for the contracts calling it.
+pub mod v0 {
+	#[derive(Debug)]
+	pub enum FuncId {
+		Dispatch,
+		ReadState,
+		SendXcm,
+		SendXcmMessage,
+	}
+}
+
+impl TryFrom<u16> for v0::FuncId {
+	type Error = DispatchError;
+
+	fn try_from(func_id: u16) -> Result<Self, Self::Error> {
+		let id = match func_id {
+			0x0 => Self::Dispatch,
+			0x1 => Self::ReadState,
+			0x2 => Self::SendXcm,
+			0x3 => Self::SendXcmMessage,
+			_ => {
+				log::error!("called an unregistered `func_id`: {:}", func_id);
+				return Err(DispatchError::Other("unimplemented func_id"));
+			},
+		};
+
+		Ok(id)
+	}
+}
+
+/// The state contracts read through the Pop API, along with the weight of reading it.
+pub trait ReadState {
+	/// The key of the state read.
+	type Key: Decode + MaxEncodedLen;
+
+	/// The weight of reading the state of `key`, charged before it is read.
+	fn weight(key: &Self::Key) -> Weight;
+
+	/// The encoded state of `key`.
+	fn read(key: Self::Key) -> Vec<u8>;
+}
+
+/// The status code returned to the contract for the outcome of a function: `0` on success,
+/// otherwise the pallet index in the runtime along with the error index, allowing for 999 errors.
+pub fn status_code(result: Result<(), DispatchError>) -> Result<RetVal, DispatchError> {
+	match result {
+		Ok(()) => Ok(RetVal::Converging(0)),
+		Err(DispatchError::Module(error)) =>
+			Ok(RetVal::Converging((error.index as u32 * 1_000) + u32::from_le_bytes(error.error))),
+		Err(e) => Err(e),
+	}
+}
+
+/// The weight of reading an item of any of the storages `S`: a database read and, as proof size,
+/// the maximum encoded length of their items.
+pub fn storage_read_weight<T: frame_system::Config, S: StorageInfoTrait>() -> Weight {
+	read_weight::<T>(S::storage_info())
+}
+
+/// The weight of reading an item of the `storage` of the pallet `P`, for the pallets whose storages
+/// are private.
+pub fn pallet_storage_read_weight<T: frame_system::Config, P: StorageInfoTrait>(
+	storage: &str,
+) -> Weight {
+	let info = P::storage_info()
+		.into_iter()
+		.filter(|info| info.storage_name == storage.as_bytes())
+		.collect();
+	read_weight::<T>(info)
+}
+
+fn read_weight<T: frame_system::Config>(info: Vec<StorageInfo>) -> Weight {
+	// The items of storages without a maximum encoded length are bounded by the proof size.
+	let proof_size = info
+		.into_iter()
+		.map(|info| info.max_size.unwrap_or(MAX_POV_SIZE))
+		.max()
+		.unwrap_or_default();
+	T::DbWeight::get()
+		.reads(1)
+		.saturating_add(Weight::from_parts(0, proof_size.into()))
+}
+
+/// Dispatch `call` from `origin`, restricted to the calls allowed by `Filter`, refunding the
+/// weight charged in excess of its actual weight.
+pub fn dispatch_call<T, E, Filter>(
+	env: &mut Environment<E, BufInBufOutState>,
+	call: RuntimeCallOf<T>,
+	mut origin: T::RuntimeOrigin,
+	log_prefix: &str,
+) -> Result<(), DispatchError>
+where
+	T: pallet_contracts::Config,
+	RuntimeCallOf<T>: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	E: Ext<T = T>,
+	Filter: Contains<RuntimeCallOf<T>> + 'static,
+{
+	let charged_dispatch_weight = env.charge_weight(call.get_dispatch_info().weight)?;
+
+	log::debug!(target:LOG_TARGET, "{} inputted RuntimeCall: {:?}", log_prefix, call);
+
+	origin.add_filter(Filter::contains);
+
+	match call.dispatch(origin) {
+		Ok(info) => {
+			log::debug!(target:LOG_TARGET, "{} success, actual weight: {:?}", log_prefix, info.actual_weight);
+
+			// refund weight if the actual weight is less than the charged weight
+			if let Some(actual_weight) = info.actual_weight {
+				env.adjust_weight(charged_dispatch_weight, actual_weight);
+			}
+
+			Ok(())
+		},
+		Err(err) => {
+			log::debug!(target:LOG_TARGET, "{} failed: error: {:?}", log_prefix, err.error);
+			Err(err.error)
+		},
+	}
+}
+
+/// Charge the weight of reading the `len` input bytes of a function.
+pub fn charge_overhead_weight<T, E>(
+	env: &mut Environment<E, BufInBufOutState>,
+	len: u32,
+	log_prefix: &str,
+) -> Result<ChargedAmount, DispatchError>
+where
+	T: pallet_contracts::Config,
+	E: Ext<T = T>,
+{
+	// calculate weight for reading bytes of `len`
+	// reference: https://github.com/paritytech/polkadot-sdk/blob/117a9433dac88d5ac00c058c9b39c511d47749d2/substrate/frame/contracts/src/wasm/runtime.rs#L267
+	let base_weight: Weight = T::WeightInfo::seal_return(len);
+
+	// debug_message weight is a good approximation of the additional overhead of going
+	// from contract layer to substrate layer.
+	// reference: https://github.com/paritytech/ink-examples/blob/b8d2caa52cf4691e0ddd7c919e4462311deb5ad0/psp22-extension/runtime/psp22-extension-example.rs#L236
+	let overhead: Weight = T::WeightInfo::seal_debug_message(len);
+
+	let charged_weight = env.charge_weight(base_weight.saturating_add(overhead))?;
+	log::debug!(target: LOG_TARGET, "{} charged weight: {:?}", log_prefix, charged_weight);
+
+	Ok(charged_weight)
+}
+
+/// Dispatch the call given as input on behalf of the calling contract.
+pub fn dispatch<T, E, Filter>(env: Environment<E, InitState>) -> Result<(), DispatchError>
+where
+	T: pallet_contracts::Config,
+	RuntimeCallOf<T>: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	E: Ext<T = T>,
+	Filter: Contains<RuntimeCallOf<T>> + 'static,
+{
+	const LOG_PREFIX: &str = " dispatch |";
+
+	let mut env = env.buf_in_buf_out();
+	let len = env.in_len();
+
+	charge_overhead_weight::<T, E>(&mut env, len, LOG_PREFIX)?;
+
+	// read the input as RuntimeCall
+	let call: RuntimeCallOf<T> = env.read_as_unbounded(len)?;
+
+	// contract is the origin by default
+	let origin: T::RuntimeOrigin = RawOrigin::Signed(env.ext().address().clone()).into();
+
+	dispatch_call::<T, E, Filter>(&mut env, call, origin, LOG_PREFIX)
+}
+
+/// Write the state of the key given as input to the output of the calling contract, charging the
+/// weight of reading it once the key is known.
+pub fn read_state<T, E, State>(env: Environment<E, InitState>) -> Result<(), DispatchError>
+where
+	T: pallet_contracts::Config,
+	E: Ext<T = T>,
+	State: ReadState,
+{
+	const LOG_PREFIX: &str = " read_state |";
+
+	let mut env = env.buf_in_buf_out();
+
+	// To be conservative, we charge the weight for reading the input bytes of a fixed-size type.
+	let base_weight: Weight = T::WeightInfo::seal_return(env.in_len());
+	let charged_weight = env.charge_weight(base_weight)?;
+
+	log::debug!(target:LOG_TARGET, "{} charged weight: {:?}", LOG_PREFIX, charged_weight);
+
+	let key: State::Key = env.read_as()?;
+
+	let charged_weight = env.charge_weight(State::weight(&key))?;
+
+	log::debug!(target:LOG_TARGET, "{} charged weight: {:?}", LOG_PREFIX, charged_weight);
+
+	let result = State::read(key).encode();
+
+	log::trace!(
+		target:LOG_TARGET,
+		"{} result: {:?}.", LOG_PREFIX, result
+	);
+	env.write(&result, false, None).map_err(|e| {
+		log::trace!(target: LOG_TARGET, "{:?}", e);
+		DispatchError::Other("unable to write results to contract memory")
+	})
+}
+
+/// The destination and the XCM of the cross-chain `message` of `contract`, sent from the parachain
+/// `para_id`.
+pub fn cross_chain_message(
+	para_id: u32,
+	contract: AccountId,
+	message: CrossChainMessage,
+) -> (Location, Xcm<()>) {
+	match message {
+		CrossChainMessage::Relay(call) => {
+			// The contract pays for the execution of the call from its sovereign account on the
+			// relay chain, i.e. the account of its location as seen from the relay chain.
+			let sovereign_account = Location::new(
+				0,
+				[Parachain(para_id), AccountId32 { network: None, id: contract.into() }],
+			);
+			let fee: Asset = (Here, RELAY_EXECUTION_FEE).into();
+			let message = Xcm(vec![
+				WithdrawAsset(fee.clone().into()),
+				BuyExecution { fees: fee, weight_limit: Unlimited },
+				Transact {
+					origin_kind: OriginKind::SovereignAccount,
+					require_weight_at_most: RELAY_CALL_WEIGHT,
+					call: call.encode().into(),
+				},
+				RefundSurplus,
+				DepositAsset { assets: Wild(AllCounted(1)), beneficiary: sovereign_account },
+			]);
+			(Location::parent(), message)
+		},
+	}
+}
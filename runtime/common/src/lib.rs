@@ -9,6 +9,9 @@ use sp_runtime::Perbill;
 /// Nonce for an account
 pub type Nonce = u32;
 
+/// The identifier of an asset of `pallet_assets`.
+pub type AssetId = parachains_common::AssetIdForTrustBackedAssets;
+
 /// This determines the average expected block time that we are targeting.
 /// Blocks will be produced at a minimum duration defined by `SLOT_DURATION`.
 /// `SLOT_DURATION` is picked up by `pallet_timestamp` which is in turn picked
@@ -64,6 +67,8 @@ pub const UNINCLUDED_SEGMENT_CAPACITY: u32 = 3;
 /// number of blocks authored per slot.
 pub const BLOCK_PROCESSING_VELOCITY: u32 = 1;
 
+pub mod extensions;
+
 /// Proxy commons for Pop runtimes
 pub mod proxy {
 
@@ -99,6 +104,8 @@ pub mod proxy {
 		MaxEncodedLen,
 		scale_info::TypeInfo,
 	)]
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
 	pub enum ProxyType {
 		/// Fully permissioned proxy. Can execute any call on behalf of _proxied_.
 		Any,
@@ -114,6 +121,8 @@ pub mod proxy {
 		AssetManager,
 		/// Collator selection proxy. Can execute calls related to collator selection mechanism.
 		Collator,
+		/// Governance proxy. Can execute calls related to governance, e.g. vote on motions.
+		Governance,
 	}
 	impl Default for ProxyType {
 		fn default() -> Self {
@@ -130,8 +139,1250 @@ pub mod proxy {
 				(ProxyType::Assets, ProxyType::AssetOwner) => true,
 				(ProxyType::Assets, ProxyType::AssetManager) => true,
 				(ProxyType::NonTransfer, ProxyType::Collator) => true,
+				(ProxyType::NonTransfer, ProxyType::Governance) => true,
 				_ => false,
 			}
 		}
 	}
 }
+
+/// Pop specific runtime APIs.
+pub mod apis {
+	use codec::{Codec, Decode, Encode, MaxEncodedLen};
+	use frame_support::{
+		dispatch::DispatchClass,
+		pallet_prelude::{OptionQuery, ValueQuery},
+		storage::{with_transaction_unchecked, TransactionOutcome},
+		storage_alias,
+		traits::{
+			fungible::Inspect, Currency, Get, PalletsInfoAccess, ReservableCurrency,
+			ValidatorRegistration,
+		},
+		weights::Weight,
+		Blake2_128Concat, Identity, Twox64Concat,
+	};
+	use pallet_collator_selection::{
+		CandidacyBond, CandidateList, DesiredCandidates, Invulnerables,
+	};
+	use pallet_contracts::{
+		debug::{
+			CallInterceptor, CallSpan, ExecResult, ExecReturnValue, ExportedFunction, Tracing,
+		},
+		CollectEvents, ContractExecResult, DebugInfo, Determinism, StorageDeposit,
+	};
+	use polkadot_parachain_primitives::primitives::{Id as ParaId, Sibling};
+	use polkadot_primitives::UpgradeGoAhead as RelayUpgradeGoAhead;
+	use scale_info::TypeInfo;
+	use sp_runtime::{
+		traits::{AccountIdConversion, Convert, Saturating},
+		DispatchError, FixedU128, RuntimeString,
+	};
+	use sp_std::vec::Vec;
+	use xcm::{
+		latest::{Junction, Location},
+		VersionedLocation,
+	};
+	use xcm_builder::{DescribeAllTerminal, DescribeFamily, HashedDescription, ParentIsPreset};
+	use xcm_executor::traits::ConvertLocation;
+
+	use crate::{AccountId, Balance, Hash};
+
+	/// The status of a runtime upgrade which has not been applied yet.
+	#[derive(Clone, Debug, Default, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+	pub struct UpgradeStatus<Hash> {
+		/// The hash of the code authorized to be applied via `system.apply_authorized_upgrade`.
+		pub authorized_hash: Option<Hash>,
+		/// The signal of the relay chain regarding the validation code scheduled to be applied:
+		/// `true` to go ahead and `false` to abort it.
+		pub go_ahead_signal: Option<bool>,
+	}
+
+	// The storage items read by `upgrade_status`, which are not public in their pallets.
+	#[storage_alias(verbatim)]
+	type AuthorizedUpgrade = StorageValue<System, (Hash, bool), OptionQuery>;
+	#[storage_alias(verbatim)]
+	type UpgradeGoAhead = StorageValue<ParachainSystem, Option<RelayUpgradeGoAhead>>;
+
+	/// The status of the pending runtime upgrade, read from the storage of the `System` and
+	/// `ParachainSystem` pallets. Upgrades authorized via `parachain_system.authorize_upgrade`
+	/// are stored by `System` as well.
+	pub fn upgrade_status() -> UpgradeStatus<Hash> {
+		UpgradeStatus {
+			// The authorization is stored as the code hash followed by whether to check the
+			// version of the code.
+			authorized_hash: AuthorizedUpgrade::get().map(|(code_hash, _)| code_hash),
+			go_ahead_signal: UpgradeGoAhead::get()
+				.flatten()
+				.map(|signal| signal == RelayUpgradeGoAhead::GoAhead),
+		}
+	}
+
+	/// The reason why an account cannot register as a collator candidate.
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+	pub enum CollatorIneligibility<Balance> {
+		/// The account is an invulnerable collator already.
+		AlreadyInvulnerable,
+		/// The account is a candidate already.
+		AlreadyCandidate,
+		/// The candidate set is full, i.e. there are as many `candidates` as `desired`.
+		NoSlotsAvailable { candidates: u32, desired: u32 },
+		/// The account has not registered its session keys.
+		SessionKeysNotRegistered,
+		/// The free balance of the account cannot cover the candidacy `bond`.
+		InsufficientBalance { bond: Balance, free: Balance },
+	}
+
+	/// Whether `who` would be accepted as a collator candidate by
+	/// `collator_selection.register_as_candidate`, or the reason why not.
+	///
+	/// Candidates are considered up to the number of desired candidates, which never exceeds the
+	/// maximum number of candidates.
+	pub fn collator_eligibility<T: pallet_collator_selection::Config>(
+		who: &T::AccountId,
+	) -> Result<(), CollatorIneligibility<<T::Currency as Currency<T::AccountId>>::Balance>> {
+		use CollatorIneligibility::*;
+
+		if Invulnerables::<T>::get().contains(who) {
+			return Err(AlreadyInvulnerable);
+		}
+		let candidates = CandidateList::<T>::get();
+		if candidates.iter().any(|candidate| &candidate.who == who) {
+			return Err(AlreadyCandidate);
+		}
+		let desired = DesiredCandidates::<T>::get().min(T::MaxCandidates::get());
+		let candidates = candidates.len() as u32;
+		if candidates >= desired {
+			return Err(NoSlotsAvailable { candidates, desired });
+		}
+		let registered = T::ValidatorIdOf::convert(who.clone())
+			.map_or(false, |validator| T::ValidatorRegistration::is_registered(&validator));
+		if !registered {
+			return Err(SessionKeysNotRegistered);
+		}
+		let bond = CandidacyBond::<T>::get();
+		if !T::Currency::can_reserve(who, bond) {
+			return Err(InsufficientBalance { bond, free: T::Currency::free_balance(who) });
+		}
+		Ok(())
+	}
+
+	/// The collators of a chain, along with the configuration of their selection by
+	/// `pallet_collator_selection`.
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+	pub struct CollatorSet<AccountId, Balance> {
+		/// The collators of the current session.
+		pub collators: Vec<AccountId>,
+		/// The invulnerable collators, which are selected for every session.
+		pub invulnerables: Vec<AccountId>,
+		/// The candidates along with their deposits, ordered by ascending deposit. Those with the
+		/// highest deposits are selected, up to the desired number of candidates.
+		pub candidates: Vec<(AccountId, Balance)>,
+		/// The number of candidates selected besides the invulnerables.
+		pub desired_candidates: u32,
+		/// The bond a candidate must reserve to register.
+		pub candidacy_bond: Balance,
+	}
+
+	/// The collator set of the chain, whose current session has the `collators`.
+	pub fn collator_set<T: pallet_collator_selection::Config>(
+		collators: Vec<T::AccountId>,
+	) -> CollatorSet<T::AccountId, <T::Currency as Currency<T::AccountId>>::Balance> {
+		CollatorSet {
+			collators,
+			invulnerables: Invulnerables::<T>::get().into_inner(),
+			candidates: CandidateList::<T>::get()
+				.into_iter()
+				.map(|candidate| (candidate.who, candidate.deposit))
+				.collect(),
+			desired_candidates: DesiredCandidates::<T>::get().min(T::MaxCandidates::get()),
+			candidacy_bond: CandidacyBond::<T>::get(),
+		}
+	}
+
+	/// Whether a frame of a traced contract call executed a contract or instantiated one.
+	#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+	pub enum FrameKind {
+		/// The `call` export of the contract was executed.
+		Call,
+		/// The `deploy` export of the contract was executed.
+		Instantiate,
+	}
+
+	/// How the execution of a frame of a traced contract call ended.
+	#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+	pub enum FrameOutcome {
+		/// The contract returned successfully.
+		Returned,
+		/// The contract returned with the revert flag set, discarding its changes.
+		Reverted,
+		/// The contract trapped, e.g. by running out of gas.
+		Trapped,
+	}
+
+	/// A frame of a traced contract call, along with the frames of the calls it made.
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+	pub struct CallFrame<AccountId> {
+		/// The contract executed by the frame.
+		pub contract: AccountId,
+		/// The export of the contract that was executed.
+		pub kind: FrameKind,
+		/// The input passed to the contract.
+		pub input: Vec<u8>,
+		/// The output returned by the contract, empty if it trapped.
+		pub output: Vec<u8>,
+		/// How the execution of the frame ended.
+		pub outcome: FrameOutcome,
+		/// The gas consumed by the frame, including the frames of the calls it made.
+		///
+		/// `pallet_contracts` does not expose the gas meter of nested frames, so nested calls are
+		/// measured by dry-running them on their own, as called by their caller without any value.
+		/// Nested instantiations cannot be dry-run on their own and are not measured.
+		pub gas_consumed: Option<Weight>,
+		/// The frames of the calls made by the contract, in order.
+		pub calls: Vec<CallFrame<AccountId>>,
+	}
+
+	/// The frames of a contract call being traced.
+	#[derive(Default)]
+	struct CallTrace {
+		/// The frames which are executing, starting with the outermost one.
+		open: Vec<CallFrame<AccountId>>,
+		/// The outermost frame, once it finished executing.
+		root: Option<CallFrame<AccountId>>,
+	}
+
+	impl CallTrace {
+		/// Close the frame at `depth` with `output`, along with the frames above it. The frames
+		/// above it have not been closed themselves because they trapped.
+		fn close(&mut self, depth: usize, output: Option<&ExecReturnValue>) {
+			while self.open.len() >= depth {
+				let Some(mut frame) = self.open.pop() else { return };
+				if self.open.len() + 1 == depth {
+					if let Some(output) = output {
+						frame.output = output.data.clone();
+						frame.outcome = if output.did_revert() {
+							FrameOutcome::Reverted
+						} else {
+							FrameOutcome::Returned
+						};
+					}
+				}
+				match self.open.last_mut() {
+					Some(caller) => caller.calls.push(frame),
+					None => self.root = Some(frame),
+				}
+			}
+		}
+	}
+
+	environmental::environmental!(call_trace: CallTrace);
+
+	/// The contract debugger of runtimes supporting [`ContractTracingApi`], recording the frames of
+	/// the contract calls traced by [`trace_contract_call`]. Other contract executions are not
+	/// affected.
+	///
+	/// A trapped frame is only closed when its caller returns, so any calls its caller makes
+	/// after the trap are recorded as calls of the trapped frame.
+	pub struct ContractTracer;
+
+	/// The span of a frame recorded by [`ContractTracer`].
+	pub struct TracedSpan {
+		/// The number of frames executing, including this one. `None` if not tracing.
+		depth: Option<usize>,
+	}
+
+	impl<T: pallet_contracts::Config<AccountId = AccountId>> Tracing<T> for ContractTracer {
+		type CallSpan = TracedSpan;
+
+		fn new_call_span(
+			contract_address: &AccountId,
+			entry_point: ExportedFunction,
+			input_data: &[u8],
+		) -> TracedSpan {
+			let depth = call_trace::with(|trace| {
+				trace.open.push(CallFrame {
+					contract: contract_address.clone(),
+					kind: match entry_point {
+						ExportedFunction::Call => FrameKind::Call,
+						ExportedFunction::Constructor => FrameKind::Instantiate,
+					},
+					input: input_data.to_vec(),
+					output: Vec::new(),
+					outcome: FrameOutcome::Trapped,
+					gas_consumed: None,
+					calls: Vec::new(),
+				});
+				trace.open.len()
+			});
+			TracedSpan { depth }
+		}
+	}
+
+	impl CallSpan for TracedSpan {
+		fn after_call(self, output: &ExecReturnValue) {
+			if let Some(depth) = self.depth {
+				call_trace::with(|trace| trace.close(depth, Some(output)));
+			}
+		}
+	}
+
+	impl<T: pallet_contracts::Config<AccountId = AccountId>> CallInterceptor<T> for ContractTracer {
+		fn intercept_call(
+			_contract_address: &AccountId,
+			_entry_point: &ExportedFunction,
+			_input_data: &[u8],
+		) -> Option<ExecResult> {
+			None
+		}
+	}
+
+	/// The storage deposit of a frame of a debugged contract call, along with the frames of the
+	/// calls it made, mirroring the [`CallFrame`] of the call.
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	pub struct FrameDeposit<AccountId, Balance> {
+		/// The contract executed by the frame.
+		pub contract: AccountId,
+		/// The storage deposit charged or refunded by the frame, including the frames of the calls
+		/// it made.
+		///
+		/// Nested calls are measured by dry-running them on their own, as for the gas consumed by
+		/// a [`CallFrame`], and nested instantiations are not measured.
+		pub storage_deposit: Option<StorageDeposit<Balance>>,
+		/// The deposits of the frames of the calls made by the contract, in order.
+		pub calls: Vec<FrameDeposit<AccountId, Balance>>,
+	}
+
+	/// An event emitted by a contract during a debugged contract call.
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	pub struct ContractEvent<AccountId> {
+		/// The contract that emitted the event.
+		pub contract: AccountId,
+		/// The data of the event, to be decoded with the metadata of the contract.
+		pub data: Vec<u8>,
+		/// The topics of the event.
+		pub topics: Vec<Hash>,
+	}
+
+	/// A contract call executed for debugging, e.g. to investigate reverts only reproducing
+	/// on-chain.
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	pub struct DebuggedCall<AccountId, Balance> {
+		/// The frames executed by the call.
+		pub trace: CallFrame<AccountId>,
+		/// The storage deposit of each frame.
+		pub deposits: FrameDeposit<AccountId, Balance>,
+		/// The debug messages emitted by the contracts executed, in order.
+		pub debug_message: Vec<u8>,
+		/// The events emitted by the contracts executed, in order.
+		pub events: Vec<ContractEvent<AccountId>>,
+	}
+
+	/// Dry-run a call of the contract at `dest` by `origin`, transferring `value` and consuming up
+	/// to `gas_limit`, and return the tree of frames it executed. Requires [`ContractTracer`] to
+	/// be the contract debugger of the runtime.
+	///
+	/// Changes made by the call are discarded. Fails if the contract was not executed at all,
+	/// e.g. because there is no contract at `dest`.
+	pub fn trace_contract_call<T: pallet_contracts::Config<AccountId = AccountId>>(
+		origin: AccountId,
+		dest: AccountId,
+		value: <T::Currency as Inspect<AccountId>>::Balance,
+		gas_limit: Weight,
+		input_data: Vec<u8>,
+	) -> Result<CallFrame<AccountId>, DispatchError> {
+		let mut trace = CallTrace::default();
+		let result = call_trace::using(&mut trace, || {
+			dry_run::<T>(origin, dest, value, gas_limit, input_data)
+		});
+		trace.close(1, None);
+		let mut root = trace.root.ok_or_else(|| {
+			result.result.err().unwrap_or(DispatchError::Other("Contract not executed"))
+		})?;
+		root.gas_consumed = Some(result.gas_consumed);
+		let caller = root.contract.clone();
+		for call in &mut root.calls {
+			measure::<T>(&caller, call, gas_limit);
+		}
+		Ok(root)
+	}
+
+	/// Dry-run a call of the contract at `dest` by `origin` as [`trace_contract_call`] does, also
+	/// returning the debug messages and events emitted by the contracts executed along with the
+	/// storage deposit of each frame.
+	pub fn debug_contract_call<T>(
+		origin: AccountId,
+		dest: AccountId,
+		value: <T::Currency as Inspect<AccountId>>::Balance,
+		gas_limit: Weight,
+		input_data: Vec<u8>,
+	) -> Result<DebuggedCall<AccountId, <T::Currency as Inspect<AccountId>>::Balance>, DispatchError>
+	where
+		T: pallet_contracts::Config<AccountId = AccountId, Hash = Hash>,
+		<T as frame_system::Config>::RuntimeEvent: TryInto<pallet_contracts::Event<T>>,
+	{
+		// The events collected include those emitted by the block before the call.
+		let emitted_before = frame_system::Pallet::<T>::event_count() as usize;
+		let mut trace = CallTrace::default();
+		let result = call_trace::using(&mut trace, || {
+			with_transaction_unchecked(|| {
+				let result = pallet_contracts::Pallet::<T>::bare_call(
+					origin,
+					dest,
+					value,
+					gas_limit,
+					None,
+					input_data,
+					DebugInfo::UnsafeDebug,
+					CollectEvents::UnsafeCollect,
+					Determinism::Enforced,
+				);
+				TransactionOutcome::Rollback(result)
+			})
+		});
+		trace.close(1, None);
+		let mut root = trace.root.ok_or_else(|| {
+			result.result.err().unwrap_or(DispatchError::Other("Contract not executed"))
+		})?;
+		root.gas_consumed = Some(result.gas_consumed);
+		let caller = root.contract.clone();
+		let deposits = FrameDeposit {
+			contract: root.contract.clone(),
+			storage_deposit: Some(result.storage_deposit),
+			calls: root
+				.calls
+				.iter_mut()
+				.map(|call| measure::<T>(&caller, call, gas_limit))
+				.collect(),
+		};
+		let events = result
+			.events
+			.unwrap_or_default()
+			.into_iter()
+			.skip(emitted_before)
+			.filter_map(|record| match record.event.try_into() {
+				Ok(pallet_contracts::Event::<T>::ContractEmitted { contract, data }) =>
+					Some(ContractEvent { contract, data, topics: record.topics }),
+				_ => None,
+			})
+			.collect();
+		Ok(DebuggedCall { trace: root, deposits, debug_message: result.debug_message, events })
+	}
+
+	/// Call the contract at `dest`, discarding the changes made by the call, and return its result.
+	fn dry_run<T: pallet_contracts::Config<AccountId = AccountId>>(
+		origin: AccountId,
+		dest: AccountId,
+		value: <T::Currency as Inspect<AccountId>>::Balance,
+		gas_limit: Weight,
+		input_data: Vec<u8>,
+	) -> ContractExecResult<
+		<T::Currency as Inspect<AccountId>>::Balance,
+		frame_system::EventRecord<
+			<T as frame_system::Config>::RuntimeEvent,
+			<T as frame_system::Config>::Hash,
+		>,
+	> {
+		with_transaction_unchecked(|| {
+			let result = pallet_contracts::Pallet::<T>::bare_call(
+				origin,
+				dest,
+				value,
+				gas_limit,
+				None,
+				input_data,
+				DebugInfo::Skip,
+				CollectEvents::Skip,
+				Determinism::Enforced,
+			);
+			TransactionOutcome::Rollback(result)
+		})
+	}
+
+	/// Measure the gas consumed and the storage deposit of the nested `frame` called by `caller`,
+	/// and of the frames it called in turn, by dry-running each call on its own.
+	fn measure<T: pallet_contracts::Config<AccountId = AccountId>>(
+		caller: &AccountId,
+		frame: &mut CallFrame<AccountId>,
+		gas_limit: Weight,
+	) -> FrameDeposit<AccountId, <T::Currency as Inspect<AccountId>>::Balance> {
+		let mut storage_deposit = None;
+		if frame.kind == FrameKind::Call {
+			let result = dry_run::<T>(
+				caller.clone(),
+				frame.contract.clone(),
+				Default::default(),
+				gas_limit,
+				frame.input.clone(),
+			);
+			frame.gas_consumed = Some(result.gas_consumed);
+			storage_deposit = Some(result.storage_deposit);
+		}
+		let contract = frame.contract.clone();
+		FrameDeposit {
+			calls: frame
+				.calls
+				.iter_mut()
+				.map(|call| measure::<T>(&contract, call, gas_limit))
+				.collect(),
+			contract,
+			storage_deposit,
+		}
+	}
+
+	/// The deposit a contract must hold for storing `bytes` bytes across `items` storage items,
+	/// as charged by `pallet_contracts`.
+	pub fn storage_deposit<T: pallet_contracts::Config<AccountId = AccountId>>(
+		bytes: u32,
+		items: u32,
+	) -> <T::Currency as Inspect<AccountId>>::Balance {
+		T::DepositPerByte::get()
+			.saturating_mul(bytes.into())
+			.saturating_add(T::DepositPerItem::get().saturating_mul(items.into()))
+	}
+
+	/// The reason why an account cannot afford to instantiate a contract.
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+	pub enum InstantiationUnaffordable<Balance> {
+		/// No code is stored under the code hash.
+		CodeNotFound,
+		/// The free balance of the account, less the existential deposit it must keep, cannot
+		/// cover the `cost` of the instantiation.
+		InsufficientBalance { cost: Balance, available: Balance },
+	}
+
+	/// The information `pallet_contracts` stores about uploaded code, which is not public in the
+	/// pallet.
+	#[derive(Encode, Decode)]
+	struct CodeInfo {
+		_owner: AccountId,
+		#[codec(compact)]
+		deposit: Balance,
+		#[codec(compact)]
+		_refcount: u64,
+		_determinism: Determinism,
+		_code_len: u32,
+	}
+
+	#[storage_alias(verbatim)]
+	type CodeInfoOf = StorageMap<Contracts, Identity, Hash, CodeInfo>;
+
+	/// The encoded size of the information `pallet_contracts` stores about a new contract: its
+	/// trie id and code hash, the bytes and items it stores, its three deposits and its (empty)
+	/// delegate dependencies. Checked against the deposit held for a contract in the runtime tests,
+	/// as the pallet does not expose it.
+	const CONTRACT_INFO_LEN: u32 = 33 + 32 + 4 + 4 + 3 * 16 + 1;
+
+	/// The base deposit `pallet_contracts` holds for a contract of the code `code_hash`, if the
+	/// code is stored: the deposit of the information it stores about the contract, along with
+	/// the share of the deposit of the code locked up by the contract, which prevents the code
+	/// from being removed.
+	pub fn contract_base_deposit<T>(code_hash: Hash) -> Option<Balance>
+	where
+		T: pallet_contracts::Config<AccountId = AccountId>,
+		T::Currency: Inspect<AccountId, Balance = Balance>,
+	{
+		let code_info = CodeInfoOf::get(code_hash)?;
+		Some(
+			storage_deposit::<T>(CONTRACT_INFO_LEN, 1)
+				.saturating_add(T::CodeHashLockupDepositPercent::get().mul_ceil(code_info.deposit)),
+		)
+	}
+
+	/// Whether `who` can afford to instantiate a contract of the code `code_hash` transferring
+	/// `endowment` to it, paying `fee` for the transaction, or the reason why not.
+	///
+	/// The cost covers the existential deposit transferred to the account of the contract and the
+	/// base deposit of the contract, but not the deposits of the storage written by its
+	/// constructor, which depend on its execution.
+	pub fn instantiation_affordability<T>(
+		who: &AccountId,
+		code_hash: Hash,
+		endowment: Balance,
+		fee: Balance,
+	) -> Result<(), InstantiationUnaffordable<Balance>>
+	where
+		T: pallet_contracts::Config<AccountId = AccountId>,
+		T::Currency: Inspect<AccountId, Balance = Balance>,
+	{
+		let base_deposit =
+			contract_base_deposit::<T>(code_hash).ok_or(InstantiationUnaffordable::CodeNotFound)?;
+		let existential_deposit = T::Currency::minimum_balance();
+		let cost = endowment
+			.saturating_add(existential_deposit)
+			.saturating_add(base_deposit)
+			.saturating_add(fee);
+		let available = T::Currency::balance(who).saturating_sub(existential_deposit);
+		if cost > available {
+			return Err(InstantiationUnaffordable::InsufficientBalance { cost, available });
+		}
+		Ok(())
+	}
+
+	/// The estimated cost of a contract call, paid by its caller besides the value it transfers.
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+	pub struct ContractCallCost<Balance> {
+		/// The gas limit required for the call to succeed, which may exceed the gas it consumes.
+		pub gas_required: Weight,
+		/// The deposit charged for the storage added by the call, which is zero if the call
+		/// refunds deposits instead.
+		pub storage_deposit: Balance,
+		/// The fee of a transaction making the call with the gas required as its gas limit.
+		pub fee: Balance,
+		/// The fee along with the storage deposit.
+		pub total: Balance,
+	}
+
+	/// Dry-run a call of the contract at `dest` by `origin` transferring `value` and consuming up
+	/// to `gas_limit`, and estimate its cost given `fee`, the fee of a transaction making the
+	/// call with a gas limit, a storage deposit limit and the input data.
+	///
+	/// Changes made by the call are discarded. Fails as the call would, including when the
+	/// contract reverts.
+	pub fn contract_call_cost<T>(
+		origin: AccountId,
+		dest: AccountId,
+		value: Balance,
+		gas_limit: Weight,
+		input_data: Vec<u8>,
+		fee: impl FnOnce(Weight, Balance, Vec<u8>) -> Balance,
+	) -> Result<ContractCallCost<Balance>, DispatchError>
+	where
+		T: pallet_contracts::Config<AccountId = AccountId>,
+		T::Currency: Inspect<AccountId, Balance = Balance>,
+	{
+		let result = with_transaction_unchecked(|| {
+			TransactionOutcome::Rollback(pallet_contracts::Pallet::<T>::bare_call(
+				origin,
+				dest,
+				value,
+				gas_limit,
+				None,
+				input_data.clone(),
+				DebugInfo::Skip,
+				CollectEvents::Skip,
+				Determinism::Enforced,
+			))
+		});
+		if result.result?.did_revert() {
+			return Err(pallet_contracts::Error::<T>::ContractReverted.into());
+		}
+		let storage_deposit = match result.storage_deposit {
+			StorageDeposit::Charge(amount) => amount,
+			StorageDeposit::Refund(_) => 0,
+		};
+		let fee = fee(result.gas_required, storage_deposit, input_data);
+		Ok(ContractCallCost {
+			gas_required: result.gas_required,
+			storage_deposit,
+			fee,
+			total: fee.saturating_add(storage_deposit),
+		})
+	}
+
+	/// The code of a contract, along with the metadata stored on-chain for it.
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	pub struct ContractMetadata<Hash> {
+		/// The hash of the code of the contract.
+		pub code_hash: Hash,
+		/// The metadata stored for the code, e.g. its ABI, if any.
+		pub metadata: Option<Vec<u8>>,
+	}
+
+	/// The accounts `account` has delegated to as proxies, along with the type of calls each may
+	/// make on its behalf, read from the storage of `pallet_proxy`.
+	pub fn proxies<T: pallet_proxy::Config>(
+		account: &T::AccountId,
+	) -> Vec<(T::AccountId, T::ProxyType)> {
+		let (proxies, _deposit) = pallet_proxy::Proxies::<T>::get(account);
+		proxies.into_iter().map(|proxy| (proxy.delegate, proxy.proxy_type)).collect()
+	}
+
+	/// The XCM messages queued for delivery but not sent yet.
+	#[derive(Clone, Debug, Default, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+	pub struct XcmQueue {
+		/// The messages queued for the relay chain via UMP.
+		pub upward: QueuedMessages,
+		/// The messages queued for each sibling parachain with an outbound XCMP channel.
+		pub horizontal: Vec<OutboundChannel>,
+	}
+
+	/// A number of queued messages along with their total size in bytes.
+	#[derive(Clone, Debug, Default, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+	pub struct QueuedMessages {
+		/// The number of messages, or of pages of messages for XCMP, which concatenates the
+		/// messages to a recipient into pages.
+		pub count: u32,
+		/// The total size of the messages in bytes.
+		pub bytes: u32,
+	}
+
+	/// The messages queued for a sibling parachain via XCMP.
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+	pub struct OutboundChannel {
+		/// The para id of the sibling parachain.
+		pub recipient: u32,
+		/// Whether the sibling suspended the channel, leaving the messages queued until it
+		/// resumes it.
+		pub suspended: bool,
+		/// The pages of messages queued.
+		pub queued: QueuedMessages,
+	}
+
+	/// The state of an outbound XCMP channel as stored by `cumulus_pallet_xcmp_queue`, which is
+	/// not public in the pallet.
+	#[derive(Encode, Decode, PartialEq)]
+	enum OutboundState {
+		Ok,
+		Suspended,
+	}
+
+	/// The details of an outbound XCMP channel as stored by `cumulus_pallet_xcmp_queue`, which
+	/// are not public in the pallet.
+	#[derive(Encode, Decode)]
+	struct OutboundChannelDetails {
+		recipient: u32,
+		state: OutboundState,
+		_signals_exist: bool,
+		first_index: u16,
+		last_index: u16,
+	}
+
+	// The storage items read by `xcm_queue`.
+	#[storage_alias(verbatim)]
+	type PendingUpwardMessages = StorageValue<ParachainSystem, Vec<Vec<u8>>, ValueQuery>;
+	#[storage_alias(verbatim)]
+	type OutboundXcmpStatus = StorageValue<XcmpQueue, Vec<OutboundChannelDetails>, ValueQuery>;
+	#[storage_alias(verbatim)]
+	type OutboundXcmpMessages =
+		StorageDoubleMap<XcmpQueue, Blake2_128Concat, u32, Twox64Concat, u16, Vec<u8>>;
+
+	/// The XCM messages queued for delivery, read from the storage of the `ParachainSystem` and
+	/// `XcmpQueue` pallets. Messages are moved out of these queues as the blocks including them
+	/// are built, so messages lingering across blocks signal that delivery is stuck.
+	pub fn xcm_queue() -> XcmQueue {
+		let len = |len: usize| u32::try_from(len).unwrap_or(u32::MAX);
+		let upward = PendingUpwardMessages::get();
+		let horizontal = OutboundXcmpStatus::get()
+			.into_iter()
+			.map(|channel| {
+				let pages = channel.first_index..channel.last_index;
+				let bytes = pages.clone().fold(0u32, |bytes, page| {
+					let page_len = OutboundXcmpMessages::decode_len(channel.recipient, page);
+					bytes.saturating_add(len(page_len.unwrap_or_default()))
+				});
+				OutboundChannel {
+					recipient: channel.recipient,
+					suspended: channel.state == OutboundState::Suspended,
+					queued: QueuedMessages { count: len(pages.len()), bytes },
+				}
+			})
+			.collect();
+		XcmQueue {
+			upward: QueuedMessages {
+				count: len(upward.len()),
+				bytes: upward
+					.iter()
+					.fold(0, |bytes, message| bytes.saturating_add(len(message.len()))),
+			},
+			horizontal,
+		}
+	}
+
+	/// The XCM messages received but not processed yet, queued by `pallet_message_queue` until
+	/// they are serviced within the weight limits of the queue.
+	#[derive(Clone, Debug, Default, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+	pub struct InboundXcmQueue {
+		/// The messages received from the relay chain via DMP.
+		pub downward: InboundMessages,
+		/// The messages received from each sibling parachain, by para id.
+		pub horizontal: Vec<InboundChannel>,
+		/// Whether the processing of XCMP messages is suspended, e.g. by governance.
+		pub suspended: bool,
+	}
+
+	/// The messages received from an origin and queued for processing.
+	#[derive(Clone, Debug, Default, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+	pub struct InboundMessages {
+		/// The number of pages storing the messages.
+		pub pages: u32,
+		/// The number of messages.
+		pub count: u64,
+		/// The total size of the messages in bytes.
+		pub bytes: u64,
+	}
+
+	/// The messages queued from a sibling parachain via XCMP.
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+	pub struct InboundChannel {
+		/// The para id of the sibling parachain.
+		pub sender: u32,
+		/// Whether the channel was suspended as its queue grew beyond the suspension threshold,
+		/// asking the sibling to hold back its messages until the queue drains.
+		pub suspended: bool,
+		/// The messages queued.
+		pub queued: InboundMessages,
+	}
+
+	/// The origin of a queue of `pallet_message_queue`, as `AggregateMessageOrigin` of
+	/// `cumulus_primitives_core`.
+	#[derive(Encode, Decode)]
+	enum MessageOrigin {
+		Here,
+		Parent,
+		Sibling(u32),
+	}
+
+	/// The state of a queue as stored by `pallet_message_queue`, which is not public in the
+	/// pallet.
+	#[derive(Encode, Decode)]
+	struct BookState {
+		_begin: u32,
+		_end: u32,
+		count: u32,
+		_ready_neighbours: Option<(MessageOrigin, MessageOrigin)>,
+		message_count: u64,
+		size: u64,
+	}
+
+	// The storage items read by `inbound_xcm_queue`.
+	#[storage_alias(verbatim)]
+	type BookStateFor = StorageMap<MessageQueue, Twox64Concat, MessageOrigin, BookState>;
+	#[storage_alias(verbatim)]
+	type InboundXcmpSuspended = StorageValue<XcmpQueue, Vec<u32>, ValueQuery>;
+	#[storage_alias(verbatim)]
+	type QueueSuspended = StorageValue<XcmpQueue, bool, ValueQuery>;
+
+	/// The XCM messages received but not processed yet, read from the storage of the
+	/// `MessageQueue` and `XcmpQueue` pallets. A channel being suspended signals that messages
+	/// arrive faster than they are processed, the sibling being asked to hold them back.
+	pub fn inbound_xcm_queue() -> InboundXcmQueue {
+		let suspended_channels = InboundXcmpSuspended::get();
+		let mut queue = InboundXcmQueue { suspended: QueueSuspended::get(), ..Default::default() };
+		for (origin, book) in BookStateFor::iter() {
+			let queued =
+				InboundMessages { pages: book.count, count: book.message_count, bytes: book.size };
+			match origin {
+				MessageOrigin::Here => {},
+				MessageOrigin::Parent => queue.downward = queued,
+				MessageOrigin::Sibling(sender) => queue.horizontal.push(InboundChannel {
+					sender,
+					suspended: suspended_channels.contains(&sender),
+					queued,
+				}),
+			}
+		}
+		// Channels are suspended while their queue drains, so have messages queued unless
+		// resuming them failed.
+		for &sender in &suspended_channels {
+			if !queue.horizontal.iter().any(|channel| channel.sender == sender) {
+				queue.horizontal.push(InboundChannel {
+					sender,
+					suspended: true,
+					queued: Default::default(),
+				});
+			}
+		}
+		queue.horizontal.sort_by_key(|channel| channel.sender);
+		queue
+	}
+
+	/// The native tokens in circulation: the total issuance less the balances frozen on accounts,
+	/// e.g. by locks, which cannot be transferred, and less the balances of the `excluded`
+	/// accounts, e.g. the treasury, whose funds do not circulate either.
+	///
+	/// Iterates over every account, so it must only be called off-chain, e.g. by runtime APIs
+	/// exposed as unsafe RPC methods, and never while building blocks.
+	pub fn circulating_supply<T>(excluded: &[AccountId]) -> Balance
+	where
+		T: pallet_balances::Config<Balance = Balance>
+			+ frame_system::Config<
+				AccountId = AccountId,
+				AccountData = pallet_balances::AccountData<Balance>,
+			>,
+	{
+		let locked =
+			frame_system::Account::<T>::iter().fold(0, |locked: Balance, (who, account)| {
+				let pallet_balances::AccountData { free, reserved, frozen, .. } = account.data;
+				let total = free.saturating_add(reserved);
+				// Balances may be frozen beyond what the account holds.
+				let locked_here = if excluded.contains(&who) { total } else { frozen.min(total) };
+				locked.saturating_add(locked_here)
+			});
+		pallet_balances::Pallet::<T>::total_issuance().saturating_sub(locked)
+	}
+
+	/// The local account of `location` as converted by `Conversion`, e.g. the sovereign account of
+	/// a sibling parachain, if the location can be converted to the latest version of XCM and has
+	/// a local account.
+	pub fn location_to_account<Conversion: ConvertLocation<AccountId>>(
+		location: VersionedLocation,
+	) -> Option<AccountId> {
+		Conversion::convert_location(&Location::try_from(location).ok()?)
+	}
+
+	/// The location of the local `account` as seen from this chain, which prefixed with the
+	/// location of this chain is the location of the account as seen from other chains.
+	pub fn account_location(account: AccountId) -> VersionedLocation {
+		Location::new(0, [Junction::AccountId32 { network: None, id: account.into() }]).into()
+	}
+
+	/// The sovereign account of the sibling parachain `para_id`, as converted by
+	/// `SiblingParachainConvertsVia`.
+	pub fn sibling_account(para_id: u32) -> AccountId {
+		Sibling::from(ParaId::from(para_id)).into_account_truncating()
+	}
+
+	/// The sovereign account of the relay chain, as converted by `ParentIsPreset`.
+	pub fn relay_chain_account() -> AccountId {
+		ParentIsPreset::<AccountId>::convert_location(&Location::parent())
+			.expect("the parent location converts to its preset account; qed")
+	}
+
+	/// The account of `location` derived from the hash of its description, as chains like Asset
+	/// Hub derive the accounts of remote locations, if the location can be described.
+	pub fn hashed_account(location: VersionedLocation) -> Option<AccountId> {
+		location_to_account::<HashedDescription<AccountId, DescribeFamily<DescribeAllTerminal>>>(
+			location,
+		)
+	}
+
+	// The storage item read by `negotiated_xcm_version`, which is not public in `pallet_xcm`.
+	#[storage_alias(verbatim)]
+	type SupportedVersion = StorageDoubleMap<
+		PolkadotXcm,
+		Twox64Concat,
+		u32,
+		Blake2_128Concat,
+		VersionedLocation,
+		u32,
+		OptionQuery,
+	>;
+
+	/// The XCM version negotiated with `dest`, read from the storage of the `PolkadotXcm` pallet,
+	/// which records it once `dest` responds to the version subscription or its version is
+	/// forced. Until then, messages to `dest` are only sent if a safe XCM version is configured.
+	pub fn negotiated_xcm_version(dest: VersionedLocation) -> Option<u32> {
+		// Versions are recorded by the location of the destination in the latest version of XCM.
+		let dest = VersionedLocation::from(Location::try_from(dest).ok()?);
+		SupportedVersion::get(xcm::latest::VERSION, dest)
+	}
+
+	/// The state of the relay chain block a parachain block is built on, as read from the relay
+	/// chain state proof of the block.
+	#[derive(Clone, Debug, Default, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+	pub struct RelayChainState {
+		/// The number of the relay chain block.
+		pub block_number: u32,
+		/// The BABE randomness of the relay chain block, derived from the VRF output of its
+		/// author, if any. As the author could withhold its block, it is only suitable where
+		/// influencing the outcome is not worth forgoing a block.
+		pub randomness: Option<[u8; 32]>,
+		/// The start of the slot of the relay chain block, in milliseconds since the unix epoch.
+		pub timestamp: u64,
+	}
+
+	/// The index and name of each of the `Pallets` as configured in `construct_runtime`, e.g.
+	/// `AllPalletsWithSystem`, ordered by index.
+	pub fn pallets<Pallets: PalletsInfoAccess>() -> Vec<(u8, RuntimeString)> {
+		let mut pallets: Vec<_> = Pallets::infos()
+			.into_iter()
+			// Pallet indices are encoded as `u8` by `construct_runtime`.
+			.map(|info| (info.index as u8, RuntimeString::Borrowed(info.name)))
+			.collect();
+		pallets.sort_by_key(|(index, _)| *index);
+		pallets
+	}
+
+	/// The space consumed by a block, e.g. to tell how close blocks run to their limits.
+	#[derive(Clone, Debug, Default, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+	pub struct BlockUsage {
+		/// The weight consumed by normal dispatches, e.g. transfers and contract calls.
+		pub normal: Weight,
+		/// The weight consumed by operational dispatches, e.g. governance.
+		pub operational: Weight,
+		/// The weight consumed by mandatory dispatches, i.e. inherents and block initialization.
+		pub mandatory: Weight,
+		/// The proof size consumed by the dispatches of every class in bytes, which approximates
+		/// the share of the PoV taken by the block as the proof size of signed transactions is
+		/// reclaimed down to what they actually recorded.
+		pub pov_size: u64,
+		/// The number of contract calls made by the block, including calls made by contracts.
+		pub contract_calls: u32,
+	}
+
+	/// The space consumed by the block, read from the storage of the `System` pallet, which keeps
+	/// the weight consumed by a block until the next block is initialized, along with the number
+	/// of `contract_calls` made by the block, which is zero on chains without contracts.
+	pub fn block_usage<T: frame_system::Config>(contract_calls: u32) -> BlockUsage {
+		let weight = frame_system::BlockWeight::<T>::get();
+		BlockUsage {
+			normal: *weight.get(DispatchClass::Normal),
+			operational: *weight.get(DispatchClass::Operational),
+			mandatory: *weight.get(DispatchClass::Mandatory),
+			pov_size: weight.total().proof_size(),
+			contract_calls,
+		}
+	}
+
+	/// The number of contract calls made by the block, counted from the `Called` events of
+	/// `pallet_contracts`, which are emitted for the calls made by contracts too. Events are kept
+	/// until the next block is initialized as well.
+	pub fn contract_calls<T>() -> u32
+	where
+		T: pallet_contracts::Config,
+		<T as frame_system::Config>::RuntimeEvent: TryInto<pallet_contracts::Event<T>>,
+	{
+		let calls = frame_system::Pallet::<T>::read_events_no_consensus()
+			.filter(|record| {
+				matches!(
+					record.event.clone().try_into(),
+					Ok(pallet_contracts::Event::Called { .. })
+				)
+			})
+			.count();
+		u32::try_from(calls).unwrap_or(u32::MAX)
+	}
+
+	sp_api::decl_runtime_apis! {
+		/// API for previewing block production.
+		pub trait NextAuthorApi<AccountId: Codec> {
+			/// The account of the collator scheduled to author the block in the slot following
+			/// the current one, if any.
+			fn next_author() -> Option<AccountId>;
+		}
+
+		/// API for previewing runtime upgrades.
+		pub trait UpgradeStatusApi<Hash: Codec> {
+			/// The status of a runtime upgrade which is authorized or scheduled but not applied
+			/// yet.
+			fn upgrade_status() -> UpgradeStatus<Hash>;
+		}
+
+		/// API for previewing collator registration.
+		pub trait CollatorRegistrationApi<AccountId: Codec, Balance: Codec> {
+			/// Whether `account` would be accepted as a collator candidate, or the reason why
+			/// not.
+			fn can_register_collator(
+				account: AccountId,
+			) -> Result<(), CollatorIneligibility<Balance>>;
+		}
+
+		/// API for querying the collators.
+		pub trait CollatorSetApi<AccountId: Codec, Balance: Codec> {
+			/// The collators of the current session, along with the invulnerables, the candidates
+			/// and the configuration of the selection of collators.
+			fn collator_set() -> CollatorSet<AccountId, Balance>;
+		}
+
+		/// API for querying transaction fees.
+		pub trait FeeApi {
+			/// The multiplier of the fees of transactions included in the next block, which rises
+			/// above one while blocks are congested and decays towards its minimum otherwise.
+			fn current_multiplier() -> FixedU128;
+		}
+
+		/// API for paying transaction fees in assets.
+		pub trait AssetFeeApi<AssetId: Codec, Balance: Codec> {
+			/// The assets transaction fees can be paid in, along with the amount of each charged
+			/// per unit of native fees. Empty if fees can only be paid in the native token.
+			fn fee_assets() -> Vec<(AssetId, FixedU128)>;
+
+			/// The fee charged in `asset` for a fee of `fee` in the native token, `None` if fees
+			/// cannot be paid in the asset.
+			fn fee_in_asset(fee: Balance, asset: AssetId) -> Option<Balance>;
+		}
+
+		/// API for inspecting the space consumed by blocks.
+		pub trait BlockUsageApi {
+			/// The weight and proof size consumed by the block, along with the number of contract
+			/// calls it made.
+			fn block_usage() -> BlockUsage;
+		}
+
+		/// API for tracing contract calls.
+		pub trait ContractTracingApi<AccountId: Codec, Balance: Codec> {
+			/// Dry-run a call of the contract at `dest` by `origin` transferring `value`, and
+			/// return the tree of frames it executed along with the gas consumed by each.
+			fn trace_call(
+				origin: AccountId,
+				dest: AccountId,
+				value: Balance,
+				input_data: Vec<u8>,
+			) -> Result<CallFrame<AccountId>, DispatchError>;
+		}
+
+		/// API for debugging contract calls.
+		pub trait ContractDebugApi<AccountId: Codec, Balance: Codec> {
+			/// Dry-run a call of the contract at `dest` by `origin` transferring `value`, and
+			/// return the tree of frames it executed along with the debug messages and events
+			/// emitted and the storage deposit of each frame.
+			fn debug_call(
+				origin: AccountId,
+				dest: AccountId,
+				value: Balance,
+				input_data: Vec<u8>,
+			) -> Result<DebuggedCall<AccountId, Balance>, DispatchError>;
+		}
+
+		/// API for querying proxies.
+		pub trait ProxyApi<AccountId: Codec, ProxyType: Codec> {
+			/// The accounts `account` has delegated to as proxies, along with the type of calls
+			/// each may make on its behalf.
+			fn proxies(account: AccountId) -> Vec<(AccountId, ProxyType)>;
+		}
+
+		/// API for estimating the storage deposits of contracts.
+		pub trait StorageDepositApi<Balance: Codec> {
+			/// The deposit a contract must hold for storing `bytes` bytes across `items` storage
+			/// items.
+			fn deposit_for(bytes: u32, items: u32) -> Balance;
+		}
+
+		/// API for previewing contract instantiations.
+		pub trait ContractInstantiationApi<AccountId: Codec, Balance: Codec, Hash: Codec> {
+			/// Whether `account` can afford to instantiate a contract of the code `code_hash`
+			/// transferring `endowment` to it, or the reason why not.
+			fn can_afford_instantiate(
+				account: AccountId,
+				code_hash: Hash,
+				endowment: Balance,
+			) -> Result<(), InstantiationUnaffordable<Balance>>;
+		}
+
+		/// API for estimating the cost of contract calls.
+		pub trait ContractCostApi<AccountId: Codec, Balance: Codec> {
+			/// Estimate the full cost of a call of the contract at `dest` by `origin` transferring
+			/// `value`: the fee of a transaction making the call with the gas it requires, along
+			/// with the storage deposit it charges.
+			fn estimate_call_cost(
+				origin: AccountId,
+				dest: AccountId,
+				value: Balance,
+				input_data: Vec<u8>,
+			) -> Result<ContractCallCost<Balance>, DispatchError>;
+		}
+
+		/// API for querying the metadata of contracts.
+		pub trait ContractMetadataApi<AccountId: Codec, Hash: Codec> {
+			/// The code hash of the contract at `address` along with the metadata stored for its
+			/// code, or `None` if there is no contract at `address`.
+			fn contract_metadata(address: AccountId) -> Option<ContractMetadata<Hash>>;
+		}
+
+		/// API for converting XCM locations.
+		pub trait XcmConversionApi<AccountId: Codec> {
+			/// The local account of `location`, e.g. the sovereign account of a sibling parachain
+			/// holding the funds it reserves on this chain, if it has one.
+			fn location_to_account(location: VersionedLocation) -> Option<AccountId>;
+		}
+
+		/// API for converting between accounts and the locations they belong to, e.g. to find the
+		/// sovereign accounts of other chains without reimplementing their derivation.
+		pub trait AccountConversionApi<AccountId: Codec> {
+			/// The location of the local `account` as seen from this chain.
+			fn account_location(account: AccountId) -> VersionedLocation;
+
+			/// The sovereign account of the sibling parachain `para_id` on this chain.
+			fn sibling_account(para_id: u32) -> AccountId;
+
+			/// The sovereign account of the relay chain on this chain.
+			fn relay_chain_account() -> AccountId;
+
+			/// The account of `location` derived from the hash of its description, which is
+			/// how chains like Asset Hub derive the accounts of remote locations. `location` is
+			/// as seen from the chain deriving the account, e.g. the account of a contract of
+			/// this chain on a sibling parachain is that of `../Parachain(id)/AccountId32`.
+			/// Returns `None` if the location cannot be described.
+			fn hashed_account(location: VersionedLocation) -> Option<AccountId>;
+		}
+
+		/// API for deriving the addresses of contracts, only provided by runtimes supporting
+		/// contracts.
+		pub trait ContractAddressApi<AccountId: Codec, Hash: Codec> {
+			/// The address of the contract `deployer` instantiates from the code `code_hash`
+			/// with `input_data` and `salt`, e.g. to fund it before its instantiation.
+			fn contract_address(
+				deployer: AccountId,
+				code_hash: Hash,
+				input_data: Vec<u8>,
+				salt: Vec<u8>,
+			) -> AccountId;
+		}
+
+		/// API for querying the supply of the native token.
+		pub trait SupplyApi<Balance: Codec> {
+			/// The total issuance of the native token.
+			fn total_issuance() -> Balance;
+
+			/// The native tokens in circulation, i.e. the total issuance less the balances which
+			/// cannot be transferred. Reads every account, so its cost grows with the number of
+			/// accounts.
+			fn circulating_supply() -> Balance;
+		}
+
+		/// API for inspecting XCM delivery.
+		pub trait XcmQueueApi {
+			/// The XCM messages queued for delivery to the relay chain and to sibling parachains.
+			fn xcm_queue() -> XcmQueue;
+		}
+
+		/// API for inspecting the processing of inbound XCM messages.
+		pub trait InboundXcmQueueApi {
+			/// The XCM messages received from the relay chain and from sibling parachains but not
+			/// processed yet, along with the suspended channels.
+			fn inbound_xcm_queue() -> InboundXcmQueue;
+		}
+
+		/// API for inspecting XCM version negotiation.
+		pub trait XcmVersionApi {
+			/// The XCM version negotiated with `dest`, or `None` if negotiation is incomplete,
+			/// e.g. as `dest` has not responded to the version subscription yet.
+			fn negotiated_version(dest: VersionedLocation) -> Option<u32>;
+		}
+
+		/// API for inspecting the composition of the runtime.
+		pub trait RuntimeInfoApi {
+			/// The index and name of each pallet of the runtime, ordered by index.
+			fn pallets() -> Vec<(u8, RuntimeString)>;
+		}
+
+		/// API for reading the state of the relay chain.
+		pub trait RelayChainStateApi {
+			/// The state of the relay chain block the block is built on, or `None` for the genesis
+			/// block, which is built on none.
+			fn relay_chain_state() -> Option<RelayChainState>;
+		}
+	}
+}
@@ -76,6 +76,8 @@ parameter_types! {
 	pub NewAssetName: BoundedVec<u8, AssetsStringLimit> = (*b"Frac").to_vec().try_into().unwrap();
 }
 
+impl pallet_genesis_collections::Config for Runtime {}
+
 impl pallet_nft_fractionalization::Config for Runtime {
 	type AssetBalance = <Self as pallet_assets::Config<TrustBackedAssets>>::Balance;
 	type AssetId = <Self as pallet_assets::Config<TrustBackedAssets>>::AssetId;
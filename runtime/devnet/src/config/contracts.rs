@@ -5,8 +5,8 @@ use frame_support::{
 use frame_system::{pallet_prelude::BlockNumberFor, EnsureSigned};
 
 use crate::{
-	deposit, extensions, Balance, Balances, BalancesCall, Perbill, Runtime, RuntimeCall,
-	RuntimeEvent, RuntimeHoldReason, Timestamp,
+	deposit, extensions, AccountId, Balance, Balances, BalancesCall, OriginCaller, Perbill,
+	Preimage, Runtime, RuntimeCall, RuntimeEvent, RuntimeHoldReason, Scheduler, Timestamp,
 };
 
 pub enum AllowBalancesCall {}
@@ -59,7 +59,8 @@ impl pallet_contracts::Config for Runtime {
 	type ChainExtension = extensions::PopApiExtension;
 	type CodeHashLockupDepositPercent = CodeHashLockupDepositPercent;
 	type Currency = Balances;
-	type Debug = ();
+	// Records the frames of contract calls traced via `ContractTracingApi`.
+	type Debug = pop_runtime_common::apis::ContractTracer;
 	type DefaultDepositLimit = DefaultDepositLimit;
 	type DepositPerByte = DepositPerByte;
 	type DepositPerItem = DepositPerItem;
@@ -90,3 +91,14 @@ impl pallet_contracts::Config for Runtime {
 	type WeightPrice = pallet_transaction_payment::Pallet<Self>;
 	type Xcm = pallet_xcm::Pallet<Self>;
 }
+
+impl pallet_genesis_codes::Config for Runtime {}
+
+impl pallet_contract_scheduler::Config for Runtime {
+	type PalletsOrigin = OriginCaller;
+	type Preimages = Preimage;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type Scheduler = Scheduler;
+	type WeightInfo = pallet_contract_scheduler::weights::SubstrateWeight<Runtime>;
+}
@@ -1,6 +1,7 @@
 mod assets;
-mod contracts;
+pub(crate) mod contracts;
 mod ismp;
 mod proxy;
+pub(crate) mod relay;
 // Public due to integration tests crate.
 pub mod xcm;
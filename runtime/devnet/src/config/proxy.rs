@@ -86,6 +86,10 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
 					RuntimeCall::Utility { .. } |
 					RuntimeCall::Multisig { .. }
 			),
+			// The runtime has no governance pallets.
+			ProxyType::Governance => {
+				matches!(c, RuntimeCall::Utility { .. } | RuntimeCall::Multisig { .. })
+			},
 		}
 	}
 
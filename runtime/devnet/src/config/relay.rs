@@ -0,0 +1,3 @@
+use crate::Runtime;
+
+impl pallet_relay_state::Config for Runtime {}
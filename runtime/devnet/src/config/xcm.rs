@@ -2,27 +2,33 @@ use core::marker::PhantomData;
 
 use frame_support::{
 	parameter_types,
-	traits::{ConstU32, Contains, ContainsPair, Everything, Get, Nothing},
+	traits::{
+		ConstU32, Contains, ContainsPair, EnsureOrigin, Everything, Get, Nothing, OriginTrait,
+		PalletInfoAccess,
+	},
 	weights::Weight,
 };
 use frame_system::EnsureRoot;
 use pallet_xcm::XcmPassthrough;
+use parachains_common::AssetIdForTrustBackedAssets;
 use polkadot_parachain_primitives::primitives::Sibling;
 use polkadot_runtime_common::impls::ToAuthor;
 use xcm::latest::prelude::*;
 use xcm_builder::{
 	AccountId32Aliases, AllowExplicitUnpaidExecutionFrom, AllowKnownQueryResponses,
-	AllowTopLevelPaidExecutionFrom, EnsureXcmOrigin, FixedWeightBounds,
-	FrameTransactionalProcessor, FungibleAdapter, IsConcrete, NativeAsset, ParentIsPreset,
-	RelayChainAsNative, SiblingParachainAsNative, SiblingParachainConvertsVia,
-	SignedAccountId32AsNative, SignedToAccountId32, SovereignSignedViaLocation, TakeWeightCredit,
-	TrailingSetTopicAsId, UsingComponents, WithComputedOrigin, WithUniqueTopic,
+	AllowTopLevelPaidExecutionFrom, AsPrefixedGeneralIndex, ConvertedConcreteId, EnsureXcmOrigin,
+	FixedWeightBounds, FrameTransactionalProcessor, FungibleAdapter, FungiblesAdapter, IsConcrete,
+	NativeAsset, NoChecking, ParentIsPreset, RelayChainAsNative, SiblingParachainAsNative,
+	SiblingParachainConvertsVia, SignedAccountId32AsNative, SignedToAccountId32,
+	SovereignSignedViaLocation, TakeWeightCredit, TrailingSetTopicAsId, UsingComponents,
+	WithComputedOrigin, WithUniqueTopic,
 };
-use xcm_executor::XcmExecutor;
+use xcm_executor::{traits::JustTry, XcmExecutor};
 
 use crate::{
-	AccountId, AllPalletsWithSystem, Balances, ParachainInfo, ParachainSystem, PolkadotXcm,
-	Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, WeightToFee, XcmpQueue,
+	AccountId, AllPalletsWithSystem, Assets, Balance, Balances, CollatorSelection, ParachainInfo,
+	ParachainSystem, PolkadotXcm, Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, WeightToFee,
+	XcmpQueue,
 };
 
 parameter_types! {
@@ -63,6 +69,44 @@ pub type LocalAssetTransactor = FungibleAdapter<
 	(),
 >;
 
+parameter_types! {
+	/// The location of `pallet_assets`, prefixing the locations of its assets.
+	pub TrustBackedAssetsPalletLocation: Location =
+		PalletInstance(<Assets as PalletInfoAccess>::index() as u8).into();
+	pub CheckingAccount: AccountId = PolkadotXcm::check_account();
+}
+
+/// Means for transacting the assets of `pallet_assets`, located by their id under the location of
+/// the pallet, e.g. `(0, [PalletInstance(52), GeneralIndex(1)])` for the asset `1`, so that other
+/// chains can hold them in reserve of this chain.
+pub type FungiblesTransactor = FungiblesAdapter<
+	// Use this fungibles implementation:
+	Assets,
+	// Use this asset when it is a fungible asset located under the pallet:
+	ConvertedConcreteId<
+		AssetIdForTrustBackedAssets,
+		Balance,
+		AsPrefixedGeneralIndex<
+			TrustBackedAssetsPalletLocation,
+			AssetIdForTrustBackedAssets,
+			JustTry,
+		>,
+		JustTry,
+	>,
+	// Convert an XCM Location into a local account id:
+	LocationToAccountId,
+	// Our chain's account ID type (we can't get away without mentioning it explicitly):
+	AccountId,
+	// We don't track any teleports of `Assets`.
+	NoChecking,
+	// The account used to track teleports, were they tracked.
+	CheckingAccount,
+>;
+
+/// Means for transacting assets on this chain, i.e. the native token and the assets of
+/// `pallet_assets`.
+pub type AssetTransactors = (LocalAssetTransactor, FungiblesTransactor);
+
 /// This is the type we use to convert an (incoming) XCM origin into a local `Origin` instance,
 /// ready for dispatching a transaction with Xcm's `Transact`. There is an `OriginKind` which can
 /// biases the kind of local `Origin` it will become.
@@ -132,7 +176,7 @@ impl xcm_executor::Config for XcmConfig {
 	type AssetExchanger = ();
 	type AssetLocker = ();
 	// How to withdraw and deposit an asset.
-	type AssetTransactor = LocalAssetTransactor;
+	type AssetTransactor = AssetTransactors;
 	type AssetTrap = PolkadotXcm;
 	type Barrier = Barrier;
 	type CallDispatcher = RuntimeCall;
@@ -186,7 +230,7 @@ impl pallet_xcm::Config for Runtime {
 	type RuntimeCall = RuntimeCall;
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeOrigin = RuntimeOrigin;
-	type SendXcmOrigin = EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
+	type SendXcmOrigin = NonContractSendOrigin;
 	type SovereignAccountOf = LocationToAccountId;
 	type TrustedLockers = ();
 	type UniversalLocation = UniversalLocation;
@@ -206,3 +250,102 @@ impl cumulus_pallet_xcm::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type XcmExecutor = XcmExecutor<XcmConfig>;
 }
+
+/// Whether an account is a contract, i.e. whether sends via [`pallet_xcm_gateway`] are on behalf of
+/// one.
+pub struct IsContract;
+
+impl Contains<AccountId> for IsContract {
+	fn contains(account: &AccountId) -> bool {
+		pallet_contracts::Pallet::<Runtime>::code_hash(account).is_some()
+	}
+}
+
+/// The local origins sending messages via `pallet_xcm`, which excludes contracts: they send
+/// messages via [`pallet_xcm_gateway`] only, so that they cannot bypass its allowlist and its fees.
+pub struct NonContractSendOrigin;
+
+impl EnsureOrigin<RuntimeOrigin> for NonContractSendOrigin {
+	type Success = Location;
+
+	fn try_origin(origin: RuntimeOrigin) -> Result<Location, RuntimeOrigin> {
+		if matches!(
+			origin.as_system_ref(),
+			Some(frame_system::RawOrigin::Signed(account)) if IsContract::contains(account)
+		) {
+			return Err(origin);
+		}
+		EnsureXcmOrigin::<RuntimeOrigin, LocalOriginToLocation>::try_origin(origin)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+		EnsureXcmOrigin::<RuntimeOrigin, LocalOriginToLocation>::try_successful_origin()
+	}
+}
+
+/// Instantiates a contract to send messages via [`pallet_xcm_gateway`] in its benchmarks.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct GatewayBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_xcm_gateway::BenchmarkHelper<AccountId> for GatewayBenchmarkHelper {
+	fn contract() -> AccountId {
+		use frame_support::traits::fungible::Mutate;
+		use pallet_contracts::{CollectEvents, DebugInfo};
+
+		// A contract doing nothing:
+		// (module
+		//   (import "env" "memory" (memory 1 1))
+		//   (func (export "deploy"))
+		//   (func (export "call")))
+		const CONTRACT: [u8; 65] = [
+			0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+			0x02, 0x10, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79,
+			0x02, 0x01, 0x01, 0x01, 0x03, 0x03, 0x02, 0x00, 0x00, 0x07, 0x11, 0x02, 0x06, 0x64,
+			0x65, 0x70, 0x6c, 0x6f, 0x79, 0x00, 0x00, 0x04, 0x63, 0x61, 0x6c, 0x6c, 0x00, 0x01,
+			0x0a, 0x07, 0x02, 0x02, 0x00, 0x0b, 0x02, 0x00, 0x0b,
+		];
+		let deployer: AccountId = frame_benchmarking::account("deployer", 0, 0);
+		let _ = Balances::mint_into(&deployer, 1_000 * crate::UNIT);
+		// The contract is endowed with the value it is instantiated with, out of which it pays
+		// the delivery fees.
+		crate::Contracts::bare_instantiate(
+			deployer,
+			100 * crate::UNIT,
+			crate::RuntimeBlockWeights::get().max_block,
+			None,
+			pallet_contracts::Code::Upload(CONTRACT.to_vec()),
+			sp_std::vec![],
+			sp_std::vec![],
+			DebugInfo::Skip,
+			CollectEvents::Skip,
+		)
+		.result
+		.expect("the contract is instantiated")
+		.account_id
+	}
+
+	fn reachable_dest() -> Option<Location> {
+		Some(Parent.into())
+	}
+}
+
+parameter_types! {
+	/// The pot of the collators, receiving the delivery fees paid by contracts, as this runtime has
+	/// no treasury.
+	pub StakingPot: AccountId = CollatorSelection::account_id();
+}
+
+impl pallet_xcm_gateway::Config for Runtime {
+	type AllowlistOrigin = EnsureRoot<AccountId>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = GatewayBenchmarkHelper;
+	type Contracts = IsContract;
+	type FeeAsset = RelayLocation;
+	type FeeDestination = StakingPot;
+	type Fungible = Balances;
+	type RuntimeEvent = RuntimeEvent;
+	type SendOrigin = EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
+	type WeightInfo = pallet_xcm_gateway::weights::SubstrateWeight<Runtime>;
+}
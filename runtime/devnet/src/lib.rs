@@ -181,7 +181,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	impl_name: create_runtime_str!("pop"),
 	authoring_version: 1,
 	#[allow(clippy::zero_prefixed_literal)]
-	spec_version: 00_01_00,
+	spec_version: 00_01_01,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
 	transaction_version: 1,
@@ -254,10 +254,50 @@ impl Contains<RuntimeCall> for FilteredCalls {
 }
 
 /// A type to identify allowed calls to the Runtime from contracts. Used by Pop API
+///
+/// The calls allowed are the ones the Pop API encodes, so they must stay stable once allowed.
 pub struct AllowedApiCalls;
 impl Contains<RuntimeCall> for AllowedApiCalls {
-	fn contains(_c: &RuntimeCall) -> bool {
-		false
+	fn contains(c: &RuntimeCall) -> bool {
+		use pallet_nfts::Call as NftsCall;
+		matches!(
+			c,
+			RuntimeCall::Balances(BalancesCall::transfer_keep_alive { .. }) |
+				RuntimeCall::Nfts(
+					NftsCall::create { .. } |
+						NftsCall::destroy { .. } |
+						NftsCall::mint { .. } |
+						NftsCall::burn { .. } |
+						NftsCall::transfer { .. } |
+						NftsCall::redeposit { .. } |
+						NftsCall::lock_item_transfer { .. } |
+						NftsCall::unlock_item_transfer { .. } |
+						NftsCall::lock_collection { .. } |
+						NftsCall::transfer_ownership { .. } |
+						NftsCall::set_team { .. } |
+						NftsCall::approve_transfer { .. } |
+						NftsCall::cancel_approval { .. } |
+						NftsCall::clear_all_transfer_approvals { .. } |
+						NftsCall::lock_item_properties { .. } |
+						NftsCall::set_attribute { .. } |
+						NftsCall::clear_attribute { .. } |
+						NftsCall::approve_item_attributes { .. } |
+						NftsCall::cancel_item_attributes_approval { .. } |
+						NftsCall::set_metadata { .. } |
+						NftsCall::clear_metadata { .. } |
+						NftsCall::set_collection_metadata { .. } |
+						NftsCall::clear_collection_metadata { .. } |
+						NftsCall::set_accept_ownership { .. } |
+						NftsCall::set_collection_max_supply { .. } |
+						NftsCall::update_mint_settings { .. } |
+						NftsCall::set_price { .. } |
+						NftsCall::buy_item { .. } |
+						NftsCall::pay_tips { .. } |
+						NftsCall::create_swap { .. } |
+						NftsCall::cancel_swap { .. } |
+						NftsCall::claim_swap { .. }
+				) | RuntimeCall::XcmGateway(pallet_xcm_gateway::Call::send { .. })
+		)
 	}
 }
 
@@ -367,7 +407,7 @@ impl cumulus_pallet_parachain_system::Config for Runtime {
 	type CheckAssociatedRelayNumber = RelayNumberMonotonicallyIncreases;
 	type ConsensusHook = ConsensusHook;
 	type DmpQueue = frame_support::traits::EnqueueWithOrigin<MessageQueue, RelayOrigin>;
-	type OnSystemEvent = ();
+	type OnSystemEvent = RelayState;
 	type OutboundXcmpMessageSource = XcmpQueue;
 	type ReservedDmpWeight = ReservedDmpWeight;
 	type ReservedXcmpWeight = ReservedXcmpWeight;
@@ -380,12 +420,15 @@ impl cumulus_pallet_parachain_system::Config for Runtime {
 impl parachain_info::Config for Runtime {}
 
 parameter_types! {
-	pub MessageQueueServiceWeight: Weight = Perbill::from_percent(35) * RuntimeBlockWeights::get().max_block;
+	// Inbound messages are serviced before any transaction, with at most a quarter of the block
+	// to leave room for contract calls, and further with the weight left unused by the block.
+	pub MessageQueueServiceWeight: Weight = Perbill::from_percent(25) * RuntimeBlockWeights::get().max_block;
+	pub MessageQueueIdleServiceWeight: Weight = Perbill::from_percent(35) * RuntimeBlockWeights::get().max_block;
 }
 
 impl pallet_message_queue::Config for Runtime {
 	type HeapSize = sp_core::ConstU32<{ 103 * 1024 }>;
-	type IdleMaxServiceWeight = ();
+	type IdleMaxServiceWeight = MessageQueueIdleServiceWeight;
 	type MaxStale = sp_core::ConstU32<8>;
 	#[cfg(feature = "runtime-benchmarks")]
 	type MessageProcessor = pallet_message_queue::mock_helpers::NoopMessageProcessor<
@@ -454,6 +497,11 @@ impl pallet_aura::Config for Runtime {
 
 parameter_types! {
 	pub const PotId: PalletId = PalletId(*b"PotStake");
+	// Candidates that have not authored a block within this many blocks are removed from the
+	// candidate set at the next session change. Invulnerables are never kicked. Should be a
+	// multiple of the session length or things will get inconsistent. Can be tuned by root via
+	// `system.set_storage` to match the expected collator SLA.
+	pub storage KickThreshold: BlockNumber = Period::get();
 	pub const SessionLength: BlockNumber = 6 * HOURS;
 	// StakingAdmin pluralistic body.
 	pub const StakingAdminBodyId: BodyId = BodyId::Defense;
@@ -467,8 +515,7 @@ pub type CollatorSelectionUpdateOrigin = EitherOfDiverse<
 
 impl pallet_collator_selection::Config for Runtime {
 	type Currency = Balances;
-	// should be a multiple of session or things will get inconsistent
-	type KickThreshold = Period;
+	type KickThreshold = KickThreshold;
 	type MaxCandidates = ConstU32<100>;
 	type MaxInvulnerables = ConstU32<20>;
 	type MinEligibleCollators = ConstU32<4>;
@@ -572,6 +619,8 @@ mod runtime {
 	pub type Timestamp = pallet_timestamp::Pallet<Runtime>;
 	#[runtime::pallet_index(3)]
 	pub type ParachainInfo = parachain_info::Pallet<Runtime>;
+	#[runtime::pallet_index(4)]
+	pub type RelayState = pallet_relay_state::Pallet<Runtime>;
 
 	// Monetary stuff.
 	#[runtime::pallet_index(10)]
@@ -612,6 +661,8 @@ mod runtime {
 	pub type CumulusXcm = cumulus_pallet_xcm::Pallet<Runtime>;
 	#[runtime::pallet_index(33)]
 	pub type MessageQueue = pallet_message_queue::Pallet<Runtime>;
+	#[runtime::pallet_index(35)]
+	pub type XcmGateway = pallet_xcm_gateway::Pallet<Runtime>;
 	#[runtime::pallet_index(37)]
 	pub type IsmpDemo = pallet_ismp_demo::Pallet<Runtime>;
 	#[runtime::pallet_index(38)]
@@ -622,6 +673,10 @@ mod runtime {
 	// Contracts
 	#[runtime::pallet_index(40)]
 	pub type Contracts = pallet_contracts::Pallet<Runtime>;
+	#[runtime::pallet_index(44)]
+	pub type ContractsGenesis = pallet_genesis_codes::Pallet<Runtime>;
+	#[runtime::pallet_index(48)]
+	pub type ContractsScheduler = pallet_contract_scheduler::Pallet<Runtime>;
 
 	// Proxy
 	#[runtime::pallet_index(41)]
@@ -640,6 +695,8 @@ mod runtime {
 	pub type NftFractionalization = pallet_nft_fractionalization::Pallet<Runtime>;
 	#[runtime::pallet_index(52)]
 	pub type Assets = pallet_assets::Pallet<Runtime, Instance1>;
+	#[runtime::pallet_index(53)]
+	pub type NftsGenesis = pallet_genesis_collections::Pallet<Runtime>;
 }
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -654,6 +711,18 @@ mod benches {
 		[pallet_collator_selection, CollatorSelection]
 		[cumulus_pallet_parachain_system, ParachainSystem]
 		[cumulus_pallet_xcmp_queue, XcmpQueue]
+		[pallet_scheduler, Scheduler]
+		[pallet_preimage, Preimage]
+		[pallet_xcm, PalletXcmExtrinsicsBenchmark::<Runtime>]
+		[pallet_xcm_gateway, XcmGateway]
+		[pallet_contracts, Contracts]
+		[pallet_proxy, Proxy]
+		[pallet_multisig, Multisig]
+		[pallet_utility, Utility]
+		[pallet_nfts, Nfts]
+		[pallet_nft_fractionalization, NftFractionalization]
+		[pallet_assets, Assets]
+		[pallet_contract_scheduler, ContractsScheduler]
 	);
 }
 
@@ -872,6 +941,246 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pop_runtime_common::apis::NextAuthorApi<Block, AccountId> for Runtime {
+		fn next_author() -> Option<AccountId> {
+			let authorities = pallet_aura::Authorities::<Runtime>::get();
+			if authorities.is_empty() {
+				return None;
+			}
+			// Aura assigns slots to authorities in a round-robin fashion.
+			let slot = *pallet_aura::CurrentSlot::<Runtime>::get() + 1;
+			let author = &authorities[(slot % authorities.len() as u64) as usize];
+			Session::key_owner(sp_core::crypto::key_types::AURA, author.as_ref())
+		}
+	}
+
+	impl pop_runtime_common::apis::UpgradeStatusApi<Block, Hash> for Runtime {
+		fn upgrade_status() -> pop_runtime_common::apis::UpgradeStatus<Hash> {
+			pop_runtime_common::apis::upgrade_status()
+		}
+	}
+
+	impl pop_runtime_common::apis::FeeApi<Block> for Runtime {
+		fn current_multiplier() -> sp_runtime::FixedU128 {
+			pallet_transaction_payment::NextFeeMultiplier::<Runtime>::get()
+		}
+	}
+
+	impl pop_runtime_common::apis::AssetFeeApi<Block, pop_runtime_common::AssetId, Balance>
+		for Runtime
+	{
+		// Fees can only be paid in the native token.
+		fn fee_assets() -> Vec<(pop_runtime_common::AssetId, sp_runtime::FixedU128)> {
+			Vec::new()
+		}
+
+		fn fee_in_asset(_fee: Balance, _asset: pop_runtime_common::AssetId) -> Option<Balance> {
+			None
+		}
+	}
+
+	impl pop_runtime_common::apis::BlockUsageApi<Block> for Runtime {
+		fn block_usage() -> pop_runtime_common::apis::BlockUsage {
+			pop_runtime_common::apis::block_usage::<Runtime>(pop_runtime_common::apis::contract_calls::<Runtime>())
+		}
+	}
+
+	impl pop_runtime_common::apis::CollatorRegistrationApi<Block, AccountId, Balance> for Runtime {
+		fn can_register_collator(
+			account: AccountId,
+		) -> Result<(), pop_runtime_common::apis::CollatorIneligibility<Balance>> {
+			pop_runtime_common::apis::collator_eligibility::<Runtime>(&account)
+		}
+	}
+
+	impl pop_runtime_common::apis::CollatorSetApi<Block, AccountId, Balance> for Runtime {
+		fn collator_set() -> pop_runtime_common::apis::CollatorSet<AccountId, Balance> {
+			pop_runtime_common::apis::collator_set::<Runtime>(pallet_session::Validators::<Runtime>::get())
+		}
+	}
+
+	impl pop_runtime_common::apis::ProxyApi<Block, AccountId, pop_runtime_common::proxy::ProxyType>
+		for Runtime
+	{
+		fn proxies(account: AccountId) -> Vec<(AccountId, pop_runtime_common::proxy::ProxyType)> {
+			pop_runtime_common::apis::proxies::<Runtime>(&account)
+		}
+	}
+
+	impl pop_runtime_common::apis::XcmConversionApi<Block, AccountId> for Runtime {
+		fn location_to_account(location: xcm::VersionedLocation) -> Option<AccountId> {
+			pop_runtime_common::apis::location_to_account::<config::xcm::LocationToAccountId>(
+				location,
+			)
+		}
+	}
+
+	impl pop_runtime_common::apis::AccountConversionApi<Block, AccountId> for Runtime {
+		fn account_location(account: AccountId) -> xcm::VersionedLocation {
+			pop_runtime_common::apis::account_location(account)
+		}
+
+		fn sibling_account(para_id: u32) -> AccountId {
+			pop_runtime_common::apis::sibling_account(para_id)
+		}
+
+		fn relay_chain_account() -> AccountId {
+			pop_runtime_common::apis::relay_chain_account()
+		}
+
+		fn hashed_account(location: xcm::VersionedLocation) -> Option<AccountId> {
+			pop_runtime_common::apis::hashed_account(location)
+		}
+	}
+
+	impl pop_runtime_common::apis::XcmQueueApi<Block> for Runtime {
+		fn xcm_queue() -> pop_runtime_common::apis::XcmQueue {
+			pop_runtime_common::apis::xcm_queue()
+		}
+	}
+
+	impl pop_runtime_common::apis::InboundXcmQueueApi<Block> for Runtime {
+		fn inbound_xcm_queue() -> pop_runtime_common::apis::InboundXcmQueue {
+			pop_runtime_common::apis::inbound_xcm_queue()
+		}
+	}
+
+	impl pop_runtime_common::apis::RelayChainStateApi<Block> for Runtime {
+		fn relay_chain_state() -> Option<pop_runtime_common::apis::RelayChainState> {
+			RelayState::relay_chain_state()
+		}
+	}
+
+	impl pop_runtime_common::apis::XcmVersionApi<Block> for Runtime {
+		fn negotiated_version(dest: xcm::VersionedLocation) -> Option<u32> {
+			pop_runtime_common::apis::negotiated_xcm_version(dest)
+		}
+	}
+
+	impl pop_runtime_common::apis::SupplyApi<Block, Balance> for Runtime {
+		fn total_issuance() -> Balance {
+			Balances::total_issuance()
+		}
+
+		fn circulating_supply() -> Balance {
+			pop_runtime_common::apis::circulating_supply::<Runtime>(&[])
+		}
+	}
+
+	impl pop_runtime_common::apis::RuntimeInfoApi<Block> for Runtime {
+		fn pallets() -> Vec<(u8, sp_runtime::RuntimeString)> {
+			pop_runtime_common::apis::pallets::<AllPalletsWithSystem>()
+		}
+	}
+
+	impl pop_runtime_common::apis::ContractTracingApi<Block, AccountId, Balance> for Runtime {
+		fn trace_call(
+			origin: AccountId,
+			dest: AccountId,
+			value: Balance,
+			input_data: Vec<u8>,
+		) -> Result<pop_runtime_common::apis::CallFrame<AccountId>, sp_runtime::DispatchError> {
+			pop_runtime_common::apis::trace_contract_call::<Runtime>(
+				origin,
+				dest,
+				value,
+				RuntimeBlockWeights::get().max_block,
+				input_data,
+			)
+		}
+	}
+
+	impl pop_runtime_common::apis::ContractDebugApi<Block, AccountId, Balance> for Runtime {
+		fn debug_call(
+			origin: AccountId,
+			dest: AccountId,
+			value: Balance,
+			input_data: Vec<u8>,
+		) -> Result<
+			pop_runtime_common::apis::DebuggedCall<AccountId, Balance>,
+			sp_runtime::DispatchError,
+		> {
+			pop_runtime_common::apis::debug_contract_call::<Runtime>(
+				origin,
+				dest,
+				value,
+				RuntimeBlockWeights::get().max_block,
+				input_data,
+			)
+		}
+	}
+
+	impl pop_runtime_common::apis::StorageDepositApi<Block, Balance> for Runtime {
+		fn deposit_for(bytes: u32, items: u32) -> Balance {
+			pop_runtime_common::apis::storage_deposit::<Runtime>(bytes, items)
+		}
+	}
+
+	impl pop_runtime_common::apis::ContractCostApi<Block, AccountId, Balance> for Runtime {
+		fn estimate_call_cost(
+			origin: AccountId,
+			dest: AccountId,
+			value: Balance,
+			input_data: Vec<u8>,
+		) -> Result<pop_runtime_common::apis::ContractCallCost<Balance>, sp_runtime::DispatchError> {
+			pop_runtime_common::apis::contract_call_cost::<Runtime>(
+				origin,
+				dest.clone(),
+				value,
+				RuntimeBlockWeights::get().max_block,
+				input_data,
+				|gas_limit, storage_deposit, data| {
+					let call = RuntimeCall::Contracts(pallet_contracts::Call::call {
+						dest: dest.into(),
+						value,
+						gas_limit,
+						storage_deposit_limit: Some(storage_deposit.into()),
+						data,
+					});
+					let len = codec::Encode::encoded_size(&call) as u32;
+					TransactionPayment::query_call_info(call, len).partial_fee
+				},
+			)
+		}
+	}
+
+	impl pop_runtime_common::apis::ContractAddressApi<Block, AccountId, Hash> for Runtime {
+		fn contract_address(
+			deployer: AccountId,
+			code_hash: Hash,
+			input_data: Vec<u8>,
+			salt: Vec<u8>,
+		) -> AccountId {
+			Contracts::contract_address(&deployer, &code_hash, &input_data, &salt)
+		}
+	}
+
+	impl pop_runtime_common::apis::ContractInstantiationApi<Block, AccountId, Balance, Hash>
+		for Runtime
+	{
+		fn can_afford_instantiate(
+			account: AccountId,
+			code_hash: Hash,
+			endowment: Balance,
+		) -> Result<(), pop_runtime_common::apis::InstantiationUnaffordable<Balance>> {
+			// The fee of instantiating without any gas, i.e. excluding the execution of the
+			// constructor.
+			let call = RuntimeCall::Contracts(pallet_contracts::Call::instantiate {
+				value: endowment,
+				gas_limit: Weight::zero(),
+				storage_deposit_limit: None,
+				code_hash,
+				data: Vec::new(),
+				salt: Vec::new(),
+			});
+			let len = codec::Encode::encoded_size(&call) as u32;
+			let fee = TransactionPayment::query_call_info(call, len).partial_fee;
+			pop_runtime_common::apis::instantiation_affordability::<Runtime>(
+				&account, code_hash, endowment, fee,
+			)
+		}
+	}
+
 	impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
 		fn collect_collation_info(header: &<Block as BlockT>::Header) -> cumulus_primitives_core::CollationInfo {
 			ParachainSystem::collect_collation_info(header)
@@ -951,6 +1260,7 @@ impl_runtime_apis! {
 			use frame_support::traits::StorageInfoTrait;
 			use frame_system_benchmarking::Pallet as SystemBench;
 			use cumulus_pallet_session_benchmarking::Pallet as SessionBench;
+			use pallet_xcm::benchmarking::Pallet as PalletXcmExtrinsicsBenchmark;
 
 			let mut list = Vec::<BenchmarkList>::new();
 			list_benchmarks!(list, extra);
@@ -979,6 +1289,37 @@ impl_runtime_apis! {
 			use cumulus_pallet_session_benchmarking::Pallet as SessionBench;
 			impl cumulus_pallet_session_benchmarking::Config for Runtime {}
 
+			use pallet_xcm::benchmarking::Pallet as PalletXcmExtrinsicsBenchmark;
+			use xcm::latest::prelude::{Asset, AssetId, Fungible, Location, Parent};
+			parameter_types! {
+				pub ExistentialDepositAsset: Option<Asset> =
+					Some((Parent, ExistentialDeposit::get()).into());
+			}
+			impl pallet_xcm::benchmarking::Config for Runtime {
+				type DeliveryHelper = cumulus_primitives_utility::ToParentDeliveryHelper<
+					config::xcm::XcmConfig,
+					ExistentialDepositAsset,
+					(),
+				>;
+
+				fn reachable_dest() -> Option<Location> {
+					Some(Parent.into())
+				}
+
+				// Assets are not teleported, the relay chain being the reserve of the native token.
+				fn teleportable_asset_and_dest() -> Option<(Asset, Location)> {
+					None
+				}
+
+				fn reserve_transferable_asset_and_dest() -> Option<(Asset, Location)> {
+					Some((Self::get_asset(), Parent.into()))
+				}
+
+				fn get_asset() -> Asset {
+					Asset { id: AssetId(Parent.into()), fun: Fungible(ExistentialDeposit::get()) }
+				}
+			}
+
 			use frame_support::traits::WhitelistedStorageKeys;
 			let whitelist = AllPalletsWithSystem::whitelisted_storage_keys();
 
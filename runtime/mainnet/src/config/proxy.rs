@@ -33,6 +33,10 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
 					RuntimeCall::Utility { .. } |
 					RuntimeCall::Multisig { .. }
 			),
+			// The runtime has no governance pallets.
+			ProxyType::Governance => {
+				matches!(c, RuntimeCall::Utility { .. } | RuntimeCall::Multisig { .. })
+			},
 		}
 	}
 
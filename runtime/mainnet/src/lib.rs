@@ -237,7 +237,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	impl_name: create_runtime_str!("pop"),
 	authoring_version: 1,
 	#[allow(clippy::zero_prefixed_literal)]
-	spec_version: 00_01_00,
+	spec_version: 00_01_01,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
 	transaction_version: 1,
@@ -493,6 +493,11 @@ impl pallet_aura::Config for Runtime {
 
 parameter_types! {
 	pub const PotId: PalletId = PalletId(*b"PotStake");
+	// Candidates that have not authored a block within this many blocks are removed from the
+	// candidate set at the next session change. Invulnerables are never kicked. Should be a
+	// multiple of the session length or things will get inconsistent. Can be tuned by root via
+	// `system.set_storage` to match the expected collator SLA.
+	pub storage KickThreshold: BlockNumber = Period::get();
 	// StakingAdmin pluralistic body.
 	pub const StakingAdminBodyId: BodyId = BodyId::Defense;
 }
@@ -505,8 +510,7 @@ pub type CollatorSelectionUpdateOrigin = EitherOfDiverse<
 
 impl pallet_collator_selection::Config for Runtime {
 	type Currency = Balances;
-	// should be a multiple of session or things will get inconsistent
-	type KickThreshold = Period;
+	type KickThreshold = KickThreshold;
 	type MaxCandidates = ConstU32<0>;
 	type MaxInvulnerables = ConstU32<20>;
 	type MinEligibleCollators = ConstU32<3>;
@@ -674,6 +678,12 @@ mod benches {
 		[pallet_collator_selection, CollatorSelection]
 		[cumulus_pallet_parachain_system, ParachainSystem]
 		[cumulus_pallet_xcmp_queue, XcmpQueue]
+		[pallet_scheduler, Scheduler]
+		[pallet_preimage, Preimage]
+		[pallet_xcm, PalletXcmExtrinsicsBenchmark::<Runtime>]
+		[pallet_proxy, Proxy]
+		[pallet_multisig, Multisig]
+		[pallet_utility, Utility]
 	);
 }
 
@@ -825,6 +835,133 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pop_runtime_common::apis::NextAuthorApi<Block, AccountId> for Runtime {
+		fn next_author() -> Option<AccountId> {
+			let authorities = pallet_aura::Authorities::<Runtime>::get();
+			if authorities.is_empty() {
+				return None;
+			}
+			// Aura assigns slots to authorities in a round-robin fashion.
+			let slot = *pallet_aura::CurrentSlot::<Runtime>::get() + 1;
+			let author = &authorities[(slot % authorities.len() as u64) as usize];
+			Session::key_owner(sp_core::crypto::key_types::AURA, author.as_ref())
+		}
+	}
+
+	impl pop_runtime_common::apis::UpgradeStatusApi<Block, Hash> for Runtime {
+		fn upgrade_status() -> pop_runtime_common::apis::UpgradeStatus<Hash> {
+			pop_runtime_common::apis::upgrade_status()
+		}
+	}
+
+	impl pop_runtime_common::apis::FeeApi<Block> for Runtime {
+		fn current_multiplier() -> sp_runtime::FixedU128 {
+			pallet_transaction_payment::NextFeeMultiplier::<Runtime>::get()
+		}
+	}
+
+	impl pop_runtime_common::apis::AssetFeeApi<Block, pop_runtime_common::AssetId, Balance>
+		for Runtime
+	{
+		// Fees can only be paid in the native token.
+		fn fee_assets() -> Vec<(pop_runtime_common::AssetId, sp_runtime::FixedU128)> {
+			Vec::new()
+		}
+
+		fn fee_in_asset(_fee: Balance, _asset: pop_runtime_common::AssetId) -> Option<Balance> {
+			None
+		}
+	}
+
+	impl pop_runtime_common::apis::BlockUsageApi<Block> for Runtime {
+		fn block_usage() -> pop_runtime_common::apis::BlockUsage {
+			// Contracts are not supported, so blocks make no contract calls.
+			pop_runtime_common::apis::block_usage::<Runtime>(0)
+		}
+	}
+
+	impl pop_runtime_common::apis::CollatorRegistrationApi<Block, AccountId, Balance> for Runtime {
+		fn can_register_collator(
+			account: AccountId,
+		) -> Result<(), pop_runtime_common::apis::CollatorIneligibility<Balance>> {
+			pop_runtime_common::apis::collator_eligibility::<Runtime>(&account)
+		}
+	}
+
+	impl pop_runtime_common::apis::CollatorSetApi<Block, AccountId, Balance> for Runtime {
+		fn collator_set() -> pop_runtime_common::apis::CollatorSet<AccountId, Balance> {
+			pop_runtime_common::apis::collator_set::<Runtime>(pallet_session::Validators::<Runtime>::get())
+		}
+	}
+
+	impl pop_runtime_common::apis::ProxyApi<Block, AccountId, pop_runtime_common::proxy::ProxyType>
+		for Runtime
+	{
+		fn proxies(account: AccountId) -> Vec<(AccountId, pop_runtime_common::proxy::ProxyType)> {
+			pop_runtime_common::apis::proxies::<Runtime>(&account)
+		}
+	}
+
+	impl pop_runtime_common::apis::XcmConversionApi<Block, AccountId> for Runtime {
+		fn location_to_account(location: xcm::VersionedLocation) -> Option<AccountId> {
+			pop_runtime_common::apis::location_to_account::<config::xcm::LocationToAccountId>(
+				location,
+			)
+		}
+	}
+
+	impl pop_runtime_common::apis::AccountConversionApi<Block, AccountId> for Runtime {
+		fn account_location(account: AccountId) -> xcm::VersionedLocation {
+			pop_runtime_common::apis::account_location(account)
+		}
+
+		fn sibling_account(para_id: u32) -> AccountId {
+			pop_runtime_common::apis::sibling_account(para_id)
+		}
+
+		fn relay_chain_account() -> AccountId {
+			pop_runtime_common::apis::relay_chain_account()
+		}
+
+		fn hashed_account(location: xcm::VersionedLocation) -> Option<AccountId> {
+			pop_runtime_common::apis::hashed_account(location)
+		}
+	}
+
+	impl pop_runtime_common::apis::XcmQueueApi<Block> for Runtime {
+		fn xcm_queue() -> pop_runtime_common::apis::XcmQueue {
+			pop_runtime_common::apis::xcm_queue()
+		}
+	}
+
+	impl pop_runtime_common::apis::InboundXcmQueueApi<Block> for Runtime {
+		fn inbound_xcm_queue() -> pop_runtime_common::apis::InboundXcmQueue {
+			pop_runtime_common::apis::inbound_xcm_queue()
+		}
+	}
+
+	impl pop_runtime_common::apis::XcmVersionApi<Block> for Runtime {
+		fn negotiated_version(dest: xcm::VersionedLocation) -> Option<u32> {
+			pop_runtime_common::apis::negotiated_xcm_version(dest)
+		}
+	}
+
+	impl pop_runtime_common::apis::SupplyApi<Block, Balance> for Runtime {
+		fn total_issuance() -> Balance {
+			Balances::total_issuance()
+		}
+
+		fn circulating_supply() -> Balance {
+			pop_runtime_common::apis::circulating_supply::<Runtime>(&[])
+		}
+	}
+
+	impl pop_runtime_common::apis::RuntimeInfoApi<Block> for Runtime {
+		fn pallets() -> Vec<(u8, sp_runtime::RuntimeString)> {
+			pop_runtime_common::apis::pallets::<AllPalletsWithSystem>()
+		}
+	}
+
 	impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
 		fn collect_collation_info(header: &<Block as BlockT>::Header) -> cumulus_primitives_core::CollationInfo {
 			ParachainSystem::collect_collation_info(header)
@@ -860,6 +997,7 @@ impl_runtime_apis! {
 			use frame_support::traits::StorageInfoTrait;
 			use frame_system_benchmarking::Pallet as SystemBench;
 			use cumulus_pallet_session_benchmarking::Pallet as SessionBench;
+			use pallet_xcm::benchmarking::Pallet as PalletXcmExtrinsicsBenchmark;
 
 			let mut list = Vec::<BenchmarkList>::new();
 			list_benchmarks!(list, extra);
@@ -888,6 +1026,37 @@ impl_runtime_apis! {
 			use cumulus_pallet_session_benchmarking::Pallet as SessionBench;
 			impl cumulus_pallet_session_benchmarking::Config for Runtime {}
 
+			use pallet_xcm::benchmarking::Pallet as PalletXcmExtrinsicsBenchmark;
+			use xcm::latest::prelude::{Asset, AssetId, Fungible, Location, Parent};
+			parameter_types! {
+				pub ExistentialDepositAsset: Option<Asset> =
+					Some((Parent, ExistentialDeposit::get()).into());
+			}
+			impl pallet_xcm::benchmarking::Config for Runtime {
+				type DeliveryHelper = cumulus_primitives_utility::ToParentDeliveryHelper<
+					config::xcm::XcmConfig,
+					ExistentialDepositAsset,
+					(),
+				>;
+
+				fn reachable_dest() -> Option<Location> {
+					Some(Parent.into())
+				}
+
+				// Assets are not teleported, the relay chain being the reserve of the native token.
+				fn teleportable_asset_and_dest() -> Option<(Asset, Location)> {
+					None
+				}
+
+				fn reserve_transferable_asset_and_dest() -> Option<(Asset, Location)> {
+					Some((Self::get_asset(), Parent.into()))
+				}
+
+				fn get_asset() -> Asset {
+					Asset { id: AssetId(Parent.into()), fun: Fungible(ExistentialDeposit::get()) }
+				}
+			}
+
 			use frame_support::traits::WhitelistedStorageKeys;
 			let whitelist = AllPalletsWithSystem::whitelisted_storage_keys();
 
@@ -4,13 +4,14 @@ use frame_support::{
 	BoundedVec, PalletId,
 };
 use frame_system::{EnsureRoot, EnsureSigned};
+use pallet_assets::FrozenBalance;
 use pallet_nfts::PalletFeatures;
 use parachains_common::{AssetIdForTrustBackedAssets, CollectionId, ItemId, Signature};
 use sp_runtime::traits::Verify;
 
 use crate::{
-	deposit, AccountId, Assets, Balance, Balances, BlockNumber, Nfts, Runtime, RuntimeEvent,
-	RuntimeHoldReason, DAYS, EXISTENTIAL_DEPOSIT, UNIT,
+	deposit, AccountFreeze, AccountId, Assets, Balance, Balances, BlockNumber, Nfts, Runtime,
+	RuntimeEvent, RuntimeHoldReason, DAYS, EXISTENTIAL_DEPOSIT, UNIT,
 };
 
 /// We allow root to execute privileged asset operations.
@@ -96,6 +97,17 @@ impl pallet_nft_fractionalization::Config for Runtime {
 	type WeightInfo = pallet_nft_fractionalization::weights::SubstrateWeight<Self>;
 }
 
+/// Freezes the whole balance of the assets held by the accounts frozen by
+/// [`pallet_account_freeze`].
+pub struct FrozenAccounts;
+impl FrozenBalance<AssetIdForTrustBackedAssets, AccountId, Balance> for FrozenAccounts {
+	fn frozen_balance(asset: AssetIdForTrustBackedAssets, who: &AccountId) -> Option<Balance> {
+		AccountFreeze::is_frozen(who).then(|| Assets::balance(asset, who))
+	}
+
+	fn died(_: AssetIdForTrustBackedAssets, _: &AccountId) {}
+}
+
 pub type TrustBackedAssets = pallet_assets::Instance1;
 pub type TrustBackedAssetsCall = pallet_assets::Call<Runtime, TrustBackedAssets>;
 impl pallet_assets::Config<TrustBackedAssets> for Runtime {
@@ -112,7 +124,7 @@ impl pallet_assets::Config<TrustBackedAssets> for Runtime {
 	type Currency = Balances;
 	type Extra = ();
 	type ForceOrigin = AssetsForceOrigin;
-	type Freezer = ();
+	type Freezer = FrozenAccounts;
 	type MetadataDepositBase = MetadataDepositBase;
 	type MetadataDepositPerByte = MetadataDepositPerByte;
 	type RemoveItemsLimit = ConstU32<1000>;
@@ -0,0 +1,59 @@
+use frame_support::{parameter_types, traits::Get};
+use frame_system::EnsureRoot;
+use pallet_session::SessionManager;
+use sp_std::{marker::PhantomData, vec::Vec};
+
+use crate::{AccountId, CollatorSelectionUpdateOrigin, Runtime, RuntimeCall, RuntimeEvent};
+
+parameter_types! {
+	/// All invulnerables and candidates may be collators at the same time.
+	pub MaxCollators: u32 =
+		<<Runtime as pallet_collator_selection::Config>::MaxInvulnerables as Get<u32>>::get() +
+		<<Runtime as pallet_collator_selection::Config>::MaxCandidates as Get<u32>>::get();
+}
+
+impl pallet_collator_set::Config for Runtime {
+	type MaxCollators = MaxCollators;
+	type RuntimeEvent = RuntimeEvent;
+}
+
+impl pallet_forced_candidates::Config for Runtime {
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_forced_candidates::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_candidacy_bond::Config for Runtime {
+	type BondChangeOrigin = CollatorSelectionUpdateOrigin;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_candidacy_bond::weights::SubstrateWeight<Runtime>;
+}
+
+/// Selects the collators of each session via `Inner`, reporting the changes of the collator set
+/// as each session starts via [`pallet_collator_set`].
+pub struct ReportCollatorSetChanges<Inner>(PhantomData<Inner>);
+impl<Inner: SessionManager<AccountId>> SessionManager<AccountId>
+	for ReportCollatorSetChanges<Inner>
+{
+	fn new_session(new_index: u32) -> Option<Vec<AccountId>> {
+		Inner::new_session(new_index)
+	}
+
+	fn new_session_genesis(new_index: u32) -> Option<Vec<AccountId>> {
+		Inner::new_session_genesis(new_index)
+	}
+
+	fn end_session(end_index: u32) {
+		Inner::end_session(end_index)
+	}
+
+	fn start_session(start_index: u32) {
+		Inner::start_session(start_index);
+		// The collators of the session are set before the session is started.
+		pallet_collator_set::Pallet::<Runtime>::note_collators(
+			start_index,
+			pallet_session::Validators::<Runtime>::get(),
+		);
+	}
+}
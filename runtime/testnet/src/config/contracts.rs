@@ -1,12 +1,27 @@
+use core::fmt;
+
+use codec::{Decode, Encode};
 use frame_support::{
 	parameter_types,
 	traits::{ConstBool, ConstU32, Randomness},
 };
 use frame_system::{pallet_prelude::BlockNumberFor, EnsureSigned};
+use pallet_asset_tx_payment::OnChargeAssetTransaction;
+use pallet_transaction_payment::OnChargeTransaction;
+use pallet_weight_overrides::InnerCalls;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{DispatchInfoOf, PostDispatchInfoOf, SignedExtension, SignedExtensionMetadata},
+	transaction_validity::{TransactionValidity, TransactionValidityError},
+	DispatchResult,
+};
+use sp_std::{marker::PhantomData, vec::Vec};
 
+use super::governance::GovernanceOrigin;
 use crate::{
-	deposit, extensions, Balance, Balances, BalancesCall, Perbill, Runtime, RuntimeCall,
-	RuntimeEvent, RuntimeHoldReason, Timestamp,
+	deposit, extensions, AccountId, Balance, Balances, BalancesCall, DispatchedCalls, OriginCaller,
+	Perbill, Preimage, Runtime, RuntimeCall, RuntimeEvent, RuntimeHoldReason, Scheduler, Timestamp,
+	MILLIUNIT,
 };
 
 pub enum AllowBalancesCall {}
@@ -43,6 +58,8 @@ parameter_types! {
 	pub Schedule: pallet_contracts::Schedule<Runtime> = schedule::<Runtime>();
 	pub const DefaultDepositLimit: Balance = deposit(1024, 1024 * 1024);
 	pub const CodeHashLockupDepositPercent: Perbill = Perbill::from_percent(0);
+	/// The least fee charged for a contract call, however low the fee multiplier.
+	pub const MinContractCallFee: Balance = 10 * MILLIUNIT;
 }
 
 impl pallet_contracts::Config for Runtime {
@@ -59,7 +76,8 @@ impl pallet_contracts::Config for Runtime {
 	type ChainExtension = extensions::PopApiExtension;
 	type CodeHashLockupDepositPercent = CodeHashLockupDepositPercent;
 	type Currency = Balances;
-	type Debug = ();
+	// Records the frames of contract calls traced via `ContractTracingApi`.
+	type Debug = pop_runtime_common::apis::ContractTracer;
 	type DefaultDepositLimit = DefaultDepositLimit;
 	type DepositPerByte = DepositPerByte;
 	type DepositPerItem = DepositPerItem;
@@ -85,8 +103,245 @@ impl pallet_contracts::Config for Runtime {
 	type Schedule = Schedule;
 	type Time = Timestamp;
 	type UnsafeUnstableInterface = ConstBool<true>;
-	type UploadOrigin = EnsureSigned<Self::AccountId>;
+	// Anyone may upload codes unless governance permissions uploads, see `pallet_upload_policy`.
+	type UploadOrigin = pallet_upload_policy::EnsureUploader<Self>;
 	type WeightInfo = pallet_contracts::weights::SubstrateWeight<Self>;
 	type WeightPrice = pallet_transaction_payment::Pallet<Self>;
 	type Xcm = pallet_xcm::Pallet<Self>;
 }
+
+impl pallet_genesis_codes::Config for Runtime {}
+
+impl pallet_contract_scheduler::Config for Runtime {
+	type PalletsOrigin = OriginCaller;
+	type Preimages = Preimage;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type Scheduler = Scheduler;
+	type WeightInfo = pallet_contract_scheduler::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_upload_policy::Config for Runtime {
+	type PolicyOrigin = GovernanceOrigin;
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_upload_policy::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_chunked_uploads::Config for Runtime {
+	type DepositPerByte = DepositPerByte;
+	type Fungible = Balances;
+	// Chunks are kept well below the maximum code length, so that the extrinsics uploading them
+	// stay small regardless of the size of the code.
+	type MaxChunkLen = ConstU32<{ 64 * 1024 }>;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type WeightInfo = pallet_chunked_uploads::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_contract_metadata::Config for Runtime {
+	type DepositPerByte = DepositPerByte;
+	type Fungible = Balances;
+	// Comfortably holds the ABI of typical ink! contracts.
+	type MaxMetadataLen = ConstU32<{ 128 * 1024 }>;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type WeightInfo = pallet_contract_metadata::weights::SubstrateWeight<Runtime>;
+}
+
+/// Charges the fee of contract extrinsics via `OCT`, but at least [`MinContractCallFee`], so that
+/// contract calls stay costly to spam while the fee multiplier is at its minimum. The fees of
+/// other extrinsics are charged as is.
+///
+/// Fees are floored whether they are paid in the native token or, as `OCT` is used as
+/// `OnChargeAssetTransaction` too, in assets, and for the extrinsics calling contracts via other
+/// calls as well (see [`calls_contracts`]). Fees paid in assets stay floored once corrected only
+/// if corrected within [`FloorContractCallFee`].
+pub struct WithMinContractCallFee<OCT>(PhantomData<OCT>);
+
+impl<OCT> WithMinContractCallFee<OCT> {
+	/// `fee`, raised to [`MinContractCallFee`] if `floored`.
+	fn floor(fee: Balance, floored: bool) -> Balance {
+		if floored {
+			fee.max(MinContractCallFee::get())
+		} else {
+			fee
+		}
+	}
+}
+
+/// Whether `call` calls contracts, either directly or via the calls dispatching other calls, e.g.
+/// `utility.batch`, `proxy.proxy` or `multisig.as_multi` (see [`DispatchedCalls`]).
+pub fn calls_contracts(call: &RuntimeCall) -> bool {
+	matches!(call, RuntimeCall::Contracts(_)) ||
+		DispatchedCalls::inner_calls(call).into_iter().any(calls_contracts)
+}
+
+impl<OCT: OnChargeTransaction<Runtime, Balance = Balance>> OnChargeTransaction<Runtime>
+	for WithMinContractCallFee<OCT>
+{
+	type Balance = Balance;
+	/// Whether the fee is floored, along with the liquidity info of `OCT`.
+	type LiquidityInfo = (bool, OCT::LiquidityInfo);
+
+	fn withdraw_fee(
+		who: &AccountId,
+		call: &RuntimeCall,
+		dispatch_info: &DispatchInfoOf<RuntimeCall>,
+		fee: Balance,
+		tip: Balance,
+	) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+		let floored = calls_contracts(call);
+		OCT::withdraw_fee(who, call, dispatch_info, Self::floor(fee, floored), tip)
+			.map(|liquidity_info| (floored, liquidity_info))
+	}
+
+	fn correct_and_deposit_fee(
+		who: &AccountId,
+		dispatch_info: &DispatchInfoOf<RuntimeCall>,
+		post_info: &PostDispatchInfoOf<RuntimeCall>,
+		corrected_fee: Balance,
+		tip: Balance,
+		(floored, already_withdrawn): Self::LiquidityInfo,
+	) -> Result<(), TransactionValidityError> {
+		OCT::correct_and_deposit_fee(
+			who,
+			dispatch_info,
+			post_info,
+			Self::floor(corrected_fee, floored),
+			tip,
+			already_withdrawn,
+		)
+	}
+}
+
+// Whether the fee of the transaction being applied is floored, set by [`FloorContractCallFee`]
+// while the fee paid in assets is corrected. `pallet_asset_tx_payment` requires the liquidity info
+// to be the credit withdrawn, which therefore cannot carry it.
+environmental::environmental!(asset_fee_floored: bool);
+
+/// Wraps the signed extension `S` charging fees, carrying whether the fee of a transaction is
+/// floored by [`WithMinContractCallFee`] from its withdrawal to its correction, as the call is not
+/// given to correct fees paid in assets. It encodes and identifies as `S`, so the format of
+/// transactions is unchanged.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+pub struct FloorContractCallFee<S>(pub S);
+
+impl<S> From<S> for FloorContractCallFee<S> {
+	fn from(extension: S) -> Self {
+		Self(extension)
+	}
+}
+
+impl<S: fmt::Debug> fmt::Debug for FloorContractCallFee<S> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+impl<S: SignedExtension<Call = RuntimeCall>> SignedExtension for FloorContractCallFee<S> {
+	type AccountId = S::AccountId;
+	type AdditionalSigned = S::AdditionalSigned;
+	type Call = RuntimeCall;
+	/// Whether the fee is floored, which is required again to correct the fee after dispatch.
+	type Pre = (bool, S::Pre);
+
+	const IDENTIFIER: &'static str = S::IDENTIFIER;
+
+	fn additional_signed(&self) -> Result<Self::AdditionalSigned, TransactionValidityError> {
+		self.0.additional_signed()
+	}
+
+	fn metadata() -> Vec<SignedExtensionMetadata> {
+		S::metadata()
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> TransactionValidity {
+		self.0.validate(who, call, info, len)
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		Ok((calls_contracts(call), self.0.pre_dispatch(who, call, info, len)?))
+	}
+
+	fn validate_unsigned(
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> TransactionValidity {
+		S::validate_unsigned(call, info, len)
+	}
+
+	fn pre_dispatch_unsigned(
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> Result<(), TransactionValidityError> {
+		S::pre_dispatch_unsigned(call, info, len)
+	}
+
+	fn post_dispatch(
+		pre: Option<Self::Pre>,
+		info: &DispatchInfoOf<Self::Call>,
+		post_info: &PostDispatchInfoOf<Self::Call>,
+		len: usize,
+		result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		let Some((mut floored, pre)) = pre else {
+			return S::post_dispatch(None, info, post_info, len, result);
+		};
+		asset_fee_floored::using(&mut floored, || {
+			S::post_dispatch(Some(pre), info, post_info, len, result)
+		})
+	}
+}
+
+/// The native fees are floored before `OCT` converts them to fees in the asset.
+impl<OCT: OnChargeAssetTransaction<Runtime, Balance = Balance>> OnChargeAssetTransaction<Runtime>
+	for WithMinContractCallFee<OCT>
+{
+	type AssetId = OCT::AssetId;
+	type Balance = Balance;
+	type LiquidityInfo = OCT::LiquidityInfo;
+
+	fn withdraw_fee(
+		who: &AccountId,
+		call: &RuntimeCall,
+		dispatch_info: &DispatchInfoOf<RuntimeCall>,
+		asset_id: Self::AssetId,
+		fee: Balance,
+		tip: Balance,
+	) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+		let fee = Self::floor(fee, calls_contracts(call));
+		OCT::withdraw_fee(who, call, dispatch_info, asset_id, fee, tip)
+	}
+
+	fn correct_and_deposit_fee(
+		who: &AccountId,
+		dispatch_info: &DispatchInfoOf<RuntimeCall>,
+		post_info: &PostDispatchInfoOf<RuntimeCall>,
+		corrected_fee: Balance,
+		tip: Balance,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Result<(Balance, Balance), TransactionValidityError> {
+		// Fees corrected outside of `FloorContractCallFee` are not floored.
+		let floored = asset_fee_floored::with(|floored| *floored).unwrap_or_default();
+		OCT::correct_and_deposit_fee(
+			who,
+			dispatch_info,
+			post_info,
+			Self::floor(corrected_fee, floored),
+			tip,
+			already_withdrawn,
+		)
+	}
+}
@@ -0,0 +1,86 @@
+use frame_support::traits::{
+	fungible::{Balanced, Credit},
+	tokens::ConversionToAssetBalance,
+	Get, Imbalance, OnUnbalanced,
+};
+use parachains_common::AssetIdForTrustBackedAssets;
+use sp_runtime::FixedPointNumber;
+
+use crate::{
+	config::{governance::GovernanceOrigin, treasury::TreasuryAccount},
+	AccountId, Authorship, Balance, Balances, CollatorSelection, Runtime, RuntimeEvent,
+};
+
+/// The pot of the collator selection, out of which the collators are paid.
+pub struct CollatorPot;
+impl Get<AccountId> for CollatorPot {
+	fn get() -> AccountId {
+		CollatorSelection::account_id()
+	}
+}
+
+impl pallet_collator_rewards::Config for Runtime {
+	type Balance = Balance;
+	type Currency = Balances;
+	type Pot = CollatorPot;
+	type RewardOrigin = GovernanceOrigin;
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_collator_rewards::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_fee_split::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type SplitOrigin = GovernanceOrigin;
+	type WeightInfo = pallet_fee_split::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_fee_assets::Config for Runtime {
+	type AssetId = AssetIdForTrustBackedAssets;
+	type RateOrigin = GovernanceOrigin;
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_fee_assets::weights::SubstrateWeight<Runtime>;
+}
+
+/// Converts native transaction fees to fees paid in an asset by the [`pallet_fee_assets::Rates`] of
+/// the chain, refusing assets without a rate.
+pub struct FeeAssetRate;
+impl ConversionToAssetBalance<Balance, AssetIdForTrustBackedAssets, Balance> for FeeAssetRate {
+	type Error = ();
+
+	fn to_asset_balance(
+		balance: Balance,
+		asset: AssetIdForTrustBackedAssets,
+	) -> Result<Balance, Self::Error> {
+		let rate = pallet_fee_assets::Rates::<Runtime>::get(asset).ok_or(())?;
+		Ok(rate.saturating_mul_int(balance))
+	}
+}
+
+/// Splits the fees of transactions, including tips and the fees of contract calls, as set by the
+/// [`pallet_fee_split::Split`] of the chain: a share is burnt, a share funds the treasury and the
+/// rest rewards the collators, as selected by the [`pallet_collator_rewards::RewardMode`] of the
+/// chain.
+///
+/// Rewards are paid to the author of the current block or accumulate in the pot of the collator
+/// selection, out of which the collators are paid at the end of each session. Rewards are
+/// accumulated regardless of the mode when the author is unknown.
+pub struct DealWithFees;
+impl OnUnbalanced<Credit<AccountId, Balances>> for DealWithFees {
+	fn on_nonzero_unbalanced(fees: Credit<AccountId, Balances>) {
+		use pallet_collator_rewards::RewardMode::*;
+		let split = pallet_fee_split::Split::<Runtime>::get();
+		let amount = fees.peek();
+		let (burnt, rest) = fees.split(split.burn * amount);
+		let (treasury, rewards) = rest.split(split.treasury * amount);
+		// Dropping the credit reduces the total issuance.
+		drop(burnt);
+		let _ = Balances::resolve(&TreasuryAccount::get(), treasury);
+		let recipient = match pallet_collator_rewards::Mode::<Runtime>::get() {
+			ImmediatePayout => Authorship::author(),
+			PotAccumulation => None,
+		}
+		.unwrap_or_else(CollatorSelection::account_id);
+		// Rewards too small to create the account of the recipient are burnt.
+		let _ = Balances::resolve(&recipient, rewards);
+	}
+}
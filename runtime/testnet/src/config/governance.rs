@@ -0,0 +1,231 @@
+use frame_support::{
+	parameter_types,
+	traits::{ConstU32, Contains, EitherOfDiverse, GetCallMetadata, PalletInfoAccess},
+	weights::Weight,
+};
+use frame_system::{EnsureRoot, EnsureWithSuccess};
+use pallet_tx_pause::RuntimeCallNameOf;
+use sp_runtime::FixedU64;
+
+use crate::{
+	AccountId, Balance, Balances, BlockNumber, Council, EmergencyUpgrade, MaintenanceMode,
+	OriginCaller, ParachainSystem, Perbill, Preimage, Runtime, RuntimeBlockWeights, RuntimeCall,
+	RuntimeEvent, RuntimeFreezeReason, RuntimeHoldReason, RuntimeOrigin, SafeMode, Scheduler, Sudo,
+	System, TechnicalCommittee, Timestamp, TxPause, DAYS,
+};
+
+/// The instance of `pallet_collective` used as the technical committee.
+pub type TechnicalCommitteeInstance = pallet_collective::Instance1;
+
+/// The origin of emergency measures, i.e. runtime upgrades, safe mode and pausing calls: root or
+/// at least two thirds of the technical committee.
+pub type EmergencyUpgradeOrigin = EitherOfDiverse<
+	EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCommitteeInstance, 2, 3>,
+>;
+
+/// The instance of `pallet_collective` used as the council.
+pub type CouncilInstance = pallet_collective::Instance2;
+
+/// The origin of the privileged calls delegated to governance: root or a majority of the council.
+pub type GovernanceOrigin = EitherOfDiverse<
+	EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionMoreThan<AccountId, CouncilInstance, 1, 2>,
+>;
+
+parameter_types! {
+	pub const CouncilMotionDuration: BlockNumber = 5 * DAYS;
+	pub const CouncilMaxProposals: u32 = 100;
+	pub const CouncilMaxMembers: u32 = 100;
+	pub const TechnicalMotionDuration: BlockNumber = 3 * DAYS;
+	pub const TechnicalMaxProposals: u32 = 100;
+	pub const TechnicalMaxMembers: u32 = 100;
+	pub MaxProposalWeight: Weight = Perbill::from_percent(50) * RuntimeBlockWeights::get().max_block;
+	pub MaxWeightMultiplier: FixedU64 = FixedU64::from_u32(10);
+}
+
+impl pallet_collective::Config<CouncilInstance> for Runtime {
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type MaxMembers = CouncilMaxMembers;
+	type MaxProposalWeight = MaxProposalWeight;
+	type MaxProposals = CouncilMaxProposals;
+	type MotionDuration = CouncilMotionDuration;
+	type Proposal = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeOrigin = RuntimeOrigin;
+	// The council elects its members itself, as the live testnet has no sudo.
+	type SetMembersOrigin = GovernanceOrigin;
+	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_collective::Config<TechnicalCommitteeInstance> for Runtime {
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type MaxMembers = TechnicalMaxMembers;
+	type MaxProposalWeight = MaxProposalWeight;
+	type MaxProposals = TechnicalMaxProposals;
+	type MotionDuration = TechnicalMotionDuration;
+	type Proposal = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeOrigin = RuntimeOrigin;
+	type SetMembersOrigin = GovernanceOrigin;
+	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
+}
+
+/// The origin allowed to authorize runtime upgrades: governance or, in an emergency, the technical
+/// committee.
+pub type AuthorizeUpgradeOrigin = EitherOfDiverse<GovernanceOrigin, EmergencyUpgradeOrigin>;
+
+impl pallet_emergency_upgrade::Config for Runtime {
+	type AuthorizeOrigin = AuthorizeUpgradeOrigin;
+	type PalletsOrigin = OriginCaller;
+	type Preimages = Preimage;
+	type RuntimeCall = RuntimeCall;
+	type Scheduler = Scheduler;
+	type WeightInfo = pallet_emergency_upgrade::weights::SubstrateWeight<Runtime>;
+}
+
+/// Whether `call` of `pallet` can be dispatched while the chain is paused: the inherents required
+/// to produce blocks, the calls of sudo, which allow root to unpause the chain, and those of the
+/// maintenance mode, which allow the pause authority to unpause it.
+///
+/// Along with [`is_critical`], this is the single source of the calls exempted from the emergency
+/// brakes of the chain: the maintenance mode, safe mode and the transaction pause.
+fn is_allowed_while_paused(pallet: &str, call: &str) -> bool {
+	(pallet == ParachainSystem::name() && call == "set_validation_data") ||
+		(pallet == Timestamp::name() && call == "set") ||
+		[Sudo::name(), MaintenanceMode::name()].contains(&pallet)
+}
+
+/// Whether `call` of `pallet` can be dispatched in safe mode and cannot be paused: those allowed
+/// while the chain is paused, along with the runtime upgrades of the system, which require root or
+/// an upgrade it authorized, and the calls of safe mode, the transaction pause and the governance
+/// pallets, so that governance can still act, e.g. to upgrade the runtime, before the brakes are
+/// released.
+fn is_critical(pallet: &str, call: &str) -> bool {
+	is_allowed_while_paused(pallet, call) ||
+		(pallet == System::name() &&
+			["set_code", "authorize_upgrade", "apply_authorized_upgrade"].contains(&call)) ||
+		[
+			SafeMode::name(),
+			TxPause::name(),
+			TechnicalCommittee::name(),
+			Council::name(),
+			EmergencyUpgrade::name(),
+		]
+		.contains(&pallet)
+}
+
+/// The calls which can be dispatched while the chain is paused, see [`is_allowed_while_paused`].
+pub struct AllowedWhilePaused;
+impl Contains<RuntimeCall> for AllowedWhilePaused {
+	fn contains(c: &RuntimeCall) -> bool {
+		let metadata = c.get_call_metadata();
+		is_allowed_while_paused(metadata.pallet_name, metadata.function_name)
+	}
+}
+
+/// The origin allowed to pause and unpause the chain: root or the pause authority set at genesis.
+pub type PauseOrigin =
+	EitherOfDiverse<EnsureRoot<AccountId>, pallet_maintenance_mode::EnsurePauseAuthority<Runtime>>;
+
+impl pallet_maintenance_mode::Config for Runtime {
+	type AllowedWhilePaused = AllowedWhilePaused;
+	type PauseOrigin = PauseOrigin;
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_maintenance_mode::weights::SubstrateWeight<Runtime>;
+}
+
+/// The calls rejected while transfers are frozen: plain and vested transfers of the native token.
+pub struct PlainTransfers;
+impl Contains<RuntimeCall> for PlainTransfers {
+	fn contains(c: &RuntimeCall) -> bool {
+		use pallet_balances::Call::*;
+		matches!(
+			c,
+			RuntimeCall::Balances(
+				transfer_allow_death { .. } | transfer_keep_alive { .. } | transfer_all { .. }
+			) | RuntimeCall::Vesting(pallet_vesting::Call::vested_transfer { .. })
+		)
+	}
+}
+
+impl pallet_transfer_freeze::Config for Runtime {
+	type FreezeOrigin = GovernanceOrigin;
+	type FrozenCalls = PlainTransfers;
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_transfer_freeze::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_account_freeze::Config for Runtime {
+	type FreezeOrigin = GovernanceOrigin;
+	type Fungible = Balances;
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type WeightInfo = pallet_account_freeze::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_weight_overrides::Config for Runtime {
+	type InnerCalls = crate::DispatchedCalls;
+	type MaxMultiplier = MaxWeightMultiplier;
+	type OverrideOrigin = GovernanceOrigin;
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_weight_overrides::weights::SubstrateWeight<Runtime>;
+}
+
+/// The calls which can be dispatched in safe mode, see [`is_critical`].
+pub struct SafeModeWhitelistedCalls;
+impl Contains<RuntimeCall> for SafeModeWhitelistedCalls {
+	fn contains(c: &RuntimeCall) -> bool {
+		let metadata = c.get_call_metadata();
+		is_critical(metadata.pallet_name, metadata.function_name)
+	}
+}
+
+parameter_types! {
+	pub const SafeModeDuration: BlockNumber = DAYS;
+	// Safe mode can only be entered and extended by the emergency origin, not against a deposit.
+	pub const SafeModeDeposit: Option<Balance> = None;
+	pub const SafeModeReleaseDelay: Option<BlockNumber> = None;
+}
+
+/// The origin allowed to enter and extend safe mode, for [`SafeModeDuration`].
+pub type SafeModeOrigin = EnsureWithSuccess<EmergencyUpgradeOrigin, AccountId, SafeModeDuration>;
+
+impl pallet_safe_mode::Config for Runtime {
+	type Currency = Balances;
+	type EnterDepositAmount = SafeModeDeposit;
+	type EnterDuration = SafeModeDuration;
+	type ExtendDepositAmount = SafeModeDeposit;
+	type ExtendDuration = SafeModeDuration;
+	type ForceDepositOrigin = EmergencyUpgradeOrigin;
+	type ForceEnterOrigin = SafeModeOrigin;
+	type ForceExitOrigin = EmergencyUpgradeOrigin;
+	type ForceExtendOrigin = SafeModeOrigin;
+	type Notify = ();
+	type ReleaseDelay = SafeModeReleaseDelay;
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type WeightInfo = pallet_safe_mode::weights::SubstrateWeight<Runtime>;
+	type WhitelistedCalls = SafeModeWhitelistedCalls;
+}
+
+/// The calls which cannot be paused by the transaction pause, see [`is_critical`].
+pub struct UnpausableCalls;
+impl Contains<RuntimeCallNameOf<Runtime>> for UnpausableCalls {
+	fn contains((pallet, call): &RuntimeCallNameOf<Runtime>) -> bool {
+		match (core::str::from_utf8(pallet), core::str::from_utf8(call)) {
+			(Ok(pallet), Ok(call)) => is_critical(pallet, call),
+			_ => false,
+		}
+	}
+}
+
+impl pallet_tx_pause::Config for Runtime {
+	type MaxNameLen = ConstU32<256>;
+	type PauseOrigin = EmergencyUpgradeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type UnpauseOrigin = EmergencyUpgradeOrigin;
+	type WeightInfo = pallet_tx_pause::weights::SubstrateWeight<Runtime>;
+	type WhitelistedCalls = UnpausableCalls;
+}
@@ -0,0 +1,47 @@
+use frame_support::parameter_types;
+use pallet_identity::legacy::IdentityInfo;
+use sp_runtime::traits::Verify;
+
+use super::governance::GovernanceOrigin;
+use crate::{
+	deposit, Balance, Balances, BlockNumber, Runtime, RuntimeEvent, Signature, Treasury, DAYS,
+};
+
+parameter_types! {
+	// One storage item; key size is 32; value is size 4+4+16+32 bytes = 56 bytes.
+	pub const BasicDeposit: Balance = deposit(1, 17);
+	// Additional bytes adds 0 items.
+	pub const ByteDeposit: Balance = deposit(0, 1);
+	// One storage item; key size 32, value size 16+32 = 53 bytes.
+	pub const SubAccountDeposit: Balance = deposit(1, 53);
+	pub const MaxSubAccounts: u32 = 100;
+	pub const MaxAdditionalFields: u32 = 100;
+	pub const MaxRegistrars: u32 = 20;
+	pub const PendingUsernameExpiration: BlockNumber = 7 * DAYS;
+	pub const MaxSuffixLength: u32 = 7;
+	pub const MaxUsernameLength: u32 = 32;
+}
+
+impl pallet_identity::Config for Runtime {
+	type BasicDeposit = BasicDeposit;
+	type ByteDeposit = ByteDeposit;
+	type Currency = Balances;
+	type ForceOrigin = GovernanceOrigin;
+	type IdentityInformation = IdentityInfo<MaxAdditionalFields>;
+	type MaxRegistrars = MaxRegistrars;
+	type MaxSubAccounts = MaxSubAccounts;
+	type MaxSuffixLength = MaxSuffixLength;
+	type MaxUsernameLength = MaxUsernameLength;
+	type OffchainSignature = Signature;
+	type PendingUsernameExpiration = PendingUsernameExpiration;
+	type RegistrarOrigin = GovernanceOrigin;
+	type RuntimeEvent = RuntimeEvent;
+	type SigningPublicKey = <Signature as Verify>::Signer;
+	// Slashed deposits fund the treasury.
+	type Slashed = Treasury;
+	type SubAccountDeposit = SubAccountDeposit;
+	type UsernameAuthorityOrigin = GovernanceOrigin;
+	type WeightInfo = pallet_identity::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_genesis_registrars::Config for Runtime {}
@@ -1,5 +1,12 @@
-mod assets;
-mod contracts;
+pub(crate) mod assets;
+pub(crate) mod collation;
+pub(crate) mod contracts;
+pub(crate) mod fees;
+pub(crate) mod governance;
+pub(crate) mod identity;
+pub(crate) mod offchain;
 mod proxy;
+pub(crate) mod relay;
+pub(crate) mod treasury;
 // Public due to integration tests crate.
 pub mod xcm;
@@ -0,0 +1,89 @@
+use codec::Encode;
+use frame_support::{parameter_types, traits::ConstU32};
+use pallet_account_freeze::CheckNotFrozen;
+use polkadot_runtime_common::BlockHashCount;
+use sp_runtime::{
+	generic::{Era, SignedPayload},
+	traits::{Extrinsic, StaticLookup, Verify},
+	transaction_validity::TransactionPriority,
+	SaturatedConversion,
+};
+
+use super::contracts::FloorContractCallFee;
+use crate::{
+	AccountId, BlockNumber, Nonce, Runtime, RuntimeCall, RuntimeEvent, Signature, SignedExtra,
+	System, UncheckedExtrinsic,
+};
+
+impl frame_system::offchain::SigningTypes for Runtime {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+where
+	RuntimeCall: From<C>,
+{
+	type Extrinsic = UncheckedExtrinsic;
+	type OverarchingCall = RuntimeCall;
+}
+
+/// Signs the transactions of offchain workers with the keys of the local keystore, paying no tip
+/// and staying valid for about half as many blocks as there are block hashes kept.
+impl<C> frame_system::offchain::CreateSignedTransaction<C> for Runtime
+where
+	RuntimeCall: From<C>,
+{
+	fn create_transaction<A: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: RuntimeCall,
+		public: <Signature as Verify>::Signer,
+		account: AccountId,
+		nonce: Nonce,
+	) -> Option<(RuntimeCall, <UncheckedExtrinsic as Extrinsic>::SignaturePayload)> {
+		let period =
+			BlockHashCount::get().checked_next_power_of_two().map(|c| c / 2).unwrap_or(2) as u64;
+		// The worker runs on top of the imported block, so the era starts at its parent.
+		let current_block = System::block_number().saturated_into::<u64>().saturating_sub(1);
+		let extra: SignedExtra = (
+			frame_system::CheckNonZeroSender::new(),
+			frame_system::CheckSpecVersion::new(),
+			frame_system::CheckTxVersion::new(),
+			frame_system::CheckGenesis::new(),
+			frame_system::CheckEra::from(Era::mortal(period, current_block)),
+			frame_system::CheckNonce::from(nonce),
+			(
+				frame_system::CheckWeight::new(),
+				FloorContractCallFee(pallet_asset_tx_payment::ChargeAssetTxPayment::from(0, None)),
+				cumulus_primitives_storage_weight_reclaim::StorageWeightReclaim::new(),
+			)
+				.into(),
+			frame_metadata_hash_extension::CheckMetadataHash::new(false),
+			CheckNotFrozen::new(),
+		);
+		let payload = SignedPayload::new(call, extra)
+			.map_err(|e| log::warn!(target: "runtime::offchain", "Unable to sign: {e:?}"))
+			.ok()?;
+		let signature = payload.using_encoded(|payload| A::sign(payload, public))?;
+		let (call, extra, _) = payload.deconstruct();
+		Some((
+			call,
+			(<Runtime as frame_system::Config>::Lookup::unlookup(account), signature, extra),
+		))
+	}
+}
+
+parameter_types! {
+	// Unsigned submissions are limited to one every few blocks, as they pay no fees.
+	pub const UnsignedInterval: BlockNumber = 5;
+	// Ranked below ordinary transactions, which are prioritised by the fee they pay.
+	pub const UnsignedPriority: TransactionPriority = TransactionPriority::MAX / 2;
+}
+
+impl pallet_price_feed::Config for Runtime {
+	type AuthorityId = pallet_price_feed::crypto::AuthorityId;
+	type MaxPrices = ConstU32<64>;
+	type RuntimeEvent = RuntimeEvent;
+	type UnsignedInterval = UnsignedInterval;
+	type UnsignedPriority = UnsignedPriority;
+	type WeightInfo = pallet_price_feed::weights::SubstrateWeight<Runtime>;
+}
@@ -0,0 +1,44 @@
+use frame_support::{
+	parameter_types,
+	traits::tokens::{pay::PayFromAccount, UnityAssetBalanceConversion},
+	PalletId,
+};
+use frame_system::EnsureWithSuccess;
+use sp_runtime::traits::{AccountIdConversion, AccountIdLookup};
+
+use super::governance::GovernanceOrigin;
+use crate::{AccountId, Balance, Balances, BlockNumber, Runtime, RuntimeEvent, DAYS};
+
+parameter_types! {
+	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+	pub TreasuryAccount: AccountId = TreasuryPalletId::get().into_account_truncating();
+	pub const SpendPeriod: BlockNumber = 6 * DAYS;
+	pub const PayoutPeriod: BlockNumber = 30 * DAYS;
+	pub const MaxApprovals: u32 = 100;
+	// Spends approved by governance are only limited by the funds of the treasury.
+	pub const MaxTreasurySpend: Balance = Balance::MAX;
+}
+
+impl pallet_treasury::Config for Runtime {
+	// Only the native token is spent.
+	type AssetKind = ();
+	type BalanceConverter = UnityAssetBalanceConversion;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+	type Beneficiary = AccountId;
+	type BeneficiaryLookup = AccountIdLookup<AccountId, ()>;
+	// Spare funds are kept, as a share of the fees is burnt already.
+	type Burn = ();
+	type BurnDestination = ();
+	type Currency = Balances;
+	type MaxApprovals = MaxApprovals;
+	type PalletId = TreasuryPalletId;
+	type Paymaster = PayFromAccount<Balances, TreasuryAccount>;
+	type PayoutPeriod = PayoutPeriod;
+	type RejectOrigin = GovernanceOrigin;
+	type RuntimeEvent = RuntimeEvent;
+	type SpendFunds = ();
+	type SpendOrigin = EnsureWithSuccess<GovernanceOrigin, AccountId, MaxTreasurySpend>;
+	type SpendPeriod = SpendPeriod;
+	type WeightInfo = pallet_treasury::weights::SubstrateWeight<Runtime>;
+}
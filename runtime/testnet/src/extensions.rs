@@ -1,22 +1,23 @@
-use frame_support::{
-	dispatch::{GetDispatchInfo, RawOrigin},
-	pallet_prelude::*,
-	traits::{Contains, OriginTrait},
+use frame_support::{dispatch::RawOrigin, pallet_prelude::*};
+use pallet_contracts::chain_extension::{ChainExtension, Environment, Ext, InitState, RetVal};
+use pop_primitives::{
+	cross_chain::CrossChainMessage,
+	storage_keys::{AssetsKeys, NftsKeys, ParachainSystemKeys, RelayChainKeys, RuntimeStateKeys},
 };
-use pallet_contracts::{
-	chain_extension::{
-		BufInBufOutState, ChainExtension, ChargedAmount, Environment, Ext, InitState, RetVal,
-	},
-	WeightInfo,
+use pop_runtime_common::extensions::{
+	charge_overhead_weight, cross_chain_message, dispatch, dispatch_call,
+	pallet_storage_read_weight, read_state, status_code, storage_read_weight, v0, ReadState,
+	LOG_TARGET,
 };
-use pop_primitives::storage_keys::RuntimeStateKeys;
 use sp_core::crypto::UncheckedFrom;
-use sp_runtime::{traits::Dispatchable, DispatchError};
-use sp_std::vec::Vec;
+use sp_runtime::DispatchError;
+use sp_std::{boxed::Box, vec::Vec};
+use xcm::{VersionedLocation, VersionedXcm};
 
-use crate::{AccountId, AllowedApiCalls, RuntimeCall, RuntimeOrigin};
-
-const LOG_TARGET: &str = "pop-api::extension";
+use crate::{
+	AccountId, AllowedApiCalls, Assets, Nfts, ParachainInfo, RelayState, Runtime, RuntimeCall,
+	RuntimeOrigin,
+};
 
 #[derive(Default)]
 pub struct PopApiExtension;
@@ -37,159 +38,188 @@ where
 	{
 		log::debug!(target:LOG_TARGET, " extension called ");
 		match v0::FuncId::try_from(env.func_id())? {
-			v0::FuncId::Dispatch => {
-				match dispatch::<T, E>(env) {
-					Ok(()) => Ok(RetVal::Converging(0)),
-					Err(DispatchError::Module(error)) => {
-						// encode status code = pallet index in runtime + error index, allowing for
-						// 999 errors
-						Ok(RetVal::Converging(
-							(error.index as u32 * 1_000) + u32::from_le_bytes(error.error),
-						))
-					},
-					Err(e) => Err(e),
-				}
-			},
+			v0::FuncId::Dispatch => status_code(dispatch::<T, E, AllowedApiCalls>(env)),
 			v0::FuncId::ReadState => {
-				read_state::<T, E>(env)?;
+				read_state::<T, E, RuntimeState>(env)?;
 				Ok(RetVal::Converging(0))
 			},
+			v0::FuncId::SendXcm => status_code(send_xcm::<T, E>(env)),
+			v0::FuncId::SendXcmMessage => status_code(send_xcm_message::<T, E>(env)),
 		}
 	}
 }
 
-pub mod v0 {
-	#[derive(Debug)]
-	pub enum FuncId {
-		Dispatch,
-		ReadState,
+/// The state of the runtime read by contracts.
+struct RuntimeState;
+
+impl ReadState for RuntimeState {
+	type Key = RuntimeStateKeys;
+
+	fn weight(key: &RuntimeStateKeys) -> Weight {
+		use cumulus_pallet_parachain_system::LastRelayChainBlockNumber;
+		use pallet_nfts::{Attribute, Collection, CollectionMetadataOf, Item, ItemMetadataOf};
+
+		match key {
+			RuntimeStateKeys::Nfts(key) => match key {
+				NftsKeys::Collection(_) | NftsKeys::CollectionOwner(_) =>
+					storage_read_weight::<Runtime, Collection<Runtime>>(),
+				NftsKeys::Item(..) | NftsKeys::Owner(..) =>
+					storage_read_weight::<Runtime, Item<Runtime>>(),
+				// The metadata is read instead for its well-known attribute key.
+				NftsKeys::Attribute(..) =>
+					storage_read_weight::<Runtime, (Attribute<Runtime>, ItemMetadataOf<Runtime>)>(),
+				NftsKeys::SystemAttribute(..) =>
+					storage_read_weight::<Runtime, Attribute<Runtime>>(),
+				NftsKeys::CollectionAttribute(..) => storage_read_weight::<
+					Runtime,
+					(Attribute<Runtime>, CollectionMetadataOf<Runtime>),
+				>(),
+			},
+			RuntimeStateKeys::ParachainSystem(key) => match key {
+				ParachainSystemKeys::LastRelayChainBlockNumber =>
+					storage_read_weight::<Runtime, LastRelayChainBlockNumber<Runtime>>(),
+			},
+			RuntimeStateKeys::Assets(key) => match key {
+				AssetsKeys::AssetExists(_) | AssetsKeys::TotalSupply(_) =>
+					pallet_storage_read_weight::<Runtime, Assets>("Asset"),
+				AssetsKeys::BalanceOf(..) =>
+					pallet_storage_read_weight::<Runtime, Assets>("Account"),
+			},
+			RuntimeStateKeys::RelayChain(_) =>
+				storage_read_weight::<Runtime, pallet_relay_state::State<Runtime>>(),
+		}
 	}
-}
-
-impl TryFrom<u16> for v0::FuncId {
-	type Error = DispatchError;
 
-	fn try_from(func_id: u16) -> Result<Self, Self::Error> {
-		let id = match func_id {
-			0x0 => Self::Dispatch,
-			0x1 => Self::ReadState,
-			_ => {
-				log::error!("called an unregistered `func_id`: {:}", func_id);
-				return Err(DispatchError::Other("unimplemented func_id"));
+	fn read(key: RuntimeStateKeys) -> Vec<u8> {
+		match key {
+			RuntimeStateKeys::Nfts(key) => read_nfts_state(key),
+			RuntimeStateKeys::ParachainSystem(key) => match key {
+				ParachainSystemKeys::LastRelayChainBlockNumber =>
+					cumulus_pallet_parachain_system::LastRelayChainBlockNumber::<Runtime>::get()
+						.encode(),
 			},
-		};
-
-		Ok(id)
+			RuntimeStateKeys::Assets(key) => read_assets_state(key),
+			RuntimeStateKeys::RelayChain(key) => read_relay_chain_state(key),
+		}
 	}
 }
 
-fn dispatch_call<T, E>(
-	env: &mut Environment<E, BufInBufOutState>,
-	call: RuntimeCall,
-	mut origin: RuntimeOrigin,
-	log_prefix: &str,
-) -> Result<(), DispatchError>
-where
-	E: Ext<T = T>,
-{
-	let charged_dispatch_weight = env.charge_weight(call.get_dispatch_info().weight)?;
-
-	log::debug!(target:LOG_TARGET, "{} inputted RuntimeCall: {:?}", log_prefix, call);
-
-	origin.add_filter(AllowedApiCalls::contains);
-
-	match call.dispatch(origin) {
-		Ok(info) => {
-			log::debug!(target:LOG_TARGET, "{} success, actual weight: {:?}", log_prefix, info.actual_weight);
-
-			// refund weight if the actual weight is less than the charged weight
-			if let Some(actual_weight) = info.actual_weight {
-				env.adjust_weight(charged_dispatch_weight, actual_weight);
-			}
-
-			Ok(())
-		},
-		Err(err) => {
-			log::debug!(target:LOG_TARGET, "{} failed: error: {:?}", log_prefix, err.error);
-			Err(err.error)
-		},
+fn read_nfts_state(key: NftsKeys) -> Vec<u8> {
+	use frame_support::traits::nonfungibles_v2::Inspect;
+
+	match key {
+		NftsKeys::Collection(collection) =>
+			pallet_nfts::Collection::<Runtime>::get(collection).encode(),
+		NftsKeys::CollectionOwner(collection) =>
+			<Nfts as Inspect<AccountId>>::collection_owner(&collection).encode(),
+		NftsKeys::Item(collection, item) =>
+			pallet_nfts::Item::<Runtime>::get(collection, item).encode(),
+		NftsKeys::Owner(collection, item) =>
+			<Nfts as Inspect<AccountId>>::owner(&collection, &item).encode(),
+		NftsKeys::Attribute(collection, item, key) =>
+			<Nfts as Inspect<AccountId>>::attribute(&collection, &item, &key).encode(),
+		NftsKeys::SystemAttribute(collection, item, key) =>
+			<Nfts as Inspect<AccountId>>::system_attribute(&collection, item.as_ref(), &key)
+				.encode(),
+		NftsKeys::CollectionAttribute(collection, key) =>
+			<Nfts as Inspect<AccountId>>::collection_attribute(&collection, &key).encode(),
 	}
 }
 
-fn charge_overhead_weight<T, E>(
-	env: &mut Environment<E, BufInBufOutState>,
-	len: u32,
-	log_prefix: &str,
-) -> Result<ChargedAmount, DispatchError>
-where
-	T: pallet_contracts::Config,
-	E: Ext<T = T>,
-{
-	// calculate weight for reading bytes of `len`
-	// reference: https://github.com/paritytech/polkadot-sdk/blob/117a9433dac88d5ac00c058c9b39c511d47749d2/substrate/frame/contracts/src/wasm/runtime.rs#L267
-	let base_weight: Weight = T::WeightInfo::seal_return(len);
-
-	// debug_message weight is a good approximation of the additional overhead of going
-	// from contract layer to substrate layer.
-	// reference: https://github.com/paritytech/ink-examples/blob/b8d2caa52cf4691e0ddd7c919e4462311deb5ad0/psp22-extension/runtime/psp22-extension-example.rs#L236
-	let overhead: Weight = T::WeightInfo::seal_debug_message(len);
+fn read_assets_state(key: AssetsKeys) -> Vec<u8> {
+	use frame_support::traits::fungibles::Inspect;
 
-	let charged_weight = env.charge_weight(base_weight.saturating_add(overhead))?;
-	log::debug!(target: LOG_TARGET, "{} charged weight: {:?}", log_prefix, charged_weight);
+	match key {
+		AssetsKeys::AssetExists(id) => <Assets as Inspect<AccountId>>::asset_exists(id).encode(),
+		AssetsKeys::TotalSupply(id) => <Assets as Inspect<AccountId>>::total_issuance(id).encode(),
+		AssetsKeys::BalanceOf(id, owner) =>
+			<Assets as Inspect<AccountId>>::balance(id, &owner.into()).encode(),
+	}
+}
 
-	Ok(charged_weight)
+fn read_relay_chain_state(key: RelayChainKeys) -> Vec<u8> {
+	// The state is recorded before any contract is called in a block, unless it fails to be read.
+	let state = RelayState::relay_chain_state();
+	match key {
+		RelayChainKeys::BlockNumber => state.map(|state| state.block_number).encode(),
+		RelayChainKeys::Randomness => state.and_then(|state| state.randomness).encode(),
+		RelayChainKeys::Timestamp => state.map(|state| state.timestamp).encode(),
+	}
 }
 
-fn dispatch<T, E>(env: Environment<E, InitState>) -> Result<(), DispatchError>
+/// Send the cross-chain message of the calling contract, built into XCM, via `XcmGateway`.
+fn send_xcm<T, E>(env: Environment<E, InitState>) -> Result<(), DispatchError>
 where
-	T: pallet_contracts::Config,
-	RuntimeOrigin: From<RawOrigin<T::AccountId>>,
+	T: pallet_contracts::Config
+		+ frame_system::Config<
+			RuntimeOrigin = RuntimeOrigin,
+			AccountId = AccountId,
+			RuntimeCall = RuntimeCall,
+		>,
 	E: Ext<T = T>,
 {
-	const LOG_PREFIX: &str = " dispatch |";
+	const LOG_PREFIX: &str = " send_xcm |";
 
 	let mut env = env.buf_in_buf_out();
 	let len = env.in_len();
 
 	charge_overhead_weight::<T, E>(&mut env, len, LOG_PREFIX)?;
 
-	// read the input as RuntimeCall
-	let call: RuntimeCall = env.read_as_unbounded(len)?;
-
-	// contract is the origin by default
-	let origin: RuntimeOrigin = RawOrigin::Signed(env.ext().address().clone()).into();
-
-	dispatch_call::<T, E>(&mut env, call, origin, LOG_PREFIX)
+	let message: CrossChainMessage = env.read_as()?;
+	let contract = env.ext().address().clone();
+	let (dest, message) =
+		cross_chain_message(ParachainInfo::parachain_id().into(), contract.clone(), message);
+
+	// The message is sent on behalf of the contract via `XcmGateway`, so that its origin is the
+	// contract, which must be allowed to send messages to the relay chain and pays for their
+	// delivery.
+	let call = RuntimeCall::XcmGateway(pallet_xcm_gateway::Call::send {
+		destination: Box::new(VersionedLocation::V4(dest)),
+		message: Box::new(VersionedXcm::V4(message)),
+	});
+	let origin: RuntimeOrigin = RawOrigin::Signed(contract).into();
+
+	dispatch_call::<T, E, AllowedApiCalls>(&mut env, call, origin, LOG_PREFIX)
 }
 
-fn read_state<T, E>(env: Environment<E, InitState>) -> Result<(), DispatchError>
+/// Send an XCM message, given as its encoded destination and message, on behalf of the calling
+/// contract via `XcmGateway`, which checks whether the contract may send messages to the
+/// destination and charges it the delivery fee.
+fn send_xcm_message<T, E>(env: Environment<E, InitState>) -> Result<(), DispatchError>
 where
-	T: pallet_contracts::Config,
+	T: pallet_contracts::Config
+		+ frame_system::Config<
+			RuntimeOrigin = RuntimeOrigin,
+			AccountId = AccountId,
+			RuntimeCall = RuntimeCall,
+		>,
 	E: Ext<T = T>,
 {
-	const LOG_PREFIX: &str = " read_state |";
+	use codec::{DecodeLimit, Error};
+	use xcm::MAX_XCM_DECODE_DEPTH;
 
-	let mut env = env.buf_in_buf_out();
+	const LOG_PREFIX: &str = " send_xcm_message |";
 
-	// To be conservative, we charge the weight for reading the input bytes of a fixed-size type.
-	let base_weight: Weight = T::WeightInfo::seal_return(env.in_len());
-	let charged_weight = env.charge_weight(base_weight)?;
+	let mut env = env.buf_in_buf_out();
+	let len = env.in_len();
 
-	log::debug!(target:LOG_TARGET, "{} charged weight: {:?}", LOG_PREFIX, charged_weight);
+	charge_overhead_weight::<T, E>(&mut env, len, LOG_PREFIX)?;
 
-	let key: RuntimeStateKeys = env.read_as()?;
+	let (destination, message): (Vec<u8>, Vec<u8>) = env.read_as_unbounded(len)?;
+	let bad_version =
+		|_: Error| -> DispatchError { pallet_xcm_gateway::Error::<Runtime>::BadVersion.into() };
+	let destination =
+		VersionedLocation::decode_all_with_depth_limit(MAX_XCM_DECODE_DEPTH, &mut &destination[..])
+			.map_err(bad_version)?;
+	let message =
+		VersionedXcm::<()>::decode_all_with_depth_limit(MAX_XCM_DECODE_DEPTH, &mut &message[..])
+			.map_err(bad_version)?;
+
+	let call = RuntimeCall::XcmGateway(pallet_xcm_gateway::Call::send {
+		destination: Box::new(destination),
+		message: Box::new(message),
+	});
+	let origin: RuntimeOrigin = RawOrigin::Signed(env.ext().address().clone()).into();
 
-	let result = match key {
-		_ => Vec::<u8>::default(),
-	}
-	.encode();
-
-	log::trace!(
-		target:LOG_TARGET,
-		"{} result: {:?}.", LOG_PREFIX, result
-	);
-	env.write(&result, false, None).map_err(|e| {
-		log::trace!(target: LOG_TARGET, "{:?}", e);
-		DispatchError::Other("unable to write results to contract memory")
-	})
+	dispatch_call::<T, E, AllowedApiCalls>(&mut env, call, origin, LOG_PREFIX)
 }
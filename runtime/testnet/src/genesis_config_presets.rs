@@ -0,0 +1,295 @@
+//! The presets of the genesis of the testnet, exposed through the `GenesisBuilder` runtime api so
+//! that the genesis of a chain can be built from the runtime alone, e.g. by chopsticks or
+//! zombienet, without the node.
+
+use cumulus_primitives_core::ParaId;
+use sp_core::{crypto::Ss58Codec, sr25519, Pair};
+use sp_genesis_builder::PresetId;
+use sp_runtime::{FixedU128, Percent};
+use sp_std::{vec, vec::Vec};
+
+use crate::{
+	config::treasury::TreasuryAccount, AccountId, AuraId, Balance, SessionKeys,
+	EXISTENTIAL_DEPOSIT, UNIT,
+};
+
+/// The preset of a development chain, with Alice as its only collator and the development
+/// accounts endowed.
+pub const DEVELOPMENT: &str = "development";
+/// The preset of a local testnet, with Alice and Bob as collators.
+pub const LOCAL_TESTNET: &str = "local_testnet";
+/// The preset of the live testnet.
+pub const LIVE: &str = "live";
+
+/// The id of the testnet on Paseo, which the presets are for.
+pub const PARA_ID: u32 = 4001;
+
+/// The XCM version used for chains whose version is not yet known.
+const SAFE_XCM_VERSION: u32 = xcm::prelude::XCM_VERSION;
+
+/// An initial collator: the account registered as invulnerable, the validator id its session keys
+/// are registered for and the key it authors blocks with.
+pub type Invulnerable = (AccountId, AccountId, AuraId);
+
+/// An asset of `pallet_assets` created at genesis, owned by `owner`, which is its admin, issuer and
+/// freezer as well.
+pub struct GenesisAsset {
+	pub id: u32,
+	pub owner: AccountId,
+	/// Whether accounts may hold the asset without holding the native token.
+	pub sufficient: bool,
+	/// The least balance of the asset an account may hold.
+	pub min_balance: Balance,
+	pub name: Vec<u8>,
+	pub symbol: Vec<u8>,
+	pub decimals: u8,
+	/// The accounts holding the asset at genesis, along with their balances.
+	pub holders: Vec<(AccountId, Balance)>,
+	/// The balance of the asset charged per unit of native fees, if transaction fees can be paid
+	/// in the asset.
+	pub fee_per_unit: Option<Balance>,
+}
+
+/// How the collators are rewarded from genesis: with the transaction fees which are neither burnt
+/// nor fund the treasury, along with the amount minted into the pot of the collator selection each
+/// block.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CollatorRewards {
+	/// The share of transaction fees which is burnt.
+	pub burn: Percent,
+	/// The share of transaction fees which funds the treasury.
+	pub treasury: Percent,
+	/// The amount minted into the pot each block.
+	pub block_reward: Balance,
+}
+
+impl Default for CollatorRewards {
+	/// A fifth of the fees is burnt and three tenths fund the treasury, the collators being
+	/// rewarded with the rest, without any block reward.
+	fn default() -> Self {
+		Self {
+			burn: Percent::from_percent(20),
+			treasury: Percent::from_percent(30),
+			block_reward: 0,
+		}
+	}
+}
+
+/// The parameters of the genesis of the testnet.
+pub struct GenesisParams {
+	/// The initial collators.
+	pub invulnerables: Vec<Invulnerable>,
+	/// The sudo account, if any. The live testnet has none, being governed by its collectives.
+	pub root: Option<AccountId>,
+	/// The members of the technical committee.
+	pub tech_committee: Vec<AccountId>,
+	/// The members of the general council.
+	pub council: Vec<AccountId>,
+	/// The accounts endowed at genesis, along with their free balance.
+	pub endowed: Vec<(AccountId, Balance)>,
+	/// The assets created at genesis.
+	pub assets: Vec<GenesisAsset>,
+	/// Whether the chain starts paused, so that only sudo can dispatch calls until unpaused.
+	pub paused: bool,
+	/// The account allowed to pause and unpause the chain besides sudo, e.g. a multisig.
+	pub pause_authority: Option<AccountId>,
+	/// How the collators are rewarded.
+	pub rewards: CollatorRewards,
+	/// The id of the parachain.
+	pub id: ParaId,
+}
+
+impl GenesisParams {
+	/// The genesis of the parachain `id` collated by the `invulnerables`, with `root` as sudo if
+	/// any and nothing else: without members of governance, endowed accounts or assets, unpaused
+	/// and with the collators rewarded as by default.
+	pub fn new(invulnerables: Vec<Invulnerable>, root: Option<AccountId>, id: ParaId) -> Self {
+		Self {
+			invulnerables,
+			root,
+			tech_committee: vec![],
+			council: vec![],
+			endowed: vec![],
+			assets: vec![],
+			paused: false,
+			pause_authority: None,
+			rewards: CollatorRewards::default(),
+			id,
+		}
+	}
+}
+
+/// The genesis config patch of the testnet described by `params`.
+pub fn testnet_genesis(params: GenesisParams) -> serde_json::Value {
+	let GenesisParams {
+		invulnerables,
+		root,
+		tech_committee,
+		council,
+		endowed,
+		assets,
+		paused,
+		pause_authority,
+		rewards,
+		id,
+	} = params;
+	let mut genesis = serde_json::json!({
+		"balances": {
+			"balances": endowed,
+		},
+		"parachainInfo": {
+			"parachainId": id,
+		},
+		"collatorSelection": {
+			"invulnerables": invulnerables.iter().map(|(account, ..)| account.clone()).collect::<Vec<_>>(),
+			"candidacyBond": EXISTENTIAL_DEPOSIT * 16,
+		},
+		"session": {
+			"keys": invulnerables
+				.into_iter()
+				.map(|(account, validator, aura)| (account, validator, SessionKeys { aura }))
+				.collect::<Vec<_>>(),
+		},
+		// Only the XCM version of chains whose version is not yet known is configured at genesis.
+		"polkadotXcm": { "safeXcmVersion": Some(SAFE_XCM_VERSION) },
+		"sudo": { "key": root },
+		"technicalCommittee": { "members": tech_committee },
+		"council": { "members": council },
+		"maintenanceMode": { "paused": paused, "pauseAuthority": pause_authority },
+		"collatorRewards": { "mode": "PotAccumulation" },
+		"feeSplit": { "split": { "burn": rewards.burn, "treasury": rewards.treasury } }
+	});
+	// Left unset otherwise, so that the genesis is unchanged.
+	if rewards.block_reward > 0 {
+		genesis["collatorRewards"]["blockReward"] = serde_json::json!(rewards.block_reward);
+	}
+	// Likewise for assets, which are rarely created at genesis.
+	if !assets.is_empty() {
+		genesis["assets"] = serde_json::json!({
+			"assets": assets
+				.iter()
+				.map(|asset| (asset.id, &asset.owner, asset.sufficient, asset.min_balance))
+				.collect::<Vec<_>>(),
+			"metadata": assets
+				.iter()
+				.map(|asset| (asset.id, &asset.name, &asset.symbol, asset.decimals))
+				.collect::<Vec<_>>(),
+			"accounts": assets
+				.iter()
+				.flat_map(|asset| {
+					asset.holders.iter().map(|(account, balance)| (asset.id, account, balance))
+				})
+				.collect::<Vec<_>>(),
+		});
+	}
+	let fee_rates: Vec<_> = assets
+		.iter()
+		.filter_map(|asset| {
+			asset.fee_per_unit.map(|fee| (asset.id, FixedU128::from_rational(fee, UNIT)))
+		})
+		.collect();
+	if !fee_rates.is_empty() {
+		genesis["feeAssets"] = serde_json::json!({ "rates": fee_rates });
+	}
+	genesis
+}
+
+/// The development account of `seed`, e.g. `//Alice`.
+fn dev_account(seed: &str) -> AccountId {
+	dev_key(seed).into()
+}
+
+/// The sr25519 key of the development account of `seed`, e.g. `//Alice`.
+fn dev_key(seed: &str) -> sr25519::Public {
+	sr25519::Pair::from_string(seed, None)
+		.expect("static values are valid; qed")
+		.public()
+}
+
+/// The treasury, endowed on local chains so that spends can be tested without accumulating fees
+/// first.
+fn endowed_treasury() -> (AccountId, Balance) {
+	(TreasuryAccount::get(), 1_000_000 * UNIT)
+}
+
+/// A development collator, authoring blocks with the key of its account.
+fn dev_collator(seed: &str) -> Invulnerable {
+	(dev_account(seed), dev_account(seed), dev_key(seed).into())
+}
+
+/// The genesis of the [`DEVELOPMENT`] preset.
+pub fn development_genesis(id: ParaId) -> serde_json::Value {
+	let alice = dev_account("//Alice");
+	let endowed = ["//Alice", "//Bob", "//Charlie", "//Dave", "//Eve", "//Ferdie"]
+		.into_iter()
+		.map(|seed| (dev_account(seed), 1_000_000 * UNIT))
+		.chain([endowed_treasury()])
+		.collect();
+	let mut genesis = testnet_genesis(GenesisParams {
+		tech_committee: vec![alice.clone()],
+		council: vec![alice.clone()],
+		endowed,
+		..GenesisParams::new(vec![dev_collator("//Alice")], Some(alice.clone()), id)
+	});
+	// Alice judges identities, so that they can be tested without a registrar added by governance.
+	genesis["identityGenesis"] = serde_json::json!({ "registrars": [alice] });
+	genesis
+}
+
+/// The genesis of the [`LOCAL_TESTNET`] preset.
+pub fn local_testnet_genesis(id: ParaId) -> serde_json::Value {
+	let alice = dev_account("//Alice");
+	testnet_genesis(GenesisParams {
+		tech_committee: vec![alice.clone()],
+		council: ["//Alice", "//Bob", "//Charlie"].map(dev_account).to_vec(),
+		endowed: vec![endowed_treasury()],
+		..GenesisParams::new(vec![dev_collator("//Alice"), dev_collator("//Bob")], Some(alice), id)
+	})
+}
+
+/// A collator of the live testnet, whose account is its validator id and aura key as well.
+fn live_collator(address: &str) -> Invulnerable {
+	let account = AccountId::from_ss58check(address).expect("static values are valid; qed");
+	let aura = sr25519::Public::from_raw(account.clone().into()).into();
+	(account.clone(), account, aura)
+}
+
+/// The genesis of the [`LIVE`] preset, without sudo: root is only reached through the collectives,
+/// whose only member is the account formerly used as sudo.
+pub fn live_genesis(id: ParaId) -> serde_json::Value {
+	let governor = AccountId::from_ss58check("5FPL3ZLqUk6MyBoZrQZ1Co29WAteX6T6N68TZ6jitHvhpyuD")
+		.expect("static values are valid; qed");
+	let invulnerables = vec![
+		// POP COLLATOR 0
+		live_collator("5Gn9dVgCNUYtC5JVMBheQQv2x6Lpg5sAMcQVRupG1s3tP2gR"),
+		// POP COLLATOR 1
+		live_collator("5FyVvcSvSXCkBwvBEHkUh1VWGGrwaR3zbYBkU3Rc5DqV75S4"),
+		// POP COLLATOR 2
+		live_collator("5GMqrQuWpyyBBK7LAWXR5psWvKc1QMqtiyasjp23VNKZWgh6"),
+	];
+	testnet_genesis(GenesisParams {
+		tech_committee: vec![governor.clone()],
+		council: vec![governor],
+		..GenesisParams::new(invulnerables, None, id)
+	})
+}
+
+/// The genesis config patch of the preset `id`, if any.
+pub fn get_preset(id: &PresetId) -> Option<Vec<u8>> {
+	let patch = match id.try_into() {
+		Ok(DEVELOPMENT) => development_genesis(PARA_ID.into()),
+		Ok(LOCAL_TESTNET) => local_testnet_genesis(PARA_ID.into()),
+		Ok(LIVE) => live_genesis(PARA_ID.into()),
+		_ => return None,
+	};
+	Some(
+		serde_json::to_string(&patch)
+			.expect("serialization to json is expected to work. qed.")
+			.into_bytes(),
+	)
+}
+
+/// The names of the presets.
+pub fn preset_names() -> Vec<PresetId> {
+	vec![PresetId::from(DEVELOPMENT), PresetId::from(LOCAL_TESTNET), PresetId::from(LIVE)]
+}
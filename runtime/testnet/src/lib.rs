@@ -1,6 +1,6 @@
 #![cfg_attr(not(feature = "std"), no_std)]
-// `construct_runtime!` does a lot of recursion and requires us to increase the limit to 256.
-#![recursion_limit = "256"]
+// `construct_runtime!` does a lot of recursion and requires us to increase the limit to 512.
+#![recursion_limit = "512"]
 
 // Make the WASM binary available.
 #[cfg(feature = "std")]
@@ -8,9 +8,18 @@ include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 mod config;
 mod extensions;
+pub mod genesis_config_presets;
+mod migrations;
+#[cfg(test)]
+mod tests;
 mod weights;
 
 use config::xcm::{RelayLocation, XcmOriginToTransactDispatchOrigin};
+pub use config::{
+	collation::ReportCollatorSetChanges,
+	contracts::{FloorContractCallFee, MinContractCallFee, WithMinContractCallFee},
+	fees::DealWithFees,
+};
 use cumulus_pallet_parachain_system::RelayNumberMonotonicallyIncreases;
 use cumulus_primitives_core::{AggregateMessageOrigin, ParaId};
 use frame_support::{
@@ -21,7 +30,7 @@ use frame_support::{
 	traits::{
 		fungible::HoldConsideration, tokens::nonfungibles_v2::Inspect, ConstBool, ConstU32,
 		ConstU64, ConstU8, Contains, EitherOfDiverse, EqualPrivilegeOnly, EverythingBut,
-		LinearStoragePrice, TransformOrigin, VariantCountOf,
+		InsideBoth, LinearStoragePrice, TransformOrigin, VariantCountOf, WithdrawReasons,
 	},
 	weights::{
 		ConstantMultiplier, Weight, WeightToFeeCoefficient, WeightToFeeCoefficients,
@@ -34,6 +43,9 @@ use frame_system::{
 	EnsureRoot,
 };
 use pallet_balances::Call as BalancesCall;
+pub use pallet_collator_rewards::RewardMode;
+pub use pallet_emergency_upgrade::upgrade_task_name;
+pub use pallet_weight_overrides::WithWeightMultiplier;
 use pallet_xcm::{EnsureXcm, IsVoiceOfBody};
 use parachains_common::message_queue::{NarrowOriginToSibling, ParaIdToSibling};
 use polkadot_runtime_common::xcm_sender::NoPriceForMessageDelivery;
@@ -42,8 +54,8 @@ use polkadot_runtime_common::{BlockHashCount, SlowAdjustingFeeUpdate};
 pub use pop_runtime_common::{
 	deposit, AuraId, Balance, BlockNumber, Hash, Nonce, Signature, AVERAGE_ON_INITIALIZE_RATIO,
 	BLOCK_PROCESSING_VELOCITY, DAYS, EXISTENTIAL_DEPOSIT, HOURS, MAXIMUM_BLOCK_WEIGHT, MICROUNIT,
-	MILLIUNIT, MINUTES, NORMAL_DISPATCH_RATIO, RELAY_CHAIN_SLOT_DURATION_MILLIS, SLOT_DURATION,
-	UNINCLUDED_SEGMENT_CAPACITY, UNIT,
+	MILLISECS_PER_BLOCK, MILLIUNIT, MINUTES, NORMAL_DISPATCH_RATIO,
+	RELAY_CHAIN_SLOT_DURATION_MILLIS, SLOT_DURATION, UNINCLUDED_SEGMENT_CAPACITY, UNIT,
 };
 use smallvec::smallvec;
 use sp_api::impl_runtime_apis;
@@ -52,7 +64,7 @@ use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 pub use sp_runtime::BuildStorage;
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
-	traits::{BlakeTwo256, Block as BlockT, IdentifyAccount, Verify},
+	traits::{BlakeTwo256, Block as BlockT, ConvertInto, IdentifyAccount, Verify},
 	transaction_validity::{TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult,
 };
@@ -92,10 +104,17 @@ pub type SignedExtra = (
 	frame_system::CheckGenesis<Runtime>,
 	frame_system::CheckEra<Runtime>,
 	frame_system::CheckNonce<Runtime>,
-	frame_system::CheckWeight<Runtime>,
-	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
-	cumulus_primitives_storage_weight_reclaim::StorageWeightReclaim<Runtime>,
+	// The signed extensions using the weight of calls, adjusted for their multiplier at once.
+	WithWeightMultiplier<
+		Runtime,
+		(
+			frame_system::CheckWeight<Runtime>,
+			FloorContractCallFee<pallet_asset_tx_payment::ChargeAssetTxPayment<Runtime>>,
+			cumulus_primitives_storage_weight_reclaim::StorageWeightReclaim<Runtime>,
+		),
+	>,
 	frame_metadata_hash_extension::CheckMetadataHash<Runtime>,
+	pallet_account_freeze::CheckNotFrozen<Runtime>,
 );
 
 /// Unchecked extrinsic type as expected by this runtime.
@@ -103,10 +122,20 @@ pub type UncheckedExtrinsic =
 	generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 
 /// Migrations to apply on runtime upgrade.
+///
+/// Any pallet added to the runtime or whose storage changes must register a migration here,
+/// e.g. [`migrations::InitStorageVersion`] for newly added pallets and [`migrations::MovePallet`]
+/// for renamed ones. Migrations should be versioned, i.e. only be applied to and bump a specific
+/// storage version of the pallet (see `frame_support::migrations::VersionedMigration`), and
+/// implement the `pre_upgrade` and `post_upgrade` checks run by `try-runtime`. They can be removed
+/// once applied on all live chains.
 pub type Migrations = (
 	pallet_contracts::Migration<Runtime>,
 	cumulus_pallet_xcmp_queue::migration::v5::MigrateV4ToV5<Runtime>,
 	pallet_xcm::migration::MigrateToLatestXcmVersion<Runtime>,
+	migrations::InitStorageVersion<TechnicalCommittee, RocksDbWeight>,
+	migrations::InitStorageVersion<Council, RocksDbWeight>,
+	migrations::InitStorageVersion<Identity, RocksDbWeight>,
 );
 
 /// Executive: handles dispatch to the various modules.
@@ -181,10 +210,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	impl_name: create_runtime_str!("pop"),
 	authoring_version: 1,
 	#[allow(clippy::zero_prefixed_literal)]
-	spec_version: 00_04_01,
+	spec_version: 00_04_15,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 4,
 	state_version: 1,
 };
 
@@ -221,6 +250,9 @@ parameter_types! {
 			weights.base_extrinsic = ExtrinsicBaseWeight::get();
 		})
 		.for_class(DispatchClass::Normal, |weights| {
+			// The `max_extrinsic` weight derived from this is enforced by `CheckWeight` when
+			// transactions are validated, so that transactions which could never fit in a block
+			// are rejected on submission with `InvalidTransaction::ExhaustsResources`.
 			weights.max_total = Some(NORMAL_DISPATCH_RATIO * MAXIMUM_BLOCK_WEIGHT);
 		})
 		.for_class(DispatchClass::Operational, |weights| {
@@ -254,10 +286,50 @@ impl Contains<RuntimeCall> for FilteredCalls {
 }
 
 /// A type to identify allowed calls to the Runtime from contracts. Used by Pop API
+///
+/// The calls allowed are the ones the Pop API encodes, so they must stay stable once allowed.
 pub struct AllowedApiCalls;
 impl Contains<RuntimeCall> for AllowedApiCalls {
-	fn contains(_c: &RuntimeCall) -> bool {
-		false
+	fn contains(c: &RuntimeCall) -> bool {
+		use pallet_nfts::Call as NftsCall;
+		matches!(
+			c,
+			RuntimeCall::Balances(BalancesCall::transfer_keep_alive { .. }) |
+				RuntimeCall::Nfts(
+					NftsCall::create { .. } |
+						NftsCall::destroy { .. } |
+						NftsCall::mint { .. } |
+						NftsCall::burn { .. } |
+						NftsCall::transfer { .. } |
+						NftsCall::redeposit { .. } |
+						NftsCall::lock_item_transfer { .. } |
+						NftsCall::unlock_item_transfer { .. } |
+						NftsCall::lock_collection { .. } |
+						NftsCall::transfer_ownership { .. } |
+						NftsCall::set_team { .. } |
+						NftsCall::approve_transfer { .. } |
+						NftsCall::cancel_approval { .. } |
+						NftsCall::clear_all_transfer_approvals { .. } |
+						NftsCall::lock_item_properties { .. } |
+						NftsCall::set_attribute { .. } |
+						NftsCall::clear_attribute { .. } |
+						NftsCall::approve_item_attributes { .. } |
+						NftsCall::cancel_item_attributes_approval { .. } |
+						NftsCall::set_metadata { .. } |
+						NftsCall::clear_metadata { .. } |
+						NftsCall::set_collection_metadata { .. } |
+						NftsCall::clear_collection_metadata { .. } |
+						NftsCall::set_accept_ownership { .. } |
+						NftsCall::set_collection_max_supply { .. } |
+						NftsCall::update_mint_settings { .. } |
+						NftsCall::set_price { .. } |
+						NftsCall::buy_item { .. } |
+						NftsCall::pay_tips { .. } |
+						NftsCall::create_swap { .. } |
+						NftsCall::cancel_swap { .. } |
+						NftsCall::claim_swap { .. }
+				) | RuntimeCall::XcmGateway(pallet_xcm_gateway::Call::send { .. })
+		)
 	}
 }
 
@@ -271,7 +343,10 @@ impl frame_system::Config for Runtime {
 	/// The identifier used to distinguish between accounts.
 	type AccountId = AccountId;
 	/// The basic call filter to use in dispatchable. Supports everything as the default.
-	type BaseCallFilter = EverythingBut<FilteredCalls>;
+	type BaseCallFilter = InsideBoth<
+		InsideBoth<InsideBoth<EverythingBut<FilteredCalls>, MaintenanceMode>, TransferFreeze>,
+		InsideBoth<SafeMode, TxPause>,
+	>;
 	/// The block type.
 	type Block = Block;
 	/// Maximum number of block number to block hash mappings to keep (oldest pruned first).
@@ -330,6 +405,26 @@ impl pallet_balances::Config for Runtime {
 	type WeightInfo = pallet_balances::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+	// Every schedule takes storage and a transfer can add one to any account, up to its limit.
+	pub const MinVestedTransfer: Balance = UNIT;
+	pub UnvestedFundsAllowedWithdrawReasons: WithdrawReasons =
+		WithdrawReasons::except(WithdrawReasons::TRANSFER | WithdrawReasons::RESERVE);
+}
+
+impl pallet_vesting::Config for Runtime {
+	// Schedules start and unlock per parachain block.
+	type BlockNumberProvider = System;
+	type BlockNumberToBalance = ConvertInto;
+	type Currency = Balances;
+	type MinVestedTransfer = MinVestedTransfer;
+	type RuntimeEvent = RuntimeEvent;
+	type UnvestedFundsAllowedWithdrawReasons = UnvestedFundsAllowedWithdrawReasons;
+	type WeightInfo = pallet_vesting::weights::SubstrateWeight<Runtime>;
+
+	const MAX_VESTING_SCHEDULES: u32 = 28;
+}
+
 parameter_types! {
 	/// Relay Chain `TransactionByteFee` / 10
 	pub const TransactionByteFee: Balance = 10 * MICROUNIT;
@@ -338,12 +433,23 @@ parameter_types! {
 impl pallet_transaction_payment::Config for Runtime {
 	type FeeMultiplierUpdate = SlowAdjustingFeeUpdate<Self>;
 	type LengthToFee = ConstantMultiplier<Balance, TransactionByteFee>;
-	type OnChargeTransaction = pallet_transaction_payment::FungibleAdapter<Balances, ()>;
+	type OnChargeTransaction =
+		WithMinContractCallFee<pallet_transaction_payment::FungibleAdapter<Balances, DealWithFees>>;
 	type OperationalFeeMultiplier = ConstU8<5>;
 	type RuntimeEvent = RuntimeEvent;
 	type WeightToFee = WeightToFee;
 }
 
+impl pallet_asset_tx_payment::Config for Runtime {
+	type Fungibles = Assets;
+	// Fees can be paid in the assets given a rate by governance, converted from the native fees
+	// by that rate, and are burnt, as collators are only rewarded with native fees.
+	type OnChargeAssetTransaction = WithMinContractCallFee<
+		pallet_asset_tx_payment::FungiblesAdapter<config::fees::FeeAssetRate, ()>,
+	>;
+	type RuntimeEvent = RuntimeEvent;
+}
+
 impl pallet_sudo::Config for Runtime {
 	type RuntimeCall = RuntimeCall;
 	type RuntimeEvent = RuntimeEvent;
@@ -367,7 +473,7 @@ impl cumulus_pallet_parachain_system::Config for Runtime {
 	type CheckAssociatedRelayNumber = RelayNumberMonotonicallyIncreases;
 	type ConsensusHook = ConsensusHook;
 	type DmpQueue = frame_support::traits::EnqueueWithOrigin<MessageQueue, RelayOrigin>;
-	type OnSystemEvent = ();
+	type OnSystemEvent = RelayState;
 	type OutboundXcmpMessageSource = XcmpQueue;
 	type ReservedDmpWeight = ReservedDmpWeight;
 	type ReservedXcmpWeight = ReservedXcmpWeight;
@@ -380,12 +486,15 @@ impl cumulus_pallet_parachain_system::Config for Runtime {
 impl parachain_info::Config for Runtime {}
 
 parameter_types! {
-	pub MessageQueueServiceWeight: Weight = Perbill::from_percent(35) * RuntimeBlockWeights::get().max_block;
+	// Inbound messages are serviced before any transaction, with at most a quarter of the block
+	// to leave room for contract calls, and further with the weight left unused by the block.
+	pub MessageQueueServiceWeight: Weight = Perbill::from_percent(25) * RuntimeBlockWeights::get().max_block;
+	pub MessageQueueIdleServiceWeight: Weight = Perbill::from_percent(35) * RuntimeBlockWeights::get().max_block;
 }
 
 impl pallet_message_queue::Config for Runtime {
 	type HeapSize = sp_core::ConstU32<{ 103 * 1024 }>;
-	type IdleMaxServiceWeight = ();
+	type IdleMaxServiceWeight = MessageQueueIdleServiceWeight;
 	type MaxStale = sp_core::ConstU32<8>;
 	#[cfg(feature = "runtime-benchmarks")]
 	type MessageProcessor = pallet_message_queue::mock_helpers::NoopMessageProcessor<
@@ -430,9 +539,33 @@ impl cumulus_pallet_xcmp_queue::migration::v5::V5Config for Runtime {
 	type ChannelList = ParachainSystem;
 }
 
+/// The shortest sane wall-clock duration of a session, in milliseconds. Shorter sessions rotate
+/// the collators before candidates had a chance to author blocks.
+pub const MIN_SESSION_DURATION_MILLIS: u64 = 60 * 60 * 1_000;
+/// The longest sane wall-clock duration of a session, in milliseconds. Longer sessions delay
+/// changes of the collator set, and kicking idle candidates, for days.
+pub const MAX_SESSION_DURATION_MILLIS: u64 = 24 * 60 * 60 * 1_000;
+
+/// Whether sessions of `period` blocks last between [`MIN_SESSION_DURATION_MILLIS`] and
+/// [`MAX_SESSION_DURATION_MILLIS`] when a block is produced every `block_time` milliseconds.
+pub const fn is_sane_session_length(period: BlockNumber, block_time: u64) -> bool {
+	let duration = period as u64 * block_time;
+	MIN_SESSION_DURATION_MILLIS <= duration && duration <= MAX_SESSION_DURATION_MILLIS
+}
+
+/// The length of a session in blocks by default.
+const DEFAULT_PERIOD: BlockNumber = 6 * HOURS;
+const _: () = assert!(
+	is_sane_session_length(DEFAULT_PERIOD, MILLISECS_PER_BLOCK),
+	"the default session length does not suit the block time"
+);
+
 parameter_types! {
-	pub const Period: u32 = 6 * HOURS;
-	pub const Offset: u32 = 0;
+	// The length of a session in blocks, and the block at which the first session rotates. Only
+	// tuned by a runtime upgrade, so that the length is checked by `is_sane_session_length` for the
+	// block time as the runtime is built.
+	pub const Period: BlockNumber = DEFAULT_PERIOD;
+	pub const Offset: BlockNumber = 0;
 }
 
 impl pallet_session::Config for Runtime {
@@ -441,7 +574,7 @@ impl pallet_session::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	// Essentially just Aura, but let's be pedantic.
 	type SessionHandler = <SessionKeys as sp_runtime::traits::OpaqueKeys>::KeyTypeIdProviders;
-	type SessionManager = CollatorSelection;
+	type SessionManager = ReportCollatorSetChanges<CollatorSelection>;
 	type ShouldEndSession = pallet_session::PeriodicSessions<Period, Offset>;
 	type ValidatorId = <Self as frame_system::Config>::AccountId;
 	// we don't have stash and controller, thus we don't need the convert as well.
@@ -459,22 +592,29 @@ impl pallet_aura::Config for Runtime {
 
 parameter_types! {
 	pub const PotId: PalletId = PalletId(*b"PotStake");
+	// Candidates that have not authored a block within this many blocks are removed from the
+	// candidate set at the next session change. Invulnerables are never kicked. Should be a
+	// multiple of the session length or things will get inconsistent. Can be tuned by root via
+	// `system.set_storage` to match the expected collator SLA.
+	pub storage KickThreshold: BlockNumber = Period::get();
 	pub const SessionLength: BlockNumber = 6 * HOURS;
+	// The most candidates that can register, which bounds the desired candidates set at genesis.
+	pub const MaxCandidates: u32 = 100;
 	// StakingAdmin pluralistic body.
 	pub const StakingAdminBodyId: BodyId = BodyId::Defense;
 }
 
-/// We allow root and the StakingAdmin to execute privileged collator selection operations.
+/// We allow governance, i.e. root or a majority of the council, and the StakingAdmin to execute
+/// privileged collator selection operations, e.g. setting the desired candidates and the bond.
 pub type CollatorSelectionUpdateOrigin = EitherOfDiverse<
-	EnsureRoot<AccountId>,
+	config::governance::GovernanceOrigin,
 	EnsureXcm<IsVoiceOfBody<RelayLocation, StakingAdminBodyId>>,
 >;
 
 impl pallet_collator_selection::Config for Runtime {
 	type Currency = Balances;
-	// should be a multiple of session or things will get inconsistent
-	type KickThreshold = Period;
-	type MaxCandidates = ConstU32<100>;
+	type KickThreshold = KickThreshold;
+	type MaxCandidates = MaxCandidates;
 	type MaxInvulnerables = ConstU32<20>;
 	type MinEligibleCollators = ConstU32<4>;
 	type PotId = PotId;
@@ -487,6 +627,8 @@ impl pallet_collator_selection::Config for Runtime {
 }
 
 parameter_types! {
+	// Leaves room in the block for the mandatory inherents and for servicing the agenda of the
+	// most tasks scheduled per block, e.g. enacted motions falling due in the same block.
 	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(60) *
 		RuntimeBlockWeights::get().max_block;
 }
@@ -503,7 +645,8 @@ impl pallet_scheduler::Config for Runtime {
 	type RuntimeCall = RuntimeCall;
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeOrigin = RuntimeOrigin;
-	type ScheduleOrigin = EnsureRoot<AccountId>;
+	// Governance schedules calls, e.g. to enact a motion after a delay.
+	type ScheduleOrigin = config::governance::GovernanceOrigin;
 	type WeightInfo = pallet_scheduler::weights::SubstrateWeight<Runtime>;
 }
 
@@ -521,7 +664,7 @@ impl pallet_preimage::Config for Runtime {
 		LinearStoragePrice<PreimageBaseDeposit, PreimageByteDeposit, Balance>,
 	>;
 	type Currency = Balances;
-	type ManagerOrigin = EnsureRoot<AccountId>;
+	type ManagerOrigin = config::governance::GovernanceOrigin;
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = pallet_preimage::weights::SubstrateWeight<Runtime>;
 }
@@ -551,6 +694,54 @@ impl pallet_utility::Config for Runtime {
 	type WeightInfo = pallet_utility::weights::SubstrateWeight<Runtime>;
 }
 
+/// The calls dispatched right away by the calls of `pallet_utility`, `pallet_proxy`,
+/// `pallet_multisig` and `pallet_sudo`, and the proposals the collectives execute or may execute
+/// as they are proposed. The calls scheduled, or proposed and then closed, are dispatched later on
+/// their own, outside of the transaction storing them, and are not included.
+pub struct DispatchedCalls;
+
+impl pallet_weight_overrides::InnerCalls<RuntimeCall> for DispatchedCalls {
+	fn inner_calls(call: &RuntimeCall) -> Vec<&RuntimeCall> {
+		use pallet_collective::Call as CollectiveCall;
+		use pallet_multisig::Call as MultisigCall;
+		use pallet_proxy::Call as ProxyCall;
+		use pallet_sudo::Call as SudoCall;
+		use pallet_utility::Call as UtilityCall;
+
+		match call {
+			RuntimeCall::Utility(
+				UtilityCall::batch { calls } |
+				UtilityCall::batch_all { calls } |
+				UtilityCall::force_batch { calls },
+			) => calls.iter().collect(),
+			RuntimeCall::Utility(
+				UtilityCall::as_derivative { call, .. } |
+				UtilityCall::dispatch_as { call, .. } |
+				UtilityCall::with_weight { call, .. },
+			) |
+			RuntimeCall::Proxy(
+				ProxyCall::proxy { call, .. } | ProxyCall::proxy_announced { call, .. },
+			) |
+			RuntimeCall::Multisig(
+				MultisigCall::as_multi { call, .. } |
+				MultisigCall::as_multi_threshold_1 { call, .. },
+			) |
+			RuntimeCall::Sudo(
+				SudoCall::sudo { call } |
+				SudoCall::sudo_unchecked_weight { call, .. } |
+				SudoCall::sudo_as { call, .. },
+			) => vec![call],
+			RuntimeCall::TechnicalCommittee(
+				CollectiveCall::execute { proposal, .. } | CollectiveCall::propose { proposal, .. },
+			) |
+			RuntimeCall::Council(
+				CollectiveCall::execute { proposal, .. } | CollectiveCall::propose { proposal, .. },
+			) => vec![proposal],
+			_ => vec![],
+		}
+	}
+}
+
 #[frame_support::runtime]
 mod runtime {
 	// Create the runtime by composing the FRAME pallets that were previously configured.
@@ -577,16 +768,46 @@ mod runtime {
 	pub type Timestamp = pallet_timestamp::Pallet<Runtime>;
 	#[runtime::pallet_index(3)]
 	pub type ParachainInfo = parachain_info::Pallet<Runtime>;
+	#[runtime::pallet_index(4)]
+	pub type RelayState = pallet_relay_state::Pallet<Runtime>;
 
 	// Monetary stuff.
 	#[runtime::pallet_index(10)]
 	pub type Balances = pallet_balances::Pallet<Runtime>;
 	#[runtime::pallet_index(11)]
 	pub type TransactionPayment = pallet_transaction_payment::Pallet<Runtime>;
+	#[runtime::pallet_index(12)]
+	pub type AssetTxPayment = pallet_asset_tx_payment::Pallet<Runtime>;
+	// Declared after `Balances`, whose genesis endows the accounts it locks the vesting funds of.
+	// Numbered below the monetary pallets, as the indices following them are taken.
+	#[runtime::pallet_index(7)]
+	pub type Vesting = pallet_vesting;
+	#[runtime::pallet_index(6)]
+	pub type FeeAssets = pallet_fee_assets::Pallet<Runtime>;
 
 	// Governance
+	#[runtime::pallet_index(14)]
+	pub type TransferFreeze = pallet_transfer_freeze::Pallet<Runtime>;
 	#[runtime::pallet_index(15)]
 	pub type Sudo = pallet_sudo;
+	#[runtime::pallet_index(16)]
+	pub type TechnicalCommittee = pallet_collective::Pallet<Runtime, Instance1>;
+	#[runtime::pallet_index(17)]
+	pub type EmergencyUpgrade = pallet_emergency_upgrade::Pallet<Runtime>;
+	#[runtime::pallet_index(18)]
+	pub type WeightOverrides = pallet_weight_overrides::Pallet<Runtime>;
+	#[runtime::pallet_index(19)]
+	pub type MaintenanceMode = pallet_maintenance_mode::Pallet<Runtime>;
+	// Numbered below the governance pallets, as the indices following them are taken.
+	#[runtime::pallet_index(9)]
+	pub type AccountFreeze = pallet_account_freeze::Pallet<Runtime>;
+	#[runtime::pallet_index(8)]
+	pub type Council = pallet_collective::Pallet<Runtime, Instance2>;
+	// Numbered after the XCM pallets, as the indices around the governance pallets are taken.
+	#[runtime::pallet_index(36)]
+	pub type SafeMode = pallet_safe_mode;
+	#[runtime::pallet_index(37)]
+	pub type TxPause = pallet_tx_pause;
 
 	// Collator support. The order of these 4 are important and shall not change.
 	#[runtime::pallet_index(20)]
@@ -599,6 +820,15 @@ mod runtime {
 	pub type Aura = pallet_aura::Pallet<Runtime>;
 	#[runtime::pallet_index(24)]
 	pub type AuraExt = cumulus_pallet_aura_ext;
+	#[runtime::pallet_index(25)]
+	pub type CollatorRewards = pallet_collator_rewards::Pallet<Runtime>;
+	#[runtime::pallet_index(26)]
+	pub type CollatorSet = pallet_collator_set::Pallet<Runtime>;
+	#[runtime::pallet_index(27)]
+	pub type ForcedCandidates = pallet_forced_candidates::Pallet<Runtime>;
+	// Numbered after the XCM helpers, as the indices following the collator support are taken.
+	#[runtime::pallet_index(34)]
+	pub type CandidacyBond = pallet_candidacy_bond::Pallet<Runtime>;
 
 	// Scheduler
 	#[runtime::pallet_index(28)]
@@ -617,10 +847,22 @@ mod runtime {
 	pub type CumulusXcm = cumulus_pallet_xcm::Pallet<Runtime>;
 	#[runtime::pallet_index(33)]
 	pub type MessageQueue = pallet_message_queue::Pallet<Runtime>;
+	#[runtime::pallet_index(35)]
+	pub type XcmGateway = pallet_xcm_gateway::Pallet<Runtime>;
 
 	// Contracts
 	#[runtime::pallet_index(40)]
 	pub type Contracts = pallet_contracts::Pallet<Runtime>;
+	#[runtime::pallet_index(44)]
+	pub type ContractsGenesis = pallet_genesis_codes::Pallet<Runtime>;
+	#[runtime::pallet_index(45)]
+	pub type ContractsChunkedUploads = pallet_chunked_uploads::Pallet<Runtime>;
+	#[runtime::pallet_index(46)]
+	pub type ContractsMetadata = pallet_contract_metadata::Pallet<Runtime>;
+	#[runtime::pallet_index(47)]
+	pub type ContractsUploadPolicy = pallet_upload_policy::Pallet<Runtime>;
+	#[runtime::pallet_index(48)]
+	pub type ContractsScheduler = pallet_contract_scheduler::Pallet<Runtime>;
 
 	// Proxy
 	#[runtime::pallet_index(41)]
@@ -639,6 +881,22 @@ mod runtime {
 	pub type NftFractionalization = pallet_nft_fractionalization::Pallet<Runtime>;
 	#[runtime::pallet_index(52)]
 	pub type Assets = pallet_assets::Pallet<Runtime, Instance1>;
+
+	// Identity
+	#[runtime::pallet_index(60)]
+	pub type Identity = pallet_identity::Pallet<Runtime>;
+	#[runtime::pallet_index(61)]
+	pub type IdentityGenesis = pallet_genesis_registrars::Pallet<Runtime>;
+
+	// Treasury
+	#[runtime::pallet_index(70)]
+	pub type Treasury = pallet_treasury::Pallet<Runtime>;
+	#[runtime::pallet_index(71)]
+	pub type FeeSplit = pallet_fee_split::Pallet<Runtime>;
+
+	// Offchain workers
+	#[runtime::pallet_index(80)]
+	pub type PriceFeed = pallet_price_feed::Pallet<Runtime>;
 }
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -646,6 +904,7 @@ mod benches {
 	frame_benchmarking::define_benchmarks!(
 		[frame_system, SystemBench::<Runtime>]
 		[pallet_balances, Balances]
+		[pallet_vesting, Vesting]
 		[pallet_session, SessionBench::<Runtime>]
 		[pallet_timestamp, Timestamp]
 		[pallet_message_queue, MessageQueue]
@@ -653,6 +912,38 @@ mod benches {
 		[pallet_collator_selection, CollatorSelection]
 		[cumulus_pallet_parachain_system, ParachainSystem]
 		[cumulus_pallet_xcmp_queue, XcmpQueue]
+		[pallet_collective, TechnicalCommittee]
+		[pallet_collective, Council]
+		[pallet_scheduler, Scheduler]
+		[pallet_preimage, Preimage]
+		[pallet_safe_mode, SafeMode]
+		[pallet_tx_pause, TxPause]
+		[pallet_xcm, PalletXcmExtrinsicsBenchmark::<Runtime>]
+		[pallet_contracts, Contracts]
+		[pallet_proxy, Proxy]
+		[pallet_multisig, Multisig]
+		[pallet_utility, Utility]
+		[pallet_nfts, Nfts]
+		[pallet_nft_fractionalization, NftFractionalization]
+		[pallet_assets, Assets]
+		[pallet_identity, Identity]
+		[pallet_treasury, Treasury]
+		[pallet_maintenance_mode, MaintenanceMode]
+		[pallet_transfer_freeze, TransferFreeze]
+		[pallet_account_freeze, AccountFreeze]
+		[pallet_emergency_upgrade, EmergencyUpgrade]
+		[pallet_weight_overrides, WeightOverrides]
+		[pallet_fee_assets, FeeAssets]
+		[pallet_fee_split, FeeSplit]
+		[pallet_collator_rewards, CollatorRewards]
+		[pallet_forced_candidates, ForcedCandidates]
+		[pallet_candidacy_bond, CandidacyBond]
+		[pallet_xcm_gateway, XcmGateway]
+		[pallet_chunked_uploads, ContractsChunkedUploads]
+		[pallet_contract_metadata, ContractsMetadata]
+		[pallet_upload_policy, ContractsUploadPolicy]
+		[pallet_contract_scheduler, ContractsScheduler]
+		[pallet_price_feed, PriceFeed]
 	);
 }
 
@@ -871,6 +1162,263 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pop_runtime_common::apis::NextAuthorApi<Block, AccountId> for Runtime {
+		fn next_author() -> Option<AccountId> {
+			let authorities = pallet_aura::Authorities::<Runtime>::get();
+			if authorities.is_empty() {
+				return None;
+			}
+			// Aura assigns slots to authorities in a round-robin fashion.
+			let slot = *pallet_aura::CurrentSlot::<Runtime>::get() + 1;
+			let author = &authorities[(slot % authorities.len() as u64) as usize];
+			Session::key_owner(sp_core::crypto::key_types::AURA, author.as_ref())
+		}
+	}
+
+	impl pop_runtime_common::apis::UpgradeStatusApi<Block, Hash> for Runtime {
+		fn upgrade_status() -> pop_runtime_common::apis::UpgradeStatus<Hash> {
+			pop_runtime_common::apis::upgrade_status()
+		}
+	}
+
+	impl pop_runtime_common::apis::FeeApi<Block> for Runtime {
+		fn current_multiplier() -> sp_runtime::FixedU128 {
+			pallet_transaction_payment::NextFeeMultiplier::<Runtime>::get()
+		}
+	}
+
+	impl pop_runtime_common::apis::AssetFeeApi<Block, pop_runtime_common::AssetId, Balance>
+		for Runtime
+	{
+		fn fee_assets() -> Vec<(pop_runtime_common::AssetId, sp_runtime::FixedU128)> {
+			pallet_fee_assets::Rates::<Runtime>::iter().collect()
+		}
+
+		fn fee_in_asset(fee: Balance, asset: pop_runtime_common::AssetId) -> Option<Balance> {
+			use frame_support::traits::tokens::ConversionToAssetBalance;
+			let converted = config::fees::FeeAssetRate::to_asset_balance(fee, asset).ok()?;
+			// Non-zero fees are charged at least one unit of the asset, as by
+			// `pallet_asset_tx_payment`.
+			Some(if fee == 0 { converted } else { converted.max(1) })
+		}
+	}
+
+	impl pop_runtime_common::apis::BlockUsageApi<Block> for Runtime {
+		fn block_usage() -> pop_runtime_common::apis::BlockUsage {
+			pop_runtime_common::apis::block_usage::<Runtime>(pop_runtime_common::apis::contract_calls::<Runtime>())
+		}
+	}
+
+	impl pop_runtime_common::apis::CollatorRegistrationApi<Block, AccountId, Balance> for Runtime {
+		fn can_register_collator(
+			account: AccountId,
+		) -> Result<(), pop_runtime_common::apis::CollatorIneligibility<Balance>> {
+			pop_runtime_common::apis::collator_eligibility::<Runtime>(&account)
+		}
+	}
+
+	impl pop_runtime_common::apis::CollatorSetApi<Block, AccountId, Balance> for Runtime {
+		fn collator_set() -> pop_runtime_common::apis::CollatorSet<AccountId, Balance> {
+			pop_runtime_common::apis::collator_set::<Runtime>(pallet_session::Validators::<Runtime>::get())
+		}
+	}
+
+	impl pop_runtime_common::apis::ProxyApi<Block, AccountId, pop_runtime_common::proxy::ProxyType>
+		for Runtime
+	{
+		fn proxies(account: AccountId) -> Vec<(AccountId, pop_runtime_common::proxy::ProxyType)> {
+			pop_runtime_common::apis::proxies::<Runtime>(&account)
+		}
+	}
+
+	impl pop_runtime_common::apis::XcmConversionApi<Block, AccountId> for Runtime {
+		fn location_to_account(location: xcm::VersionedLocation) -> Option<AccountId> {
+			pop_runtime_common::apis::location_to_account::<config::xcm::LocationToAccountId>(
+				location,
+			)
+		}
+	}
+
+	impl pop_runtime_common::apis::AccountConversionApi<Block, AccountId> for Runtime {
+		fn account_location(account: AccountId) -> xcm::VersionedLocation {
+			pop_runtime_common::apis::account_location(account)
+		}
+
+		fn sibling_account(para_id: u32) -> AccountId {
+			pop_runtime_common::apis::sibling_account(para_id)
+		}
+
+		fn relay_chain_account() -> AccountId {
+			pop_runtime_common::apis::relay_chain_account()
+		}
+
+		fn hashed_account(location: xcm::VersionedLocation) -> Option<AccountId> {
+			pop_runtime_common::apis::hashed_account(location)
+		}
+	}
+
+	impl pop_runtime_common::apis::XcmQueueApi<Block> for Runtime {
+		fn xcm_queue() -> pop_runtime_common::apis::XcmQueue {
+			pop_runtime_common::apis::xcm_queue()
+		}
+	}
+
+	impl pop_runtime_common::apis::InboundXcmQueueApi<Block> for Runtime {
+		fn inbound_xcm_queue() -> pop_runtime_common::apis::InboundXcmQueue {
+			pop_runtime_common::apis::inbound_xcm_queue()
+		}
+	}
+
+	impl pop_runtime_common::apis::RelayChainStateApi<Block> for Runtime {
+		fn relay_chain_state() -> Option<pop_runtime_common::apis::RelayChainState> {
+			RelayState::relay_chain_state()
+		}
+	}
+
+	impl pop_runtime_common::apis::XcmVersionApi<Block> for Runtime {
+		fn negotiated_version(dest: xcm::VersionedLocation) -> Option<u32> {
+			pop_runtime_common::apis::negotiated_xcm_version(dest)
+		}
+	}
+
+	impl pop_runtime_common::apis::SupplyApi<Block, Balance> for Runtime {
+		fn total_issuance() -> Balance {
+			Balances::total_issuance()
+		}
+
+		fn circulating_supply() -> Balance {
+			pop_runtime_common::apis::circulating_supply::<Runtime>(&[config::treasury::TreasuryAccount::get()])
+		}
+	}
+
+	impl pop_runtime_common::apis::RuntimeInfoApi<Block> for Runtime {
+		fn pallets() -> Vec<(u8, sp_runtime::RuntimeString)> {
+			pop_runtime_common::apis::pallets::<AllPalletsWithSystem>()
+		}
+	}
+
+	impl pop_runtime_common::apis::ContractTracingApi<Block, AccountId, Balance> for Runtime {
+		fn trace_call(
+			origin: AccountId,
+			dest: AccountId,
+			value: Balance,
+			input_data: Vec<u8>,
+		) -> Result<pop_runtime_common::apis::CallFrame<AccountId>, sp_runtime::DispatchError> {
+			pop_runtime_common::apis::trace_contract_call::<Runtime>(
+				origin,
+				dest,
+				value,
+				RuntimeBlockWeights::get().max_block,
+				input_data,
+			)
+		}
+	}
+
+	impl pop_runtime_common::apis::ContractDebugApi<Block, AccountId, Balance> for Runtime {
+		fn debug_call(
+			origin: AccountId,
+			dest: AccountId,
+			value: Balance,
+			input_data: Vec<u8>,
+		) -> Result<
+			pop_runtime_common::apis::DebuggedCall<AccountId, Balance>,
+			sp_runtime::DispatchError,
+		> {
+			pop_runtime_common::apis::debug_contract_call::<Runtime>(
+				origin,
+				dest,
+				value,
+				RuntimeBlockWeights::get().max_block,
+				input_data,
+			)
+		}
+	}
+
+	impl pop_runtime_common::apis::StorageDepositApi<Block, Balance> for Runtime {
+		fn deposit_for(bytes: u32, items: u32) -> Balance {
+			pop_runtime_common::apis::storage_deposit::<Runtime>(bytes, items)
+		}
+	}
+
+	impl pop_runtime_common::apis::ContractCostApi<Block, AccountId, Balance> for Runtime {
+		fn estimate_call_cost(
+			origin: AccountId,
+			dest: AccountId,
+			value: Balance,
+			input_data: Vec<u8>,
+		) -> Result<pop_runtime_common::apis::ContractCallCost<Balance>, sp_runtime::DispatchError> {
+			pop_runtime_common::apis::contract_call_cost::<Runtime>(
+				origin,
+				dest.clone(),
+				value,
+				RuntimeBlockWeights::get().max_block,
+				input_data,
+				|gas_limit, storage_deposit, data| {
+					let call = RuntimeCall::Contracts(pallet_contracts::Call::call {
+						dest: dest.into(),
+						value,
+						gas_limit,
+						storage_deposit_limit: Some(storage_deposit.into()),
+						data,
+					});
+					let len = codec::Encode::encoded_size(&call) as u32;
+					TransactionPayment::query_call_info(call, len)
+						.partial_fee
+						.max(MinContractCallFee::get())
+				},
+			)
+		}
+	}
+
+	impl pop_runtime_common::apis::ContractAddressApi<Block, AccountId, Hash> for Runtime {
+		fn contract_address(
+			deployer: AccountId,
+			code_hash: Hash,
+			input_data: Vec<u8>,
+			salt: Vec<u8>,
+		) -> AccountId {
+			Contracts::contract_address(&deployer, &code_hash, &input_data, &salt)
+		}
+	}
+
+	impl pop_runtime_common::apis::ContractInstantiationApi<Block, AccountId, Balance, Hash>
+		for Runtime
+	{
+		fn can_afford_instantiate(
+			account: AccountId,
+			code_hash: Hash,
+			endowment: Balance,
+		) -> Result<(), pop_runtime_common::apis::InstantiationUnaffordable<Balance>> {
+			// The fee of instantiating without any gas, i.e. excluding the execution of the
+			// constructor.
+			let call = RuntimeCall::Contracts(pallet_contracts::Call::instantiate {
+				value: endowment,
+				gas_limit: Weight::zero(),
+				storage_deposit_limit: None,
+				code_hash,
+				data: Vec::new(),
+				salt: Vec::new(),
+			});
+			let len = codec::Encode::encoded_size(&call) as u32;
+			let fee = TransactionPayment::query_call_info(call, len)
+				.partial_fee
+				.max(MinContractCallFee::get());
+			pop_runtime_common::apis::instantiation_affordability::<Runtime>(
+				&account, code_hash, endowment, fee,
+			)
+		}
+	}
+
+	impl pop_runtime_common::apis::ContractMetadataApi<Block, AccountId, Hash> for Runtime {
+		fn contract_metadata(
+			address: AccountId,
+		) -> Option<pop_runtime_common::apis::ContractMetadata<Hash>> {
+			let code_hash = Contracts::code_hash(&address)?;
+			let metadata = ContractsMetadata::metadata(code_hash);
+			Some(pop_runtime_common::apis::ContractMetadata { code_hash, metadata })
+		}
+	}
+
 	impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
 		fn collect_collation_info(header: &<Block as BlockT>::Header) -> cumulus_primitives_core::CollationInfo {
 			ParachainSystem::collect_collation_info(header)
@@ -950,6 +1498,7 @@ impl_runtime_apis! {
 			use frame_support::traits::StorageInfoTrait;
 			use frame_system_benchmarking::Pallet as SystemBench;
 			use cumulus_pallet_session_benchmarking::Pallet as SessionBench;
+			use pallet_xcm::benchmarking::Pallet as PalletXcmExtrinsicsBenchmark;
 
 			let mut list = Vec::<BenchmarkList>::new();
 			list_benchmarks!(list, extra);
@@ -978,6 +1527,37 @@ impl_runtime_apis! {
 			use cumulus_pallet_session_benchmarking::Pallet as SessionBench;
 			impl cumulus_pallet_session_benchmarking::Config for Runtime {}
 
+			use pallet_xcm::benchmarking::Pallet as PalletXcmExtrinsicsBenchmark;
+			use xcm::latest::prelude::{Asset, AssetId, Fungible, Location, Parent};
+			parameter_types! {
+				pub ExistentialDepositAsset: Option<Asset> =
+					Some((Parent, ExistentialDeposit::get()).into());
+			}
+			impl pallet_xcm::benchmarking::Config for Runtime {
+				type DeliveryHelper = cumulus_primitives_utility::ToParentDeliveryHelper<
+					config::xcm::XcmConfig,
+					ExistentialDepositAsset,
+					(),
+				>;
+
+				fn reachable_dest() -> Option<Location> {
+					Some(Parent.into())
+				}
+
+				// Assets are not teleported, the relay chain being the reserve of the native token.
+				fn teleportable_asset_and_dest() -> Option<(Asset, Location)> {
+					None
+				}
+
+				fn reserve_transferable_asset_and_dest() -> Option<(Asset, Location)> {
+					Some((Self::get_asset(), Parent.into()))
+				}
+
+				fn get_asset() -> Asset {
+					Asset { id: AssetId(Parent.into()), fun: Fungible(ExistentialDeposit::get()) }
+				}
+			}
+
 			use frame_support::traits::WhitelistedStorageKeys;
 			let whitelist = AllPalletsWithSystem::whitelisted_storage_keys();
 
@@ -996,11 +1576,11 @@ impl_runtime_apis! {
 		}
 
 		fn get_preset(id: &Option<sp_genesis_builder::PresetId>) -> Option<Vec<u8>> {
-			get_preset::<RuntimeGenesisConfig>(id, |_| None)
+			get_preset::<RuntimeGenesisConfig>(id, genesis_config_presets::get_preset)
 		}
 
 		fn preset_names() -> Vec<sp_genesis_builder::PresetId> {
-			Default::default()
+			genesis_config_presets::preset_names()
 		}
 	}
 }
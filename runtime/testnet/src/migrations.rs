@@ -0,0 +1,114 @@
+//! Runtime specific migrations, registered in [`crate::Migrations`].
+
+use core::marker::PhantomData;
+
+#[cfg(feature = "try-runtime")]
+use codec::{Decode, Encode};
+#[cfg(feature = "try-runtime")]
+use frame_support::ensure;
+use frame_support::{
+	storage::{migration::move_pallet, PrefixIterator},
+	traits::{Get, GetStorageVersion, OnRuntimeUpgrade, PalletInfoAccess, StorageVersion},
+	weights::{RuntimeDbWeight, Weight},
+};
+use sp_core::hashing::twox_128;
+#[cfg(feature = "try-runtime")]
+use sp_runtime::TryRuntimeError;
+use sp_std::vec::Vec;
+
+/// Initialises the on-chain storage version of pallet `P` to its in-code storage version.
+///
+/// The storage version of a pallet is only set when building the genesis, so pallets added to a
+/// live chain require this migration to be registered alongside them. Once set, the migration is
+/// a no-op.
+pub struct InitStorageVersion<P, DbWeight>(PhantomData<(P, DbWeight)>);
+
+impl<P, DbWeight> OnRuntimeUpgrade for InitStorageVersion<P, DbWeight>
+where
+	P: GetStorageVersion<InCodeStorageVersion = StorageVersion> + PalletInfoAccess,
+	DbWeight: Get<RuntimeDbWeight>,
+{
+	fn on_runtime_upgrade() -> Weight {
+		if P::on_chain_storage_version() != StorageVersion::default() {
+			return DbWeight::get().reads(1);
+		}
+		P::in_code_storage_version().put::<P>();
+		DbWeight::get().reads_writes(1, 1)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+		Ok(P::on_chain_storage_version().encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+		let before = StorageVersion::decode(&mut &state[..])
+			.map_err(|_| "Failed to decode the storage version before the upgrade")?;
+		let expected =
+			if before == StorageVersion::default() { P::in_code_storage_version() } else { before };
+		ensure!(P::on_chain_storage_version() == expected, "Unexpected on-chain storage version");
+		Ok(())
+	}
+}
+
+/// Moves the storage of pallet `P` from under the prefix of its former name `Old`, e.g. after
+/// renaming the pallet in `construct_runtime`.
+///
+/// Storage is prefixed by the name of the pallet rather than its index, so changing the index
+/// alone requires no migration. Once moved, or if there is nothing stored under the former name,
+/// the migration is a no-op.
+pub struct MovePallet<Old, P, DbWeight>(PhantomData<(Old, P, DbWeight)>);
+
+impl<Old, P, DbWeight> MovePallet<Old, P, DbWeight>
+where
+	Old: Get<&'static str>,
+	P: PalletInfoAccess,
+{
+	/// The raw keys, without their prefix, and values stored under the prefix of `name`.
+	fn storage(name: &str) -> Vec<(Vec<u8>, Vec<u8>)> {
+		let prefix = twox_128(name.as_bytes());
+		PrefixIterator::<_>::new(prefix.to_vec(), prefix.to_vec(), |key, value| {
+			Ok((key.to_vec(), value.to_vec()))
+		})
+		.collect()
+	}
+}
+
+impl<Old, P, DbWeight> OnRuntimeUpgrade for MovePallet<Old, P, DbWeight>
+where
+	Old: Get<&'static str>,
+	P: PalletInfoAccess,
+	DbWeight: Get<RuntimeDbWeight>,
+{
+	fn on_runtime_upgrade() -> Weight {
+		let items = Self::storage(Old::get()).len() as u64;
+		if items == 0 {
+			return DbWeight::get().reads(1);
+		}
+		move_pallet(Old::get().as_bytes(), P::name().as_bytes());
+		// Each item is read twice, then written under the new prefix and removed from the old.
+		DbWeight::get().reads_writes(2 * items, 2 * items)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+		let old = Self::storage(Old::get());
+		ensure!(
+			old.is_empty() || Self::storage(P::name()).is_empty(),
+			"Storage under both the former and the current name of the pallet"
+		);
+		Ok(old.encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+		let moved = Vec::<(Vec<u8>, Vec<u8>)>::decode(&mut &state[..])
+			.map_err(|_| "Failed to decode the storage before the upgrade")?;
+		ensure!(Self::storage(Old::get()).is_empty(), "Storage left under the former name");
+		if !moved.is_empty() {
+			ensure!(Self::storage(P::name()) == moved, "Storage not moved intact");
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,285 @@
+use pallet_collator_selection::{
+	CandidateInfo, CandidateList, DesiredCandidates, Invulnerables, LastAuthoredBlock,
+};
+use pallet_session::SessionManager;
+
+use super::*;
+
+#[test]
+fn kick_threshold_defaults_to_session_period() {
+	new_test_ext().execute_with(|| assert_eq!(KickThreshold::get(), Period::get()));
+}
+
+#[test]
+fn session_length_suits_block_time() {
+	assert!(is_sane_session_length(Period::get(), MILLISECS_PER_BLOCK));
+	// Sessions are tunable by a runtime upgrade, but tuning them is only sane within bounds.
+	assert!(is_sane_session_length(HOURS, MILLISECS_PER_BLOCK));
+	// A session of a few blocks.
+	assert!(!is_sane_session_length(10, MILLISECS_PER_BLOCK));
+	// A session length in blocks kept while blocks are produced twelve times slower.
+	assert!(!is_sane_session_length(DEFAULT_PERIOD * 12, MILLISECS_PER_BLOCK));
+	assert!(!is_sane_session_length(DEFAULT_PERIOD, 12 * MILLISECS_PER_BLOCK));
+}
+
+#[test]
+fn non_producing_candidate_is_kicked() {
+	new_test_ext().execute_with(|| {
+		let invulnerables: Vec<AccountId> = (1..=4).map(|i| AccountId::from([i; 32])).collect();
+		Invulnerables::<Runtime>::put(BoundedVec::try_from(invulnerables.clone()).unwrap());
+		let candidate = AccountId::from([5; 32]);
+		CandidateList::<Runtime>::put(
+			BoundedVec::try_from(vec![CandidateInfo { who: candidate.clone(), deposit: 0 }])
+				.unwrap(),
+		);
+		DesiredCandidates::<Runtime>::put(1);
+		LastAuthoredBlock::<Runtime>::insert(&candidate, 1);
+
+		// Still within the threshold: the candidate is kept.
+		System::set_block_number(KickThreshold::get());
+		assert!(CollatorSelection::new_session(1).unwrap().contains(&candidate));
+
+		// No block authored for `KickThreshold` blocks: the candidate is removed, whereas the
+		// (equally idle) invulnerables are exempt.
+		System::set_block_number(1 + KickThreshold::get());
+		let collators = CollatorSelection::new_session(2).unwrap();
+		assert!(!collators.contains(&candidate));
+		assert!(CandidateList::<Runtime>::get().is_empty());
+		assert_eq!(collators, invulnerables);
+	});
+}
+
+/// Charges a remark with a tip of the existential deposit, in the first block of a chain
+/// starting in reward `mode` with a single collator, returning the fee paid, the balance of
+/// the collator and the balance of the pot.
+fn reward_of_remark(mode: RewardMode) -> (Balance, Balance, Balance) {
+	use codec::Encode;
+	use frame_support::dispatch::{GetDispatchInfo, PostDispatchInfo};
+	use sp_runtime::{traits::SignedExtension, DigestItem};
+
+	let (who, collator) = (AccountId::from([1; 32]), AccountId::from([2; 32]));
+	let aura: AuraId = sp_core::sr25519::Public::from_raw([2; 32]).into();
+	let storage = RuntimeGenesisConfig {
+		balances: pallet_balances::GenesisConfig { balances: vec![(who.clone(), UNIT)] },
+		collator_rewards: pallet_collator_rewards::GenesisConfig { mode, ..Default::default() },
+		collator_selection: pallet_collator_selection::GenesisConfig {
+			invulnerables: vec![collator.clone()],
+			..Default::default()
+		},
+		session: pallet_session::GenesisConfig {
+			keys: vec![(collator.clone(), collator.clone(), SessionKeys { aura })],
+		},
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap();
+	let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+
+	sp_io::TestExternalities::new(storage).execute_with(|| {
+		System::deposit_log(DigestItem::PreRuntime(
+			sp_consensus_aura::AURA_ENGINE_ID,
+			0u64.encode(),
+		));
+		assert_eq!(Authorship::author(), Some(collator.clone()));
+		let (info, len) = (remark.get_dispatch_info(), remark.encoded_size());
+		let extension = pallet_asset_tx_payment::ChargeAssetTxPayment::<Runtime>::from(
+			EXISTENTIAL_DEPOSIT,
+			None,
+		);
+		let pre = extension.pre_dispatch(&who, &remark, &info, len).unwrap();
+		assert_ok!(pallet_asset_tx_payment::ChargeAssetTxPayment::<Runtime>::post_dispatch(
+			Some(pre),
+			&info,
+			&PostDispatchInfo::default(),
+			len,
+			&Ok(())
+		));
+		(
+			UNIT - Balances::free_balance(&who),
+			Balances::free_balance(&collator),
+			Balances::free_balance(CollatorSelection::account_id()),
+		)
+	})
+}
+
+#[test]
+fn immediate_payout_rewards_the_author() {
+	let (paid, author, pot) = reward_of_remark(RewardMode::ImmediatePayout);
+	assert!(paid > EXISTENTIAL_DEPOSIT);
+	assert_eq!((author, pot), (paid, 0));
+}
+
+#[test]
+fn pot_accumulation_rewards_the_pot() {
+	let (paid, author, pot) = reward_of_remark(RewardMode::PotAccumulation);
+	assert!(paid > EXISTENTIAL_DEPOSIT);
+	assert_eq!((author, pot), (0, paid));
+}
+
+#[test]
+fn next_author_is_one_of_the_invulnerables() {
+	use pop_runtime_common::apis::runtime_decl_for_next_author_api::NextAuthorApiV1;
+	use sp_consensus_aura::Slot;
+
+	let collators: Vec<(AccountId, AuraId)> = (1..=2)
+		.map(|i| (AccountId::from([i; 32]), sp_core::sr25519::Public::from_raw([i; 32]).into()))
+		.collect();
+	let invulnerables: Vec<_> = collators.iter().map(|(account, _)| account.clone()).collect();
+	let storage = RuntimeGenesisConfig {
+		collator_selection: pallet_collator_selection::GenesisConfig {
+			invulnerables: invulnerables.clone(),
+			..Default::default()
+		},
+		session: pallet_session::GenesisConfig {
+			keys: collators
+				.into_iter()
+				.map(|(account, aura)| (account.clone(), account, SessionKeys { aura }))
+				.collect(),
+		},
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap();
+
+	sp_io::TestExternalities::new(storage).execute_with(|| {
+		let authors: Vec<_> = (0..4)
+			.map(|slot| {
+				pallet_aura::CurrentSlot::<Runtime>::put(Slot::from(slot));
+				<Runtime as NextAuthorApiV1<Block, AccountId>>::next_author().unwrap()
+			})
+			.collect();
+		assert!(authors.iter().all(|author| invulnerables.contains(author)));
+		// Authors alternate between slots.
+		assert_ne!(authors[0], authors[1]);
+		assert_eq!(authors[0], authors[2]);
+		assert_eq!(authors[1], authors[3]);
+	});
+}
+
+#[test]
+fn collator_registration_is_previewed() {
+	use pop_runtime_common::apis::{
+		runtime_decl_for_collator_registration_api::CollatorRegistrationApiV1,
+		CollatorIneligibility::*,
+	};
+
+	let bond = 100 * UNIT;
+	let (rich, poor) = (AccountId::from([1; 32]), AccountId::from([2; 32]));
+	let new_ext = |desired_candidates| {
+		let storage = RuntimeGenesisConfig {
+			balances: pallet_balances::GenesisConfig {
+				balances: vec![(rich.clone(), 1_000 * UNIT), (poor.clone(), bond / 2)],
+			},
+			collator_selection: pallet_collator_selection::GenesisConfig {
+				candidacy_bond: bond,
+				desired_candidates,
+				..Default::default()
+			},
+			..Default::default()
+		}
+		.build_storage()
+		.unwrap();
+		let mut ext = sp_io::TestExternalities::new(storage);
+		ext.execute_with(|| {
+			System::set_block_number(1);
+			for (i, who) in [&rich, &poor].into_iter().enumerate() {
+				let aura = sp_core::sr25519::Public::from_raw([i as u8; 32]).into();
+				assert_ok!(Session::set_keys(
+					RuntimeOrigin::signed(who.clone()),
+					SessionKeys { aura },
+					vec![]
+				));
+			}
+		});
+		ext
+	};
+	let can_register = |who: &AccountId| {
+		<Runtime as CollatorRegistrationApiV1<Block, AccountId, Balance>>::can_register_collator(
+			who.clone(),
+		)
+	};
+
+	new_ext(1).execute_with(|| {
+		assert_eq!(can_register(&poor), Err(InsufficientBalance { bond, free: bond / 2 }));
+		assert!(
+			CollatorSelection::register_as_candidate(RuntimeOrigin::signed(poor.clone())).is_err()
+		);
+
+		assert_eq!(can_register(&rich), Ok(()));
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(rich.clone())));
+		assert_eq!(can_register(&rich), Err(AlreadyCandidate));
+	});
+	new_ext(0).execute_with(|| {
+		assert_eq!(can_register(&rich), Err(NoSlotsAvailable { candidates: 0, desired: 0 }));
+	});
+}
+
+#[test]
+fn council_opens_candidate_slots_reported_by_collator_set() {
+	use codec::Encode;
+	use pop_runtime_common::apis::{
+		runtime_decl_for_collator_set_api::CollatorSetApiV1, CollatorSet,
+	};
+
+	let invulnerable = AccountId::from([1; 32]);
+	let (member, candidate) = (AccountId::from([2; 32]), AccountId::from([3; 32]));
+	let bond = 100 * UNIT;
+	let storage = RuntimeGenesisConfig {
+		balances: pallet_balances::GenesisConfig {
+			balances: vec![(candidate.clone(), 1_000 * UNIT)],
+		},
+		collator_selection: pallet_collator_selection::GenesisConfig {
+			invulnerables: vec![invulnerable.clone()],
+			candidacy_bond: 16 * EXISTENTIAL_DEPOSIT,
+			desired_candidates: 0,
+		},
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap();
+
+	sp_io::TestExternalities::new(storage).execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			CollatorSelection::set_desired_candidates(RuntimeOrigin::signed(member.clone()), 1),
+			DispatchError::BadOrigin
+		);
+		// A motion of the council, whose only member is a majority, opens a candidate slot.
+		assert_ok!(Council::set_members(RuntimeOrigin::root(), vec![member.clone()], None, 0));
+		for call in [
+			RuntimeCall::CollatorSelection(
+				pallet_collator_selection::Call::set_desired_candidates { max: 1 },
+			),
+			RuntimeCall::CollatorSelection(pallet_collator_selection::Call::set_candidacy_bond {
+				bond,
+			}),
+		] {
+			let len = call.encoded_size() as u32;
+			assert_ok!(Council::propose(
+				RuntimeOrigin::signed(member.clone()),
+				1,
+				Box::new(call),
+				len
+			));
+		}
+		assert_ok!(Session::set_keys(
+			RuntimeOrigin::signed(candidate.clone()),
+			SessionKeys { aura: sp_core::sr25519::Public::from_raw([3; 32]).into() },
+			vec![]
+		));
+		assert_ok!(CollatorSelection::register_as_candidate(RuntimeOrigin::signed(
+			candidate.clone()
+		)));
+
+		assert_eq!(
+			<Runtime as CollatorSetApiV1<Block, AccountId, Balance>>::collator_set(),
+			CollatorSet {
+				collators: Session::validators(),
+				invulnerables: vec![invulnerable],
+				candidates: vec![(candidate, bond)],
+				desired_candidates: 1,
+				candidacy_bond: bond,
+			}
+		);
+	});
+}
@@ -0,0 +1,493 @@
+use super::*;
+
+#[test]
+fn contract_call_returns_debug_message() {
+	use pallet_contracts::{
+		runtime_decl_for_contracts_api::ContractsApiV2, CollectEvents, DebugInfo,
+	};
+
+	new_test_ext().execute_with(|| {
+		let alice = AccountId::from([1; 32]);
+		assert_ok!(Balances::force_set_balance(
+			RuntimeOrigin::root(),
+			alice.clone().into(),
+			1_000 * UNIT
+		));
+		let contract = Contracts::bare_instantiate(
+			alice.clone(),
+			0,
+			MAXIMUM_BLOCK_WEIGHT,
+			None,
+			pallet_contracts::Code::Upload(debug_message_contract()),
+			vec![],
+			vec![],
+			DebugInfo::Skip,
+			CollectEvents::Skip,
+		)
+		.result
+		.unwrap()
+		.account_id;
+
+		let result = <Runtime as ContractsApiV2<
+			Block,
+			AccountId,
+			Balance,
+			BlockNumber,
+			Hash,
+			EventRecord,
+		>>::call(alice, contract, 0, None, None, vec![]);
+		assert!(result.result.is_ok());
+		assert_eq!(result.debug_message, b"Hello Pop");
+	});
+}
+
+#[test]
+fn block_usage_counts_consumed_weight_and_contract_calls() {
+	use frame_support::dispatch::DispatchClass;
+	use pallet_contracts::{CollectEvents, DebugInfo};
+	use pop_runtime_common::apis::runtime_decl_for_block_usage_api::BlockUsageApiV1;
+
+	new_test_ext().execute_with(|| {
+		let alice = AccountId::from([1; 32]);
+		assert_ok!(Balances::force_set_balance(
+			RuntimeOrigin::root(),
+			alice.clone().into(),
+			1_000 * UNIT
+		));
+		let contract = Contracts::bare_instantiate(
+			alice.clone(),
+			0,
+			MAXIMUM_BLOCK_WEIGHT,
+			None,
+			pallet_contracts::Code::Upload(debug_message_contract()),
+			vec![],
+			vec![],
+			DebugInfo::Skip,
+			CollectEvents::Skip,
+		)
+		.result
+		.unwrap()
+		.account_id;
+		assert_eq!(Runtime::block_usage().contract_calls, 0);
+
+		for _ in 0..2 {
+			assert_ok!(Contracts::call(
+				RuntimeOrigin::signed(alice.clone()),
+				contract.clone().into(),
+				0,
+				Weight::from_parts(10_000_000_000, 1024 * 1024),
+				None,
+				vec![]
+			));
+		}
+		let (normal, operational) = (Weight::from_parts(3, 5), Weight::from_parts(7, 11));
+		System::register_extra_weight_unchecked(normal, DispatchClass::Normal);
+		System::register_extra_weight_unchecked(operational, DispatchClass::Operational);
+
+		let usage = Runtime::block_usage();
+		assert_eq!(usage.normal, normal);
+		assert_eq!(usage.operational, operational);
+		assert_eq!(usage.mandatory, Weight::zero());
+		assert_eq!(usage.pov_size, 16);
+		assert_eq!(usage.contract_calls, 2);
+	});
+}
+
+#[test]
+fn nested_contract_calls_are_traced() {
+	use codec::Encode;
+	use pallet_contracts::{CollectEvents, DebugInfo};
+	use pop_runtime_common::apis::{
+		runtime_decl_for_contract_tracing_api::ContractTracingApiV1, FrameKind, FrameOutcome,
+	};
+
+	new_test_ext().execute_with(|| {
+		let alice = AccountId::from([1; 32]);
+		assert_ok!(Balances::force_set_balance(
+			RuntimeOrigin::root(),
+			alice.clone().into(),
+			1_000 * UNIT
+		));
+		let instantiate = |code: &[u8]| {
+			Contracts::bare_instantiate(
+				alice.clone(),
+				0,
+				MAXIMUM_BLOCK_WEIGHT,
+				None,
+				pallet_contracts::Code::Upload(code.to_vec()),
+				vec![],
+				vec![],
+				DebugInfo::Skip,
+				CollectEvents::Skip,
+			)
+			.result
+			.unwrap()
+			.account_id
+		};
+		let callee = instantiate(&debug_message_contract());
+		let caller = instantiate(&caller_contract());
+
+		let trace = <Runtime as ContractTracingApiV1<Block, AccountId, Balance>>::trace_call(
+			alice.clone(),
+			caller.clone(),
+			0,
+			callee.encode(),
+		)
+		.unwrap();
+		assert_eq!(trace.contract, caller);
+		assert_eq!(trace.kind, FrameKind::Call);
+		assert_eq!(trace.outcome, FrameOutcome::Returned);
+		assert_eq!(trace.calls.len(), 1);
+		let nested = &trace.calls[0];
+		assert_eq!(nested.contract, callee);
+		assert_eq!(nested.kind, FrameKind::Call);
+		assert_eq!(nested.outcome, FrameOutcome::Returned);
+		assert!(nested.calls.is_empty());
+		assert!(nested.gas_consumed.unwrap().all_lt(trace.gas_consumed.unwrap()));
+
+		// Only existing contracts can be traced.
+		assert!(<Runtime as ContractTracingApiV1<Block, AccountId, Balance>>::trace_call(
+			alice.clone(),
+			alice,
+			0,
+			vec![]
+		)
+		.is_err());
+	});
+}
+
+#[test]
+fn contract_calls_are_debugged() {
+	use codec::Encode;
+	use pallet_contracts::{CollectEvents, DebugInfo, StorageDeposit};
+	use pop_runtime_common::apis::{
+		runtime_decl_for_contract_debug_api::ContractDebugApiV1, FrameOutcome,
+	};
+
+	new_test_ext().execute_with(|| {
+		let alice = AccountId::from([1; 32]);
+		assert_ok!(Balances::force_set_balance(
+			RuntimeOrigin::root(),
+			alice.clone().into(),
+			1_000 * UNIT
+		));
+		let instantiate = |code: &[u8]| {
+			Contracts::bare_instantiate(
+				alice.clone(),
+				0,
+				MAXIMUM_BLOCK_WEIGHT,
+				None,
+				pallet_contracts::Code::Upload(code.to_vec()),
+				vec![],
+				vec![],
+				DebugInfo::Skip,
+				CollectEvents::Skip,
+			)
+			.result
+			.unwrap()
+			.account_id
+		};
+		let callee = instantiate(&debug_message_contract());
+		let caller = instantiate(&caller_contract());
+
+		let debugged = <Runtime as ContractDebugApiV1<Block, AccountId, Balance>>::debug_call(
+			alice.clone(),
+			caller.clone(),
+			0,
+			callee.encode(),
+		)
+		.unwrap();
+		assert_eq!(debugged.trace.outcome, FrameOutcome::Returned);
+		assert_eq!(debugged.trace.calls[0].contract, callee);
+		// The debug message of the nested call is recorded.
+		assert_eq!(debugged.debug_message, b"Hello Pop");
+		// Neither contract stores anything or emits events.
+		assert!(debugged.events.is_empty());
+		assert_eq!(debugged.deposits.contract, caller);
+		assert_eq!(debugged.deposits.storage_deposit, Some(StorageDeposit::Charge(0)));
+		assert_eq!(debugged.deposits.calls.len(), 1);
+		assert_eq!(debugged.deposits.calls[0].contract, callee);
+		assert_eq!(debugged.deposits.calls[0].storage_deposit, Some(StorageDeposit::Charge(0)));
+
+		// Only existing contracts can be debugged.
+		assert!(<Runtime as ContractDebugApiV1<Block, AccountId, Balance>>::debug_call(
+			alice.clone(),
+			alice,
+			0,
+			vec![]
+		)
+		.is_err());
+	});
+}
+
+#[test]
+fn storage_deposit_is_computed_from_contract_deposits() {
+	use config::contracts::{DepositPerByte, DepositPerItem};
+	use pop_runtime_common::apis::runtime_decl_for_storage_deposit_api::StorageDepositApiV1;
+
+	assert_eq!(
+		<Runtime as StorageDepositApiV1<Block, Balance>>::deposit_for(1024, 1),
+		1024 * DepositPerByte::get() + DepositPerItem::get()
+	);
+	assert_eq!(<Runtime as StorageDepositApiV1<Block, Balance>>::deposit_for(0, 0), 0);
+}
+
+#[test]
+fn instantiation_is_unaffordable_with_insufficient_balance() {
+	use pallet_contracts::Determinism;
+	use pop_runtime_common::apis::{
+		runtime_decl_for_contract_instantiation_api::ContractInstantiationApiV1,
+		InstantiationUnaffordable::*,
+	};
+
+	let can_afford = |who: &AccountId, code_hash, endowment| {
+		<Runtime as ContractInstantiationApiV1<Block, AccountId, Balance, Hash>>::can_afford_instantiate(
+			who.clone(),
+			code_hash,
+			endowment,
+		)
+	};
+
+	new_test_ext().execute_with(|| {
+		let (rich, poor) = (AccountId::from([1; 32]), AccountId::from([2; 32]));
+		for (who, balance) in [(&rich, 1_000 * UNIT), (&poor, UNIT)] {
+			assert_ok!(Balances::force_set_balance(
+				RuntimeOrigin::root(),
+				who.clone().into(),
+				balance
+			));
+		}
+		let code_hash = Contracts::bare_upload_code(
+			rich.clone(),
+			debug_message_contract(),
+			None,
+			Determinism::Enforced,
+		)
+		.unwrap()
+		.code_hash;
+
+		assert_eq!(can_afford(&poor, Hash::repeat_byte(1), 0), Err(CodeNotFound));
+		assert_eq!(can_afford(&rich, code_hash, UNIT), Ok(()));
+		let Err(InsufficientBalance { cost, available }) = can_afford(&poor, code_hash, UNIT)
+		else {
+			panic!("the endowment alone exceeds the balance");
+		};
+		assert_eq!(available, UNIT - EXISTENTIAL_DEPOSIT);
+		assert!(cost > UNIT + EXISTENTIAL_DEPOSIT + MinContractCallFee::get());
+	});
+}
+
+#[test]
+fn contract_base_deposit_is_the_deposit_held_for_a_new_contract() {
+	use frame_support::traits::fungible::InspectHold;
+	use pallet_contracts::{CollectEvents, DebugInfo, Determinism};
+	use pop_runtime_common::apis::contract_base_deposit;
+
+	new_test_ext().execute_with(|| {
+		let alice = AccountId::from([1; 32]);
+		assert_ok!(Balances::force_set_balance(
+			RuntimeOrigin::root(),
+			alice.clone().into(),
+			1_000 * UNIT
+		));
+		assert_eq!(contract_base_deposit::<Runtime>(Hash::repeat_byte(1)), None);
+		let code_hash = Contracts::bare_upload_code(
+			alice.clone(),
+			debug_message_contract(),
+			None,
+			Determinism::Enforced,
+		)
+		.unwrap()
+		.code_hash;
+		// The constructor of the contract stores nothing, so that only the base deposit is held.
+		let contract = Contracts::bare_instantiate(
+			alice,
+			0,
+			MAXIMUM_BLOCK_WEIGHT,
+			None,
+			pallet_contracts::Code::Existing(code_hash),
+			vec![],
+			vec![],
+			DebugInfo::Skip,
+			CollectEvents::Skip,
+		)
+		.result
+		.unwrap()
+		.account_id;
+
+		assert_eq!(
+			contract_base_deposit::<Runtime>(code_hash),
+			Some(Balances::balance_on_hold(
+				&pallet_contracts::HoldReason::StorageDepositReserve.into(),
+				&contract
+			))
+		);
+	});
+}
+
+#[test]
+fn contract_call_cost_includes_fee_and_storage_deposit() {
+	use pallet_contracts::{CollectEvents, DebugInfo};
+	use pop_runtime_common::apis::runtime_decl_for_contract_cost_api::ContractCostApiV1;
+	use sp_runtime::traits::Zero;
+
+	new_test_ext().execute_with(|| {
+		let alice = AccountId::from([1; 32]);
+		assert_ok!(Balances::force_set_balance(
+			RuntimeOrigin::root(),
+			alice.clone().into(),
+			1_000 * UNIT
+		));
+		let contract = Contracts::bare_instantiate(
+			alice.clone(),
+			0,
+			MAXIMUM_BLOCK_WEIGHT,
+			None,
+			pallet_contracts::Code::Upload(debug_message_contract()),
+			vec![],
+			vec![],
+			DebugInfo::Skip,
+			CollectEvents::Skip,
+		)
+		.result
+		.unwrap()
+		.account_id;
+
+		let cost = <Runtime as ContractCostApiV1<Block, AccountId, Balance>>::estimate_call_cost(
+			alice.clone(),
+			contract,
+			0,
+			vec![],
+		)
+		.unwrap();
+		assert!(!cost.gas_required.is_zero());
+		assert!(cost.fee >= MinContractCallFee::get());
+		assert_eq!(cost.total, cost.fee + cost.storage_deposit);
+
+		// Only calls of existing contracts are estimated.
+		assert!(<Runtime as ContractCostApiV1<Block, AccountId, Balance>>::estimate_call_cost(
+			alice.clone(),
+			alice,
+			0,
+			vec![]
+		)
+		.is_err());
+	});
+}
+
+#[test]
+fn contract_metadata_is_stored_on_chain() {
+	use config::contracts::DepositPerByte;
+	use frame_support::traits::fungible::InspectHold;
+	use pallet_contracts::{CollectEvents, DebugInfo};
+	use pop_runtime_common::apis::{
+		runtime_decl_for_contract_metadata_api::ContractMetadataApiV1, ContractMetadata,
+	};
+
+	let contract_metadata = |address: &AccountId| {
+		<Runtime as ContractMetadataApiV1<Block, AccountId, Hash>>::contract_metadata(
+			address.clone(),
+		)
+	};
+	let abi = br#"{"spec":{"messages":[]}}"#.to_vec();
+
+	new_test_ext().execute_with(|| {
+		let (alice, bob) = (AccountId::from([1; 32]), AccountId::from([2; 32]));
+		for who in [&alice, &bob] {
+			assert_ok!(Balances::force_set_balance(
+				RuntimeOrigin::root(),
+				who.clone().into(),
+				1_000 * UNIT
+			));
+		}
+		let contract = Contracts::bare_instantiate(
+			alice.clone(),
+			0,
+			MAXIMUM_BLOCK_WEIGHT,
+			None,
+			pallet_contracts::Code::Upload(debug_message_contract()),
+			vec![],
+			vec![],
+			DebugInfo::Skip,
+			CollectEvents::Skip,
+		)
+		.result
+		.unwrap()
+		.account_id;
+		let code_hash = Contracts::code_hash(&contract).unwrap();
+		assert_eq!(
+			contract_metadata(&contract),
+			Some(ContractMetadata { code_hash, metadata: None })
+		);
+		assert_eq!(contract_metadata(&bob), None);
+
+		let metadata = BoundedVec::truncate_from(abi.clone());
+		let held = Balances::total_balance_on_hold(&alice);
+		assert_ok!(ContractsMetadata::set_metadata(
+			RuntimeOrigin::signed(alice.clone()),
+			code_hash,
+			metadata
+		));
+		assert_eq!(
+			contract_metadata(&contract),
+			Some(ContractMetadata { code_hash, metadata: Some(abi.clone()) })
+		);
+		assert_eq!(
+			Balances::total_balance_on_hold(&alice),
+			held + DepositPerByte::get() * abi.len() as Balance
+		);
+
+		assert_ok!(ContractsMetadata::clear_metadata(
+			RuntimeOrigin::signed(alice.clone()),
+			code_hash
+		));
+		assert_eq!(
+			contract_metadata(&contract),
+			Some(ContractMetadata { code_hash, metadata: None })
+		);
+		assert_eq!(Balances::total_balance_on_hold(&alice), held);
+	});
+}
+
+#[test]
+fn contract_uploads_follow_the_upload_policy() {
+	use pallet_contracts::Determinism;
+	use pallet_upload_policy::Policy;
+
+	let (uploader, other) = (AccountId::from([1; 32]), AccountId::from([2; 32]));
+	let new_ext = |policy| {
+		let storage = RuntimeGenesisConfig {
+			balances: pallet_balances::GenesisConfig {
+				balances: vec![(uploader.clone(), 1_000 * UNIT), (other.clone(), 1_000 * UNIT)],
+			},
+			contracts_upload_policy: pallet_upload_policy::GenesisConfig {
+				policy,
+				uploaders: vec![uploader.clone()],
+			},
+			..Default::default()
+		}
+		.build_storage()
+		.unwrap();
+		let mut ext = sp_io::TestExternalities::new(storage);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	};
+	let upload = |who: &AccountId| {
+		Contracts::upload_code(
+			RuntimeOrigin::signed(who.clone()),
+			debug_message_contract(),
+			None,
+			Determinism::Enforced,
+		)
+	};
+
+	// Anyone uploads codes on chains starting with permissionless uploads.
+	new_ext(Policy::Permissionless).execute_with(|| {
+		assert_ok!(upload(&other));
+	});
+
+	new_ext(Policy::Permissioned).execute_with(|| {
+		assert_noop!(upload(&other), DispatchError::BadOrigin);
+		assert_ok!(upload(&uploader));
+	});
+}
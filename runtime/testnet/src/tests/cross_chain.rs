@@ -0,0 +1,315 @@
+use super::*;
+
+#[test]
+fn sibling_location_converts_to_its_sovereign_account() {
+	use pop_runtime_common::apis::runtime_decl_for_xcm_conversion_api::XcmConversionApiV1;
+	use xcm::{latest::prelude::*, VersionedLocation};
+
+	let account = |location: Location| {
+		<Runtime as XcmConversionApiV1<Block, AccountId>>::location_to_account(
+			VersionedLocation::V4(location),
+		)
+	};
+	// `b"sibl"` followed by the encoded para id, padded with zeros.
+	let mut sibling = [0; 32];
+	sibling[..8].copy_from_slice(&[b's', b'i', b'b', b'l', 0xe8, 0x03, 0, 0]);
+	assert_eq!(account(Location::new(1, [Parachain(1000)])), Some(sibling.into()));
+	let mut parent = [0; 32];
+	parent[..6].copy_from_slice(b"Parent");
+	assert_eq!(account(Location::parent()), Some(parent.into()));
+	// Locations of other consensus systems have no local account.
+	assert_eq!(account(Location::new(2, [GlobalConsensus(Kusama)])), None);
+}
+
+#[test]
+fn accounts_convert_as_configured_for_xcm() {
+	use pallet_contracts::{CollectEvents, DebugInfo};
+	use pop_runtime_common::apis::{
+		runtime_decl_for_account_conversion_api::AccountConversionApiV1,
+		runtime_decl_for_contract_address_api::ContractAddressApiV1,
+		runtime_decl_for_xcm_conversion_api::XcmConversionApiV1,
+	};
+	use xcm::{latest::prelude::*, VersionedLocation};
+
+	let account = |location: Location| {
+		<Runtime as XcmConversionApiV1<Block, AccountId>>::location_to_account(
+			VersionedLocation::V4(location),
+		)
+	};
+	let alice = AccountId::from([1; 32]);
+
+	// Accounts are the local accounts of their locations.
+	let location =
+		<Runtime as AccountConversionApiV1<Block, AccountId>>::account_location(alice.clone());
+	assert_eq!(account(location.try_into().unwrap()), Some(alice.clone()));
+	assert_eq!(
+		Some(<Runtime as AccountConversionApiV1<Block, AccountId>>::sibling_account(1000)),
+		account(Location::new(1, [Parachain(1000)]))
+	);
+	assert_eq!(
+		Some(<Runtime as AccountConversionApiV1<Block, AccountId>>::relay_chain_account()),
+		account(Location::parent())
+	);
+	// The accounts of alice of different parachains, as derived by chains deriving accounts
+	// from the description of their locations.
+	let hashed = |para_id| {
+		<Runtime as AccountConversionApiV1<Block, AccountId>>::hashed_account(
+			VersionedLocation::V4(Location::new(
+				1,
+				[Parachain(para_id), AccountId32 { network: None, id: [1; 32] }],
+			)),
+		)
+		.unwrap()
+	};
+	assert_ne!(hashed(4001), alice);
+	assert_ne!(hashed(4001), hashed(4002));
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(Balances::force_set_balance(
+			RuntimeOrigin::root(),
+			alice.clone().into(),
+			1_000 * UNIT
+		));
+		let code_hash = <Runtime as frame_system::Config>::Hashing::hash(&debug_message_contract());
+		let address = <Runtime as ContractAddressApiV1<Block, AccountId, Hash>>::contract_address(
+			alice.clone(),
+			code_hash,
+			vec![],
+			vec![7],
+		);
+		let instantiated = Contracts::bare_instantiate(
+			alice.clone(),
+			0,
+			MAXIMUM_BLOCK_WEIGHT,
+			None,
+			pallet_contracts::Code::Upload(debug_message_contract()),
+			vec![],
+			vec![7],
+			DebugInfo::Skip,
+			CollectEvents::Skip,
+		)
+		.result
+		.unwrap()
+		.account_id;
+		assert_eq!(address, instantiated);
+	});
+}
+
+#[test]
+fn negotiated_xcm_version_is_reported_once_recorded() {
+	use pop_runtime_common::apis::runtime_decl_for_xcm_version_api::XcmVersionApiV1;
+	use xcm::{latest::prelude::*, VersionedLocation};
+
+	new_test_ext().execute_with(|| {
+		let version = <Runtime as XcmVersionApiV1<Block>>::negotiated_version;
+		let sibling = Location::new(1, [Parachain(1000)]);
+		assert_eq!(version(VersionedLocation::V4(sibling.clone())), None);
+
+		assert_ok!(PolkadotXcm::force_xcm_version(
+			RuntimeOrigin::root(),
+			Box::new(sibling.clone()),
+			3
+		));
+		assert_eq!(version(VersionedLocation::V4(sibling.clone())), Some(3));
+		// The destination may be given in any version of XCM.
+		let v3 = xcm::v3::Location::try_from(sibling).unwrap();
+		assert_eq!(version(VersionedLocation::V3(v3)), Some(3));
+		assert_eq!(version(VersionedLocation::V4(Location::parent())), None);
+	});
+}
+
+#[test]
+fn reserve_transfer_is_queued_for_the_relay_chain() {
+	use pop_runtime_common::apis::runtime_decl_for_xcm_queue_api::XcmQueueApiV1;
+	use xcm::latest::prelude::*;
+
+	let alice = AccountId::from([1; 32]);
+	let storage = RuntimeGenesisConfig {
+		balances: pallet_balances::GenesisConfig { balances: vec![(alice.clone(), 100 * UNIT)] },
+		// Messages are only sent to destinations whose XCM version is known.
+		polkadot_xcm: pallet_xcm::GenesisConfig {
+			safe_xcm_version: Some(XCM_VERSION),
+			..Default::default()
+		},
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap();
+
+	sp_io::TestExternalities::new(storage).execute_with(|| {
+		let queue = <Runtime as XcmQueueApiV1<Block>>::xcm_queue;
+		assert_eq!(queue(), Default::default());
+
+		assert_ok!(PolkadotXcm::limited_reserve_transfer_assets(
+			RuntimeOrigin::signed(alice),
+			Box::new(Parent.into()),
+			Box::new(AccountId32 { network: None, id: [2; 32] }.into()),
+			Box::new((Parent, 10 * UNIT).into()),
+			0,
+			Unlimited,
+		));
+		let queue = queue();
+		assert_eq!(queue.upward.count, 1);
+		assert!(queue.upward.bytes > 0);
+		assert!(queue.horizontal.is_empty());
+	});
+}
+
+#[test]
+fn inbound_messages_are_reported_until_processed() {
+	use frame_support::{
+		traits::{EnqueueMessage, ServiceQueues},
+		BoundedSlice,
+	};
+	use pop_runtime_common::apis::{
+		runtime_decl_for_inbound_xcm_queue_api::InboundXcmQueueApiV1, InboundMessages,
+	};
+
+	new_test_ext().execute_with(|| {
+		let queue = <Runtime as InboundXcmQueueApiV1<Block>>::inbound_xcm_queue;
+		assert_eq!(queue(), Default::default());
+
+		let message = [0u8; 32];
+		let enqueue = |count, origin| {
+			MessageQueue::enqueue_messages(
+				vec![BoundedSlice::truncate_from(&message[..]); count].into_iter(),
+				origin,
+			)
+		};
+		enqueue(1, AggregateMessageOrigin::Parent);
+		enqueue(2, AggregateMessageOrigin::Sibling(2_000.into()));
+		let inbound = queue();
+		assert_eq!(inbound.downward, InboundMessages { pages: 1, count: 1, bytes: 32 });
+		let [channel] = &inbound.horizontal[..] else { panic!("expected a single channel") };
+		assert_eq!((channel.sender, channel.suspended), (2_000, false));
+		assert_eq!(channel.queued, InboundMessages { pages: 1, count: 2, bytes: 64 });
+		assert!(!inbound.suspended);
+
+		// Processed messages are no longer queued, even if failing to decode.
+		MessageQueue::service_queues(Weight::MAX);
+		let inbound = queue();
+		assert_eq!(inbound.downward.count, 0);
+		assert!(inbound.horizontal.iter().all(|channel| channel.queued.count == 0));
+
+		// Channels suspended as their queue filled up are reported, as is the processing of
+		// XCMP messages being suspended.
+		cumulus_pallet_xcmp_queue::InboundXcmpSuspended::<Runtime>::mutate(|suspended| {
+			suspended.try_insert(2_001.into()).unwrap()
+		});
+		assert_ok!(XcmpQueue::suspend_xcm_execution(RuntimeOrigin::root()));
+		let inbound = queue();
+		assert_eq!(
+			inbound.horizontal.iter().map(|c| (c.sender, c.suspended)).collect::<Vec<_>>(),
+			vec![(2_000, false), (2_001, true)]
+		);
+		assert!(inbound.suspended);
+	});
+}
+
+#[test]
+fn assets_are_transacted_over_xcm() {
+	use config::xcm::AssetTransactors;
+	use xcm::latest::prelude::{AccountId32, Asset, GeneralIndex, Location, PalletInstance};
+	use xcm_executor::traits::TransactAsset;
+
+	new_test_ext().execute_with(|| {
+		let (alice, bob) = (AccountId::from([1; 32]), AccountId::from([2; 32]));
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 1.into(), alice.into(), true, 1));
+		let asset = |id| -> Asset {
+			(Location::new(0, [PalletInstance(52), GeneralIndex(id)]), 100u128).into()
+		};
+		let beneficiary = AccountId32 { network: None, id: bob.clone().into() }.into();
+
+		assert_ok!(AssetTransactors::deposit_asset(&asset(1), &beneficiary, None));
+		assert_eq!(Assets::balance(1, &bob), 100);
+		assert_ok!(AssetTransactors::withdraw_asset(&asset(1), &beneficiary, None));
+		assert_eq!(Assets::balance(1, &bob), 0);
+		// Assets which were not created are not transacted.
+		assert!(AssetTransactors::deposit_asset(&asset(2), &beneficiary, None).is_err());
+	});
+}
+
+#[test]
+fn contracts_send_xcm_to_allowed_destinations_via_the_gateway() {
+	use codec::Decode;
+	use config::xcm::RelayNetwork;
+	use pallet_contracts::{CollectEvents, DebugInfo};
+	use pallet_xcm_gateway::{Allowlist, Error, Event};
+	use xcm::{latest::prelude::*, VersionedLocation, VersionedXcm};
+
+	new_test_ext().execute_with(|| {
+		let alice = AccountId::from([1; 32]);
+		assert_ok!(Balances::force_set_balance(
+			RuntimeOrigin::root(),
+			alice.clone().into(),
+			1_000 * UNIT
+		));
+		let contract = Contracts::bare_instantiate(
+			alice.clone(),
+			0,
+			MAXIMUM_BLOCK_WEIGHT,
+			None,
+			pallet_contracts::Code::Upload(debug_message_contract()),
+			vec![],
+			vec![],
+			DebugInfo::Skip,
+			CollectEvents::Skip,
+		)
+		.result
+		.unwrap()
+		.account_id;
+		let send = |origin: &AccountId| {
+			XcmGateway::send(
+				RuntimeOrigin::signed(origin.clone()),
+				Box::new(Parent.into()),
+				Box::new(VersionedXcm::from(Xcm::<()>(vec![ClearOrigin]))),
+			)
+		};
+
+		// Only contracts send messages, and only to the destinations allowed by governance.
+		assert_noop!(send(&alice), Error::<Runtime>::NotContract);
+		assert_noop!(send(&contract), Error::<Runtime>::DestinationNotAllowed);
+		assert_noop!(
+			XcmGateway::allow_destination(
+				RuntimeOrigin::signed(alice.clone()),
+				contract.clone(),
+				Box::new(Parent.into()),
+			),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(XcmGateway::allow_destination(
+			RuntimeOrigin::root(),
+			contract.clone(),
+			Box::new(Parent.into()),
+		));
+		assert!(Allowlist::<Runtime>::contains_key(&contract, VersionedLocation::from(Parent)));
+
+		assert_ok!(send(&contract));
+		let messages = cumulus_pallet_parachain_system::PendingUpwardMessages::<Runtime>::get();
+		assert_eq!(messages.len(), 1);
+		let message: Xcm<()> =
+			VersionedXcm::<()>::decode(&mut &messages[0][..]).unwrap().try_into().unwrap();
+		assert_eq!(
+			message.0[..2],
+			[
+				DescendOrigin(
+					AccountId32 { network: RelayNetwork::get(), id: contract.clone().into() }
+						.into()
+				),
+				ClearOrigin,
+			]
+		);
+		assert!(System::events().iter().any(|record| matches!(
+			&record.event,
+			RuntimeEvent::XcmGateway(Event::Sent { contract: sender, fee: 0, .. })
+				if *sender == contract
+		)));
+
+		assert_ok!(XcmGateway::disallow_destination(
+			RuntimeOrigin::root(),
+			contract.clone(),
+			Box::new(Parent.into()),
+		));
+		assert_noop!(send(&contract), Error::<Runtime>::DestinationNotAllowed);
+	});
+}
@@ -0,0 +1,533 @@
+use super::*;
+
+#[test]
+fn fees_are_split_between_burn_treasury_and_collators() {
+	use config::treasury::TreasuryAccount;
+	use frame_support::traits::{fungible::Balanced, OnUnbalanced};
+	use pallet_fee_split::{Error, Shares};
+	use sp_runtime::Percent;
+
+	new_test_ext().execute_with(|| {
+		let shares = |burn, treasury| Shares {
+			burn: Percent::from_percent(burn),
+			treasury: Percent::from_percent(treasury),
+		};
+		assert_noop!(
+			FeeSplit::set_split(RuntimeOrigin::signed(AccountId::from([1; 32])), shares(20, 30)),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			FeeSplit::set_split(RuntimeOrigin::root(), shares(80, 30)),
+			Error::<Runtime>::InvalidSplit
+		);
+		assert_ok!(FeeSplit::set_split(RuntimeOrigin::root(), shares(20, 30)));
+
+		let issuance = Balances::total_issuance();
+		DealWithFees::on_unbalanced(<Balances as Balanced<AccountId>>::issue(100 * UNIT));
+		assert_eq!(Balances::total_issuance(), issuance + 80 * UNIT);
+		assert_eq!(Balances::free_balance(TreasuryAccount::get()), 30 * UNIT);
+		assert_eq!(Balances::free_balance(CollatorSelection::account_id()), 50 * UNIT);
+	});
+}
+
+#[test]
+fn fee_asset_rates_are_set_by_governance() {
+	use pallet_fee_assets::Error;
+	use pop_runtime_common::{apis::runtime_decl_for_asset_fee_api::AssetFeeApiV1, AssetId};
+	use sp_runtime::{traits::Zero, FixedU128};
+
+	const ASSET: AssetId = 1;
+	let half = FixedU128::from_rational(1, 2);
+	let fee_in_asset =
+		|fee| <Runtime as AssetFeeApiV1<Block, AssetId, Balance>>::fee_in_asset(fee, ASSET);
+	new_test_ext().execute_with(|| {
+		assert_eq!(fee_in_asset(1_000), None);
+		assert_noop!(
+			FeeAssets::set_rate(RuntimeOrigin::signed(AccountId::from([1; 32])), ASSET, Some(half)),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			FeeAssets::set_rate(RuntimeOrigin::root(), ASSET, Some(FixedU128::zero())),
+			Error::<Runtime>::ZeroRate
+		);
+
+		assert_ok!(FeeAssets::set_rate(RuntimeOrigin::root(), ASSET, Some(half)));
+		assert_eq!(
+			<Runtime as AssetFeeApiV1<Block, AssetId, Balance>>::fee_assets(),
+			vec![(ASSET, half)]
+		);
+		assert_eq!(fee_in_asset(1_000), Some(500));
+		// Non-zero fees are charged at least one unit of the asset.
+		assert_eq!(fee_in_asset(1), Some(1));
+		assert_eq!(fee_in_asset(0), Some(0));
+
+		assert_ok!(FeeAssets::set_rate(RuntimeOrigin::root(), ASSET, None));
+		assert_eq!(fee_in_asset(1_000), None);
+	});
+}
+
+#[test]
+fn transactions_exceeding_extrinsic_weight_limit_are_rejected() {
+	use codec::Encode;
+	use frame_support::dispatch::GetDispatchInfo;
+	use sp_core::{sr25519, Pair};
+	use sp_runtime::{
+		generic::{Era, SignedPayload},
+		transaction_validity::{InvalidTransaction, TransactionSource},
+	};
+
+	let signer = sr25519::Pair::from_seed(&[1; 32]);
+	let account = AccountId::from(signer.public());
+	let storage = RuntimeGenesisConfig {
+		balances: pallet_balances::GenesisConfig {
+			balances: vec![(account.clone(), 1_000 * UNIT)],
+		},
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap();
+	let contract_call = |gas_limit| {
+		RuntimeCall::Contracts(pallet_contracts::Call::call {
+			dest: MultiAddress::Id(AccountId::from([2; 32])),
+			value: 0,
+			gas_limit,
+			storage_deposit_limit: None,
+			data: vec![],
+		})
+	};
+	let sign = |call: RuntimeCall| {
+		let extra: SignedExtra = (
+			frame_system::CheckNonZeroSender::new(),
+			frame_system::CheckSpecVersion::new(),
+			frame_system::CheckTxVersion::new(),
+			frame_system::CheckGenesis::new(),
+			frame_system::CheckEra::from(Era::Immortal),
+			frame_system::CheckNonce::from(0),
+			(
+				frame_system::CheckWeight::new(),
+				FloorContractCallFee(pallet_asset_tx_payment::ChargeAssetTxPayment::from(0, None)),
+				cumulus_primitives_storage_weight_reclaim::StorageWeightReclaim::new(),
+			)
+				.into(),
+			frame_metadata_hash_extension::CheckMetadataHash::new(false),
+			pallet_account_freeze::CheckNotFrozen::new(),
+		);
+		let payload = SignedPayload::new(call.clone(), extra.clone()).unwrap();
+		let signature = payload.using_encoded(|payload| signer.sign(payload));
+		UncheckedExtrinsic::new_signed(
+			call,
+			MultiAddress::Id(account.clone()),
+			signature.into(),
+			extra,
+		)
+	};
+
+	sp_io::TestExternalities::new(storage).execute_with(|| {
+		let max_extrinsic =
+			RuntimeBlockWeights::get().get(DispatchClass::Normal).max_extrinsic.unwrap();
+		let validate = |call| {
+			Executive::validate_transaction(
+				TransactionSource::External,
+				sign(call),
+				frame_system::BlockHash::<Runtime>::get(0),
+			)
+		};
+
+		let within_limit = contract_call(Weight::from_parts(1_000_000, 1_000));
+		assert!(within_limit.get_dispatch_info().weight.all_lte(max_extrinsic));
+		assert_ok!(validate(within_limit));
+
+		// Declares more weight than available to a single extrinsic and can therefore never be
+		// included in a block.
+		let over_limit = contract_call(max_extrinsic);
+		assert!(over_limit.get_dispatch_info().weight.any_gt(max_extrinsic));
+		assert_eq!(validate(over_limit), Err(InvalidTransaction::ExhaustsResources.into()));
+
+		// A batch declares the summed weight of its calls, so calls fitting on their own are
+		// rejected once batched beyond the limit, rather than never being included.
+		let half = contract_call(max_extrinsic / 2);
+		assert!(half.get_dispatch_info().weight.all_lte(max_extrinsic));
+		let batch = RuntimeCall::Utility(pallet_utility::Call::batch_all { calls: vec![half; 2] });
+		assert!(batch.get_dispatch_info().weight.any_gt(max_extrinsic));
+		assert_eq!(validate(batch), Err(InvalidTransaction::ExhaustsResources.into()));
+	});
+}
+
+#[test]
+fn transaction_fees_can_be_paid_in_assets() {
+	use codec::Encode;
+	use frame_support::dispatch::GetDispatchInfo;
+	use sp_core::{sr25519, Pair};
+	use sp_runtime::{
+		generic::{Era, SignedPayload},
+		transaction_validity::{InvalidTransaction, TransactionValidityError},
+		FixedU128,
+	};
+
+	const ASSET: u32 = 1;
+	const UNLISTED: u32 = 2;
+	let signer = sr25519::Pair::from_seed(&[1; 32]);
+	let (account, dest) = (AccountId::from(signer.public()), AccountId::from([2; 32]));
+	let storage = RuntimeGenesisConfig {
+		balances: pallet_balances::GenesisConfig { balances: vec![(account.clone(), 10 * UNIT)] },
+		fee_assets: pallet_fee_assets::GenesisConfig {
+			rates: vec![(ASSET, FixedU128::from_u32(2))],
+		},
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap();
+	let transfer = RuntimeCall::Balances(BalancesCall::transfer_allow_death {
+		dest: MultiAddress::Id(dest.clone()),
+		value: UNIT,
+	});
+
+	sp_io::TestExternalities::new(storage).execute_with(|| {
+		System::set_block_number(1);
+		for asset in [ASSET, UNLISTED] {
+			assert_ok!(Assets::force_create(
+				RuntimeOrigin::root(),
+				asset.into(),
+				account.clone().into(),
+				true,
+				EXISTENTIAL_DEPOSIT
+			));
+			assert_ok!(Assets::mint(
+				RuntimeOrigin::signed(account.clone()),
+				asset.into(),
+				account.clone().into(),
+				UNIT
+			));
+		}
+
+		let signed = |asset| {
+			let extra: SignedExtra = (
+				frame_system::CheckNonZeroSender::new(),
+				frame_system::CheckSpecVersion::new(),
+				frame_system::CheckTxVersion::new(),
+				frame_system::CheckGenesis::new(),
+				frame_system::CheckEra::from(Era::Immortal),
+				frame_system::CheckNonce::from(0),
+				(
+					frame_system::CheckWeight::new(),
+					FloorContractCallFee(pallet_asset_tx_payment::ChargeAssetTxPayment::from(
+						0,
+						Some(asset),
+					)),
+					cumulus_primitives_storage_weight_reclaim::StorageWeightReclaim::new(),
+				)
+					.into(),
+				frame_metadata_hash_extension::CheckMetadataHash::new(false),
+				pallet_account_freeze::CheckNotFrozen::new(),
+			);
+			let payload = SignedPayload::new(transfer.clone(), extra.clone()).unwrap();
+			let signature = payload.using_encoded(|payload| signer.sign(payload));
+			UncheckedExtrinsic::new_signed(
+				transfer.clone(),
+				MultiAddress::Id(account.clone()),
+				signature.into(),
+				extra,
+			)
+		};
+
+		// Assets without a rate are refused, leaving no changes as when building a block.
+		sp_io::storage::start_transaction();
+		assert_eq!(
+			Executive::apply_extrinsic(signed(UNLISTED)),
+			Err(TransactionValidityError::Invalid(InvalidTransaction::Payment))
+		);
+		sp_io::storage::rollback_transaction();
+
+		// Fees are converted from the native fee by the rate of the asset.
+		let xt = signed(ASSET);
+		// The weight charged includes looking the weight multiplier of the transfer up.
+		let mut info = xt.get_dispatch_info();
+		info.weight = WeightOverrides::adjustment(&transfer).apply(info.weight);
+		let native_fee = TransactionPayment::compute_fee(xt.encoded_size() as u32, &info, 0);
+		assert_ok!(Executive::apply_extrinsic(xt).unwrap());
+		// Only the transferred amount is debited from the native balance.
+		assert_eq!(Balances::free_balance(&account), 9 * UNIT);
+		assert_eq!(Balances::free_balance(&dest), UNIT);
+		let fee = UNIT - Assets::balance(ASSET, &account);
+		assert_eq!(fee, 2 * native_fee);
+		assert_eq!(Assets::balance(UNLISTED, &account), UNIT);
+		System::assert_has_event(
+			pallet_asset_tx_payment::Event::AssetTxFeePaid {
+				who: account,
+				actual_fee: fee,
+				tip: 0,
+				asset_id: Some(ASSET),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn unused_proof_size_is_reclaimed() {
+	use codec::Encode;
+	use frame_support::dispatch::GetDispatchInfo;
+	use sp_core::{sr25519, Pair};
+	use sp_runtime::generic::{Era, SignedPayload};
+	use sp_trie::{proof_size_extension::ProofSizeExt, ProofSizeProvider};
+
+	/// Reports a proof growing by `PROOF_SIZE` bytes on every read, i.e. before and after the
+	/// dispatch of an extrinsic.
+	struct Recorder(std::sync::atomic::AtomicUsize);
+	const PROOF_SIZE: usize = 100;
+	impl ProofSizeProvider for Recorder {
+		fn estimate_encoded_size(&self) -> usize {
+			self.0.fetch_add(PROOF_SIZE, std::sync::atomic::Ordering::Relaxed)
+		}
+	}
+
+	let signer = sr25519::Pair::from_seed(&[1; 32]);
+	let account = AccountId::from(signer.public());
+	let storage = RuntimeGenesisConfig {
+		balances: pallet_balances::GenesisConfig { balances: vec![(account.clone(), 10 * UNIT)] },
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap();
+	let transfer = RuntimeCall::Balances(BalancesCall::transfer_allow_death {
+		dest: MultiAddress::Id(AccountId::from([2; 32])),
+		value: UNIT,
+	});
+	let reserved = transfer.get_dispatch_info().weight.proof_size();
+	assert!(reserved > PROOF_SIZE as u64);
+	let extra: SignedExtra = (
+		frame_system::CheckNonZeroSender::new(),
+		frame_system::CheckSpecVersion::new(),
+		frame_system::CheckTxVersion::new(),
+		frame_system::CheckGenesis::new(),
+		frame_system::CheckEra::from(Era::Immortal),
+		frame_system::CheckNonce::from(0),
+		(
+			frame_system::CheckWeight::new(),
+			FloorContractCallFee(pallet_asset_tx_payment::ChargeAssetTxPayment::from(0, None)),
+			cumulus_primitives_storage_weight_reclaim::StorageWeightReclaim::new(),
+		)
+			.into(),
+		frame_metadata_hash_extension::CheckMetadataHash::new(false),
+		pallet_account_freeze::CheckNotFrozen::new(),
+	);
+	// The proof size consumed by the block after applying the transfer.
+	let consumed = |reclaim: bool| {
+		let mut ext = sp_io::TestExternalities::new(storage.clone());
+		if reclaim {
+			ext.register_extension(ProofSizeExt::new(Recorder(Default::default())));
+		}
+		ext.execute_with(|| {
+			System::set_block_number(1);
+			let payload = SignedPayload::new(transfer.clone(), extra.clone()).unwrap();
+			let signature = payload.using_encoded(|payload| signer.sign(payload));
+			let xt = UncheckedExtrinsic::new_signed(
+				transfer.clone(),
+				MultiAddress::Id(account.clone()),
+				signature.into(),
+				extra.clone(),
+			);
+			assert_ok!(Executive::apply_extrinsic(xt).unwrap());
+			System::block_weight().total().proof_size()
+		})
+	};
+
+	// The proof size reserved by the transfer but not recorded while dispatching it is
+	// reclaimed, which is only possible when the node provides the size of the proof. So is
+	// that of looking its weight multiplier up, which is recorded before the dispatch.
+	let lookup =
+		new_test_ext().execute_with(|| WeightOverrides::adjustment(&transfer).lookup.proof_size());
+	assert_eq!(consumed(false) - consumed(true), reserved + lookup - PROOF_SIZE as u64);
+}
+
+#[test]
+fn fee_multiplier_decays_towards_minimum_on_quiet_chain() {
+	use frame_support::traits::OnFinalize;
+	use pop_runtime_common::apis::runtime_decl_for_fee_api::FeeApiV1;
+	use sp_runtime::FixedU128;
+
+	let minimum: FixedU128 = polkadot_runtime_common::MinimumMultiplier::get();
+	new_test_ext().execute_with(|| {
+		pallet_transaction_payment::NextFeeMultiplier::<Runtime>::put(FixedU128::from(2));
+		let mut previous = <Runtime as FeeApiV1<Block>>::current_multiplier();
+		assert_eq!(previous, FixedU128::from(2));
+		// Empty blocks are below the targeted block fullness.
+		for _ in 0..10 {
+			TransactionPayment::on_finalize(System::block_number());
+			let current = <Runtime as FeeApiV1<Block>>::current_multiplier();
+			assert!(current < previous);
+			assert!(current > minimum);
+			previous = current;
+		}
+
+		pallet_transaction_payment::NextFeeMultiplier::<Runtime>::put(minimum);
+		TransactionPayment::on_finalize(System::block_number());
+		assert_eq!(<Runtime as FeeApiV1<Block>>::current_multiplier(), minimum);
+	});
+}
+
+#[test]
+fn contract_calls_are_charged_at_least_the_minimum_fee() {
+	use codec::Encode;
+	use frame_support::dispatch::{GetDispatchInfo, PostDispatchInfo};
+	use sp_runtime::{traits::SignedExtension, FixedU128};
+
+	const ASSET: u32 = 1;
+	let who = AccountId::from([1; 32]);
+	let storage = RuntimeGenesisConfig {
+		balances: pallet_balances::GenesisConfig { balances: vec![(who.clone(), UNIT)] },
+		fee_assets: pallet_fee_assets::GenesisConfig {
+			rates: vec![(ASSET, FixedU128::from_u32(2))],
+		},
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap();
+	let contract_call = RuntimeCall::Contracts(pallet_contracts::Call::call {
+		dest: MultiAddress::Id(AccountId::from([2; 32])),
+		value: 0,
+		gas_limit: Weight::from_parts(1_000_000, 1_000),
+		storage_deposit_limit: None,
+		data: vec![],
+	});
+	let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+	let batch = RuntimeCall::Utility(pallet_utility::Call::batch {
+		calls: vec![remark.clone(), contract_call.clone()],
+	});
+	let proxied = RuntimeCall::Proxy(pallet_proxy::Call::proxy {
+		real: MultiAddress::Id(AccountId::from([3; 32])),
+		force_proxy_type: None,
+		call: Box::new(contract_call.clone()),
+	});
+
+	sp_io::TestExternalities::new(storage).execute_with(|| {
+		assert_ok!(Assets::force_create(
+			RuntimeOrigin::root(),
+			ASSET.into(),
+			who.clone().into(),
+			true,
+			EXISTENTIAL_DEPOSIT
+		));
+		assert_ok!(Assets::mint(
+			RuntimeOrigin::signed(who.clone()),
+			ASSET.into(),
+			who.clone().into(),
+			UNIT
+		));
+		let minimum: FixedU128 = polkadot_runtime_common::MinimumMultiplier::get();
+		pallet_transaction_payment::NextFeeMultiplier::<Runtime>::put(minimum);
+		// The fee computed for `call`, and the fee charged for it in `asset`, if any, or in the
+		// native token.
+		let fees = |call: &RuntimeCall, asset: Option<u32>| {
+			let (info, len) = (call.get_dispatch_info(), call.encoded_size());
+			let balance = || match asset {
+				Some(asset) => Assets::balance(asset, &who),
+				None => Balances::free_balance(&who),
+			};
+			let before = balance();
+			type Extension =
+				FloorContractCallFee<pallet_asset_tx_payment::ChargeAssetTxPayment<Runtime>>;
+			let extension: Extension =
+				FloorContractCallFee(pallet_asset_tx_payment::ChargeAssetTxPayment::from(0, asset));
+			let pre = extension.pre_dispatch(&who, call, &info, len).unwrap();
+			assert_ok!(Extension::post_dispatch(
+				Some(pre),
+				&info,
+				&PostDispatchInfo::default(),
+				len,
+				&Ok(())
+			));
+			(TransactionPayment::compute_fee(len as u32, &info, 0), before - balance())
+		};
+
+		let (computed, charged) = fees(&contract_call, None);
+		assert!(computed < MinContractCallFee::get());
+		assert_eq!(charged, MinContractCallFee::get());
+		// Other extrinsics are charged the computed fee.
+		let (computed, charged) = fees(&remark, None);
+		assert!(computed < MinContractCallFee::get());
+		assert_eq!(charged, computed);
+		// Contract calls dispatched via other calls are floored too.
+		for call in [&batch, &proxied] {
+			let (computed, charged) = fees(call, None);
+			assert!(computed < MinContractCallFee::get());
+			assert_eq!(charged, MinContractCallFee::get());
+		}
+		// Fees paid in assets are floored before being converted by the rate of the asset.
+		let (computed, charged) = fees(&contract_call, Some(ASSET));
+		assert!(computed < MinContractCallFee::get());
+		assert_eq!(charged, 2 * MinContractCallFee::get());
+		let (computed, charged) = fees(&remark, Some(ASSET));
+		assert_eq!(charged, 2 * computed);
+	});
+}
+
+#[test]
+fn weight_multiplier_multiplies_charged_weight() {
+	use frame_support::dispatch::GetDispatchInfo;
+	use pallet_weight_overrides::{Error, PalletName};
+	use sp_runtime::{traits::SignedExtension, FixedU64};
+
+	new_test_ext().execute_with(|| {
+		let who = AccountId::from([1; 32]);
+		let transfer = RuntimeCall::Balances(BalancesCall::transfer_allow_death {
+			dest: MultiAddress::Id(AccountId::from([2; 32])),
+			value: UNIT,
+		});
+		let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+		// The weight charged to the block by dispatching `call`.
+		let charged = |call: &RuntimeCall| {
+			let consumed = || frame_system::BlockWeight::<Runtime>::get().total();
+			let before = consumed();
+			let extension: WithWeightMultiplier<Runtime, _> =
+				frame_system::CheckWeight::<Runtime>::new().into();
+			assert_ok!(extension.pre_dispatch(&who, call, &call.get_dispatch_info(), 0));
+			consumed() - before
+		};
+		// A batch dispatching the transfer, which would otherwise escape its multiplier.
+		let batch = RuntimeCall::Utility(pallet_utility::Call::batch {
+			calls: vec![remark.clone(), transfer.clone()],
+		});
+		// A proposal of the technical committee executed right away by one of its members.
+		let execute = RuntimeCall::TechnicalCommittee(pallet_collective::Call::execute {
+			proposal: Box::new(transfer.clone()),
+			length_bound: codec::Encode::encoded_size(&transfer) as u32,
+		});
+		let (transfer_weight, remark_weight, batch_weight, execute_weight) =
+			(charged(&transfer), charged(&remark), charged(&batch), charged(&execute));
+
+		let balances = PalletName::try_from(b"Balances".to_vec()).unwrap();
+		for out_of_bounds in [FixedU64::from_rational(1, 2), FixedU64::from_u32(11)] {
+			assert_noop!(
+				WeightOverrides::set_weight_multiplier(
+					RuntimeOrigin::root(),
+					balances.clone(),
+					Some(out_of_bounds)
+				),
+				Error::<Runtime>::MultiplierOutOfBounds
+			);
+		}
+		assert_noop!(
+			WeightOverrides::set_weight_multiplier(
+				RuntimeOrigin::signed(who.clone()),
+				balances.clone(),
+				Some(FixedU64::from_u32(2))
+			),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(WeightOverrides::set_weight_multiplier(
+			RuntimeOrigin::root(),
+			balances.clone(),
+			Some(FixedU64::from_u32(2))
+		));
+
+		// Only the calls of the pallet are affected, along with the calls dispatching them.
+		assert_eq!(charged(&transfer), transfer_weight + transfer.get_dispatch_info().weight);
+		assert_eq!(charged(&remark), remark_weight);
+		assert_eq!(charged(&batch), batch_weight + batch.get_dispatch_info().weight);
+		assert_eq!(charged(&execute), execute_weight + execute.get_dispatch_info().weight);
+
+		assert_ok!(WeightOverrides::set_weight_multiplier(RuntimeOrigin::root(), balances, None));
+		assert_eq!(charged(&transfer), transfer_weight);
+	});
+}
@@ -0,0 +1,14 @@
+;; A contract calling the contract whose address it is called with.
+(module
+	(import "seal0" "seal_input" (func $seal_input (param i32 i32)))
+	(import "seal1" "seal_call"
+		(func $seal_call (param i32 i32 i64 i32 i32 i32 i32 i32) (result i32)))
+	(import "env" "memory" (memory 1 1))
+	;; The capacity of the input buffer at [4, 36), followed by the zero value to transfer.
+	(data (i32.const 0) "\20")
+	(func (export "deploy"))
+	(func (export "call")
+		(call $seal_input (i32.const 4) (i32.const 0))
+		(drop (call $seal_call (i32.const 0) (i32.const 4) (i64.const 0) (i32.const 36)
+			(i32.const 0) (i32.const 0) (i32.const -1) (i32.const 0))))
+)
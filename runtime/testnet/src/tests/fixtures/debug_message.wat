@@ -0,0 +1,8 @@
+;; A contract emitting a debug message when called.
+(module
+	(import "seal0" "debug_message" (func $debug_message (param i32 i32) (result i32)))
+	(import "env" "memory" (memory 1 1))
+	(data (i32.const 0) "Hello Pop")
+	(func (export "deploy"))
+	(func (export "call") (drop (call $debug_message (i32.const 0) (i32.const 9))))
+)
@@ -0,0 +1,20 @@
+;; A contract calling the Pop API function given by the first four bytes of its input, with the
+;; rest of its input, and returning the status code of the function followed by its output.
+(module
+	(import "seal0" "seal_input" (func $seal_input (param i32 i32)))
+	(import "seal0" "seal_call_chain_extension"
+		(func $seal_call_chain_extension (param i32 i32 i32 i32 i32) (result i32)))
+	(import "seal0" "seal_return" (func $seal_return (param i32 i32 i32)))
+	(import "env" "memory" (memory 1 1))
+	;; The capacities of the output buffer at [0x2000, 0x3000) and of the input buffer at
+	;; [8, 0x1008).
+	(data (i32.const 0) "\00\10\00\00\00\10\00\00")
+	(func (export "deploy"))
+	(func (export "call")
+		(call $seal_input (i32.const 8) (i32.const 4))
+		(i32.store (i32.const 0x1ffc)
+			(call $seal_call_chain_extension (i32.load (i32.const 8)) (i32.const 12)
+				(i32.sub (i32.load (i32.const 4)) (i32.const 4)) (i32.const 0x2000) (i32.const 0)))
+		(call $seal_return (i32.const 0) (i32.const 0x1ffc)
+			(i32.add (i32.load (i32.const 0)) (i32.const 4))))
+)
@@ -0,0 +1,167 @@
+use super::*;
+
+#[test]
+fn governance_schedules_calls_across_sessions() {
+	use codec::Encode;
+	use frame_support::traits::Hooks;
+
+	let invulnerable = AccountId::from([1; 32]);
+	let member = AccountId::from([2; 32]);
+	let registrar = AccountId::from([3; 32]);
+	let storage = RuntimeGenesisConfig {
+		collator_selection: pallet_collator_selection::GenesisConfig {
+			invulnerables: vec![invulnerable.clone()],
+			candidacy_bond: 100 * UNIT,
+			desired_candidates: 1,
+		},
+		session: pallet_session::GenesisConfig {
+			keys: vec![(
+				invulnerable.clone(),
+				invulnerable,
+				SessionKeys { aura: sp_core::sr25519::Public::from_raw([1; 32]).into() },
+			)],
+		},
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap();
+
+	sp_io::TestExternalities::new(storage).execute_with(|| {
+		System::set_block_number(1);
+		let when = 2 * Period::get();
+		let schedule = |call: RuntimeCall, when| {
+			RuntimeCall::Scheduler(pallet_scheduler::Call::schedule {
+				when,
+				maybe_periodic: None,
+				priority: 0,
+				call: Box::new(call),
+			})
+		};
+		let add_registrar = RuntimeCall::Identity(pallet_identity::Call::add_registrar {
+			account: registrar.clone().into(),
+		});
+
+		// Calls are scheduled by governance only.
+		assert_noop!(
+			Scheduler::schedule(
+				RuntimeOrigin::signed(member.clone()),
+				when,
+				None,
+				0,
+				Box::new(add_registrar.clone())
+			),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Council::set_members(RuntimeOrigin::root(), vec![member.clone()], None, 0));
+		let motion = schedule(add_registrar, when);
+		let motion_len = motion.encoded_size() as u32;
+		assert_ok!(Council::propose(
+			RuntimeOrigin::signed(member),
+			1,
+			Box::new(motion),
+			motion_len
+		));
+
+		// The call is dispatched with the origin of the motion two sessions later.
+		for n in 2..=when {
+			assert!(Identity::registrars().is_empty());
+			System::set_block_number(n);
+			Session::on_initialize(n);
+			Scheduler::on_initialize(n);
+		}
+		assert_eq!(Session::current_index(), 2);
+		System::assert_has_event(
+			pallet_scheduler::Event::Dispatched { task: (when, 0), id: None, result: Ok(()) }
+				.into(),
+		);
+		let registrars = Identity::registrars();
+		assert_eq!(registrars.len(), 1);
+		assert_eq!(registrars[0].as_ref().map(|r| &r.account), Some(&registrar));
+	});
+}
+
+#[test]
+fn scheduler_agenda_fits_in_block() {
+	use frame_support::traits::Get;
+	use pallet_scheduler::WeightInfo as _;
+
+	type SchedulerWeights = pallet_scheduler::weights::SubstrateWeight<Runtime>;
+	let max_block = RuntimeBlockWeights::get().max_block;
+	let max_scheduled = <Runtime as pallet_scheduler::Config>::MaxScheduledPerBlock::get();
+	// Servicing a full agenda, excluding the weight of the calls dispatched.
+	let full_agenda = SchedulerWeights::service_agenda_base(max_scheduled)
+		.saturating_add(SchedulerWeights::service_task_base().saturating_mul(max_scheduled.into()));
+	assert!(full_agenda.all_lte(MaximumSchedulerWeight::get()));
+	assert!(MaximumSchedulerWeight::get().all_lt(max_block));
+}
+
+#[test]
+fn council_majority_freezes_transfers() {
+	use codec::Encode;
+	use frame_support::dispatch::GetDispatchInfo;
+
+	let members: Vec<AccountId> = (1..=3).map(|i| AccountId::from([i; 32])).collect();
+	let call: RuntimeCall = pallet_transfer_freeze::Call::set_frozen { frozen: true }.into();
+	let (call_hash, call_len, call_weight) =
+		(BlakeTwo256::hash_of(&call), call.encoded_size() as u32, call.get_dispatch_info().weight);
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(Council::set_members(RuntimeOrigin::root(), members.clone(), None, 0));
+		let frozen = pallet_transfer_freeze::Frozen::<Runtime>::get;
+
+		// A motion of a single member is executed at once, by a minority.
+		assert_ok!(Council::propose(
+			RuntimeOrigin::signed(members[0].clone()),
+			1,
+			Box::new(call.clone()),
+			call_len
+		));
+		assert!(!frozen());
+
+		// A majority freezes transfers.
+		assert_ok!(Council::propose(
+			RuntimeOrigin::signed(members[0].clone()),
+			2,
+			Box::new(call.clone()),
+			call_len
+		));
+		for member in &members[..2] {
+			assert_ok!(Council::vote(RuntimeOrigin::signed(member.clone()), call_hash, 0, true));
+		}
+		assert_ok!(Council::close(
+			RuntimeOrigin::signed(members[0].clone()),
+			call_hash,
+			0,
+			call_weight,
+			call_len
+		));
+		assert!(frozen());
+	});
+}
+
+#[test]
+fn council_majority_sets_its_members() {
+	use codec::Encode;
+	use config::governance::CouncilInstance;
+
+	let member = AccountId::from([1; 32]);
+	let members = vec![member.clone(), AccountId::from([2; 32])];
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(Council::set_members(RuntimeOrigin::root(), vec![member.clone()], None, 0));
+		// Members cannot set the members by themselves.
+		assert_noop!(
+			Council::set_members(RuntimeOrigin::signed(member.clone()), members.clone(), None, 1),
+			DispatchError::BadOrigin
+		);
+
+		let call = RuntimeCall::Council(pallet_collective::Call::set_members {
+			new_members: members.clone(),
+			prime: None,
+			old_count: 1,
+		});
+		let call_len = call.encoded_size() as u32;
+		assert_ok!(Council::propose(RuntimeOrigin::signed(member), 1, Box::new(call), call_len));
+		assert_eq!(pallet_collective::Members::<Runtime, CouncilInstance>::get(), members);
+	});
+}
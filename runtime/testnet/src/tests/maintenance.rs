@@ -0,0 +1,277 @@
+use super::*;
+
+#[test]
+fn only_sudo_can_dispatch_while_paused() {
+	use sp_runtime::traits::Dispatchable;
+
+	let (key, other) = (AccountId::from([1; 32]), AccountId::from([2; 32]));
+	let storage = RuntimeGenesisConfig {
+		balances: pallet_balances::GenesisConfig { balances: vec![(key.clone(), 1_000 * UNIT)] },
+		sudo: pallet_sudo::GenesisConfig { key: Some(key.clone()) },
+		maintenance_mode: pallet_maintenance_mode::GenesisConfig {
+			paused: true,
+			..Default::default()
+		},
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap();
+	let transfer = RuntimeCall::Balances(BalancesCall::transfer_allow_death {
+		dest: MultiAddress::Id(other.clone()),
+		value: UNIT,
+	});
+	let sudo =
+		|call: RuntimeCall| RuntimeCall::Sudo(pallet_sudo::Call::sudo { call: Box::new(call) });
+
+	sp_io::TestExternalities::new(storage).execute_with(|| {
+		System::set_block_number(1);
+		let signed = RuntimeOrigin::signed(key.clone());
+		assert_eq!(
+			transfer.clone().dispatch(signed.clone()).map_err(|e| e.error),
+			Err(frame_system::Error::<Runtime>::CallFiltered.into())
+		);
+
+		let force_transfer = RuntimeCall::Balances(BalancesCall::force_transfer {
+			source: MultiAddress::Id(key.clone()),
+			dest: MultiAddress::Id(other.clone()),
+			value: UNIT,
+		});
+		assert_ok!(sudo(force_transfer).dispatch(signed.clone()));
+		assert_eq!(Balances::free_balance(&other), UNIT);
+
+		let unpause = RuntimeCall::MaintenanceMode(pallet_maintenance_mode::Call::set_paused {
+			paused: false,
+		});
+		assert_noop!(
+			unpause.clone().dispatch(signed.clone()).map_err(|e| e.error),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(sudo(unpause).dispatch(signed.clone()));
+		assert_ok!(transfer.dispatch(signed));
+		assert_eq!(Balances::free_balance(&other), 2 * UNIT);
+	});
+}
+
+#[test]
+fn pause_authority_can_only_pause() {
+	use sp_runtime::traits::Dispatchable;
+
+	let (key, authority) = (AccountId::from([1; 32]), AccountId::from([2; 32]));
+	let storage = RuntimeGenesisConfig {
+		balances: pallet_balances::GenesisConfig {
+			balances: vec![(authority.clone(), 1_000 * UNIT)],
+		},
+		sudo: pallet_sudo::GenesisConfig { key: Some(key) },
+		maintenance_mode: pallet_maintenance_mode::GenesisConfig {
+			pause_authority: Some(authority.clone()),
+			..Default::default()
+		},
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap();
+	let set_paused =
+		|paused| RuntimeCall::MaintenanceMode(pallet_maintenance_mode::Call::set_paused { paused });
+
+	sp_io::TestExternalities::new(storage).execute_with(|| {
+		System::set_block_number(1);
+		let signed = RuntimeOrigin::signed(authority.clone());
+		let set_code = RuntimeCall::System(frame_system::Call::set_code { code: vec![] });
+		assert_noop!(
+			set_code.clone().dispatch(signed.clone()).map_err(|e| e.error),
+			DispatchError::BadOrigin
+		);
+		let sudo = RuntimeCall::Sudo(pallet_sudo::Call::sudo { call: Box::new(set_code) });
+		assert_noop!(
+			sudo.dispatch(signed.clone()).map_err(|e| e.error),
+			pallet_sudo::Error::<Runtime>::RequireSudo
+		);
+		assert_noop!(
+			set_paused(true)
+				.dispatch(RuntimeOrigin::signed(AccountId::from([3; 32])))
+				.map_err(|e| e.error),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(set_paused(true).dispatch(signed.clone()));
+		assert!(pallet_maintenance_mode::Paused::<Runtime>::get());
+		let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+		assert_noop!(
+			remark.clone().dispatch(signed.clone()).map_err(|e| e.error),
+			frame_system::Error::<Runtime>::CallFiltered
+		);
+		assert_ok!(set_paused(false).dispatch(signed.clone()));
+		assert_ok!(remark.dispatch(signed));
+	});
+}
+
+/// Instantiate the contract writing a debug message, returning a call of it by `origin`, who is
+/// endowed to instantiate it.
+fn debug_message_contract_call(origin: &AccountId) -> RuntimeCall {
+	use pallet_contracts::{CollectEvents, DebugInfo};
+
+	assert_ok!(Balances::force_set_balance(
+		RuntimeOrigin::root(),
+		origin.clone().into(),
+		1_000 * UNIT
+	));
+	let contract = Contracts::bare_instantiate(
+		origin.clone(),
+		0,
+		MAXIMUM_BLOCK_WEIGHT,
+		None,
+		pallet_contracts::Code::Upload(debug_message_contract()),
+		vec![],
+		vec![],
+		DebugInfo::Skip,
+		CollectEvents::Skip,
+	)
+	.result
+	.unwrap()
+	.account_id;
+	RuntimeCall::Contracts(pallet_contracts::Call::call {
+		dest: contract.into(),
+		value: 0,
+		gas_limit: MAXIMUM_BLOCK_WEIGHT,
+		storage_deposit_limit: None,
+		data: vec![],
+	})
+}
+
+/// The origin of a motion approved by two thirds of the technical committee.
+fn technical_committee_origin() -> RuntimeOrigin {
+	use config::governance::TechnicalCommitteeInstance;
+
+	pallet_collective::RawOrigin::<AccountId, TechnicalCommitteeInstance>::Members(2, 3).into()
+}
+
+#[test]
+fn contract_calls_can_be_paused_and_resumed() {
+	use pallet_tx_pause::RuntimeCallNameOf;
+	use sp_runtime::traits::Dispatchable;
+
+	let name = |pallet: &[u8], call: &[u8]| -> RuntimeCallNameOf<Runtime> {
+		(pallet.to_vec().try_into().unwrap(), call.to_vec().try_into().unwrap())
+	};
+
+	new_test_ext().execute_with(|| {
+		let alice = AccountId::from([1; 32]);
+		let call = debug_message_contract_call(&alice);
+		let signed = RuntimeOrigin::signed(alice);
+		assert_ok!(call.clone().dispatch(signed.clone()));
+
+		// Only the technical committee can pause calls.
+		assert_noop!(
+			TxPause::pause(signed.clone(), name(b"Contracts", b"call")),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(TxPause::pause(technical_committee_origin(), name(b"Contracts", b"call")));
+		assert_eq!(
+			call.clone().dispatch(signed.clone()).map_err(|e| e.error),
+			Err(frame_system::Error::<Runtime>::CallFiltered.into())
+		);
+		// The calls required to produce blocks and to govern cannot be paused.
+		for (pallet, call) in [
+			(&b"ParachainSystem"[..], &b"set_validation_data"[..]),
+			(b"Timestamp", b"set"),
+			(b"System", b"apply_authorized_upgrade"),
+			(b"Sudo", b"sudo"),
+			(b"TechnicalCommittee", b"close"),
+			(b"TxPause", b"unpause"),
+		] {
+			assert_noop!(
+				TxPause::pause(technical_committee_origin(), name(pallet, call)),
+				pallet_tx_pause::Error::<Runtime>::Unpausable
+			);
+		}
+
+		assert_noop!(
+			TxPause::unpause(signed.clone(), name(b"Contracts", b"call")),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(TxPause::unpause(technical_committee_origin(), name(b"Contracts", b"call")));
+		assert_ok!(call.dispatch(signed));
+	});
+}
+
+#[test]
+fn safe_mode_only_allows_critical_calls() {
+	use config::governance::{CouncilInstance, SafeModeDuration};
+	use sp_runtime::traits::Dispatchable;
+
+	new_test_ext().execute_with(|| {
+		let alice = AccountId::from([1; 32]);
+		let call = debug_message_contract_call(&alice);
+		let signed = RuntimeOrigin::signed(alice.clone());
+
+		// Safe mode is entered by the technical committee, rather than the council.
+		let council = pallet_collective::RawOrigin::<AccountId, CouncilInstance>::Members(3, 4);
+		assert_noop!(SafeMode::force_enter(council.into()), DispatchError::BadOrigin);
+		assert_noop!(
+			SafeMode::enter(signed.clone()),
+			pallet_safe_mode::Error::<Runtime>::NotConfigured
+		);
+		assert_ok!(SafeMode::force_enter(technical_committee_origin()));
+		assert_eq!(
+			pallet_safe_mode::EnteredUntil::<Runtime>::get(),
+			Some(System::block_number() + SafeModeDuration::get())
+		);
+
+		let filtered: Result<_, DispatchError> =
+			Err(frame_system::Error::<Runtime>::CallFiltered.into());
+		assert_eq!(call.clone().dispatch(signed.clone()).map_err(|e| e.error), filtered);
+		let transfer = RuntimeCall::Balances(BalancesCall::transfer_keep_alive {
+			dest: MultiAddress::Id(AccountId::from([2; 32])),
+			value: UNIT,
+		});
+		assert_eq!(transfer.dispatch(signed.clone()).map_err(|e| e.error), filtered);
+		// Only specific calls of the system are critical.
+		assert_eq!(
+			RuntimeCall::System(frame_system::Call::remark { remark: vec![] })
+				.dispatch(signed.clone())
+				.map_err(|e| e.error),
+			filtered
+		);
+		// Whitelisted calls can still be dispatched, including those exiting safe mode.
+		assert_ok!(RuntimeCall::SafeMode(pallet_safe_mode::Call::force_exit {})
+			.dispatch(technical_committee_origin()));
+
+		assert_ok!(call.dispatch(signed));
+	});
+}
+
+#[test]
+fn calls_allowed_while_paused_are_exempt_from_every_brake() {
+	use config::governance::{AllowedWhilePaused, SafeModeWhitelistedCalls, UnpausableCalls};
+	use frame_support::traits::{Contains, GetCallMetadata};
+	use pallet_tx_pause::RuntimeCallNameOf;
+
+	let unpause =
+		RuntimeCall::MaintenanceMode(pallet_maintenance_mode::Call::set_paused { paused: false });
+	let set_timestamp = RuntimeCall::Timestamp(pallet_timestamp::Call::set { now: 0 });
+	let apply_upgrade =
+		RuntimeCall::System(frame_system::Call::apply_authorized_upgrade { code: vec![] });
+	let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+	let name = |call: &RuntimeCall| -> RuntimeCallNameOf<Runtime> {
+		let metadata = call.get_call_metadata();
+		(
+			metadata.pallet_name.as_bytes().to_vec().try_into().unwrap(),
+			metadata.function_name.as_bytes().to_vec().try_into().unwrap(),
+		)
+	};
+
+	for call in [&unpause, &set_timestamp] {
+		assert!(AllowedWhilePaused::contains(call));
+		assert!(SafeModeWhitelistedCalls::contains(call));
+		assert!(UnpausableCalls::contains(&name(call)));
+	}
+	// The calls only exempted from safe mode and the transaction pause are not allowed while
+	// the chain is paused.
+	assert!(!AllowedWhilePaused::contains(&apply_upgrade));
+	assert!(SafeModeWhitelistedCalls::contains(&apply_upgrade));
+	assert!(UnpausableCalls::contains(&name(&apply_upgrade)));
+	// Exempting a call does not exempt the other calls of its pallet.
+	assert!(!AllowedWhilePaused::contains(&remark));
+	assert!(!SafeModeWhitelistedCalls::contains(&remark));
+	assert!(!UnpausableCalls::contains(&name(&remark)));
+}
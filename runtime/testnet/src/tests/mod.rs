@@ -0,0 +1,46 @@
+use frame_support::{assert_noop, assert_ok, BoundedVec};
+use sp_runtime::{traits::Hash as _, DispatchError};
+
+use super::*;
+
+mod collation;
+mod contracts;
+mod cross_chain;
+mod fees;
+mod governance;
+mod maintenance;
+mod offchain;
+mod pop_api;
+mod proxy;
+mod runtime_metadata;
+mod storage_migrations;
+mod transfers;
+mod upgrades;
+
+/// The code of the contract described by the WebAssembly text `wat`.
+fn contract_code(wat: &str) -> Vec<u8> {
+	wat::parse_str(wat).expect("the fixtures are valid; qed")
+}
+
+/// A contract emitting a debug message when called.
+fn debug_message_contract() -> Vec<u8> {
+	contract_code(include_str!("fixtures/debug_message.wat"))
+}
+
+/// A contract calling the contract whose address it is called with.
+fn caller_contract() -> Vec<u8> {
+	contract_code(include_str!("fixtures/caller.wat"))
+}
+
+/// A contract calling the Pop API function given by the first four bytes of its input, with the
+/// rest of its input, and returning the status code of the function followed by its output.
+fn pop_api_contract() -> Vec<u8> {
+	contract_code(include_str!("fixtures/pop_api.wat"))
+}
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let storage = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
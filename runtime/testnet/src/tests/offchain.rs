@@ -0,0 +1,77 @@
+use super::*;
+
+#[test]
+fn price_feed_worker_submits_fetched_prices() {
+	use codec::Decode;
+	use frame_support::{traits::Hooks, unsigned::ValidateUnsigned};
+	use pallet_price_feed::{NextUnsignedAt, Prices, KEY_TYPE, SOURCE_KEY};
+	use sp_core::offchain::{
+		testing::{PendingRequest, TestOffchainExt, TestTransactionPoolExt},
+		OffchainDbExt, OffchainWorkerExt, StorageKind, TransactionPoolExt,
+	};
+	use sp_keystore::{testing::MemoryKeystore, Keystore, KeystoreExt};
+
+	const SOURCE: &str = "https://prices.example/dot-usd";
+	let (offchain, offchain_state) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let keystore = MemoryKeystore::new();
+	let public = keystore.sr25519_generate_new(KEY_TYPE, None).unwrap();
+	let mut ext = new_test_ext();
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+	ext.register_extension(OffchainWorkerExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+	ext.register_extension(KeystoreExt::new(keystore));
+	let respond = |price: &[u8]| {
+		offchain_state.write().expect_request(PendingRequest {
+			method: "GET".into(),
+			uri: SOURCE.into(),
+			response: Some(price.to_vec()),
+			sent: true,
+			..Default::default()
+		})
+	};
+	let submitted = || {
+		let tx = pool_state.write().transactions.pop().expect("a price is submitted");
+		UncheckedExtrinsic::decode(&mut &*tx).unwrap()
+	};
+
+	ext.execute_with(|| {
+		// Nothing is submitted until a source is set.
+		System::set_block_number(1);
+		PriceFeed::offchain_worker(1);
+		assert!(pool_state.read().transactions.is_empty());
+		sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, SOURCE_KEY, SOURCE.as_bytes());
+
+		// Odd blocks submit unsigned transactions, accepted once per interval.
+		respond(b"1234\n");
+		PriceFeed::offchain_worker(1);
+		let tx = submitted();
+		assert!(tx.signature.is_none());
+		let RuntimeCall::PriceFeed(call) = tx.function else {
+			panic!("unexpected call: {:?}", tx.function)
+		};
+		assert_eq!(
+			call,
+			pallet_price_feed::Call::submit_price_unsigned { block_number: 1, price: 1234 }
+		);
+		assert!(PriceFeed::validate_unsigned(TransactionSource::Local, &call).is_ok());
+		assert_ok!(PriceFeed::submit_price_unsigned(RuntimeOrigin::none(), 1, 1234));
+		assert_eq!(NextUnsignedAt::<Runtime>::get(), 1 + config::offchain::UnsignedInterval::get());
+		assert!(PriceFeed::validate_unsigned(TransactionSource::Local, &call).is_err());
+
+		// Even blocks submit transactions signed by the key of the keystore, mortal from the
+		// parent block.
+		System::initialize(&2, &Hash::repeat_byte(1), &Default::default());
+		respond(b"4321");
+		PriceFeed::offchain_worker(2);
+		let tx = submitted();
+		assert_eq!(tx.signature.unwrap().0, MultiAddress::Id(AccountId::from(public)));
+		assert_eq!(
+			tx.function,
+			RuntimeCall::PriceFeed(pallet_price_feed::Call::submit_price { price: 4321 })
+		);
+		assert_ok!(PriceFeed::submit_price(RuntimeOrigin::signed(AccountId::from(public)), 4321));
+		assert_eq!(Prices::<Runtime>::get().into_inner(), vec![1234, 4321]);
+		assert_eq!(PriceFeed::average_price(), Some(2777));
+	});
+}
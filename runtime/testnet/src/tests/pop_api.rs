@@ -0,0 +1,340 @@
+use super::*;
+
+/// Instantiate the contract calling the Pop API, endowed with `value` by `origin`.
+fn instantiate_pop_api_contract(origin: &AccountId, value: Balance) -> AccountId {
+	use pallet_contracts::{CollectEvents, DebugInfo};
+
+	Contracts::bare_instantiate(
+		origin.clone(),
+		value,
+		MAXIMUM_BLOCK_WEIGHT,
+		None,
+		pallet_contracts::Code::Upload(pop_api_contract()),
+		vec![],
+		vec![],
+		DebugInfo::Skip,
+		CollectEvents::Skip,
+	)
+	.result
+	.unwrap()
+	.account_id
+}
+
+/// Call the Pop API function `func_id` with `input` via `contract`, returning the status code
+/// and output of the function, along with the weight consumed by the contract call. The output
+/// is only meaningful for functions writing one.
+fn call_pop_api(
+	contract: &AccountId,
+	func_id: u16,
+	input: impl codec::Encode,
+) -> (Result<(u32, Vec<u8>), DispatchError>, Weight) {
+	use codec::Encode;
+	use pallet_contracts::{CollectEvents, DebugInfo, Determinism};
+
+	// The id of the Pop API chain extension, in the upper half of the function id.
+	let id = (909u32 << 16) | func_id as u32;
+	let result = Contracts::bare_call(
+		AccountId::from([1; 32]),
+		contract.clone(),
+		0,
+		MAXIMUM_BLOCK_WEIGHT,
+		None,
+		(id, input).encode(),
+		DebugInfo::Skip,
+		CollectEvents::Skip,
+		Determinism::Enforced,
+	);
+	let output = result.result.map(|value| {
+		let (status, output) = value.data.split_at(4);
+		(u32::from_le_bytes(status.try_into().unwrap()), output.to_vec())
+	});
+	(output, result.gas_consumed)
+}
+
+/// Read the runtime state `key` via the Pop API from `contract`.
+fn read_pop_api_state<T: codec::Decode>(
+	contract: &AccountId,
+	key: pop_primitives::storage_keys::RuntimeStateKeys,
+) -> T {
+	use codec::Decode;
+
+	let (status, output) = call_pop_api(contract, 1, key).0.unwrap();
+	assert_eq!(status, 0);
+	let value = Vec::<u8>::decode(&mut &output[..]).unwrap();
+	T::decode(&mut &value[..]).unwrap()
+}
+
+#[test]
+fn pop_api_dispatches_allowed_calls() {
+	use frame_support::dispatch::GetDispatchInfo;
+
+	new_test_ext().execute_with(|| {
+		let (alice, bob) = (AccountId::from([1; 32]), AccountId::from([2; 32]));
+		assert_ok!(Balances::force_set_balance(
+			RuntimeOrigin::root(),
+			alice.clone().into(),
+			1_000 * UNIT
+		));
+		let contract = instantiate_pop_api_contract(&alice, 10 * UNIT);
+
+		let transfer = RuntimeCall::Balances(BalancesCall::transfer_keep_alive {
+			dest: bob.clone().into(),
+			value: UNIT,
+		});
+		let (result, weight) = call_pop_api(&contract, 0, &transfer);
+		assert_eq!(result.unwrap().0, 0);
+		assert_eq!(Balances::free_balance(&bob), UNIT);
+		// The contract call is charged the weight of the dispatched call.
+		assert!(weight.all_gte(transfer.get_dispatch_info().weight));
+
+		// Calls not allowed are filtered, surfacing `frame_system::Error::CallFiltered`.
+		let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+		assert_eq!(call_pop_api(&contract, 0, &remark).0.unwrap().0, 5);
+		// Errors of pallets are encoded as the index of the pallet followed by the index of
+		// the error, here `pallet_nfts::Error::UnknownCollection`.
+		let destroy = RuntimeCall::Nfts(pallet_nfts::Call::destroy {
+			collection: 0,
+			witness: pallet_nfts::DestroyWitness {
+				item_metadatas: 0,
+				item_configs: 0,
+				attributes: 0,
+			},
+		});
+		assert_eq!(call_pop_api(&contract, 0, &destroy).0.unwrap().0, 50_001);
+	});
+}
+
+#[test]
+fn pop_api_reads_runtime_state() {
+	use pop_primitives::storage_keys::{
+		AssetsKeys, NftsKeys, ParachainSystemKeys, RelayChainKeys, RuntimeStateKeys,
+	};
+	use pop_runtime_common::apis::RelayChainState;
+
+	new_test_ext().execute_with(|| {
+		let (alice, bob) = (AccountId::from([1; 32]), AccountId::from([2; 32]));
+		assert_ok!(Balances::force_set_balance(
+			RuntimeOrigin::root(),
+			alice.clone().into(),
+			1_000 * UNIT
+		));
+		let contract = instantiate_pop_api_contract(&alice, 0);
+
+		cumulus_pallet_parachain_system::LastRelayChainBlockNumber::<Runtime>::put(7);
+		assert_eq!(
+			read_pop_api_state::<BlockNumber>(
+				&contract,
+				RuntimeStateKeys::ParachainSystem(ParachainSystemKeys::LastRelayChainBlockNumber)
+			),
+			7
+		);
+
+		let relay_chain = |key| RuntimeStateKeys::RelayChain(key);
+		assert_eq!(
+			read_pop_api_state::<Option<[u8; 32]>>(
+				&contract,
+				relay_chain(RelayChainKeys::Randomness)
+			),
+			None
+		);
+		pallet_relay_state::State::<Runtime>::put(RelayChainState {
+			block_number: 7,
+			randomness: Some([3; 32]),
+			timestamp: 42_000,
+		});
+		assert_eq!(
+			read_pop_api_state::<Option<u32>>(&contract, relay_chain(RelayChainKeys::BlockNumber)),
+			Some(7)
+		);
+		assert_eq!(
+			read_pop_api_state::<Option<[u8; 32]>>(
+				&contract,
+				relay_chain(RelayChainKeys::Randomness)
+			),
+			Some([3; 32])
+		);
+		assert_eq!(
+			read_pop_api_state::<Option<u64>>(&contract, relay_chain(RelayChainKeys::Timestamp)),
+			Some(42_000)
+		);
+
+		assert_ok!(Assets::force_create(
+			RuntimeOrigin::root(),
+			1.into(),
+			alice.clone().into(),
+			true,
+			1
+		));
+		assert_ok!(Assets::mint(
+			RuntimeOrigin::signed(alice.clone()),
+			1.into(),
+			bob.clone().into(),
+			100
+		));
+		let exists = |id| {
+			read_pop_api_state::<bool>(
+				&contract,
+				RuntimeStateKeys::Assets(AssetsKeys::AssetExists(id)),
+			)
+		};
+		assert!(exists(1));
+		assert!(!exists(2));
+		let balance = |key| read_pop_api_state::<Balance>(&contract, RuntimeStateKeys::Assets(key));
+		assert_eq!(balance(AssetsKeys::TotalSupply(1)), 100);
+		assert_eq!(balance(AssetsKeys::BalanceOf(1, bob.into())), 100);
+		assert_eq!(balance(AssetsKeys::BalanceOf(1, alice.into())), 0);
+
+		assert_eq!(
+			read_pop_api_state::<Option<AccountId>>(
+				&contract,
+				RuntimeStateKeys::Nfts(NftsKeys::CollectionOwner(0))
+			),
+			None
+		);
+	});
+}
+
+#[test]
+fn pop_api_charges_the_proof_size_of_the_state_read() {
+	use frame_support::traits::Get;
+	use pop_primitives::storage_keys::{NftsKeys, ParachainSystemKeys, RuntimeStateKeys};
+
+	new_test_ext().execute_with(|| {
+		let alice = AccountId::from([1; 32]);
+		assert_ok!(Balances::force_set_balance(
+			RuntimeOrigin::root(),
+			alice.clone().into(),
+			1_000 * UNIT
+		));
+		let contract = instantiate_pop_api_contract(&alice, 0);
+
+		let block_number =
+			RuntimeStateKeys::ParachainSystem(ParachainSystemKeys::LastRelayChainBlockNumber);
+		let (result, block_number_weight) = call_pop_api(&contract, 1, block_number);
+		assert_eq!(result.unwrap().0, 0);
+		// Reading an attribute is charged the proof size of its largest value, even when
+		// it does not exist.
+		let attribute = RuntimeStateKeys::Nfts(NftsKeys::Attribute(0, 0, Default::default()));
+		let (result, attribute_weight) = call_pop_api(&contract, 1, attribute);
+		assert_eq!(result.unwrap().0, 0);
+		assert!(
+			attribute_weight.proof_size() >=
+				block_number_weight.proof_size() +
+					<<Runtime as pallet_nfts::Config>::ValueLimit as Get<u32>>::get() as u64
+		);
+	});
+}
+
+#[test]
+fn pop_api_sends_xcm_to_relay_chain() {
+	use codec::Encode;
+	use pop_primitives::cross_chain::{CrossChainMessage, OnDemand, RelayChainMessage};
+	use xcm::{
+		latest::prelude::{AccountId32, DescendOrigin, Location, Polkadot, Transact},
+		VersionedLocation, VersionedXcm,
+	};
+	use xcm_builder::InspectMessageQueues;
+
+	new_test_ext().execute_with(|| {
+		let alice = AccountId::from([1; 32]);
+		assert_ok!(Balances::force_set_balance(
+			RuntimeOrigin::root(),
+			alice.clone().into(),
+			1_000 * UNIT
+		));
+		let contract = instantiate_pop_api_contract(&alice, 0);
+		assert_ok!(XcmGateway::allow_destination(
+			RuntimeOrigin::root(),
+			contract.clone(),
+			Box::new(Location::parent().into()),
+		));
+
+		let order = RelayChainMessage::OnDemand(OnDemand::PlaceOrderKeepAlive {
+			max_amount: UNIT,
+			para_id: 4001,
+		});
+		let encoded_order = order.encode();
+		let (result, _) = call_pop_api(&contract, 2, CrossChainMessage::Relay(order));
+		assert_eq!(result.unwrap().0, 0);
+
+		// The order is sent to the relay chain on behalf of the contract.
+		let messages = ParachainSystem::get_messages();
+		let [(dest, messages)] = &messages[..] else { panic!("expected a single queue") };
+		assert_eq!(*dest, VersionedLocation::V4(Location::parent()));
+		let [VersionedXcm::V4(message)] = &messages[..] else {
+			panic!("expected a single message")
+		};
+		assert_eq!(
+			message.0[0],
+			DescendOrigin(
+				AccountId32 { network: Some(Polkadot), id: contract.clone().into() }.into()
+			)
+		);
+		assert!(message.0.iter().any(|instruction| matches!(
+			instruction,
+			Transact { call, .. } if call.clone().into_encoded() == encoded_order
+		)));
+	});
+}
+
+#[test]
+fn contracts_only_send_xcm_to_allowed_destinations() {
+	use pallet_xcm_gateway::Error;
+	use pop_primitives::cross_chain::{CrossChainMessage, OnDemand, RelayChainMessage};
+	use sp_runtime::traits::Dispatchable;
+	use xcm::{latest::prelude::*, VersionedXcm};
+	use xcm_builder::InspectMessageQueues;
+
+	new_test_ext().execute_with(|| {
+		let alice = AccountId::from([1; 32]);
+		assert_ok!(Balances::force_set_balance(
+			RuntimeOrigin::root(),
+			alice.clone().into(),
+			1_000 * UNIT
+		));
+		let contract = instantiate_pop_api_contract(&alice, 0);
+		let send = pallet_xcm::Call::send {
+			dest: Box::new(Location::parent().into()),
+			message: Box::new(VersionedXcm::from(Xcm::<()>(vec![ClearOrigin]))),
+		};
+
+		// Contracts cannot send messages via `pallet_xcm`, neither by dispatching its calls via
+		// the Pop API, filtered as `frame_system::Error::CallFiltered`, nor directly.
+		let call = RuntimeCall::PolkadotXcm(send.clone());
+		assert_eq!(call_pop_api(&contract, 0, &call).0.unwrap().0, 5);
+		assert_noop!(
+			call.dispatch(RuntimeOrigin::signed(contract.clone())),
+			DispatchError::BadOrigin
+		);
+		// Messages to the relay chain are sent via `XcmGateway`, which only sends them to the
+		// destinations allowed for the contract.
+		let order = RelayChainMessage::OnDemand(OnDemand::PlaceOrderKeepAlive {
+			max_amount: UNIT,
+			para_id: 4001,
+		});
+		let error: DispatchError = Error::<Runtime>::DestinationNotAllowed.into();
+		let DispatchError::Module(error) = error else { unreachable!() };
+		assert_eq!(
+			call_pop_api(&contract, 2, CrossChainMessage::Relay(order)).0.unwrap().0,
+			error.index as u32 * 1_000 + u32::from_le_bytes(error.error)
+		);
+		assert!(ParachainSystem::get_messages().iter().all(|(_, messages)| messages.is_empty()));
+
+		// Accounts other than contracts still send messages via `pallet_xcm`.
+		assert_ok!(RuntimeCall::PolkadotXcm(send).dispatch(RuntimeOrigin::signed(alice)));
+	});
+}
+
+#[test]
+fn pop_api_rejects_unknown_functions() {
+	new_test_ext().execute_with(|| {
+		let alice = AccountId::from([1; 32]);
+		assert_ok!(Balances::force_set_balance(
+			RuntimeOrigin::root(),
+			alice.clone().into(),
+			1_000 * UNIT
+		));
+		let contract = instantiate_pop_api_contract(&alice, 0);
+		assert!(call_pop_api(&contract, 4, ()).0.is_err());
+	});
+}
@@ -0,0 +1,46 @@
+use super::*;
+
+#[test]
+fn proxy_api_returns_proxies_with_their_types() {
+	use pop_runtime_common::{apis::runtime_decl_for_proxy_api::ProxyApiV1, proxy::ProxyType};
+
+	new_test_ext().execute_with(|| {
+		let alice = AccountId::from([1; 32]);
+		let (bob, charlie) = (AccountId::from([2; 32]), AccountId::from([3; 32]));
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), alice.clone().into(), UNIT));
+		let proxies =
+			|| <Runtime as ProxyApiV1<Block, AccountId, ProxyType>>::proxies(alice.clone());
+		assert!(proxies().is_empty());
+
+		for (delegate, proxy_type) in [(&bob, ProxyType::Assets), (&charlie, ProxyType::Any)] {
+			assert_ok!(Proxy::add_proxy(
+				RuntimeOrigin::signed(alice.clone()),
+				delegate.clone().into(),
+				proxy_type,
+				0
+			));
+		}
+		assert_eq!(proxies(), vec![(bob, ProxyType::Assets), (charlie, ProxyType::Any)]);
+	});
+}
+
+#[test]
+fn governance_proxy_only_dispatches_governance_calls() {
+	use frame_support::traits::InstanceFilter;
+	use pop_runtime_common::proxy::ProxyType;
+
+	let vote = RuntimeCall::Council(pallet_collective::Call::vote {
+		proposal: Default::default(),
+		index: 0,
+		approve: true,
+	});
+	let transfer = RuntimeCall::Balances(BalancesCall::transfer_keep_alive {
+		dest: AccountId::from([1; 32]).into(),
+		value: UNIT,
+	});
+	assert!(ProxyType::Governance.filter(&vote));
+	assert!(!ProxyType::Governance.filter(&transfer));
+	// Delegates which may not transfer funds may delegate governance.
+	assert!(ProxyType::NonTransfer.is_superset(&ProxyType::Governance));
+	assert!(!ProxyType::Governance.is_superset(&ProxyType::NonTransfer));
+}
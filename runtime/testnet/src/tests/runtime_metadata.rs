@@ -0,0 +1,39 @@
+use super::*;
+
+#[test]
+fn metadata_api_returns_decodable_metadata() {
+	use codec::Decode;
+	use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed};
+	use sp_api::runtime_decl_for_metadata::MetadataV2;
+
+	new_test_ext().execute_with(|| {
+		let metadata = <Runtime as MetadataV2<Block>>::metadata();
+		let RuntimeMetadataPrefixed(_, RuntimeMetadata::V14(metadata)) =
+			RuntimeMetadataPrefixed::decode(&mut &metadata[..]).unwrap()
+		else {
+			panic!("metadata is not v14");
+		};
+		let pallets: Vec<_> = metadata.pallets.iter().map(|p| p.name.as_str()).collect();
+		for pallet in ["System", "Balances", "Sudo", "Contracts", "TechnicalCommittee"] {
+			assert!(pallets.contains(&pallet), "{pallet} missing from metadata");
+		}
+	});
+}
+
+#[test]
+fn pallets_are_listed_at_their_indices() {
+	use pop_runtime_common::apis::runtime_decl_for_runtime_info_api::RuntimeInfoApiV1;
+
+	let pallets = <Runtime as RuntimeInfoApiV1<Block>>::pallets();
+	for (index, name) in [
+		(0, "System"),
+		(1, "ParachainSystem"),
+		(15, "Sudo"),
+		(21, "CollatorSelection"),
+		(22, "Session"),
+		(31, "PolkadotXcm"),
+	] {
+		assert!(pallets.contains(&(index, name.into())), "{name} is not at index {index}");
+	}
+	assert!(pallets.windows(2).all(|pair| pair[0].0 < pair[1].0));
+}
@@ -0,0 +1,94 @@
+use super::*;
+
+#[test]
+fn init_storage_version_is_only_applied_once() {
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	type Migration = migrations::InitStorageVersion<TechnicalCommittee, RocksDbWeight>;
+
+	new_test_ext().execute_with(|| {
+		// Pallets added to a live chain have no storage version.
+		assert_eq!(TechnicalCommittee::on_chain_storage_version(), 0);
+		Migration::on_runtime_upgrade();
+		assert_eq!(
+			TechnicalCommittee::on_chain_storage_version(),
+			TechnicalCommittee::in_code_storage_version()
+		);
+
+		// An existing storage version is left untouched.
+		StorageVersion::new(1).put::<TechnicalCommittee>();
+		Migration::on_runtime_upgrade();
+		assert_eq!(TechnicalCommittee::on_chain_storage_version(), 1);
+	});
+}
+
+#[cfg(feature = "try-runtime")]
+#[test]
+fn init_storage_version_passes_try_runtime_checks() {
+	use frame_support::traits::OnRuntimeUpgrade;
+
+	type Migration = migrations::InitStorageVersion<TechnicalCommittee, RocksDbWeight>;
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(Migration::try_on_runtime_upgrade(true));
+		// Applying the migration again is a no-op.
+		assert_ok!(Migration::try_on_runtime_upgrade(true));
+	});
+}
+
+/// Stores the technical committee under a former name `Committee`, as in a snapshot of the
+/// chain before the committee was renamed, returning its members.
+fn committee_stored_under_former_name() -> Vec<AccountId> {
+	use frame_support::{
+		storage::{migration::put_storage_value, unhashed},
+		traits::StorageVersion,
+	};
+
+	let members = vec![AccountId::from([1; 32]), AccountId::from([2; 32])];
+	let _ = unhashed::clear_prefix(&sp_core::twox_128(b"TechnicalCommittee"), None, None);
+	put_storage_value(b"Committee", b"Members", &[], members.clone());
+	put_storage_value(b"Committee", b":__STORAGE_VERSION__:", &[], StorageVersion::new(4));
+	members
+}
+
+frame_support::parameter_types! {
+	pub const FormerName: &'static str = "Committee";
+}
+
+type MoveCommittee = migrations::MovePallet<FormerName, TechnicalCommittee, RocksDbWeight>;
+
+#[test]
+fn moved_pallet_keeps_its_storage() {
+	use frame_support::{
+		instances::Instance1,
+		storage::migration::get_storage_value,
+		traits::{GetStorageVersion, OnRuntimeUpgrade},
+	};
+
+	new_test_ext().execute_with(|| {
+		let members = committee_stored_under_former_name();
+		assert!(pallet_collective::Members::<Runtime, Instance1>::get().is_empty());
+
+		MoveCommittee::on_runtime_upgrade();
+		assert_eq!(pallet_collective::Members::<Runtime, Instance1>::get(), members);
+		assert_eq!(TechnicalCommittee::on_chain_storage_version(), 4);
+		assert_eq!(get_storage_value::<Vec<AccountId>>(b"Committee", b"Members", &[]), None);
+
+		// Applying the migration again is a no-op.
+		MoveCommittee::on_runtime_upgrade();
+		assert_eq!(pallet_collective::Members::<Runtime, Instance1>::get(), members);
+	});
+}
+
+#[cfg(feature = "try-runtime")]
+#[test]
+fn move_pallet_passes_try_runtime_checks() {
+	use frame_support::traits::OnRuntimeUpgrade;
+
+	new_test_ext().execute_with(|| {
+		committee_stored_under_former_name();
+		assert_ok!(MoveCommittee::try_on_runtime_upgrade(true));
+		// Applying the migration again is a no-op.
+		assert_ok!(MoveCommittee::try_on_runtime_upgrade(true));
+	});
+}
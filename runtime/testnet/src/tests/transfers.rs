@@ -0,0 +1,273 @@
+use super::*;
+
+#[test]
+fn only_transfers_are_rejected_while_frozen() {
+	use pallet_contracts::{CollectEvents, DebugInfo};
+	use sp_runtime::traits::Dispatchable;
+
+	let (alice, bob) = (AccountId::from([1; 32]), AccountId::from([2; 32]));
+	let transfer = RuntimeCall::Balances(BalancesCall::transfer_keep_alive {
+		dest: MultiAddress::Id(bob.clone()),
+		value: UNIT,
+	});
+	let set_frozen = |frozen| TransferFreeze::set_frozen(RuntimeOrigin::root(), frozen);
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(Balances::force_set_balance(
+			RuntimeOrigin::root(),
+			alice.clone().into(),
+			1_000 * UNIT
+		));
+		let contract = Contracts::bare_instantiate(
+			alice.clone(),
+			0,
+			MAXIMUM_BLOCK_WEIGHT,
+			None,
+			pallet_contracts::Code::Upload(debug_message_contract()),
+			vec![],
+			vec![],
+			DebugInfo::Skip,
+			CollectEvents::Skip,
+		)
+		.result
+		.unwrap()
+		.account_id;
+		let contract_call = RuntimeCall::Contracts(pallet_contracts::Call::call {
+			dest: contract.into(),
+			value: 0,
+			gas_limit: MAXIMUM_BLOCK_WEIGHT / 10,
+			storage_deposit_limit: None,
+			data: vec![],
+		});
+		let signed = RuntimeOrigin::signed(alice.clone());
+
+		assert_noop!(TransferFreeze::set_frozen(signed.clone(), true), DispatchError::BadOrigin);
+		assert_ok!(set_frozen(true));
+		assert_eq!(
+			transfer.clone().dispatch(signed.clone()).map_err(|e| e.error),
+			Err(frame_system::Error::<Runtime>::CallFiltered.into())
+		);
+		assert_ok!(contract_call.dispatch(signed.clone()));
+
+		assert_ok!(set_frozen(false));
+		assert_ok!(transfer.dispatch(signed));
+		assert_eq!(Balances::free_balance(&bob), UNIT);
+	});
+}
+
+#[test]
+fn vested_transfers_work_with_batch_and_multisig() {
+	use frame_support::traits::{InstanceFilter, VestingSchedule};
+	use pop_runtime_common::proxy::ProxyType;
+	use sp_runtime::traits::Dispatchable;
+
+	let (alice, bob, charlie) =
+		(AccountId::from([1; 32]), AccountId::from([2; 32]), AccountId::from([3; 32]));
+	let storage = RuntimeGenesisConfig {
+		balances: pallet_balances::GenesisConfig {
+			balances: vec![(alice.clone(), 1_000 * UNIT), (charlie.clone(), 40 * UNIT)],
+		},
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap();
+
+	sp_io::TestExternalities::new(storage).execute_with(|| {
+		System::set_block_number(1);
+		// Locks 60 units of Charlie, unlocking 6 units per block from block 10 on.
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(alice.clone()),
+			charlie.clone().into(),
+			pallet_vesting::VestingInfo::new(60 * UNIT, 6 * UNIT, 10)
+		));
+		assert_eq!(Vesting::vesting_balance(&charlie), Some(60 * UNIT));
+		assert_noop!(
+			Balances::transfer_keep_alive(
+				RuntimeOrigin::signed(charlie.clone()),
+				alice.clone().into(),
+				50 * UNIT
+			),
+			sp_runtime::TokenError::Frozen
+		);
+
+		let schedule = pallet_vesting::VestingInfo::new(10 * UNIT, UNIT, 5);
+		let vested_transfer = RuntimeCall::Vesting(pallet_vesting::Call::vested_transfer {
+			target: bob.clone().into(),
+			schedule,
+		});
+		let signed = RuntimeOrigin::signed(alice.clone());
+		assert_ok!(Utility::batch_all(
+			signed.clone(),
+			vec![vested_transfer.clone(), vested_transfer.clone()]
+		));
+		assert_eq!(Vesting::vesting_balance(&bob), Some(20 * UNIT));
+
+		// A multisig of Alice and Charlie, funded by Alice.
+		let multisig = Multisig::multi_account_id(&[alice.clone(), charlie.clone()], 1);
+		assert_ok!(Balances::transfer_keep_alive(signed.clone(), multisig.into(), 100 * UNIT));
+		assert_ok!(Multisig::as_multi_threshold_1(
+			RuntimeOrigin::signed(charlie),
+			vec![alice],
+			Box::new(vested_transfer.clone())
+		));
+		assert_eq!(pallet_vesting::Vesting::<Runtime>::get(&bob).unwrap().len(), 3);
+		assert_eq!(Vesting::vesting_balance(&bob), Some(30 * UNIT));
+
+		// Vested transfers are transfers nonetheless.
+		assert!(!ProxyType::NonTransfer.filter(&vested_transfer));
+		assert!(ProxyType::NonTransfer.filter(&RuntimeCall::Vesting(pallet_vesting::Call::vest {})));
+		assert_ok!(TransferFreeze::set_frozen(RuntimeOrigin::root(), true));
+		assert_eq!(
+			vested_transfer.dispatch(signed).map_err(|e| e.error),
+			Err(frame_system::Error::<Runtime>::CallFiltered.into())
+		);
+	});
+}
+
+#[test]
+fn frozen_accounts_cannot_transfer_or_call_contracts() {
+	use frame_support::{dispatch::GetDispatchInfo, instances::Instance1};
+	use pallet_account_freeze::{CheckNotFrozen, Error};
+	use pallet_contracts::{CollectEvents, DebugInfo};
+	use pop_runtime_common::proxy::ProxyType;
+	use sp_runtime::{traits::SignedExtension, transaction_validity::InvalidTransaction};
+
+	const ASSET: u32 = 1;
+	let (alice, bob) = (AccountId::from([1; 32]), AccountId::from([2; 32]));
+	let transfer = RuntimeCall::Balances(BalancesCall::transfer_keep_alive {
+		dest: MultiAddress::Id(bob.clone()),
+		value: UNIT,
+	});
+	let validate = |who: &AccountId, call: &RuntimeCall| {
+		CheckNotFrozen::<Runtime>::new()
+			.validate(who, call, &call.get_dispatch_info(), 0)
+			.map(|_| ())
+	};
+	let transfer_asset = |who: &AccountId| {
+		Assets::transfer(RuntimeOrigin::signed(who.clone()), ASSET.into(), bob.clone().into(), 1)
+	};
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(Balances::force_set_balance(
+			RuntimeOrigin::root(),
+			alice.clone().into(),
+			1_000 * UNIT
+		));
+		let contract = Contracts::bare_instantiate(
+			alice.clone(),
+			0,
+			MAXIMUM_BLOCK_WEIGHT,
+			None,
+			pallet_contracts::Code::Upload(debug_message_contract()),
+			vec![],
+			vec![],
+			DebugInfo::Skip,
+			CollectEvents::Skip,
+		)
+		.result
+		.unwrap()
+		.account_id;
+		let contract_call = RuntimeCall::Contracts(pallet_contracts::Call::call {
+			dest: contract.into(),
+			value: 0,
+			gas_limit: MAXIMUM_BLOCK_WEIGHT / 10,
+			storage_deposit_limit: None,
+			data: vec![],
+		});
+		assert_ok!(Assets::force_create(
+			RuntimeOrigin::root(),
+			ASSET.into(),
+			alice.clone().into(),
+			true,
+			1
+		));
+		assert_ok!(Assets::mint(
+			RuntimeOrigin::signed(alice.clone()),
+			ASSET.into(),
+			alice.clone().into(),
+			10
+		));
+		assert_ok!(Proxy::add_proxy(
+			RuntimeOrigin::signed(alice.clone()),
+			bob.clone().into(),
+			ProxyType::Any,
+			0
+		));
+
+		assert_noop!(
+			AccountFreeze::freeze_account(RuntimeOrigin::signed(bob.clone()), alice.clone()),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(AccountFreeze::freeze_account(RuntimeOrigin::root(), alice.clone()));
+		assert_noop!(
+			AccountFreeze::freeze_account(RuntimeOrigin::root(), alice.clone()),
+			Error::<Runtime>::AlreadyFrozen
+		);
+		for call in [&transfer, &contract_call] {
+			assert_eq!(validate(&alice, call), Err(InvalidTransaction::BadSigner.into()));
+			assert_ok!(validate(&bob, call));
+		}
+		assert_noop!(
+			transfer_asset(&alice),
+			pallet_assets::Error::<Runtime, Instance1>::BalanceLow
+		);
+		// Delegates can no longer act on behalf of the frozen account.
+		assert!(Proxy::proxies(&alice).0.is_empty());
+
+		assert_ok!(AccountFreeze::unfreeze_account(RuntimeOrigin::root(), alice.clone()));
+		assert_noop!(
+			AccountFreeze::unfreeze_account(RuntimeOrigin::root(), alice.clone()),
+			Error::<Runtime>::NotFrozen
+		);
+		for call in [&transfer, &contract_call] {
+			assert_ok!(validate(&alice, call));
+		}
+		assert_ok!(transfer_asset(&alice));
+		assert_eq!(Assets::balance(ASSET, &bob), 1);
+	});
+}
+
+#[test]
+fn frozen_accounts_cannot_transfer_when_dispatched_as_by_others() {
+	let (alice, bob, charlie) =
+		(AccountId::from([1; 32]), AccountId::from([2; 32]), AccountId::from([3; 32]));
+	let transfer = Box::new(RuntimeCall::Balances(BalancesCall::transfer_keep_alive {
+		dest: MultiAddress::Id(charlie.clone()),
+		value: UNIT,
+	}));
+
+	new_test_ext().execute_with(|| {
+		let multisig = Multisig::multi_account_id(&[alice.clone(), bob.clone()], 1);
+		for account in [&alice, &multisig] {
+			assert_ok!(Balances::force_set_balance(
+				RuntimeOrigin::root(),
+				account.clone().into(),
+				1_000 * UNIT
+			));
+		}
+		// A signatory dispatches the transfer as the multisig account.
+		let as_multi = || {
+			Multisig::as_multi_threshold_1(
+				RuntimeOrigin::signed(alice.clone()),
+				vec![bob.clone()],
+				transfer.clone(),
+			)
+		};
+
+		// The signatories are not frozen, but the tokens of the multisig account are.
+		assert_ok!(AccountFreeze::freeze_account(RuntimeOrigin::root(), multisig.clone()));
+		assert!(as_multi().is_err());
+		// Tokens received while frozen are frozen as well.
+		assert_ok!(Balances::transfer_keep_alive(
+			RuntimeOrigin::signed(alice.clone()),
+			multisig.clone().into(),
+			UNIT
+		));
+		assert!(as_multi().is_err());
+		assert_eq!(Balances::free_balance(&charlie), 0);
+
+		assert_ok!(AccountFreeze::unfreeze_account(RuntimeOrigin::root(), multisig.clone()));
+		assert_ok!(as_multi());
+		assert_eq!(Balances::free_balance(&charlie), UNIT);
+	});
+}
@@ -0,0 +1,252 @@
+use super::*;
+
+#[test]
+fn authorize_upgrade_requires_root() {
+	new_test_ext().execute_with(|| {
+		let code_hash = BlakeTwo256::hash(b"code");
+		assert_noop!(
+			System::authorize_upgrade(RuntimeOrigin::signed(AccountId::from([1; 32])), code_hash),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(System::authorize_upgrade(RuntimeOrigin::root(), code_hash));
+		System::assert_last_event(
+			frame_system::Event::UpgradeAuthorized { code_hash, check_version: true }.into(),
+		);
+	});
+}
+
+#[test]
+fn apply_authorized_upgrade_rejects_mismatched_code() {
+	new_test_ext().execute_with(|| {
+		let origin = RuntimeOrigin::signed(AccountId::from([1; 32]));
+		assert_noop!(
+			System::apply_authorized_upgrade(origin.clone(), b"code".to_vec()),
+			frame_system::Error::<Runtime>::NothingAuthorized
+		);
+
+		assert_ok!(System::authorize_upgrade(RuntimeOrigin::root(), BlakeTwo256::hash(b"code")));
+		assert_noop!(
+			System::apply_authorized_upgrade(origin, b"other code".to_vec()),
+			frame_system::Error::<Runtime>::Unauthorized
+		);
+	});
+}
+
+#[test]
+fn scheduled_upgrade_is_applied_at_target_block() {
+	use codec::Encode;
+	use cumulus_primitives_core::{
+		relay_chain::AsyncBackingParams, AbridgedHostConfiguration, PersistedValidationData,
+	};
+	use frame_support::{
+		storage::{storage_prefix, unhashed},
+		traits::Hooks,
+	};
+	use sp_core::traits::{Externalities, ReadRuntimeVersion, ReadRuntimeVersionExt};
+
+	/// Reads any code as the current runtime with a higher spec version.
+	struct NextVersion;
+	impl ReadRuntimeVersion for NextVersion {
+		fn read_runtime_version(
+			&self,
+			_: &[u8],
+			_: &mut dyn Externalities,
+		) -> Result<Vec<u8>, String> {
+			Ok(RuntimeVersion { spec_version: VERSION.spec_version + 1, ..VERSION }.encode())
+		}
+	}
+
+	let mut ext = new_test_ext();
+	ext.register_extension(ReadRuntimeVersionExt::new(NextVersion));
+	ext.execute_with(|| {
+		// The relay chain state required by `parachain_system` to schedule a code upgrade.
+		unhashed::put(
+			&storage_prefix(b"ParachainSystem", b"ValidationData"),
+			&PersistedValidationData::<Hash, BlockNumber>::default(),
+		);
+		unhashed::put(
+			&storage_prefix(b"ParachainSystem", b"HostConfiguration"),
+			&AbridgedHostConfiguration {
+				max_code_size: 3 * 1024 * 1024,
+				max_head_data_size: 32 * 1024,
+				max_upward_queue_count: 8,
+				max_upward_queue_size: 1024 * 1024,
+				max_upward_message_size: 4 * 1024,
+				max_upward_message_num_per_candidate: 2,
+				hrmp_max_message_num_per_candidate: 2,
+				validation_upgrade_cooldown: 2,
+				validation_upgrade_delay: 2,
+				async_backing_params: AsyncBackingParams {
+					max_candidate_depth: 3,
+					allowed_ancestry_len: 2,
+				},
+			},
+		);
+
+		let code = b"code".to_vec();
+		let task = upgrade_task_name(&BlakeTwo256::hash(&code));
+		assert_noop!(
+			EmergencyUpgrade::schedule_upgrade(
+				RuntimeOrigin::signed(AccountId::from([1; 32])),
+				code.clone(),
+				5
+			),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(EmergencyUpgrade::schedule_upgrade(RuntimeOrigin::root(), code.clone(), 5));
+
+		for n in 2..=5 {
+			// Nothing is authorized, so the upgrade cannot be applied before the target block.
+			assert_noop!(
+				System::apply_authorized_upgrade(
+					RuntimeOrigin::signed(AccountId::from([1; 32])),
+					code.clone()
+				),
+				frame_system::Error::<Runtime>::NothingAuthorized
+			);
+			assert!(!unhashed::exists(&storage_prefix(
+				b"ParachainSystem",
+				b"PendingValidationCode"
+			)));
+
+			System::set_block_number(n);
+			Scheduler::on_initialize(n);
+		}
+		System::assert_has_event(
+			pallet_scheduler::Event::Dispatched { task: (5, 0), id: Some(task), result: Ok(()) }
+				.into(),
+		);
+		assert_eq!(
+			unhashed::get::<Vec<u8>>(&storage_prefix(b"ParachainSystem", b"PendingValidationCode")),
+			Some(code)
+		);
+		assert!(System::authorized_upgrade().is_none());
+	});
+}
+
+#[test]
+fn upgrade_status_reports_authorized_hash() {
+	use cumulus_primitives_core::relay_chain::UpgradeGoAhead;
+	use frame_support::storage::{storage_prefix, unhashed};
+	use pop_runtime_common::apis::{
+		runtime_decl_for_upgrade_status_api::UpgradeStatusApiV1, UpgradeStatus,
+	};
+
+	new_test_ext().execute_with(|| {
+		assert_eq!(Runtime::upgrade_status(), UpgradeStatus::default());
+
+		let code_hash = BlakeTwo256::hash(b"code");
+		assert_ok!(System::authorize_upgrade(RuntimeOrigin::root(), code_hash));
+		assert_eq!(Runtime::upgrade_status().authorized_hash, Some(code_hash));
+
+		unhashed::put(
+			&storage_prefix(b"ParachainSystem", b"UpgradeGoAhead"),
+			&Some(UpgradeGoAhead::GoAhead),
+		);
+		assert_eq!(Runtime::upgrade_status().go_ahead_signal, Some(true));
+	});
+}
+
+#[test]
+fn technical_committee_supermajority_authorizes_upgrade() {
+	use codec::Encode;
+	use frame_support::dispatch::GetDispatchInfo;
+
+	let members: Vec<AccountId> = (1..=4).map(|i| AccountId::from([i; 32])).collect();
+	let code = b"code".to_vec();
+	let code_hash = BlakeTwo256::hash(&code);
+	let call: RuntimeCall = pallet_emergency_upgrade::Call::authorize_upgrade { code_hash }.into();
+	let (call_hash, call_len, call_weight) =
+		(BlakeTwo256::hash_of(&call), call.encoded_size() as u32, call.get_dispatch_info().weight);
+	// Propose the call as motion `index` with the given member approval threshold and have the
+	// first `ayes` members approve it, after which the motion is closed. Proposing a motion
+	// does not vote for it.
+	let motion = |index: u32, threshold: u32, ayes: usize| {
+		assert_ok!(TechnicalCommittee::propose(
+			RuntimeOrigin::signed(members[0].clone()),
+			threshold,
+			Box::new(call.clone()),
+			call_len
+		));
+		for member in &members[..ayes] {
+			assert_ok!(TechnicalCommittee::vote(
+				RuntimeOrigin::signed(member.clone()),
+				call_hash,
+				index,
+				true
+			));
+		}
+		if ayes < threshold as usize {
+			System::set_block_number(
+				System::block_number() + config::governance::TechnicalMotionDuration::get(),
+			);
+		}
+		assert_ok!(TechnicalCommittee::close(
+			RuntimeOrigin::signed(members[0].clone()),
+			call_hash,
+			index,
+			call_weight,
+			call_len
+		));
+	};
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(TechnicalCommittee::set_members(
+			RuntimeOrigin::root(),
+			members.clone(),
+			None,
+			0
+		));
+		assert_noop!(
+			EmergencyUpgrade::authorize_upgrade(
+				RuntimeOrigin::signed(members[0].clone()),
+				code_hash
+			),
+			DispatchError::BadOrigin
+		);
+
+		let nothing_authorized = || {
+			assert_noop!(
+				System::apply_authorized_upgrade(
+					RuntimeOrigin::signed(members[0].clone()),
+					code.clone()
+				),
+				frame_system::Error::<Runtime>::NothingAuthorized
+			);
+		};
+		// A simple majority is not enough.
+		motion(0, 2, 2);
+		nothing_authorized();
+		// A motion failing to reach its threshold is not executed.
+		motion(1, 3, 2);
+		nothing_authorized();
+		// A supermajority authorizes the upgrade.
+		motion(2, 3, 3);
+		System::assert_has_event(
+			frame_system::Event::UpgradeAuthorized { code_hash, check_version: true }.into(),
+		);
+	});
+}
+
+#[test]
+fn council_majority_authorizes_upgrade() {
+	use config::governance::CouncilInstance;
+
+	let code_hash = BlakeTwo256::hash(b"code");
+	let council = |ayes, members| {
+		RuntimeOrigin::from(pallet_collective::RawOrigin::<AccountId, CouncilInstance>::Members(
+			ayes, members,
+		))
+	};
+
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			EmergencyUpgrade::authorize_upgrade(council(1, 2), code_hash),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(EmergencyUpgrade::authorize_upgrade(council(2, 3), code_hash));
+		System::assert_has_event(
+			frame_system::Event::UpgradeAuthorized { code_hash, check_version: true }.into(),
+		);
+	});
+}
@@ -0,0 +1,29 @@
+#!/usr/bin/env bash
+# Generates the weights of the pallets in `pallets/` by running their benchmarks on the testnet
+# runtime, which includes all of them. Run from the root of the repository on reference hardware:
+#
+#   cargo build --release --features runtime-benchmarks
+#   ./scripts/benchmark-pallets.sh [pallet-name ...]
+#
+# Without arguments, the weights of every pallet with benchmarks are generated.
+
+set -euo pipefail
+
+NODE=${NODE:-./target/release/pop-node}
+CHAIN=${CHAIN:-test}
+
+if [ $# -eq 0 ]; then
+	set -- $(ls pallets/*/src/benchmarking.rs | cut -d/ -f2)
+fi
+
+for name in "$@"; do
+	"$NODE" benchmark pallet \
+		--chain "$CHAIN" \
+		--pallet "pallet_${name//-/_}" \
+		--extrinsic '*' \
+		--steps 50 \
+		--repeat 20 \
+		--wasm-execution compiled \
+		--template scripts/pallet-weights-template.hbs \
+		--output "pallets/$name/src/weights.rs"
+done